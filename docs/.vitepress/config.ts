@@ -96,7 +96,17 @@ export default defineConfig({
                     { text: 'config', link: '/zh/guide/commands/config' },
                     { text: 'alias', link: '/zh/guide/commands/alias' },
                     { text: 'stats', link: '/zh/guide/commands/stats' },
+                    { text: 'tag', link: '/zh/guide/commands/tag' },
+                    { text: 'reword', link: '/zh/guide/commands/reword' },
+                    { text: 'undo', link: '/zh/guide/commands/undo' },
                     { text: 'hook', link: '/zh/guide/commands/hook' },
+                    { text: 'provider', link: '/zh/guide/commands/provider' },
+                    { text: 'completions', link: '/zh/guide/commands/completions' },
+                    { text: 'prompt', link: '/zh/guide/commands/prompt' },
+                    { text: 'usage', link: '/zh/guide/commands/usage' },
+                    { text: 'audit', link: '/zh/guide/commands/audit' },
+                    { text: 'api', link: '/zh/guide/commands/api' },
+                    { text: 'self', link: '/zh/guide/commands/self' },
                     { text: '自动化与环境', link: '/zh/guide/commands/automation' },
                   ],
                 },
@@ -235,7 +245,17 @@ export default defineConfig({
                 { text: 'config', link: '/guide/commands/config' },
                 { text: 'alias', link: '/guide/commands/alias' },
                 { text: 'stats', link: '/guide/commands/stats' },
+                { text: 'tag', link: '/guide/commands/tag' },
+                { text: 'reword', link: '/guide/commands/reword' },
+                { text: 'undo', link: '/guide/commands/undo' },
                 { text: 'hook', link: '/guide/commands/hook' },
+                { text: 'provider', link: '/guide/commands/provider' },
+                { text: 'completions', link: '/guide/commands/completions' },
+                { text: 'prompt', link: '/guide/commands/prompt' },
+                { text: 'usage', link: '/guide/commands/usage' },
+                { text: 'audit', link: '/guide/commands/audit' },
+                { text: 'api', link: '/guide/commands/api' },
+                { text: 'self', link: '/guide/commands/self' },
                 { text: 'Automation & Env Vars', link: '/guide/commands/automation' },
               ],
             },
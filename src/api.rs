@@ -0,0 +1,527 @@
+//! High-level library API.
+//!
+//! [`Gcop`] wraps the commit, review and stats pipelines of the CLI for
+//! programs that embed gcop-rs. It returns the same structs the commands
+//! work with and never writes to stdout or stderr: prompts, spinners,
+//! warnings and confirmations all belong to the command layer.
+//!
+//! ```no_run
+//! use gcop_rs::api::{CommitSource, Gcop, ReviewTarget};
+//!
+//! # async fn example() -> gcop_rs::error::Result<()> {
+//! let gcop = Gcop::load()?.with_repo("/path/to/repo");
+//!
+//! let commit = gcop.generate_commit_message(CommitSource::Staged).await?;
+//! println!("{}", commit.message);
+//!
+//! let review = gcop.review(ReviewTarget::Range(Some("main..feature".into()))).await?;
+//! println!("{} issue(s)", review.issues.len());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Secret handling (`[privacy]`), diff truncation and message
+//! post-processing (conventions, tickets, trailers) behave as in the CLI.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::commands::options::StatsOptions;
+use crate::commands::stats::RepoStats;
+use crate::commands::{
+    commit as commit_cmd, privacy, review_baseline, smart_truncate_diff, stats, trailers,
+};
+use crate::config::{self, AppConfig, CommandLlmConfig};
+use crate::error::{GcopError, Result};
+use crate::git::repository::GitRepository;
+use crate::git::{DiffStats, GitOperations};
+use crate::llm::provider::base::response::process_commit_response;
+use crate::llm::provider::create_provider;
+use crate::llm::{CommitContext, LLMProvider, ReviewResult, ReviewType};
+use crate::ui::{self, SilentReporter};
+
+/// Changes a commit message is generated for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitSource {
+    /// Staged changes (`git commit`).
+    Staged,
+    /// `HEAD` plus any staged changes (`git commit --amend`).
+    Amend,
+    /// An existing commit, by revision (message rewording).
+    Commit(String),
+    /// A unified diff supplied by the caller.
+    Diff(String),
+}
+
+/// What to review.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewTarget {
+    /// Uncommitted working tree changes.
+    Changes,
+    /// A single commit, by revision.
+    Commit(String),
+    /// A commit range; a bare ref or `None` is compared with the default
+    /// branch, as with `review range`.
+    Range(Option<String>),
+    /// The full content of a file.
+    File(String),
+    /// A unified diff supplied by the caller.
+    Diff(String),
+}
+
+/// Per-call options of [`Gcop::generate_commit_message_with`].
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// Feedback for the model, as with `commit <feedback...>`.
+    pub feedback: Vec<String>,
+    /// Provider override (key under `[llm.providers]`).
+    pub provider: Option<String>,
+    /// Add a `Signed-off-by` trailer, as with `--signoff`.
+    pub signoff: bool,
+    /// Extra `Co-authored-by` entries (`Name <email>` or `email`).
+    pub co_authors: Vec<String>,
+}
+
+/// A generated commit message.
+#[derive(Debug, Clone)]
+pub struct CommitMessage {
+    /// Final message, post-processed like `gcop-rs commit` does.
+    pub message: String,
+    /// Statistics of the diff the message describes.
+    pub diff_stats: DiffStats,
+}
+
+/// Entry point of the library API.
+///
+/// Cheap to construct; the repository is opened and the provider created
+/// on every call, so configuration changes take effect immediately.
+///
+/// Calls run with the status output of the [`ui`](crate::ui) module (for
+/// example fallback warnings) silenced for their own task only; the
+/// process-wide quiet setting of a host CLI is left alone.
+pub struct Gcop {
+    config: AppConfig,
+    warnings: Vec<String>,
+    repo_dir: Option<PathBuf>,
+    provider: Option<Arc<dyn LLMProvider>>,
+}
+
+impl Gcop {
+    /// Creates an instance for `config`, working on the repository of the
+    /// current directory.
+    pub fn new(config: AppConfig) -> Self {
+        Self {
+            config,
+            warnings: Vec::new(),
+            repo_dir: None,
+            provider: None,
+        }
+    }
+
+    /// Creates an instance from the user and project configuration files,
+    /// as the CLI does. Loading warnings are kept in [`Gcop::warnings`].
+    pub fn load() -> Result<Self> {
+        let (config, warnings) = config::load_config_with_warnings(None)?;
+        Ok(Self {
+            warnings,
+            ..Self::new(config)
+        })
+    }
+
+    /// Works on the repository containing `dir` instead of the current directory.
+    pub fn with_repo(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.repo_dir = Some(dir.into());
+        self
+    }
+
+    /// Uses `provider` for every request instead of `[llm]` providers.
+    pub fn with_provider(mut self, provider: Arc<dyn LLMProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// The configuration in use.
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    /// Warnings raised while loading the configuration (`api_key` in
+    /// project config, stale remote preset); empty for [`Gcop::new`].
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Opens the repository this instance works on.
+    pub fn open_repo(&self) -> Result<GitRepository> {
+        match &self.repo_dir {
+            Some(dir) => GitRepository::open_at(dir, Some(&self.config.file)),
+            None => GitRepository::open(Some(&self.config.file)),
+        }
+    }
+
+    /// Generates a commit message for `source` with default options.
+    pub async fn generate_commit_message(&self, source: CommitSource) -> Result<CommitMessage> {
+        self.generate_commit_message_with(source, &CommitOptions::default())
+            .await
+    }
+
+    /// Generates a commit message for `source`.
+    ///
+    /// Fails with [`GcopError::NoStagedChanges`] when `source` is
+    /// [`CommitSource::Staged`] and nothing is staged.
+    pub async fn generate_commit_message_with(
+        &self,
+        source: CommitSource,
+        options: &CommitOptions,
+    ) -> Result<CommitMessage> {
+        ui::quiet_scope(true, async {
+            let repo = self.open_repo()?;
+            let prompt = CommitPrompt::prepare(&repo, &self.config, &source, options)?;
+            let provider = self.provider(&self.config.commit.llm, options.provider.as_deref())?;
+            let raw = crate::llm::provider::tiered::diff_size_scope(
+                prompt.diff.len(),
+                provider.send_prompt(&prompt.system, &prompt.user, None),
+            )
+            .await?;
+            Ok(CommitMessage {
+                message: prompt.finish(raw, &self.config),
+                diff_stats: prompt.stats,
+            })
+        })
+        .await
+    }
+
+    /// Reviews `target`.
+    ///
+    /// Issues accepted in the repository's review baseline
+    /// (`.gcop/review-baseline.json`) are removed from the result.
+    pub async fn review(&self, target: ReviewTarget) -> Result<ReviewResult> {
+        ui::quiet_scope(true, async {
+            let repo = self.open_repo()?;
+            let (diff, review_type) = review_input(&repo, &self.config, target)?;
+            let provider = self.provider(&self.config.review.llm, None)?;
+            let mut result = provider
+                .review_code(
+                    &diff,
+                    review_type,
+                    self.config.review.custom_prompt.as_deref(),
+                    None,
+                )
+                .await?;
+            apply_review_baseline(&repo, &mut result)?;
+            Ok(result)
+        })
+        .await
+    }
+
+    /// Computes repository statistics.
+    ///
+    /// `options.format` is ignored; the statistics are returned instead of
+    /// rendered. Without `options.repos`, the instance's repository is analyzed.
+    pub fn stats(&self, options: &StatsOptions<'_>) -> Result<RepoStats> {
+        ui::quiet_scope_sync(true, || {
+            let repos = match options.repos {
+                Some(_) => stats::open_repositories(options, &self.config)?,
+                None => vec![(String::new(), self.open_repo()?)],
+            };
+            Ok(
+                stats::compute(options, &self.config, &repos, &SilentReporter)?
+                    .unwrap_or_else(|| RepoStats::from_commits(&[], None)),
+            )
+        })
+    }
+
+    fn provider(
+        &self,
+        command: &CommandLlmConfig,
+        name: Option<&str>,
+    ) -> Result<Arc<dyn LLMProvider>> {
+        match &self.provider {
+            Some(provider) => Ok(provider.clone()),
+            None => create_provider(&self.config, command, name, None),
+        }
+    }
+}
+
+/// Prompt and context of one commit message, before the provider is called.
+pub(crate) struct CommitPrompt {
+    /// System prompt.
+    pub(crate) system: String,
+    /// User prompt.
+    pub(crate) user: String,
+    /// Diff as sent (after `[privacy]` and truncation).
+    pub(crate) diff: String,
+    context: CommitContext,
+    /// Statistics of the full diff.
+    pub(crate) stats: DiffStats,
+    trailers: Vec<String>,
+}
+
+impl CommitPrompt {
+    /// Reads the diff of `source` and builds the prompt, like `gcop-rs commit`.
+    pub(crate) fn prepare(
+        repo: &dyn GitOperations,
+        config: &AppConfig,
+        source: &CommitSource,
+        options: &CommitOptions,
+    ) -> Result<Self> {
+        let diff = match source {
+            CommitSource::Staged => {
+                if !repo.has_staged_changes()? {
+                    return Err(GcopError::NoStagedChanges);
+                }
+                commit_cmd::get_diff(repo, false)?
+            }
+            CommitSource::Amend => {
+                if repo.is_empty()? {
+                    return Err(GcopError::InvalidInput(
+                        rust_i18n::t!("commit.amend_no_commits").to_string(),
+                    ));
+                }
+                commit_cmd::get_diff(repo, true)?
+            }
+            CommitSource::Commit(rev) => repo.get_commit_diff(rev)?,
+            CommitSource::Diff(diff) => {
+                if diff.trim().is_empty() {
+                    return Err(GcopError::InvalidInput(
+                        rust_i18n::t!("api.empty_diff").to_string(),
+                    ));
+                }
+                diff.clone()
+            }
+        };
+
+        let trailers = trailers::resolve_trailers(
+            repo,
+            &config.commit.trailers,
+            options.signoff,
+            &options.co_authors,
+        )?;
+        let stats = repo.get_diff_stats(&diff)?;
+        let diff = privacy::guard(diff, config, true, false)?;
+        let (diff, _truncated) = smart_truncate_diff(&diff, config.llm.max_diff_size);
+        let branch_name = repo.get_current_branch()?;
+        let scope_info = commit_cmd::compute_scope_info_pub(repo, &stats.files_changed, config);
+        let history_examples = commit_cmd::compute_history_examples(repo, config);
+        let context = commit_cmd::build_commit_context(
+            &stats,
+            config,
+            &options.feedback,
+            &branch_name,
+            &config.commit.custom_prompt,
+            &scope_info,
+            &history_examples,
+        );
+        let (system, user) = crate::llm::prompt::build_commit_prompt_split(
+            &diff,
+            &context,
+            context.custom_prompt.as_deref(),
+            context.convention.as_ref(),
+        );
+        Ok(Self {
+            system,
+            user,
+            diff,
+            context,
+            stats,
+            trailers,
+        })
+    }
+
+    /// Turns the raw model output into the final message.
+    pub(crate) fn finish(&self, raw: String, config: &AppConfig) -> String {
+        let message = process_commit_response(raw);
+        commit_cmd::finish_message(message, &self.diff, &self.context, config, &self.trailers)
+    }
+}
+
+/// Reads the diff to review for `target`, after `[privacy]` and truncation.
+pub(crate) fn review_input(
+    git: &dyn GitOperations,
+    config: &AppConfig,
+    target: ReviewTarget,
+) -> Result<(String, ReviewType)> {
+    let (diff, review_type) = match target {
+        ReviewTarget::Changes => {
+            let diff = git.get_uncommitted_diff()?;
+            if diff.trim().is_empty() {
+                return Err(GcopError::InvalidInput(
+                    rust_i18n::t!("review.no_uncommitted_changes_to_review").to_string(),
+                ));
+            }
+            (diff, ReviewType::UncommittedChanges)
+        }
+        ReviewTarget::Commit(hash) => (git.get_commit_diff(&hash)?, ReviewType::SingleCommit(hash)),
+        ReviewTarget::Range(range) => {
+            let range = crate::git::expand_range(git, range.as_deref())?;
+            (git.get_range_diff(&range)?, ReviewType::CommitRange(range))
+        }
+        ReviewTarget::File(path) => {
+            let content = git.get_file_content(&path)?;
+            (
+                format!("--- {}\n+++ {}\n{}", path, path, content),
+                ReviewType::FileOrDir {
+                    path,
+                    context: None,
+                },
+            )
+        }
+        ReviewTarget::Diff(diff) => {
+            if diff.trim().is_empty() {
+                return Err(GcopError::InvalidInput(
+                    rust_i18n::t!("api.empty_diff").to_string(),
+                ));
+            }
+            (diff, ReviewType::UncommittedChanges)
+        }
+    };
+    let diff = privacy::guard(diff, config, true, false)?;
+    let (diff, _truncated) = smart_truncate_diff(&diff, config.llm.max_diff_size);
+    Ok((diff, review_type))
+}
+
+/// Drops issues accepted in the review baseline; returns how many were dropped.
+pub(crate) fn apply_review_baseline(
+    git: &dyn GitOperations,
+    result: &mut ReviewResult,
+) -> Result<usize> {
+    let path = review_baseline::baseline_path(&git.get_workdir()?);
+    Ok(match review_baseline::ReviewBaseline::load(&path)? {
+        Some(baseline) => baseline.filter(result),
+        None => 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::MockGitOperations;
+    use crate::llm::{ProgressReporter, StreamHandle};
+    use async_trait::async_trait;
+
+    /// 记录收到的 prompt 并返回固定回复的 provider
+    struct EchoProvider;
+
+    #[async_trait]
+    impl LLMProvider for EchoProvider {
+        async fn send_prompt(
+            &self,
+            _system: &str,
+            user: &str,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<String> {
+            assert!(user.contains("fn library()"));
+            Ok("```\nfeat: add library entry point\n```".to_string())
+        }
+
+        async fn send_prompt_streaming(&self, _system: &str, _user: &str) -> Result<StreamHandle> {
+            unreachable!("the library API does not stream")
+        }
+
+        async fn review_code(
+            &self,
+            diff: &str,
+            _review_type: ReviewType,
+            _custom_prompt: Option<&str>,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<ReviewResult> {
+            Ok(ReviewResult {
+                summary: format!("{} bytes", diff.len()),
+                issues: vec![],
+                suggestions: vec![],
+            })
+        }
+
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        async fn validate(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    const DIFF: &str =
+        "diff --git a/lib.rs b/lib.rs\n--- a/lib.rs\n+++ b/lib.rs\n+fn library() {}\n";
+
+    fn repo() -> MockGitOperations {
+        let mut git = MockGitOperations::new();
+        git.expect_has_staged_changes().returning(|| Ok(true));
+        git.expect_get_staged_diff()
+            .returning(|| Ok(DIFF.to_string()));
+        git.expect_get_diff_stats()
+            .returning(crate::git::diff::parse_diff_stats);
+        git.expect_get_current_branch()
+            .returning(|| Ok(Some("main".to_string())));
+        git.expect_get_commit_history().returning(|_| Ok(vec![]));
+        git.expect_get_workdir()
+            .returning(|| Ok(std::env::temp_dir()));
+        git
+    }
+
+    #[tokio::test]
+    async fn test_commit_prompt_round_trip() {
+        let config = AppConfig::default();
+        let prompt = CommitPrompt::prepare(
+            &repo(),
+            &config,
+            &CommitSource::Staged,
+            &CommitOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(prompt.stats.files_changed, vec!["lib.rs"]);
+
+        let raw = EchoProvider
+            .send_prompt(&prompt.system, &prompt.user, None)
+            .await
+            .unwrap();
+        // 与 CLI 相同的后处理：去掉代码块围栏
+        assert_eq!(prompt.finish(raw, &config), "feat: add library entry point");
+    }
+
+    #[test]
+    fn test_staged_source_requires_staged_changes() {
+        let mut git = MockGitOperations::new();
+        git.expect_has_staged_changes().returning(|| Ok(false));
+        let result = CommitPrompt::prepare(
+            &git,
+            &AppConfig::default(),
+            &CommitSource::Staged,
+            &CommitOptions::default(),
+        );
+        assert!(matches!(result, Err(GcopError::NoStagedChanges)));
+    }
+
+    #[test]
+    fn test_review_input_from_supplied_diff() {
+        let (diff, review_type) = review_input(
+            &repo(),
+            &AppConfig::default(),
+            ReviewTarget::Diff(DIFF.to_string()),
+        )
+        .unwrap();
+        assert!(diff.contains("fn library()"));
+        assert!(matches!(review_type, ReviewType::UncommittedChanges));
+
+        assert!(matches!(
+            review_input(
+                &repo(),
+                &AppConfig::default(),
+                ReviewTarget::Diff(" \n".to_string())
+            ),
+            Err(GcopError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_gcop_uses_injected_provider() {
+        let gcop = Gcop::new(AppConfig::default()).with_provider(Arc::new(EchoProvider));
+        let provider = gcop
+            .provider(&gcop.config().review.llm, Some("ignored"))
+            .unwrap();
+        assert_eq!(provider.name(), "echo");
+        assert!(gcop.warnings().is_empty());
+        // 不修改进程级 quiet 状态
+        assert!(!ui::is_quiet());
+    }
+}
@@ -1,4 +1,4 @@
-use clap::{Args, Parser, Subcommand, builder::styling};
+use clap::{Args, Parser, Subcommand, ValueEnum, builder::styling};
 
 const STYLES: styling::Styles = styling::Styles::styled()
     .header(styling::AnsiColor::Green.on_default().bold())
@@ -20,9 +20,25 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
-    /// Override the default LLM provider (used by `commit` and `review`).
+    /// Suppress all non-error output (progress, status messages, previews).
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Override the default LLM provider (used by `commit`, `review` and `provider ollama`).
     #[arg(short, long, global = true)]
     pub provider: Option<String>,
+
+    /// Override the selected provider's model for this run (checked against `allowed_models`).
+    #[arg(long, global = true)]
+    pub model: Option<String>,
+
+    /// Named config profile to apply (`[profiles.<name>]`, also `GCOP_PROFILE`).
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Run as if started in `<PATH>` (like `git -C`).
+    #[arg(short = 'C', long = "repo", value_name = "PATH", global = true)]
+    pub repo: Option<std::path::PathBuf>,
 }
 
 /// Arguments for the `commit` subcommand.
@@ -40,7 +56,7 @@ pub struct CommitArgs {
     #[arg(short, long)]
     pub dry_run: bool,
 
-    /// Output format: `text` or `json` (`json` implies `--dry-run`).
+    /// Output format: `text`, `json` or `json-stream` (`json` implies `--dry-run`).
     #[arg(short, long, default_value = "text")]
     pub format: String,
 
@@ -48,6 +64,10 @@ pub struct CommitArgs {
     #[arg(long)]
     pub json: bool,
 
+    /// Shortcut for `--format json-stream`: newline-delimited progress and delta events, then the result.
+    #[arg(long, conflicts_with = "json")]
+    pub json_stream: bool,
+
     /// Split staged changes into multiple atomic commits.
     #[arg(short = 's', long)]
     pub split: bool,
@@ -56,11 +76,230 @@ pub struct CommitArgs {
     #[arg(long)]
     pub amend: bool,
 
+    /// Create a `fixup!` commit for an earlier commit (for `git rebase --autosquash`).
+    #[arg(long, value_name = "COMMIT", conflicts_with_all = ["squash", "amend", "split"])]
+    pub fixup: Option<String>,
+
+    /// Create a `squash!` commit for an earlier commit, with a generated message body.
+    #[arg(long, value_name = "COMMIT", conflicts_with_all = ["amend", "split"])]
+    pub squash: Option<String>,
+
+    /// Stage modified and deleted tracked files first, like `git commit -a`.
+    #[arg(short = 'a', long)]
+    pub all: bool,
+
+    /// Do not sign the commit, even if `commit.gpgsign` or `commit.sign` is set.
+    #[arg(long)]
+    pub no_sign: bool,
+
+    /// Add a `Signed-off-by` trailer (DCO) to the message.
+    #[arg(long)]
+    pub signoff: bool,
+
+    /// Add a `Co-authored-by` trailer (`Name <email>` or an email from history; repeatable).
+    #[arg(long = "co-author", value_name = "EMAIL")]
+    pub co_authors: Vec<String>,
+
+    /// Generate the message from the diff without calling an LLM.
+    #[arg(long)]
+    pub offline: bool,
+
+    /// Print the diff as it would be sent after `[privacy]` redaction, without sending it.
+    #[arg(long)]
+    pub show_redacted: bool,
+
+    /// Write the generated message to `<PATH>` (`-` for stdout only) instead of committing.
+    #[arg(
+        short = 'o',
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["split", "json", "json_stream", "fixup", "squash"]
+    )]
+    pub output: Option<std::path::PathBuf>,
+
+    /// Existing message file to refine against the staged changes (alias `--refine`).
+    #[arg(
+        long,
+        visible_alias = "refine",
+        value_name = "PATH",
+        conflicts_with_all = ["split", "fixup", "squash", "offline"]
+    )]
+    pub message_file: Option<std::path::PathBuf>,
+
+    /// Split mode: run `<CMD>` on each group's staged snapshot before committing it.
+    #[arg(long, value_name = "CMD", conflicts_with_all = ["fixup", "squash", "amend", "offline"])]
+    pub verify: Option<String>,
+
+    /// Review the staged changes for critical issues before generating the message.
+    #[arg(long, conflicts_with_all = ["split", "fixup", "squash", "offline", "json", "json_stream"])]
+    pub review: bool,
+
     /// Feedback or constraints passed to commit message generation.
     #[arg(trailing_var_arg = true)]
     pub feedback: Vec<String>,
 }
 
+/// Arguments for the `review` subcommand.
+#[derive(Args, Debug)]
+pub struct ReviewArgs {
+    /// Review target.
+    #[command(subcommand)]
+    pub target: ReviewTarget,
+
+    /// Output format: `text`, `json`, `json-stream`, `markdown`, `annotated`, `github`, or `junit`.
+    #[arg(short, long, default_value = "text")]
+    pub format: String,
+
+    /// Shortcut for `--format json`.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Shortcut for `--format json-stream`: newline-delimited progress and delta events, then the result.
+    #[arg(long, conflicts_with = "json")]
+    pub json_stream: bool,
+
+    /// Record current findings in `.gcop/review-baseline.json`.
+    #[arg(long)]
+    pub update_baseline: bool,
+
+    /// Exit non-zero when an issue at or above this severity is found.
+    #[arg(long, value_name = "SEVERITY", value_parser = ["critical", "warning", "info"])]
+    pub fail_on: Option<String>,
+
+    /// Focused review: specialized prompt and fixed issue categories.
+    #[arg(long, value_name = "PRESET", value_parser = ["security", "performance", "style"])]
+    pub preset: Option<String>,
+
+    /// Preview and apply suggested patches to the working tree (`changes` only).
+    #[arg(long, global = true)]
+    pub apply: bool,
+
+    /// Print the input as it would be sent after `[privacy]` redaction, without sending it.
+    #[arg(long, global = true)]
+    pub show_redacted: bool,
+
+    /// `changes` only: review just what changed since the last incremental review.
+    #[arg(long, global = true)]
+    pub incremental: bool,
+
+    /// Review everything even when `review.incremental` is enabled.
+    #[arg(long, global = true, conflicts_with = "incremental")]
+    pub full: bool,
+
+    /// `commit` / `range` only: ignore cached results and review again.
+    #[arg(long, global = true)]
+    pub no_cache: bool,
+}
+
+/// Arguments for the `stats` subcommand.
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    /// Output format: `text`, `json`, `json-stream`, `markdown`, `csv`, or `html`.
+    #[arg(short, long, default_value = "text")]
+    pub format: String,
+
+    /// Shortcut for `--format json`.
+    #[arg(long)]
+    pub json: bool,
+
+    /// Shortcut for `--format json-stream`: newline-delimited progress events, then the result.
+    #[arg(long, conflicts_with = "json")]
+    pub json_stream: bool,
+
+    /// Filter by author name or email.
+    #[arg(long)]
+    pub author: Option<String>,
+
+    /// Show per-author line-level contribution statistics.
+    #[arg(long)]
+    pub contrib: bool,
+
+    /// Show file extension, language and hot-file statistics (reads every commit diff).
+    #[arg(long)]
+    pub detailed: bool,
+
+    /// Show a full-year commit calendar (52-week grid) instead of the 30-day heat map.
+    #[arg(long)]
+    pub calendar: bool,
+
+    /// Only include commits since this date (`YYYY-MM-DD`, RFC 3339, or `14d`/`2w`/`3m`/`1y`).
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Only include commits until this date (same formats as `--since`).
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Revision or range to analyze instead of `HEAD` (e.g. `v1.0..v2.0`).
+    #[arg(long)]
+    pub range: Option<String>,
+
+    /// Only include commits touching this file or directory.
+    #[arg(long)]
+    pub path: Option<String>,
+
+    /// Aggregate several repositories into one report (comma-separated paths; `[stats] repos` when empty).
+    #[arg(
+        long,
+        value_name = "PATHS",
+        value_delimiter = ',',
+        num_args = 0..,
+        conflicts_with_all = ["range", "path"]
+    )]
+    pub repos: Option<Vec<String>>,
+
+    /// Count an author as another one: `"Old Name=New Name"`, or emails / `Name <email>` (repeatable).
+    #[arg(long, value_name = "OLD=NEW")]
+    pub merge_authors: Vec<String>,
+}
+
+/// Arguments for the `tag` subcommand.
+#[derive(Args, Debug)]
+pub struct TagArgs {
+    /// Name of the tag to create (for example `v1.2.0`).
+    pub name: String,
+
+    /// Previous tag to summarize from (default: the latest tag reachable from `HEAD`).
+    #[arg(long, value_name = "TAG")]
+    pub from: Option<String>,
+
+    /// Generate and print the tag message without creating the tag.
+    #[arg(short, long)]
+    pub dry_run: bool,
+
+    /// Skip confirmation before creating the tag.
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Push the tag after creating it.
+    #[arg(long)]
+    pub push: bool,
+
+    /// Remote used by `--push`.
+    #[arg(long, default_value = "origin", requires = "push")]
+    pub remote: String,
+}
+
+/// Arguments for the `reword` subcommand.
+#[derive(Args, Debug)]
+pub struct RewordArgs {
+    /// Commit whose message to improve (default: `HEAD`).
+    #[arg(value_name = "COMMIT", default_value = "HEAD")]
+    pub commit: String,
+
+    /// Generate and print the new message without rewriting the commit.
+    #[arg(short, long)]
+    pub dry_run: bool,
+
+    /// Skip confirmation before rewriting the commit.
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Feedback or constraints passed to message generation (after `--`).
+    #[arg(last = true)]
+    pub feedback: Vec<String>,
+}
+
 #[derive(Subcommand)]
 /// Supported gcop-rs subcommands.
 pub enum Commands {
@@ -68,19 +307,7 @@ pub enum Commands {
     Commit(CommitArgs),
 
     /// Review code changes.
-    Review {
-        /// Review target.
-        #[command(subcommand)]
-        target: ReviewTarget,
-
-        /// Output format: `text`, `json`, or `markdown`.
-        #[arg(short, long, default_value = "text")]
-        format: String,
-
-        /// Shortcut for `--format json`.
-        #[arg(long)]
-        json: bool,
-    },
+    Review(ReviewArgs),
 
     /// Initialize a configuration file.
     Init {
@@ -91,6 +318,14 @@ pub enum Commands {
         /// Initialize `.gcop/config.toml` at the current repository root.
         #[arg(long)]
         project: bool,
+
+        /// Write the commented example config instead of running the setup wizard.
+        #[arg(long, conflicts_with = "project")]
+        template: bool,
+
+        /// Migrate a Python gcop `config.yaml` (auto-detected when PATH is omitted).
+        #[arg(long, value_name = "PATH", num_args = 0..=1, conflicts_with = "project")]
+        from_gcop: Option<Option<std::path::PathBuf>>,
     },
 
     /// Manage configuration.
@@ -116,22 +351,29 @@ pub enum Commands {
     },
 
     /// Show repository statistics.
-    Stats {
-        /// Output format: `text`, `json`, or `markdown`.
-        #[arg(short, long, default_value = "text")]
-        format: String,
+    Stats(StatsArgs),
 
-        /// Shortcut for `--format json`.
-        #[arg(long)]
-        json: bool,
+    /// Create an annotated tag with an AI-generated release message.
+    Tag(TagArgs),
 
-        /// Filter by author name or email.
-        #[arg(long)]
-        author: Option<String>,
+    /// Improve the message of an existing commit and rewrite it.
+    Reword(RewordArgs),
 
-        /// Show per-author line-level contribution statistics.
-        #[arg(long)]
-        contrib: bool,
+    /// Mark a commit for rewording in a rebase todo list (called by Git during `reword`, not intended for direct use).
+    #[command(name = "reword-todo", hide = true)]
+    RewordTodo {
+        /// Full hash of the commit to reword.
+        hash: String,
+
+        /// Path to the rebase todo list (provided by Git).
+        todo_file: String,
+    },
+
+    /// Undo the last commit created by gcop-rs, keeping its changes staged.
+    Undo {
+        /// Skip confirmation before undoing the commit.
+        #[arg(short = 'y', long)]
+        yes: bool,
     },
 
     /// Manage git hooks (prepare-commit-msg)
@@ -140,6 +382,72 @@ pub enum Commands {
         #[command(subcommand)]
         action: HookAction,
     },
+
+    /// Provider diagnostics.
+    Provider {
+        /// Provider action to run.
+        #[command(subcommand)]
+        action: ProviderAction,
+    },
+
+    /// Serve a newline-delimited JSON API on stdin/stdout (for editor plugins).
+    Api,
+
+    /// Print the shell completion script.
+    Completions {
+        /// Target shell.
+        shell: clap_complete::Shell,
+    },
+
+    /// Inspect prompt templates.
+    Prompt {
+        /// Prompt action to run.
+        #[command(subcommand)]
+        action: PromptAction,
+    },
+
+    /// Show recorded LLM token usage and estimated cost.
+    Usage {
+        /// Aggregation period.
+        #[arg(long, value_enum, default_value_t = UsagePeriod::Daily)]
+        period: UsagePeriod,
+
+        /// Only include records since this date (`YYYY-MM-DD`, RFC 3339, or `14d`/`2w`/`3m`/`1y`).
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Output format: `text` or `json`.
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Shortcut for `--format json`.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Inspect the audit log of requests sent to providers.
+    Audit {
+        /// Audit action to run.
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+
+    /// Manage the gcop-rs installation.
+    #[command(name = "self")]
+    SelfCmd {
+        /// Self action to run.
+        #[command(subcommand)]
+        action: SelfAction,
+    },
+}
+
+/// Aggregation period of the `usage` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum UsagePeriod {
+    /// One row per day.
+    Daily,
+    /// One row per calendar month.
+    Monthly,
 }
 
 #[derive(Subcommand, Debug)]
@@ -156,14 +464,30 @@ pub enum ReviewTarget {
 
     /// Review a range of commits.
     Range {
-        /// Commit range (for example `main..feature`).
-        range: String,
+        /// Commit range (for example `main..feature` or `main...feature`);
+        /// a bare ref is compared with the default branch, and no range
+        /// reviews `HEAD` against it.
+        range: Option<String>,
     },
 
-    /// Review a specific file.
+    /// Review a file or directory.
     File {
-        /// Path to file.
+        /// Path to a file or directory.
         path: String,
+
+        /// Include related files (module entry, tests, siblings) as context.
+        #[arg(long)]
+        with_context: bool,
+    },
+
+    /// Review a GitLab merge request.
+    Mr {
+        /// Merge request IID (the number shown as `!<iid>`).
+        iid: u64,
+
+        /// Post the summary and issues as merge request discussions.
+        #[arg(long)]
+        post: bool,
     },
 }
 
@@ -175,6 +499,89 @@ pub enum ConfigAction {
 
     /// Validate merged config and test provider-chain connectivity.
     Validate,
+
+    /// Print the JSON Schema of the config file.
+    Schema,
+
+    /// Print the effective value of a dotted key (for example `ui.language`).
+    Get {
+        /// Dotted key path.
+        key: String,
+    },
+
+    /// Set a dotted key in the config file, preserving comments.
+    Set {
+        /// Dotted key path (for example `llm.default_provider`).
+        key: String,
+
+        /// Value (TOML literal such as `true`, `42`, `["a"]`; otherwise a string).
+        value: String,
+
+        /// Write to `.gcop/config.toml` at the repository root instead of the user config.
+        #[arg(long)]
+        project: bool,
+    },
+
+    /// Remove a dotted key from the config file.
+    Unset {
+        /// Dotted key path.
+        key: String,
+
+        /// Edit `.gcop/config.toml` at the repository root instead of the user config.
+        #[arg(long)]
+        project: bool,
+    },
+
+    /// List recognized environment variables, their values and what they map to.
+    Env {
+        /// Print JSON instead of text.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check config, API keys, repository, hook and editor, and suggest fixes.
+    Doctor {
+        /// Also send a test request to the default provider.
+        #[arg(long)]
+        online: bool,
+    },
+}
+
+#[derive(Subcommand)]
+/// Actions for the `provider` command.
+pub enum ProviderAction {
+    /// Send a tiny prompt to every configured provider and report latency/success.
+    Test {
+        /// Output format: `text`, `json`, or `markdown`.
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Shortcut for `--format json`.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage models of an Ollama provider.
+    Ollama {
+        /// Ollama action to run.
+        #[command(subcommand)]
+        action: OllamaAction,
+    },
+}
+
+#[derive(Subcommand)]
+/// Actions for the `provider ollama` command.
+pub enum OllamaAction {
+    /// List the models installed on the Ollama server.
+    ListModels {
+        /// Output format: `text` or `json`.
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Shortcut for `--format json`.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -205,3 +612,75 @@ pub enum HookAction {
         sha: String,
     },
 }
+
+#[derive(Subcommand, Debug)]
+/// Actions for the `self` command.
+pub enum SelfAction {
+    /// Update gcop-rs to the latest release.
+    Update {
+        /// Only check whether an update is available.
+        #[arg(long)]
+        check: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+/// Actions for the `audit` command.
+pub enum AuditAction {
+    /// Show the most recent audit records.
+    Show {
+        /// Only include records since this date (`YYYY-MM-DD`, RFC 3339, or `14d`/`2w`/`3m`/`1y`).
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Maximum number of records to show (newest last).
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output format: `text` or `json`.
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Shortcut for `--format json`.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export audit records as JSON Lines or CSV.
+    Export {
+        /// Only include records since this date (`YYYY-MM-DD`, RFC 3339, or `14d`/`2w`/`3m`/`1y`).
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Export format.
+        #[arg(short, long, value_enum, default_value_t = AuditExportFormat::Jsonl)]
+        format: AuditExportFormat,
+
+        /// Write to this file instead of stdout.
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Export format of `audit export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AuditExportFormat {
+    /// One JSON object per line.
+    Jsonl,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+#[derive(Subcommand)]
+/// Actions for the `prompt` command.
+pub enum PromptAction {
+    /// Render the effective template of a prompt with sample data.
+    Show {
+        /// Prompt to show.
+        template: crate::llm::template::PromptTemplate,
+
+        /// Print the raw template source instead of rendering it.
+        #[arg(long)]
+        source: bool,
+    },
+}
@@ -2,6 +2,7 @@ use colored::Colorize;
 
 use crate::error::{GcopError, Result};
 use crate::ui;
+use std::collections::BTreeMap;
 use std::process::Command;
 use which::which;
 
@@ -27,22 +28,143 @@ const GCOP_ALIASES: &[(&str, &str, &str)] = &[
     ("undo", "!git reset --soft HEAD^", "alias.desc.undo"),
 ];
 
+/// Git config section recording the command gcop-rs last wrote for each alias.
+///
+/// Comparing it with the live `alias.<name>` value tells a definition that
+/// changed since install (outdated) from a manual edit (drifted).
+const RECORD_SECTION: &str = "gcop.alias";
+
+/// Where an alias definition comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AliasOrigin {
+    /// Built into gcop-rs (`GCOP_ALIASES`).
+    Builtin,
+    /// Declared in the `[aliases]` config section.
+    User,
+}
+
+/// An alias managed by gcop-rs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AliasDef {
+    name: String,
+    command: String,
+    description: String,
+    origin: AliasOrigin,
+}
+
+/// State of an alias in the global git config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AliasStatus {
+    /// No alias with this name.
+    NotInstalled,
+    /// Matches the definition.
+    Installed,
+    /// Still the value gcop-rs wrote, but the definition has changed since.
+    Outdated,
+    /// Written by gcop-rs, then edited manually.
+    Drifted(String),
+    /// An alias gcop-rs did not write.
+    Conflicts(String),
+}
+
+/// Built-in aliases followed by user aliases.
+///
+/// A user alias may not reuse a built-in name: `git c` must always run
+/// `gcop-rs commit`.
+fn alias_defs(user: &BTreeMap<String, String>) -> Result<Vec<AliasDef>> {
+    if let Some((name, _, _)) = GCOP_ALIASES
+        .iter()
+        .find(|(name, _, _)| user.contains_key(*name))
+    {
+        return Err(GcopError::Config(
+            rust_i18n::t!("alias.shadows_builtin", name = *name).to_string(),
+        ));
+    }
+    let builtin = GCOP_ALIASES
+        .iter()
+        .map(|(name, command, description)| AliasDef {
+            name: name.to_string(),
+            command: command.to_string(),
+            description: rust_i18n::t!(*description).to_string(),
+            origin: AliasOrigin::Builtin,
+        });
+    let custom = user.iter().map(|(name, value)| {
+        let command = user_alias_command(value);
+        AliasDef {
+            name: name.clone(),
+            description: command.trim_start_matches('!').to_string(),
+            command,
+            origin: AliasOrigin::User,
+        }
+    });
+    Ok(builtin.chain(custom).collect())
+}
+
+/// Git alias value for a `[aliases]` entry.
+///
+/// Values are gcop-rs arguments (`commit --yes`); a value starting with `!`
+/// is used verbatim as a shell alias, like in git.
+fn user_alias_command(value: &str) -> String {
+    let value = value.trim();
+    if value.starts_with('!') {
+        value.to_string()
+    } else {
+        format!("!gcop-rs {}", value)
+    }
+}
+
+/// Classifies the live git alias against its definition and install record.
+fn alias_status(expected: &str, current: Option<&str>, recorded: Option<&str>) -> AliasStatus {
+    match current {
+        None => AliasStatus::NotInstalled,
+        Some(current) if current == expected => AliasStatus::Installed,
+        Some(current) if recorded == Some(current) => AliasStatus::Outdated,
+        Some(current) if recorded.is_some() => AliasStatus::Drifted(current.to_string()),
+        Some(current) => AliasStatus::Conflicts(current.to_string()),
+    }
+}
+
+/// Looks up the live alias and its install record.
+fn current_status(def: &AliasDef) -> Result<AliasStatus> {
+    let current = get_git_alias(&def.name)?;
+    let recorded = get_git_config(&record_key(&def.name))?;
+    Ok(alias_status(
+        &def.command,
+        current.as_deref(),
+        recorded.as_deref(),
+    ))
+}
+
 /// Managing git aliases
-pub fn run(force: bool, list: bool, remove: bool, colored: bool) -> Result<()> {
+///
+/// `aliases` are the user definitions from the `[aliases]` config section.
+pub fn run(
+    force: bool,
+    list: bool,
+    remove: bool,
+    aliases: &BTreeMap<String, String>,
+    colored: bool,
+) -> Result<()> {
+    let defs = alias_defs(aliases)?;
+
     if list {
-        return list_aliases(colored);
+        return list_aliases(&defs, colored);
     }
 
     if remove {
-        return remove_aliases(force, colored);
+        return remove_aliases(&defs, force, colored);
     }
 
     // Default: Install all aliases in batches
-    install_all(force, colored)
+    install_defs(&defs, force, colored)
 }
 
 /// Install all git aliases in batches (public, for init calls)
-pub fn install_all(force: bool, colored: bool) -> Result<()> {
+pub fn install_all(force: bool, aliases: &BTreeMap<String, String>, colored: bool) -> Result<()> {
+    install_defs(&alias_defs(aliases)?, force, colored)
+}
+
+fn install_defs(defs: &[AliasDef], force: bool, colored: bool) -> Result<()> {
     // 1. Detect gcop-rs command
     if !is_gcop_in_path() {
         ui::error(&rust_i18n::t!("alias.not_found"), colored);
@@ -66,12 +188,12 @@ pub fn install_all(force: bool, colored: bool) -> Result<()> {
     let mut failed: Vec<String> = Vec::new();
 
     // 2. Install alias one by one
-    for (name, command, description) in GCOP_ALIASES {
-        match install_single_alias(name, command, description, force, colored) {
+    for def in defs {
+        match install_single_alias(def, force, colored) {
             Ok(true) => installed += 1,
             Ok(false) => skipped += 1,
             Err(e) => {
-                failed.push(format!("{}: {}", name, e));
+                failed.push(format!("{}: {}", def.name, e));
             }
         }
     }
@@ -112,24 +234,21 @@ pub fn install_all(force: bool, colored: bool) -> Result<()> {
     println!("{}", rust_i18n::t!("alias.use_gconfig"));
     println!("{}", rust_i18n::t!("alias.use_p"));
     println!("{}", rust_i18n::t!("alias.use_undo"));
+    for def in defs.iter().filter(|d| d.origin == AliasOrigin::User) {
+        println!("  git {:8} # {}", def.name, def.description);
+    }
 
     Ok(())
 }
 
 /// Install a single alias
-fn install_single_alias(
-    name: &str,
-    command: &str,
-    description: &str,
-    force: bool,
-    colored: bool,
-) -> Result<bool> {
-    let description = rust_i18n::t!(description).to_string();
-    let existing = get_git_alias(name)?;
+fn install_single_alias(def: &AliasDef, force: bool, colored: bool) -> Result<bool> {
+    let name = def.name.as_str();
+    let description = &def.description;
 
-    match existing {
-        None => {
-            add_git_alias(name, command)?;
+    match current_status(def)? {
+        AliasStatus::NotInstalled => {
+            add_git_alias(name, &def.command)?;
             if colored {
                 println!(
                     "  {}  git {:10} → {}",
@@ -142,7 +261,7 @@ fn install_single_alias(
             }
             Ok(true)
         }
-        Some(existing_cmd) if existing_cmd == command => {
+        AliasStatus::Installed => {
             if colored {
                 println!(
                     "  {}  git {:10} → {} {}",
@@ -161,44 +280,86 @@ fn install_single_alias(
             }
             Ok(false)
         }
-        Some(existing_cmd) => {
-            if force {
-                add_git_alias(name, command)?;
-                if colored {
-                    println!(
-                        "  {}  git {:10} → {} {}",
-                        "⚠".yellow().bold(),
-                        name.bold(),
-                        description,
-                        rust_i18n::t!("alias.overwritten").yellow()
-                    );
-                } else {
-                    println!(
-                        "  ⚠  git {:10} → {} {}",
-                        name,
-                        description,
-                        rust_i18n::t!("alias.overwritten")
-                    );
-                }
-                Ok(true)
+        // gcop-rs owns the current value, so a new release may update a built-in
+        // alias; a changed user definition still needs --force to be applied.
+        AliasStatus::Outdated if force || def.origin == AliasOrigin::Builtin => {
+            add_git_alias(name, &def.command)?;
+            if colored {
+                println!(
+                    "  {}  git {:10} → {} {}",
+                    "✓".green().bold(),
+                    name.bold(),
+                    description,
+                    rust_i18n::t!("alias.updated").dimmed()
+                );
             } else {
-                if colored {
-                    println!(
-                        "  {}  git {:10} - {}",
-                        "⊗".red().bold(),
-                        name.bold(),
-                        rust_i18n::t!("alias.conflicts", cmd = existing_cmd).dimmed()
-                    );
-                } else {
-                    println!(
-                        "  ⊗  git {:10} - {}",
-                        name,
-                        rust_i18n::t!("alias.conflicts", cmd = existing_cmd)
-                    );
-                }
-                Ok(false)
+                println!(
+                    "  ✓  git {:10} → {} {}",
+                    name,
+                    description,
+                    rust_i18n::t!("alias.updated")
+                );
             }
+            Ok(true)
         }
+        AliasStatus::Drifted(_) | AliasStatus::Conflicts(_) if force => {
+            add_git_alias(name, &def.command)?;
+            if colored {
+                println!(
+                    "  {}  git {:10} → {} {}",
+                    "⚠".yellow().bold(),
+                    name.bold(),
+                    description,
+                    rust_i18n::t!("alias.overwritten").yellow()
+                );
+            } else {
+                println!(
+                    "  ⚠  git {:10} → {} {}",
+                    name,
+                    description,
+                    rust_i18n::t!("alias.overwritten")
+                );
+            }
+            Ok(true)
+        }
+        AliasStatus::Outdated => {
+            print_skipped(
+                name,
+                &rust_i18n::t!("alias.outdated", cmd = def.command),
+                colored,
+            );
+            Ok(false)
+        }
+        AliasStatus::Drifted(existing_cmd) => {
+            print_skipped(
+                name,
+                &rust_i18n::t!("alias.drifted", cmd = existing_cmd),
+                colored,
+            );
+            Ok(false)
+        }
+        AliasStatus::Conflicts(existing_cmd) => {
+            print_skipped(
+                name,
+                &rust_i18n::t!("alias.conflicts", cmd = existing_cmd),
+                colored,
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Print an alias left untouched because it needs `--force`
+fn print_skipped(name: &str, reason: &str, colored: bool) {
+    if colored {
+        println!(
+            "  {}  git {:10} - {}",
+            "⊗".red().bold(),
+            name.bold(),
+            reason.dimmed()
+        );
+    } else {
+        println!("  ⊗  git {:10} - {}", name, reason);
     }
 }
 
@@ -214,26 +375,48 @@ fn add_git_alias(name: &str, command: &str) -> Result<()> {
         ));
     }
 
+    // Remember what was written, for drift detection. Best-effort: without a
+    // record the alias is reported as a conflict instead.
+    let recorded = Command::new("git")
+        .args(["config", "--global", &record_key(name), command])
+        .status();
+    if !recorded.is_ok_and(|status| status.success()) {
+        tracing::debug!("Failed to record alias '{}'", name);
+    }
+
     Ok(())
 }
 
 /// List all available aliases and their status
-fn list_aliases(colored: bool) -> Result<()> {
+fn list_aliases(defs: &[AliasDef], colored: bool) -> Result<()> {
     println!("{}", ui::info(&rust_i18n::t!("alias.available"), colored));
     println!();
 
-    for (name, command, description) in GCOP_ALIASES {
-        let description = rust_i18n::t!(*description).to_string();
-        let existing = get_git_alias(name)?;
-        let status = match existing {
-            Some(existing_cmd) if existing_cmd == *command => {
+    for def in defs {
+        let status = match current_status(def)? {
+            AliasStatus::Installed => {
                 if colored {
                     rust_i18n::t!("alias.status_installed").green().to_string()
                 } else {
                     rust_i18n::t!("alias.status_installed").to_string()
                 }
             }
-            Some(existing_cmd) => {
+            AliasStatus::Outdated => {
+                if colored {
+                    rust_i18n::t!("alias.status_outdated").cyan().to_string()
+                } else {
+                    rust_i18n::t!("alias.status_outdated").to_string()
+                }
+            }
+            AliasStatus::Drifted(existing_cmd) => {
+                let msg = rust_i18n::t!("alias.status_drifted", cmd = existing_cmd).to_string();
+                if colored {
+                    msg.yellow().to_string()
+                } else {
+                    msg
+                }
+            }
+            AliasStatus::Conflicts(existing_cmd) => {
                 let msg = rust_i18n::t!("alias.status_conflicts", cmd = existing_cmd).to_string();
                 if colored {
                     msg.yellow().to_string()
@@ -241,7 +424,7 @@ fn list_aliases(colored: bool) -> Result<()> {
                     msg
                 }
             }
-            None => {
+            AliasStatus::NotInstalled => {
                 if colored {
                     rust_i18n::t!("alias.status_not_installed")
                         .dimmed()
@@ -251,11 +434,25 @@ fn list_aliases(colored: bool) -> Result<()> {
                 }
             }
         };
+        let origin = match def.origin {
+            AliasOrigin::Builtin => rust_i18n::t!("alias.origin_builtin"),
+            AliasOrigin::User => rust_i18n::t!("alias.origin_user"),
+        };
+        let origin = format!("{:9}", origin);
 
         if colored {
-            println!("  git {:10} → {:45} [{}]", name.bold(), description, status);
+            println!(
+                "  git {:10} → {:45} {} [{}]",
+                def.name.bold(),
+                def.description,
+                origin.dimmed(),
+                status
+            );
         } else {
-            println!("  git {:10} → {:45} [{}]", name, description, status);
+            println!(
+                "  git {:10} → {:45} {} [{}]",
+                def.name, def.description, origin, status
+            );
         }
     }
 
@@ -267,12 +464,12 @@ fn list_aliases(colored: bool) -> Result<()> {
 }
 
 /// Remove all gcop-related aliases
-fn remove_aliases(force: bool, colored: bool) -> Result<()> {
+fn remove_aliases(defs: &[AliasDef], force: bool, colored: bool) -> Result<()> {
     if !force {
         ui::warning(&rust_i18n::t!("alias.remove_warning"), colored);
         println!();
         println!("{}", ui::info(&rust_i18n::t!("alias.to_remove"), colored));
-        for (name, _, _) in GCOP_ALIASES {
+        for AliasDef { name, .. } in defs {
             if get_git_alias(name)?.is_some() {
                 if colored {
                     println!("  - git {}", name.bold());
@@ -295,7 +492,12 @@ fn remove_aliases(force: bool, colored: bool) -> Result<()> {
 
     let mut removed = 0;
 
-    for (name, _, _) in GCOP_ALIASES {
+    for AliasDef { name, .. } in defs {
+        // Drop the install record even when the alias itself is already gone.
+        let _ = Command::new("git")
+            .args(["config", "--global", "--unset", &record_key(name)])
+            .output();
+
         if get_git_alias(name)?.is_some() {
             let status = Command::new("git")
                 .args(["config", "--global", "--unset", &format!("alias.{}", name)])
@@ -336,8 +538,18 @@ fn is_gcop_in_path() -> bool {
 
 /// Get the value of git alias
 fn get_git_alias(name: &str) -> Result<Option<String>> {
+    get_git_config(&format!("alias.{}", name))
+}
+
+/// Git config key of the install record for `name`.
+fn record_key(name: &str) -> String {
+    format!("{}.{}", RECORD_SECTION, name)
+}
+
+/// Get a value from the global git config
+fn get_git_config(key: &str) -> Result<Option<String>> {
     let output = Command::new("git")
-        .args(["config", "--global", &format!("alias.{}", name)])
+        .args(["config", "--global", key])
         .output()?;
 
     if output.status.success() {
@@ -347,3 +559,66 @@ fn get_git_alias(name: &str) -> Result<Option<String>> {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_user_alias_command() {
+        assert_eq!(user_alias_command("commit --yes"), "!gcop-rs commit --yes");
+        // 以 ! 开头的值原样作为 shell alias
+        assert_eq!(user_alias_command("!git log -1"), "!git log -1");
+    }
+
+    #[test]
+    fn test_alias_defs_appends_user_aliases() {
+        let mut user = BTreeMap::new();
+        user.insert("gcm".to_string(), "commit --no-edit".to_string());
+        let defs = alias_defs(&user).unwrap();
+
+        assert_eq!(defs.len(), GCOP_ALIASES.len() + 1);
+        let gcm = defs.last().unwrap();
+        assert_eq!(gcm.name, "gcm");
+        assert_eq!(gcm.description, "gcop-rs commit --no-edit");
+        assert_eq!(defs[0].origin, AliasOrigin::Builtin);
+    }
+
+    #[test]
+    fn test_alias_defs_rejects_builtin_names() {
+        // 内置别名（如 `git c`）不能被替换成别的命令
+        let mut user = BTreeMap::new();
+        user.insert("c".to_string(), "!git push --force".to_string());
+        let err = alias_defs(&user).unwrap_err();
+        assert!(matches!(err, GcopError::Config(_)));
+    }
+
+    #[test]
+    fn test_alias_status() {
+        let expected = "!gcop-rs commit --yes";
+        assert_eq!(
+            alias_status(expected, None, None),
+            AliasStatus::NotInstalled
+        );
+        assert_eq!(
+            alias_status(expected, Some(expected), None),
+            AliasStatus::Installed
+        );
+        // 定义已变更，但 git 中仍是 gcop-rs 写入的旧值
+        assert_eq!(
+            alias_status(expected, Some("!gcop-rs commit"), Some("!gcop-rs commit")),
+            AliasStatus::Outdated
+        );
+        // gcop-rs 写入后被手动修改
+        assert_eq!(
+            alias_status(expected, Some("!my-commit"), Some(expected)),
+            AliasStatus::Drifted("!my-commit".to_string())
+        );
+        // 非 gcop-rs 写入的 alias
+        assert_eq!(
+            alias_status(expected, Some("!my-commit"), None),
+            AliasStatus::Conflicts("!my-commit".to_string())
+        );
+    }
+}
@@ -0,0 +1,594 @@
+//! Machine interface for editor plugins (`gcop-rs api`).
+//!
+//! A long-running process that reads newline-delimited JSON requests on
+//! stdin and writes newline-delimited JSON events on stdout, so plugins do
+//! not need to spawn `gcop-rs` for every action. Requests are handled one at
+//! a time, in order.
+//!
+//! ```text
+//! → {"id": 1, "cmd": "commit.generate", "params": {"feedback": ["use Chinese"]}}
+//! ← {"id": 1, "event": "progress", "stage": "generating"}
+//! ← {"id": 1, "event": "delta", "text": "feat: "}
+//! ← {"id": 1, "event": "result", "data": {"message": "feat: ...", "diff_stats": {...}}}
+//! ```
+//!
+//! Every request ends with exactly one `result` or `error` event. `id` is
+//! echoed back verbatim (any JSON value, `null` when omitted). Diagnostics
+//! and logs go to stderr; stdout only ever carries events.
+
+use std::io::Write;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+use super::commit::DiffStatsJson;
+use super::json::ErrorJson;
+use crate::api::{
+    CommitOptions, CommitPrompt, CommitSource, ReviewTarget, apply_review_baseline, review_input,
+};
+use crate::config::{AppConfig, CommandLlmConfig};
+use crate::error::{GcopError, Result};
+use crate::git::commit::{CommitSigning, CommitStdin};
+use crate::git::{GitOperations, repository::GitRepository};
+use crate::llm::provider::create_provider;
+use crate::llm::{LLMProvider, StreamChunk};
+
+/// Protocol version reported by `ping`; bumped on incompatible changes.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Opens the repository for a request.
+type RepoFactory<'a> = Box<dyn Fn() -> Result<Box<dyn GitOperations>> + 'a>;
+
+/// Creates the provider for a request (`None` = configured default).
+type ProviderFactory<'a> =
+    Box<dyn Fn(&CommandLlmConfig, Option<&str>) -> Result<Arc<dyn LLMProvider>> + 'a>;
+
+/// One line of input.
+#[derive(Debug, Deserialize)]
+struct Request {
+    /// Opaque request identifier, echoed in every event.
+    #[serde(default)]
+    id: Value,
+    /// Command name (`commit.generate`, `review`, ...).
+    cmd: String,
+    /// Command parameters.
+    #[serde(default)]
+    params: Value,
+}
+
+/// One line of output.
+#[derive(Debug, Serialize)]
+struct Envelope<'a> {
+    id: &'a Value,
+    #[serde(flatten)]
+    event: Event<'a>,
+}
+
+/// Events emitted for a request.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Event<'a> {
+    /// The request moved to a new stage.
+    Progress { stage: &'a str },
+    /// A chunk of streamed model output.
+    Delta { text: &'a str },
+    /// The request succeeded (final event).
+    Result { data: Value },
+    /// The request failed (final event).
+    Error { error: ErrorJson },
+}
+
+/// Parameters of `commit.generate`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CommitGenerateParams {
+    /// Feedback for the model, as with `commit <feedback...>`.
+    feedback: Vec<String>,
+    /// Provider override.
+    provider: Option<String>,
+    /// Generate a replacement message for `HEAD`.
+    amend: bool,
+    /// Stream `delta` events (defaults to `ui.streaming`).
+    stream: Option<bool>,
+    /// Add a `Signed-off-by` trailer, as with `--signoff`.
+    signoff: bool,
+    /// Extra `Co-authored-by` entries, as with `--co-author`.
+    co_authors: Vec<String>,
+}
+
+/// Parameters of `commit.create`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CommitCreateParams {
+    /// Commit message.
+    message: String,
+    /// Amend `HEAD` instead of creating a new commit.
+    #[serde(default)]
+    amend: bool,
+}
+
+/// Parameters of `review`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ReviewParams {
+    /// `changes` (default), `commit`, `range` or `file`.
+    target: Option<String>,
+    /// Commit hash, range or path for the non-default targets.
+    value: Option<String>,
+    /// Provider override.
+    provider: Option<String>,
+}
+
+/// Serves requests against injected git and provider factories.
+pub(crate) struct ApiServer<'a> {
+    config: &'a AppConfig,
+    open_repo: RepoFactory<'a>,
+    create_provider: ProviderFactory<'a>,
+}
+
+/// Runs the API on stdin/stdout until `shutdown` or end of input.
+pub async fn run(config: &AppConfig) -> Result<()> {
+    let server = ApiServer {
+        config,
+        open_repo: Box::new(|| {
+            let signing = CommitSigning::resolve(config.commit.sign, false);
+            // Requests arrive on stdin; hooks and signers must not consume them
+            let repo = GitRepository::open(Some(&config.file))?
+                .with_signing(signing)
+                .with_commit_stdin(CommitStdin::Null);
+            Ok(Box::new(repo) as _)
+        }),
+        create_provider: Box::new(|command, name| create_provider(config, command, name, None)),
+    };
+    let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+    server.serve(stdin, std::io::stdout().lock()).await
+}
+
+impl<'a> ApiServer<'a> {
+    /// Handles requests from `input` until `shutdown` or end of input.
+    ///
+    /// Only I/O errors on the streams end the loop; request failures are
+    /// reported as `error` events.
+    pub async fn serve<R, W>(&self, input: R, output: W) -> Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: Write,
+    {
+        let mut events = EventWriter { out: output };
+        let mut lines = input.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let request: Request = match serde_json::from_str(&line) {
+                Ok(request) => request,
+                Err(e) => {
+                    let err = GcopError::InvalidInput(
+                        rust_i18n::t!("api.invalid_request", error = e.to_string()).to_string(),
+                    );
+                    events.error(&Value::Null, &err)?;
+                    continue;
+                }
+            };
+
+            if request.cmd == "shutdown" {
+                events.emit(&request.id, Event::Result { data: Value::Null })?;
+                break;
+            }
+            match self.handle(&request, &mut events).await {
+                Ok(data) => events.emit(&request.id, Event::Result { data })?,
+                Err(e) => events.error(&request.id, &e)?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle<W: Write>(
+        &self,
+        request: &Request,
+        events: &mut EventWriter<W>,
+    ) -> Result<Value> {
+        let id = &request.id;
+        match request.cmd.as_str() {
+            "ping" => Ok(json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "protocol": PROTOCOL_VERSION,
+            })),
+            "providers.list" => Ok(self.providers_list()),
+            "commit.generate" => {
+                let params = parse_params(&request.cmd, &request.params)?;
+                self.commit_generate(id, params, events).await
+            }
+            "commit.create" => {
+                let params = parse_params(&request.cmd, &request.params)?;
+                self.commit_create(params)
+            }
+            "review" => {
+                let params = parse_params(&request.cmd, &request.params)?;
+                self.review(id, params, events).await
+            }
+            cmd => Err(GcopError::InvalidInput(
+                rust_i18n::t!("api.unknown_command", cmd = cmd).to_string(),
+            )),
+        }
+    }
+
+    fn providers_list(&self) -> Value {
+        let mut names: Vec<&String> = self.config.llm.providers.keys().collect();
+        names.sort();
+        let providers: Vec<Value> = names
+            .into_iter()
+            .map(|name| json!({ "name": name, "model": self.config.llm.providers[name].model }))
+            .collect();
+        json!({
+            "default": self.config.llm.default_provider,
+            "fallback": self.config.llm.fallback_providers,
+            "providers": providers,
+        })
+    }
+
+    async fn commit_generate<W: Write>(
+        &self,
+        id: &Value,
+        params: CommitGenerateParams,
+        events: &mut EventWriter<W>,
+    ) -> Result<Value> {
+        let config = self.config;
+        let repo = (self.open_repo)()?;
+        let source = if params.amend {
+            CommitSource::Amend
+        } else {
+            CommitSource::Staged
+        };
+        let options = CommitOptions {
+            feedback: params.feedback,
+            provider: params.provider,
+            signoff: params.signoff,
+            co_authors: params.co_authors,
+        };
+
+        let prompt = CommitPrompt::prepare(repo.as_ref(), config, &source, &options)?;
+        events.emit(id, Event::Progress { stage: "analyzing" })?;
+        let provider = (self.create_provider)(&config.commit.llm, options.provider.as_deref())?;
+        events.emit(
+            id,
+            Event::Progress {
+                stage: "generating",
+            },
+        )?;
+        let stream = params.stream.unwrap_or(config.ui.streaming) && provider.supports_streaming();
+        let message = if stream {
+            let mut handle = crate::llm::provider::tiered::diff_size_scope(
+                prompt.diff.len(),
+                provider.send_prompt_streaming(&prompt.system, &prompt.user),
+            )
+            .await?;
+            let mut message = String::new();
+            while let Some(chunk) = handle.receiver.recv().await {
+                match chunk {
+                    StreamChunk::Delta(text) => {
+                        events.emit(id, Event::Delta { text: &text })?;
+                        message.push_str(&text);
+                    }
+                    StreamChunk::Retry => {
+                        message.clear();
+                        events.emit(id, Event::Progress { stage: "retrying" })?;
+                    }
+                    StreamChunk::Error(e) => return Err(GcopError::Llm(e)),
+                    StreamChunk::Done => break,
+                }
+            }
+            message
+        } else {
+            crate::llm::provider::tiered::diff_size_scope(
+                prompt.diff.len(),
+                provider.send_prompt(&prompt.system, &prompt.user, None),
+            )
+            .await?
+        };
+
+        Ok(json!({
+            "message": prompt.finish(message, config),
+            "diff_stats": DiffStatsJson::from(&prompt.stats),
+        }))
+    }
+
+    fn commit_create(&self, params: CommitCreateParams) -> Result<Value> {
+        if params.message.trim().is_empty() {
+            return Err(GcopError::InvalidInput(
+                rust_i18n::t!("api.missing_param", cmd = "commit.create", name = "message")
+                    .to_string(),
+            ));
+        }
+        let repo = (self.open_repo)()?;
+        if params.amend {
+            repo.commit_amend(&params.message)?;
+        } else {
+            repo.commit(&params.message)?;
+        }
+        Ok(json!({ "committed": true }))
+    }
+
+    async fn review<W: Write>(
+        &self,
+        id: &Value,
+        params: ReviewParams,
+        events: &mut EventWriter<W>,
+    ) -> Result<Value> {
+        let config = self.config;
+        let git = (self.open_repo)()?;
+        let value = || {
+            params.value.clone().ok_or_else(|| {
+                GcopError::InvalidInput(
+                    rust_i18n::t!("api.missing_param", cmd = "review", name = "value").to_string(),
+                )
+            })
+        };
+
+        events.emit(id, Event::Progress { stage: "analyzing" })?;
+        let target = match params.target.as_deref().unwrap_or("changes") {
+            "changes" => ReviewTarget::Changes,
+            "commit" => ReviewTarget::Commit(value()?),
+            "range" => ReviewTarget::Range(params.value.clone()),
+            "file" => ReviewTarget::File(value()?),
+            target => {
+                return Err(GcopError::InvalidInput(
+                    rust_i18n::t!("api.unknown_target", target = target).to_string(),
+                ));
+            }
+        };
+        let (diff, review_type) = review_input(git.as_ref(), config, target)?;
+
+        let provider = (self.create_provider)(&config.review.llm, params.provider.as_deref())?;
+        events.emit(id, Event::Progress { stage: "reviewing" })?;
+        let mut result = provider
+            .review_code(
+                &diff,
+                review_type,
+                config.review.custom_prompt.as_deref(),
+                None,
+            )
+            .await?;
+
+        let suppressed = apply_review_baseline(git.as_ref(), &mut result)?;
+        let mut data = serde_json::to_value(&result)?;
+        data["suppressed"] = json!(suppressed);
+        Ok(data)
+    }
+}
+
+/// Deserializes command parameters (`null` means "all defaults").
+fn parse_params<T: DeserializeOwned>(cmd: &str, params: &Value) -> Result<T> {
+    let params = if params.is_null() {
+        Value::Object(Default::default())
+    } else {
+        params.clone()
+    };
+    serde_json::from_value(params).map_err(|e| {
+        GcopError::InvalidInput(
+            rust_i18n::t!("api.invalid_params", cmd = cmd, error = e.to_string()).to_string(),
+        )
+    })
+}
+
+/// Writes one JSON event per line, flushing after each.
+struct EventWriter<W: Write> {
+    out: W,
+}
+
+impl<W: Write> EventWriter<W> {
+    fn emit(&mut self, id: &Value, event: Event<'_>) -> Result<()> {
+        serde_json::to_writer(&mut self.out, &Envelope { id, event })?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn error(&mut self, id: &Value, err: &GcopError) -> Result<()> {
+        self.emit(
+            id,
+            Event::Error {
+                error: ErrorJson::from_error(err),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{DiffStats, MockGitOperations};
+    use crate::llm::ReviewResult;
+    use crate::llm::provider::test_utils::ScriptedProvider;
+    use pretty_assertions::assert_eq;
+
+    fn staged_repo() -> MockGitOperations {
+        let mut git = MockGitOperations::new();
+        git.expect_has_staged_changes().returning(|| Ok(true));
+        git.expect_get_staged_diff()
+            .returning(|| Ok("diff --git a/a.rs b/a.rs\n+fn a() {}\n".to_string()));
+        git.expect_get_diff_stats().returning(|_| {
+            Ok(DiffStats {
+                files_changed: vec!["a.rs".to_string()],
+                insertions: 1,
+                deletions: 0,
+            })
+        });
+        git.expect_get_current_branch().returning(|| Ok(None));
+        git.expect_get_commit_history().returning(|_| Ok(vec![]));
+        git.expect_get_user_identity()
+            .returning(|| Ok(Some("Dev <dev@example.com>".to_string())));
+        git
+    }
+
+    async fn serve(
+        input: &str,
+        config: &AppConfig,
+        repo: impl Fn() -> MockGitOperations,
+        streaming: bool,
+    ) -> Vec<Value> {
+        let server = ApiServer {
+            config,
+            open_repo: Box::new(move || Ok(Box::new(repo()) as _)),
+            create_provider: Box::new(move |_, _| {
+                let provider = ScriptedProvider::new(&["feat: add api"])
+                    .with_review(ReviewResult {
+                        summary: "looks good".to_string(),
+                        issues: vec![],
+                        suggestions: vec![],
+                    })
+                    .with_streaming(streaming);
+                Ok(Arc::new(provider) as _)
+            }),
+        };
+        let mut out = Vec::new();
+        server.serve(input.as_bytes(), &mut out).await.unwrap();
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn events(values: &[Value]) -> Vec<&str> {
+        values
+            .iter()
+            .map(|v| v["event"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_ping_and_shutdown() {
+        let config = AppConfig::default();
+        let input = "{\"id\":1,\"cmd\":\"ping\"}\n{\"id\":2,\"cmd\":\"shutdown\"}\n{\"id\":3,\"cmd\":\"ping\"}\n";
+        let out = serve(input, &config, MockGitOperations::new, false).await;
+        // shutdown 之后的请求不再处理
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0]["id"], 1);
+        assert_eq!(out[0]["event"], "result");
+        assert_eq!(out[0]["data"]["protocol"], PROTOCOL_VERSION);
+        assert_eq!(out[1]["id"], 2);
+        assert_eq!(out[1]["data"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests_report_errors() {
+        let config = AppConfig::default();
+        let input = "not json\n\n{\"id\":\"a\",\"cmd\":\"nope\"}\n{\"id\":\"b\",\"cmd\":\"commit.create\",\"params\":{}}\n";
+        let out = serve(input, &config, MockGitOperations::new, false).await;
+        assert_eq!(events(&out), vec!["error", "error", "error"]);
+        assert_eq!(out[0]["id"], Value::Null);
+        assert_eq!(out[0]["error"]["code"], "INVALID_INPUT");
+        assert_eq!(out[1]["id"], "a");
+        assert!(
+            out[1]["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("nope")
+        );
+        assert_eq!(out[2]["id"], "b");
+    }
+
+    #[tokio::test]
+    async fn test_commit_generate_streams_deltas() {
+        let config = AppConfig::default();
+        let input = "{\"id\":7,\"cmd\":\"commit.generate\",\"params\":{\"stream\":true}}\n";
+        let out = serve(input, &config, staged_repo, true).await;
+        assert_eq!(
+            events(&out),
+            vec!["progress", "progress", "delta", "delta", "delta", "result"]
+        );
+        assert_eq!(out[2]["text"], "feat: ");
+        assert_eq!(out[5]["id"], 7);
+        assert_eq!(out[5]["data"]["message"], "feat: add api");
+        assert_eq!(out[5]["data"]["diff_stats"]["files_changed"][0], "a.rs");
+    }
+
+    #[tokio::test]
+    async fn test_commit_generate_without_streaming() {
+        let config = AppConfig::default();
+        let input = "{\"id\":1,\"cmd\":\"commit.generate\",\"params\":{\"stream\":false}}\n";
+        let out = serve(input, &config, staged_repo, true).await;
+        assert_eq!(events(&out), vec!["progress", "progress", "result"]);
+        assert_eq!(out[2]["data"]["message"], "feat: add api");
+    }
+
+    #[tokio::test]
+    async fn test_commit_generate_appends_trailers() {
+        let mut config = AppConfig::default();
+        config.commit.trailers.co_authors = vec!["Jane <jane@example.com>".to_string()];
+        let input = "{\"id\":1,\"cmd\":\"commit.generate\",\"params\":{\"stream\":false,\"signoff\":true}}\n";
+        let out = serve(input, &config, staged_repo, true).await;
+        assert_eq!(
+            out[2]["data"]["message"],
+            "feat: add api\n\nCo-authored-by: Jane <jane@example.com>\nSigned-off-by: Dev <dev@example.com>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_generate_no_staged_changes() {
+        let config = AppConfig::default();
+        let repo = || {
+            let mut git = MockGitOperations::new();
+            git.expect_has_staged_changes().returning(|| Ok(false));
+            git
+        };
+        let out = serve("{\"cmd\":\"commit.generate\"}\n", &config, repo, false).await;
+        assert_eq!(events(&out), vec!["error"]);
+        assert_eq!(out[0]["error"]["code"], "NO_STAGED_CHANGES");
+    }
+
+    #[tokio::test]
+    async fn test_commit_create() {
+        let config = AppConfig::default();
+        let repo = || {
+            let mut git = MockGitOperations::new();
+            git.expect_commit()
+                .withf(|message| message == "feat: add api")
+                .times(1)
+                .returning(|_| Ok(()));
+            git
+        };
+        let input =
+            "{\"id\":1,\"cmd\":\"commit.create\",\"params\":{\"message\":\"feat: add api\"}}\n";
+        let out = serve(input, &config, repo, false).await;
+        assert_eq!(out[0]["event"], "result");
+        assert_eq!(out[0]["data"]["committed"], true);
+    }
+
+    #[tokio::test]
+    async fn test_review_requires_value_for_commit_target() {
+        let config = AppConfig::default();
+        let input = "{\"id\":1,\"cmd\":\"review\",\"params\":{\"target\":\"commit\"}}\n";
+        let out = serve(input, &config, MockGitOperations::new, false).await;
+        assert_eq!(events(&out), vec!["progress", "error"]);
+        assert!(
+            out[1]["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("value")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_changes() {
+        let config = AppConfig::default();
+        let workdir = tempfile::TempDir::new().unwrap();
+        let path = workdir.path().to_path_buf();
+        let repo = move || {
+            let mut git = MockGitOperations::new();
+            git.expect_get_uncommitted_diff()
+                .returning(|| Ok("diff --git a/a.rs b/a.rs\n+x\n".to_string()));
+            let path = path.clone();
+            git.expect_get_workdir().returning(move || Ok(path.clone()));
+            git
+        };
+        let out = serve("{\"cmd\":\"review\"}\n", &config, repo, false).await;
+        assert_eq!(events(&out), vec!["progress", "progress", "result"]);
+        assert_eq!(out[2]["data"]["summary"], "looks good");
+        assert_eq!(out[2]["data"]["suppressed"], 0);
+    }
+}
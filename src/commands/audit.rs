@@ -0,0 +1,277 @@
+//! Audit log inspection (`gcop-rs audit show/export`).
+//!
+//! Reads the audit log written when `[privacy].audit_log` is enabled (see
+//! [`crate::llm::audit`]).
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use colored::Colorize;
+
+use super::format::OutputFormat;
+use crate::cli::{AuditAction, AuditExportFormat};
+use crate::commands::json::{self, JsonOutput};
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::llm::audit::{self, AuditRecord};
+use crate::ui;
+
+/// Hash prefix length shown by `audit show`.
+const SHORT_HASH: usize = 12;
+
+/// CSV columns of `audit export --format csv`.
+const CSV_HEADER: &str =
+    "timestamp,provider,model,prompt_sha256,response_sha256,prompt_tokens,completion_tokens";
+
+/// Execute audit command
+pub fn run(action: &AuditAction, config: &AppConfig) -> Result<()> {
+    match action {
+        AuditAction::Show {
+            since,
+            limit,
+            format,
+            json,
+        } => {
+            let format = OutputFormat::from_cli(format, *json);
+            let result = show(since.as_deref(), *limit, format, config);
+            if let Err(ref e) = result
+                && format.is_json()
+            {
+                let _ = json::output_json_error::<Vec<AuditRecord>>(e);
+            }
+            result
+        }
+        AuditAction::Export {
+            since,
+            format,
+            output,
+        } => export(since.as_deref(), *format, output.as_deref(), config),
+    }
+}
+
+fn load(since: Option<&str>) -> Result<(PathBuf, Vec<AuditRecord>)> {
+    let since = since
+        .map(|v| super::stats::parse_date_bound(v, false))
+        .transpose()?;
+    let path = audit::audit_path()
+        .ok_or_else(|| GcopError::Config(rust_i18n::t!("audit.no_data_dir").to_string()))?;
+    let records = filter_since(audit::load(&path)?, since);
+    Ok((path, records))
+}
+
+/// Drops records before `since`.
+fn filter_since(records: Vec<AuditRecord>, since: Option<DateTime<Local>>) -> Vec<AuditRecord> {
+    records
+        .into_iter()
+        .filter(|record| since.is_none_or(|since| record.timestamp >= since))
+        .collect()
+}
+
+fn show(since: Option<&str>, limit: usize, format: OutputFormat, config: &AppConfig) -> Result<()> {
+    format.reject_stats_only()?;
+    let (path, records) = load(since)?;
+    let records = &records[records.len().saturating_sub(limit)..];
+
+    if format.is_json() {
+        let output = JsonOutput {
+            success: true,
+            data: Some(records),
+            error: None,
+            meta: None,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let colored = format.effective_colored(config.ui.colored);
+    if records.is_empty() {
+        println!(
+            "{}",
+            ui::info(
+                &rust_i18n::t!("audit.empty", path = path.display()),
+                colored
+            )
+        );
+        if !config.privacy.audit_log {
+            println!("{}", ui::info(&rust_i18n::t!("audit.disabled"), colored));
+        }
+        return Ok(());
+    }
+    output_text(records, colored);
+    Ok(())
+}
+
+fn output_text(records: &[AuditRecord], colored: bool) {
+    println!("{}", ui::info(&rust_i18n::t!("audit.title"), colored));
+    println!("{}", "─".repeat(72));
+
+    let provider_width = records
+        .iter()
+        .map(|r| r.provider.len())
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    let model_width = records
+        .iter()
+        .map(|r| r.model.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+
+    let header = format!(
+        "  {:<19}  {:<provider_width$}  {:<model_width$}  {:>13}  {:<SHORT_HASH$}  {:<SHORT_HASH$}",
+        rust_i18n::t!("audit.col_time"),
+        rust_i18n::t!("audit.col_provider"),
+        rust_i18n::t!("audit.col_model"),
+        rust_i18n::t!("audit.col_tokens"),
+        rust_i18n::t!("audit.col_prompt"),
+        rust_i18n::t!("audit.col_response"),
+    );
+    if colored {
+        println!("{}", header.bold());
+    } else {
+        println!("{}", header);
+    }
+
+    for record in records {
+        let tokens = match (record.prompt_tokens, record.completion_tokens) {
+            (Some(prompt), Some(completion)) => format!("{}+{}", prompt, completion),
+            _ => "-".to_string(),
+        };
+        let response = match &record.response_sha256 {
+            Some(hash) => short_hash(hash).to_string(),
+            None => rust_i18n::t!("audit.failed").to_string(),
+        };
+        println!(
+            "  {:<19}  {:<provider_width$}  {:<model_width$}  {:>13}  {:<SHORT_HASH$}  {}",
+            record.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            record.provider,
+            record.model,
+            tokens,
+            short_hash(&record.prompt_sha256),
+            response,
+        );
+    }
+}
+
+fn short_hash(hash: &str) -> &str {
+    hash.get(..SHORT_HASH).unwrap_or(hash)
+}
+
+fn export(
+    since: Option<&str>,
+    format: AuditExportFormat,
+    output: Option<&Path>,
+    config: &AppConfig,
+) -> Result<()> {
+    let (_, records) = load(since)?;
+    let content = render_export(&records, format)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            ui::success(
+                &rust_i18n::t!(
+                    "audit.exported",
+                    count = records.len(),
+                    path = path.display()
+                ),
+                config.ui.colored,
+            );
+        }
+        None => std::io::stdout().write_all(content.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Renders `records` in the export `format` (one line per record).
+pub fn render_export(records: &[AuditRecord], format: AuditExportFormat) -> Result<String> {
+    let mut out = String::new();
+    match format {
+        AuditExportFormat::Jsonl => {
+            for record in records {
+                out.push_str(&serde_json::to_string(record)?);
+                out.push('\n');
+            }
+        }
+        AuditExportFormat::Csv => {
+            out.push_str(CSV_HEADER);
+            out.push('\n');
+            for record in records {
+                let optional =
+                    |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_default();
+                let fields = [
+                    record.timestamp.to_rfc3339(),
+                    csv_field(&record.provider),
+                    csv_field(&record.model),
+                    record.prompt_sha256.clone(),
+                    record.response_sha256.clone().unwrap_or_default(),
+                    optional(record.prompt_tokens),
+                    optional(record.completion_tokens),
+                ];
+                out.push_str(&fields.join(","));
+                out.push('\n');
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record(day: u32, model: &str, response: Option<&str>) -> AuditRecord {
+        AuditRecord {
+            timestamp: Local.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap(),
+            provider: "openai".to_string(),
+            model: model.to_string(),
+            prompt_sha256: "a".repeat(64),
+            response_sha256: response.map(str::to_string),
+            prompt_tokens: response.map(|_| 812),
+            completion_tokens: response.map(|_| 24),
+        }
+    }
+
+    #[test]
+    fn test_filter_since() {
+        let records = vec![record(1, "m", None), record(17, "m", Some("b"))];
+        let since = Local.with_ymd_and_hms(2026, 10, 10, 0, 0, 0).unwrap();
+        let filtered = filter_since(records.clone(), Some(since));
+        assert_eq!(filtered, vec![records[1].clone()]);
+        assert_eq!(filter_since(records.clone(), None), records);
+    }
+
+    #[test]
+    fn test_render_export_csv() {
+        let records = vec![record(17, "gpt-4o", Some("bb")), record(18, "a,b", None)];
+        let csv = render_export(&records, AuditExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].ends_with(&format!(",openai,gpt-4o,{},bb,812,24", "a".repeat(64))));
+        // 含逗号的字段加引号，失败请求的哈希和 token 为空
+        assert!(lines[2].ends_with(&format!(",openai,\"a,b\",{},,,", "a".repeat(64))));
+    }
+
+    #[test]
+    fn test_render_export_jsonl_round_trips() {
+        let records = vec![record(17, "gpt-4o", Some("bb")), record(18, "m", None)];
+        let jsonl = render_export(&records, AuditExportFormat::Jsonl).unwrap();
+        let parsed: Vec<AuditRecord> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(parsed, records);
+    }
+}
@@ -0,0 +1,154 @@
+//! Conventional Commits breaking-change markers.
+//!
+//! A message counts as breaking when its header has `!` before the colon
+//! (`feat(api)!: ...`) or it has a `BREAKING CHANGE:` footer. Generated
+//! messages that carry one marker get the other as well, and the offline
+//! message is marked when `llm::breaking` found likely breaking changes.
+
+use crate::convention::{BREAKING_TOKENS, ConventionalMessage, replace_header};
+use crate::llm::breaking::BreakingChange;
+
+/// Whether `message` is marked as a breaking change.
+pub(crate) fn is_breaking(message: &str) -> bool {
+    header_marked(message) || has_footer(message)
+}
+
+/// Completes the markers of a message that already carries one of them.
+pub(crate) fn apply_breaking(message: String, changes: &[BreakingChange]) -> String {
+    if is_breaking(&message) {
+        mark_breaking(message, changes)
+    } else {
+        message
+    }
+}
+
+/// Marks `message` as breaking: `!` in a conventional header and a
+/// `BREAKING CHANGE:` footer describing `changes` (or repeating the subject).
+pub(crate) fn mark_breaking(message: String, changes: &[BreakingChange]) -> String {
+    let message = add_header_marker(message);
+    if has_footer(&message) {
+        return message;
+    }
+
+    let description = if changes.is_empty() {
+        match ConventionalMessage::parse(&message) {
+            Some(parsed) => parsed.subject,
+            None => {
+                let header = message.lines().next().unwrap_or_default();
+                header
+                    .split_once(": ")
+                    .map_or(header, |(_, subject)| subject)
+                    .to_string()
+            }
+        }
+    } else {
+        changes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+    let message = message.trim_end();
+    let separator = if super::ticket::ends_with_trailers(message) {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    format!(
+        "{}{}{}: {}",
+        message, separator, BREAKING_TOKENS[0], description
+    )
+}
+
+/// Whether `line` is a `BREAKING CHANGE:` footer line.
+pub(crate) fn is_footer_line(line: &str) -> bool {
+    BREAKING_TOKENS.iter().any(|token| {
+        line.strip_prefix(token)
+            .is_some_and(|rest| rest.starts_with(": "))
+    })
+}
+
+fn has_footer(message: &str) -> bool {
+    message.lines().skip(1).any(is_footer_line)
+}
+
+fn header_marked(message: &str) -> bool {
+    ConventionalMessage::parse(message).is_some_and(|parsed| parsed.breaking)
+}
+
+/// Inserts `!` before the colon of a conventional header; other headers
+/// (gitmoji, free-form) are left alone.
+fn add_header_marker(message: String) -> String {
+    match ConventionalMessage::parse(&message) {
+        Some(mut parsed) if !parsed.breaking => {
+            parsed.breaking = true;
+            replace_header(&message, &parsed)
+        }
+        _ => message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::breaking::BreakingKind;
+
+    fn removed_fn() -> BreakingChange {
+        BreakingChange {
+            kind: BreakingKind::Removed,
+            item: "function",
+            name: "connect".to_string(),
+            file: "src/lib.rs".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_breaking() {
+        assert!(is_breaking("feat(api)!: drop connect"));
+        assert!(is_breaking("feat!: drop connect"));
+        assert!(is_breaking(
+            "refactor: x\n\nBREAKING CHANGE: connect is gone"
+        ));
+        assert!(is_breaking(
+            "refactor: x\n\nBREAKING-CHANGE: connect is gone"
+        ));
+        assert!(!is_breaking("feat(api): add connect"));
+        // 只有正文中提到，不算 footer
+        assert!(!is_breaking("docs: explain BREAKING CHANGE: usage"));
+    }
+
+    #[test]
+    fn test_apply_breaking_completes_markers() {
+        assert_eq!(
+            apply_breaking("feat(api)!: drop connect".to_string(), &[removed_fn()]),
+            "feat(api)!: drop connect\n\nBREAKING CHANGE: removed public function `connect` (src/lib.rs)"
+        );
+        assert_eq!(
+            apply_breaking(
+                "feat(api): drop connect\n\nBREAKING CHANGE: use open()".to_string(),
+                &[]
+            ),
+            "feat(api)!: drop connect\n\nBREAKING CHANGE: use open()"
+        );
+        assert_eq!(
+            apply_breaking("feat!: drop connect".to_string(), &[]),
+            "feat!: drop connect\n\nBREAKING CHANGE: drop connect"
+        );
+        // 未标记的消息保持不变
+        assert_eq!(
+            apply_breaking("fix: typo".to_string(), &[removed_fn()]),
+            "fix: typo"
+        );
+    }
+
+    #[test]
+    fn test_mark_breaking_non_conventional_header() {
+        assert_eq!(
+            mark_breaking(
+                ":boom: drop connect\n\nRefs: ABC-1".to_string(),
+                &[removed_fn()]
+            ),
+            ":boom: drop connect\n\nRefs: ABC-1\nBREAKING CHANGE: removed public function `connect` (src/lib.rs)"
+        );
+    }
+}
@@ -1,18 +1,26 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use colored::Colorize;
 use serde::Serialize;
 
+use super::commit_session::{self, CommitSession};
+use super::format::OutputFormat;
 use super::options::CommitOptions;
 use super::smart_truncate_diff;
 use crate::commands::commit_state_machine::{CommitState, GenerationResult, UserAction};
 use crate::commands::json::{self, JsonOutput};
-use crate::config::AppConfig;
+use crate::config::{AppConfig, CommitConstraints, CommitConvention, ConventionStyle, GitmojiMode};
+use crate::convention::constraints::{self, ConstraintIssue};
 use crate::error::{GcopError, Result};
-use crate::git::{DiffStats, GitOperations, repository::GitRepository};
+use crate::git::{CommitInfo, DiffStats, GitOperations, HistoryOptions, repository::GitRepository};
+use crate::llm::metrics::{self, LlmMetrics};
 use crate::llm::provider::base::response::process_commit_response;
-use crate::llm::{CommitContext, LLMProvider, ScopeInfo, provider::create_provider};
-use crate::ui;
+use crate::llm::{
+    CommitContext, LLMProvider, ProgressReporter, ScopeInfo, provider::create_provider,
+};
+use crate::llm::{heuristic, ledger};
+use crate::ui::{self, Reporter};
 
 /// The data part of the Commit command
 #[derive(Debug, Serialize)]
@@ -55,20 +63,45 @@ impl From<&DiffStats> for DiffStatsJson {
 /// * `options` - Commit command options
 /// * `config` - application configuration
 pub async fn run(options: &CommitOptions<'_>, config: &AppConfig) -> Result<()> {
-    let repo = GitRepository::open(None)?;
-    let provider = create_provider(config, options.provider_override)?;
+    let repo = GitRepository::open(Some(&config.file))?.with_signing(options.signing(config));
+    let reporter = options.format.reporter(options.effective_colored(config));
+    if options.show_redacted {
+        return show_redacted(options, config, &repo, reporter.as_ref());
+    }
+    let provider: Arc<dyn LLMProvider> = if options.offline {
+        Arc::new(heuristic::OfflineProvider)
+    } else {
+        create_provider(
+            config,
+            &config.commit.llm,
+            options.provider_override,
+            options.model_override,
+        )?
+    };
 
-    run_with_deps(options, config, &repo as &dyn GitOperations, &provider).await
+    run_with_deps(
+        options,
+        config,
+        &repo as &dyn GitOperations,
+        &provider,
+        reporter.as_ref(),
+    )
+    .await
 }
 
 /// Execute commit command (testable version, accepts trait objects)
+///
+/// Status output goes to `reporter`; the message itself and JSON results
+/// are still printed here.
 #[allow(dead_code)] // for testing
 async fn run_with_deps(
     options: &CommitOptions<'_>,
     config: &AppConfig,
     repo: &dyn GitOperations,
     provider: &Arc<dyn LLMProvider>,
+    reporter: &dyn Reporter,
 ) -> Result<()> {
+    options.format.reject_stats_only()?;
     let colored = options.effective_colored(config);
 
     // Merge command line parameters into one feedback (easy to use without quotes)
@@ -79,20 +112,53 @@ async fn run_with_deps(
         vec![options.feedback.join(" ")]
     };
 
+    // Fixup/squash mode: separate flow
+    if let Some(target) = options.fixup {
+        if repo.is_empty()? {
+            reporter.error(&rust_i18n::t!("fixup.no_commits"));
+            return Err(GcopError::InvalidInput(
+                "Cannot create a fixup commit: repository has no commits".to_string(),
+            ));
+        }
+        return crate::commands::fixup::run_fixup_flow(
+            options,
+            target,
+            config,
+            repo,
+            provider,
+            &initial_feedbacks,
+            reporter,
+        )
+        .await;
+    }
+
     // Split mode: separate flow
     if options.split {
         if options.amend {
-            ui::error(&rust_i18n::t!("commit.amend_split_conflict"), colored);
+            reporter.error(&rust_i18n::t!("commit.amend_split_conflict"));
             return Err(GcopError::InvalidInput(
                 "Cannot use --amend with --split".to_string(),
             ));
         }
-        return crate::commands::split::run_split_flow(options, config, repo, provider).await;
+        if options.offline {
+            reporter.error(&rust_i18n::t!("commit.offline_split_conflict"));
+            return Err(GcopError::InvalidInput(
+                "Cannot use --offline with --split".to_string(),
+            ));
+        }
+        return crate::commands::split::run_split_flow(options, config, repo, provider, reporter)
+            .await;
+    }
+    if options.verify.is_some() {
+        reporter.error(&rust_i18n::t!("commit.verify_requires_split"));
+        return Err(GcopError::InvalidInput(
+            "--verify requires --split".to_string(),
+        ));
     }
 
     // Amend: require at least one existing commit
     if options.amend && repo.is_empty()? {
-        ui::error(&rust_i18n::t!("commit.amend_no_commits"), colored);
+        reporter.error(&rust_i18n::t!("commit.amend_no_commits"));
         return Err(GcopError::InvalidInput(
             "Cannot amend: repository has no commits".to_string(),
         ));
@@ -100,40 +166,63 @@ async fn run_with_deps(
 
     // JSON Schema: Standalone Process
     if options.format.is_json() {
-        return handle_json_mode(options, config, repo, provider, &initial_feedbacks).await;
+        return handle_json_mode(
+            options,
+            config,
+            repo,
+            provider,
+            &initial_feedbacks,
+            reporter,
+        )
+        .await;
     }
 
     // Get diff based on mode (normal vs amend)
-    if !options.amend && !repo.has_staged_changes()? {
-        ui::error(&rust_i18n::t!("commit.no_staged_changes"), colored);
+    if !ensure_staged_changes(options, config, repo, reporter)? {
+        reporter.error(&rust_i18n::t!("commit.no_staged_changes"));
         return Err(GcopError::NoStagedChanges);
     }
-    let diff = get_diff(repo, options.amend)?;
+    let mut diff = get_diff(repo, options.amend)?;
+    if options.review && !options.offline {
+        diff = super::commit_review::review_staged(options, config, repo, provider, diff, colored)
+            .await?;
+    }
+    // Sessions are keyed by the staged diff as git reports it, before redaction
+    let mut session = CommitSession::new(&diff);
+    let trailers = options.trailers(config, repo)?;
+    let draft = options.message_file.map(read_message_file).transpose()?;
 
     // Get diff statistics
     let stats = repo.get_diff_stats(&diff)?;
 
+    // Apply [privacy].on_secret (nothing is sent offline)
+    let diff = if options.offline {
+        diff
+    } else {
+        super::privacy::guard(diff, config, false, colored)?
+    };
+
     // Truncate overly large diffs to prevent tokens from exceeding the limit
     let (diff, truncated) = smart_truncate_diff(&diff, config.llm.max_diff_size);
     if truncated {
-        ui::warning(&rust_i18n::t!("diff.truncated"), colored);
+        reporter.warning(&rust_i18n::t!("diff.truncated"));
     }
 
     // Workspace scope detection
-    let scope_info = compute_scope_info(&stats.files_changed, config);
+    let scope_info = compute_scope_info(repo, &stats.files_changed, config);
+    let history_examples = compute_history_examples(repo, config);
 
-    ui::step(
+    reporter.step(
         &rust_i18n::t!("commit.step1"),
         &rust_i18n::t!(
             "commit.analyzed",
             files = stats.files_changed.len(),
             changes = stats.insertions + stats.deletions
         ),
-        colored,
     );
 
     if config.commit.show_diff_preview {
-        println!("\n{}", ui::format_diff_stats(&stats, colored));
+        reporter.detail(&ui::format_diff_stats(&stats, colored));
     }
 
     // dry_run mode: only generate without submitting
@@ -148,13 +237,23 @@ async fn run_with_deps(
             &initial_feedbacks,
             0,
             options.verbose,
+            options.offline,
             &branch_name,
             &custom_prompt,
             &scope_info,
+            &history_examples,
+            draft.as_deref(),
+            &trailers,
+            reporter,
         )
         .await?;
+        if let Some(path) = options.output {
+            return write_output(path, &message, reporter);
+        }
         if !already_displayed {
-            display_message(&message, 0, config.ui.colored);
+            // The bare message is the result of --dry-run, whatever the reporter
+            reporter.info(&format_message_header(0));
+            println!("{}", message);
         }
         return Ok(());
     }
@@ -167,19 +266,46 @@ async fn run_with_deps(
     let branch_name = repo.get_current_branch()?;
     let custom_prompt = config.commit.custom_prompt.clone();
 
-    let mut state = CommitState::Generating {
+    // Session persistence (`.git/gcop-state.json`); not used for --amend or --yes
+    let session_path = (!options.amend && !options.yes)
+        .then(|| repo.get_git_dir().ok())
+        .flatten()
+        .map(|git_dir| commit_session::state_path(&git_dir));
+
+    // Edits round-trip through `.git/COMMIT_EDITMSG` like `git commit` does
+    let editor_file = repo
+        .get_git_dir()
+        .ok()
+        .map(|dir| dir.join("COMMIT_EDITMSG"));
+    let editor_guidance = editor_guidance(&stats, config.commit.convention.as_ref());
+    let feedback_presets = config.commit.feedback_presets();
+
+    let resumed = match session_path.as_deref() {
+        Some(path) => resume_session(path, &session.diff_hash, &initial_feedbacks, reporter)?,
+        None => None,
+    };
+    let mut state = resumed.unwrap_or(CommitState::Generating {
         attempt: 0,
         feedbacks: initial_feedbacks,
-    };
+    });
 
     loop {
+        if let Some(path) = session_path.as_deref()
+            && matches!(
+                state,
+                CommitState::Generating { .. } | CommitState::WaitingForAction { .. }
+            )
+        {
+            session.record(&state);
+            session.save(path);
+        }
+
         state = match state {
             CommitState::Generating { attempt, feedbacks } => {
                 handle_generating(
                     attempt,
                     feedbacks,
                     max_retries,
-                    colored,
                     options,
                     config,
                     provider,
@@ -188,6 +314,10 @@ async fn run_with_deps(
                     &branch_name,
                     &custom_prompt,
                     &scope_info,
+                    &history_examples,
+                    draft.as_deref(),
+                    &trailers,
+                    reporter,
                 )
                 .await?
             }
@@ -196,39 +326,102 @@ async fn run_with_deps(
                 ref message,
                 attempt,
                 ref feedbacks,
-            } => handle_waiting_for_action(message, attempt, feedbacks, should_edit, colored)?,
+            } => handle_waiting_for_action(
+                message,
+                attempt,
+                feedbacks,
+                should_edit,
+                &editor_guidance,
+                editor_file.as_deref(),
+                &feedback_presets,
+                colored,
+                reporter,
+            )?,
 
             CommitState::Accepted { ref message } => {
-                ui::step(
+                reporter.step(
                     &rust_i18n::t!("commit.step4"),
                     &rust_i18n::t!("commit.creating"),
-                    colored,
                 );
+                // Edits may have dropped requested trailers; restore them.
+                let message = &super::trailers::apply_trailers(message.clone(), &trailers);
                 if options.amend {
                     repo.commit_amend(message)?;
                 } else {
                     repo.commit(message)?;
                 }
-                println!();
+                if let Some(path) = session_path.as_deref() {
+                    commit_session::clear(path);
+                }
                 if options.amend {
-                    ui::success(&rust_i18n::t!("commit.amend_success"), colored);
+                    reporter.success(&rust_i18n::t!("commit.amend_success"));
                 } else {
-                    ui::success(&rust_i18n::t!("commit.success"), colored);
+                    reporter.success(&rust_i18n::t!("commit.success"));
                 }
                 if options.verbose {
-                    println!("\n{}", message);
+                    reporter.detail(message);
                 }
                 return Ok(());
             }
 
-            CommitState::Cancelled => {
-                ui::warning(&rust_i18n::t!("commit.cancelled"), colored);
+            CommitState::Cancelled { interrupted: true } => {
+                return Err(GcopError::Interrupted);
+            }
+
+            CommitState::Cancelled { interrupted: false } => {
+                reporter.warning(&rust_i18n::t!("commit.cancelled"));
                 return Err(GcopError::UserCancelled);
             }
         };
     }
 }
 
+/// Offers to resume a saved session for the current staged diff.
+///
+/// Returns the state to start from, or `None` to start fresh (no saved
+/// session, or the user declined, which also discards it). Feedback given on
+/// the command line is appended to the saved feedback and forces a
+/// regeneration; otherwise the last message is shown again.
+fn resume_session(
+    path: &std::path::Path,
+    diff_hash: &str,
+    initial_feedbacks: &[String],
+    reporter: &dyn Reporter,
+) -> Result<Option<CommitState>> {
+    let Some(saved) = CommitSession::load(path, diff_hash) else {
+        return Ok(None);
+    };
+
+    let prompt = rust_i18n::t!("commit.resume.prompt", count = saved.feedbacks.len());
+    if !reporter.confirm(&prompt, true)? {
+        commit_session::clear(path);
+        return Ok(None);
+    }
+
+    let mut feedbacks = saved.feedbacks;
+    feedbacks.extend_from_slice(initial_feedbacks);
+
+    let state = match saved.message {
+        Some(message) if initial_feedbacks.is_empty() => {
+            reporter.preview(&format_message_header(saved.attempt), &message);
+            CommitState::WaitingForAction {
+                message,
+                attempt: saved.attempt,
+                feedbacks,
+            }
+        }
+        Some(_) => CommitState::Generating {
+            attempt: saved.attempt + 1,
+            feedbacks,
+        },
+        None => CommitState::Generating {
+            attempt: saved.attempt,
+            feedbacks,
+        },
+    };
+    Ok(Some(state))
+}
+
 /// Full execution flow for JSON output mode.
 async fn handle_json_mode(
     options: &CommitOptions<'_>,
@@ -236,34 +429,82 @@ async fn handle_json_mode(
     repo: &dyn GitOperations,
     provider: &Arc<dyn LLMProvider>,
     initial_feedbacks: &[String],
+    reporter: &dyn Reporter,
 ) -> Result<()> {
-    if !options.amend && !repo.has_staged_changes()? {
-        json::output_json_error::<CommitData>(&GcopError::NoStagedChanges)?;
+    if !ensure_staged_changes(options, config, repo, reporter)? {
+        json::output_error::<CommitData>(&GcopError::NoStagedChanges, options.format)?;
         return Err(GcopError::NoStagedChanges);
     }
     let diff = get_diff(repo, options.amend)?;
+    let trailers = match options.trailers(config, repo) {
+        Ok(trailers) => trailers,
+        Err(e) => {
+            json::output_error::<CommitData>(&e, options.format)?;
+            return Err(e);
+        }
+    };
+    let draft = match options.message_file.map(read_message_file).transpose() {
+        Ok(draft) => draft,
+        Err(e) => {
+            json::output_error::<CommitData>(&e, options.format)?;
+            return Err(e);
+        }
+    };
     let stats = repo.get_diff_stats(&diff)?;
+    let diff = if options.offline {
+        diff
+    } else {
+        match super::privacy::guard(diff, config, true, false) {
+            Ok(diff) => diff,
+            Err(e) => {
+                json::output_error::<CommitData>(&e, options.format)?;
+                return Err(e);
+            }
+        }
+    };
     let (diff, _truncated) = smart_truncate_diff(&diff, config.llm.max_diff_size);
     let branch_name = repo.get_current_branch()?;
     let custom_prompt = config.commit.custom_prompt.clone();
-    let scope_info = compute_scope_info(&stats.files_changed, config);
+    let scope_info = compute_scope_info(repo, &stats.files_changed, config);
+    let history_examples = compute_history_examples(repo, config);
+
+    // Progress and streamed deltas are only emitted with --json-stream
+    reporter.step(
+        &rust_i18n::t!("commit.step1"),
+        &rust_i18n::t!(
+            "commit.analyzed",
+            files = stats.files_changed.len(),
+            changes = stats.insertions + stats.deletions
+        ),
+    );
+    reporter.step(
+        &rust_i18n::t!("commit.step2"),
+        &rust_i18n::t!("spinner.generating"),
+    );
 
     match generate_message_no_streaming(
         provider,
         &diff,
         &stats,
+        config,
         initial_feedbacks,
         options.verbose,
+        options.offline,
         &branch_name,
         &custom_prompt,
-        &config.commit.convention,
         &scope_info,
+        &history_examples,
+        draft.as_deref(),
+        &trailers,
+        Some(reporter),
     )
     .await
     {
-        Ok(message) => output_json_success(&message, &stats, false),
+        Ok((message, llm_metrics)) => {
+            output_json_success(&message, &stats, false, llm_metrics, options.format)
+        }
         Err(e) => {
-            json::output_json_error::<CommitData>(&e)?;
+            json::output_error::<CommitData>(&e, options.format)?;
             Err(e)
         }
     }
@@ -275,7 +516,6 @@ async fn handle_generating(
     attempt: usize,
     feedbacks: Vec<String>,
     max_retries: usize,
-    colored: bool,
     options: &CommitOptions<'_>,
     config: &AppConfig,
     provider: &Arc<dyn LLMProvider>,
@@ -284,6 +524,10 @@ async fn handle_generating(
     branch_name: &Option<String>,
     custom_prompt: &Option<String>,
     scope_info: &Option<ScopeInfo>,
+    history_examples: &[String],
+    draft: Option<&str>,
+    trailers: &[String],
+    reporter: &dyn Reporter,
 ) -> Result<CommitState> {
     // Check retry limit
     let gen_state = CommitState::Generating {
@@ -292,15 +536,12 @@ async fn handle_generating(
     };
 
     if gen_state.is_at_max_retries(max_retries) {
-        ui::warning(
-            &rust_i18n::t!("commit.max_retries", count = max_retries),
-            colored,
-        );
+        reporter.warning(&rust_i18n::t!("commit.max_retries", count = max_retries));
         return gen_state.handle_generation(GenerationResult::MaxRetriesExceeded, options.yes);
     }
 
-    // Generate message.
-    let (message, already_displayed) = generate_message(
+    // Generate message; Ctrl+C drops the request and cancels the session.
+    let generated = ui::interruptible(generate_message(
         provider,
         diff,
         stats,
@@ -308,59 +549,85 @@ async fn handle_generating(
         &feedbacks,
         attempt,
         options.verbose,
+        options.offline,
         branch_name,
         custom_prompt,
         scope_info,
-    )
-    .await?;
+        history_examples,
+        draft,
+        trailers,
+        reporter,
+    ))
+    .await;
+    let gen_state = CommitState::Generating { attempt, feedbacks };
+    let (message, already_displayed) = match generated {
+        Ok(generated) => generated?,
+        Err(e) => return gen_state.handle_generation_error(e, options.yes),
+    };
 
     // Use state-machine transition for generation result.
-    let gen_state = CommitState::Generating { attempt, feedbacks };
     let result = GenerationResult::Success(message.clone());
     let next_state = gen_state.handle_generation(result, options.yes)?;
 
     // Show generated message unless it was auto-accepted or already streamed.
     if !options.yes && !already_displayed {
-        display_message(&message, attempt, colored);
+        reporter.preview(&format_message_header(attempt), &message);
+    }
+    if !options.yes {
+        warn_convention_issues(&message, config.commit.convention.as_ref(), reporter);
+        warn_constraint_issues(&message, config, reporter);
     }
 
     Ok(next_state)
 }
 
 /// Handles the `WaitingForAction` state.
+#[allow(clippy::too_many_arguments)]
 fn handle_waiting_for_action(
     message: &str,
     attempt: usize,
     feedbacks: &[String],
     should_edit: bool,
+    editor_guidance: &[String],
+    editor_file: Option<&std::path::Path>,
+    feedback_presets: &[(String, String)],
     colored: bool,
+    reporter: &dyn Reporter,
 ) -> Result<CommitState> {
-    ui::step(
+    reporter.step(
         &rust_i18n::t!("commit.step3"),
         &rust_i18n::t!("commit.choose_action"),
-        colored,
     );
     let ui_action = ui::commit_action_menu(message, should_edit, attempt, colored)?;
 
     // Map UI action to state-machine action and apply editor flow when needed.
     let user_action = match ui_action {
-        ui::CommitAction::Accept => UserAction::Accept,
+        ui::CommitAction::Accept => {
+            if super::breaking::is_breaking(message) && !confirm_breaking(reporter)? {
+                // Back to the menu so the message can be edited or regenerated
+                return Ok(CommitState::WaitingForAction {
+                    message: message.to_string(),
+                    attempt,
+                    feedbacks: feedbacks.to_vec(),
+                });
+            }
+            UserAction::Accept
+        }
 
         ui::CommitAction::Edit => {
-            ui::step(
+            reporter.step(
                 &rust_i18n::t!("commit.step3"),
                 &rust_i18n::t!("commit.opening_editor"),
-                colored,
             );
-            match ui::edit_text(message) {
+            match ui::edit_commit_message(message, editor_guidance, editor_file) {
                 Ok(edited) => {
-                    display_edited_message(&edited, colored);
+                    reporter.preview(&format_edited_header(), &edited);
                     UserAction::Edit {
                         new_message: edited,
                     }
                 }
                 Err(GcopError::UserCancelled) => {
-                    ui::warning(&rust_i18n::t!("commit.edit_cancelled"), colored);
+                    reporter.warning(&rust_i18n::t!("commit.edit_cancelled"));
                     UserAction::EditCancelled
                 }
                 Err(e) => return Err(e),
@@ -370,9 +637,9 @@ fn handle_waiting_for_action(
         ui::CommitAction::Retry => UserAction::Retry,
 
         ui::CommitAction::RetryWithFeedback => {
-            let new_feedback = ui::get_retry_feedback(colored)?;
+            let new_feedback = ui::get_retry_feedback(colored, feedback_presets)?;
             if new_feedback.is_none() {
-                ui::warning(&rust_i18n::t!("commit.feedback.empty"), colored);
+                reporter.warning(&rust_i18n::t!("commit.feedback.empty"));
             }
             UserAction::RetryWithFeedback {
                 feedback: new_feedback,
@@ -390,6 +657,67 @@ fn handle_waiting_for_action(
     Ok(waiting_state.handle_action(user_action))
 }
 
+/// Warns when a message breaks `[commit.convention]` with `style = "conventional"`.
+fn warn_convention_issues(
+    message: &str,
+    convention: Option<&CommitConvention>,
+    reporter: &dyn Reporter,
+) {
+    let Some(convention) = convention.filter(|c| c.style == ConventionStyle::Conventional) else {
+        return;
+    };
+    let issues = crate::convention::lint::lint(message, convention.types.as_deref());
+    if issues.is_empty() {
+        return;
+    }
+    let mut text = rust_i18n::t!("convention.lint_header").to_string();
+    for issue in &issues {
+        text.push_str(&format!("\n  - {}", issue));
+    }
+    reporter.warning(&text);
+}
+
+/// Asks before committing a message marked as a breaking change.
+fn confirm_breaking(reporter: &dyn Reporter) -> Result<bool> {
+    reporter.warning(&rust_i18n::t!("commit.breaking_marked"));
+    reporter.confirm(&rust_i18n::t!("commit.breaking_confirm"), false)
+}
+
+/// Commented guidance shown below the scissors line when editing a message:
+/// convention rules (if configured), then the diff stat and changed files.
+fn editor_guidance(stats: &DiffStats, convention: Option<&CommitConvention>) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(convention) = convention {
+        match convention.style {
+            ConventionStyle::Conventional => {
+                lines.push(rust_i18n::t!("editor.convention_conventional").to_string())
+            }
+            ConventionStyle::Gitmoji => {
+                lines.push(rust_i18n::t!("editor.convention_gitmoji").to_string())
+            }
+            ConventionStyle::Custom => {}
+        }
+        if let Some(types) = &convention.types {
+            lines.push(
+                rust_i18n::t!("editor.convention_types", types = types.join(", ")).to_string(),
+            );
+        }
+        if let Some(template) = &convention.template {
+            lines
+                .push(rust_i18n::t!("editor.convention_template", template = template).to_string());
+        }
+        if let Some(extra) = &convention.extra_prompt {
+            lines.extend(extra.lines().map(String::from));
+        }
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+    }
+    lines.push(ui::format_diff_stats(stats, false));
+    lines.extend(stats.files_changed.iter().map(|file| format!("  {}", file)));
+    lines
+}
+
 /// Generates a commit message.
 ///
 /// Returns `(message, already_displayed)`.
@@ -402,20 +730,33 @@ async fn generate_message(
     feedbacks: &[String],
     attempt: usize,
     verbose: bool,
+    offline: bool,
     branch_name: &Option<String>,
     custom_prompt: &Option<String>,
     scope_info: &Option<ScopeInfo>,
+    history_examples: &[String],
+    previous_message: Option<&str>,
+    trailers: &[String],
+    reporter: &dyn Reporter,
 ) -> Result<(String, bool)> {
-    let context = CommitContext {
-        files_changed: stats.files_changed.clone(),
-        insertions: stats.insertions,
-        deletions: stats.deletions,
-        branch_name: branch_name.clone(),
-        custom_prompt: custom_prompt.clone(),
-        user_feedback: feedbacks.to_vec(),
-        convention: config.commit.convention.clone(),
-        scope_info: scope_info.clone(),
-    };
+    let mut context = build_commit_context(
+        stats,
+        config,
+        feedbacks,
+        branch_name,
+        custom_prompt,
+        scope_info,
+        history_examples,
+    );
+    context.previous_message = previous_message.map(str::to_string);
+
+    if offline {
+        reporter.step(
+            &rust_i18n::t!("commit.step2"),
+            &rust_i18n::t!("commit.offline_generating"),
+        );
+        return Ok((offline_message(diff, &context, config, trailers), false));
+    }
 
     // Build prompt once
     let (system, user) = crate::llm::prompt::build_commit_prompt_split(
@@ -431,7 +772,8 @@ async fn generate_message(
     }
 
     // Decide whether to use streaming mode.
-    let use_streaming = config.ui.streaming && provider.supports_streaming();
+    // Quiet mode has nothing to stream to
+    let use_streaming = config.ui.streaming && provider.supports_streaming() && !ui::is_quiet();
     let colored = config.ui.colored;
 
     if use_streaming {
@@ -441,17 +783,42 @@ async fn generate_message(
         } else {
             rust_i18n::t!("spinner.regenerating_streaming")
         };
-        ui::step(&rust_i18n::t!("commit.step2"), &step_msg, colored);
+        reporter.step(&rust_i18n::t!("commit.step2"), &step_msg);
         println!("\n{}", ui::info(&format_message_header(attempt), colored));
 
-        let stream_handle = provider.send_prompt_streaming(&system, &user).await?;
-
+        metrics::start();
         let mut output = ui::StreamingOutput::new(colored);
-        let message = output.process(stream_handle.receiver).await?;
+        let streamed = match crate::llm::provider::tiered::diff_size_scope(
+            diff.len(),
+            provider.send_prompt_streaming(&system, &user),
+        )
+        .await
+        {
+            Ok(stream_handle) => output.process(stream_handle.receiver).await,
+            Err(e) => Err(e),
+        };
+        let message = match streamed {
+            Ok(message) => message,
+            Err(e) if can_fall_back_offline(&e, config) => {
+                reporter.warning(&rust_i18n::t!(
+                    "commit.offline_fallback",
+                    error = e.to_string()
+                ));
+                return Ok((offline_message(diff, &context, config, trailers), false));
+            }
+            Err(e) => return Err(e),
+        };
         let message = process_commit_response(message);
+        let message = repair_message(provider, diff, &context, config, message, None).await;
+        let llm_metrics = metrics::finish();
+        ledger::record("commit", &llm_metrics, config);
+        let message = finish_message(message, diff, &context, config, trailers);
 
         // If code fences were stripped, erase raw output and redisplay clean version
         output.redisplay_if_cleaned(&message);
+        if verbose {
+            print_verbose_metrics(&llm_metrics, false, colored);
+        }
 
         Ok((message, true)) // Already shown
     } else {
@@ -461,76 +828,329 @@ async fn generate_message(
         } else {
             rust_i18n::t!("spinner.regenerating").to_string()
         };
-        let mut spinner = ui::Spinner::new_with_cancel_hint(&spinner_message, colored);
-        spinner.start_time_display();
-
-        let message = provider.send_prompt(&system, &user, Some(&spinner)).await?;
+        let spinner = reporter.cancellable_spinner(&spinner_message);
+        let finish_spinner = || {
+            if let Some(spinner) = &spinner {
+                spinner.finish_and_clear();
+            }
+        };
 
-        spinner.finish_and_clear();
+        metrics::start();
+        let progress = spinner.as_ref().map(|s| s as &dyn ProgressReporter);
+        let message = match crate::llm::provider::tiered::diff_size_scope(
+            diff.len(),
+            provider.send_prompt(&system, &user, progress),
+        )
+        .await
+        {
+            Ok(message) => message,
+            Err(e) if can_fall_back_offline(&e, config) => {
+                finish_spinner();
+                reporter.warning(&rust_i18n::t!(
+                    "commit.offline_fallback",
+                    error = e.to_string()
+                ));
+                return Ok((offline_message(diff, &context, config, trailers), false));
+            }
+            Err(e) => return Err(e),
+        };
         let message = process_commit_response(message);
+        let message =
+            repair_message(provider, diff, &context, config, message, spinner.as_ref()).await;
+        let llm_metrics = metrics::finish();
+        ledger::record("commit", &llm_metrics, config);
+
+        finish_spinner();
+        if verbose {
+            print_verbose_metrics(&llm_metrics, false, colored);
+        }
+        let message = finish_message(message, diff, &context, config, trailers);
         Ok((message, false)) // Not shown yet
     }
 }
 
-/// Formats the message header (pure function, easy to test).
-fn format_message_header(attempt: usize) -> String {
-    if attempt == 0 {
-        rust_i18n::t!("commit.generated").to_string()
-    } else {
-        rust_i18n::t!("commit.regenerated", attempt = attempt + 1).to_string()
+/// Sends a message breaking `[commit.constraints]` or `commit.body` /
+/// `commit.body_bullets` back to the model with the violations as feedback,
+/// up to `max_repairs` times.
+///
+/// Returns the last message either way; a failed repair request keeps the
+/// message it tried to repair.
+async fn repair_message(
+    provider: &Arc<dyn LLMProvider>,
+    diff: &str,
+    context: &CommitContext,
+    config: &AppConfig,
+    mut message: String,
+    spinner: Option<&ui::Spinner>,
+) -> String {
+    let max_repairs = config
+        .commit
+        .constraints
+        .as_ref()
+        .map_or(CommitConstraints::default().max_repairs, |c| c.max_repairs);
+    let custom = context
+        .convention
+        .as_ref()
+        .is_some_and(|c| c.style == ConventionStyle::Custom);
+
+    for _ in 0..max_repairs {
+        // Checked after the header tidying `finish_message` applies anyway
+        let tidied = if custom {
+            message.clone()
+        } else {
+            crate::convention::tidy_header(message.clone())
+        };
+        let issues = message_issues(&tidied, config);
+        if issues.is_empty() {
+            break;
+        }
+        let summary = issues
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        tracing::debug!("Repairing commit message: {}", summary);
+        if let Some(spinner) = spinner {
+            spinner.set_message(&rust_i18n::t!("commit.repairing", issues = summary));
+        }
+
+        let mut repair = context.clone();
+        repair.previous_message = Some(message.clone());
+        repair.user_feedback.push(format!(
+            "The previous message breaks these rules; fix them and keep everything else: {}",
+            issues
+                .iter()
+                .map(|issue| issue.instruction())
+                .collect::<Vec<_>>()
+                .join(" ")
+        ));
+        let (system, user) = crate::llm::prompt::build_commit_prompt_split(
+            diff,
+            &repair,
+            repair.custom_prompt.as_deref(),
+            repair.convention.as_ref(),
+        );
+        let progress = spinner.map(|s| s as &dyn ProgressReporter);
+        match crate::llm::provider::tiered::diff_size_scope(
+            diff.len(),
+            provider.send_prompt(&system, &user, progress),
+        )
+        .await
+        {
+            Ok(repaired) => message = process_commit_response(repaired),
+            Err(e) => {
+                tracing::warn!("Commit message repair failed: {}", e);
+                break;
+            }
+        }
     }
+    message
 }
 
-/// Formats the edited-message header (pure function, easy to test).
-fn format_edited_header() -> String {
-    rust_i18n::t!("commit.updated").to_string()
+/// Violations of `[commit.constraints]` and `commit.body` / `commit.body_bullets`.
+fn message_issues(message: &str, config: &AppConfig) -> Vec<ConstraintIssue> {
+    let mut issues = config
+        .commit
+        .constraints
+        .as_ref()
+        .map(|limits| constraints::check(message, limits))
+        .unwrap_or_default();
+    issues.extend(constraints::check_body(
+        message,
+        config.commit.body,
+        config.commit.body_bullets,
+    ));
+    issues
 }
 
-/// Displays the generated message.
-fn display_message(message: &str, attempt: usize, colored: bool) {
-    let header = format_message_header(attempt);
+/// Warns when a message still breaks the message rules after repairs.
+fn warn_constraint_issues(message: &str, config: &AppConfig, reporter: &dyn Reporter) {
+    let issues = message_issues(message, config);
+    if issues.is_empty() {
+        return;
+    }
+    let mut text = rust_i18n::t!("convention.constraints_header").to_string();
+    for issue in &issues {
+        text.push_str(&format!("\n  - {}", issue));
+    }
+    reporter.warning(&text);
+}
 
-    println!("\n{}", ui::info(&header, colored));
-    if colored {
-        println!("{}", message.yellow());
+/// Heuristic message for `--offline` and `[llm] offline_fallback`, with
+/// ticket and trailers applied like a generated one.
+pub(crate) fn offline_message(
+    diff: &str,
+    context: &CommitContext,
+    config: &AppConfig,
+    trailers: &[String],
+) -> String {
+    let message = heuristic::commit_message(diff, context);
+    let changes = crate::llm::breaking::detect(diff);
+    let message = if changes.is_empty() {
+        message
     } else {
-        println!("{}", message);
-    }
+        super::breaking::mark_breaking(message, &changes)
+    };
+    let message = super::ticket::apply_ticket(message, context.ticket.as_deref(), &config.commit);
+    super::trailers::apply_trailers(message, trailers)
 }
 
-/// Show the edited message
-fn display_edited_message(message: &str, colored: bool) {
-    println!("\n{}", ui::info(&format_edited_header(), colored));
-    if colored {
-        println!("{}", message.yellow());
+/// Post-processing shared by generated messages: tidies a conventional
+/// header (unless the convention is custom), completes breaking-change
+/// markers, converts gitmoji headers, then applies the ticket and trailers.
+pub(crate) fn finish_message(
+    message: String,
+    diff: &str,
+    context: &CommitContext,
+    config: &AppConfig,
+    trailers: &[String],
+) -> String {
+    let custom = context
+        .convention
+        .as_ref()
+        .is_some_and(|c| c.style == ConventionStyle::Custom);
+    let message = if custom {
+        message
     } else {
-        println!("{}", message);
+        crate::convention::tidy_header(message)
+    };
+    let message = super::breaking::apply_breaking(message, &crate::llm::breaking::detect(diff));
+    let message = convert_gitmoji(message, context.convention.as_ref());
+    let message = super::ticket::apply_ticket(message, context.ticket.as_deref(), &config.commit);
+    super::trailers::apply_trailers(message, trailers)
+}
+
+/// Converts between conventional and gitmoji headers with the convention's
+/// emoji table: to gitmoji with `gitmoji_mode = "convert"`, back to
+/// conventional when a conventional style got a gitmoji header.
+fn convert_gitmoji(message: String, convention: Option<&CommitConvention>) -> String {
+    let table = crate::convention::gitmoji::table(convention);
+    match convention {
+        Some(c) if c.style == ConventionStyle::Gitmoji => match c.gitmoji_mode {
+            GitmojiMode::Convert => crate::convention::gitmoji::to_gitmoji(message, &table),
+            GitmojiMode::Prompt => message,
+        },
+        Some(c) if c.style == ConventionStyle::Custom => message,
+        _ => crate::convention::gitmoji::from_gitmoji(&message, &table).unwrap_or(message),
     }
 }
 
-/// Generate commit message (non-streaming version, for JSON output mode)
-#[allow(clippy::too_many_arguments)]
-async fn generate_message_no_streaming(
-    provider: &Arc<dyn LLMProvider>,
-    diff: &str,
+/// Whether `error` may be replaced by a heuristic message (`[llm] offline_fallback`).
+pub(crate) fn can_fall_back_offline(error: &GcopError, config: &AppConfig) -> bool {
+    config.llm.offline_fallback && error.is_provider_failure()
+}
+
+/// Metrics reported in JSON `meta` for heuristic messages.
+fn offline_metrics() -> LlmMetrics {
+    LlmMetrics {
+        provider: Some(heuristic::PROVIDER_NAME.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Builds the prompt context shared by all commit generation paths.
+pub(crate) fn build_commit_context(
     stats: &DiffStats,
+    config: &AppConfig,
     feedbacks: &[String],
-    verbose: bool,
     branch_name: &Option<String>,
     custom_prompt: &Option<String>,
-    convention: &Option<crate::config::CommitConvention>,
     scope_info: &Option<ScopeInfo>,
-) -> Result<String> {
-    let context = CommitContext {
+    history_examples: &[String],
+) -> CommitContext {
+    CommitContext {
         files_changed: stats.files_changed.clone(),
         insertions: stats.insertions,
         deletions: stats.deletions,
         branch_name: branch_name.clone(),
+        ticket: super::ticket::resolve_ticket(branch_name.as_deref(), &config.commit),
         custom_prompt: custom_prompt.clone(),
         user_feedback: feedbacks.to_vec(),
-        convention: convention.clone(),
+        convention: config.commit.convention.clone(),
         scope_info: scope_info.clone(),
-    };
+        history_examples: history_examples.to_vec(),
+        previous_message: None,
+        body: config.commit.body,
+        body_bullets: config.commit.body_bullets,
+    }
+}
+
+/// Reads the draft given with `--message-file`, dropping git comment lines.
+fn read_message_file(path: &Path) -> Result<String> {
+    let contents = std::fs::read_to_string(path)?;
+    super::hook::extract_previous_message(&contents).ok_or_else(|| {
+        GcopError::InvalidInput(
+            rust_i18n::t!("commit.message_file_empty", path = path.display()).to_string(),
+        )
+    })
+}
+
+/// Writes the generated message for `--output`; `-` prints only the message.
+fn write_output(path: &Path, message: &str, reporter: &dyn Reporter) -> Result<()> {
+    if path == Path::new("-") {
+        println!("{}", message);
+        return Ok(());
+    }
+    std::fs::write(path, format!("{}\n", message))?;
+    reporter.success(&rust_i18n::t!(
+        "commit.message_written",
+        path = path.display()
+    ));
+    Ok(())
+}
+
+/// Formats the message header (pure function, easy to test).
+fn format_message_header(attempt: usize) -> String {
+    if attempt == 0 {
+        rust_i18n::t!("commit.generated").to_string()
+    } else {
+        rust_i18n::t!("commit.regenerated", attempt = attempt + 1).to_string()
+    }
+}
+
+/// Formats the edited-message header (pure function, easy to test).
+fn format_edited_header() -> String {
+    rust_i18n::t!("commit.updated").to_string()
+}
+
+/// Generate commit message without terminal rendering (JSON output mode)
+///
+/// With `stream_to`, streaming providers forward their deltas to that reporter
+/// (`--json-stream`); otherwise the non-streaming API is used.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn generate_message_no_streaming(
+    provider: &Arc<dyn LLMProvider>,
+    diff: &str,
+    stats: &DiffStats,
+    config: &AppConfig,
+    feedbacks: &[String],
+    verbose: bool,
+    offline: bool,
+    branch_name: &Option<String>,
+    custom_prompt: &Option<String>,
+    scope_info: &Option<ScopeInfo>,
+    history_examples: &[String],
+    previous_message: Option<&str>,
+    trailers: &[String],
+    stream_to: Option<&dyn Reporter>,
+) -> Result<(String, LlmMetrics)> {
+    let mut context = build_commit_context(
+        stats,
+        config,
+        feedbacks,
+        branch_name,
+        custom_prompt,
+        scope_info,
+        history_examples,
+    );
+    context.previous_message = previous_message.map(str::to_string);
+
+    if offline {
+        return Ok((
+            offline_message(diff, &context, config, trailers),
+            offline_metrics(),
+        ));
+    }
 
     // Build prompt
     let (system, user) = crate::llm::prompt::build_commit_prompt_split(
@@ -546,12 +1166,65 @@ async fn generate_message_no_streaming(
         print_verbose_prompt(&system, &user, true, false);
     }
 
-    // Use the non-streaming API directly
-    provider.send_prompt(&system, &user, None).await
+    // Stream only when the deltas have somewhere to go (--json-stream)
+    let stream_to = stream_to.filter(|_| config.ui.streaming && provider.supports_streaming());
+    metrics::start();
+    let response = match stream_to {
+        Some(reporter) => {
+            match crate::llm::provider::tiered::diff_size_scope(
+                diff.len(),
+                provider.send_prompt_streaming(&system, &user),
+            )
+            .await
+            {
+                Ok(handle) => ui::forward_stream(handle.receiver, reporter).await,
+                Err(e) => Err(e),
+            }
+        }
+        None => {
+            crate::llm::provider::tiered::diff_size_scope(
+                diff.len(),
+                provider.send_prompt(&system, &user, None),
+            )
+            .await
+        }
+    };
+    let message = match response {
+        Ok(message) => message,
+        Err(e) if can_fall_back_offline(&e, config) => {
+            metrics::finish();
+            // JSON mode: stdout is reserved for the result
+            eprintln!(
+                "⚠ {}",
+                rust_i18n::t!("commit.offline_fallback", error = e.to_string())
+            );
+            return Ok((
+                offline_message(diff, &context, config, trailers),
+                offline_metrics(),
+            ));
+        }
+        Err(e) => return Err(e),
+    };
+    let llm_metrics = metrics::finish();
+    ledger::record("commit", &llm_metrics, config);
+    if verbose {
+        print_verbose_metrics(&llm_metrics, true, false);
+    }
+
+    Ok((
+        finish_message(message, diff, &context, config, trailers),
+        llm_metrics,
+    ))
 }
 
 /// JSON format successfully output
-fn output_json_success(message: &str, stats: &DiffStats, committed: bool) -> Result<()> {
+fn output_json_success(
+    message: &str,
+    stats: &DiffStats,
+    committed: bool,
+    llm_metrics: LlmMetrics,
+    format: OutputFormat,
+) -> Result<()> {
     let output = JsonOutput {
         success: true,
         data: Some(CommitData {
@@ -560,16 +1233,16 @@ fn output_json_success(message: &str, stats: &DiffStats, committed: bool) -> Res
             committed,
         }),
         error: None,
+        meta: Some(llm_metrics),
     };
-    println!("{}", serde_json::to_string_pretty(&output)?);
-    Ok(())
+    json::print_output(&output, format)
 }
 
 /// Display prompt details in verbose mode.
 ///
 /// `to_stderr`: use stderr (for JSON mode where stdout is reserved)
 /// `colored`: apply color formatting
-fn print_verbose_prompt(system: &str, user: &str, to_stderr: bool, colored: bool) {
+pub(crate) fn print_verbose_prompt(system: &str, user: &str, to_stderr: bool, colored: bool) {
     macro_rules! vprintln {
         ($($arg:tt)*) => {
             if to_stderr {
@@ -605,24 +1278,113 @@ fn print_verbose_prompt(system: &str, user: &str, to_stderr: bool, colored: bool
     }
 }
 
+/// Display the LLM metrics summary line in verbose mode.
+///
+/// `to_stderr` and `colored` behave as in [`print_verbose_prompt`].
+pub(crate) fn print_verbose_metrics(llm_metrics: &LlmMetrics, to_stderr: bool, colored: bool) {
+    let line = ui::info(&llm_metrics.summary(), colored);
+    if to_stderr {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
 /// Public wrapper for `compute_scope_info` (used by split module).
 pub(crate) fn compute_scope_info_pub(
+    repo: &dyn GitOperations,
     files_changed: &[String],
     config: &AppConfig,
 ) -> Option<ScopeInfo> {
-    compute_scope_info(files_changed, config)
+    compute_scope_info(repo, files_changed, config)
 }
 
+/// Maximum number of recent commits scanned for historical scopes.
+const HISTORY_SCOPE_SCAN_LIMIT: usize = 500;
+
+/// Maximum number of historical scopes passed to the prompt.
+const MAX_HISTORY_SCOPES: usize = 3;
+
 /// Calculate workspace scope information
 ///
 /// Detect workspace configuration from git root and infer the scope of changed files.
 /// Supports manual configuration override automatic detection. Returns None (non-fatal) if detection fails.
-fn compute_scope_info(files_changed: &[String], config: &AppConfig) -> Option<ScopeInfo> {
+/// When `workspace.symbol_scope` is enabled and no package scope was found, falls back to
+/// the module-based heuristic in [`crate::workspace::scope::infer_symbol_scope`].
+/// When `workspace.history_scope` is enabled, scopes of earlier commits touching the same
+/// files are added as `history_scopes`, and the most frequent one is suggested if nothing
+/// else was inferred.
+fn compute_scope_info(
+    repo: &dyn GitOperations,
+    files_changed: &[String],
+    config: &AppConfig,
+) -> Option<ScopeInfo> {
     if !config.workspace.enabled {
         return None;
     }
 
     let root = crate::git::find_git_root()?;
+    let mut info = compute_workspace_scope_info(files_changed, config, &root);
+    let has_scope =
+        |info: &Option<ScopeInfo>| info.as_ref().is_some_and(|s| s.suggested_scope.is_some());
+
+    if config.workspace.symbol_scope
+        && !has_scope(&info)
+        && let Some(symbol_scope) =
+            crate::workspace::scope::infer_symbol_scope(files_changed, &root)
+    {
+        tracing::debug!(
+            "{}",
+            rust_i18n::t!("workspace.scope_suggestion", scope = symbol_scope.as_str())
+        );
+        info.get_or_insert_default().suggested_scope = Some(symbol_scope);
+    }
+
+    if config.workspace.history_scope {
+        let history_scopes = compute_history_scopes(repo, files_changed);
+        if !history_scopes.is_empty() {
+            let fill_scope = !has_scope(&info);
+            let info = info.get_or_insert_default();
+            if fill_scope {
+                tracing::debug!(
+                    "{}",
+                    rust_i18n::t!(
+                        "workspace.scope_suggestion",
+                        scope = history_scopes[0].as_str()
+                    )
+                );
+                info.suggested_scope = Some(history_scopes[0].clone());
+            }
+            info.history_scopes = history_scopes;
+        }
+    }
+
+    info
+}
+
+/// Most frequent scopes of earlier conventional commits touching `files_changed`
+///
+/// Returns an empty list when history cannot be read (non-fatal).
+fn compute_history_scopes(repo: &dyn GitOperations, files_changed: &[String]) -> Vec<String> {
+    match repo.get_commits_touching(files_changed, HISTORY_SCOPE_SCAN_LIMIT) {
+        Ok(commits) => crate::workspace::scope::history_scopes(
+            commits.iter().map(|c| c.message.as_str()),
+            MAX_HISTORY_SCOPES,
+        ),
+        Err(e) => {
+            tracing::debug!("Failed to read commit history for scopes: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Package-based scope information from workspace detection (None if not a monorepo)
+fn compute_workspace_scope_info(
+    files_changed: &[String],
+    config: &AppConfig,
+    root: &std::path::Path,
+) -> Option<ScopeInfo> {
+    let root = root.to_path_buf();
 
     // Build WorkspaceInfo: Manual configuration takes precedence, otherwise automatic detection
     let workspace_info = if let Some(ref manual_members) = config.workspace.members {
@@ -659,7 +1421,11 @@ fn compute_scope_info(files_changed: &[String], config: &AppConfig) -> Option<Sc
         );
     }
 
-    let scope = crate::workspace::scope::infer_scope(files_changed, &workspace_info, None);
+    let style = crate::workspace::scope::ScopeStyle {
+        depth: config.workspace.scope_depth,
+        joiner: &config.workspace.scope_joiner,
+    };
+    let scope = crate::workspace::scope::infer_scope(files_changed, &workspace_info, None, &style);
 
     // Apply scope_mappings remapping
     let suggested = scope.suggested_scope.map(|s| {
@@ -684,14 +1450,131 @@ fn compute_scope_info(files_changed: &[String], config: &AppConfig) -> Option<Sc
         packages: scope.packages,
         suggested_scope: suggested,
         has_root_changes: !scope.root_files.is_empty(),
+        history_scopes: Vec::new(),
     })
 }
 
+/// Maximum subject length for a commit to be used as a history example.
+const MAX_HISTORY_SUBJECT_LEN: usize = 100;
+
+/// Collect recent commit subjects to use as few-shot style examples.
+///
+/// Returns an empty list when `commit.learn_from_history` is disabled or
+/// history cannot be read (non-fatal).
+pub(crate) fn compute_history_examples(
+    repo: &dyn GitOperations,
+    config: &AppConfig,
+) -> Vec<String> {
+    if !config.commit.learn_from_history || config.commit.history_sample_size == 0 {
+        return Vec::new();
+    }
+
+    match repo.get_commit_history(&HistoryOptions::default()) {
+        Ok(commits) => select_history_examples(&commits, config.commit.history_sample_size),
+        Err(e) => {
+            tracing::debug!("Failed to read commit history for examples: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Pick up to `limit` well-formed, distinct commit subjects (newest first).
+///
+/// Skips merge commits, fixup/squash/amend commits, reverts, WIP commits and
+/// subjects that are empty or unusually long.
+fn select_history_examples(commits: &[CommitInfo], limit: usize) -> Vec<String> {
+    let mut examples: Vec<String> = Vec::new();
+
+    for commit in commits {
+        if examples.len() >= limit {
+            break;
+        }
+        if commit.parent_count > 1 {
+            continue;
+        }
+
+        let subject = commit.message.trim();
+        if !is_well_formed_subject(subject) || examples.iter().any(|e| e == subject) {
+            continue;
+        }
+        examples.push(subject.to_string());
+    }
+
+    examples
+}
+
+fn is_well_formed_subject(subject: &str) -> bool {
+    if subject.is_empty() || subject.chars().count() > MAX_HISTORY_SUBJECT_LEN {
+        return false;
+    }
+
+    let lower = subject.to_lowercase();
+    const SKIPPED_PREFIXES: &[&str] = &[
+        "fixup!", "squash!", "amend!", "merge ", "revert ", "wip:", "wip ",
+    ];
+    lower != "wip" && !SKIPPED_PREFIXES.iter().any(|p| lower.starts_with(p))
+}
+
+/// Make sure there is something staged, staging tracked changes when allowed.
+///
+/// `--all` stages modified and deleted tracked files up front, like
+/// `git commit -a`. When nothing is staged but the working tree has changes,
+/// they are staged automatically with `commit.auto_stage`, otherwise the user
+/// is asked (text mode without `--yes` only). Amending never prompts.
+///
+/// Returns `false` when there is still nothing to commit.
+pub(crate) fn ensure_staged_changes(
+    options: &CommitOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    reporter: &dyn Reporter,
+) -> Result<bool> {
+    if options.all {
+        repo.stage_tracked()?;
+    }
+    if options.amend || repo.has_staged_changes()? {
+        return Ok(true);
+    }
+    if repo.get_uncommitted_diff()?.trim().is_empty() {
+        return Ok(false);
+    }
+
+    let interactive = !options.yes && !options.format.is_json();
+    let stage = config.commit.auto_stage
+        || (interactive && reporter.confirm(&rust_i18n::t!("commit.stage_all_confirm"), true)?);
+    if !stage {
+        return Ok(false);
+    }
+
+    repo.stage_tracked()?;
+    reporter.success(&rust_i18n::t!("commit.staged_all"));
+    repo.has_staged_changes()
+}
+
+/// `--show-redacted`: prints the diff as it would be sent, without creating
+/// a provider or committing.
+fn show_redacted(
+    options: &CommitOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    reporter: &dyn Reporter,
+) -> Result<()> {
+    let colored = options.effective_colored(config);
+    if !ensure_staged_changes(options, config, repo, reporter)? {
+        reporter.error(&rust_i18n::t!("commit.no_staged_changes"));
+        return Err(GcopError::NoStagedChanges);
+    }
+    let diff = super::privacy::guard(get_diff(repo, options.amend)?, config, false, colored)?;
+    let (diff, _) = smart_truncate_diff(&diff, config.llm.max_diff_size);
+    super::privacy::print_preview(&rust_i18n::t!("privacy.preview_title"), &diff, colored);
+    Ok(())
+}
+
 /// Get diff based on commit mode.
 ///
 /// - Amend: HEAD commit diff, optionally combined with new staged changes.
 /// - Normal: staged diff (caller must check `has_staged_changes` before calling).
-fn get_diff(repo: &dyn GitOperations, amend: bool) -> Result<String> {
+pub(crate) fn get_diff(repo: &dyn GitOperations, amend: bool) -> Result<String> {
     if amend {
         let commit_diff = repo.get_commit_diff("HEAD")?;
         if repo.has_staged_changes()? {
@@ -708,6 +1591,7 @@ fn get_diff(repo: &dyn GitOperations, amend: bool) -> Result<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::SilentReporter;
     use pretty_assertions::assert_eq;
 
     // === format_message_header test ===
@@ -737,4 +1621,354 @@ mod tests {
         let header = format_edited_header();
         assert_eq!(header, "Updated commit message:");
     }
+
+    // === history examples test ===
+
+    fn commit_info(message: &str, parent_count: usize) -> CommitInfo {
+        CommitInfo {
+            hash: "abc123".to_string(),
+            parent_count,
+            author_name: "Test".to_string(),
+            author_email: "test@example.com".to_string(),
+            timestamp: chrono::Local::now(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_select_history_examples_filters_noise() {
+        let commits = vec![
+            commit_info("feat(cli): add --profile flag", 1),
+            commit_info("Merge branch 'main' into dev", 2),
+            commit_info("fixup! feat(cli): add --profile flag", 1),
+            commit_info("WIP", 1),
+            commit_info("Revert \"fix: typo\"", 1),
+            commit_info("", 1),
+            commit_info(&"x".repeat(200), 1),
+            commit_info("fix(git): handle detached HEAD", 1),
+            commit_info("feat(cli): add --profile flag", 1),
+        ];
+
+        let examples = select_history_examples(&commits, 10);
+        assert_eq!(
+            examples,
+            vec![
+                "feat(cli): add --profile flag".to_string(),
+                "fix(git): handle detached HEAD".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_select_history_examples_respects_limit() {
+        let commits: Vec<CommitInfo> = (0..5)
+            .map(|i| commit_info(&format!("chore: bump {}", i), 1))
+            .collect();
+
+        let examples = select_history_examples(&commits, 2);
+        assert_eq!(examples, vec!["chore: bump 0", "chore: bump 1"]);
+    }
+
+    #[test]
+    fn test_compute_history_examples_disabled_by_default() {
+        let repo = crate::git::MockGitOperations::new();
+        let config = AppConfig::default();
+        // No expectation on get_commit_history: calling it would panic
+        assert!(compute_history_examples(&repo, &config).is_empty());
+    }
+
+    #[test]
+    fn test_compute_history_scopes() {
+        let mut repo = crate::git::MockGitOperations::new();
+        repo.expect_get_commits_touching()
+            .withf(|paths, max_scan| paths == ["src/parser.rs"] && *max_scan == 500)
+            .returning(|_, _| {
+                Ok(vec![
+                    commit_info("fix(parser): handle EOF", 1),
+                    commit_info("feat(lexer): add tokens", 1),
+                    commit_info("feat(parser): add AST", 1),
+                ])
+            });
+
+        let scopes = compute_history_scopes(&repo, &["src/parser.rs".to_string()]);
+        assert_eq!(scopes, vec!["parser", "lexer"]);
+    }
+
+    #[test]
+    fn test_compute_history_scopes_error_is_non_fatal() {
+        let mut repo = crate::git::MockGitOperations::new();
+        repo.expect_get_commits_touching()
+            .returning(|_, _| Err(GcopError::GitCommand("boom".to_string())));
+        assert!(compute_history_scopes(&repo, &["a.rs".to_string()]).is_empty());
+    }
+
+    // === --message-file / --output test ===
+
+    #[test]
+    fn test_read_message_file_strips_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("MSG");
+        std::fs::write(&path, "fix: draft wording\n\n# Please enter the message\n").unwrap();
+        assert_eq!(read_message_file(&path).unwrap(), "fix: draft wording");
+
+        // 只有注释时报错
+        std::fs::write(&path, "# only comments\n").unwrap();
+        assert!(matches!(
+            read_message_file(&path),
+            Err(GcopError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_output_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.txt");
+        write_output(&path, "feat: add output", &SilentReporter).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "feat: add output\n"
+        );
+    }
+
+    // === ensure_staged_changes test ===
+
+    fn staging_options(all: bool) -> CommitOptions<'static> {
+        CommitOptions {
+            no_edit: true,
+            yes: true,
+            dry_run: false,
+            split: false,
+            amend: false,
+            fixup: None,
+            all,
+            no_sign: false,
+            signoff: false,
+            co_authors: &[],
+            offline: false,
+            show_redacted: false,
+            output: None,
+            message_file: None,
+            verify: None,
+            review: false,
+            format: crate::commands::format::OutputFormat::Text,
+            feedback: &[],
+            verbose: false,
+            provider_override: None,
+            model_override: None,
+        }
+    }
+
+    #[test]
+    fn test_ensure_staged_changes_all_stages_first() {
+        let mut repo = crate::git::MockGitOperations::new();
+        repo.expect_stage_tracked().times(1).returning(|| Ok(()));
+        repo.expect_has_staged_changes().returning(|| Ok(true));
+        let ok = ensure_staged_changes(
+            &staging_options(true),
+            &AppConfig::default(),
+            &repo,
+            &SilentReporter,
+        );
+        assert!(ok.unwrap());
+    }
+
+    #[test]
+    fn test_ensure_staged_changes_auto_stage() {
+        let mut config = AppConfig::default();
+        config.commit.auto_stage = true;
+        let mut repo = crate::git::MockGitOperations::new();
+        let staged = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let flag = staged.clone();
+        repo.expect_has_staged_changes()
+            .returning(move || Ok(flag.load(std::sync::atomic::Ordering::SeqCst)));
+        repo.expect_get_uncommitted_diff()
+            .returning(|| Ok("diff --git a/a.rs b/a.rs".to_string()));
+        let flag = staged.clone();
+        repo.expect_stage_tracked().times(1).returning(move || {
+            flag.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        });
+        assert!(
+            ensure_staged_changes(&staging_options(false), &config, &repo, &SilentReporter)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ensure_staged_changes_without_consent() {
+        // --yes 且未开启 auto_stage：不询问也不暂存
+        let mut repo = crate::git::MockGitOperations::new();
+        repo.expect_has_staged_changes().returning(|| Ok(false));
+        repo.expect_get_uncommitted_diff()
+            .returning(|| Ok("diff --git a/a.rs b/a.rs".to_string()));
+        repo.expect_stage_tracked().never();
+        let ok = ensure_staged_changes(
+            &staging_options(false),
+            &AppConfig::default(),
+            &repo,
+            &SilentReporter,
+        );
+        assert!(!ok.unwrap());
+
+        // 工作区没有更改
+        let mut repo = crate::git::MockGitOperations::new();
+        repo.expect_has_staged_changes().returning(|| Ok(false));
+        repo.expect_get_uncommitted_diff()
+            .returning(|| Ok(String::new()));
+        repo.expect_stage_tracked().never();
+        let mut config = AppConfig::default();
+        config.commit.auto_stage = true;
+        assert!(
+            !ensure_staged_changes(&staging_options(false), &config, &repo, &SilentReporter)
+                .unwrap()
+        );
+    }
+
+    /// 依次返回预设回复并记录 prompt 的 provider
+    struct ScriptedProvider {
+        replies: std::sync::Mutex<Vec<&'static str>>,
+        prompts: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl LLMProvider for ScriptedProvider {
+        async fn send_prompt(
+            &self,
+            _system: &str,
+            user: &str,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<String> {
+            self.prompts.lock().unwrap().push(user.to_string());
+            let mut replies = self.replies.lock().unwrap();
+            Ok(if replies.len() > 1 {
+                replies.remove(0)
+            } else {
+                replies[0]
+            }
+            .to_string())
+        }
+
+        async fn review_code(
+            &self,
+            _diff: &str,
+            _review_type: crate::llm::ReviewType,
+            _custom_prompt: Option<&str>,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<crate::llm::ReviewResult> {
+            unreachable!()
+        }
+
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        async fn validate(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn scripted_provider(replies: Vec<&'static str>) -> Arc<ScriptedProvider> {
+        Arc::new(ScriptedProvider {
+            replies: std::sync::Mutex::new(replies),
+            prompts: Default::default(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_repair_message_fixes_violations() {
+        let mut config = AppConfig::default();
+        config.commit.constraints = Some(crate::config::CommitConstraints::default());
+        let scripted = scripted_provider(vec!["feat: add login"]);
+        let provider: Arc<dyn LLMProvider> = scripted.clone();
+
+        let repaired = repair_message(
+            &provider,
+            "diff",
+            &CommitContext::default(),
+            &config,
+            "feat: added login".to_string(),
+            None,
+        )
+        .await;
+        assert_eq!(repaired, "feat: add login");
+        let prompts = scripted.prompts.lock().unwrap().clone();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("feat: added login"));
+        assert!(prompts[0].contains("imperative verb"));
+
+        // 已合规的消息不会触发修复
+        let clean = repair_message(
+            &provider,
+            "diff",
+            &CommitContext::default(),
+            &config,
+            "fix: handle errors".to_string(),
+            None,
+        )
+        .await;
+        assert_eq!(clean, "fix: handle errors");
+        assert_eq!(scripted.prompts.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_repair_message_is_bounded() {
+        let mut config = AppConfig::default();
+        config.commit.constraints = Some(crate::config::CommitConstraints {
+            max_repairs: 3,
+            ..Default::default()
+        });
+        let scripted = scripted_provider(vec!["feat: adds login"]);
+        let provider: Arc<dyn LLMProvider> = scripted.clone();
+
+        let message = repair_message(
+            &provider,
+            "diff",
+            &CommitContext::default(),
+            &config,
+            "feat: added login".to_string(),
+            None,
+        )
+        .await;
+        assert_eq!(message, "feat: adds login");
+        assert_eq!(scripted.prompts.lock().unwrap().len(), 3);
+
+        // 未配置 [commit.constraints] 时不做任何请求
+        let unconfigured = scripted_provider(vec!["unused"]);
+        let provider: Arc<dyn LLMProvider> = unconfigured.clone();
+        let message = repair_message(
+            &provider,
+            "diff",
+            &CommitContext::default(),
+            &AppConfig::default(),
+            "feat: added login.".to_string(),
+            None,
+        )
+        .await;
+        assert_eq!(message, "feat: added login.");
+        assert!(unconfigured.prompts.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_convert_gitmoji_by_style() {
+        let mut convention = CommitConvention {
+            style: ConventionStyle::Gitmoji,
+            gitmoji_mode: GitmojiMode::Convert,
+            ..Default::default()
+        };
+        let message = "feat(ui): add dark mode\n\nBody.".to_string();
+        assert_eq!(
+            convert_gitmoji(message.clone(), Some(&convention)),
+            ":sparkles: ui: add dark mode\n\nBody."
+        );
+
+        // prompt 模式信任模型输出
+        convention.gitmoji_mode = GitmojiMode::Prompt;
+        assert_eq!(convert_gitmoji(message.clone(), Some(&convention)), message);
+
+        // conventional 风格把 gitmoji 标题转回来
+        assert_eq!(
+            convert_gitmoji(":bug: fix crash".to_string(), None),
+            "fix: fix crash"
+        );
+    }
 }
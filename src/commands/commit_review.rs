@@ -0,0 +1,260 @@
+//! Commit-time quick review (`commit --review`).
+//!
+//! Before a message is generated, the staged diff is reviewed with a prompt
+//! that only asks for critical problems. When some are found, they are shown
+//! and the user chooses to continue anyway, fix them and review again, or
+//! abort. The gate is a seatbelt: a failed review request only warns.
+
+use std::sync::Arc;
+
+use super::options::CommitOptions;
+use super::smart_truncate_diff;
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::git::GitOperations;
+use crate::llm::metrics;
+use crate::llm::prompt::commit_review_prompt;
+use crate::llm::{
+    IssueSeverity, LLMProvider, ProgressReporter, ReviewIssue, ReviewResult, ReviewType, ledger,
+};
+use crate::ui;
+
+/// Reviews the staged `diff` and returns the diff to generate the message
+/// from: the same one, or the re-staged one after the user chose to fix.
+///
+/// Without a terminal, critical issues fail with
+/// [`GcopError::ReviewGateFailed`]; aborting returns
+/// [`GcopError::UserCancelled`].
+pub(crate) async fn review_staged(
+    options: &CommitOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    mut diff: String,
+    colored: bool,
+) -> Result<String> {
+    loop {
+        let Some(result) = quick_review(config, provider, &diff, colored).await? else {
+            return Ok(diff);
+        };
+        let critical = critical_issues(&result);
+        if critical.is_empty() {
+            ui::success(&rust_i18n::t!("commit.review.passed"), colored);
+            return Ok(diff);
+        }
+
+        ui::warning(
+            &rust_i18n::t!("commit.review.found", count = critical.len()),
+            colored,
+        );
+        if !ui::is_quiet() {
+            println!();
+            for (i, issue) in critical.iter().enumerate() {
+                super::review::print_issue(i + 1, issue, colored);
+            }
+        }
+        if !ui::is_interactive() {
+            return Err(GcopError::ReviewGateFailed {
+                highest: IssueSeverity::Critical,
+                threshold: IssueSeverity::Critical,
+                count: critical.len(),
+            });
+        }
+
+        match ui::review_gate_menu(colored)? {
+            ui::ReviewGateAction::Continue => return Ok(diff),
+            ui::ReviewGateAction::Fix => {
+                println!(
+                    "{}",
+                    ui::info(&rust_i18n::t!("commit.review.fix_hint"), colored)
+                );
+                if !ui::confirm(&rust_i18n::t!("commit.review.fix_ready"), true)? {
+                    ui::warning(&rust_i18n::t!("commit.cancelled"), colored);
+                    return Err(GcopError::UserCancelled);
+                }
+                diff = super::commit::get_diff(repo, options.amend)?;
+                if diff.trim().is_empty() {
+                    ui::error(&rust_i18n::t!("commit.no_staged_changes"), colored);
+                    return Err(GcopError::NoStagedChanges);
+                }
+            }
+            ui::ReviewGateAction::Abort => {
+                ui::warning(&rust_i18n::t!("commit.cancelled"), colored);
+                return Err(GcopError::UserCancelled);
+            }
+        }
+    }
+}
+
+/// Sends the redacted, truncated diff for review; `None` when the request
+/// failed (the commit goes on without the gate).
+async fn quick_review(
+    config: &AppConfig,
+    provider: &Arc<dyn LLMProvider>,
+    diff: &str,
+    colored: bool,
+) -> Result<Option<ReviewResult>> {
+    // Warnings are shown once by the message generation that follows
+    let diff = super::privacy::guard(diff.to_string(), config, true, colored)?;
+    let (diff, _) = smart_truncate_diff(&diff, config.llm.max_diff_size);
+
+    let spinner = ui::Spinner::new(&rust_i18n::t!("spinner.commit_reviewing"), colored);
+    metrics::start();
+    let result = provider
+        .review_code(
+            &diff,
+            ReviewType::UncommittedChanges,
+            Some(commit_review_prompt()),
+            Some(&spinner as &dyn ProgressReporter),
+        )
+        .await;
+    spinner.finish_and_clear();
+    ledger::record("review", &metrics::finish(), config);
+
+    match result {
+        Ok(result) => Ok(Some(result)),
+        Err(GcopError::Interrupted) => Err(GcopError::Interrupted),
+        Err(e) => {
+            ui::warning(
+                &rust_i18n::t!("commit.review.failed", error = e.to_string()),
+                colored,
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Issues of `result` that stop the commit.
+fn critical_issues(result: &ReviewResult) -> Vec<&ReviewIssue> {
+    result
+        .issues
+        .iter()
+        .filter(|issue| matches!(issue.severity, IssueSeverity::Critical))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::MockGitOperations;
+
+    /// review_code 返回固定结果的 provider
+    struct FixedReview(Option<ReviewResult>);
+
+    #[async_trait::async_trait]
+    impl LLMProvider for FixedReview {
+        async fn send_prompt(
+            &self,
+            _system: &str,
+            _user: &str,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<String> {
+            unreachable!()
+        }
+
+        async fn review_code(
+            &self,
+            _diff: &str,
+            _review_type: ReviewType,
+            custom_prompt: Option<&str>,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<ReviewResult> {
+            assert_eq!(custom_prompt, Some(commit_review_prompt()));
+            self.0.clone().ok_or_else(|| GcopError::LlmApi {
+                status: 503,
+                message: "Service Unavailable".to_string(),
+            })
+        }
+
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn validate(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn review(severities: &[IssueSeverity]) -> Option<ReviewResult> {
+        Some(ReviewResult {
+            summary: "quick".to_string(),
+            issues: severities
+                .iter()
+                .map(|&severity| ReviewIssue {
+                    severity,
+                    description: "problem".to_string(),
+                    file: None,
+                    line: None,
+                    suggested_patch: None,
+                    category: None,
+                })
+                .collect(),
+            suggestions: Vec::new(),
+        })
+    }
+
+    fn options() -> CommitOptions<'static> {
+        CommitOptions {
+            no_edit: false,
+            yes: false,
+            dry_run: false,
+            split: false,
+            amend: false,
+            fixup: None,
+            all: false,
+            no_sign: false,
+            signoff: false,
+            co_authors: &[],
+            offline: false,
+            show_redacted: false,
+            output: None,
+            message_file: None,
+            verify: None,
+            review: true,
+            format: crate::commands::format::OutputFormat::Text,
+            feedback: &[],
+            verbose: false,
+            provider_override: None,
+            model_override: None,
+        }
+    }
+
+    async fn gate(provider: FixedReview) -> Result<String> {
+        let provider: Arc<dyn LLMProvider> = Arc::new(provider);
+        let repo = MockGitOperations::new();
+        review_staged(
+            &options(),
+            &AppConfig::default(),
+            &repo,
+            &provider,
+            "diff --git a/a.rs b/a.rs\n+x".to_string(),
+            false,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_review_gate_passes_without_critical_issues() {
+        let diff = gate(FixedReview(review(&[IssueSeverity::Warning])))
+            .await
+            .unwrap();
+        assert!(diff.contains("+x"));
+        // 审查请求失败时不阻止提交
+        assert!(gate(FixedReview(None)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_review_gate_fails_without_terminal() {
+        // 测试环境没有终端，无法询问用户
+        let result = gate(FixedReview(review(&[
+            IssueSeverity::Critical,
+            IssueSeverity::Info,
+            IssueSeverity::Critical,
+        ])))
+        .await;
+        assert!(matches!(
+            result,
+            Err(GcopError::ReviewGateFailed { count: 2, .. })
+        ));
+    }
+}
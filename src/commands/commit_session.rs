@@ -0,0 +1,209 @@
+//! Resumable commit sessions.
+//!
+//! The interactive commit flow records its feedback history and last
+//! generated message in `.git/gcop-state.json`. When the user quits (or the
+//! flow fails), the next `commit` for the same staged diff offers to pick up
+//! where it left off instead of starting from scratch. The file is removed
+//! once a commit is created; `gcop-rs undo` writes one holding the undone
+//! commit's message.
+//!
+//! Persistence is best-effort: read and write failures are logged and never
+//! interrupt the commit flow.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::commit_state_machine::CommitState;
+use super::review_baseline::fnv1a64;
+
+/// State file name inside the `.git` directory.
+pub(crate) const STATE_FILE: &str = "gcop-state.json";
+
+/// Current state file format version.
+const STATE_VERSION: u32 = 1;
+
+/// Persisted progress of an interactive commit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CommitSession {
+    /// File format version.
+    pub version: u32,
+    /// Hash of the staged diff the session belongs to.
+    pub diff_hash: String,
+    /// Accumulated retry feedback, oldest first.
+    #[serde(default)]
+    pub feedbacks: Vec<String>,
+    /// Last generated (or edited) message, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Retry attempt of `message` (0 = first generation).
+    #[serde(default)]
+    pub attempt: usize,
+}
+
+impl CommitSession {
+    /// Starts an empty session for the given staged diff.
+    pub fn new(diff: &str) -> Self {
+        Self {
+            version: STATE_VERSION,
+            diff_hash: diff_hash(diff),
+            feedbacks: Vec::new(),
+            message: None,
+            attempt: 0,
+        }
+    }
+
+    /// Loads the saved session for the staged diff identified by `diff_hash`
+    /// (see [`CommitSession::new`]).
+    ///
+    /// Returns `None` when there is no state file, it cannot be parsed, it
+    /// belongs to a different staged diff, or it holds nothing to resume.
+    pub fn load(path: &Path, diff_hash: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let session: Self = match serde_json::from_str(&content) {
+            Ok(session) => session,
+            Err(e) => {
+                tracing::debug!("Ignoring invalid {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        (session.version == STATE_VERSION
+            && session.diff_hash == diff_hash
+            && session.is_resumable())
+        .then_some(session)
+    }
+
+    /// Whether the session carries feedback or a message worth resuming.
+    pub fn is_resumable(&self) -> bool {
+        !self.feedbacks.is_empty() || self.message.is_some()
+    }
+
+    /// Updates the session from the current state-machine state.
+    ///
+    /// `Generating` keeps the previous message (it is still the latest one
+    /// the user has seen); terminal states leave the session unchanged.
+    pub fn record(&mut self, state: &CommitState) {
+        match state {
+            CommitState::Generating { feedbacks, .. } => {
+                self.feedbacks = feedbacks.clone();
+            }
+            CommitState::WaitingForAction {
+                message,
+                attempt,
+                feedbacks,
+            } => {
+                self.feedbacks = feedbacks.clone();
+                self.message = Some(message.clone());
+                self.attempt = *attempt;
+            }
+            CommitState::Accepted { .. } | CommitState::Cancelled { .. } => {}
+        }
+    }
+
+    /// Writes the session as pretty-printed JSON (errors are logged only).
+    pub fn save(&self, path: &Path) {
+        let result = serde_json::to_string_pretty(self)
+            .map_err(std::io::Error::other)
+            .and_then(|content| std::fs::write(path, content + "\n"));
+        if let Err(e) = result {
+            tracing::debug!("Failed to save {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// State file path for the repository whose git directory is `git_dir`.
+pub(crate) fn state_path(git_dir: &Path) -> PathBuf {
+    git_dir.join(STATE_FILE)
+}
+
+/// Removes the state file, ignoring a missing file.
+pub(crate) fn clear(path: &Path) {
+    if let Err(e) = std::fs::remove_file(path)
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::debug!("Failed to remove {}: {}", path.display(), e);
+    }
+}
+
+/// Identifies a staged diff (16 hex chars).
+fn diff_hash(diff: &str) -> String {
+    format!("{:016x}", fnv1a64(diff.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const DIFF: &str = "diff --git a/a.rs b/a.rs\n+fn a() {}\n";
+
+    fn waiting(message: &str, attempt: usize, feedbacks: &[&str]) -> CommitState {
+        CommitState::WaitingForAction {
+            message: message.to_string(),
+            attempt,
+            feedbacks: feedbacks.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = state_path(dir.path());
+
+        let mut session = CommitSession::new(DIFF);
+        session.record(&waiting("feat: add a", 1, &["use Chinese"]));
+        session.save(&path);
+
+        let loaded = CommitSession::load(&path, &diff_hash(DIFF)).unwrap();
+        assert_eq!(loaded, session);
+        assert_eq!(loaded.message.as_deref(), Some("feat: add a"));
+        assert_eq!(loaded.attempt, 1);
+    }
+
+    #[test]
+    fn test_load_rejects_other_diff_and_empty_session() {
+        let dir = TempDir::new().unwrap();
+        let path = state_path(dir.path());
+
+        // 空会话没有可恢复的内容
+        CommitSession::new(DIFF).save(&path);
+        assert!(CommitSession::load(&path, &diff_hash(DIFF)).is_none());
+
+        let mut session = CommitSession::new(DIFF);
+        session.record(&waiting("feat: add a", 0, &[]));
+        session.save(&path);
+        assert!(CommitSession::load(&path, &diff_hash("diff --git a/b.rs b/b.rs\n")).is_none());
+        assert!(CommitSession::load(&path, &diff_hash(DIFF)).is_some());
+    }
+
+    #[test]
+    fn test_load_ignores_invalid_file() {
+        let dir = TempDir::new().unwrap();
+        let path = state_path(dir.path());
+        std::fs::write(&path, "not json").unwrap();
+        assert!(CommitSession::load(&path, &diff_hash(DIFF)).is_none());
+    }
+
+    #[test]
+    fn test_record_generating_keeps_last_message() {
+        let mut session = CommitSession::new(DIFF);
+        session.record(&waiting("feat: add a", 0, &[]));
+        session.record(&CommitState::Generating {
+            attempt: 1,
+            feedbacks: vec!["be concise".to_string()],
+        });
+        assert_eq!(session.feedbacks, vec!["be concise"]);
+        assert_eq!(session.message.as_deref(), Some("feat: add a"));
+        assert_eq!(session.attempt, 0);
+    }
+
+    #[test]
+    fn test_clear_missing_file_is_noop() {
+        let dir = TempDir::new().unwrap();
+        let path = state_path(dir.path());
+        clear(&path);
+        std::fs::write(&path, "{}").unwrap();
+        clear(&path);
+        assert!(!path.exists());
+    }
+}
@@ -8,7 +8,8 @@
 //!     │                        │                           │
 //!     │ ├──> Generating (retry) └──> Execute commit
 //!     │                        └──> Cancelled
-//!     └──> MaxRetriesExceeded ──> Cancelled
+//!     ├──> MaxRetriesExceeded ──> Cancelled
+//!     └──> Interrupted (Ctrl+C) ──> Cancelled
 //! ```
 //!
 //! # Design
@@ -56,7 +57,7 @@ use crate::error::{GcopError, Result};
 /// - [`Generating`] - Generating commit message
 /// - [`WaitingForAction`] - Waiting for user action
 /// - [`Accepted`] - The user accepted the message
-/// - [`Cancelled`] - User canceled or maximum retries reached
+/// - [`Cancelled`] - User canceled, pressed Ctrl+C or maximum retries reached
 ///
 /// [`Generating`]: CommitState::Generating
 /// [`WaitingForAction`]: CommitState::WaitingForAction
@@ -106,7 +107,13 @@ pub enum CommitState {
     /// User cancels or maximum retries reached
     ///
     /// Termination status, no commit is performed.
-    Cancelled,
+    ///
+    /// # Fields
+    /// - `interrupted`: generation was aborted with Ctrl+C
+    Cancelled {
+        /// Whether the user pressed Ctrl+C during generation (exit code 130).
+        interrupted: bool,
+    },
 }
 
 /// User operations
@@ -162,9 +169,11 @@ pub enum UserAction {
 /// # Variants
 /// - [`Success`] - generated successfully
 /// - [`MaxRetriesExceeded`] - Maximum number of retries reached
+/// - [`Interrupted`] - The user pressed Ctrl+C while generating
 ///
 /// [`Success`]: GenerationResult::Success
 /// [`MaxRetriesExceeded`]: GenerationResult::MaxRetriesExceeded
+/// [`Interrupted`]: GenerationResult::Interrupted
 #[derive(Debug, Clone)]
 pub enum GenerationResult {
     /// Generated successfully
@@ -174,6 +183,8 @@ pub enum GenerationResult {
     Success(String),
     /// Maximum number of retries reached
     MaxRetriesExceeded,
+    /// Generation was aborted with Ctrl+C
+    Interrupted,
 }
 
 impl CommitState {
@@ -213,6 +224,7 @@ impl CommitState {
     /// - `Success` + `auto_accept=false` → `WaitingForAction`
     /// - `Success` + `auto_accept=true` → `Accepted`
     /// - `MaxRetriesExceeded` → `Err(MaxRetriesExceeded)`
+    /// - `Interrupted` → `Cancelled { interrupted: true }`
     ///
     /// # Errors
     /// - Calling this method in a non-`Generating` state will return [`GcopError::InvalidInput`]
@@ -234,6 +246,7 @@ impl CommitState {
         match self {
             CommitState::Generating { attempt, feedbacks } => match result {
                 GenerationResult::MaxRetriesExceeded => Err(GcopError::MaxRetriesExceeded(attempt)),
+                GenerationResult::Interrupted => Ok(CommitState::Cancelled { interrupted: true }),
                 GenerationResult::Success(message) => {
                     if auto_accept {
                         Ok(CommitState::Accepted { message })
@@ -253,6 +266,19 @@ impl CommitState {
         }
     }
 
+    /// Process a failed interruptible generation (see [`crate::ui::interruptible`])
+    ///
+    /// [`GcopError::Interrupted`] (Ctrl+C) becomes
+    /// [`GenerationResult::Interrupted`]; any other error is returned as is.
+    pub fn handle_generation_error(self, error: GcopError, auto_accept: bool) -> Result<Self> {
+        match error {
+            GcopError::Interrupted => {
+                self.handle_generation(GenerationResult::Interrupted, auto_accept)
+            }
+            other => Err(other),
+        }
+    }
+
     /// Handle user actions (pure function)
     ///
     /// Transition the [`CommitState::WaitingForAction`] state to the next state.
@@ -325,11 +351,11 @@ impl CommitState {
                     }
                 }
 
-                UserAction::Quit => CommitState::Cancelled,
+                UserAction::Quit => CommitState::Cancelled { interrupted: false },
             },
             _ => {
                 tracing::error!("handle_action called in wrong state: {:?}", self);
-                CommitState::Cancelled
+                CommitState::Cancelled { interrupted: false }
             }
         }
     }
@@ -421,6 +447,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generating_interrupted() {
+        let state = CommitState::Generating {
+            attempt: 1,
+            feedbacks: vec![],
+        };
+        // Ctrl+C 即使在 --yes 下也不提交
+        let result = state
+            .handle_generation(GenerationResult::Interrupted, true)
+            .unwrap();
+        assert_eq!(result, CommitState::Cancelled { interrupted: true });
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_c_during_generation_cancels() {
+        let generated =
+            crate::ui::interrupt::interruptible_with(std::future::pending::<String>(), async {})
+                .await;
+        let state = CommitState::Generating {
+            attempt: 0,
+            feedbacks: vec![],
+        };
+        let result = state
+            .handle_generation_error(generated.unwrap_err(), true)
+            .unwrap();
+        assert_eq!(result, CommitState::Cancelled { interrupted: true });
+    }
+
+    #[test]
+    fn test_generation_error_other_than_interrupt_is_returned() {
+        let state = CommitState::Generating {
+            attempt: 0,
+            feedbacks: vec![],
+        };
+        let err = state
+            .handle_generation_error(GcopError::UserCancelled, false)
+            .unwrap_err();
+        assert!(matches!(err, GcopError::UserCancelled));
+    }
+
     #[test]
     fn test_generating_preserves_feedbacks() {
         let feedbacks = vec!["use Chinese".to_string(), "be concise".to_string()];
@@ -558,6 +624,6 @@ mod tests {
         };
 
         let result = state.handle_action(UserAction::Quit);
-        assert!(matches!(result, CommitState::Cancelled));
+        assert_eq!(result, CommitState::Cancelled { interrupted: false });
     }
 }
@@ -0,0 +1,270 @@
+//! Shell completions (`gcop-rs completions <shell>`).
+//!
+//! The generated script is a thin shim: on every `<Tab>` the shell calls
+//! `gcop-rs` again with `COMPLETE=<shell>` set, and clap's completion engine
+//! answers from the live command definition. Values therefore come from the
+//! configuration in effect when completing, not from when the script was
+//! generated. On top of subcommands and flags, this module offers:
+//!
+//! - configured provider names for `--provider`
+//! - profile names for `--profile`
+//! - dotted config keys for `config get/set/unset`
+
+use std::io::Write;
+
+use clap::Command;
+use clap_complete::env::Shells;
+use clap_complete::{ArgValueCandidates, CompletionCandidate, Shell};
+use serde_json::Value;
+
+use crate::config::{AppConfig, json_schema};
+use crate::error::{GcopError, Result};
+
+/// Environment variable that switches `gcop-rs` into completion mode.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Binary name the completion script registers for.
+const BIN_NAME: &str = "gcop-rs";
+
+/// Prints the registration script for `shell` to stdout.
+pub fn run(shell: Shell) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells.completer(&shell.to_string()).ok_or_else(|| {
+        GcopError::InvalidInput(
+            rust_i18n::t!("completions.unsupported_shell", shell = shell.to_string()).to_string(),
+        )
+    })?;
+    // Point the script at this executable so completion works even when
+    // `gcop-rs` is not on `PATH` (for example a `cargo run` build).
+    let executable = std::env::current_exe()
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| BIN_NAME.to_string());
+
+    let mut stdout = std::io::stdout().lock();
+    completer.write_registration(COMPLETE_VAR, BIN_NAME, BIN_NAME, &executable, &mut stdout)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// A completion value and its optional help text.
+pub type Candidate = (String, Option<String>);
+
+/// Attaches config-driven value candidates to the CLI definition.
+pub fn with_dynamic_values(cmd: Command, config: &AppConfig) -> Command {
+    let providers = provider_names(config);
+    let profiles = sorted(config.profiles.keys())
+        .into_iter()
+        .map(|name| (name, None))
+        .collect();
+    let keys = config_keys(config);
+
+    // `mut_arg` re-appends the argument, which would move the positional
+    // `key` behind `value`; `mut_args` edits in place and keeps the order.
+    let key_arg = |sub: Command| {
+        sub.mut_args(|arg| match arg.get_id().as_str() {
+            "key" => arg.add(candidates(keys.clone())),
+            _ => arg,
+        })
+    };
+
+    cmd.mut_arg("provider", |arg| arg.add(candidates(providers)))
+        .mut_arg("profile", |arg| arg.add(candidates(profiles)))
+        .mut_subcommand("config", |config_cmd| {
+            config_cmd
+                .mut_subcommand("get", key_arg)
+                .mut_subcommand("set", key_arg)
+                .mut_subcommand("unset", key_arg)
+        })
+}
+
+fn candidates(values: Vec<Candidate>) -> ArgValueCandidates {
+    ArgValueCandidates::new(move || {
+        values
+            .iter()
+            .map(|(value, help)| CompletionCandidate::new(value).help(help.clone().map(Into::into)))
+            .collect()
+    })
+}
+
+/// Configured provider names, with the model as help text.
+pub fn provider_names(config: &AppConfig) -> Vec<Candidate> {
+    sorted(config.llm.providers.keys())
+        .into_iter()
+        .map(|name| {
+            let model = config.llm.providers[&name].model.clone();
+            (name, Some(model))
+        })
+        .collect()
+}
+
+/// Every settable dotted key, with the first line of its description.
+///
+/// Keys are derived from the config JSON Schema. Map sections
+/// (`llm.providers`, `profiles`, `aliases`) expand to the entries present in `config`,
+/// so `llm.providers.<name>.model` is offered for each configured provider.
+pub fn config_keys(config: &AppConfig) -> Vec<Candidate> {
+    let schema = json_schema();
+    let mut keys = Vec::new();
+    collect_keys(&schema, &schema, "", config, &mut keys);
+    keys.sort();
+    keys
+}
+
+fn collect_keys(
+    root: &Value,
+    node: &Value,
+    prefix: &str,
+    config: &AppConfig,
+    out: &mut Vec<Candidate>,
+) {
+    let schema = resolve(root, node);
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (name, property) in properties {
+            collect_keys(root, property, &join(prefix, name), config, out);
+        }
+    } else if let Some(entry) = schema.get("additionalProperties").filter(|v| v.is_object()) {
+        for name in map_entries(prefix, config) {
+            collect_keys(root, entry, &join(prefix, &name), config, out);
+        }
+    } else if !prefix.is_empty() {
+        let help = node
+            .get("description")
+            .and_then(Value::as_str)
+            .and_then(|text| text.lines().next())
+            .map(str::to_string);
+        out.push((prefix.to_string(), help));
+    }
+}
+
+/// Follows `$ref` and picks the object variant of `Option<Struct>` unions.
+fn resolve<'a>(root: &'a Value, node: &'a Value) -> &'a Value {
+    if let Some(name) = node
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix("#/$defs/"))
+        && let Some(target) = root.get("$defs").and_then(|defs| defs.get(name))
+    {
+        return resolve(root, target);
+    }
+    if let Some(variants) = node.get("anyOf").and_then(Value::as_array) {
+        let object = variants
+            .iter()
+            .map(|variant| resolve(root, variant))
+            .find(|variant| variant.get("properties").is_some());
+        if let Some(object) = object {
+            return object;
+        }
+    }
+    node
+}
+
+/// Names of the user-defined entries of a map section.
+fn map_entries(prefix: &str, config: &AppConfig) -> Vec<String> {
+    match prefix {
+        "llm.providers" => sorted(config.llm.providers.keys()),
+        "profiles" => sorted(config.profiles.keys()),
+        "aliases" => sorted(config.aliases.keys()),
+        _ => Vec::new(),
+    }
+}
+
+fn sorted<'a>(names: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut names: Vec<String> = names.cloned().collect();
+    names.sort();
+    names
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}.{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use crate::llm::provider::test_utils::test_provider_config;
+    use clap::CommandFactory;
+
+    fn config() -> AppConfig {
+        let mut config = AppConfig::default();
+        config.llm.providers.insert(
+            "work".to_string(),
+            test_provider_config(String::new(), None, "gpt-4o".to_string()),
+        );
+        config.llm.providers.insert(
+            "local".to_string(),
+            test_provider_config(String::new(), None, "llama3".to_string()),
+        );
+        config
+    }
+
+    fn complete(args: &[&str]) -> Vec<String> {
+        let mut cmd = with_dynamic_values(Cli::command(), &config());
+        let args: Vec<std::ffi::OsString> = args.iter().map(Into::into).collect();
+        let index = args.len() - 1;
+        clap_complete::engine::complete(&mut cmd, args, index, None)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.get_value().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_config_keys_cover_sections_and_providers() {
+        let keys: Vec<String> = config_keys(&config()).into_iter().map(|(k, _)| k).collect();
+        for key in [
+            "llm.default_provider",
+            "ui.streaming",
+            "commit.convention.style",
+            "llm.providers.work.model",
+            "llm.providers.local.api_key",
+        ] {
+            assert!(keys.contains(&key.to_string()), "missing {key}");
+        }
+        // 表本身不是可设置的键
+        assert!(!keys.contains(&"llm.providers".to_string()));
+        assert!(!keys.contains(&"ui".to_string()));
+    }
+
+    #[test]
+    fn test_config_keys_have_help() {
+        let keys = config_keys(&config());
+        let (_, help) = keys.iter().find(|(k, _)| k == "ui.streaming").unwrap();
+        assert!(help.is_some());
+    }
+
+    #[test]
+    fn test_provider_completion_offers_configured_names() {
+        let values = complete(&["gcop-rs", "commit", "--provider", ""]);
+        assert_eq!(values, vec!["local", "work"]);
+        let values = complete(&["gcop-rs", "commit", "--provider", "w"]);
+        assert_eq!(values, vec!["work"]);
+    }
+
+    #[test]
+    fn test_config_set_completes_keys() {
+        let values = complete(&["gcop-rs", "config", "set", "llm.providers.w"]);
+        assert!(
+            values.contains(&"llm.providers.work.model".to_string()),
+            "{values:?}"
+        );
+        assert!(values.iter().all(|v| v.starts_with("llm.providers.work.")));
+    }
+
+    #[test]
+    fn test_run_supports_all_shells() {
+        for shell in [
+            Shell::Bash,
+            Shell::Elvish,
+            Shell::Fish,
+            Shell::PowerShell,
+            Shell::Zsh,
+        ] {
+            assert!(Shells::builtins().completer(&shell.to_string()).is_some());
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use crate::config::{self, load_config};
+use std::path::{Path, PathBuf};
+
+use super::json::JsonOutput;
+use crate::config::{
+    self, AppConfig, CommandLlmConfig, ConfigDiagnostic, DiagnosticLevel, EnvVarGroup,
+    EnvVarStatus, check_config_content, key_path, load_config,
+};
 use crate::error::{GcopError, Result};
 use crate::llm::provider::create_provider;
 use crate::ui;
@@ -11,15 +17,91 @@ enum EditAction {
     Ignore, // Ignore errors and force save
 }
 
-/// Runs the `config` command with either edit or validate behavior.
-pub async fn run(action: Option<crate::cli::ConfigAction>, colored: bool) -> Result<()> {
+/// Runs the `config` command (edit, validate, or key get/set/unset).
+pub async fn run(action: Option<crate::cli::ConfigAction>, config: &AppConfig) -> Result<()> {
+    let colored = config.ui.colored;
     // Default behavior: call edit
     let action = action.unwrap_or(crate::cli::ConfigAction::Edit);
 
     match action {
         crate::cli::ConfigAction::Edit => edit(colored),
         crate::cli::ConfigAction::Validate => validate(colored).await,
+        crate::cli::ConfigAction::Schema => schema(),
+        crate::cli::ConfigAction::Get { key } => get(config, &key),
+        crate::cli::ConfigAction::Set {
+            key,
+            value,
+            project,
+        } => set(&key, &value, project, colored),
+        crate::cli::ConfigAction::Unset { key, project } => unset(&key, project, colored),
+        crate::cli::ConfigAction::Env { json } => env(json, colored),
+        crate::cli::ConfigAction::Doctor { online } => super::doctor::run(online, colored).await,
+    }
+}
+
+/// Print the environment variables gcop-rs reads, grouped by family
+fn env(json: bool, colored: bool) -> Result<()> {
+    let status = config::env_status();
+    if json {
+        let output = JsonOutput {
+            success: true,
+            data: Some(status),
+            error: None,
+            meta: None,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    print!("{}", render_env(&status, colored));
+    Ok(())
+}
+
+/// Text table of [`config::env_status`], one section per family
+fn render_env(status: &[EnvVarStatus], colored: bool) -> String {
+    use std::fmt::Write;
+
+    let groups = [
+        (EnvVarGroup::Override, "config.env_group_override"),
+        (EnvVarGroup::Ci, "config.env_group_ci"),
+        (EnvVarGroup::Gcop, "config.env_group_gcop"),
+        (EnvVarGroup::Native, "config.env_group_native"),
+        (EnvVarGroup::System, "config.env_group_system"),
+    ];
+    let width = status.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    let unset = rust_i18n::t!("config.env_unset").to_string();
+
+    let mut out = String::new();
+    for (group, title) in groups {
+        let title = rust_i18n::t!(title).to_string();
+        let _ = writeln!(
+            out,
+            "{}",
+            if colored {
+                title.bold().to_string()
+            } else {
+                title
+            }
+        );
+        let entries: Vec<_> = status.iter().filter(|s| s.group == group).collect();
+        if entries.is_empty() {
+            let _ = writeln!(out, "  {}", rust_i18n::t!("config.env_none_set"));
+        }
+        for entry in entries {
+            let value = match &entry.value {
+                Some(value) if colored => value.green().to_string(),
+                Some(value) => value.clone(),
+                None if colored => unset.dimmed().to_string(),
+                None => unset.clone(),
+            };
+            let _ = writeln!(
+                out,
+                "  {:<width$}  {}  -> {}",
+                entry.name, value, entry.target
+            );
+        }
+        out.push('\n');
     }
+    out
 }
 
 /// Open the editor to edit the configuration file (with verification)
@@ -28,7 +110,7 @@ fn edit(colored: bool) -> Result<()> {
         GcopError::Config(rust_i18n::t!("config.failed_determine_dir").to_string())
     })?;
 
-    let config_file = config_dir.join("config.toml");
+    let config_file = config::user_config_path().unwrap_or_else(|| config_dir.join("config.toml"));
 
     // If the configuration file does not exist, prompt to run init
     if !config_file.exists() {
@@ -45,8 +127,8 @@ fn edit(colored: bool) -> Result<()> {
         ));
     }
 
-    // Initial reading of configuration content
-    let mut content = std::fs::read_to_string(&config_file)?;
+    // Initial reading of configuration content (decrypted for `config.toml.enc`)
+    let mut content = config::read_config_file(&config_file)?;
 
     // Edit-Verify Loop
     loop {
@@ -58,30 +140,33 @@ fn edit(colored: bool) -> Result<()> {
             )
         );
 
-        // Open the editor using the edit crate (automatic fallback: $VISUAL > $EDITOR > platform preset list)
-        let edited = edit::edit(&content)?;
-
-        // Verify configuration (deserialized through config crate, path consistent with load_config)
-        let validation: std::result::Result<crate::config::AppConfig, _> =
-            ::config::Config::builder()
-                .add_source(::config::File::from_str(
-                    &edited,
-                    ::config::FileFormat::Toml,
-                ))
-                .build()
-                .and_then(|c| c.try_deserialize());
-        match validation {
+        // $VISUAL > $EDITOR > platform preset list; the temporary copy is private
+        // and removed on every exit path, since it may hold decrypted secrets
+        let edited = ui::edit_private_text(&content, ".toml")?;
+
+        match check_content(&edited) {
             Ok(_) => {
-                // Verification successful, write to file
-                std::fs::write(&config_file, &edited)?;
+                // Verification successful, write to file (re-encrypted for `config.toml.enc`)
+                config::write_config_file(&config_file, &edited)?;
                 ui::success(&rust_i18n::t!("config.file_updated"), colored);
+                print_diagnostics(
+                    &config_file,
+                    &check_config_content(&edited),
+                    DiagnosticLevel::Warning,
+                    colored,
+                );
                 return Ok(());
             }
             Err(e) => {
-                // Verification failed
+                // Verification failed; prefer the line-level diagnostic when available
+                let error = check_config_content(&edited)
+                    .into_iter()
+                    .find(|d| d.level == DiagnosticLevel::Error)
+                    .map(|d| format!("{}:{}", config_file.display(), d))
+                    .unwrap_or_else(|| e.to_string());
                 println!();
                 ui::error(
-                    &rust_i18n::t!("config.validation_failed", error = e.to_string()),
+                    &rust_i18n::t!("config.validation_failed", error = error),
                     colored,
                 );
                 println!();
@@ -99,7 +184,7 @@ fn edit(colored: bool) -> Result<()> {
                     }
                     EditAction::Ignore => {
                         // Force saving of incorrect configuration
-                        std::fs::write(&config_file, &edited)?;
+                        config::write_config_file(&config_file, &edited)?;
                         ui::warning(&rust_i18n::t!("config.saved_with_errors"), colored);
                         return Ok(());
                     }
@@ -109,6 +194,166 @@ fn edit(colored: bool) -> Result<()> {
     }
 }
 
+/// Checks config file content (deserialized through config crate, path consistent with load_config)
+pub(super) fn check_content(
+    content: &str,
+) -> std::result::Result<AppConfig, ::config::ConfigError> {
+    ::config::Config::builder()
+        .add_source(::config::File::from_str(
+            content,
+            ::config::FileFormat::Toml,
+        ))
+        .build()
+        .and_then(|c| c.try_deserialize())
+}
+
+/// Print the JSON Schema of the config file
+fn schema() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&config::json_schema())?);
+    Ok(())
+}
+
+/// Print diagnostics of one level as `path:line:column: message`
+fn print_diagnostics(
+    path: &Path,
+    diagnostics: &[ConfigDiagnostic],
+    level: DiagnosticLevel,
+    colored: bool,
+) {
+    for diagnostic in diagnostics.iter().filter(|d| d.level == level) {
+        let message = format!("{}:{}", path.display(), diagnostic);
+        match level {
+            DiagnosticLevel::Error => ui::error(&message, colored),
+            DiagnosticLevel::Warning => ui::warning(&message, colored),
+        }
+    }
+}
+
+/// Check the user and project config files on their own (existing files only)
+pub(super) fn check_config_files() -> Vec<(PathBuf, Vec<ConfigDiagnostic>)> {
+    [config::user_config_path(), config::find_project_config()]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| {
+            let content = config::read_config_file(&path).ok()?;
+            Some((path, check_config_content(&content)))
+        })
+        .collect()
+}
+
+/// Path of the config file edited by `set`/`unset`.
+fn target_file(project: bool) -> Result<PathBuf> {
+    if project {
+        let root = crate::git::find_git_root().ok_or_else(|| {
+            GcopError::Config(rust_i18n::t!("config.project_not_in_repo").to_string())
+        })?;
+        return Ok(root.join(".gcop").join("config.toml"));
+    }
+    config::user_config_path()
+        .ok_or_else(|| GcopError::Config(rust_i18n::t!("config.failed_determine_dir").to_string()))
+}
+
+/// Reads a config file for editing (a missing file is an empty document).
+fn read_document(path: &std::path::Path) -> Result<toml_edit::DocumentMut> {
+    let content = if path.exists() {
+        config::read_config_file(path)?
+    } else {
+        String::new()
+    };
+    content.parse().map_err(|e: toml_edit::TomlError| {
+        GcopError::Config(
+            rust_i18n::t!("config.validation_failed", error = e.to_string()).to_string(),
+        )
+    })
+}
+
+/// Print the effective value at a dotted key path
+fn get(config: &AppConfig, key: &str) -> Result<()> {
+    let path = key_path::parse_key(key)?;
+    let value = toml::Value::try_from(config).map_err(|e| GcopError::Other(e.to_string()))?;
+    let found = key_path::lookup(&value, &path).ok_or_else(|| {
+        GcopError::Config(rust_i18n::t!("config.key_not_set", key = key).to_string())
+    })?;
+    println!("{}", key_path::format_value(&path, found));
+    Ok(())
+}
+
+/// Set a key in the config file, keeping comments and formatting
+///
+/// The value is parsed as a TOML literal first; when the result does not fit
+/// the config schema (e.g. `version = 1.0` for a string field), it is stored
+/// as a string instead.
+fn set(key: &str, raw_value: &str, project: bool, colored: bool) -> Result<()> {
+    let path = key_path::parse_key(key)?;
+    let file = target_file(project)?;
+    let original = read_document(&file)?;
+
+    let typed = key_path::parse_value(raw_value);
+    let mut candidates = vec![typed.clone()];
+    if !typed.is_str() {
+        candidates.push(toml_edit::Value::from(raw_value));
+    }
+
+    let mut last_error = None;
+    for value in candidates {
+        let mut doc = original.clone();
+        key_path::set_value(&mut doc, &path, value.clone())?;
+        let content = doc.to_string();
+        match check_content(&content) {
+            Ok(_) => {
+                if let Some(parent) = file.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                config::write_config_file(&file, &content)?;
+                ui::success(
+                    &rust_i18n::t!(
+                        "config.key_set",
+                        key = key,
+                        value = value.to_string(),
+                        path = file.display().to_string()
+                    ),
+                    colored,
+                );
+                return Ok(());
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    let error = last_error.map(|e| e.to_string()).unwrap_or_default();
+    Err(GcopError::Config(
+        rust_i18n::t!("config.validation_failed", error = error).to_string(),
+    ))
+}
+
+/// Remove a key from the config file
+fn unset(key: &str, project: bool, colored: bool) -> Result<()> {
+    let path = key_path::parse_key(key)?;
+    let file = target_file(project)?;
+    let mut doc = read_document(&file)?;
+
+    if !key_path::unset_value(&mut doc, &path) {
+        return Err(GcopError::Config(
+            rust_i18n::t!(
+                "config.key_not_found",
+                key = key,
+                path = file.display().to_string()
+            )
+            .to_string(),
+        ));
+    }
+    config::write_config_file(&file, &doc.to_string())?;
+    ui::success(
+        &rust_i18n::t!(
+            "config.key_unset",
+            key = key,
+            path = file.display().to_string()
+        ),
+        colored,
+    );
+    Ok(())
+}
+
 /// Prompt user to select an action
 fn prompt_edit_action(colored: bool) -> Result<EditAction> {
     let items: Vec<String> = if colored {
@@ -154,8 +399,24 @@ fn prompt_edit_action(colored: bool) -> Result<EditAction> {
 async fn validate(colored: bool) -> Result<()> {
     ui::step("1/2", &rust_i18n::t!("config.loading"), colored);
 
-    // Load configuration
-    let config = load_config()?;
+    // Unknown keys are reported even when the configuration loads
+    let file_diagnostics = check_config_files();
+    for (path, diagnostics) in &file_diagnostics {
+        print_diagnostics(path, diagnostics, DiagnosticLevel::Warning, colored);
+    }
+
+    // Load configuration; on failure, point at the offending lines.
+    // Per-file errors are only shown here because a file checked on its own may
+    // rely on another layer (e.g. the project config on the user config).
+    let config = match load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            for (path, diagnostics) in &file_diagnostics {
+                print_diagnostics(path, diagnostics, DiagnosticLevel::Error, colored);
+            }
+            return Err(e);
+        }
+    };
 
     ui::success(&rust_i18n::t!("config.loaded"), colored);
     println!();
@@ -170,7 +431,8 @@ async fn validate(colored: bool) -> Result<()> {
     // Verify provider chain availability (default provider + fallback providers)
     ui::step("2/2", &rust_i18n::t!("config.testing"), colored);
 
-    let provider = create_provider(&config, None)?;
+    super::provider::offer_ollama_pulls(&config, colored).await?;
+    let provider = create_provider(&config, &CommandLlmConfig::default(), None, None)?;
 
     match provider.validate().await {
         Ok(_) => {
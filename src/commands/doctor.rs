@@ -0,0 +1,398 @@
+//! `config doctor`: one-shot health check of the configuration, providers,
+//! repository, hook and editor, with a suggested fix for every problem.
+
+use colored::Colorize;
+
+use super::hook::{HookState, hook_state};
+use crate::config::{
+    ApiStyle, AppConfig, CommandLlmConfig, DiagnosticLevel, keychain, load_config,
+};
+use crate::error::{GcopError, Result};
+use crate::git::find_git_root;
+use crate::llm::provider::create_provider;
+use crate::ui;
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    /// Optional setup that is not in place
+    Info,
+    Warn,
+    Fail,
+}
+
+/// One line of the report, with an optional suggested fix
+#[derive(Debug)]
+struct Check {
+    status: Status,
+    message: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn new(status: Status, message: impl Into<String>, fix: Option<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            fix,
+        }
+    }
+
+    fn ok(message: impl Into<String>) -> Self {
+        Self::new(Status::Ok, message, None)
+    }
+}
+
+/// Runs every check and prints the report.
+///
+/// Provider reachability sends a real request and only runs with `online`.
+/// Returns an error when any check fails; warnings and hints do not.
+pub async fn run(online: bool, colored: bool) -> Result<()> {
+    let (config_checks, config) = config_checks();
+    let mut sections = vec![("doctor.section_config", config_checks)];
+    if let Some(config) = &config {
+        sections.push(("doctor.section_providers", api_key_checks(config)));
+    }
+    sections.push(("doctor.section_repository", repository_checks()));
+    sections.push(("doctor.section_editor", vec![editor_check()]));
+    if online && let Some(config) = &config {
+        sections.push(("doctor.section_online", vec![online_check(config).await]));
+    }
+
+    let mut failures = 0;
+    for (title, checks) in &sections {
+        let title = rust_i18n::t!(*title).to_string();
+        println!(
+            "{}",
+            if colored {
+                title.bold().to_string()
+            } else {
+                title
+            }
+        );
+        for check in checks {
+            print!("{}", render_check(check, colored));
+        }
+        println!();
+        failures += checks.iter().filter(|c| c.status == Status::Fail).count();
+    }
+    if !online {
+        println!(
+            "{}",
+            ui::info(&rust_i18n::t!("doctor.online_skipped"), colored)
+        );
+    }
+
+    if failures > 0 {
+        return Err(GcopError::Config(
+            rust_i18n::t!("doctor.failed", count = failures).to_string(),
+        ));
+    }
+    ui::success(&rust_i18n::t!("doctor.all_good"), colored);
+    Ok(())
+}
+
+/// Config file syntax and whether the merged configuration loads
+fn config_checks() -> (Vec<Check>, Option<AppConfig>) {
+    let files = super::config::check_config_files();
+    let loaded = load_config();
+
+    let mut checks = Vec::new();
+    if files.is_empty() {
+        checks.push(Check::new(
+            Status::Info,
+            rust_i18n::t!("doctor.config_none"),
+            Some(rust_i18n::t!("doctor.fix_config_none").to_string()),
+        ));
+    }
+    for (path, diagnostics) in &files {
+        let path = path.display().to_string();
+        // A file checked on its own may rely on another layer, so per-file
+        // errors only count when the merged configuration fails to load
+        let error = diagnostics
+            .iter()
+            .find(|d| d.level == DiagnosticLevel::Error && loaded.is_err());
+        let warnings = diagnostics
+            .iter()
+            .filter(|d| d.level == DiagnosticLevel::Warning)
+            .count();
+        checks.push(if let Some(error) = error {
+            let error = match error.line {
+                Some(line) => format!("{} (line {})", error.message, line),
+                None => error.message.clone(),
+            };
+            Check::new(
+                Status::Fail,
+                rust_i18n::t!("doctor.config_file_invalid", path = path, error = error),
+                Some(rust_i18n::t!("doctor.fix_config_file").to_string()),
+            )
+        } else if warnings > 0 {
+            Check::new(
+                Status::Warn,
+                rust_i18n::t!("doctor.config_file_warnings", path = path, count = warnings),
+                Some(rust_i18n::t!("doctor.fix_config_warnings").to_string()),
+            )
+        } else {
+            Check::ok(rust_i18n::t!("doctor.config_file_ok", path = path))
+        });
+    }
+
+    match loaded {
+        Ok(config) => {
+            checks.push(Check::ok(rust_i18n::t!("doctor.config_loaded")));
+            (checks, Some(config))
+        }
+        Err(e) => {
+            checks.push(Check::new(
+                Status::Fail,
+                rust_i18n::t!("doctor.config_load_failed", error = e.localized_message()),
+                Some(
+                    e.localized_suggestion()
+                        .unwrap_or_else(|| rust_i18n::t!("doctor.fix_config_file").to_string()),
+                ),
+            ));
+            (checks, None)
+        }
+    }
+}
+
+/// API key presence for every provider the commands can reach
+fn api_key_checks(config: &AppConfig) -> Vec<Check> {
+    let mut names: Vec<&str> = Vec::new();
+    let referenced = std::iter::once(config.llm.default_provider.as_str())
+        .chain(config.llm.fallback_providers.iter().map(String::as_str))
+        .chain(config.commit.llm.provider.as_deref())
+        .chain(config.review.llm.provider.as_deref());
+    for name in referenced {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            let Some(provider) = config.llm.providers.get(name) else {
+                return Check::new(
+                    Status::Fail,
+                    rust_i18n::t!("doctor.provider_missing", name = name),
+                    Some(rust_i18n::t!("doctor.fix_provider_missing", name = name).to_string()),
+                );
+            };
+            let style = provider.api_style.or_else(|| name.parse().ok());
+            if let Some(style @ (ApiStyle::Ollama | ApiStyle::Local | ApiStyle::Exec)) = style {
+                return Check::ok(rust_i18n::t!(
+                    "doctor.key_not_needed",
+                    name = name,
+                    style = style.to_string()
+                ));
+            }
+            if provider
+                .api_key
+                .as_deref()
+                .is_some_and(|k| !k.trim().is_empty())
+            {
+                Check::ok(rust_i18n::t!("doctor.key_present", name = name))
+            } else if keychain::lookup(name).is_some() {
+                Check::ok(rust_i18n::t!("doctor.key_in_keychain", name = name))
+            } else {
+                Check::new(
+                    Status::Fail,
+                    rust_i18n::t!("doctor.key_missing", name = name),
+                    Some(
+                        rust_i18n::t!(
+                            "doctor.fix_key_missing",
+                            name = name,
+                            upper = name.to_uppercase()
+                        )
+                        .to_string(),
+                    ),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Repository health and prepare-commit-msg hook status
+fn repository_checks() -> Vec<Check> {
+    let Some(root) = find_git_root() else {
+        return vec![Check::new(
+            Status::Warn,
+            rust_i18n::t!("doctor.not_in_repo"),
+            Some(rust_i18n::t!("doctor.fix_not_in_repo").to_string()),
+        )];
+    };
+    let path = root.display().to_string();
+
+    let mut checks = vec![match git2::Repository::open(&root) {
+        Err(e) => Check::new(
+            Status::Fail,
+            rust_i18n::t!("doctor.repo_broken", path = path, error = e.message()),
+            Some(rust_i18n::t!("doctor.fix_repo_broken").to_string()),
+        ),
+        Ok(repo) if repo.head().is_err() => Check::new(
+            Status::Info,
+            rust_i18n::t!("doctor.repo_no_commits", path = path),
+            None,
+        ),
+        Ok(_) => Check::ok(rust_i18n::t!("doctor.repo_ok", path = path)),
+    }];
+
+    checks.push(hook_check(
+        hook_state(&root),
+        which::which("gcop-rs").is_ok(),
+    ));
+    checks
+}
+
+fn hook_check(state: HookState, gcop_in_path: bool) -> Check {
+    match state {
+        HookState::Installed if gcop_in_path => Check::ok(rust_i18n::t!("doctor.hook_installed")),
+        HookState::Installed => Check::new(
+            Status::Warn,
+            rust_i18n::t!("doctor.hook_not_in_path"),
+            Some(rust_i18n::t!("doctor.fix_hook_not_in_path").to_string()),
+        ),
+        HookState::Missing => Check::new(
+            Status::Info,
+            rust_i18n::t!("doctor.hook_missing"),
+            Some(rust_i18n::t!("doctor.fix_hook_missing").to_string()),
+        ),
+        HookState::Foreign => Check::new(
+            Status::Info,
+            rust_i18n::t!("doctor.hook_foreign"),
+            Some(rust_i18n::t!("doctor.fix_hook_foreign").to_string()),
+        ),
+    }
+}
+
+/// Whether the editor used for commit messages can be launched
+fn editor_check() -> Check {
+    let Some(editor) = ui::configured_editor() else {
+        return Check::new(
+            Status::Info,
+            rust_i18n::t!("doctor.editor_default"),
+            Some(rust_i18n::t!("doctor.fix_editor_default").to_string()),
+        );
+    };
+    match ui::editor_program(&editor) {
+        Some(program) if which::which(&program).is_err() => Check::new(
+            Status::Fail,
+            rust_i18n::t!("doctor.editor_not_found", program = program),
+            Some(rust_i18n::t!("doctor.fix_editor_not_found").to_string()),
+        ),
+        _ => Check::ok(rust_i18n::t!("doctor.editor_ok", editor = editor)),
+    }
+}
+
+/// Sends a validation request through the default provider chain
+async fn online_check(config: &AppConfig) -> Check {
+    let provider = &config.llm.default_provider;
+    let result = match create_provider(config, &CommandLlmConfig::default(), None, None) {
+        Ok(llm) => llm.validate().await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => Check::ok(rust_i18n::t!(
+            "doctor.provider_reachable",
+            provider = provider
+        )),
+        Err(e) => Check::new(
+            Status::Fail,
+            rust_i18n::t!(
+                "doctor.provider_unreachable",
+                provider = provider,
+                error = e.localized_message()
+            ),
+            e.localized_suggestion(),
+        ),
+    }
+}
+
+fn render_check(check: &Check, colored: bool) -> String {
+    let symbol = match check.status {
+        Status::Ok => "✓",
+        Status::Info => "ℹ",
+        Status::Warn => "⚠",
+        Status::Fail => "✗",
+    };
+    let mut out = if colored {
+        let symbol = match check.status {
+            Status::Ok => symbol.green(),
+            Status::Info => symbol.blue(),
+            Status::Warn => symbol.yellow(),
+            Status::Fail => symbol.red(),
+        };
+        format!("  {} {}\n", symbol.bold(), check.message)
+    } else {
+        format!("  {} {}\n", symbol, check.message)
+    };
+    if let Some(fix) = &check.fix {
+        let fix = format!("→ {}", fix);
+        out.push_str(&format!(
+            "    {}\n",
+            if colored {
+                fix.bright_black().to_string()
+            } else {
+                fix
+            }
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+    use crate::llm::provider::test_utils::test_provider_config;
+
+    fn provider(style: ApiStyle, api_key: Option<&str>) -> ProviderConfig {
+        let mut config =
+            test_provider_config(String::new(), api_key.map(str::to_string), String::new());
+        config.api_style = Some(style);
+        config
+    }
+
+    #[test]
+    fn test_api_key_checks() {
+        let mut config = AppConfig::default();
+        config.llm.default_provider = "claude".to_string();
+        config.llm.fallback_providers = vec!["ollama".to_string(), "claude".to_string()];
+        config.review.llm.provider = Some("gpt".to_string());
+        config.llm.providers.insert(
+            "claude".to_string(),
+            provider(ApiStyle::Claude, Some("sk-ant-123")),
+        );
+        config
+            .llm
+            .providers
+            .insert("ollama".to_string(), provider(ApiStyle::Ollama, None));
+
+        let checks = api_key_checks(&config);
+        let statuses: Vec<Status> = checks.iter().map(|c| c.status).collect();
+        // claude (deduplicated), ollama (no key needed), gpt (undefined)
+        assert_eq!(statuses, vec![Status::Ok, Status::Ok, Status::Fail]);
+        assert!(checks[2].message.contains("gpt"));
+
+        config
+            .llm
+            .providers
+            .insert("gpt".to_string(), provider(ApiStyle::OpenAI, Some("  ")));
+        let checks = api_key_checks(&config);
+        assert_eq!(checks[2].status, Status::Fail);
+        assert!(checks[2].fix.as_deref().unwrap().contains("GPT"));
+    }
+
+    #[test]
+    fn test_hook_check_and_render() {
+        assert_eq!(hook_check(HookState::Installed, true).status, Status::Ok);
+        assert_eq!(hook_check(HookState::Installed, false).status, Status::Warn);
+        assert_eq!(hook_check(HookState::Missing, true).status, Status::Info);
+
+        let check = Check::new(Status::Fail, "broken", Some("fix it".to_string()));
+        assert_eq!(render_check(&check, false), "  ✗ broken\n    → fix it\n");
+        assert_eq!(render_check(&Check::ok("fine"), false), "  ✓ fine\n");
+    }
+}
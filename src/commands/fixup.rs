@@ -0,0 +1,595 @@
+//! `fixup!` / `squash!` commits for `git rebase --autosquash`.
+//!
+//! `commit --fixup <COMMIT>` and `commit --squash <COMMIT>` prefix the target
+//! commit's subject like `git commit --fixup` does. Before committing, the
+//! provider checks that the staged diff plausibly belongs to the target; an
+//! unrelated verdict asks for confirmation. `--squash` also generates a
+//! message body describing the staged changes.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::commit::DiffStatsJson;
+use super::json::{self, JsonOutput};
+use super::options::CommitOptions;
+use super::smart_truncate_diff;
+use crate::cli::CommitArgs;
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::git::{CommitInfo, DiffStats, GitOperations};
+use crate::llm::metrics::{self, LlmMetrics};
+use crate::llm::provider::base::response::{
+    clean_json_response, process_commit_response, strip_thinking_tags, truncate_for_preview,
+};
+use crate::llm::{LLMProvider, ProgressReporter, ledger};
+use crate::ui::{self, Reporter};
+
+/// Autosquash prefixes git recognizes, stripped from target subjects.
+const AUTOSQUASH_PREFIXES: &[&str] = &["fixup! ", "squash! ", "amend! "];
+
+/// Kind of autosquash commit to create.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixupKind {
+    /// `fixup!`: folded into the target, its own message is discarded.
+    Fixup,
+    /// `squash!`: folded into the target, its message is kept for editing.
+    Squash,
+}
+
+impl FixupKind {
+    /// Subject prefix recognized by `git rebase --autosquash`.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::Fixup => "fixup! ",
+            Self::Squash => "squash! ",
+        }
+    }
+}
+
+/// Target of `--fixup` / `--squash`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixupTarget<'a> {
+    /// `fixup!` or `squash!`.
+    pub kind: FixupKind,
+    /// Target revision as given on the command line.
+    pub commit: &'a str,
+}
+
+impl<'a> FixupTarget<'a> {
+    /// Reads `--fixup` / `--squash` (clap rejects both at once).
+    pub fn from_cli(args: &'a CommitArgs) -> Option<Self> {
+        let fixup = args.fixup.as_deref().map(|commit| Self {
+            kind: FixupKind::Fixup,
+            commit,
+        });
+        fixup.or_else(|| {
+            args.squash.as_deref().map(|commit| Self {
+                kind: FixupKind::Squash,
+                commit,
+            })
+        })
+    }
+}
+
+/// Provider verdict on whether the staged changes belong to the target.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixupCheck {
+    /// Whether the staged changes fix or complete the target commit.
+    pub related: bool,
+    /// One-sentence explanation.
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// JSON output data for `--fixup` / `--squash` with `--json`.
+#[derive(Debug, Serialize)]
+pub struct FixupCommitData {
+    /// Generated `fixup!` / `squash!` message.
+    pub message: String,
+    /// Full hash of the target commit.
+    pub target: String,
+    /// Provider verdict (`null` with `--offline` or when the check failed).
+    pub check: Option<FixupCheck>,
+    /// Staged diff statistics.
+    pub diff_stats: DiffStatsJson,
+    /// Whether a commit was created (always `false` in JSON mode).
+    pub committed: bool,
+}
+
+/// Entry point for `commit --fixup` / `commit --squash`.
+///
+/// Called from `commands::commit::run_with_deps()` when `options.fixup` is set.
+pub async fn run_fixup_flow(
+    options: &CommitOptions<'_>,
+    target: FixupTarget<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    initial_feedbacks: &[String],
+    reporter: &dyn Reporter,
+) -> Result<()> {
+    let result = fixup_flow(
+        options,
+        target,
+        config,
+        repo,
+        provider,
+        initial_feedbacks,
+        reporter,
+    )
+    .await;
+    if let Err(e) = &result
+        && options.format.is_json()
+    {
+        json::output_json_error::<FixupCommitData>(e)?;
+    }
+    result
+}
+
+async fn fixup_flow(
+    options: &CommitOptions<'_>,
+    target: FixupTarget<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    initial_feedbacks: &[String],
+    reporter: &dyn Reporter,
+) -> Result<()> {
+    let colored = options.effective_colored(config);
+    let is_json = options.format.is_json();
+
+    let target_commit = repo.get_commit_info(target.commit)?;
+    if !super::commit::ensure_staged_changes(options, config, repo, reporter)? {
+        reporter.error(&rust_i18n::t!("commit.no_staged_changes"));
+        return Err(GcopError::NoStagedChanges);
+    }
+
+    let diff = repo.get_staged_diff()?;
+    let stats = repo.get_diff_stats(&diff)?;
+    let diff = if options.offline {
+        diff
+    } else {
+        super::privacy::guard(diff, config, is_json, colored)?
+    };
+    let (diff, truncated) = smart_truncate_diff(&diff, config.llm.max_diff_size);
+    let subject = target_subject(&target_commit.message);
+
+    if truncated {
+        reporter.warning(&rust_i18n::t!("diff.truncated"));
+    }
+    reporter.step(
+        &rust_i18n::t!("commit.step1"),
+        &rust_i18n::t!(
+            "fixup.target",
+            hash = short_hash(&target_commit.hash),
+            subject = subject
+        ),
+    );
+    if config.commit.show_diff_preview && !is_json {
+        reporter.detail(&ui::format_diff_stats(&stats, colored));
+    }
+
+    let check = if options.offline {
+        None
+    } else {
+        check_target(
+            options,
+            config,
+            repo,
+            provider,
+            &target_commit,
+            &diff,
+            colored,
+            reporter,
+        )
+        .await
+    };
+    if let Some(check) = &check
+        && !check.related
+        && !is_json
+    {
+        reporter.warning(&rust_i18n::t!(
+            "fixup.unrelated",
+            reason = check.reason.as_str()
+        ));
+        let proceed = options.yes
+            || options.dry_run
+            || reporter.confirm(&rust_i18n::t!("fixup.confirm_unrelated"), false)?;
+        if !proceed {
+            return Err(GcopError::UserCancelled);
+        }
+    }
+
+    let body = match target.kind {
+        FixupKind::Fixup => None,
+        FixupKind::Squash => Some(
+            squash_body(
+                options,
+                config,
+                repo,
+                provider,
+                &diff,
+                &stats,
+                initial_feedbacks,
+                reporter,
+            )
+            .await?,
+        ),
+    };
+    let message = fixup_message(target.kind, subject, body.as_deref());
+
+    if is_json {
+        let output = JsonOutput {
+            success: true,
+            data: Some(FixupCommitData {
+                message,
+                target: target_commit.hash,
+                check,
+                diff_stats: (&stats).into(),
+                committed: false,
+            }),
+            error: None,
+            meta: None::<LlmMetrics>,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if options.dry_run {
+        // The bare message is the result of --dry-run, whatever the reporter
+        reporter.info(&rust_i18n::t!("commit.generated"));
+        println!("{}", message);
+        return Ok(());
+    }
+    reporter.preview(&rust_i18n::t!("commit.generated"), &message);
+    if !options.yes && !reporter.confirm(&rust_i18n::t!("fixup.confirm_commit"), true)? {
+        reporter.warning(&rust_i18n::t!("commit.cancelled"));
+        return Err(GcopError::UserCancelled);
+    }
+
+    reporter.step(
+        &rust_i18n::t!("commit.step4"),
+        &rust_i18n::t!("commit.creating"),
+    );
+    repo.commit(&message)?;
+    reporter.success(&rust_i18n::t!("commit.success"));
+    Ok(())
+}
+
+/// Asks the provider whether the staged diff belongs to the target.
+///
+/// Advisory only: failures are logged (or warned about in text mode) and
+/// return `None` so the fixup can still be created.
+#[allow(clippy::too_many_arguments)]
+async fn check_target(
+    options: &CommitOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    target: &CommitInfo,
+    diff: &str,
+    colored: bool,
+    reporter: &dyn Reporter,
+) -> Option<FixupCheck> {
+    let is_json = options.format.is_json();
+    let result = async {
+        // Findings in the target were already reported when it was committed
+        let target_diff =
+            super::privacy::guard(repo.get_commit_diff(&target.hash)?, config, true, false)?;
+        let (target_diff, _) = smart_truncate_diff(&target_diff, config.llm.max_diff_size);
+        let (system, user) = crate::llm::prompt::build_fixup_prompt(
+            target_subject(&target.message),
+            &target_diff,
+            diff,
+        );
+        if options.verbose {
+            super::commit::print_verbose_prompt(&system, &user, is_json, colored && !is_json);
+        }
+
+        let spinner = reporter.cancellable_spinner(&rust_i18n::t!("fixup.checking"));
+        metrics::start();
+        let progress = spinner.as_ref().map(|s| s as &dyn ProgressReporter);
+        let response = crate::llm::provider::tiered::diff_size_scope(
+            diff.len() + target_diff.len(),
+            provider.send_prompt(&system, &user, progress),
+        )
+        .await;
+        let llm_metrics = metrics::finish();
+        if let Some(spinner) = &spinner {
+            spinner.finish_and_clear();
+        }
+        let response = response?;
+        ledger::record("commit", &llm_metrics, config);
+        if options.verbose {
+            super::commit::print_verbose_metrics(&llm_metrics, is_json, colored && !is_json);
+        }
+        parse_fixup_check(&response)
+    }
+    .await;
+
+    match result {
+        Ok(check) => Some(check),
+        Err(e) => {
+            if is_json {
+                tracing::warn!("fixup target check failed: {}", e);
+            } else {
+                reporter.warning(&rust_i18n::t!("fixup.check_failed", error = e.to_string()));
+            }
+            None
+        }
+    }
+}
+
+/// Generates the message body of a `squash!` commit.
+#[allow(clippy::too_many_arguments)]
+async fn squash_body(
+    options: &CommitOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    diff: &str,
+    stats: &DiffStats,
+    feedbacks: &[String],
+    reporter: &dyn Reporter,
+) -> Result<String> {
+    let trailers = options.trailers(config, repo)?;
+    let branch_name = repo.get_current_branch()?;
+    let scope_info = super::commit::compute_scope_info_pub(repo, &stats.files_changed, config);
+    let history_examples = super::commit::compute_history_examples(repo, config);
+
+    reporter.step(
+        &rust_i18n::t!("commit.step2"),
+        &rust_i18n::t!("spinner.generating"),
+    );
+    let spinner = reporter.cancellable_spinner(&rust_i18n::t!("spinner.generating"));
+    let (message, _) = super::commit::generate_message_no_streaming(
+        provider,
+        diff,
+        stats,
+        config,
+        feedbacks,
+        options.verbose,
+        options.offline,
+        &branch_name,
+        &config.commit.custom_prompt,
+        &scope_info,
+        &history_examples,
+        None,
+        &trailers,
+        None,
+    )
+    .await?;
+    if let Some(spinner) = &spinner {
+        spinner.finish_and_clear();
+    }
+    Ok(process_commit_response(message))
+}
+
+/// Target subject without autosquash prefixes, so a fixup of a fixup still
+/// points at the original commit.
+fn target_subject(subject: &str) -> &str {
+    let mut subject = subject.trim();
+    while let Some(rest) = AUTOSQUASH_PREFIXES
+        .iter()
+        .find_map(|prefix| subject.strip_prefix(prefix))
+    {
+        subject = rest.trim_start();
+    }
+    subject
+}
+
+/// `fixup! <subject>`, or `squash! <subject>` followed by the generated body.
+fn fixup_message(kind: FixupKind, subject: &str, body: Option<&str>) -> String {
+    let header = format!("{}{}", kind.prefix(), subject);
+    match body.map(str::trim).filter(|body| !body.is_empty()) {
+        Some(body) => format!("{}\n\n{}", header, body),
+        None => header,
+    }
+}
+
+/// Parses the provider's `{"related": ..., "reason": ...}` verdict.
+fn parse_fixup_check(response: &str) -> Result<FixupCheck> {
+    let cleaned = strip_thinking_tags(response);
+    serde_json::from_str(clean_json_response(&cleaned)).map_err(|e| {
+        GcopError::Llm(
+            rust_i18n::t!(
+                "fixup.invalid_check",
+                error = e.to_string(),
+                preview = truncate_for_preview(response)
+            )
+            .to_string(),
+        )
+    })
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::format::OutputFormat;
+    use crate::git::MockGitOperations;
+    use crate::llm::provider::test_utils::ScriptedProvider;
+    use crate::ui::SilentReporter;
+    use chrono::Local;
+
+    fn options(kind: FixupKind) -> CommitOptions<'static> {
+        CommitOptions {
+            no_edit: true,
+            yes: true,
+            dry_run: false,
+            split: false,
+            amend: false,
+            fixup: Some(FixupTarget {
+                kind,
+                commit: "HEAD~1",
+            }),
+            all: false,
+            no_sign: false,
+            signoff: false,
+            co_authors: &[],
+            offline: false,
+            show_redacted: false,
+            output: None,
+            message_file: None,
+            verify: None,
+            review: false,
+            format: OutputFormat::Text,
+            feedback: &[],
+            verbose: false,
+            provider_override: None,
+            model_override: None,
+        }
+    }
+
+    fn config() -> AppConfig {
+        let mut config = AppConfig::default();
+        config.ui.colored = false;
+        config.commit.show_diff_preview = false;
+        config
+    }
+
+    fn repo(expected_message: &'static str) -> MockGitOperations {
+        let mut repo = MockGitOperations::new();
+        repo.expect_get_commit_info()
+            .withf(|rev| rev == "HEAD~1")
+            .returning(|_| {
+                Ok(CommitInfo {
+                    hash: "1234567890abcdef".to_string(),
+                    parent_count: 1,
+                    author_name: "Dev".to_string(),
+                    author_email: "dev@example.com".to_string(),
+                    timestamp: Local::now(),
+                    message: "fixup! feat(auth): add login".to_string(),
+                })
+            });
+        repo.expect_has_staged_changes().returning(|| Ok(true));
+        repo.expect_get_staged_diff()
+            .returning(|| Ok("+    validate(token)?;".to_string()));
+        repo.expect_get_diff_stats().returning(|_| {
+            Ok(DiffStats {
+                files_changed: vec!["src/auth.rs".to_string()],
+                insertions: 1,
+                deletions: 0,
+            })
+        });
+        repo.expect_get_commit_diff()
+            .withf(|hash| hash == "1234567890abcdef")
+            .returning(|_| Ok("+fn login() {}".to_string()));
+        repo.expect_commit()
+            .withf(move |message| message == expected_message)
+            .times(1)
+            .returning(|_| Ok(()));
+        repo
+    }
+
+    #[test]
+    fn test_target_subject_strips_autosquash_prefixes() {
+        assert_eq!(target_subject("feat: add login"), "feat: add login");
+        assert_eq!(target_subject("fixup! feat: add login"), "feat: add login");
+        assert_eq!(
+            target_subject("squash! fixup!  amend! feat: add login"),
+            "feat: add login"
+        );
+    }
+
+    #[test]
+    fn test_fixup_message() {
+        assert_eq!(
+            fixup_message(FixupKind::Fixup, "feat: add login", None),
+            "fixup! feat: add login"
+        );
+        assert_eq!(
+            fixup_message(
+                FixupKind::Squash,
+                "feat: add login",
+                Some("fix: validate token\n")
+            ),
+            "squash! feat: add login\n\nfix: validate token"
+        );
+        assert_eq!(
+            fixup_message(FixupKind::Squash, "feat: add login", Some("  ")),
+            "squash! feat: add login"
+        );
+    }
+
+    #[test]
+    fn test_parse_fixup_check() {
+        let check = parse_fixup_check(
+            "<think>hmm</think>```json\n{\"related\": false, \"reason\": \"Touches docs only\"}\n```",
+        )
+        .unwrap();
+        assert_eq!(
+            check,
+            FixupCheck {
+                related: false,
+                reason: "Touches docs only".to_string()
+            }
+        );
+        assert!(
+            parse_fixup_check("{\"related\": true}")
+                .unwrap()
+                .reason
+                .is_empty()
+        );
+        assert!(parse_fixup_check("yes").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fixup_flow_commits_prefixed_message() {
+        let repo = repo("fixup! feat(auth): add login");
+        let provider = Arc::new(ScriptedProvider::new(&[
+            r#"{"related": true, "reason": "Same function"}"#,
+        ]));
+        let dyn_provider: Arc<dyn LLMProvider> = provider.clone();
+        let options = options(FixupKind::Fixup);
+
+        run_fixup_flow(
+            &options,
+            options.fixup.unwrap(),
+            &config(),
+            &repo,
+            &dyn_provider,
+            &[],
+            &SilentReporter,
+        )
+        .await
+        .unwrap();
+
+        let prompts = provider.prompts();
+        assert_eq!(prompts.len(), 1);
+        assert!(prompts[0].contains("## Target commit:\nfeat(auth): add login\n"));
+        assert!(prompts[0].contains("+fn login() {}"));
+        assert!(prompts[0].contains("+    validate(token)?;"));
+    }
+
+    #[tokio::test]
+    async fn test_squash_flow_generates_body() {
+        let mut repo = repo("squash! feat(auth): add login\n\nfix(auth): validate the token");
+        repo.expect_get_current_branch()
+            .returning(|| Ok(Some("main".to_string())));
+        let provider = Arc::new(ScriptedProvider::new(&[
+            r#"{"related": false, "reason": "Different module"}"#,
+            "fix(auth): validate the token",
+        ]));
+        let dyn_provider: Arc<dyn LLMProvider> = provider.clone();
+        let options = options(FixupKind::Squash);
+
+        // --yes 时不相关的判定只提示警告，仍然创建提交
+        run_fixup_flow(
+            &options,
+            options.fixup.unwrap(),
+            &config(),
+            &repo,
+            &dyn_provider,
+            &[],
+            &SilentReporter,
+        )
+        .await
+        .unwrap();
+        assert_eq!(provider.prompts().len(), 2);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::error::{GcopError, Result};
+use crate::ui::{JsonReporter, Reporter, SilentReporter, TerminalReporter};
 use std::str::FromStr;
 
 /// Output format enum
@@ -10,17 +12,37 @@ pub enum OutputFormat {
     Text,
     /// Machine-readable JSON output.
     Json,
+    /// Newline-delimited JSON events: progress and streamed text, then the
+    /// JSON payload as a final `result` (or `error`) event (`commit`, `review`
+    /// and `stats`; other commands print the plain JSON payload).
+    JsonStream,
     /// Markdown report output.
     Markdown,
+    /// Reviewed diff with inline issue markers (`review` only; other commands fall back to plain text).
+    Annotated,
+    /// GitHub Actions workflow commands (`review` only; other commands fall back to plain text).
+    Github,
+    /// JUnit XML report (`review` only; other commands fall back to plain text).
+    Junit,
+    /// CSV export (`stats` only; other commands reject it).
+    Csv,
+    /// Self-contained HTML report (`stats` only; other commands reject it).
+    Html,
 }
 
 impl FromStr for OutputFormat {
     type Err = std::convert::Infallible;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         Ok(match s.to_lowercase().as_str() {
             "json" => Self::Json,
+            "json-stream" | "ndjson" => Self::JsonStream,
             "markdown" | "md" => Self::Markdown,
+            "csv" => Self::Csv,
+            "html" => Self::Html,
+            "annotated" => Self::Annotated,
+            "github" => Self::Github,
+            "junit" => Self::Junit,
             _ => Self::Text,
         })
     }
@@ -38,16 +60,49 @@ impl OutputFormat {
         }
     }
 
-    /// Is it in JSON format?
+    /// `--json-stream` takes precedence over `--format` and `--json`
+    pub fn or_stream(self, json_stream: bool) -> Self {
+        if json_stream { Self::JsonStream } else { self }
+    }
+
+    /// Reject the `stats`-only export formats (`csv`, `html`)
+    ///
+    /// Other commands have no renderer for them and would otherwise print text.
+    pub fn reject_stats_only(self) -> Result<Self> {
+        let name = match self {
+            Self::Csv => "csv",
+            Self::Html => "html",
+            _ => return Ok(self),
+        };
+        Err(GcopError::InvalidInput(
+            rust_i18n::t!("format.stats_only", format = name).to_string(),
+        ))
+    }
+
+    /// Is it in JSON format (including `json-stream`)?
     pub fn is_json(&self) -> bool {
-        matches!(self, Self::Json)
+        matches!(self, Self::Json | Self::JsonStream)
+    }
+
+    /// Is it the newline-delimited event stream (`--json-stream`)?
+    pub fn is_json_stream(&self) -> bool {
+        matches!(self, Self::JsonStream)
     }
 
-    /// Is it in a machine-readable format (JSON/Markdown)
+    /// Is it in a machine-readable format (JSON/JSON stream/Markdown/GitHub/JUnit/CSV/HTML)
     ///
     /// Used to decide whether to skip interactive UI elements (spinner, step prompt, etc.).
     pub fn is_machine_readable(&self) -> bool {
-        matches!(self, Self::Json | Self::Markdown)
+        matches!(
+            self,
+            Self::Json
+                | Self::JsonStream
+                | Self::Markdown
+                | Self::Github
+                | Self::Junit
+                | Self::Csv
+                | Self::Html
+        )
     }
 
     /// Get the effective colored setting (color disabled in machine-readable format)
@@ -58,6 +113,18 @@ impl OutputFormat {
             config_colored
         }
     }
+
+    /// Status output sink for this format: the terminal UI, JSON events on
+    /// stdout for `json-stream`, or nothing for other machine-readable formats
+    pub fn reporter(&self, config_colored: bool) -> Box<dyn Reporter> {
+        if self.is_json_stream() {
+            Box::new(JsonReporter::stdout())
+        } else if self.is_machine_readable() {
+            Box::new(SilentReporter)
+        } else {
+            Box::new(TerminalReporter::new(config_colored))
+        }
+    }
 }
 
 #[cfg(test)]
@@ -73,15 +140,42 @@ mod tests {
     #[test]
     fn test_from_cli_format_string() {
         assert_eq!(OutputFormat::from_cli("json", false), OutputFormat::Json);
+        assert_eq!(
+            OutputFormat::from_cli("json-stream", false),
+            OutputFormat::JsonStream
+        );
         assert_eq!(
             OutputFormat::from_cli("markdown", false),
             OutputFormat::Markdown
         );
         assert_eq!(OutputFormat::from_cli("md", false), OutputFormat::Markdown);
+        assert_eq!(OutputFormat::from_cli("CSV", false), OutputFormat::Csv);
+        assert_eq!(OutputFormat::from_cli("html", false), OutputFormat::Html);
+        assert_eq!(
+            OutputFormat::from_cli("annotated", false),
+            OutputFormat::Annotated
+        );
+        assert_eq!(
+            OutputFormat::from_cli("github", false),
+            OutputFormat::Github
+        );
         assert_eq!(OutputFormat::from_cli("text", false), OutputFormat::Text);
         assert_eq!(OutputFormat::from_cli("unknown", false), OutputFormat::Text);
     }
 
+    #[test]
+    fn test_reject_stats_only() {
+        assert!(matches!(
+            OutputFormat::Csv.reject_stats_only(),
+            Err(GcopError::InvalidInput(_))
+        ));
+        assert!(OutputFormat::Html.reject_stats_only().is_err());
+        assert_eq!(
+            OutputFormat::Markdown.reject_stats_only().unwrap(),
+            OutputFormat::Markdown
+        );
+    }
+
     #[test]
     fn test_effective_colored() {
         assert!(!OutputFormat::Json.effective_colored(true));
@@ -93,7 +187,13 @@ mod tests {
     #[test]
     fn test_is_machine_readable() {
         assert!(OutputFormat::Json.is_machine_readable());
+        assert!(OutputFormat::JsonStream.is_machine_readable());
         assert!(OutputFormat::Markdown.is_machine_readable());
+        assert!(OutputFormat::Csv.is_machine_readable());
+        assert!(OutputFormat::Html.is_machine_readable());
+        assert!(OutputFormat::Github.is_machine_readable());
+        assert!(OutputFormat::Junit.is_machine_readable());
         assert!(!OutputFormat::Text.is_machine_readable());
+        assert!(!OutputFormat::Annotated.is_machine_readable());
     }
 }
@@ -1,10 +1,12 @@
 use std::fs;
+use std::path::Path;
+use std::time::Duration;
 
-use crate::commands::smart_truncate_diff;
-use crate::config::AppConfig;
+use crate::commands::{hook_cache, smart_truncate_diff};
+use crate::config::{AppConfig, HookFailure};
 use crate::error::{GcopError, Result};
 use crate::git::repository::GitRepository;
-use crate::git::{GitOperations, find_git_root};
+use crate::git::{GitOperations, find_git_root, hooks_dir};
 use crate::llm::CommitContext;
 use crate::llm::provider::base::response::process_commit_response;
 use crate::llm::provider::create_provider;
@@ -23,6 +25,55 @@ fi
 gcop-rs hook run "$1" "$2" "$3"
 "#;
 
+/// Hook script for Git for Windows.
+///
+/// Git for Windows runs hooks with its bundled `sh`, whose `PATH` lookup does
+/// not find `.cmd` shims (npm, pip and scoop installs). Those are started
+/// through `cmd`; path conversion is disabled so arguments reach gcop-rs as is.
+const HOOK_SCRIPT_WINDOWS: &str = r#"#!/bin/sh
+# gcop-rs prepare-commit-msg hook
+# Installed by: gcop-rs hook install
+# To remove: gcop-rs hook uninstall
+export MSYS_NO_PATHCONV=1
+if command -v gcop-rs >/dev/null 2>&1; then
+    exec gcop-rs hook run "$1" "$2" "$3"
+fi
+if command -v gcop-rs.cmd >/dev/null 2>&1; then
+    exec cmd /c gcop-rs.cmd hook run "$1" "$2" "$3"
+fi
+exit 0
+"#;
+
+/// Hook script for the current platform (always LF line endings)
+fn hook_script() -> &'static str {
+    if cfg!(windows) {
+        HOOK_SCRIPT_WINDOWS
+    } else {
+        HOOK_SCRIPT
+    }
+}
+
+/// State of the prepare-commit-msg hook in a repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookState {
+    /// No hook file
+    Missing,
+    /// Hook installed by gcop-rs
+    Installed,
+    /// Hook written by something else
+    Foreign,
+}
+
+/// Inspect the prepare-commit-msg hook of the repository at `git_root`.
+pub fn hook_state(git_root: &Path) -> HookState {
+    let hook_path = hooks_dir(git_root).join("prepare-commit-msg");
+    match fs::read_to_string(hook_path) {
+        Ok(content) if content.contains(HOOK_MARKER) => HookState::Installed,
+        Ok(_) => HookState::Foreign,
+        Err(_) => HookState::Missing,
+    }
+}
+
 /// Install the prepare-commit-msg hook into the current git repository.
 ///
 /// If the hook already exists and was installed by gcop-rs, prints an info message.
@@ -38,7 +89,7 @@ pub fn install(force: bool) -> Result<()> {
         )))
     })?;
 
-    let hooks_dir = git_root.join(".git").join("hooks");
+    let hooks_dir = hooks_dir(&git_root);
     fs::create_dir_all(&hooks_dir)?;
 
     let hook_path = hooks_dir.join("prepare-commit-msg");
@@ -71,7 +122,7 @@ pub fn install(force: bool) -> Result<()> {
         );
     }
 
-    fs::write(&hook_path, HOOK_SCRIPT)?;
+    fs::write(&hook_path, hook_script())?;
 
     // Set executable permission on Unix
     #[cfg(unix)]
@@ -100,10 +151,7 @@ pub fn uninstall() -> Result<()> {
         )))
     })?;
 
-    let hook_path = git_root
-        .join(".git")
-        .join("hooks")
-        .join("prepare-commit-msg");
+    let hook_path = hooks_dir(&git_root).join("prepare-commit-msg");
 
     if !hook_path.exists() {
         eprintln!("{}", rust_i18n::t!("hook.no_hook_found"));
@@ -129,8 +177,10 @@ pub fn uninstall() -> Result<()> {
 /// Safe wrapper for `run_hook_inner` that catches and prints errors to stderr.
 ///
 /// This function is called from the CLI when `gcop-rs hook run` is invoked
-/// by the prepare-commit-msg hook script. Errors are printed but do not
-/// cause git commit to fail (exit code 0).
+/// by the prepare-commit-msg hook script. Generation is bounded by
+/// `[hook].timeout_secs`; failures are printed and handled by
+/// `[hook].on_failure`. Only `abort` returns the error, which makes the hook
+/// exit non-zero so git aborts the commit.
 ///
 /// # Arguments
 /// * `commit_msg_file` - Path to the file containing the commit message (from git)
@@ -139,6 +189,7 @@ pub fn uninstall() -> Result<()> {
 /// * `config` - Application configuration
 /// * `verbose` - Whether verbose mode is enabled
 /// * `provider_override` - Optional provider name override
+/// * `model_override` - Optional model override for the selected provider
 pub async fn run_hook_safe(
     commit_msg_file: &str,
     source: &str,
@@ -146,21 +197,68 @@ pub async fn run_hook_safe(
     config: &AppConfig,
     verbose: bool,
     provider_override: Option<&str>,
-) {
-    if let Err(e) = run_hook_inner(
+    model_override: Option<&str>,
+) -> Result<()> {
+    let run = run_hook_inner(
         commit_msg_file,
         source,
         sha,
         config,
         verbose,
         provider_override,
-    )
-    .await
-    {
-        eprintln!("gcop-rs: {}", e.localized_message());
+        model_override,
+    );
+    // The message file is only written once generation succeeded, so a
+    // timed-out run leaves it untouched
+    let timeout_secs = config.hook.timeout_secs;
+    let result = if timeout_secs == 0 {
+        run.await
+    } else {
+        tokio::time::timeout(Duration::from_secs(timeout_secs), run)
+            .await
+            .unwrap_or_else(|_| {
+                Err(GcopError::Other(
+                    rust_i18n::t!("hook.timed_out", secs = timeout_secs).to_string(),
+                ))
+            })
+    };
+    let Err(e) = result else {
+        return Ok(());
+    };
+
+    // stderr of hooks is often hidden; keep the cause in the `[logging]` file
+    tracing::debug!("prepare-commit-msg hook failed: {}", e);
+    eprintln!("gcop-rs: {}", e.localized_message());
+    match config.hook.on_failure {
+        HookFailure::Empty => Ok(()),
+        HookFailure::Abort => {
+            eprintln!("gcop-rs: {}", rust_i18n::t!("hook.aborting"));
+            Err(e)
+        }
+        HookFailure::Placeholder => {
+            if let Err(e) = write_placeholder(Path::new(commit_msg_file)) {
+                tracing::debug!("Failed to write placeholder message: {}", e);
+            }
+            Ok(())
+        }
     }
 }
 
+/// Puts a placeholder subject above git's comment lines, unless the file
+/// already holds a message (amend, reword, `commit.template`).
+fn write_placeholder(commit_msg_file: &Path) -> Result<()> {
+    let contents = fs::read_to_string(commit_msg_file).unwrap_or_default();
+    if extract_previous_message(&contents).is_some() {
+        return Ok(());
+    }
+    let placeholder = rust_i18n::t!("hook.placeholder");
+    fs::write(
+        commit_msg_file,
+        format!("{}\n{}", placeholder, contents.trim_start_matches('\n')),
+    )?;
+    Ok(())
+}
+
 /// Result of analyzing hook source and sha parameters.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum HookMode {
@@ -170,6 +268,8 @@ enum HookMode {
     Normal,
     /// Amend commit: generate message from the original commit's diff
     Amend,
+    /// Reword during an interactive rebase: regenerate the picked commit's message
+    Reword,
 }
 
 /// Determines the hook mode based on `source` and `sha` parameters from git.
@@ -179,23 +279,62 @@ enum HookMode {
 /// - `$2` (source): `"message"`, `"merge"`, `"commit"`, `"squash"`, or `""` (empty)
 /// - `$3` (sha): commit SHA (non-empty only for `--amend`)
 ///
-/// | source     | sha       | mode   | rationale                                  |
-/// |------------|-----------|--------|--------------------------------------------|
-/// | `message`  | *         | Skip   | user already provided `-m` / `-C` / `-c`   |
-/// | `merge`    | *         | Skip   | merge commit message auto-generated        |
-/// | `squash`   | *         | Skip   | squash merge message auto-generated        |
-/// | `commit`   | empty     | Skip   | non-amend reuse (e.g. `git commit -C`)     |
-/// | `commit`   | non-empty | Amend  | `--amend` with known target SHA            |
-/// | `""` / _   | *         | Normal | regular `git commit`                       |
-fn determine_hook_mode(source: &str, sha: &str) -> HookMode {
+/// `rewording` is `true` when an interactive rebase is currently applying a
+/// `reword` step (see [`is_rebase_reword`]); git then passes the picked
+/// commit's message with source `message` (or `commit`).
+///
+/// | source     | sha       | rewording | mode   | rationale                                  |
+/// |------------|-----------|-----------|--------|--------------------------------------------|
+/// | `message`  | *         | yes       | Reword | rebase `reword` step                       |
+/// | `message`  | *         | no        | Skip   | user already provided `-m` / `-C` / `-c`   |
+/// | `merge`    | *         | *         | Skip   | merge commit message auto-generated        |
+/// | `squash`   | *         | *         | Skip   | squash merge message auto-generated        |
+/// | `commit`   | empty     | yes       | Reword | rebase `reword` step                       |
+/// | `commit`   | empty     | no        | Skip   | non-amend reuse (e.g. `git commit -C`)     |
+/// | `commit`   | non-empty | *         | Amend  | `--amend` with known target SHA            |
+/// | `""` / _   | *         | *         | Normal | regular `git commit`                       |
+fn determine_hook_mode(source: &str, sha: &str, rewording: bool) -> HookMode {
     match source {
+        "message" if rewording => HookMode::Reword,
         "message" | "merge" | "squash" => HookMode::Skip,
+        "commit" if sha.is_empty() && rewording => HookMode::Reword,
         "commit" if sha.is_empty() => HookMode::Skip,
         "commit" => HookMode::Amend,
         _ => HookMode::Normal,
     }
 }
 
+/// Returns `true` if an interactive rebase is applying a `reword` step.
+///
+/// Reads the last command from `<git-dir>/rebase-merge/done`.
+fn is_rebase_reword(git_dir: &Path) -> bool {
+    fs::read_to_string(git_dir.join("rebase-merge").join("done"))
+        .map(|done| last_rebase_command_is_reword(&done))
+        .unwrap_or(false)
+}
+
+/// Checks whether the most recent todo command in a rebase `done` file is `reword`.
+fn last_rebase_command_is_reword(done: &str) -> bool {
+    done.lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty() && !line.starts_with('#'))
+        .and_then(|line| line.split_whitespace().next())
+        .is_some_and(|cmd| cmd == "reword" || cmd == "r")
+}
+
+/// Extracts the existing message from the commit message file, dropping
+/// git's `#` comment lines and anything below the scissors line.
+pub(crate) fn extract_previous_message(contents: &str) -> Option<String> {
+    let message = contents
+        .lines()
+        .take_while(|line| !line.starts_with("# ------------------------ >8"))
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let message = message.trim();
+    (!message.is_empty()).then(|| message.to_string())
+}
+
 /// Internal hook logic that generates a commit message and writes it to the
 /// commit message file.
 ///
@@ -203,6 +342,10 @@ fn determine_hook_mode(source: &str, sha: &str) -> HookMode {
 /// provided (message, merge, squash). For `source == "commit"` (amend), skips
 /// only when `sha` is empty (e.g. `git commit -C`); when `sha` is non-empty,
 /// generates a new message based on the amend target's diff.
+///
+/// During an interactive rebase `reword` step the staged diff is used, falling
+/// back to the `HEAD` commit diff when nothing is staged. For amend and reword,
+/// the existing message is passed to the LLM as context.
 async fn run_hook_inner(
     commit_msg_file: &str,
     source: &str,
@@ -210,55 +353,118 @@ async fn run_hook_inner(
     config: &AppConfig,
     _verbose: bool,
     provider_override: Option<&str>,
+    model_override: Option<&str>,
 ) -> Result<()> {
-    let mode = determine_hook_mode(source, sha);
-    if mode == HookMode::Skip {
+    // Sources skipped regardless of rebase state: no need to open the repository.
+    // `gcop-rs reword` provides the message itself.
+    if determine_hook_mode(source, sha, true) == HookMode::Skip
+        || std::env::var_os(crate::git::reword::REWORD_VAR).is_some()
+    {
         return Ok(());
     }
 
-    let is_amend = mode == HookMode::Amend;
-
     // Open repository
     let repo = GitRepository::open(Some(&config.file))?;
 
+    let mode = determine_hook_mode(source, sha, is_rebase_reword(repo.git_dir()));
+    if mode == HookMode::Skip {
+        return Ok(());
+    }
+
     // Get diff based on scenario
-    let diff = if is_amend {
-        // Amend scenario: get the original commit's diff
-        let commit_diff = repo.get_commit_diff(sha)?;
-        if repo.has_staged_changes()? {
-            // Amend with additional staged changes: combine both diffs
-            let staged_diff = repo.get_staged_diff()?;
-            format!("{}\n{}", commit_diff, staged_diff)
-        } else {
-            // Amend without new staged changes (pure message rewrite)
-            commit_diff
+    let diff = match mode {
+        HookMode::Amend => {
+            // Amend scenario: get the original commit's diff
+            let commit_diff = repo.get_commit_diff(sha)?;
+            if repo.has_staged_changes()? {
+                // Amend with additional staged changes: combine both diffs
+                let staged_diff = repo.get_staged_diff()?;
+                format!("{}\n{}", commit_diff, staged_diff)
+            } else {
+                // Amend without new staged changes (pure message rewrite)
+                commit_diff
+            }
         }
-    } else {
-        // Normal commit: require staged changes
-        if !repo.has_staged_changes()? {
-            return Ok(());
+        HookMode::Reword => {
+            // Reword: the picked changes are staged, or already committed as HEAD
+            if repo.has_staged_changes()? {
+                repo.get_staged_diff()?
+            } else {
+                repo.get_commit_diff("HEAD")?
+            }
+        }
+        _ => {
+            // Normal commit: require staged changes
+            if !repo.has_staged_changes()? {
+                return Ok(());
+            }
+            repo.get_staged_diff()?
         }
-        repo.get_staged_diff()?
+    };
+
+    // Reuse the message generated for the same staged tree (editor aborted, commit retried)
+    let cache = if config.hook.cache_ttl_secs > 0 {
+        repo.staged_tree().ok().map(|tree| {
+            let mode = format!("{:?}", mode).to_lowercase();
+            (
+                hook_cache::cache_path(repo.git_dir()),
+                hook_cache::cache_key(&tree, &mode, sha),
+            )
+        })
+    } else {
+        None
+    };
+    if let Some((path, key)) = &cache
+        && !hook_cache::regenerate_requested()
+        && let Some(message) =
+            hook_cache::load(path, key, Duration::from_secs(config.hook.cache_ttl_secs))
+    {
+        fs::write(commit_msg_file, &message)?;
+        eprintln!(
+            "gcop-rs: {}",
+            rust_i18n::t!("hook.cached", var = hook_cache::REGENERATE_VAR)
+        );
+        return Ok(());
+    }
+
+    // Existing message (amend/reword) is passed to the LLM as context
+    let previous_message = if mode == HookMode::Normal {
+        None
+    } else {
+        fs::read_to_string(commit_msg_file)
+            .ok()
+            .and_then(|contents| extract_previous_message(&contents))
     };
 
     let stats = repo.get_diff_stats(&diff)?;
 
+    // Apply [privacy].on_secret before anything is sent
+    let diff = super::privacy::guard(diff, config, false, false)?;
+
     // Truncate diff to fit LLM token limit
     let (diff, _) = smart_truncate_diff(&diff, config.llm.max_diff_size);
 
     // Get current branch name
     let branch_name = repo.get_current_branch()?;
 
+    let ticket = super::ticket::resolve_ticket(branch_name.as_deref(), &config.commit);
+    let trailers = super::trailers::resolve_trailers(&repo, &config.commit.trailers, false, &[])?;
+
     // Build commit context
     let context = CommitContext {
         files_changed: stats.files_changed,
         insertions: stats.insertions,
         deletions: stats.deletions,
         branch_name,
+        ticket,
         custom_prompt: config.commit.custom_prompt.clone(),
         user_feedback: vec![],
         convention: config.commit.convention.clone(),
         scope_info: None, // Hook mode does not currently support workspace scope
+        history_examples: super::commit::compute_history_examples(&repo, config),
+        previous_message,
+        body: config.commit.body,
+        body_bullets: config.commit.body_bullets,
     };
 
     // Build prompt
@@ -270,18 +476,45 @@ async fn run_hook_inner(
     );
 
     // Create LLM provider
-    let provider = create_provider(config, provider_override)?;
+    let provider = create_provider(
+        config,
+        &config.commit.llm,
+        provider_override,
+        model_override,
+    )?;
 
     // Print status to stderr (stdout must not be used in hooks)
-    if is_amend {
-        eprintln!("gcop-rs: {}", rust_i18n::t!("hook.generating_amend"));
-    } else {
-        eprintln!("gcop-rs: {}", rust_i18n::t!("hook.generating"));
+    match mode {
+        HookMode::Amend => eprintln!("gcop-rs: {}", rust_i18n::t!("hook.generating_amend")),
+        HookMode::Reword => eprintln!("gcop-rs: {}", rust_i18n::t!("hook.generating_reword")),
+        _ => eprintln!("gcop-rs: {}", rust_i18n::t!("hook.generating")),
     }
 
     // Generate commit message
-    let message = provider.send_prompt(&system, &user, None).await?;
-    let message = process_commit_response(message);
+    let message = match crate::llm::provider::tiered::diff_size_scope(
+        diff.len(),
+        provider.send_prompt(&system, &user, None),
+    )
+    .await
+    {
+        Ok(message) => {
+            let message = process_commit_response(message);
+            let message =
+                super::commit::finish_message(message, &diff, &context, config, &trailers);
+            if let Some((path, key)) = &cache {
+                hook_cache::store(path, key, &message);
+            }
+            message
+        }
+        Err(e) if super::commit::can_fall_back_offline(&e, config) => {
+            eprintln!(
+                "gcop-rs: {}",
+                rust_i18n::t!("commit.offline_fallback", error = e.to_string())
+            );
+            super::commit::offline_message(&diff, &context, config, &trailers)
+        }
+        Err(e) => return Err(e),
+    };
 
     // Write generated message to the commit message file
     fs::write(commit_msg_file, &message)?;
@@ -296,41 +529,61 @@ async fn run_hook_inner(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hook_scripts_are_posix_sh() {
+        // Git for Windows 也用 sh 执行 hook：两个模板都要有 shebang、标记和 LF 换行
+        for script in [HOOK_SCRIPT, HOOK_SCRIPT_WINDOWS] {
+            assert!(script.starts_with("#!/bin/sh\n"));
+            assert!(script.contains(HOOK_MARKER));
+            assert!(!script.contains('\r'));
+        }
+        assert!(HOOK_SCRIPT_WINDOWS.contains("gcop-rs.cmd"));
+    }
+
     // === determine_hook_mode tests ===
 
     #[test]
     fn test_source_message_skips() {
-        assert_eq!(determine_hook_mode("message", ""), HookMode::Skip);
-        assert_eq!(determine_hook_mode("message", "abc123"), HookMode::Skip);
+        assert_eq!(determine_hook_mode("message", "", false), HookMode::Skip);
+        assert_eq!(
+            determine_hook_mode("message", "abc123", false),
+            HookMode::Skip
+        );
     }
 
     #[test]
     fn test_source_merge_skips() {
-        assert_eq!(determine_hook_mode("merge", ""), HookMode::Skip);
-        assert_eq!(determine_hook_mode("merge", "abc123"), HookMode::Skip);
+        assert_eq!(determine_hook_mode("merge", "", false), HookMode::Skip);
+        assert_eq!(
+            determine_hook_mode("merge", "abc123", false),
+            HookMode::Skip
+        );
     }
 
     #[test]
     fn test_source_squash_skips() {
-        assert_eq!(determine_hook_mode("squash", ""), HookMode::Skip);
-        assert_eq!(determine_hook_mode("squash", "abc123"), HookMode::Skip);
+        assert_eq!(determine_hook_mode("squash", "", false), HookMode::Skip);
+        assert_eq!(
+            determine_hook_mode("squash", "abc123", false),
+            HookMode::Skip
+        );
     }
 
     #[test]
     fn test_source_commit_empty_sha_skips() {
         // git commit -C / -c without amend: source is "commit" but sha is empty
-        assert_eq!(determine_hook_mode("commit", ""), HookMode::Skip);
+        assert_eq!(determine_hook_mode("commit", "", false), HookMode::Skip);
     }
 
     #[test]
     fn test_source_commit_with_sha_is_amend() {
         // git commit --amend: source is "commit" and sha is the HEAD commit hash
         assert_eq!(
-            determine_hook_mode("commit", "abc123def456"),
+            determine_hook_mode("commit", "abc123def456", false),
             HookMode::Amend
         );
         assert_eq!(
-            determine_hook_mode("commit", "a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2"),
+            determine_hook_mode("commit", "a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2", false),
             HookMode::Amend
         );
     }
@@ -338,13 +591,88 @@ mod tests {
     #[test]
     fn test_empty_source_is_normal() {
         // Regular git commit: source is empty string
-        assert_eq!(determine_hook_mode("", ""), HookMode::Normal);
+        assert_eq!(determine_hook_mode("", "", false), HookMode::Normal);
+    }
+
+    #[test]
+    fn test_rebase_reword_regenerates() {
+        // Interactive rebase reword passes the picked message with source "message"
+        assert_eq!(determine_hook_mode("message", "", true), HookMode::Reword);
+        assert_eq!(determine_hook_mode("commit", "", true), HookMode::Reword);
+        // An explicit amend target still wins
+        assert_eq!(
+            determine_hook_mode("commit", "abc123", true),
+            HookMode::Amend
+        );
+        // Merge/squash messages are never regenerated
+        assert_eq!(determine_hook_mode("merge", "", true), HookMode::Skip);
+        assert_eq!(determine_hook_mode("squash", "", true), HookMode::Skip);
+    }
+
+    // === rebase / message helpers tests ===
+
+    #[test]
+    fn test_last_rebase_command_is_reword() {
+        assert!(last_rebase_command_is_reword(
+            "pick abc123 feat: one\nreword def456 fix: two\n"
+        ));
+        assert!(last_rebase_command_is_reword(
+            "pick abc123 a\nr def456 b\n\n"
+        ));
+        assert!(!last_rebase_command_is_reword(
+            "reword abc123 a\npick def456 b\n"
+        ));
+        assert!(!last_rebase_command_is_reword(""));
+    }
+
+    #[test]
+    fn test_is_rebase_reword_without_rebase_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_rebase_reword(dir.path()));
+
+        std::fs::create_dir(dir.path().join("rebase-merge")).unwrap();
+        std::fs::write(
+            dir.path().join("rebase-merge").join("done"),
+            "reword abc123 fix: typo\n",
+        )
+        .unwrap();
+        assert!(is_rebase_reword(dir.path()));
+    }
+
+    #[test]
+    fn test_extract_previous_message_strips_comments() {
+        let contents = "fix: typo in README\n\nLonger body.\n\n# Please enter the commit message\n# ------------------------ >8 ------------------------\ndiff --git a/x b/x\n";
+        assert_eq!(
+            extract_previous_message(contents).as_deref(),
+            Some("fix: typo in README\n\nLonger body.")
+        );
+        assert_eq!(extract_previous_message("# only comments\n\n"), None);
+    }
+
+    #[test]
+    fn test_write_placeholder_keeps_existing_message() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("COMMIT_EDITMSG");
+
+        // 只有注释时在注释上方写入占位主题
+        fs::write(&path, "\n# Please enter the commit message\n").unwrap();
+        write_placeholder(&path).unwrap();
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with(&format!("{}\n#", rust_i18n::t!("hook.placeholder"))));
+
+        // 已有消息（amend/reword）时保持不变
+        fs::write(&path, "fix: keep me\n# comment\n").unwrap();
+        write_placeholder(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "fix: keep me\n# comment\n"
+        );
     }
 
     #[test]
     fn test_unknown_source_is_normal() {
         // Any unrecognized source falls through to normal
-        assert_eq!(determine_hook_mode("template", ""), HookMode::Normal);
-        assert_eq!(determine_hook_mode("unknown", ""), HookMode::Normal);
+        assert_eq!(determine_hook_mode("template", "", false), HookMode::Normal);
+        assert_eq!(determine_hook_mode("unknown", "", false), HookMode::Normal);
     }
 }
@@ -0,0 +1,116 @@
+//! Cached hook messages for editor retries.
+//!
+//! When the user aborts the editor opened by `git commit` and runs it again,
+//! the `prepare-commit-msg` hook would generate (and pay for) the same
+//! message. The last generated message is kept in `.git/gcop/hook-message.json`
+//! keyed by the staged tree hash and reused within `[hook].cache_ttl_secs`.
+//! Setting [`REGENERATE_VAR`] forces a new message; failures only cost the cache.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::review_snapshot::STATE_DIR;
+
+/// Environment variable forcing the hook to regenerate the message.
+pub const REGENERATE_VAR: &str = "GCOP_HOOK_REGENERATE";
+
+/// Cache file inside [`STATE_DIR`].
+const CACHE_FILE: &str = "hook-message.json";
+
+/// Bumped when the cached payload or key inputs change meaning.
+const CACHE_VERSION: u32 = 1;
+
+/// The cached message.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Entry format version.
+    version: u32,
+    /// What the message was generated for (see [`cache_key`]).
+    key: String,
+    /// Generation time, in seconds since the Unix epoch.
+    created_at: u64,
+    /// The generated message.
+    message: String,
+}
+
+/// Key of a message generated for the staged `tree`; amend and reword
+/// messages also depend on the `mode` and the amended commit `sha`.
+pub(crate) fn cache_key(tree: &str, mode: &str, sha: &str) -> String {
+    format!("{}:{}:{}", mode, tree, sha)
+}
+
+/// Cache file of the repository whose git directory is `git_dir`.
+pub(crate) fn cache_path(git_dir: &Path) -> PathBuf {
+    git_dir.join(STATE_DIR).join(CACHE_FILE)
+}
+
+/// Whether [`REGENERATE_VAR`] asks for a new message.
+pub(crate) fn regenerate_requested() -> bool {
+    std::env::var(REGENERATE_VAR)
+        .map(|value| !matches!(value.trim(), "" | "0" | "false"))
+        .unwrap_or(false)
+}
+
+/// Cached message for `key`, if generated less than `ttl` ago.
+pub(crate) fn load(path: &Path, key: &str, ttl: Duration) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let entry = match serde_json::from_str::<CacheEntry>(&content) {
+        Ok(entry) => entry,
+        Err(e) => {
+            tracing::debug!("Ignoring invalid {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    let age = now().saturating_sub(entry.created_at);
+    (entry.version == CACHE_VERSION && entry.key == key && age < ttl.as_secs())
+        .then_some(entry.message)
+}
+
+/// Stores `message` under `key`, replacing the previous entry; failures are logged.
+pub(crate) fn store(path: &Path, key: &str, message: &str) {
+    let entry = CacheEntry {
+        version: CACHE_VERSION,
+        key: key.to_string(),
+        created_at: now(),
+        message: message.to_string(),
+    };
+    let write = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| serde_json::to_string_pretty(&entry).map_err(std::io::Error::other))
+        .and_then(|content| std::fs::write(path, content + "\n"));
+    if let Err(e) = write {
+        tracing::debug!("Failed to cache hook message in {}: {}", path.display(), e);
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip_is_keyed_by_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = cache_path(dir.path());
+        let ttl = Duration::from_secs(600);
+        let key = cache_key("4b825dc", "normal", "");
+
+        assert!(load(&path, &key, ttl).is_none());
+        store(&path, &key, "feat: add cache\n");
+        assert_eq!(load(&path, &key, ttl).as_deref(), Some("feat: add cache\n"));
+
+        // 暂存内容变化即失效
+        assert!(load(&path, &cache_key("e69de29", "normal", ""), ttl).is_none());
+        // 超过有效期即失效
+        assert!(load(&path, &key, Duration::ZERO).is_none());
+    }
+}
@@ -1,20 +1,53 @@
-use crate::config;
+use crate::config::{self, ApiStyle, AppConfig, CommandLlmConfig, ConventionStyle, keychain};
 use crate::error::{GcopError, Result};
 use crate::git::find_git_root;
+use crate::llm::provider::create_provider;
+use crate::llm::provider::models::fetch_models;
 use crate::ui;
 use std::fs;
+use std::path::PathBuf;
+
+/// Providers offered by the setup wizard, with their display names
+const WIZARD_PROVIDERS: &[(ApiStyle, &str)] = &[
+    (ApiStyle::Claude, "Claude (Anthropic)"),
+    (ApiStyle::OpenAI, "OpenAI"),
+    (ApiStyle::Gemini, "Gemini (Google)"),
+    (ApiStyle::DeepSeek, "DeepSeek"),
+    (ApiStyle::Moonshot, "Moonshot (Kimi)"),
+    (ApiStyle::Ollama, "Ollama"),
+];
+
+/// Comment at the top of a config file written by the wizard
+const WIZARD_HEADER: &str = "\
+# gcop-rs Configuration File (written by `gcop-rs init`)
+# Permissions: chmod 600 recommended (may contain API keys, DO NOT commit to git)
+#
+# Full documentation: https://gcop.docs.esap.cc/guide/configuration.html
+";
 
 /// Initialization configuration file
-pub fn run(force: bool, project: bool, colored: bool) -> Result<()> {
-    if project {
+///
+/// `from_gcop` is `Some` for `--from-gcop [PATH]` (the inner path is optional).
+/// The user config is written by the setup wizard in a terminal, or copied
+/// from the commented example with `template` (and without a terminal).
+pub async fn run(
+    force: bool,
+    project: bool,
+    template: bool,
+    from_gcop: Option<Option<PathBuf>>,
+    colored: bool,
+) -> Result<()> {
+    if let Some(source) = from_gcop {
+        crate::commands::migrate::run(source.as_deref(), force, colored)
+    } else if project {
         run_project_init(force, colored)
     } else {
-        run_user_init(force, colored)
+        run_user_init(force, template || !ui::is_interactive(), colored).await
     }
 }
 
 /// Initialize user-level configuration file
-fn run_user_init(force: bool, colored: bool) -> Result<()> {
+async fn run_user_init(force: bool, template: bool, colored: bool) -> Result<()> {
     // 1. Get the configuration directory and file path
     let config_dir = config::get_config_dir().ok_or_else(|| {
         GcopError::Config(rust_i18n::t!("config.failed_determine_dir").to_string())
@@ -22,10 +55,11 @@ fn run_user_init(force: bool, colored: bool) -> Result<()> {
 
     let config_file = config_dir.join("config.toml");
 
-    // 2. Check if the configuration file already exists
-    if config_file.exists() && !force {
+    // 2. Check if the configuration file (or its encrypted variant) already exists
+    let existing = config::user_config_path().filter(|path| path.exists());
+    if let Some(existing) = existing.filter(|_| !force) {
         ui::warning(
-            &rust_i18n::t!("init.exists", path = config_file.display()),
+            &rust_i18n::t!("init.exists", path = existing.display()),
             colored,
         );
         println!();
@@ -34,22 +68,28 @@ fn run_user_init(force: bool, colored: bool) -> Result<()> {
         return Ok(());
     }
 
-    // 3. Create configuration directory
+    // 3. Ask the wizard questions before touching the file system
+    let content = if template {
+        include_str!("../../examples/config.toml.example").to_string()
+    } else {
+        run_wizard(colored).await?
+    };
+
+    // 4. Create configuration directory
     fs::create_dir_all(&config_dir)?;
     ui::success(
         &rust_i18n::t!("init.dir_created", path = config_dir.display()),
         colored,
     );
 
-    // 4. Copy the sample configuration
-    let example_config = include_str!("../../examples/config.toml.example");
-    fs::write(&config_file, example_config)?;
+    // 5. Write the configuration
+    fs::write(&config_file, content)?;
     ui::success(
         &rust_i18n::t!("init.file_created", path = config_file.display()),
         colored,
     );
 
-    // 5. Set file permissions (Unix only)
+    // 6. Set file permissions (Unix only)
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
@@ -59,22 +99,24 @@ fn run_user_init(force: bool, colored: bool) -> Result<()> {
         ui::success(&rust_i18n::t!("init.permissions"), colored);
     }
 
-    // 6. Display next step prompt
-    println!();
-    println!("{}", ui::info(&rust_i18n::t!("init.next_steps"), colored));
-    println!("{}", rust_i18n::t!("init.step1"));
-    println!("{}", rust_i18n::t!("init.step1_cmd"));
-    println!();
-    println!("{}", rust_i18n::t!("init.step2"));
-    println!("{}", rust_i18n::t!("init.step2_url"));
+    // 7. Display next step prompt (the wizard already filled in the provider)
     println!();
+    if template {
+        println!("{}", ui::info(&rust_i18n::t!("init.next_steps"), colored));
+        println!("{}", rust_i18n::t!("init.step1"));
+        println!("{}", rust_i18n::t!("init.step1_cmd"));
+        println!();
+        println!("{}", rust_i18n::t!("init.step2"));
+        println!("{}", rust_i18n::t!("init.step2_url"));
+        println!();
+    }
 
-    // 7. Ask whether to install git aliases
+    // 8. Ask whether to install git aliases
     let install_aliases = ui::confirm(&rust_i18n::t!("init.install_aliases"), true)?;
 
     if install_aliases {
         println!();
-        match crate::commands::alias::install_all(force, colored) {
+        match crate::commands::alias::install_all(force, &Default::default(), colored) {
             Ok(_) => {}
             Err(e) => {
                 ui::warning(
@@ -98,6 +140,182 @@ fn run_user_init(force: bool, colored: bool) -> Result<()> {
     Ok(())
 }
 
+/// Answers collected by the setup wizard
+struct WizardAnswers {
+    style: ApiStyle,
+    model: String,
+    /// Key written to the config file; `None` when kept in the keychain or skipped
+    api_key: Option<String>,
+    language: Option<String>,
+    convention: ConventionStyle,
+}
+
+/// Guided setup: provider, API key, model, language and convention, then a
+/// connection test. Returns the config file content.
+async fn run_wizard(colored: bool) -> Result<String> {
+    println!("{}", ui::info(&rust_i18n::t!("init.wizard_intro"), colored));
+    println!();
+
+    // 1. Provider
+    let names: Vec<&str> = WIZARD_PROVIDERS.iter().map(|(_, label)| *label).collect();
+    let choice =
+        inquire::Select::new(&rust_i18n::t!("init.wizard_provider"), names).raw_prompt()?;
+    let style = WIZARD_PROVIDERS[choice.index].0;
+    let name = style.to_string();
+
+    let mut answers = WizardAnswers {
+        style,
+        model: style.default_model().to_string(),
+        api_key: None,
+        language: None,
+        convention: ConventionStyle::Conventional,
+    };
+
+    // 2. API key, in the keychain when possible
+    if style != ApiStyle::Ollama {
+        let key = inquire::Password::new(&rust_i18n::t!("init.wizard_api_key"))
+            .with_display_mode(inquire::PasswordDisplayMode::Masked)
+            .without_confirmation()
+            .with_help_message(&rust_i18n::t!("init.wizard_api_key_help"))
+            .prompt()?;
+        let key = key.trim().to_string();
+        if key.is_empty() {
+            ui::warning(
+                &rust_i18n::t!("init.wizard_api_key_skipped", provider = name),
+                colored,
+            );
+        } else if keychain::is_available()
+            && ui::confirm(&rust_i18n::t!("init.wizard_keychain"), true)?
+        {
+            match keychain::store(&name, &key) {
+                Ok(()) => ui::success(&rust_i18n::t!("init.wizard_keychain_stored"), colored),
+                Err(e) => {
+                    ui::warning(&e.to_string(), colored);
+                    answers.api_key = Some(key);
+                }
+            }
+        } else {
+            answers.api_key = Some(key);
+        }
+    }
+
+    // 3. Model, from the provider's list when it can be fetched
+    let config = wizard_config(&answers)?;
+    let spinner = ui::Spinner::new(&rust_i18n::t!("init.wizard_fetching_models"), colored);
+    let models = fetch_models(&name, style, &config.llm.providers[&name], &config.network).await;
+    spinner.finish_and_clear();
+    answers.model = match models {
+        Ok(models) if !models.is_empty() => {
+            let cursor = models.iter().position(|m| *m == answers.model).unwrap_or(0);
+            inquire::Select::new(&rust_i18n::t!("init.wizard_model"), models)
+                .with_starting_cursor(cursor)
+                .prompt()?
+        }
+        result => {
+            if let Err(e) = result {
+                ui::warning(
+                    &rust_i18n::t!("init.wizard_models_failed", error = e.to_string()),
+                    colored,
+                );
+            }
+            inquire::Text::new(&rust_i18n::t!("init.wizard_model"))
+                .with_default(&answers.model)
+                .prompt()?
+        }
+    };
+
+    // 4. Language and commit convention
+    let languages = [
+        (None, rust_i18n::t!("init.wizard_language_auto").to_string()),
+        (Some("en"), "English".to_string()),
+        (Some("zh-CN"), "简体中文".to_string()),
+    ];
+    let labels: Vec<&str> = languages.iter().map(|(_, label)| label.as_str()).collect();
+    let choice =
+        inquire::Select::new(&rust_i18n::t!("init.wizard_language"), labels).raw_prompt()?;
+    answers.language = languages[choice.index].0.map(str::to_string);
+
+    let conventions = [
+        (
+            ConventionStyle::Conventional,
+            "Conventional Commits (feat(scope): ...)",
+        ),
+        (ConventionStyle::Gitmoji, "Gitmoji (:sparkles: ...)"),
+    ];
+    let labels: Vec<&str> = conventions.iter().map(|(_, label)| *label).collect();
+    let choice =
+        inquire::Select::new(&rust_i18n::t!("init.wizard_convention"), labels).raw_prompt()?;
+    answers.convention = conventions[choice.index].0.clone();
+
+    // 5. Connection test
+    let config = wizard_config(&answers)?;
+    let spinner = ui::Spinner::new(&rust_i18n::t!("config.testing"), colored);
+    let result = match create_provider(&config, &CommandLlmConfig::default(), None, None) {
+        Ok(provider) => provider.validate().await,
+        Err(e) => Err(e),
+    };
+    spinner.finish_and_clear();
+    match result {
+        Ok(()) => ui::success(&rust_i18n::t!("config.validated", provider = name), colored),
+        Err(e) => {
+            ui::error(
+                &rust_i18n::t!("config.validation_failed_short", error = e.to_string()),
+                colored,
+            );
+            if !ui::confirm(&rust_i18n::t!("init.wizard_save_anyway"), true)? {
+                return Err(GcopError::UserCancelled);
+            }
+        }
+    }
+
+    Ok(render_wizard_config(&answers))
+}
+
+/// Configuration the wizard would write, as loaded by gcop-rs
+fn wizard_config(answers: &WizardAnswers) -> Result<AppConfig> {
+    Ok(super::config::check_content(&render_wizard_config(
+        answers,
+    ))?)
+}
+
+/// Config file content for the wizard answers
+fn render_wizard_config(answers: &WizardAnswers) -> String {
+    use toml_edit::{DocumentMut, table, value};
+
+    let name = answers.style.to_string();
+    let mut doc = DocumentMut::new();
+    doc["llm"] = table();
+    doc["llm"]["default_provider"] = value(&name);
+    doc["llm"]["providers"] = table();
+    if let Some(providers) = doc["llm"]["providers"].as_table_mut() {
+        providers.set_implicit(true);
+    }
+    doc["llm"]["providers"][&name] = table();
+    if let Some(key) = &answers.api_key {
+        doc["llm"]["providers"][&name]["api_key"] = value(key);
+    }
+    doc["llm"]["providers"][&name]["model"] = value(&answers.model);
+
+    doc["commit"] = table();
+    if let Some(commit) = doc["commit"].as_table_mut() {
+        commit.set_implicit(true);
+    }
+    doc["commit"]["convention"] = table();
+    let style = match answers.convention {
+        ConventionStyle::Conventional => "conventional",
+        ConventionStyle::Gitmoji => "gitmoji",
+        ConventionStyle::Custom => "custom",
+    };
+    doc["commit"]["convention"]["style"] = value(style);
+
+    if let Some(language) = &answers.language {
+        doc["ui"] = table();
+        doc["ui"]["language"] = value(language);
+    }
+
+    format!("{}\n{}", WIZARD_HEADER, doc)
+}
+
 /// Initialize the project-level configuration file (.gcop/config.toml)
 ///
 /// If the current directory is not in the Git repository, return to the current working directory to create `.gcop/config.toml` and give a prompt.
@@ -151,3 +369,60 @@ fn run_project_init(force: bool, colored: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard_config_round_trips() {
+        let mut answers = WizardAnswers {
+            style: ApiStyle::DeepSeek,
+            model: "deepseek-chat".to_string(),
+            api_key: Some("sk-123".to_string()),
+            language: Some("zh-CN".to_string()),
+            convention: ConventionStyle::Gitmoji,
+        };
+        let config = wizard_config(&answers).unwrap();
+        assert_eq!(config.llm.default_provider, "deepseek");
+        let provider = &config.llm.providers["deepseek"];
+        assert_eq!(provider.model, "deepseek-chat");
+        assert_eq!(provider.api_key.as_deref(), Some("sk-123"));
+        assert_eq!(config.ui.language.as_deref(), Some("zh-CN"));
+        assert_eq!(
+            config.commit.convention.map(|c| c.style),
+            Some(ConventionStyle::Gitmoji)
+        );
+
+        // Keys kept in the keychain never reach the file
+        answers.api_key = None;
+        answers.language = None;
+        let content = render_wizard_config(&answers);
+        assert!(!content.contains("api_key"));
+        assert!(!content.contains("[ui]"));
+        assert!(content.starts_with("# gcop-rs Configuration File"));
+    }
+
+    #[test]
+    fn test_keychain_key_is_found_by_provider() {
+        crate::llm::provider::test_utils::ensure_crypto_provider();
+        for style in [ApiStyle::OpenAI, ApiStyle::Claude, ApiStyle::Gemini] {
+            // The wizard stores the key under the name it writes to the config
+            let name = style.to_string();
+            keychain::store(&name, "sk-keychain").unwrap();
+            let answers = WizardAnswers {
+                style,
+                model: style.default_model().to_string(),
+                api_key: None,
+                language: None,
+                convention: ConventionStyle::Conventional,
+            };
+            let config = wizard_config(&answers).unwrap();
+            assert!(config.llm.providers[&name].api_key.is_none());
+            assert!(
+                crate::llm::provider::create_single_provider(&config, &name, false).is_ok(),
+                "{name}"
+            );
+        }
+    }
+}
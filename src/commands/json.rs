@@ -1,6 +1,8 @@
 use serde::Serialize;
 
+use super::format::OutputFormat;
 use crate::error::{GcopError, Result};
+use crate::llm::metrics::LlmMetrics;
 
 /// JSON error output structure (unified)
 #[derive(Debug, Serialize)]
@@ -36,6 +38,9 @@ pub struct JsonOutput<T: Serialize> {
     /// Optional error payload when `success == false`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorJson>,
+    /// LLM usage, latency and retries of commands that call a provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<LlmMetrics>,
 }
 
 /// Output errors in JSON format (generic function)
@@ -55,13 +60,53 @@ pub struct JsonOutput<T: Serialize> {
 /// # }
 /// ```
 pub fn output_json_error<T: Serialize>(err: &GcopError) -> Result<()> {
+    output_error::<T>(err, OutputFormat::Json)
+}
+
+/// Final event of `--json-stream` output: the fields of [`JsonOutput`]
+/// tagged `result` on success and `error` on failure.
+#[derive(Debug, Serialize)]
+struct StreamEvent<'a, T: Serialize> {
+    event: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<&'a T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a ErrorJson>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<&'a LlmMetrics>,
+}
+
+/// Render a JSON payload in `format`: pretty-printed, or as one
+/// `{"event":"result",...}` / `{"event":"error",...}` line for `json-stream`
+pub fn render_output<T: Serialize>(output: &JsonOutput<T>, format: OutputFormat) -> Result<String> {
+    if !format.is_json_stream() {
+        return Ok(serde_json::to_string_pretty(output)?);
+    }
+    let event = StreamEvent {
+        event: if output.success { "result" } else { "error" },
+        data: output.data.as_ref(),
+        error: output.error.as_ref(),
+        meta: output.meta.as_ref(),
+    };
+    Ok(serde_json::to_string(&event)?)
+}
+
+/// Print a JSON payload in `format` (see [`render_output`])
+pub fn print_output<T: Serialize>(output: &JsonOutput<T>, format: OutputFormat) -> Result<()> {
+    println!("{}", render_output(output, format)?);
+    Ok(())
+}
+
+/// Output errors in JSON `format`: the pretty-printed payload, or the final
+/// `error` event for `json-stream`
+pub fn output_error<T: Serialize>(err: &GcopError, format: OutputFormat) -> Result<()> {
     let output = JsonOutput::<T> {
         success: false,
         data: None,
         error: Some(ErrorJson::from_error(err)),
+        meta: None,
     };
-    println!("{}", serde_json::to_string_pretty(&output)?);
-    Ok(())
+    print_output(&output, format)
 }
 
 /// Map error type to code string
@@ -70,6 +115,7 @@ pub fn error_to_code(err: &GcopError) -> String {
         GcopError::NoStagedChanges => "NO_STAGED_CHANGES",
         GcopError::InvalidInput(_) => "INVALID_INPUT",
         GcopError::UserCancelled => "USER_CANCELLED",
+        GcopError::Interrupted => "INTERRUPTED",
         GcopError::MaxRetriesExceeded(_) => "MAX_RETRIES_EXCEEDED",
         GcopError::Config(_) => "CONFIG_ERROR",
         GcopError::Llm(_) => "LLM_ERROR",
@@ -77,7 +123,48 @@ pub fn error_to_code(err: &GcopError) -> String {
         GcopError::Network(_) => "NETWORK_ERROR",
         GcopError::Git(_) => "GIT_ERROR",
         GcopError::Io(_) => "IO_ERROR",
+        GcopError::Forge(_) => "FORGE_ERROR",
+        GcopError::SecretsDetected(_) => "SECRETS_DETECTED",
+        GcopError::ReviewGateFailed { .. } => "REVIEW_GATE_FAILED",
+        GcopError::SplitVerifyFailed { .. } => "SPLIT_VERIFY_FAILED",
+        GcopError::NonInteractive => "NON_INTERACTIVE",
         _ => "UNKNOWN_ERROR",
     }
     .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    #[test]
+    fn test_render_output_as_stream_event() {
+        let output = JsonOutput {
+            success: true,
+            data: Some("feat: add api"),
+            error: None,
+            meta: None,
+        };
+        let line = render_output(&output, OutputFormat::JsonStream).unwrap();
+        // 单行事件，event 字段在最前
+        assert_eq!(line, r#"{"event":"result","data":"feat: add api"}"#);
+
+        let pretty = render_output(&output, OutputFormat::Json).unwrap();
+        assert!(pretty.contains("\"success\": true"));
+    }
+
+    #[test]
+    fn test_render_error_as_stream_event() {
+        let output = JsonOutput::<String> {
+            success: false,
+            data: None,
+            error: Some(ErrorJson::from_error(&GcopError::NoStagedChanges)),
+            meta: None,
+        };
+        let line = render_output(&output, OutputFormat::JsonStream).unwrap();
+        let event: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(event["event"], json!("error"));
+        assert_eq!(event["error"]["code"], json!("NO_STAGED_CHANGES"));
+    }
+}
@@ -0,0 +1,418 @@
+//! Migration from the Python gcop configuration (`init --from-gcop`).
+//!
+//! Python gcop keeps a single model in `config.yaml` using LiteLLM-style
+//! model strings (`openai/gpt-4o`, `anthropic/claude-3-5-sonnet`, ...).
+//! The string prefix selects the gcop-rs provider; unknown prefixes become
+//! OpenAI-compatible providers named after the prefix.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use toml_edit::{DocumentMut, Item, Table, value};
+
+use crate::config::{self, ApiStyle};
+use crate::error::{GcopError, Result};
+use crate::ui;
+
+/// Python gcop `config.yaml`.
+#[derive(Debug, Default, Deserialize)]
+struct PyGcopConfig {
+    /// `model:` section (current format) or a bare model string.
+    #[serde(default)]
+    model: Option<PyModel>,
+    /// Top-level model keys used by older gcop versions.
+    #[serde(default)]
+    model_name: Option<String>,
+    #[serde(default)]
+    api_key: Option<String>,
+    #[serde(default)]
+    api_base: Option<String>,
+    #[serde(default)]
+    commit_template: Option<String>,
+    #[serde(default)]
+    include_git_history: Option<bool>,
+    /// Keys without a gcop-rs equivalent (reported as skipped).
+    #[serde(flatten)]
+    other: BTreeMap<String, serde_yaml_ng::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum PyModel {
+    Section {
+        #[serde(default)]
+        model_name: Option<String>,
+        #[serde(default)]
+        api_key: Option<String>,
+        #[serde(default)]
+        api_base: Option<String>,
+    },
+    Name(String),
+}
+
+/// Provider derived from a LiteLLM model string.
+#[derive(Debug, Clone, PartialEq)]
+struct ProviderMapping {
+    /// Key under `[llm.providers]`.
+    name: String,
+    /// Explicit `api_style`, only when the name does not imply it.
+    api_style: Option<ApiStyle>,
+    /// Model name without the provider prefix.
+    model: String,
+}
+
+/// Result of translating a Python gcop config.
+#[derive(Debug)]
+struct Migration {
+    /// Rendered `config.toml`.
+    toml: String,
+    /// `(python key, gcop-rs key)` pairs that were translated.
+    translated: Vec<(String, String)>,
+    /// Python keys that have no gcop-rs equivalent.
+    skipped: Vec<String>,
+}
+
+/// Default locations of the Python gcop config, in lookup order.
+fn default_source_paths() -> Vec<PathBuf> {
+    let Some(dirs) = directories::BaseDirs::new() else {
+        return Vec::new();
+    };
+    let home = dirs.home_dir();
+    vec![
+        home.join(".zeeland").join("gcop").join("config.yaml"),
+        home.join(".gcop").join("config.yaml"),
+        dirs.config_dir().join("gcop").join("config.yaml"),
+    ]
+}
+
+/// Runs `init --from-gcop [PATH]`, writing the user `config.toml`.
+pub fn run(source: Option<&Path>, force: bool, colored: bool) -> Result<()> {
+    let source = match source {
+        Some(path) => path.to_path_buf(),
+        None => default_source_paths()
+            .into_iter()
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                GcopError::Config(rust_i18n::t!("init.migrate.not_found").to_string())
+            })?,
+    };
+
+    let config_dir = config::get_config_dir().ok_or_else(|| {
+        GcopError::Config(rust_i18n::t!("config.failed_determine_dir").to_string())
+    })?;
+    let config_file = config_dir.join("config.toml");
+    if config_file.exists() && !force {
+        ui::warning(
+            &rust_i18n::t!("init.exists", path = config_file.display()),
+            colored,
+        );
+        println!();
+        println!("{}", rust_i18n::t!("init.use_force"));
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&source)?;
+    let py_config: PyGcopConfig = serde_yaml_ng::from_str(&content).map_err(|e| {
+        GcopError::Config(
+            rust_i18n::t!(
+                "init.migrate.invalid",
+                path = source.display(),
+                error = e.to_string()
+            )
+            .to_string(),
+        )
+    })?;
+    let migration = translate(&py_config, &source)?;
+
+    fs::create_dir_all(&config_dir)?;
+    fs::write(&config_file, &migration.toml)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&config_file, fs::Permissions::from_mode(0o600))?;
+    }
+
+    ui::success(
+        &rust_i18n::t!(
+            "init.migrate.done",
+            from = source.display(),
+            to = config_file.display()
+        ),
+        colored,
+    );
+    println!();
+    println!("{}", rust_i18n::t!("init.migrate.translated_title"));
+    for (from, to) in &migration.translated {
+        println!("  {} → {}", from, to);
+    }
+    if !migration.skipped.is_empty() {
+        println!();
+        println!("{}", rust_i18n::t!("init.migrate.skipped_title"));
+        for key in &migration.skipped {
+            println!("  {}", key);
+        }
+    }
+    println!();
+    println!("{}", rust_i18n::t!("init.migrate.next"));
+    Ok(())
+}
+
+/// Maps a LiteLLM model string (`provider/model`) to a gcop-rs provider.
+fn map_model(model_name: &str) -> ProviderMapping {
+    let model_name = model_name.trim();
+    let known = |name: &str, model: &str| ProviderMapping {
+        name: name.to_string(),
+        api_style: None,
+        model: model.to_string(),
+    };
+
+    match model_name.split_once('/') {
+        Some((prefix, model)) => match prefix.to_lowercase().as_str() {
+            "openai" | "text-completion-openai" => known("openai", model),
+            "anthropic" | "claude" => known("claude", model),
+            "gemini" | "google" => known("gemini", model),
+            "ollama" | "ollama_chat" => known("ollama", model),
+            "deepseek" => known("deepseek", model),
+            "moonshot" | "kimi" => known("moonshot", model),
+            other => ProviderMapping {
+                name: other.to_string(),
+                api_style: Some(ApiStyle::OpenAI),
+                model: model.to_string(),
+            },
+        },
+        // No prefix: infer from the model family, OpenAI otherwise.
+        None => {
+            let lower = model_name.to_lowercase();
+            let name = if lower.starts_with("claude") {
+                "claude"
+            } else if lower.starts_with("gemini") {
+                "gemini"
+            } else if lower.starts_with("deepseek") {
+                "deepseek"
+            } else if lower.starts_with("moonshot") || lower.starts_with("kimi") {
+                "moonshot"
+            } else {
+                "openai"
+            };
+            known(name, model_name)
+        }
+    }
+}
+
+/// Builds the gcop-rs `config.toml` for a Python gcop config.
+fn translate(py: &PyGcopConfig, source: &Path) -> Result<Migration> {
+    let (model_name, api_key, api_base, model_key) = match &py.model {
+        Some(PyModel::Section {
+            model_name,
+            api_key,
+            api_base,
+        }) => (model_name.as_deref(), api_key, api_base, "model"),
+        Some(PyModel::Name(name)) => (Some(name.as_str()), &py.api_key, &py.api_base, ""),
+        None => (py.model_name.as_deref(), &py.api_key, &py.api_base, ""),
+    };
+    let key_of = |field: &str| {
+        if model_key.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", model_key, field)
+        }
+    };
+    let model_name = model_name
+        .filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| {
+            GcopError::Config(
+                rust_i18n::t!("init.migrate.no_model", path = source.display()).to_string(),
+            )
+        })?;
+    let mapping = map_model(model_name);
+    let provider_path = format!("llm.providers.{}", mapping.name);
+
+    let mut doc = DocumentMut::new();
+    doc.decor_mut().set_prefix(format!(
+        "# gcop-rs configuration, migrated from {}\n\n",
+        source.display()
+    ));
+    let mut translated = vec![(
+        key_of("model_name"),
+        format!("{}.model = \"{}\"", provider_path, mapping.model),
+    )];
+
+    let mut llm = Table::new();
+    llm.insert("default_provider", value(&mapping.name));
+    let mut provider = Table::new();
+    if let Some(style) = mapping.api_style {
+        provider.insert("api_style", value(style.to_string()));
+    }
+    if let Some(endpoint) = api_base.as_deref().filter(|s| !s.trim().is_empty()) {
+        provider.insert("endpoint", value(endpoint));
+        translated.push((key_of("api_base"), format!("{}.endpoint", provider_path)));
+    }
+    if let Some(api_key) = api_key.as_deref().filter(|s| !s.trim().is_empty()) {
+        provider.insert("api_key", value(api_key));
+        translated.push((key_of("api_key"), format!("{}.api_key", provider_path)));
+    }
+    provider.insert("model", value(&mapping.model));
+
+    let mut providers = Table::new();
+    providers.set_implicit(true);
+    providers.insert(&mapping.name, Item::Table(provider));
+    llm.insert("providers", Item::Table(providers));
+    doc.insert("llm", Item::Table(llm));
+
+    let mut commit = Table::new();
+    if let Some(template) = py
+        .commit_template
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+    {
+        commit.insert("custom_prompt", value(template));
+        translated.push((
+            "commit_template".to_string(),
+            "commit.custom_prompt".to_string(),
+        ));
+    }
+    if let Some(history) = py.include_git_history {
+        commit.insert("learn_from_history", value(history));
+        translated.push((
+            "include_git_history".to_string(),
+            "commit.learn_from_history".to_string(),
+        ));
+    }
+    if !commit.is_empty() {
+        doc.insert("commit", Item::Table(commit));
+    }
+
+    let skipped = py.other.keys().cloned().collect();
+    Ok(Migration {
+        toml: doc.to_string(),
+        translated,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+    use pretty_assertions::assert_eq;
+
+    fn parse(yaml: &str) -> PyGcopConfig {
+        serde_yaml_ng::from_str(yaml).unwrap()
+    }
+
+    fn load(toml: &str) -> AppConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_map_model_prefixes() {
+        let cases = [
+            ("openai/gpt-4o", "openai", None, "gpt-4o"),
+            (
+                "anthropic/claude-3-5-sonnet",
+                "claude",
+                None,
+                "claude-3-5-sonnet",
+            ),
+            ("gemini/gemini-1.5-pro", "gemini", None, "gemini-1.5-pro"),
+            ("ollama_chat/llama3", "ollama", None, "llama3"),
+            ("deepseek/deepseek-chat", "deepseek", None, "deepseek-chat"),
+            // 未知前缀 → OpenAI 兼容 provider，模型名保留剩余部分
+            (
+                "openrouter/anthropic/claude-3",
+                "openrouter",
+                Some(ApiStyle::OpenAI),
+                "anthropic/claude-3",
+            ),
+            // 无前缀 → 按模型族推断
+            ("claude-3-haiku", "claude", None, "claude-3-haiku"),
+            ("gpt-4o-mini", "openai", None, "gpt-4o-mini"),
+        ];
+        for (input, name, api_style, model) in cases {
+            assert_eq!(
+                map_model(input),
+                ProviderMapping {
+                    name: name.to_string(),
+                    api_style,
+                    model: model.to_string(),
+                },
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_translate_current_format() {
+        let py = parse(
+            r#"
+model:
+  model_name: openai/gpt-4o
+  api_key: sk-test
+  api_base: https://proxy.example.com/v1
+commit_template: "Use the Angular convention"
+include_git_history: true
+enable_data_improvement: false
+"#,
+        );
+        let migration = translate(&py, Path::new("/home/u/.zeeland/gcop/config.yaml")).unwrap();
+
+        let config = load(&migration.toml);
+        assert_eq!(config.llm.default_provider, "openai");
+        let provider = &config.llm.providers["openai"];
+        assert_eq!(provider.model, "gpt-4o");
+        assert_eq!(provider.api_key.as_deref(), Some("sk-test"));
+        assert_eq!(
+            provider.endpoint.as_deref(),
+            Some("https://proxy.example.com/v1")
+        );
+        assert_eq!(
+            config.commit.custom_prompt.as_deref(),
+            Some("Use the Angular convention")
+        );
+        assert!(config.commit.learn_from_history);
+
+        assert!(
+            migration
+                .toml
+                .starts_with("# gcop-rs configuration, migrated from")
+        );
+        assert!(migration.toml.contains("[llm.providers.openai]"));
+        assert_eq!(migration.skipped, vec!["enable_data_improvement"]);
+        let from: Vec<_> = migration
+            .translated
+            .iter()
+            .map(|(f, _)| f.as_str())
+            .collect();
+        assert_eq!(
+            from,
+            vec![
+                "model.model_name",
+                "model.api_base",
+                "model.api_key",
+                "commit_template",
+                "include_git_history"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_translate_flat_format_and_custom_provider() {
+        let py = parse("model_name: groq/llama3-70b\napi_key: gsk-test\n");
+        let migration = translate(&py, Path::new("config.yaml")).unwrap();
+
+        let config = load(&migration.toml);
+        assert_eq!(config.llm.default_provider, "groq");
+        let provider = &config.llm.providers["groq"];
+        assert_eq!(provider.api_style, Some(ApiStyle::OpenAI));
+        assert_eq!(provider.model, "llama3-70b");
+        assert!(!migration.toml.contains("[commit]"));
+    }
+
+    #[test]
+    fn test_translate_requires_model() {
+        let py = parse("model:\n  api_key: sk-test\n");
+        assert!(translate(&py, Path::new("config.yaml")).is_err());
+    }
+}
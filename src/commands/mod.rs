@@ -4,13 +4,34 @@
 //!
 //! # Modules
 //! - `commit` - Commit message generation flow.
+//! - `fixup` - `fixup!` / `squash!` commits for autosquash.
 //! - `review` - Code review.
+//! - `review_baseline` - Known-issue suppression for review.
+//! - `review_cache` - Result cache of `commit` / `range` reviews.
+//! - `review_context` - Input files and related-file context for `review file`.
+//! - `privacy` - `[privacy]` secret guard and redaction for outgoing diffs.
 //! - `config` - Configuration management.
+//! - `completions` - Shell completion scripts.
+//! - `doctor` - Environment health checks (`config doctor`).
 //! - `alias` - Git alias management.
+//! - `api` - Newline-delimited JSON API on stdio for editor plugins.
 //! - `init` - Project initialization.
+//! - `migrate` - Migration from the Python gcop config (`init --from-gcop`).
 //! - `stats` - Repository statistics.
+//! - `tag` - Annotated tags with AI-generated release messages.
+//! - `reword` - Improving the message of an existing commit.
 //! - `hook` - Git hook management (`prepare-commit-msg`).
+//! - `ticket` - Ticket ID extraction from branch names.
+//! - `breaking` - Conventional Commits breaking-change markers.
+//! - `trailers` - `Signed-off-by` / `Co-authored-by` trailers.
+//! - `provider` - Provider diagnostics.
+//! - `prompt` - Prompt template inspection.
+//! - `undo` - Undo of the last commit created by gcop-rs.
+//! - `usage` - LLM usage ledger report.
+//! - `audit` - Audit log of provider requests.
 //! - `commit_state_machine` - Commit workflow state machine.
+//! - `commit_review` - Quick review of staged changes before committing (`commit --review`).
+//! - `commit_session` - Resumable commit sessions (`.git/gcop-state.json`).
 //! - `format` - Output format definition.
 //! - `options` - Command option structs.
 //! - `json` - JSON output helpers.
@@ -27,33 +48,83 @@
 
 /// Git alias management commands.
 pub mod alias;
+/// Stdio JSON API for editor plugins.
+pub mod api;
+/// Audit log inspection.
+pub mod audit;
+/// Breaking-change markers (`!`, `BREAKING CHANGE:` footer).
+pub mod breaking;
 /// Commit generation command flow.
 pub mod commit;
+/// Commit-time quick review gate.
+pub mod commit_review;
+/// Resumable commit sessions.
+pub mod commit_session;
 /// Commit workflow state machine.
 pub mod commit_state_machine;
+/// Shell completion scripts with config-driven values.
+pub mod completions;
 /// Configuration edit/validation commands.
 pub mod config;
+/// `config doctor` health checks.
+pub mod doctor;
+/// `commit --fixup` / `--squash` flow.
+pub mod fixup;
 /// Output format types and parsing helpers.
 pub mod format;
 /// Git hook install/uninstall command.
 pub mod hook;
+/// Cached hook messages reused when `git commit` is retried.
+pub mod hook_cache;
 /// Configuration initialization commands.
 pub mod init;
 /// Shared JSON output helpers.
 pub mod json;
+/// Python gcop config migration.
+pub mod migrate;
 /// Shared command option structs.
 pub mod options;
+/// Secret guard and redaction for diffs sent to providers.
+pub mod privacy;
+/// Prompt template inspection.
+pub mod prompt;
+/// Provider diagnostics commands.
+pub mod provider;
 /// Code review command flow.
 pub mod review;
+/// Review baseline (known-issue suppression).
+pub mod review_baseline;
+/// Result cache of commit and range reviews.
+pub mod review_cache;
+/// Directory walking and related files for `review file`.
+pub mod review_context;
+/// Last-reviewed snapshots for incremental reviews.
+pub mod review_snapshot;
+/// `reword` command flow.
+pub mod reword;
+/// Self-update from GitHub releases and the weekly update hint.
+pub mod self_update;
 /// Atomic split commit logic.
 pub mod split;
+/// Dependency-aware ordering of split commit groups.
+pub mod split_order;
 /// Repository statistics command flow.
 pub mod stats;
+/// Annotated tag command flow.
+pub mod tag;
+/// Ticket ID extraction and injection.
+pub mod ticket;
+/// Commit message trailer injection.
+pub mod trailers;
+/// Undo of the last gcop-rs commit.
+pub mod undo;
+/// LLM usage ledger report.
+pub mod usage;
 
 // Re-export for external use (tests, library users).
 #[allow(unused_imports)]
 pub use format::OutputFormat;
-pub use options::{CommitOptions, ReviewOptions, StatsOptions};
+pub use options::{CommitOptions, ReviewOptions, RewordOptions, StatsOptions, TagOptions};
 
 use crate::git::diff::{FileDiff, split_diff_by_file};
 use std::fmt::Write;
@@ -174,6 +245,57 @@ pub(crate) fn smart_truncate_diff(diff: &str, max_size: usize) -> (String, bool)
     (output, was_truncated)
 }
 
+/// Splits a diff larger than `max_size` into at most `max_chunks` parts at
+/// file granularity, for reviews that cover each part separately.
+///
+/// Files are packed in their original order. Once `max_chunks` parts exist,
+/// the remaining files go into the last part; every part is then passed
+/// through [`smart_truncate_diff`], so oversized parts keep summaries only.
+///
+/// Returns `(parts, had_downgraded_files)`; `parts` is empty when the diff
+/// fits, has a single file, or `max_chunks` is below 2.
+pub(crate) fn chunk_diff(diff: &str, max_size: usize, max_chunks: usize) -> (Vec<String>, bool) {
+    if max_chunks < 2 || diff.len() <= max_size {
+        return (Vec::new(), false);
+    }
+    let files = split_diff_by_file(diff);
+    if files.len() < 2 {
+        return (Vec::new(), false);
+    }
+
+    let mut parts: Vec<Vec<&FileDiff>> = Vec::new();
+    let mut part_size = 0usize;
+    for file in &files {
+        let size = file.content.len() + 1;
+        let full = parts.len() == max_chunks;
+        match parts.last_mut() {
+            Some(part) if part_size + size <= max_size || full => {
+                part.push(file);
+                part_size += size;
+            }
+            _ => {
+                parts.push(vec![file]);
+                part_size = size;
+            }
+        }
+    }
+    if parts.len() < 2 {
+        return (Vec::new(), false);
+    }
+
+    let mut truncated = false;
+    let chunks = parts
+        .iter()
+        .map(|part| {
+            let text: Vec<&str> = part.iter().map(|file| file.content.as_str()).collect();
+            let (chunk, was_truncated) = smart_truncate_diff(&text.join("\n"), max_size);
+            truncated |= was_truncated;
+            chunk
+        })
+        .collect();
+    (chunks, truncated)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +425,33 @@ mod tests {
         assert!(result.contains("## Full diff"));
         assert!(result.contains("## Summary only"));
     }
+
+    #[test]
+    fn test_chunk_diff_packs_files_in_order() {
+        let file = |name: &str, size: usize| {
+            format!(
+                "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n{1}",
+                name,
+                "+".repeat(size)
+            )
+        };
+        let diff = [file("a.rs", 100), file("b.rs", 100), file("c.rs", 100)].join("\n");
+
+        // 两个文件放得下一个部分，第三个进入下一部分
+        let (chunks, truncated) = chunk_diff(&diff, 300, 8);
+        assert!(!truncated);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].contains("a.rs") && chunks[0].contains("b.rs"));
+        assert!(chunks[1].contains("c.rs") && !chunks[1].contains("a.rs"));
+
+        // 超出 max_chunks 的文件并入最后一部分，并被截断为摘要
+        let (chunks, truncated) = chunk_diff(&diff, 150, 2);
+        assert!(truncated);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[1].contains("## Summary only"));
+
+        assert!(chunk_diff(&diff, diff.len(), 8).0.is_empty());
+        assert!(chunk_diff(&diff, 150, 1).0.is_empty());
+        assert!(chunk_diff(&file("a.rs", 500), 150, 8).0.is_empty());
+    }
 }
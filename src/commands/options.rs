@@ -18,16 +18,35 @@
 //!     dry_run: true,
 //!     split: false,
 //!     amend: false,
+//!     fixup: None,
+//!     all: false,
+//!     no_sign: false,
+//!     signoff: false,
+//!     co_authors: &[],
+//!     offline: false,
+//!     show_redacted: false,
+//!     output: None,
+//!     message_file: None,
+//!     verify: None,
+//!     review: false,
 //!     format: OutputFormat::Text,
 //!     feedback: &[],
 //!     verbose: false,
 //!     provider_override: None,
+//!     model_override: None,
 //! };
 //! ```
 
+use std::path::Path;
+
+use super::fixup::FixupTarget;
 use super::format::OutputFormat;
-use crate::cli::{Cli, CommitArgs, ReviewTarget};
-use crate::config::AppConfig;
+use crate::cli::{Cli, CommitArgs, ReviewArgs, ReviewTarget, RewordArgs, StatsArgs, TagArgs};
+use crate::config::{AppConfig, NonInteractiveAction};
+use crate::error::{GcopError, Result};
+use crate::git::GitOperations;
+use crate::git::commit::CommitSigning;
+use crate::llm::{IssueSeverity, ReviewPreset};
 
 /// Commit command options
 ///
@@ -42,7 +61,17 @@ use crate::config::AppConfig;
 /// - `feedback`: initial feedback/instruction (such as "use Chinese", "be concise")
 /// - `verbose`: verbose mode (display API requests/responses)
 /// - `provider_override`: override the provider in the configuration (such as `--provider openai`)
+/// - `model_override`: override the selected provider's model (such as `--model gpt-4o`)
 /// - `amend`: amend the last commit with a new message
+/// - `fixup`: create a `fixup!` / `squash!` commit for an earlier commit instead
+/// - `no_sign`: disable commit signing (overrides git config and `commit.sign`)
+/// - `signoff`: add a `Signed-off-by` trailer (in addition to `commit.trailers.signoff`)
+/// - `co_authors`: extra `Co-authored-by` entries (in addition to `commit.trailers.co_authors`)
+/// - `offline`: build the message heuristically from the diff, without any provider
+/// - `show_redacted`: print the diff as it would be sent and stop
+/// - `output`: write the generated message to a file (or stdout) instead of committing
+/// - `message_file`: draft message the LLM rewrites for the staged changes
+/// - `review`: quick review of the staged diff before generating the message
 ///
 /// # Example
 /// ```no_run
@@ -55,10 +84,22 @@ use crate::config::AppConfig;
 ///     dry_run: false,
 ///     split: false,
 ///     amend: false,
+///     fixup: None,
+///     all: false,
+///     no_sign: false,
+///     signoff: false,
+///     co_authors: &[],
+///     offline: false,
+///     show_redacted: false,
+///     output: None,
+///     message_file: None,
+///     verify: None,
+///     review: false,
 ///     format: OutputFormat::Text,
 ///     feedback: &["use conventional commits".to_string()],
 ///     verbose: false,
 ///     provider_override: None,
+///     model_override: None,
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -78,6 +119,39 @@ pub struct CommitOptions<'a> {
     /// Whether to amend the last commit
     pub amend: bool,
 
+    /// `fixup!` / `squash!` target (`--fixup` / `--squash`)
+    pub fixup: Option<FixupTarget<'a>>,
+
+    /// Whether to stage modified tracked files first (like `git commit -a`)
+    pub all: bool,
+
+    /// Whether to disable commit signing
+    pub no_sign: bool,
+
+    /// Whether to add a `Signed-off-by` trailer
+    pub signoff: bool,
+
+    /// Extra co-authors (`Name <email>` or a bare email)
+    pub co_authors: &'a [String],
+
+    /// Whether to generate the message offline (no LLM)
+    pub offline: bool,
+
+    /// Print the redacted diff instead of sending it (`--show-redacted`)
+    pub show_redacted: bool,
+
+    /// Write the generated message here instead of committing (`-` = stdout)
+    pub output: Option<&'a Path>,
+
+    /// Draft message to refine (`--message-file`)
+    pub message_file: Option<&'a Path>,
+
+    /// Command checking each split group's staged snapshot (`--verify`)
+    pub verify: Option<&'a str>,
+
+    /// Whether to review the staged diff for critical issues first (`--review`)
+    pub review: bool,
+
     /// Output format
     pub format: OutputFormat,
 
@@ -89,6 +163,9 @@ pub struct CommitOptions<'a> {
 
     /// Covered providers
     pub provider_override: Option<&'a str>,
+
+    /// Model override for the selected provider (`--model`)
+    pub model_override: Option<&'a str>,
 }
 
 impl<'a> CommitOptions<'a> {
@@ -109,16 +186,50 @@ impl<'a> CommitOptions<'a> {
         Self {
             no_edit: args.no_edit,
             yes: args.yes,
-            dry_run: args.dry_run,
+            // --output only writes the message
+            dry_run: args.dry_run || args.output.is_some(),
             split: args.split || config.commit.split,
             amend: args.amend,
-            format: OutputFormat::from_cli(&args.format, args.json),
+            fixup: FixupTarget::from_cli(args),
+            all: args.all,
+            no_sign: args.no_sign,
+            signoff: args.signoff,
+            co_authors: &args.co_authors,
+            offline: args.offline,
+            show_redacted: args.show_redacted,
+            output: args.output.as_deref(),
+            message_file: args.message_file.as_deref(),
+            verify: args.verify.as_deref(),
+            review: args.review || config.commit.review,
+            format: OutputFormat::from_cli(&args.format, args.json).or_stream(args.json_stream),
             feedback: &args.feedback,
             verbose: cli.verbose,
             provider_override: cli.provider.as_deref(),
+            model_override: cli.model.as_deref(),
         }
     }
 
+    /// Apply `[commit].non_interactive` when there is no terminal
+    ///
+    /// Without a terminal, prompts cannot be answered: `commit` turns on
+    /// `--yes --no-edit`, `dry-run` turns on `--dry-run` and `fail` returns
+    /// [`GcopError::NonInteractive`]. JSON output, `--show-redacted` and runs that
+    /// already pass `--yes` or `--dry-run` are left unchanged.
+    pub fn for_terminal(mut self, interactive: bool, config: &AppConfig) -> Result<Self> {
+        if interactive || self.format.is_json() || self.show_redacted || self.yes || self.dry_run {
+            return Ok(self);
+        }
+        match config.commit.non_interactive {
+            NonInteractiveAction::Commit => {
+                self.yes = true;
+                self.no_edit = true;
+            }
+            NonInteractiveAction::DryRun => self.dry_run = true,
+            NonInteractiveAction::Fail => return Err(GcopError::NonInteractive),
+        }
+        Ok(self)
+    }
+
     /// Get valid colored settings
     ///
     /// Combines the output format and the colored setting of the configuration file.
@@ -136,6 +247,25 @@ impl<'a> CommitOptions<'a> {
     pub fn effective_colored(&self, config: &AppConfig) -> bool {
         self.format.effective_colored(config.ui.colored)
     }
+
+    /// Get the effective commit signing mode
+    ///
+    /// `--no-sign` overrides `commit.sign`, which overrides git's `commit.gpgsign`.
+    pub fn signing(&self, config: &AppConfig) -> CommitSigning {
+        CommitSigning::resolve(config.commit.sign, self.no_sign)
+    }
+
+    /// Resolve the trailer lines to append to generated messages
+    ///
+    /// Combines `[commit.trailers]` with `--signoff` and `--co-author`.
+    pub fn trailers(&self, config: &AppConfig, repo: &dyn GitOperations) -> Result<Vec<String>> {
+        super::trailers::resolve_trailers(
+            repo,
+            &config.commit.trailers,
+            self.signoff,
+            self.co_authors,
+        )
+    }
 }
 
 /// Review command options
@@ -145,8 +275,16 @@ impl<'a> CommitOptions<'a> {
 /// # Field description
 /// - `target`: review target (unstaged changes/single commit/scope/file)
 /// - `format`: output format
-/// - `verbose`: verbose mode (currently not used, reserved)
+/// - `verbose`: verbose mode (prints the LLM metrics summary)
 /// - `provider_override`: override the provider in the configuration
+/// - `model_override`: override the selected provider's model (such as `--model gpt-4o`)
+/// - `update_baseline`: record current findings as the review baseline
+/// - `fail_on`: fail with a severity-specific exit code at or above this level
+/// - `apply`: interactively apply suggested patches (`changes` target only)
+/// - `preset`: focused review preset (`--preset`)
+/// - `show_redacted`: print the input as it would be sent and stop
+/// - `incremental`: `--incremental` (`Some(true)`) / `--full` (`Some(false)`), `None` = `review.incremental`
+/// - `no_cache`: bypass the result cache of `commit` / `range` reviews
 ///
 /// # Example
 /// ```no_run
@@ -160,6 +298,14 @@ impl<'a> CommitOptions<'a> {
 ///     format: OutputFormat::Text,
 ///     verbose: false,
 ///     provider_override: None,
+///     model_override: None,
+///     update_baseline: false,
+///     fail_on: None,
+///     apply: false,
+///     preset: None,
+///     show_redacted: false,
+///     incremental: None,
+///     no_cache: false,
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -171,12 +317,34 @@ pub struct ReviewOptions<'a> {
     pub format: OutputFormat,
 
     /// Whether to use verbose mode
-    // TODO: Currently the review command does not use verbose. Detailed output may need to be added in the future.
-    #[allow(dead_code)]
     pub verbose: bool,
 
     /// Covered providers
     pub provider_override: Option<&'a str>,
+
+    /// Model override for the selected provider (`--model`)
+    pub model_override: Option<&'a str>,
+
+    /// Record current findings in `.gcop/review-baseline.json` instead of filtering
+    pub update_baseline: bool,
+
+    /// `--fail-on` threshold for the CI exit-code contract
+    pub fail_on: Option<IssueSeverity>,
+
+    /// Preview and apply suggested patches after the review
+    pub apply: bool,
+
+    /// Focused review preset replacing the review system prompt
+    pub preset: Option<ReviewPreset>,
+
+    /// Print the redacted input instead of sending it (`--show-redacted`)
+    pub show_redacted: bool,
+
+    /// Incremental review override: `--incremental` / `--full`
+    pub incremental: Option<bool>,
+
+    /// Bypass the review result cache (`--no-cache`)
+    pub no_cache: bool,
 }
 
 impl<'a> ReviewOptions<'a> {
@@ -184,18 +352,28 @@ impl<'a> ReviewOptions<'a> {
     ///
     /// # Parameters
     /// - `cli`: parsed CLI parameters
-    /// - `target`: review target
-    /// - `format`: `--format` parameter
-    /// - `json`: `--json` flag
+    /// - `args`: `review` subcommand arguments
     ///
     /// # Returns
     /// Constructed `ReviewOptions` instance
-    pub fn from_cli(cli: &'a Cli, target: &'a ReviewTarget, format: &str, json: bool) -> Self {
+    pub fn from_cli(cli: &'a Cli, args: &'a ReviewArgs) -> Self {
         Self {
-            target,
-            format: OutputFormat::from_cli(format, json),
+            target: &args.target,
+            format: OutputFormat::from_cli(&args.format, args.json).or_stream(args.json_stream),
             verbose: cli.verbose,
             provider_override: cli.provider.as_deref(),
+            model_override: cli.model.as_deref(),
+            update_baseline: args.update_baseline,
+            fail_on: args.fail_on.as_deref().map(IssueSeverity::from_config_str),
+            apply: args.apply,
+            preset: args.preset.as_deref().and_then(ReviewPreset::from_cli_str),
+            show_redacted: args.show_redacted,
+            incremental: if args.full {
+                Some(false)
+            } else {
+                args.incremental.then_some(true)
+            },
+            no_cache: args.no_cache,
         }
     }
 
@@ -210,6 +388,12 @@ impl<'a> ReviewOptions<'a> {
     pub fn effective_colored(&self, config: &AppConfig) -> bool {
         self.format.effective_colored(config.ui.colored)
     }
+
+    /// Whether `review changes` runs incrementally (`--incremental` /
+    /// `--full`, falling back to `review.incremental`)
+    pub fn effective_incremental(&self, config: &AppConfig) -> bool {
+        self.incremental.unwrap_or(config.review.incremental)
+    }
 }
 
 /// Stats command options
@@ -229,6 +413,14 @@ impl<'a> ReviewOptions<'a> {
 ///     format: OutputFormat::Markdown,
 ///     author: Some("alice@example.com"),
 ///     contrib: false,
+///     detailed: false,
+///     calendar: false,
+///     since: Some("2w"),
+///     until: None,
+///     range: None,
+///     path: Some("src"),
+///     repos: None,
+///     merge_authors: &[],
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -241,23 +433,53 @@ pub struct StatsOptions<'a> {
 
     /// Show line-level contribution statistics
     pub contrib: bool,
+
+    /// Show file, extension and language statistics
+    pub detailed: bool,
+
+    /// Show the full-year commit calendar (`commits_by_day` covers 365 days)
+    pub calendar: bool,
+
+    /// Lower date bound (raw `--since` value)
+    pub since: Option<&'a str>,
+
+    /// Upper date bound (raw `--until` value)
+    pub until: Option<&'a str>,
+
+    /// Revision or revision range to analyze
+    pub range: Option<&'a str>,
+
+    /// File or directory filter
+    pub path: Option<&'a str>,
+
+    /// Repositories to aggregate (`Some(&[])` uses `[stats] repos`)
+    pub repos: Option<&'a [String]>,
+
+    /// Ad-hoc author merges (`Old=New`), applied after `.mailmap` and `[stats.authors]`
+    pub merge_authors: &'a [String],
 }
 
 impl<'a> StatsOptions<'a> {
     /// Constructed from CLI parameters
     ///
     /// # Parameters
-    /// - `format`: `--format` parameter
-    /// - `json`: `--json` flag
-    /// - `author`: `--author` parameter (optional)
+    /// - `args`: stats-specific CLI arguments
     ///
     /// # Returns
     /// Constructed `StatsOptions` instance
-    pub fn from_cli(format: &str, json: bool, author: Option<&'a str>, contrib: bool) -> Self {
+    pub fn from_cli(args: &'a StatsArgs) -> Self {
         Self {
-            format: OutputFormat::from_cli(format, json),
-            author,
-            contrib,
+            format: OutputFormat::from_cli(&args.format, args.json).or_stream(args.json_stream),
+            author: args.author.as_deref(),
+            contrib: args.contrib,
+            detailed: args.detailed,
+            calendar: args.calendar,
+            since: args.since.as_deref(),
+            until: args.until.as_deref(),
+            range: args.range.as_deref(),
+            path: args.path.as_deref(),
+            repos: args.repos.as_deref(),
+            merge_authors: &args.merge_authors,
         }
     }
 
@@ -274,6 +496,155 @@ impl<'a> StatsOptions<'a> {
     }
 }
 
+/// Tag command options
+///
+/// Constructed from CLI parameters and passed to `commands::tag::run()`.
+///
+/// # Field description
+/// - `name`: tag to create
+/// - `from`: previous tag (default: latest tag reachable from `HEAD`)
+/// - `dry_run`: only generate and print the message
+/// - `yes`: create the tag without confirmation
+/// - `push`: push the tag to `remote` after creating it
+/// - `provider_override`: override the provider in the configuration
+/// - `model_override`: override the selected provider's model (such as `--model gpt-4o`)
+///
+/// # Example
+/// ```no_run
+/// use gcop_rs::commands::options::TagOptions;
+///
+/// let options = TagOptions {
+///     name: "v1.2.0",
+///     from: Some("v1.1.0"),
+///     dry_run: true,
+///     yes: false,
+///     push: false,
+///     remote: "origin",
+///     verbose: false,
+///     provider_override: None,
+///     model_override: None,
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagOptions<'a> {
+    /// Tag to create
+    pub name: &'a str,
+
+    /// Previous tag to summarize from
+    pub from: Option<&'a str>,
+
+    /// Whether to only generate and not create the tag
+    pub dry_run: bool,
+
+    /// Whether to skip confirmation
+    pub yes: bool,
+
+    /// Whether to push the tag after creating it
+    pub push: bool,
+
+    /// Remote used for pushing
+    pub remote: &'a str,
+
+    /// Whether to use verbose mode
+    pub verbose: bool,
+
+    /// Covered providers
+    pub provider_override: Option<&'a str>,
+
+    /// Model override for the selected provider (`--model`)
+    pub model_override: Option<&'a str>,
+}
+
+impl<'a> TagOptions<'a> {
+    /// Constructed from CLI parameters
+    ///
+    /// # Parameters
+    /// - `cli`: parsed CLI parameters (provides verbose, provider override)
+    /// - `args`: tag-specific CLI arguments
+    pub fn from_cli(cli: &'a Cli, args: &'a TagArgs) -> Self {
+        Self {
+            name: &args.name,
+            from: args.from.as_deref(),
+            dry_run: args.dry_run,
+            yes: args.yes,
+            push: args.push,
+            remote: &args.remote,
+            verbose: cli.verbose,
+            provider_override: cli.provider.as_deref(),
+            model_override: cli.model.as_deref(),
+        }
+    }
+}
+
+/// Reword command options
+///
+/// Constructed from CLI parameters and passed to `commands::reword::run()`.
+///
+/// # Field description
+/// - `commit`: commit whose message to improve
+/// - `dry_run`: only generate and print the new message
+/// - `yes`: rewrite the commit without confirmation
+/// - `feedback`: extra instructions for message generation
+/// - `provider_override`: override the provider in the configuration
+/// - `model_override`: override the selected provider's model (such as `--model gpt-4o`)
+///
+/// # Example
+/// ```no_run
+/// use gcop_rs::commands::options::RewordOptions;
+///
+/// let options = RewordOptions {
+///     commit: "HEAD~2",
+///     dry_run: true,
+///     yes: false,
+///     feedback: &[],
+///     verbose: false,
+///     provider_override: None,
+///     model_override: None,
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct RewordOptions<'a> {
+    /// Commit to reword
+    pub commit: &'a str,
+
+    /// Whether to only generate and not rewrite the commit
+    pub dry_run: bool,
+
+    /// Whether to skip confirmation
+    pub yes: bool,
+
+    /// Extra instructions for message generation
+    pub feedback: &'a [String],
+
+    /// Whether to use verbose mode
+    pub verbose: bool,
+
+    /// Covered providers
+    pub provider_override: Option<&'a str>,
+
+    /// Model override for the selected provider (`--model`)
+    pub model_override: Option<&'a str>,
+}
+
+impl<'a> RewordOptions<'a> {
+    /// Constructed from CLI parameters
+    ///
+    /// # Parameters
+    /// - `cli`: parsed CLI parameters (provides verbose, provider override)
+    /// - `args`: reword-specific CLI arguments
+    pub fn from_cli(cli: &'a Cli, args: &'a RewordArgs) -> Self {
+        Self {
+            commit: &args.commit,
+            dry_run: args.dry_run,
+            yes: args.yes,
+            feedback: &args.feedback,
+            verbose: cli.verbose,
+            provider_override: cli.provider.as_deref(),
+            model_override: cli.model.as_deref(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,9 +654,15 @@ mod tests {
             command: crate::cli::Commands::Init {
                 force: false,
                 project: false,
+                template: false,
+                from_gcop: None,
             },
             verbose: true,
+            quiet: false,
             provider: Some("test-provider".to_string()),
+            model: Some("test-model".to_string()),
+            profile: None,
+            repo: None,
         }
     }
 
@@ -300,8 +677,21 @@ mod tests {
             dry_run: false,
             split: false,
             amend: false,
+            fixup: None,
+            squash: None,
+            all: false,
+            no_sign: false,
+            signoff: false,
+            co_authors: vec![],
+            offline: false,
+            show_redacted: false,
+            output: None,
+            message_file: None,
+            verify: None,
+            review: false,
             format: "text".to_string(),
             json: false,
+            json_stream: false,
             feedback: vec![],
         }
     }
@@ -316,8 +706,21 @@ mod tests {
             dry_run: true,
             split: false,
             amend: false,
+            fixup: None,
+            squash: None,
+            all: false,
+            no_sign: false,
+            signoff: false,
+            co_authors: vec![],
+            offline: false,
+            show_redacted: false,
+            output: None,
+            message_file: None,
+            verify: None,
+            review: false,
             format: "text".to_string(),
             json: false,
+            json_stream: false,
             feedback: vec!["use conventional commits".to_string()],
         };
         let opts = CommitOptions::from_cli(&cli, &args, &config);
@@ -330,6 +733,46 @@ mod tests {
         assert_eq!(opts.feedback.len(), 1);
         assert!(opts.verbose);
         assert_eq!(opts.provider_override, Some("test-provider"));
+        assert_eq!(opts.model_override, Some("test-model"));
+    }
+
+    #[test]
+    fn test_commit_options_for_terminal() {
+        let cli = mock_cli();
+        let mut config = mock_config();
+        let args = mock_commit_args();
+        let options = || CommitOptions::from_cli(&cli, &args, &config);
+
+        // 有终端时不做任何改变
+        let opts = options().for_terminal(true, &config).unwrap();
+        assert!(!opts.yes && !opts.no_edit && !opts.dry_run);
+
+        // 默认：等同 --yes --no-edit
+        let opts = options().for_terminal(false, &config).unwrap();
+        assert!(opts.yes && opts.no_edit && !opts.dry_run);
+
+        config.commit.non_interactive = NonInteractiveAction::DryRun;
+        let opts = CommitOptions::from_cli(&cli, &args, &config)
+            .for_terminal(false, &config)
+            .unwrap();
+        assert!(opts.dry_run && !opts.yes);
+
+        config.commit.non_interactive = NonInteractiveAction::Fail;
+        let err = CommitOptions::from_cli(&cli, &args, &config)
+            .for_terminal(false, &config)
+            .unwrap_err();
+        assert!(matches!(err, GcopError::NonInteractive));
+
+        // 显式 --yes 时 fail 模式也放行
+        let yes_args = CommitArgs {
+            yes: true,
+            ..mock_commit_args()
+        };
+        assert!(
+            CommitOptions::from_cli(&cli, &yes_args, &config)
+                .for_terminal(false, &config)
+                .is_ok()
+        );
     }
 
     #[test]
@@ -373,9 +816,30 @@ mod tests {
 
     #[test]
     fn test_stats_options() {
-        let opts = StatsOptions::from_cli("markdown", false, Some("author@example.com"), false);
+        let args = StatsArgs {
+            format: "markdown".to_string(),
+            json: false,
+            json_stream: false,
+            author: Some("author@example.com".to_string()),
+            contrib: false,
+            detailed: true,
+            calendar: true,
+            since: Some("2026-01-01".to_string()),
+            until: None,
+            range: Some("v1.0..v2.0".to_string()),
+            path: None,
+            repos: Some(vec!["../api".to_string()]),
+            merge_authors: vec!["jdoe=Jane Doe".to_string()],
+        };
+        let opts = StatsOptions::from_cli(&args);
 
         assert_eq!(opts.format, OutputFormat::Markdown);
         assert_eq!(opts.author, Some("author@example.com"));
+        assert_eq!(opts.since, Some("2026-01-01"));
+        assert_eq!(opts.range, Some("v1.0..v2.0"));
+        assert!(opts.detailed);
+        assert!(opts.calendar);
+        assert_eq!(opts.repos, Some(&["../api".to_string()][..]));
+        assert_eq!(opts.merge_authors, ["jdoe=Jane Doe".to_string()]);
     }
 }
@@ -0,0 +1,229 @@
+//! Prompt template inspection (`gcop-rs prompt show <name>`).
+//!
+//! Renders the effective template (project or user override, otherwise the
+//! built-in one) with fixed sample data so template edits can be checked
+//! without staging changes or calling a provider.
+
+use crate::cli::PromptAction;
+use crate::commands::commit::print_verbose_prompt;
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::git::diff::FileDiff;
+use crate::llm::template::{self, PromptTemplate, TemplateOrigin, TemplateSource};
+use crate::llm::{CommitContext, IssueSeverity, ReviewIssue, ReviewResult, ReviewType, prompt};
+use crate::ui;
+
+const SAMPLE_DIFF: &str = "diff --git a/src/greet.rs b/src/greet.rs
+--- a/src/greet.rs
++++ b/src/greet.rs
+@@ -1,3 +1,3 @@
+ pub fn greet(name: &str) -> String {
+-    format!(\"Hi {}\", name)
++    format!(\"Hello, {}!\", name)
+ }";
+
+const SAMPLE_TARGET_DIFF: &str = "diff --git a/src/greet.rs b/src/greet.rs
+new file mode 100644
+--- /dev/null
++++ b/src/greet.rs
+@@ -0,0 +1,3 @@
++pub fn greet(name: &str) -> String {
++    format!(\"Hi {}\", name)
++}";
+
+const SAMPLE_TARGET_SUBJECT: &str = "feat(greet): add greet()";
+
+/// Execute prompt command
+pub fn run(action: &PromptAction, config: &AppConfig) -> Result<()> {
+    match action {
+        PromptAction::Show { template, source } => show(*template, *source, config),
+    }
+}
+
+fn show(template: PromptTemplate, raw: bool, config: &AppConfig) -> Result<()> {
+    let colored = config.ui.colored;
+    let source = template::load(template)?;
+
+    let origin = match &source.origin {
+        TemplateOrigin::Builtin => rust_i18n::t!("prompt.builtin").to_string(),
+        TemplateOrigin::File(path) => path.display().to_string(),
+    };
+    println!(
+        "{}",
+        ui::info(
+            &rust_i18n::t!(
+                "prompt.template_origin",
+                name = template.name(),
+                origin = origin
+            ),
+            colored
+        )
+    );
+
+    if raw {
+        println!("{}", source.source.trim_end());
+        return Ok(());
+    }
+
+    let (system, user) = render_sample(&source, config)?;
+    println!(
+        "{}",
+        ui::info(&rust_i18n::t!("prompt.sample_data"), colored)
+    );
+    print_verbose_prompt(&system, &user, false, colored);
+    Ok(())
+}
+
+/// Renders `source` with sample data, returning `(system_prompt, user_message)`.
+///
+/// Unlike prompt building during generation, template errors are returned
+/// instead of falling back to the built-in template.
+fn render_sample(source: &TemplateSource, config: &AppConfig) -> Result<(String, String)> {
+    let context = sample_context(config);
+    let convention = context.convention.as_ref();
+
+    let file_diffs = sample_file_diffs();
+    let commits = sample_commits();
+    let reviews = sample_reviews();
+
+    let vars = match source.template {
+        PromptTemplate::Commit => prompt::commit_vars(SAMPLE_DIFF, &context, convention),
+        PromptTemplate::Split => prompt::split_vars(&file_diffs, &context, convention),
+        PromptTemplate::Review => prompt::review_vars(SAMPLE_DIFF, None),
+        PromptTemplate::ReviewMerge => prompt::review_merge_vars(&reviews),
+        PromptTemplate::Tag => prompt::tag_vars("v1.1.0", Some("v1.0.0"), &commits, 0),
+        PromptTemplate::Fixup => {
+            prompt::fixup_vars(SAMPLE_TARGET_SUBJECT, SAMPLE_TARGET_DIFF, SAMPLE_DIFF)
+        }
+    };
+    // Render first so a broken override fails here instead of being replaced
+    // by the built-in template inside the prompt builders below.
+    let user = source.render(&vars)?;
+
+    let custom_prompt = context.custom_prompt.as_deref();
+    let (system, _) = match source.template {
+        PromptTemplate::Commit => {
+            prompt::build_commit_prompt_split(SAMPLE_DIFF, &context, custom_prompt, convention)
+        }
+        PromptTemplate::Split => {
+            prompt::build_split_commit_prompt(&file_diffs, &context, custom_prompt, convention)
+        }
+        PromptTemplate::Review => prompt::build_review_prompt_split(
+            SAMPLE_DIFF,
+            &ReviewType::UncommittedChanges,
+            config.review.custom_prompt.as_deref(),
+        ),
+        PromptTemplate::ReviewMerge => prompt::build_review_merge_prompt(&reviews),
+        PromptTemplate::Tag => prompt::build_tag_prompt("v1.1.0", Some("v1.0.0"), &commits, 0),
+        PromptTemplate::Fixup => {
+            prompt::build_fixup_prompt(SAMPLE_TARGET_SUBJECT, SAMPLE_TARGET_DIFF, SAMPLE_DIFF)
+        }
+    };
+
+    Ok((system, user))
+}
+
+fn sample_commits() -> Vec<String> {
+    vec![
+        "feat(greet): add an exclamation mark".to_string(),
+        "fix(cli): handle empty names".to_string(),
+    ]
+}
+
+fn sample_reviews() -> Vec<ReviewResult> {
+    let review = |summary: &str, issue: Option<ReviewIssue>| ReviewResult {
+        summary: summary.to_string(),
+        issues: issue.into_iter().collect(),
+        suggestions: vec![],
+    };
+    vec![
+        review(
+            "The greeting format changes.",
+            Some(ReviewIssue {
+                severity: IssueSeverity::Info,
+                description: "Update tests that assert the old greeting".to_string(),
+                file: Some("src/greet.rs".to_string()),
+                line: Some(2),
+                suggested_patch: None,
+                category: None,
+            }),
+        ),
+        review("The README documents the new greeting.", None),
+    ]
+}
+
+fn sample_context(config: &AppConfig) -> CommitContext {
+    CommitContext {
+        files_changed: vec!["src/greet.rs".to_string()],
+        insertions: 1,
+        deletions: 1,
+        branch_name: Some("feature/ABC-123-greeting".to_string()),
+        ticket: Some("ABC-123".to_string()),
+        custom_prompt: config.commit.custom_prompt.clone(),
+        user_feedback: vec!["Keep the subject under 50 characters".to_string()],
+        convention: config.commit.convention.clone(),
+        scope_info: None,
+        history_examples: vec![
+            "feat(cli): add --repo option".to_string(),
+            "fix(git): handle detached HEAD".to_string(),
+        ],
+        previous_message: None,
+        body: config.commit.body,
+        body_bullets: config.commit.body_bullets,
+    }
+}
+
+fn sample_file_diffs() -> Vec<FileDiff> {
+    vec![
+        FileDiff {
+            filename: "src/greet.rs".to_string(),
+            old_filename: None,
+            content: SAMPLE_DIFF.to_string(),
+            insertions: 1,
+            deletions: 1,
+        },
+        FileDiff {
+            filename: "README.md".to_string(),
+            old_filename: None,
+            content: "diff --git a/README.md b/README.md\n+Run `greet` to say hello.".to_string(),
+            insertions: 1,
+            deletions: 0,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_sample_builtin_templates() {
+        let config = AppConfig::default();
+        for template in [
+            PromptTemplate::Commit,
+            PromptTemplate::Split,
+            PromptTemplate::Review,
+            PromptTemplate::ReviewMerge,
+            PromptTemplate::Tag,
+            PromptTemplate::Fixup,
+        ] {
+            let (system, user) =
+                render_sample(&TemplateSource::builtin(template), &config).unwrap();
+            assert!(!system.is_empty());
+            assert!(!user.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_render_sample_override_and_errors() {
+        let config = AppConfig::default();
+        let mut source = TemplateSource::builtin(PromptTemplate::Commit);
+        source.source = "{{ branch }} | {{ files | join(sep=\",\") }}".to_string();
+        let (_, user) = render_sample(&source, &config).unwrap();
+        assert_eq!(user, "feature/ABC-123-greeting | src/greet.rs");
+
+        // 渲染错误直接返回，不回退到内置模板
+        source.source = "{{ unknown_variable }}".to_string();
+        assert!(render_sample(&source, &config).is_err());
+    }
+}
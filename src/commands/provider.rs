@@ -0,0 +1,631 @@
+//! Provider diagnostics (`gcop-rs provider test`) and Ollama model
+//! management (`gcop-rs provider ollama list-models`).
+//!
+//! `test` sends a tiny prompt to every configured provider and reports
+//! success, latency, and streaming support. Unlike `config validate`, which
+//! stops at the first healthy provider in the chain, this probes each
+//! provider independently.
+
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use super::format::OutputFormat;
+use crate::commands::json::{self, JsonOutput};
+use crate::config::{ApiStyle, AppConfig};
+use crate::error::{GcopError, Result};
+use crate::llm::provider::backends::OllamaProvider;
+use crate::llm::provider::backends::ollama::OllamaModel;
+use crate::llm::provider::create_single_provider;
+use crate::llm::{LLMProvider, StreamChunk};
+use crate::ui;
+
+/// System prompt used for probe requests.
+const PROBE_SYSTEM_PROMPT: &str = "You are a connectivity check. Reply with the single word OK.";
+
+/// User prompt used for probe requests.
+const PROBE_USER_PROMPT: &str = "ping";
+
+/// Probe result for one configured provider.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderProbe {
+    /// Provider name (key under `[llm.providers]`).
+    pub name: String,
+    /// Resolved API style, if it could be determined.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_style: Option<String>,
+    /// Configured model name.
+    pub model: String,
+    /// Whether the non-streaming request succeeded.
+    pub success: bool,
+    /// Round-trip latency of the non-streaming request in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    /// Whether the provider implementation supports streaming.
+    pub streaming: bool,
+    /// Time to first streamed delta in milliseconds (only when streaming is supported and succeeded).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_token_ms: Option<u64>,
+    /// Error description when the probe failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Installed models of one Ollama provider (JSON `data` payload of
+/// `provider ollama list-models`).
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaModelList {
+    /// Provider name (key under `[llm.providers]`).
+    pub provider: String,
+    /// Model configured for the provider.
+    pub model: String,
+    /// Whether the configured model is installed.
+    pub installed: bool,
+    /// Models installed on the server.
+    pub models: Vec<OllamaModel>,
+}
+
+/// Runs `provider test` against every configured provider.
+pub async fn test(format: OutputFormat, config: &AppConfig) -> Result<()> {
+    format.reject_stats_only()?;
+    let colored = format.effective_colored(config.ui.colored);
+
+    let names = ordered_provider_names(config);
+    if names.is_empty() {
+        return Err(GcopError::Config(
+            rust_i18n::t!("provider.no_providers_configured").to_string(),
+        ));
+    }
+
+    if !format.is_machine_readable() {
+        ui::step(
+            "1/1",
+            &rust_i18n::t!("provider_test.probing", count = names.len()),
+            colored,
+        );
+    }
+
+    let mut probes = Vec::with_capacity(names.len());
+    for name in &names {
+        let provider_config = &config.llm.providers[name.as_str()];
+        let api_style = provider_config
+            .api_style
+            .or_else(|| name.parse::<ApiStyle>().ok())
+            .map(|s| s.to_string());
+
+        let probe = match create_single_provider(config, name, false) {
+            Ok(provider) => {
+                probe_provider(name, api_style, &provider_config.model, provider.as_ref()).await
+            }
+            Err(e) => ProviderProbe {
+                name: name.clone(),
+                api_style,
+                model: provider_config.model.clone(),
+                success: false,
+                latency_ms: None,
+                streaming: false,
+                first_token_ms: None,
+                error: Some(e.to_string()),
+            },
+        };
+        probes.push(probe);
+    }
+
+    let failed = probes.iter().filter(|p| !p.success).count();
+
+    match format {
+        OutputFormat::Json | OutputFormat::JsonStream => {
+            let output = JsonOutput {
+                success: failed == 0,
+                data: Some(&probes),
+                error: None,
+                meta: None,
+            };
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Markdown => output_markdown(&probes),
+        OutputFormat::Text
+        | OutputFormat::Csv
+        | OutputFormat::Html
+        | OutputFormat::Annotated
+        | OutputFormat::Github
+        | OutputFormat::Junit => output_text(&probes, colored),
+    }
+
+    if failed > 0 {
+        return Err(GcopError::Llm(
+            rust_i18n::t!(
+                "provider_test.failed_count",
+                failed = failed,
+                total = probes.len()
+            )
+            .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Runs the `provider` command, printing JSON errors when requested.
+///
+/// `provider_name` is the global `--provider` override, used to pick the
+/// Ollama provider for `provider ollama`.
+pub async fn run(
+    action: &crate::cli::ProviderAction,
+    provider_name: Option<&str>,
+    config: &AppConfig,
+) -> Result<()> {
+    match action {
+        crate::cli::ProviderAction::Test { format, json } => {
+            let format = OutputFormat::from_cli(format, *json);
+            let result = test(format, config).await;
+            // Probe failures are already reported inside the JSON payload.
+            if let Err(ref e) = result
+                && format.is_json()
+                && !matches!(e, GcopError::Llm(_))
+            {
+                let _ = json::output_json_error::<Vec<ProviderProbe>>(e);
+            }
+            result
+        }
+        crate::cli::ProviderAction::Ollama {
+            action: crate::cli::OllamaAction::ListModels { format, json },
+        } => {
+            let format = OutputFormat::from_cli(format, *json);
+            let result = list_ollama_models(provider_name, format, config).await;
+            if let Err(ref e) = result
+                && format.is_json()
+            {
+                let _ = json::output_json_error::<OllamaModelList>(e);
+            }
+            result
+        }
+    }
+}
+
+/// Lists the models installed on an Ollama provider's server.
+pub async fn list_ollama_models(
+    provider_name: Option<&str>,
+    format: OutputFormat,
+    config: &AppConfig,
+) -> Result<()> {
+    format.reject_stats_only()?;
+    let name = match provider_name {
+        Some(name) if is_ollama(config, name) => name.to_string(),
+        Some(name) if !config.llm.providers.contains_key(name) => {
+            return Err(GcopError::Config(
+                rust_i18n::t!("provider.provider_not_found", name = name).to_string(),
+            ));
+        }
+        Some(name) => {
+            return Err(GcopError::Config(
+                rust_i18n::t!("provider_ollama.not_ollama", provider = name).to_string(),
+            ));
+        }
+        None => ordered_provider_names(config)
+            .into_iter()
+            .find(|name| is_ollama(config, name))
+            .ok_or_else(|| {
+                GcopError::Config(rust_i18n::t!("provider_ollama.none_configured").to_string())
+            })?,
+    };
+
+    let backend = ollama_backend(config, &name)?;
+    let models = backend.list_models().await?;
+    let list = OllamaModelList {
+        installed: models.iter().any(|m| m.matches(backend.model())),
+        provider: name,
+        model: backend.model().to_string(),
+        models,
+    };
+
+    if format.is_json() {
+        let output = JsonOutput {
+            success: true,
+            data: Some(&list),
+            error: None,
+            meta: None,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    output_models_text(&list, format.effective_colored(config.ui.colored));
+    Ok(())
+}
+
+/// Offers to pull missing models of the Ollama providers in the chain
+/// (default provider + fallbacks), used by `config validate`.
+///
+/// Only prompts on an interactive terminal; failures are reported and left
+/// to the regular validation that follows.
+pub(crate) async fn offer_ollama_pulls(config: &AppConfig, colored: bool) -> Result<()> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    let chain = std::iter::once(&config.llm.default_provider).chain(&config.llm.fallback_providers);
+    let mut seen: Vec<&String> = Vec::new();
+    for name in chain {
+        if seen.contains(&name) || !is_ollama(config, name) {
+            continue;
+        }
+        seen.push(name);
+
+        let Ok(backend) = ollama_backend(config, name) else {
+            continue;
+        };
+        // Unreachable servers are reported by the validation itself
+        if !matches!(backend.has_model().await, Ok(false)) {
+            continue;
+        }
+
+        let model = backend.model();
+        let prompt = rust_i18n::t!(
+            "provider_ollama.pull_confirm",
+            model = model,
+            provider = name
+        );
+        if !ui::confirm(&prompt, true)? {
+            continue;
+        }
+
+        let spinner = ui::Spinner::new(
+            &rust_i18n::t!("provider_ollama.pulling", model = model),
+            colored,
+        );
+        let result = backend.pull_model(Some(&spinner)).await;
+        spinner.finish_and_clear();
+        match result {
+            Ok(()) => ui::success(
+                &rust_i18n::t!("provider_ollama.pulled", model = model),
+                colored,
+            ),
+            Err(e) => ui::error(&e.to_string(), colored),
+        }
+    }
+    Ok(())
+}
+
+/// Whether `name` is a configured provider using the Ollama API style.
+fn is_ollama(config: &AppConfig, name: &str) -> bool {
+    config.llm.providers.get(name).is_some_and(|p| {
+        p.api_style.or_else(|| name.parse::<ApiStyle>().ok()) == Some(ApiStyle::Ollama)
+    })
+}
+
+/// Builds the Ollama backend of provider `name`, ignoring model tiers.
+fn ollama_backend(config: &AppConfig, name: &str) -> Result<OllamaProvider> {
+    let provider_config = config.llm.providers.get(name).ok_or_else(|| {
+        GcopError::Config(rust_i18n::t!("provider.provider_not_found", name = name).to_string())
+    })?;
+    OllamaProvider::new(
+        provider_config,
+        name,
+        &config
+            .network
+            .with_overrides(provider_config.network.as_ref()),
+        false,
+    )
+}
+
+fn output_models_text(list: &OllamaModelList, colored: bool) {
+    println!();
+    println!(
+        "{}",
+        ui::info(
+            &rust_i18n::t!("provider_ollama.title", provider = list.provider),
+            colored
+        )
+    );
+    println!("{}", "─".repeat(72));
+
+    if list.models.is_empty() {
+        println!("  {}", rust_i18n::t!("provider_ollama.no_models"));
+    } else {
+        let name_width = list
+            .models
+            .iter()
+            .map(|m| m.name.len())
+            .max()
+            .unwrap_or(0)
+            .max(5);
+        println!(
+            "    {:<name_width$}  {:>10}  {}",
+            rust_i18n::t!("provider_ollama.col_model"),
+            rust_i18n::t!("provider_ollama.col_size"),
+            rust_i18n::t!("provider_ollama.col_modified"),
+        );
+        for model in &list.models {
+            let marker = if model.matches(&list.model) { "*" } else { " " };
+            let line = format!(
+                "  {} {:<name_width$}  {:>10}  {}",
+                marker,
+                model.name,
+                crate::git::diff::format_size(model.size),
+                model
+                    .modified_at
+                    .as_deref()
+                    .map(|t| t.get(..10).unwrap_or(t))
+                    .unwrap_or("-"),
+            );
+            if colored && marker == "*" {
+                println!("{}", line.green());
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+
+    println!();
+    if !list.installed {
+        ui::warning(
+            &rust_i18n::t!("provider_ollama.model_missing", model = list.model),
+            colored,
+        );
+    }
+}
+
+/// Returns provider names in probe order: default, fallbacks, then the rest alphabetically.
+fn ordered_provider_names(config: &AppConfig) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let chain = std::iter::once(&config.llm.default_provider).chain(&config.llm.fallback_providers);
+    for name in chain {
+        if config.llm.providers.contains_key(name) && !names.contains(name) {
+            names.push(name.clone());
+        }
+    }
+
+    let mut rest: Vec<&String> = config
+        .llm
+        .providers
+        .keys()
+        .filter(|k| !names.contains(k))
+        .collect();
+    rest.sort();
+    names.extend(rest.into_iter().cloned());
+    names
+}
+
+/// Probes a single provider with a non-streaming and (if supported) a streaming request.
+pub async fn probe_provider(
+    name: &str,
+    api_style: Option<String>,
+    model: &str,
+    provider: &dyn LLMProvider,
+) -> ProviderProbe {
+    let streaming = provider.supports_streaming();
+
+    let start = Instant::now();
+    let result = provider
+        .send_prompt(PROBE_SYSTEM_PROMPT, PROBE_USER_PROMPT, None)
+        .await;
+    let latency_ms = duration_ms(start.elapsed());
+
+    let (success, mut error) = match result {
+        Ok(_) => (true, None),
+        Err(e) => (false, Some(e.to_string())),
+    };
+
+    let mut first_token_ms = None;
+    if success && streaming {
+        match measure_first_token(provider).await {
+            Ok(ms) => first_token_ms = ms,
+            Err(e) => {
+                error = Some(rust_i18n::t!("provider_test.streaming_failed", error = e).to_string())
+            }
+        }
+    }
+
+    ProviderProbe {
+        name: name.to_string(),
+        api_style,
+        model: model.to_string(),
+        success,
+        latency_ms: success.then_some(latency_ms),
+        streaming,
+        first_token_ms,
+        error,
+    }
+}
+
+/// Measures time to the first streamed delta.
+async fn measure_first_token(
+    provider: &dyn LLMProvider,
+) -> std::result::Result<Option<u64>, String> {
+    let start = Instant::now();
+    let mut handle = provider
+        .send_prompt_streaming(PROBE_SYSTEM_PROMPT, PROBE_USER_PROMPT)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut first = None;
+    while let Some(chunk) = handle.receiver.recv().await {
+        match chunk {
+            StreamChunk::Delta(_) => {
+                if first.is_none() {
+                    first = Some(duration_ms(start.elapsed()));
+                }
+            }
+            StreamChunk::Done => break,
+            StreamChunk::Error(e) => return Err(e),
+            StreamChunk::Retry => first = None,
+        }
+    }
+    Ok(first)
+}
+
+fn duration_ms(d: Duration) -> u64 {
+    u64::try_from(d.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn format_ms(ms: Option<u64>) -> String {
+    ms.map(|v| format!("{} ms", v))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn output_text(probes: &[ProviderProbe], colored: bool) {
+    println!();
+    println!(
+        "{}",
+        ui::info(&rust_i18n::t!("provider_test.title"), colored)
+    );
+    println!("{}", "─".repeat(72));
+
+    let name_width = probes
+        .iter()
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    let model_width = probes
+        .iter()
+        .map(|p| p.model.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+
+    println!(
+        "  {:<name_width$}  {:<7}  {:<model_width$}  {:<6}  {:>10}  {:>10}",
+        rust_i18n::t!("provider_test.col_provider"),
+        rust_i18n::t!("provider_test.col_style"),
+        rust_i18n::t!("provider_test.col_model"),
+        rust_i18n::t!("provider_test.col_status"),
+        rust_i18n::t!("provider_test.col_latency"),
+        rust_i18n::t!("provider_test.col_stream"),
+    );
+
+    for probe in probes {
+        let status = if probe.success { "ok" } else { "failed" };
+        let status = if colored {
+            if probe.success {
+                format!("{:<6}", status).green().to_string()
+            } else {
+                format!("{:<6}", status).red().to_string()
+            }
+        } else {
+            format!("{:<6}", status)
+        };
+        let stream = if probe.streaming {
+            format_ms(probe.first_token_ms)
+        } else {
+            rust_i18n::t!("provider_test.no_streaming").to_string()
+        };
+        println!(
+            "  {:<name_width$}  {:<7}  {:<model_width$}  {}  {:>10}  {:>10}",
+            probe.name,
+            probe.api_style.as_deref().unwrap_or("?"),
+            probe.model,
+            status,
+            format_ms(probe.latency_ms),
+            stream,
+        );
+        if let Some(err) = &probe.error {
+            let line = format!("    ↳ {}", err);
+            if colored {
+                println!("{}", line.truecolor(150, 150, 150));
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+    println!();
+}
+
+fn output_markdown(probes: &[ProviderProbe]) {
+    println!("{}\n", rust_i18n::t!("provider_test.md_title"));
+    println!(
+        "| {} | {} | {} | {} | {} | {} |",
+        rust_i18n::t!("provider_test.col_provider"),
+        rust_i18n::t!("provider_test.col_style"),
+        rust_i18n::t!("provider_test.col_model"),
+        rust_i18n::t!("provider_test.col_status"),
+        rust_i18n::t!("provider_test.col_latency"),
+        rust_i18n::t!("provider_test.col_stream"),
+    );
+    println!("|---|---|---|---|---|---|");
+    for probe in probes {
+        println!(
+            "| {} | {} | {} | {} | {} | {} |",
+            probe.name,
+            probe.api_style.as_deref().unwrap_or("?"),
+            probe.model,
+            if probe.success { "✅" } else { "❌" },
+            format_ms(probe.latency_ms),
+            if probe.streaming {
+                format_ms(probe.first_token_ms)
+            } else {
+                "-".to_string()
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+    use crate::llm::provider::test_utils::ScriptedProvider;
+
+    fn provider_config(model: &str) -> ProviderConfig {
+        ProviderConfig {
+            api_style: None,
+            endpoint: None,
+            api_key: None,
+            model: model.to_string(),
+            max_tokens: None,
+            temperature: None,
+            top_p: None,
+            stop: None,
+            proxy: None,
+            network: None,
+            pricing: None,
+            tiers: None,
+            allowed_models: None,
+            extra: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_probe_provider_success() {
+        let probe = probe_provider(
+            "a",
+            Some("openai".into()),
+            "m",
+            &ScriptedProvider::new(&["OK"]).with_streaming(true),
+        )
+        .await;
+        assert!(probe.success);
+        assert!(probe.latency_ms.is_some());
+        assert!(probe.streaming);
+        assert!(probe.first_token_ms.is_some());
+        assert!(probe.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_probe_provider_failure() {
+        let probe = probe_provider("a", None, "m", &ScriptedProvider::failing("boom")).await;
+        assert!(!probe.success);
+        assert!(probe.latency_ms.is_none());
+        assert!(probe.first_token_ms.is_none());
+        assert!(probe.error.unwrap().contains("boom"));
+    }
+
+    #[test]
+    fn test_ordered_provider_names_chain_first() {
+        let mut config = AppConfig::default();
+        for name in ["zeta", "alpha", "main", "backup"] {
+            config
+                .llm
+                .providers
+                .insert(name.to_string(), provider_config("m"));
+        }
+        config.llm.default_provider = "main".to_string();
+        config.llm.fallback_providers = vec!["backup".to_string()];
+
+        assert_eq!(
+            ordered_provider_names(&config),
+            vec!["main", "backup", "alpha", "zeta"]
+        );
+    }
+}
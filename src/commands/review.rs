@@ -1,72 +1,169 @@
 use super::options::ReviewOptions;
-use super::smart_truncate_diff;
+use super::review_baseline::{self, BASELINE_PATH, ReviewBaseline};
+use super::review_cache::{self, CacheKey};
+use super::review_context::{self, ReviewFile};
+use super::review_snapshot::{self, ReviewSnapshots};
+use super::{chunk_diff, smart_truncate_diff};
 use crate::cli::ReviewTarget;
 use crate::commands::json::{self, JsonOutput};
 use crate::config::AppConfig;
 use crate::error::{GcopError, Result};
+use crate::forge::{self, Forge, MergeRequest};
 use crate::git::{GitOperations, repository::GitRepository};
+use crate::llm::audit::sha256_hex;
+use crate::llm::ledger;
+use crate::llm::metrics::{self, LlmMetrics};
+use crate::llm::prompt::{
+    build_review_merge_prompt, build_review_prompt_split, review_preset_prompt,
+};
+use crate::llm::provider::base::process_review_response;
 use crate::llm::{
-    IssueSeverity, LLMProvider, ProgressReporter, ReviewResult, ReviewType,
+    IssueSeverity, LLMProvider, ProgressReporter, ReviewIssue, ReviewResult, ReviewType,
     provider::create_provider,
 };
-use crate::ui;
+use crate::ui::{self, Reporter};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// JSON payload: the review result plus the `--fail-on` gate outcome.
+#[derive(Debug, Serialize)]
+struct ReviewJson<'a> {
+    #[serde(flatten)]
+    result: &'a ReviewResult,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gate: Option<ReviewGate>,
+}
+
+/// Outcome of `--fail-on`, mirrored in the process exit code.
+#[derive(Debug, Serialize)]
+struct ReviewGate {
+    /// Threshold passed to `--fail-on`.
+    fail_on: IssueSeverity,
+    /// Highest severity among the reported issues (`null` when there are none).
+    highest_severity: Option<IssueSeverity>,
+    /// Number of issues at or above the threshold.
+    failing_issues: usize,
+    /// Whether the gate passed.
+    passed: bool,
+    /// Process exit code (`0` pass, `10` critical, `11` warning, `12` info).
+    exit_code: i32,
+}
 
 /// Execute review command (public interface)
 pub async fn run(options: &ReviewOptions<'_>, config: &AppConfig) -> Result<()> {
     let repo = GitRepository::open(Some(&config.file))?;
-    let provider = create_provider(config, options.provider_override)?;
-    let result = run_internal(options, config, &repo, provider.as_ref()).await;
+    let provider = create_provider(
+        config,
+        &config.review.llm,
+        options.provider_override,
+        options.model_override,
+    )?;
+    let reporter = options.format.reporter(config.ui.colored);
+    let result = run_internal(options, config, &repo, provider.as_ref(), reporter.as_ref()).await;
+    // The gate outcome is already part of the JSON payload
     if let Err(ref e) = result
         && options.format.is_json()
+        && !matches!(e, GcopError::ReviewGateFailed { .. })
     {
-        let _ = json::output_json_error::<ReviewResult>(e);
+        let _ = json::output_error::<ReviewResult>(e, options.format);
     }
     result
 }
 
 /// Internal implementation, accepts dependency injection (for testing)
+///
+/// Status output (steps, spinners, messages, confirmations) goes to
+/// `reporter`; the review itself is printed in `options.format`.
 #[cfg_attr(not(feature = "test-utils"), allow(dead_code))]
 pub async fn run_internal(
     options: &ReviewOptions<'_>,
     config: &AppConfig,
     git: &dyn GitOperations,
     llm: &dyn LLMProvider,
+    reporter: &dyn Reporter,
 ) -> Result<()> {
+    options.format.reject_stats_only()?;
     let skip_ui = options.format.is_machine_readable();
     let colored = options.effective_colored(config);
 
+    // --apply edits the working tree and asks for confirmation
+    if options.apply {
+        if !matches!(options.target, ReviewTarget::Changes) {
+            return Err(GcopError::InvalidInput(
+                rust_i18n::t!("review.apply.changes_only").to_string(),
+            ));
+        }
+        if skip_ui {
+            return Err(GcopError::InvalidInput(
+                rust_i18n::t!("review.apply.text_only").to_string(),
+            ));
+        }
+    }
+
+    if options.incremental == Some(true) && !matches!(options.target, ReviewTarget::Changes) {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("review.incremental.changes_only").to_string(),
+        ));
+    }
+    // Snapshot recorded once an incremental review completes: (file, branch, tree)
+    let mut pending_snapshot: Option<(PathBuf, Option<String>, String)> = None;
+
+    // Merge request reviews keep the forge client around for --post
+    let mut merge_request: Option<(Box<dyn Forge>, MergeRequest)> = None;
+    // Related files for `review file --with-context`
+    let mut file_context: Option<String> = None;
+    // Range of `review range` after default-branch expansion
+    let mut resolved_range: Option<String> = None;
+
     // Route based on destination type
     let (diff, description) = match options.target {
         ReviewTarget::Changes => {
-            if !skip_ui {
-                ui::step(
-                    &rust_i18n::t!("review.step1"),
-                    &rust_i18n::t!("review.analyzing_changes"),
-                    colored,
-                );
-            }
-            let diff = git.get_uncommitted_diff()?;
-            if diff.trim().is_empty() {
-                if !skip_ui {
-                    ui::error(&rust_i18n::t!("review.no_changes"), colored);
+            reporter.step(
+                &rust_i18n::t!("review.step1"),
+                &rust_i18n::t!("review.analyzing_changes"),
+            );
+            let incremental = options.effective_incremental(config);
+            let since_last = if incremental {
+                incremental_diff(git, &mut pending_snapshot)?
+            } else {
+                None
+            };
+            match since_last {
+                Some(diff) => {
+                    if diff.trim().is_empty() {
+                        return Err(GcopError::InvalidInput(
+                            rust_i18n::t!("review.incremental.unchanged").to_string(),
+                        ));
+                    }
+                    reporter.info(&rust_i18n::t!("review.incremental.since"));
+                    (
+                        diff,
+                        rust_i18n::t!("review.description.incremental").to_string(),
+                    )
+                }
+                None => {
+                    if incremental {
+                        reporter.info(&rust_i18n::t!("review.incremental.first"));
+                    }
+                    let diff = git.get_uncommitted_diff()?;
+                    if diff.trim().is_empty() {
+                        reporter.error(&rust_i18n::t!("review.no_changes"));
+                        return Err(GcopError::InvalidInput(
+                            rust_i18n::t!("review.no_uncommitted_changes_to_review").to_string(),
+                        ));
+                    }
+                    (
+                        diff,
+                        rust_i18n::t!("review.description.uncommitted").to_string(),
+                    )
                 }
-                return Err(GcopError::InvalidInput(
-                    rust_i18n::t!("review.no_uncommitted_changes_to_review").to_string(),
-                ));
             }
-            (
-                diff,
-                rust_i18n::t!("review.description.uncommitted").to_string(),
-            )
         }
         ReviewTarget::Commit { hash } => {
-            if !skip_ui {
-                ui::step(
-                    &rust_i18n::t!("review.step1"),
-                    &rust_i18n::t!("review.analyzing_commit", hash = hash),
-                    colored,
-                );
-            }
+            reporter.step(
+                &rust_i18n::t!("review.step1"),
+                &rust_i18n::t!("review.analyzing_commit", hash = hash),
+            );
             let diff = git.get_commit_diff(hash)?;
             (
                 diff,
@@ -74,93 +171,664 @@ pub async fn run_internal(
             )
         }
         ReviewTarget::Range { range } => {
-            if !skip_ui {
-                ui::step(
-                    &rust_i18n::t!("review.step1"),
-                    &rust_i18n::t!("review.analyzing_range", range = range),
-                    colored,
-                );
-            }
-            let diff = git.get_range_diff(range)?;
-            (
-                diff,
-                rust_i18n::t!("review.description.range", range = range).to_string(),
-            )
+            let range = crate::git::expand_range(git, range.as_deref())?;
+            reporter.step(
+                &rust_i18n::t!("review.step1"),
+                &rust_i18n::t!("review.analyzing_range", range = range.as_str()),
+            );
+            let diff = git.get_range_diff(&range)?;
+            let description =
+                rust_i18n::t!("review.description.range", range = range.as_str()).to_string();
+            resolved_range = Some(range);
+            (diff, description)
         }
-        ReviewTarget::File { path } => {
-            if !skip_ui {
-                ui::step(
-                    &rust_i18n::t!("review.step1"),
-                    &rust_i18n::t!("review.analyzing_file", path = path),
-                    colored,
+        ReviewTarget::File { path, with_context } => {
+            reporter.step(
+                &rust_i18n::t!("review.step1"),
+                &rust_i18n::t!("review.analyzing_file", path = path),
+            );
+            let (files, targets) = read_review_files(git, path, config, reporter)?;
+            if *with_context {
+                let related = review_context::related_files(
+                    git,
+                    &targets,
+                    &git.get_workdir()?,
+                    config.review.context_tokens,
                 );
+                reporter.info(&rust_i18n::t!(
+                    "review.context_files",
+                    count = related.len()
+                ));
+                file_context = (!related.is_empty()).then(|| review_context::render(&related));
             }
-            let content = git.get_file_content(path)?;
             // File review requires special handling, wrapping content into diff format
-            let diff = format!("--- {}\n+++ {}\n{}", path, path, content);
             (
-                diff,
+                review_context::render(&files),
                 rust_i18n::t!("review.description.file", path = path).to_string(),
             )
         }
+        ReviewTarget::Mr { iid, .. } => {
+            reporter.step(
+                &rust_i18n::t!("review.step1"),
+                &rust_i18n::t!("review.analyzing_mr", iid = iid),
+            );
+            let forge = forge::open(config, git)?;
+            let mr = forge.fetch_merge_request(*iid).await?;
+            if mr.diff.trim().is_empty() {
+                return Err(GcopError::InvalidInput(
+                    rust_i18n::t!("review.mr_no_changes", iid = iid).to_string(),
+                ));
+            }
+            let result = (
+                mr.diff.clone(),
+                rust_i18n::t!(
+                    "review.description.mr",
+                    iid = iid,
+                    title = mr.title.as_str(),
+                    source = mr.source_branch.as_str(),
+                    target = mr.target_branch.as_str()
+                )
+                .to_string(),
+            );
+            merge_request = Some((forge, mr));
+            result
+        }
     };
 
-    // Call LLM for review (truncate overly large diffs)
-    let (diff, truncated) = smart_truncate_diff(&diff, config.llm.max_diff_size);
-    if truncated && !skip_ui {
-        ui::warning(&rust_i18n::t!("diff.truncated"), colored);
+    // Apply [privacy].on_secret, then truncate overly large diffs
+    let diff = super::privacy::guard(diff, config, skip_ui, colored)?;
+    let file_context = file_context
+        .map(|context| super::privacy::guard(context, config, skip_ui, colored))
+        .transpose()?;
+    // Diffs over budget are reviewed in parts when possible, truncated otherwise
+    let (chunks, chunks_truncated) = if options.show_redacted {
+        (Vec::new(), false)
+    } else {
+        chunk_diff(&diff, config.llm.max_diff_size, config.review.max_chunks)
+    };
+    let (diff, truncated) = if chunks.is_empty() {
+        smart_truncate_diff(&diff, config.llm.max_diff_size)
+    } else {
+        (diff, chunks_truncated)
+    };
+    if truncated {
+        reporter.warning(&rust_i18n::t!("diff.truncated"));
+    }
+    if options.show_redacted {
+        super::privacy::print_preview(&rust_i18n::t!("privacy.preview_title"), &diff, colored);
+        if let Some(context) = &file_context {
+            super::privacy::print_preview(
+                &rust_i18n::t!("privacy.preview_context"),
+                context,
+                colored,
+            );
+        }
+        return Ok(());
     }
     let review_type = match options.target {
         ReviewTarget::Changes => ReviewType::UncommittedChanges,
         ReviewTarget::Commit { hash } => ReviewType::SingleCommit(hash.clone()),
-        ReviewTarget::Range { range } => ReviewType::CommitRange(range.clone()),
-        ReviewTarget::File { path } => ReviewType::FileOrDir(path.clone()),
+        ReviewTarget::Range { .. } => ReviewType::CommitRange(resolved_range.unwrap_or_default()),
+        ReviewTarget::File { path, .. } => ReviewType::FileOrDir {
+            path: path.clone(),
+            context: file_context,
+        },
+        ReviewTarget::Mr { iid, .. } => ReviewType::MergeRequest(*iid),
     };
 
-    // Machine-readable format does not display spinner
-    let spinner = if skip_ui {
+    // A preset replaces the configured review prompt
+    let preset_prompt = options.preset.map(review_preset_prompt);
+    let custom_prompt = preset_prompt
+        .as_deref()
+        .or(config.review.custom_prompt.as_deref());
+    if let Some(preset) = options.preset {
+        reporter.info(&rust_i18n::t!(
+            "review.preset_active",
+            preset = preset.as_str()
+        ));
+    }
+
+    // Stream text reviews when possible; structured output needs the full response
+    let use_streaming = matches!(options.format, super::format::OutputFormat::Text)
+        && config.ui.streaming
+        && !config.review.structured_output
+        && llm.supports_streaming();
+    // --json-stream forwards the raw model output as `delta` events
+    let stream_events = options.format.is_json_stream()
+        && config.ui.streaming
+        && !config.review.structured_output
+        && llm.supports_streaming();
+
+    // Reviews of immutable objects are served from the result cache
+    let cache = if options.no_cache {
         None
     } else {
-        Some(ui::Spinner::new(
-            &rust_i18n::t!("spinner.reviewing"),
-            colored,
-        ))
+        review_cache_key(options, config, git, &review_type, custom_prompt)
     };
+    let cached = cache
+        .as_ref()
+        .and_then(|(dir, key)| review_cache::load(dir, key).map(|result| (key, result)));
+    let from_cache = cached.is_some();
 
-    let result = llm
-        .review_code(
-            &diff,
-            review_type,
-            config.review.custom_prompt.as_deref(),
-            spinner.as_ref().map(|s| s as &dyn ProgressReporter),
-        )
-        .await?;
+    let mut streamed_summary = None;
+    metrics::start();
+    let mut result = if let Some((key, result)) = cached {
+        reporter.info(&rust_i18n::t!(
+            "review.cache_hit",
+            target = key.target.as_str()
+        ));
+        result
+    } else if !chunks.is_empty() {
+        reporter.info(&rust_i18n::t!("review.chunked", count = chunks.len()));
+        let spinner = reporter.spinner(&rust_i18n::t!("spinner.reviewing"));
+        let result =
+            review_in_chunks(llm, &chunks, &review_type, custom_prompt, spinner.as_ref()).await;
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+        result?
+    } else if use_streaming {
+        reporter.step(
+            &rust_i18n::t!("review.step2"),
+            &rust_i18n::t!("spinner.reviewing_streaming"),
+        );
+        println!();
+        let handle = llm
+            .review_code_streaming(&diff, review_type, custom_prompt)
+            .await?;
+        let mut output = ui::ReviewStreamingOutput::new(colored);
+        let response = output.process(handle.receiver).await?;
+        streamed_summary = Some(output.summary().to_string());
+        process_review_response(&response)?
+    } else if stream_events {
+        reporter.step(
+            &rust_i18n::t!("review.step2"),
+            &rust_i18n::t!("spinner.reviewing_streaming"),
+        );
+        let handle = llm
+            .review_code_streaming(&diff, review_type, custom_prompt)
+            .await?;
+        let response = ui::forward_stream(handle.receiver, reporter).await?;
+        process_review_response(&response)?
+    } else {
+        let spinner = reporter.spinner(&rust_i18n::t!("spinner.reviewing"));
+
+        let result = llm
+            .review_code(
+                &diff,
+                review_type,
+                custom_prompt,
+                spinner.as_ref().map(|s| s as &dyn ProgressReporter),
+            )
+            .await?;
 
-    if let Some(s) = spinner {
-        s.finish_and_clear();
+        if let Some(s) = spinner {
+            s.finish_and_clear();
+        }
+        result
+    };
+    let llm_metrics = metrics::finish();
+    ledger::record("review", &llm_metrics, config);
+    if let Some((path, branch, tree)) = pending_snapshot {
+        let mut snapshots = ReviewSnapshots::load(&path);
+        snapshots.set(branch.as_deref(), tree);
+        snapshots.save(&path);
+    }
+    if let Some(preset) = options.preset {
+        preset.categorize(&mut result.issues);
+    }
+    if !from_cache && let Some((dir, key)) = &cache {
+        review_cache::store(dir, key, &result);
+    }
+    if options.verbose {
+        // Machine-readable formats keep stdout clean
+        super::commit::print_verbose_metrics(&llm_metrics, skip_ui, colored);
+    }
+
+    // Record or apply the known-issue baseline
+    let baseline_path = review_baseline::baseline_path(&git.get_workdir()?);
+    if options.update_baseline {
+        ReviewBaseline::from_issues(&result.issues).save(&baseline_path)?;
+        reporter.success(&rust_i18n::t!(
+            "review.baseline.updated",
+            count = result.issues.len(),
+            path = BASELINE_PATH
+        ));
+    } else if let Some(baseline) = ReviewBaseline::load(&baseline_path)? {
+        let suppressed = baseline.filter(&mut result);
+        if suppressed > 0 {
+            reporter.info(&rust_i18n::t!(
+                "review.baseline.suppressed",
+                count = suppressed
+            ));
+        }
     }
 
     // Formatted output
+    reporter.step(
+        &rust_i18n::t!("review.step3"),
+        &rust_i18n::t!("review.formatting"),
+    );
     if !skip_ui {
-        ui::step(
-            &rust_i18n::t!("review.step3"),
-            &rust_i18n::t!("review.formatting"),
-            colored,
-        );
         println!();
     }
 
+    let gate_error = options
+        .fail_on
+        .and_then(|threshold| check_gate(&result.issues, threshold));
+
     match options.format {
-        super::format::OutputFormat::Json => print_json(&result)?,
+        super::format::OutputFormat::Json | super::format::OutputFormat::JsonStream => {
+            let gate = options.fail_on.map(|threshold| ReviewGate {
+                fail_on: threshold,
+                highest_severity: highest_severity(&result.issues),
+                failing_issues: match gate_error {
+                    Some(GcopError::ReviewGateFailed { count, .. }) => count,
+                    _ => 0,
+                },
+                passed: gate_error.is_none(),
+                exit_code: gate_error.as_ref().map_or(0, GcopError::exit_code),
+            });
+            print_json(&result, gate, llm_metrics, options.format)?
+        }
         super::format::OutputFormat::Markdown => print_markdown(&result, &description, colored),
-        super::format::OutputFormat::Text => print_text(&result, &description, config),
+        super::format::OutputFormat::Annotated => {
+            print_annotated(&result, &description, &diff, config)
+        }
+        super::format::OutputFormat::Github => print_github(&result),
+        super::format::OutputFormat::Junit => print_junit(&result, config),
+        super::format::OutputFormat::Text
+        | super::format::OutputFormat::Csv
+        | super::format::OutputFormat::Html => {
+            let summary_shown = streamed_summary.is_some_and(|summary| {
+                !summary.is_empty() && summary.trim() == result.summary.trim()
+            });
+            print_text(&result, &description, config, summary_shown)
+        }
+    }
+
+    if options.apply {
+        apply_suggested_patches(&result, git, reporter, colored)?;
+    }
+
+    if let ReviewTarget::Mr { post: true, .. } = options.target
+        && let Some((forge, mr)) = &merge_request
+    {
+        let posted = forge.post_review(mr, &result).await?;
+        reporter.success(&rust_i18n::t!(
+            "review.mr_posted",
+            count = posted,
+            iid = mr.iid,
+            url = mr.web_url.as_str()
+        ));
+    }
+
+    match gate_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Diff of `review changes --incremental`: the changes since the tree last
+/// reviewed on the current branch, or `None` when there is no usable earlier
+/// snapshot. Stores the snapshot to record in `pending` once the review
+/// succeeds; snapshot failures only cost the incremental mode.
+fn incremental_diff(
+    git: &dyn GitOperations,
+    pending: &mut Option<(PathBuf, Option<String>, String)>,
+) -> Result<Option<String>> {
+    let path = review_snapshot::snapshot_path(&git.get_git_dir()?);
+    let branch = git.get_current_branch()?;
+    let previous = ReviewSnapshots::load(&path)
+        .get(branch.as_deref())
+        .map(str::to_string);
+
+    match git.snapshot_workdir() {
+        Ok(tree) => *pending = Some((path, branch, tree)),
+        Err(e) => tracing::warn!("Failed to snapshot the working tree: {}", e),
+    }
+
+    Ok(
+        previous.and_then(|tree| match git.get_snapshot_diff(&tree) {
+            Ok(diff) => Some(diff),
+            // The tree may have been garbage-collected
+            Err(e) => {
+                tracing::warn!("Ignoring review snapshot {}: {}", tree, e);
+                None
+            }
+        }),
+    )
+}
+
+/// Result cache entry of a `commit` / `range` review, or `None` for other
+/// targets or when the reviewed commits cannot be resolved.
+///
+/// The key pins the resolved commit hashes, the provider and model, and a
+/// hash of the review prompt and of the settings shaping the sent diff.
+fn review_cache_key(
+    options: &ReviewOptions<'_>,
+    config: &AppConfig,
+    git: &dyn GitOperations,
+    review_type: &ReviewType,
+    custom_prompt: Option<&str>,
+) -> Option<(PathBuf, CacheKey)> {
+    let resolve = |rev: &str| {
+        let rev = if rev.is_empty() { "HEAD" } else { rev };
+        git.get_commit_info(rev)
+            .map(|info| info.hash)
+            .map_err(|e| tracing::debug!("Not caching review of {}: {}", rev, e))
+            .ok()
+    };
+    let target = match review_type {
+        ReviewType::SingleCommit(hash) => resolve(hash)?,
+        ReviewType::CommitRange(range) => {
+            let separator = if range.contains("...") { "..." } else { ".." };
+            let (base, head) = range.split_once(separator)?;
+            format!("{}{}{}", resolve(base)?, separator, resolve(head)?)
+        }
+        _ => return None,
+    };
+
+    let (provider, model) = config.review.llm.select(
+        &config.llm.default_provider,
+        options.provider_override,
+        options.model_override,
+    );
+    let model = model
+        .map(str::to_string)
+        .or_else(|| config.llm.providers.get(provider).map(|p| p.model.clone()))
+        .unwrap_or_default();
+
+    let (system, user) = build_review_prompt_split("", review_type, custom_prompt);
+    let prompt = format!(
+        "{}\n{}\n{}\n{}\n{}",
+        system,
+        user,
+        config.llm.max_diff_size,
+        config.review.max_chunks,
+        config.review.structured_output
+    );
+
+    let dir = review_cache::cache_dir(&git.get_git_dir().ok()?);
+    Some((
+        dir,
+        CacheKey {
+            target,
+            model: format!("{}:{}", provider, model),
+            prompt: sha256_hex(prompt.as_bytes()),
+        },
+    ))
+}
+
+/// Reviews a diff split by [`chunk_diff`]: each part is reviewed on its own
+/// (map), then one more request merges the partial reviews into a single
+/// summary and issue list (reduce).
+///
+/// When the merge request fails or cannot be parsed, the partial reviews
+/// are concatenated instead so the work already done is not lost.
+async fn review_in_chunks(
+    llm: &dyn LLMProvider,
+    chunks: &[String],
+    review_type: &ReviewType,
+    custom_prompt: Option<&str>,
+    spinner: Option<&ui::Spinner>,
+) -> Result<ReviewResult> {
+    let mut parts = Vec::with_capacity(chunks.len());
+    for (index, chunk) in chunks.iter().enumerate() {
+        if let Some(spinner) = spinner {
+            spinner.set_message(&rust_i18n::t!(
+                "spinner.reviewing_chunk",
+                index = index + 1,
+                total = chunks.len()
+            ));
+        }
+        let part = llm
+            .review_code(
+                chunk,
+                review_type.clone(),
+                custom_prompt,
+                spinner.map(|s| s as &dyn ProgressReporter),
+            )
+            .await?;
+        parts.push(part);
+    }
+
+    if let Some(spinner) = spinner {
+        spinner.set_message(&rust_i18n::t!(
+            "spinner.merging_reviews",
+            total = parts.len()
+        ));
+    }
+    let (system, user) = build_review_merge_prompt(&parts);
+    let merged = llm
+        .send_prompt(&system, &user, spinner.map(|s| s as &dyn ProgressReporter))
+        .await
+        .and_then(|response| process_review_response(&response));
+    match merged {
+        Ok(mut merged) => {
+            restore_patches(&mut merged, &parts);
+            Ok(merged)
+        }
+        Err(e) => {
+            tracing::warn!("Merging chunked reviews failed, concatenating them: {}", e);
+            Ok(concat_reviews(parts))
+        }
+    }
+}
+
+/// Concatenates partial reviews, dropping repeated issues and suggestions.
+fn concat_reviews(parts: Vec<ReviewResult>) -> ReviewResult {
+    let mut merged = ReviewResult {
+        summary: String::new(),
+        issues: Vec::new(),
+        suggestions: Vec::new(),
+    };
+    let mut summaries = Vec::new();
+    for part in parts {
+        if !part.summary.trim().is_empty() {
+            summaries.push(part.summary.trim().to_string());
+        }
+        for issue in part.issues {
+            let seen = merged.issues.iter().any(|known| {
+                known.file == issue.file
+                    && known.line == issue.line
+                    && known.description == issue.description
+            });
+            if !seen {
+                merged.issues.push(issue);
+            }
+        }
+        for suggestion in part.suggestions {
+            if !merged.suggestions.contains(&suggestion) {
+                merged.suggestions.push(suggestion);
+            }
+        }
+    }
+    merged.summary = summaries.join("\n\n");
+    merged
+}
+
+/// Puts back suggested patches the merge request dropped, matching issues
+/// of the partial reviews by file and line.
+fn restore_patches(merged: &mut ReviewResult, parts: &[ReviewResult]) {
+    for issue in merged
+        .issues
+        .iter_mut()
+        .filter(|issue| issue.suggested_patch.is_none() && issue.file.is_some())
+    {
+        issue.suggested_patch = parts
+            .iter()
+            .flat_map(|part| &part.issues)
+            .find(|original| original.file == issue.file && original.line == issue.line)
+            .and_then(|original| original.suggested_patch.clone());
+    }
+}
+
+/// Reads the files to review for `review file <PATH>`.
+///
+/// A file is read as is (errors are reported); a directory is walked with
+/// `.gitignore` / `.gcopignore` applied, keeping files in path order until
+/// `[llm].max_diff_size` is reached. Returns the files and the reviewed paths.
+fn read_review_files(
+    git: &dyn GitOperations,
+    path: &str,
+    config: &AppConfig,
+    reporter: &dyn Reporter,
+) -> Result<(Vec<ReviewFile>, Vec<PathBuf>)> {
+    if !Path::new(path).is_dir() {
+        let file = ReviewFile {
+            path: path.to_string(),
+            content: git.get_file_content(path)?,
+        };
+        return Ok((vec![file], vec![PathBuf::from(path)]));
+    }
+
+    let (files, omitted) = review_context::read_files(
+        git,
+        &review_context::walk(Path::new(path)),
+        config.llm.max_diff_size,
+    );
+    if files.is_empty() {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("review.no_files", path = path).to_string(),
+        ));
+    }
+    if omitted > 0 {
+        reporter.warning(&rust_i18n::t!("review.files_omitted", count = omitted));
+    }
+    let targets = files.iter().map(|file| PathBuf::from(&file.path)).collect();
+    Ok((files, targets))
+}
+
+/// Preview each suggested patch and apply the confirmed ones via `git apply`.
+fn apply_suggested_patches(
+    result: &ReviewResult,
+    git: &dyn GitOperations,
+    reporter: &dyn Reporter,
+    colored: bool,
+) -> Result<()> {
+    let patches: Vec<(&ReviewIssue, String)> = result
+        .issues
+        .iter()
+        .filter_map(|issue| {
+            issue
+                .suggested_patch
+                .as_deref()
+                .and_then(normalize_patch)
+                .map(|patch| (issue, patch))
+        })
+        .collect();
+
+    if patches.is_empty() {
+        reporter.info(&rust_i18n::t!("review.apply.none"));
+        return Ok(());
+    }
+
+    let total = patches.len();
+    let mut applied = 0;
+    for (i, (issue, patch)) in patches.iter().enumerate() {
+        println!(
+            "{}",
+            rust_i18n::t!(
+                "review.apply.header",
+                index = i + 1,
+                total = total,
+                description = issue.description.as_str()
+            )
+        );
+        println!();
+        print_patch_preview(patch, colored);
+        println!();
+
+        if let Err(e) = git.apply_patch(patch, true) {
+            reporter.warning(&rust_i18n::t!(
+                "review.apply.does_not_apply",
+                error = e.to_string()
+            ));
+            println!();
+            continue;
+        }
+
+        if reporter.confirm(&rust_i18n::t!("review.apply.confirm"), false)? {
+            git.apply_patch(patch, false)?;
+            applied += 1;
+            reporter.success(&rust_i18n::t!("review.apply.applied"));
+        }
+        println!();
     }
 
+    reporter.info(&rust_i18n::t!(
+        "review.apply.summary",
+        applied = applied,
+        total = total
+    ));
     Ok(())
 }
 
+/// Normalize an LLM-provided patch for `git apply`.
+///
+/// Strips surrounding Markdown code fences and guarantees a trailing newline.
+/// Returns `None` if the text does not look like a unified diff.
+fn normalize_patch(patch: &str) -> Option<String> {
+    let mut lines: Vec<&str> = patch.trim_matches('\n').lines().collect();
+    if lines
+        .first()
+        .is_some_and(|l| l.trim_start().starts_with("```"))
+    {
+        lines.remove(0);
+    }
+    if lines.last().is_some_and(|l| l.trim() == "```") {
+        lines.pop();
+    }
+
+    let has_header = lines.iter().any(|l| l.starts_with("+++ "));
+    let has_hunk = lines.iter().any(|l| l.starts_with("@@"));
+    if !has_header || !has_hunk {
+        return None;
+    }
+
+    let mut normalized = lines.join("\n");
+    normalized.push('\n');
+    Some(normalized)
+}
+
+/// Print a patch with diff coloring.
+fn print_patch_preview(patch: &str, colored: bool) {
+    for line in patch.lines() {
+        println!("    {}", color_diff_line(line, colored));
+    }
+}
+
+/// Highest severity among `issues`.
+fn highest_severity(issues: &[ReviewIssue]) -> Option<IssueSeverity> {
+    issues
+        .iter()
+        .map(|issue| issue.severity)
+        .min_by_key(IssueSeverity::level)
+}
+
+/// Check `issues` against the `--fail-on` threshold.
+///
+/// Returns [`GcopError::ReviewGateFailed`] when at least one issue is at or
+/// above `threshold`.
+fn check_gate(issues: &[ReviewIssue], threshold: IssueSeverity) -> Option<GcopError> {
+    let count = issues
+        .iter()
+        .filter(|issue| issue.severity.level() <= threshold.level())
+        .count();
+    if count == 0 {
+        return None;
+    }
+    Some(GcopError::ReviewGateFailed {
+        highest: highest_severity(issues)?,
+        threshold,
+        count,
+    })
+}
+
 /// Output review result in text format
-fn print_text(result: &ReviewResult, description: &str, config: &AppConfig) {
+/// `summary_shown` skips the summary when it was already streamed to the terminal.
+fn print_text(result: &ReviewResult, description: &str, config: &AppConfig, summary_shown: bool) {
     let colored = config.ui.colored;
 
     println!(
@@ -173,76 +841,407 @@ fn print_text(result: &ReviewResult, description: &str, config: &AppConfig) {
     println!();
 
     // Output summary
-    println!("{}", rust_i18n::t!("review.summary_title"));
-    println!("{}", result.summary);
-    println!();
+    if !summary_shown {
+        println!("{}", rust_i18n::t!("review.summary_title"));
+        println!("{}", result.summary);
+        println!();
+    }
 
     // Output problem
     if !result.issues.is_empty() {
         println!("{}", rust_i18n::t!("review.issues_found"));
         println!();
 
+        // Filter severity based on configuration
+        let min_severity = IssueSeverity::from_config_str(&config.review.min_severity);
         for (i, issue) in result.issues.iter().enumerate() {
-            // Filter severity based on configuration
-            let min_severity = IssueSeverity::from_config_str(&config.review.min_severity);
-
             // Skip issues below minimum severity
             if issue.severity.level() > min_severity.level() {
                 continue;
             }
-
-            // Output problem
-            print!("  {}. ", i + 1);
-
-            if colored {
-                print!("{}", issue.severity.colored_label());
-            } else {
-                print!("{}", issue.severity.label(false));
-            }
-
-            println!(" {}", issue.description);
-
-            // Output location information
-            if let Some(file) = &issue.file {
-                if let Some(line) = issue.line {
-                    println!(
-                        "     {}",
-                        rust_i18n::t!("review.location.with_line", file = file, line = line)
-                    );
-                } else {
-                    println!(
-                        "     {}",
-                        rust_i18n::t!("review.location.file_only", file = file)
-                    );
-                }
-            }
-            println!();
+            print_issue(i + 1, issue, colored);
         }
     } else {
         println!("{}", rust_i18n::t!("review.no_issues"));
         println!();
     }
 
-    // Output suggestions
-    if !result.suggestions.is_empty() {
+    print_suggestions(&result.suggestions);
+}
+
+/// Output one numbered issue of the text format.
+pub(crate) fn print_issue(number: usize, issue: &ReviewIssue, colored: bool) {
+    println!(
+        "  {}. {} {}",
+        number,
+        severity_badge(issue.severity, colored),
+        issue.description
+    );
+
+    // Output location information
+    if let Some(file) = &issue.file {
+        if let Some(line) = issue.line {
+            println!(
+                "     {}",
+                rust_i18n::t!("review.location.with_line", file = file, line = line)
+            );
+        } else {
+            println!(
+                "     {}",
+                rust_i18n::t!("review.location.file_only", file = file)
+            );
+        }
+    }
+    if let Some(category) = &issue.category {
+        println!(
+            "     {}",
+            rust_i18n::t!("review.category", category = category)
+        );
+    }
+    if issue.suggested_patch.is_some() {
+        println!("     {}", rust_i18n::t!("review.patch_available"));
+    }
+    println!();
+}
+
+/// Output the suggestions section of the text formats.
+fn print_suggestions(suggestions: &[String]) {
+    if !suggestions.is_empty() {
         println!("{}", rust_i18n::t!("review.suggestions_title"));
         println!();
-        for suggestion in &result.suggestions {
+        for suggestion in suggestions {
             println!("  • {}", suggestion);
         }
         println!();
     }
 }
 
+/// Severity label, colored or bracketed.
+fn severity_badge(severity: IssueSeverity, colored: bool) -> String {
+    if colored {
+        severity.colored_label()
+    } else {
+        severity.label(false)
+    }
+}
+
+/// Output review result as the reviewed diff with issue markers under the
+/// lines they refer to (`--format annotated`).
+fn print_annotated(result: &ReviewResult, description: &str, diff: &str, config: &AppConfig) {
+    let colored = config.ui.colored;
+
+    println!(
+        "{}",
+        ui::info(
+            &rust_i18n::t!("review.title", description = description),
+            colored
+        )
+    );
+    println!();
+    println!("{}", rust_i18n::t!("review.summary_title"));
+    println!("{}", result.summary);
+    println!();
+
+    let min_severity = IssueSeverity::from_config_str(&config.review.min_severity);
+    let issues: Vec<&ReviewIssue> = result
+        .issues
+        .iter()
+        .filter(|issue| issue.severity.level() <= min_severity.level())
+        .collect();
+    let (annotated, unplaced) = annotate_diff(diff, &issues, colored);
+    print!("{}", annotated);
+    println!();
+
+    if issues.is_empty() {
+        println!("{}", rust_i18n::t!("review.no_issues"));
+        println!();
+    } else if !unplaced.is_empty() {
+        println!("{}", rust_i18n::t!("review.annotated.unplaced"));
+        println!();
+        for (number, issue) in unplaced.iter().enumerate() {
+            print_issue(number + 1, issue, colored);
+        }
+    }
+
+    print_suggestions(&result.suggestions);
+}
+
+/// Renders `diff` with a marker after each line an issue points at (new-file
+/// line numbers); file-level issues and lines outside the hunks are listed at
+/// the end of their file. Returns the rendered diff and the issues whose file
+/// is not part of the diff.
+fn annotate_diff<'a>(
+    diff: &str,
+    issues: &[&'a ReviewIssue],
+    colored: bool,
+) -> (String, Vec<&'a ReviewIssue>) {
+    let mut placed = vec![false; issues.len()];
+    let mut output = String::new();
+    let mut file: Option<String> = None;
+    let mut new_line: Option<usize> = None;
+
+    // Appends the markers of the unplaced issues of `file` matching `filter`
+    let mut mark = |output: &mut String, file: &str, filter: &dyn Fn(&ReviewIssue) -> bool| {
+        for (issue, placed) in issues.iter().zip(placed.iter_mut()) {
+            if !*placed && issue_in_file(issue, file) && filter(issue) {
+                *placed = true;
+                output.push_str(&format!(
+                    "  ▲ {} {}\n",
+                    severity_badge(issue.severity, colored),
+                    issue.description
+                ));
+            }
+        }
+    };
+
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            if let Some(previous) = file.as_deref() {
+                mark(&mut output, previous, &|_| true);
+            }
+            file = line.rsplit_once(" b/").map(|(_, path)| path.to_string());
+            new_line = None;
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            if path != "/dev/null" {
+                file = Some(path.strip_prefix("b/").unwrap_or(path).to_string());
+            }
+            // Content without hunks (`review file`) starts at line 1
+            new_line = Some(1);
+        } else if line.starts_with("@@") {
+            new_line = hunk_new_start(line);
+        }
+
+        output.push_str(&color_diff_line(line, colored));
+        output.push('\n');
+
+        let is_new_line = !["+++", "@@", "-", "\\", "diff --git "]
+            .iter()
+            .any(|prefix| line.starts_with(prefix));
+        if is_new_line && let (Some(current), Some(number)) = (file.as_deref(), new_line) {
+            mark(&mut output, current, &|issue| issue.line == Some(number));
+            new_line = Some(number + 1);
+        }
+    }
+    if let Some(last) = file.as_deref() {
+        mark(&mut output, last, &|_| true);
+    }
+
+    let unplaced = issues
+        .iter()
+        .zip(&placed)
+        .filter(|(_, placed)| !**placed)
+        .map(|(issue, _)| *issue)
+        .collect();
+    (output, unplaced)
+}
+
+/// Start line in the new file of a `@@ -a,b +c,d @@` hunk header.
+fn hunk_new_start(header: &str) -> Option<usize> {
+    let new = header
+        .split_whitespace()
+        .find(|part| part.starts_with('+'))?;
+    new[1..].split(',').next()?.parse().ok()
+}
+
+/// Whether `issue` refers to `file` (tolerating `./`, `a/` and `b/` prefixes).
+fn issue_in_file(issue: &ReviewIssue, file: &str) -> bool {
+    issue.file.as_deref().is_some_and(|path| {
+        let path = path.trim_start_matches("./");
+        path == file || path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")) == Some(file)
+    })
+}
+
+/// A diff line with diff coloring.
+fn color_diff_line(line: &str, colored: bool) -> String {
+    use colored::Colorize;
+
+    if !colored {
+        line.to_string()
+    } else if line.starts_with("+++") || line.starts_with("---") || line.starts_with("diff ") {
+        line.bold().to_string()
+    } else if line.starts_with('+') {
+        line.green().to_string()
+    } else if line.starts_with('-') {
+        line.red().to_string()
+    } else if line.starts_with("@@") {
+        line.cyan().to_string()
+    } else {
+        line.to_string()
+    }
+}
+
 /// Output review result in JSON format
-fn print_json(result: &ReviewResult) -> Result<()> {
+fn print_json(
+    result: &ReviewResult,
+    gate: Option<ReviewGate>,
+    llm_metrics: LlmMetrics,
+    format: super::format::OutputFormat,
+) -> Result<()> {
     let output = JsonOutput {
         success: true,
-        data: Some(result.clone()),
+        data: Some(ReviewJson { result, gate }),
         error: None,
+        meta: Some(llm_metrics),
     };
-    println!("{}", serde_json::to_string_pretty(&output)?);
-    Ok(())
+    json::print_output(&output, format)
+}
+
+/// Output review result as GitHub Actions workflow commands
+fn print_github(result: &ReviewResult) {
+    print!("{}", render_github(result));
+}
+
+/// Renders issues as `::error` / `::warning` / `::notice` workflow commands,
+/// which GitHub shows inline on the pull request's changed files; the
+/// summary and suggestions become one unlocated notice.
+fn render_github(result: &ReviewResult) -> String {
+    let mut output = String::new();
+    for issue in &result.issues {
+        let level = match issue.severity {
+            IssueSeverity::Critical => "error",
+            IssueSeverity::Warning => "warning",
+            IssueSeverity::Info => "notice",
+        };
+        let mut properties = Vec::new();
+        if let Some(file) = &issue.file {
+            let file = file.strip_prefix("./").unwrap_or(file);
+            properties.push(format!("file={}", github_escape_property(file)));
+            if let Some(line) = issue.line {
+                properties.push(format!("line={}", line));
+            }
+        }
+        let title = match &issue.category {
+            Some(category) => rust_i18n::t!("review.github.title_category", category = category),
+            None => rust_i18n::t!("review.github.title"),
+        };
+        properties.push(format!("title={}", github_escape_property(&title)));
+        output.push_str(&format!(
+            "::{} {}::{}\n",
+            level,
+            properties.join(","),
+            github_escape_data(&issue.description)
+        ));
+    }
+
+    let mut summary = result.summary.trim().to_string();
+    for suggestion in &result.suggestions {
+        summary.push_str(&format!("\n- {}", suggestion));
+    }
+    if !summary.trim().is_empty() {
+        output.push_str(&format!(
+            "::notice title={}::{}\n",
+            github_escape_property(&rust_i18n::t!("review.github.summary_title")),
+            github_escape_data(summary.trim())
+        ));
+    }
+    output
+}
+
+/// Escapes the message of a workflow command.
+fn github_escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a `key=value` property of a workflow command.
+fn github_escape_property(value: &str) -> String {
+    github_escape_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Output review result as a JUnit XML report
+fn print_junit(result: &ReviewResult, config: &AppConfig) {
+    print!("{}", render_junit(result, config));
+}
+
+/// Renders issues at or above `review.min_severity` as a JUnit report: one
+/// test suite per file and one failed test case per issue. A review without
+/// such issues yields a single passing test case.
+fn render_junit(result: &ReviewResult, config: &AppConfig) -> String {
+    let min_severity = IssueSeverity::from_config_str(&config.review.min_severity);
+    let general = rust_i18n::t!("review.junit.general").to_string();
+    let mut suites: Vec<(&str, Vec<&ReviewIssue>)> = Vec::new();
+    for issue in result
+        .issues
+        .iter()
+        .filter(|issue| issue.severity.level() <= min_severity.level())
+    {
+        let file = issue.file.as_deref().map_or(general.as_str(), |file| {
+            file.strip_prefix("./").unwrap_or(file)
+        });
+        match suites.iter_mut().find(|(name, _)| *name == file) {
+            Some((_, issues)) => issues.push(issue),
+            None => suites.push((file, vec![issue])),
+        }
+    }
+
+    let total: usize = suites.iter().map(|(_, issues)| issues.len()).sum();
+    let name = xml_escape(&rust_i18n::t!("review.junit.name"));
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(&format!(
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+        name,
+        total.max(1),
+        total
+    ));
+    if suites.is_empty() {
+        output.push_str(&format!(
+            "  <testsuite name=\"{0}\" tests=\"1\" failures=\"0\">\n    <testcase name=\"{1}\" classname=\"{0}\"/>\n  </testsuite>\n",
+            name,
+            xml_escape(&rust_i18n::t!("review.junit.passed"))
+        ));
+    }
+    for (file, issues) in &suites {
+        let file = xml_escape(file);
+        output.push_str(&format!(
+            "  <testsuite name=\"{0}\" tests=\"{1}\" failures=\"{1}\">\n",
+            file,
+            issues.len()
+        ));
+        for issue in issues {
+            let title = issue.description.lines().next().unwrap_or_default();
+            let title = match issue.line {
+                Some(line) => format!("{}: {}", line, title),
+                None => title.to_string(),
+            };
+            let mut body = issue.description.clone();
+            if let Some(patch) = issue.suggested_patch.as_deref().and_then(normalize_patch) {
+                body.push_str(&format!(
+                    "\n\n{}\n{}",
+                    rust_i18n::t!("review.junit.suggested_patch"),
+                    patch
+                ));
+            }
+            output.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\">\n      <failure message=\"{}\" type=\"{}\">{}</failure>\n    </testcase>\n",
+                xml_escape(&title),
+                file,
+                xml_escape(&issue.description),
+                issue.severity.as_str(),
+                xml_escape(&body)
+            ));
+        }
+        output.push_str("  </testsuite>\n");
+    }
+    output.push_str("</testsuites>\n");
+    output
+}
+
+/// Escapes text for XML content and attribute values, dropping control
+/// characters XML 1.0 cannot represent.
+fn xml_escape(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
 }
 
 /// Output review result in Markdown format
@@ -296,6 +1295,23 @@ fn print_markdown(result: &ReviewResult, description: &str, _colored: bool) {
                 }
                 println!();
             }
+
+            if let Some(category) = &issue.category {
+                println!(
+                    "{}",
+                    rust_i18n::t!("review.md.category", category = category)
+                );
+                println!();
+            }
+
+            if let Some(patch) = issue.suggested_patch.as_deref().and_then(normalize_patch) {
+                println!("{}", rust_i18n::t!("review.md.suggested_patch"));
+                println!();
+                println!("```diff");
+                print!("{}", patch);
+                println!("```");
+                println!();
+            }
         }
     } else {
         println!("{}", rust_i18n::t!("review.md.no_issues_title"));
@@ -314,3 +1330,228 @@ fn print_markdown(result: &ReviewResult, description: &str, _colored: bool) {
         println!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::ReviewPreset;
+
+    fn issue(severity: IssueSeverity) -> ReviewIssue {
+        ReviewIssue {
+            severity,
+            description: "issue".to_string(),
+            file: None,
+            line: None,
+            suggested_patch: None,
+            category: None,
+        }
+    }
+
+    #[test]
+    fn test_preset_categorize_forces_known_categories() {
+        let mut issues: Vec<ReviewIssue> =
+            [Some("Unsafe_Code"), Some("style"), None, Some("authz")]
+                .into_iter()
+                .map(|category| ReviewIssue {
+                    category: category.map(str::to_string),
+                    ..issue(IssueSeverity::Warning)
+                })
+                .collect();
+        ReviewPreset::Security.categorize(&mut issues);
+        let categories: Vec<_> = issues.iter().map(|i| i.category.as_deref()).collect();
+        // 未知或缺失的类别归为 other
+        assert_eq!(
+            categories,
+            vec![
+                Some("unsafe-code"),
+                Some("other"),
+                Some("other"),
+                Some("authz")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_gate_passes_below_threshold() {
+        let issues = vec![issue(IssueSeverity::Info), issue(IssueSeverity::Warning)];
+        assert!(check_gate(&issues, IssueSeverity::Critical).is_none());
+        assert!(check_gate(&[], IssueSeverity::Info).is_none());
+    }
+
+    #[test]
+    fn test_check_gate_exit_code_follows_highest_severity() {
+        let issues = vec![
+            issue(IssueSeverity::Info),
+            issue(IssueSeverity::Critical),
+            issue(IssueSeverity::Warning),
+        ];
+        let err = check_gate(&issues, IssueSeverity::Warning).unwrap();
+        assert!(matches!(
+            err,
+            GcopError::ReviewGateFailed {
+                highest: IssueSeverity::Critical,
+                count: 2,
+                ..
+            }
+        ));
+        assert_eq!(err.exit_code(), 10);
+
+        let err = check_gate(&[issue(IssueSeverity::Info)], IssueSeverity::Info).unwrap();
+        assert_eq!(err.exit_code(), 12);
+    }
+
+    #[test]
+    fn test_concat_and_restore_chunked_reviews() {
+        let located = |file: &str, line: usize, patch: Option<&str>| ReviewIssue {
+            file: Some(file.to_string()),
+            line: Some(line),
+            suggested_patch: patch.map(str::to_string),
+            ..issue(IssueSeverity::Warning)
+        };
+        let part = |summary: &str, issues: Vec<ReviewIssue>| ReviewResult {
+            summary: summary.to_string(),
+            issues,
+            suggestions: vec!["add tests".to_string()],
+        };
+        let parts = vec![
+            part("first", vec![located("a.rs", 1, Some("patch-a"))]),
+            part(
+                "second",
+                vec![located("a.rs", 1, None), located("b.rs", 2, None)],
+            ),
+        ];
+
+        // 合并结果丢失的补丁按文件和行号找回
+        let mut merged = part(
+            "all",
+            vec![located("a.rs", 1, None), located("b.rs", 2, None)],
+        );
+        restore_patches(&mut merged, &parts);
+        assert_eq!(merged.issues[0].suggested_patch.as_deref(), Some("patch-a"));
+        assert_eq!(merged.issues[1].suggested_patch, None);
+
+        let concat = concat_reviews(parts);
+        assert_eq!(concat.summary, "first\n\nsecond");
+        assert_eq!(concat.issues.len(), 2);
+        assert_eq!(concat.suggestions, vec!["add tests".to_string()]);
+    }
+
+    #[test]
+    fn test_annotate_diff_places_markers_under_lines() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -10,3 +10,3 @@ fn main() {\n ctx\n-old\n+new\n tail";
+        let at = |file: &str, line: Option<usize>, description: &str| ReviewIssue {
+            description: description.to_string(),
+            file: Some(file.to_string()),
+            line,
+            ..issue(IssueSeverity::Warning)
+        };
+        let on_new = at("./src/a.rs", Some(11), "on new");
+        let whole_file = at("src/a.rs", None, "whole file");
+        let elsewhere = at("src/b.rs", Some(1), "elsewhere");
+        let issues = vec![&on_new, &whole_file, &elsewhere];
+
+        let (output, unplaced) = annotate_diff(diff, &issues, false);
+        let lines: Vec<&str> = output.lines().collect();
+        // 新文件第 11 行是 "+new"，标记紧随其后；文件级问题在文件末尾
+        let new_at = lines.iter().position(|l| *l == "+new").unwrap();
+        assert_eq!(lines[new_at + 1], "  ▲ [WARNING] on new");
+        assert_eq!(lines.last(), Some(&"  ▲ [WARNING] whole file"));
+        assert_eq!(unplaced.len(), 1);
+        assert_eq!(unplaced[0].description, "elsewhere");
+
+        assert_eq!(hunk_new_start("@@ -1,2 +30,4 @@"), Some(30));
+        assert_eq!(hunk_new_start("@@ -1 +7 @@"), Some(7));
+    }
+
+    #[test]
+    fn test_render_github_workflow_commands() {
+        let result = ReviewResult {
+            summary: "Looks good".to_string(),
+            issues: vec![
+                ReviewIssue {
+                    description: "100% wrong\nreally".to_string(),
+                    file: Some("./src/a,b.rs".to_string()),
+                    line: Some(12),
+                    ..issue(IssueSeverity::Critical)
+                },
+                issue(IssueSeverity::Info),
+            ],
+            suggestions: vec!["add tests".to_string()],
+        };
+        let output = render_github(&result);
+        let lines: Vec<&str> = output.lines().collect();
+        // 数据与属性按 GitHub 规则转义；无位置的问题不带 file/line
+        assert_eq!(
+            lines[0],
+            format!(
+                "::error file=src/a%2Cb.rs,line=12,title={}::100%25 wrong%0Areally",
+                rust_i18n::t!("review.github.title")
+            )
+        );
+        assert!(lines[1].starts_with("::notice title="));
+        assert!(lines[2].ends_with("::Looks good%0A- add tests"));
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_render_junit_groups_issues_by_file() {
+        let mut config = AppConfig::default();
+        config.review.min_severity = "warning".to_string();
+        let at = |file: &str, severity: IssueSeverity, description: &str| ReviewIssue {
+            description: description.to_string(),
+            file: Some(file.to_string()),
+            line: Some(3),
+            ..issue(severity)
+        };
+        let result = ReviewResult {
+            summary: String::new(),
+            issues: vec![
+                at(
+                    "./src/a.rs",
+                    IssueSeverity::Critical,
+                    "uses <unsafe> & more",
+                ),
+                at("src/b.rs", IssueSeverity::Warning, "b"),
+                at("src/a.rs", IssueSeverity::Warning, "a again"),
+                // 低于 min_severity 的问题被过滤
+                at("src/c.rs", IssueSeverity::Info, "c"),
+            ],
+            suggestions: Vec::new(),
+        };
+        let xml = render_junit(&result, &config);
+        assert!(xml.contains("tests=\"3\" failures=\"3\""));
+        assert!(xml.contains("<testsuite name=\"src/a.rs\" tests=\"2\" failures=\"2\">"));
+        assert!(xml.contains("<testsuite name=\"src/b.rs\" tests=\"1\" failures=\"1\">"));
+        assert!(!xml.contains("src/c.rs"));
+        assert!(
+            xml.contains("<failure message=\"uses &lt;unsafe&gt; &amp; more\" type=\"critical\">")
+        );
+
+        // 没有问题时输出一个通过的用例
+        let clean = render_junit(
+            &ReviewResult {
+                summary: String::new(),
+                issues: Vec::new(),
+                suggestions: Vec::new(),
+            },
+            &config,
+        );
+        assert!(clean.contains("tests=\"1\" failures=\"0\""));
+        assert!(!clean.contains("<failure"));
+    }
+
+    #[test]
+    fn test_normalize_patch_strips_fences() {
+        let patch = "```diff\n--- a/x.rs\n+++ b/x.rs\n@@ -1 +1 @@\n-a\n+b\n```";
+        assert_eq!(
+            normalize_patch(patch).unwrap(),
+            "--- a/x.rs\n+++ b/x.rs\n@@ -1 +1 @@\n-a\n+b\n"
+        );
+    }
+
+    #[test]
+    fn test_normalize_patch_rejects_non_diff() {
+        assert!(normalize_patch("Replace unwrap with ?").is_none());
+        assert!(normalize_patch("").is_none());
+    }
+}
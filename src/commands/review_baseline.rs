@@ -0,0 +1,243 @@
+//! Review baseline (known-issue suppression).
+//!
+//! `.gcop/review-baseline.json` records the fingerprints of issues that are
+//! already known, so subsequent `review` runs only report new findings.
+//! A fingerprint is a hash of the issue's file path and normalized description;
+//! line numbers and severity are ignored so unrelated edits do not resurface
+//! old findings.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GcopError, Result};
+use crate::llm::{IssueSeverity, ReviewIssue, ReviewResult};
+
+/// Baseline file location relative to the repository root.
+pub(crate) const BASELINE_PATH: &str = ".gcop/review-baseline.json";
+
+/// Current baseline file format version.
+const BASELINE_VERSION: u32 = 1;
+
+/// Known review issues persisted in `.gcop/review-baseline.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ReviewBaseline {
+    /// File format version.
+    pub version: u32,
+    /// Recorded issues, sorted by file then description.
+    pub issues: Vec<BaselineEntry>,
+}
+
+/// A single recorded issue.
+///
+/// Only `fingerprint` is used for matching; the other fields keep the file
+/// readable in code review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct BaselineEntry {
+    /// Stable hash of file path and normalized description.
+    pub fingerprint: String,
+    /// Severity at the time the issue was recorded.
+    pub severity: IssueSeverity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Related file path, if any.
+    pub file: Option<String>,
+    /// Issue description as reported.
+    pub description: String,
+}
+
+impl ReviewBaseline {
+    /// Build a baseline from the given findings (duplicates are collapsed).
+    pub fn from_issues(issues: &[ReviewIssue]) -> Self {
+        let mut seen = HashSet::new();
+        let mut entries: Vec<BaselineEntry> = issues
+            .iter()
+            .filter_map(|issue| {
+                let fingerprint = fingerprint(issue);
+                seen.insert(fingerprint.clone()).then(|| BaselineEntry {
+                    fingerprint,
+                    severity: issue.severity,
+                    file: issue.file.clone(),
+                    description: issue.description.clone(),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (a.file.as_deref(), a.description.as_str())
+                .cmp(&(b.file.as_deref(), b.description.as_str()))
+        });
+
+        Self {
+            version: BASELINE_VERSION,
+            issues: entries,
+        }
+    }
+
+    /// Load a baseline file. Returns `Ok(None)` when the file does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        let baseline = serde_json::from_str(&content).map_err(|e| {
+            GcopError::Config(
+                rust_i18n::t!("review.baseline.invalid", path = path.display(), error = e)
+                    .to_string(),
+            )
+        })?;
+        Ok(Some(baseline))
+    }
+
+    /// Write the baseline as pretty-printed JSON, creating `.gcop/` if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut content = serde_json::to_string_pretty(self)?;
+        content.push('\n');
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Remove issues already recorded in the baseline.
+    ///
+    /// Returns the number of suppressed issues.
+    pub fn filter(&self, result: &mut ReviewResult) -> usize {
+        let known: HashSet<&str> = self
+            .issues
+            .iter()
+            .map(|entry| entry.fingerprint.as_str())
+            .collect();
+        let before = result.issues.len();
+        result
+            .issues
+            .retain(|issue| !known.contains(fingerprint(issue).as_str()));
+        before - result.issues.len()
+    }
+}
+
+/// Baseline file path for the repository rooted at `workdir`.
+pub(crate) fn baseline_path(workdir: &Path) -> PathBuf {
+    workdir.join(BASELINE_PATH)
+}
+
+/// Compute the stable fingerprint of an issue (16 hex chars).
+///
+/// The description is trimmed, whitespace-collapsed and lowercased so that
+/// cosmetic differences between runs do not change the fingerprint.
+pub(crate) fn fingerprint(issue: &ReviewIssue) -> String {
+    let file = issue
+        .file
+        .as_deref()
+        .map(|f| f.trim().trim_start_matches("./"))
+        .unwrap_or_default();
+    let description = issue
+        .description
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    format!(
+        "{:016x}",
+        fnv1a64(format!("{}\n{}", file, description).as_bytes())
+    )
+}
+
+/// 64-bit FNV-1a hash (stable across Rust versions, unlike `DefaultHasher`).
+pub(crate) fn fnv1a64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(file: Option<&str>, description: &str, line: Option<usize>) -> ReviewIssue {
+        ReviewIssue {
+            severity: IssueSeverity::Warning,
+            description: description.to_string(),
+            file: file.map(str::to_string),
+            line,
+            suggested_patch: None,
+            category: None,
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_line_and_whitespace() {
+        let a = issue(
+            Some("src/lib.rs"),
+            "Unchecked  unwrap on user input",
+            Some(10),
+        );
+        let b = issue(
+            Some("./src/lib.rs"),
+            "unchecked unwrap on user input\n",
+            Some(42),
+        );
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        assert_eq!(fingerprint(&a).len(), 16);
+
+        let other_file = issue(Some("src/main.rs"), "Unchecked unwrap on user input", None);
+        assert_ne!(fingerprint(&a), fingerprint(&other_file));
+    }
+
+    #[test]
+    fn test_fnv1a64_known_vectors() {
+        assert_eq!(fnv1a64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a64(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_filter_suppresses_known_issues() {
+        let known = issue(Some("src/lib.rs"), "Legacy debt", Some(1));
+        let baseline = ReviewBaseline::from_issues(&[known.clone(), known.clone()]);
+        assert_eq!(baseline.issues.len(), 1);
+
+        let mut result = ReviewResult {
+            summary: String::new(),
+            issues: vec![
+                issue(Some("src/lib.rs"), "Legacy debt", Some(7)),
+                issue(Some("src/lib.rs"), "New problem", Some(8)),
+            ],
+            suggestions: vec![],
+        };
+        assert_eq!(baseline.filter(&mut result), 1);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].description, "New problem");
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = baseline_path(dir.path());
+        assert!(ReviewBaseline::load(&path).unwrap().is_none());
+
+        let baseline = ReviewBaseline::from_issues(&[
+            issue(Some("b.rs"), "Second", None),
+            issue(None, "General", None),
+            issue(Some("a.rs"), "First", Some(3)),
+        ]);
+        baseline.save(&path).unwrap();
+
+        let loaded = ReviewBaseline::load(&path).unwrap().unwrap();
+        assert_eq!(loaded.version, BASELINE_VERSION);
+        let files: Vec<_> = loaded.issues.iter().map(|e| e.file.as_deref()).collect();
+        assert_eq!(files, vec![None, Some("a.rs"), Some("b.rs")]);
+    }
+
+    #[test]
+    fn test_load_invalid_file_is_config_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("review-baseline.json");
+        std::fs::write(&path, "not json").unwrap();
+        assert!(matches!(
+            ReviewBaseline::load(&path),
+            Err(GcopError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+//! Cached results of reviews of immutable objects.
+//!
+//! `review commit` and `review range` review diffs fully determined by commit
+//! hashes, so their results are stored in `.git/gcop/review-cache/` keyed by
+//! the resolved hashes, the provider and model, and a hash of the prompt and
+//! input settings. Re-running the same review is then served from disk.
+//! `--no-cache` bypasses the cache; failures only cost the cache.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::review_snapshot::STATE_DIR;
+use crate::llm::ReviewResult;
+use crate::llm::audit::sha256_hex;
+
+/// Cache directory inside [`STATE_DIR`].
+const CACHE_DIR: &str = "review-cache";
+
+/// Bumped when the cached payload or key inputs change meaning.
+const CACHE_VERSION: u32 = 1;
+
+/// A cached review.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Entry format version.
+    version: u32,
+    /// Reviewed object (`<sha>` or `<sha>..<sha>` / `<sha>...<sha>`).
+    target: String,
+    /// `provider:model` that produced the result.
+    model: String,
+    /// The cached result.
+    result: ReviewResult,
+}
+
+/// What a cached review depends on.
+#[derive(Debug, Clone)]
+pub(crate) struct CacheKey {
+    /// Reviewed object with hashes resolved.
+    pub target: String,
+    /// `provider:model` of the review.
+    pub model: String,
+    /// Hash of the prompt and of the settings shaping the input.
+    pub prompt: String,
+}
+
+impl CacheKey {
+    /// File name of the entry.
+    fn file_name(&self) -> String {
+        let key = format!(
+            "{}\n{}\n{}\n{}",
+            CACHE_VERSION, self.target, self.model, self.prompt
+        );
+        format!("{}.json", sha256_hex(key.as_bytes()))
+    }
+}
+
+/// Cache directory of the repository whose git directory is `git_dir`.
+pub(crate) fn cache_dir(git_dir: &Path) -> PathBuf {
+    git_dir.join(STATE_DIR).join(CACHE_DIR)
+}
+
+/// Cached result for `key`, if any.
+pub(crate) fn load(dir: &Path, key: &CacheKey) -> Option<ReviewResult> {
+    let path = dir.join(key.file_name());
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str::<CacheEntry>(&content) {
+        Ok(entry) if entry.version == CACHE_VERSION => Some(entry.result),
+        Ok(_) => None,
+        Err(e) => {
+            tracing::debug!("Ignoring invalid {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Stores `result` under `key`; failures are logged.
+pub(crate) fn store(dir: &Path, key: &CacheKey, result: &ReviewResult) {
+    let path = dir.join(key.file_name());
+    let entry = CacheEntry {
+        version: CACHE_VERSION,
+        target: key.target.clone(),
+        model: key.model.clone(),
+        result: result.clone(),
+    };
+    let write = std::fs::create_dir_all(dir)
+        .and_then(|_| serde_json::to_string_pretty(&entry).map_err(std::io::Error::other))
+        .and_then(|content| std::fs::write(&path, content + "\n"));
+    if let Err(e) = write {
+        tracing::debug!("Failed to cache review in {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(model: &str) -> CacheKey {
+        CacheKey {
+            target: "abc...def".to_string(),
+            model: model.to_string(),
+            prompt: "p1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cache_round_trip_is_keyed_by_model() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = ReviewResult {
+            summary: "ok".to_string(),
+            issues: Vec::new(),
+            suggestions: vec!["add tests".to_string()],
+        };
+
+        assert!(load(dir.path(), &key("openai:gpt-4o")).is_none());
+        store(dir.path(), &key("openai:gpt-4o"), &result);
+        let cached = load(dir.path(), &key("openai:gpt-4o")).unwrap();
+        assert_eq!(cached.summary, "ok");
+        assert_eq!(cached.suggestions, result.suggestions);
+
+        // 换模型或提示词版本即失效
+        assert!(load(dir.path(), &key("claude:sonnet")).is_none());
+        let mut other_prompt = key("openai:gpt-4o");
+        other_prompt.prompt = "p2".to_string();
+        assert!(load(dir.path(), &other_prompt).is_none());
+    }
+}
@@ -0,0 +1,309 @@
+//! Input files for `review file`.
+//!
+//! Directories are walked the way git sees them: `.gitignore`,
+//! `.git/info/exclude` and `.gcopignore` files (same syntax, for files that
+//! are tracked but should not be reviewed) are honoured and hidden entries are
+//! skipped. With `--with-context`, related files are added for reference:
+//! the module entry file next to each target, tests that mention its name,
+//! then sibling files, until the token budget is spent.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use ignore::WalkBuilder;
+
+use crate::git::GitOperations;
+
+/// Per-directory ignore file for review walks.
+pub(crate) const IGNORE_FILE: &str = ".gcopignore";
+
+/// Rough size of a token, used to turn `review.context_tokens` into bytes.
+const BYTES_PER_TOKEN: usize = 4;
+
+/// Files that define or re-export the module of their directory.
+const ENTRY_FILES: &[&str] = &[
+    "mod.rs",
+    "lib.rs",
+    "main.rs",
+    "__init__.py",
+    "index.ts",
+    "index.tsx",
+    "index.js",
+    "index.jsx",
+];
+
+/// A file included in the review input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReviewFile {
+    /// Path as shown to the model.
+    pub path: String,
+    /// File content.
+    pub content: String,
+}
+
+/// Files below `root`, sorted, without ignored or hidden entries.
+pub(crate) fn walk(root: &Path) -> Vec<PathBuf> {
+    walk_depth(root, None)
+}
+
+fn walk_depth(root: &Path, max_depth: Option<usize>) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkBuilder::new(root)
+        .max_depth(max_depth)
+        .require_git(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .build()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                tracing::debug!("Skipping review walk entry: {}", e);
+                None
+            }
+        })
+        .filter(|entry| entry.file_type().is_some_and(|kind| kind.is_file()))
+        .map(|entry| entry.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// Reads `paths` in order while they fit in `budget` bytes.
+///
+/// Files that cannot be read as text (binary, too large) are skipped.
+/// Returns the files read and how many were left out.
+pub(crate) fn read_files(
+    git: &dyn GitOperations,
+    paths: &[PathBuf],
+    budget: usize,
+) -> (Vec<ReviewFile>, usize) {
+    let mut files = Vec::new();
+    let mut used = 0;
+    let mut omitted = 0;
+    for path in paths {
+        let name = path.to_string_lossy();
+        match git.get_file_content(&name) {
+            Ok(content) if used + content.len() <= budget => {
+                used += content.len();
+                files.push(ReviewFile {
+                    path: name.into_owned(),
+                    content,
+                });
+            }
+            Ok(_) => omitted += 1,
+            Err(e) => tracing::debug!("Skipping {} in review: {}", name, e),
+        }
+    }
+    (files, omitted)
+}
+
+/// Renders files as the `--- path` / `+++ path` blocks used for review input.
+pub(crate) fn render(files: &[ReviewFile]) -> String {
+    files
+        .iter()
+        .map(|file| format!("--- {}\n+++ {}\n{}", file.path, file.path, file.content))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Related files for `targets`, best candidates first, within `tokens`.
+///
+/// Candidates are the module entry file of each target's directory, test
+/// files under `workdir` whose name contains a target's name, then siblings
+/// with the same extension (smallest first). A candidate that does not fit
+/// in the remaining budget is skipped, so smaller ones later can still fit.
+pub(crate) fn related_files(
+    git: &dyn GitOperations,
+    targets: &[PathBuf],
+    workdir: &Path,
+    tokens: usize,
+) -> Vec<ReviewFile> {
+    let mut seen: HashSet<PathBuf> = targets.iter().map(|path| canonical(path)).collect();
+    let candidates: Vec<PathBuf> = entry_files(targets)
+        .into_iter()
+        .chain(test_files(targets, workdir))
+        .chain(sibling_files(targets))
+        .filter(|path| seen.insert(canonical(path)))
+        .collect();
+
+    let (mut files, _) = read_files(git, &candidates, tokens.saturating_mul(BYTES_PER_TOKEN));
+    for file in &mut files {
+        if let Ok(relative) = canonical(Path::new(&file.path)).strip_prefix(canonical(workdir)) {
+            file.path = relative.to_string_lossy().into_owned();
+        }
+    }
+    files
+}
+
+fn entry_files(targets: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for target in targets {
+        let Some(dir) = target.parent() else {
+            continue;
+        };
+        files.extend(
+            ENTRY_FILES
+                .iter()
+                .map(|name| dir.join(name))
+                .filter(|path| path.is_file()),
+        );
+    }
+    files
+}
+
+fn test_files(targets: &[PathBuf], workdir: &Path) -> Vec<PathBuf> {
+    let names: Vec<String> = targets
+        .iter()
+        .filter_map(|path| module_name(path))
+        .collect();
+    if names.is_empty() {
+        return Vec::new();
+    }
+    walk(workdir)
+        .into_iter()
+        .filter(|path| {
+            let relative = path.strip_prefix(workdir).unwrap_or(path);
+            crate::llm::heuristic::is_test(&crate::git::to_git_path(relative))
+                && file_stem(path).is_some_and(|stem| names.iter().any(|name| stem.contains(name)))
+        })
+        .collect()
+}
+
+fn sibling_files(targets: &[PathBuf]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for target in targets {
+        let (Some(dir), Some(extension)) = (target.parent(), target.extension()) else {
+            continue;
+        };
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        let mut siblings: Vec<PathBuf> = walk_depth(dir, Some(1))
+            .into_iter()
+            .filter(|path| path.extension() == Some(extension))
+            .collect();
+        siblings.sort_by_key(|path| path.metadata().map(|m| m.len()).unwrap_or(u64::MAX));
+        files.extend(siblings);
+    }
+    files
+}
+
+/// Name tests refer to: the file stem, or the directory name for entry files.
+fn module_name(path: &Path) -> Option<String> {
+    let stem = file_stem(path)?;
+    let name = if ["mod", "lib", "main", "index", "__init__"].contains(&stem.as_str()) {
+        path.parent()?.file_name()?.to_string_lossy().to_lowercase()
+    } else {
+        stem
+    };
+    (name.len() >= 3).then_some(name)
+}
+
+fn file_stem(path: &Path) -> Option<String> {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::MockGitOperations;
+    use std::fs;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn fs_repo() -> MockGitOperations {
+        let mut git = MockGitOperations::new();
+        git.expect_get_file_content()
+            .returning(|path| Ok(fs::read_to_string(path)?));
+        git
+    }
+
+    fn relative(root: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+        paths
+            .iter()
+            .map(|p| {
+                p.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_walk_respects_ignore_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, ".gitignore", "target/\n");
+        write(root, "src/.gcopignore", "generated.rs\n");
+        write(root, "src/lib.rs", "");
+        write(root, "src/generated.rs", "");
+        write(root, "src/.hidden.rs", "");
+        write(root, "target/out.rs", "");
+
+        assert_eq!(relative(root, walk(root)), vec!["src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_read_files_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "a.rs", "12345");
+        write(root, "b.rs", "1234567890");
+        write(root, "c.rs", "123");
+
+        let (files, omitted) = read_files(&fs_repo(), &walk(root), 9);
+        let names: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(names.len(), 2);
+        assert!(names[0].ends_with("a.rs") && names[1].ends_with("c.rs"));
+        assert_eq!(omitted, 1);
+        assert!(render(&files).contains("+++ ") && render(&files).contains("\n12345\n--- "));
+    }
+
+    #[test]
+    fn test_related_files_order_and_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "src/auth/mod.rs", "pub mod login;");
+        write(root, "src/auth/login.rs", "fn login() {}");
+        write(root, "src/auth/token.rs", "fn token() {}");
+        write(root, "src/auth/big.rs", &"x".repeat(400));
+        write(root, "tests/login_test.rs", "#[test] fn t() {}");
+        write(root, "tests/other_test.rs", "#[test] fn o() {}");
+
+        let target = root.join("src/auth/login.rs");
+        let files = related_files(&fs_repo(), &[target], root, 30);
+        let paths: Vec<String> = files.iter().map(|f| f.path.replace('\\', "/")).collect();
+        // 入口文件 > 相关测试 > 同目录文件；超出预算的 big.rs 被跳过
+        assert_eq!(
+            paths,
+            vec![
+                "src/auth/mod.rs",
+                "tests/login_test.rs",
+                "src/auth/token.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_name() {
+        assert_eq!(
+            module_name(Path::new("src/auth/mod.rs")).as_deref(),
+            Some("auth")
+        );
+        assert_eq!(
+            module_name(Path::new("src/Login.ts")).as_deref(),
+            Some("login")
+        );
+        assert_eq!(module_name(Path::new("src/db.rs")), None);
+    }
+}
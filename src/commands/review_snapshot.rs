@@ -0,0 +1,110 @@
+//! Last-reviewed snapshots for `review changes --incremental`.
+//!
+//! After an incremental-mode review, the reviewed working tree is written as a
+//! git tree object and its hash is recorded per branch in
+//! `.git/gcop/reviewed.json`. The next incremental run only reviews what
+//! changed since that tree. Like commit sessions, persistence is best-effort:
+//! failures are logged and fall back to a full review.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Directory inside the `.git` directory holding gcop state.
+pub(crate) const STATE_DIR: &str = "gcop";
+
+/// Snapshot file name inside [`STATE_DIR`].
+const SNAPSHOT_FILE: &str = "reviewed.json";
+
+/// Key used for a detached `HEAD`.
+const DETACHED_KEY: &str = "HEAD";
+
+/// Current snapshot file format version.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Last-reviewed tree hash of each branch.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReviewSnapshots {
+    /// File format version.
+    pub version: u32,
+    /// Branch name (`HEAD` when detached) → tree hash.
+    #[serde(default)]
+    pub branches: BTreeMap<String, String>,
+}
+
+impl ReviewSnapshots {
+    /// Loads the snapshot file; a missing or invalid file yields no snapshots.
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match serde_json::from_str::<Self>(&content) {
+            Ok(snapshots) if snapshots.version == SNAPSHOT_VERSION => snapshots,
+            Ok(_) => Self::default(),
+            Err(e) => {
+                tracing::debug!("Ignoring invalid {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Tree last reviewed on `branch` (`None` = detached `HEAD`).
+    pub fn get(&self, branch: Option<&str>) -> Option<&str> {
+        self.branches
+            .get(branch.unwrap_or(DETACHED_KEY))
+            .map(String::as_str)
+    }
+
+    /// Records `tree` as last reviewed on `branch`.
+    pub fn set(&mut self, branch: Option<&str>, tree: String) {
+        self.version = SNAPSHOT_VERSION;
+        self.branches
+            .insert(branch.unwrap_or(DETACHED_KEY).to_string(), tree);
+    }
+
+    /// Writes the snapshot file, creating its directory; failures are logged.
+    pub fn save(&self, path: &Path) {
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| serde_json::to_string_pretty(self).map_err(std::io::Error::other))
+            .and_then(|content| std::fs::write(path, content + "\n"));
+        if let Err(e) = result {
+            tracing::debug!("Failed to save {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Snapshot file path for the repository whose git directory is `git_dir`.
+pub(crate) fn snapshot_path(git_dir: &Path) -> PathBuf {
+    git_dir.join(STATE_DIR).join(SNAPSHOT_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshots_round_trip_per_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_path(dir.path());
+
+        // 文件不存在时没有快照
+        let mut snapshots = ReviewSnapshots::load(&path);
+        assert_eq!(snapshots.get(Some("main")), None);
+
+        snapshots.set(Some("main"), "aaa".to_string());
+        snapshots.set(Some("feature/x"), "bbb".to_string());
+        snapshots.set(None, "ccc".to_string());
+        snapshots.save(&path);
+
+        let loaded = ReviewSnapshots::load(&path);
+        assert_eq!(loaded, snapshots);
+        assert_eq!(loaded.get(Some("feature/x")), Some("bbb"));
+        assert_eq!(loaded.get(None), Some("ccc"));
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(ReviewSnapshots::load(&path), ReviewSnapshots::default());
+    }
+}
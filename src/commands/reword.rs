@@ -0,0 +1,285 @@
+//! Improving the message of an existing commit (`gcop-rs reword`).
+//!
+//! Sends the commit's diff and current message to the provider, which
+//! rewrites the message per the configured convention. After confirmation,
+//! `HEAD` is amended; older commits are reworded with an automated
+//! `git rebase -i`, which rewrites every commit after them.
+
+use std::sync::Arc;
+
+use super::options::RewordOptions;
+use super::smart_truncate_diff;
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::git::commit::CommitSigning;
+use crate::git::{GitOperations, HistoryOptions, repository::GitRepository};
+use crate::llm::provider::base::response::process_commit_response;
+use crate::llm::{LLMProvider, provider::create_provider};
+use crate::ui::{Reporter, TerminalReporter};
+
+/// Trailers carried over from the current message (identity, not content).
+const KEPT_TRAILERS: &[&str] = &["Signed-off-by:", "Co-authored-by:"];
+
+/// Execute reword command
+///
+/// # Arguments
+/// * `options` - Reword command options
+/// * `config` - application configuration
+pub async fn run(options: &RewordOptions<'_>, config: &AppConfig) -> Result<()> {
+    let repo = GitRepository::open(Some(&config.file))?
+        .with_signing(CommitSigning::resolve(config.commit.sign, false));
+    let provider = create_provider(
+        config,
+        &config.commit.llm,
+        options.provider_override,
+        options.model_override,
+    )?;
+
+    let reporter = TerminalReporter::new(config.ui.colored);
+    run_with_deps(options, config, &repo, &provider, &reporter).await
+}
+
+/// Rewords the commit, reporting progress to `reporter`.
+async fn run_with_deps(
+    options: &RewordOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    reporter: &dyn Reporter,
+) -> Result<()> {
+    let colored = config.ui.colored;
+
+    if repo.is_empty()? {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("reword.no_commits").to_string(),
+        ));
+    }
+    let target = repo.get_commit_info(options.commit)?;
+    let short = short_hash(&target.hash);
+    if target.parent_count > 1 {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("reword.merge_commit", hash = short).to_string(),
+        ));
+    }
+    if !repo.is_ancestor(&target.hash, "HEAD")? {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("reword.not_ancestor", hash = short).to_string(),
+        ));
+    }
+    let rewritten = later_commits(repo, &target.hash)?;
+    let current = repo.get_commit_message(&target.hash)?;
+
+    let diff = repo.get_commit_diff(&target.hash)?;
+    let stats = repo.get_diff_stats(&diff)?;
+    let diff = super::privacy::guard(diff, config, false, colored)?;
+    let (diff, truncated) = smart_truncate_diff(&diff, config.llm.max_diff_size);
+    if truncated {
+        reporter.warning(&rust_i18n::t!("diff.truncated"));
+    }
+    reporter.step(
+        &rust_i18n::t!("reword.step1"),
+        &rust_i18n::t!("reword.target", hash = short, subject = target.message),
+    );
+
+    let branch_name = repo.get_current_branch()?;
+    let scope_info = super::commit::compute_scope_info_pub(repo, &stats.files_changed, config);
+    let history_examples = super::commit::compute_history_examples(repo, config);
+    let trailers = kept_trailers(&current);
+
+    reporter.step(
+        &rust_i18n::t!("reword.step2"),
+        &rust_i18n::t!("spinner.generating"),
+    );
+    let spinner = reporter.cancellable_spinner(&rust_i18n::t!("reword.generating"));
+    let generated = super::commit::generate_message_no_streaming(
+        provider,
+        &diff,
+        &stats,
+        config,
+        options.feedback,
+        options.verbose,
+        false,
+        &branch_name,
+        &config.commit.custom_prompt,
+        &scope_info,
+        &history_examples,
+        Some(current.trim()),
+        &trailers,
+        None,
+    )
+    .await;
+    if let Some(spinner) = &spinner {
+        spinner.finish_and_clear();
+    }
+    let message = process_commit_response(generated?.0);
+    if message.trim().is_empty() {
+        return Err(GcopError::Llm(
+            rust_i18n::t!("reword.empty_message").to_string(),
+        ));
+    }
+    if message.trim() == current.trim() {
+        reporter.success(&rust_i18n::t!("reword.unchanged", hash = short));
+        return Ok(());
+    }
+
+    reporter.preview(&rust_i18n::t!("reword.current"), current.trim());
+    if options.dry_run {
+        // The bare message is the result of --dry-run, whatever the reporter
+        reporter.info(&rust_i18n::t!("reword.generated"));
+        println!("{}", message);
+        return Ok(());
+    }
+    reporter.preview(&rust_i18n::t!("reword.generated"), &message);
+
+    if rewritten > 0 {
+        reporter.warning(&rust_i18n::t!("reword.rewrites_history", count = rewritten));
+    }
+    // Rewriting history is never the default answer
+    if !options.yes && !reporter.confirm(&rust_i18n::t!("reword.confirm", hash = short), false)? {
+        reporter.warning(&rust_i18n::t!("reword.cancelled"));
+        return Err(GcopError::UserCancelled);
+    }
+
+    reporter.step(
+        &rust_i18n::t!("reword.step3"),
+        &rust_i18n::t!("reword.rewording", hash = short),
+    );
+    repo.reword_commit(&target.hash, &message)?;
+    reporter.success(&rust_i18n::t!("reword.done", hash = short));
+    Ok(())
+}
+
+/// Number of commits after `hash` that the rebase rewrites.
+fn later_commits(repo: &dyn GitOperations, hash: &str) -> Result<usize> {
+    let history = HistoryOptions {
+        range: Some(format!("{}..HEAD", hash)),
+        ..Default::default()
+    };
+    Ok(repo.get_commit_history(&history)?.len())
+}
+
+/// `Signed-off-by` / `Co-authored-by` lines of `message`, re-applied to the new message.
+fn kept_trailers(message: &str) -> Vec<String> {
+    message
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            KEPT_TRAILERS.iter().any(|key| {
+                line.get(..key.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(key))
+            })
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+fn short_hash(hash: &str) -> &str {
+    &hash[..hash.len().min(7)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{CommitInfo, MockGitOperations};
+    use crate::llm::provider::test_utils::ScriptedProvider;
+    use crate::ui::SilentReporter;
+    use chrono::Local;
+
+    const REPLY: &str = "fix(auth): handle expired tokens";
+
+    fn commit(parent_count: usize) -> CommitInfo {
+        CommitInfo {
+            hash: "abc1234def".to_string(),
+            parent_count,
+            author_name: "Test".to_string(),
+            author_email: "test@example.com".to_string(),
+            timestamp: Local::now(),
+            message: "fix stuff".to_string(),
+        }
+    }
+
+    fn options() -> RewordOptions<'static> {
+        RewordOptions {
+            commit: "HEAD~1",
+            dry_run: false,
+            yes: true,
+            feedback: &[],
+            verbose: false,
+            provider_override: None,
+            model_override: None,
+        }
+    }
+
+    fn repo(parent_count: usize) -> MockGitOperations {
+        let mut repo = MockGitOperations::new();
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_get_commit_info()
+            .returning(move |_| Ok(commit(parent_count)));
+        repo.expect_is_ancestor().returning(|_, _| Ok(true));
+        repo.expect_get_commit_history()
+            .returning(|_| Ok(vec![commit(1)]));
+        repo.expect_get_commit_message()
+            .returning(|_| Ok("fix stuff\n\nSigned-off-by: Test <test@example.com>\n".to_string()));
+        repo.expect_get_commit_diff().returning(|_| {
+            Ok("diff --git a/src/auth.rs b/src/auth.rs\n--- a/src/auth.rs\n+++ b/src/auth.rs\n@@ -1 +1 @@\n-a\n+b\n".to_string())
+        });
+        repo.expect_get_diff_stats()
+            .returning(crate::git::diff::parse_diff_stats);
+        repo.expect_get_current_branch()
+            .returning(|| Ok(Some("main".to_string())));
+        repo
+    }
+
+    #[tokio::test]
+    async fn test_reword_keeps_identity_trailers() {
+        let mut repo = repo(1);
+        repo.expect_reword_commit()
+            .withf(|hash, message| {
+                hash == "abc1234def"
+                    && message
+                        == "fix(auth): handle expired tokens\n\nSigned-off-by: Test <test@example.com>"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let fake = Arc::new(ScriptedProvider::new(&[REPLY]));
+        let provider: Arc<dyn LLMProvider> = fake.clone();
+
+        run_with_deps(
+            &options(),
+            &AppConfig::default(),
+            &repo,
+            &provider,
+            &SilentReporter,
+        )
+        .await
+        .unwrap();
+        // 原提交信息作为改写依据传给模型
+        assert!(fake.prompts()[0].contains("fix stuff"));
+    }
+
+    #[tokio::test]
+    async fn test_reword_rejects_merge_commit() {
+        let repo = repo(2);
+        let provider: Arc<dyn LLMProvider> = Arc::new(ScriptedProvider::new(&[REPLY]));
+        let err = run_with_deps(
+            &options(),
+            &AppConfig::default(),
+            &repo,
+            &provider,
+            &SilentReporter,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, GcopError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_kept_trailers() {
+        let message =
+            "feat: x\n\nBody\n\nRefs: #12\nsigned-off-by: A <a@x>\nCo-authored-by: B <b@x>";
+        assert_eq!(
+            kept_trailers(message),
+            vec!["signed-off-by: A <a@x>", "Co-authored-by: B <b@x>"]
+        );
+    }
+}
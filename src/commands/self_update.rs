@@ -0,0 +1,535 @@
+//! `gcop-rs self update` and the weekly update hint.
+//!
+//! Every release publishes one binary per platform
+//! (`gcop-rs-v<version>-<platform>`) with a `<binary>.sha256` checksum next to
+//! it. The update downloads both, refuses a binary whose SHA-256 does not
+//! match, and swaps the running executable in place. The checksum comes from
+//! the same release, so it only catches corrupted or truncated downloads; it
+//! is not a signature. `--check` stops after comparing versions.
+//!
+//! With `[update] check = true` (opt-in), interactive runs ask GitHub for the latest
+//! release at most once a week (state in `<data_dir>/update-check.json`) and
+//! print a hint when it is newer. The passive check never fails a command.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::llm::audit::sha256_hex;
+use crate::ui;
+
+/// Latest release of the repository (pre-releases excluded).
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/AptS-1547/gcop-rs/releases/latest";
+
+/// Timeout of `self update` requests (the binary is a few MiB).
+const UPDATE_TIMEOUT_SECS: u64 = 120;
+
+/// Timeout of the passive check, which runs after the command has finished.
+const PASSIVE_TIMEOUT_SECS: u64 = 3;
+
+/// Minimum delay between two passive checks.
+const CHECK_INTERVAL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Passive check state inside the data directory.
+const STATE_FILE: &str = "update-check.json";
+
+/// A GitHub release.
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+/// A file attached to a release.
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    /// Release version without the `v` prefix.
+    fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// When the passive check last ran and what it found.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CheckState {
+    /// Seconds since the Unix epoch.
+    checked_at: u64,
+    /// Latest released version at that time.
+    #[serde(default)]
+    latest: Option<String>,
+}
+
+/// Runs `gcop-rs self update`.
+///
+/// # Arguments
+/// * `check` - Only report whether an update is available (`--check`)
+/// * `colored` - Whether to use colored output
+pub async fn run(check: bool, colored: bool) -> Result<()> {
+    let client = http_client(UPDATE_TIMEOUT_SECS)?;
+    let current = env!("CARGO_PKG_VERSION");
+
+    let spinner = ui::Spinner::new(&rust_i18n::t!("self_update.checking"), colored);
+    let release = fetch_latest(&client, LATEST_RELEASE_URL).await;
+    spinner.finish_and_clear();
+    let release = release?;
+    save_state(&CheckState {
+        checked_at: now(),
+        latest: Some(release.version().to_string()),
+    });
+
+    if !is_newer(release.version(), current) {
+        ui::success(
+            &rust_i18n::t!("self_update.up_to_date", version = current),
+            colored,
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        ui::info(
+            &rust_i18n::t!(
+                "self_update.available",
+                current = current,
+                latest = release.version(),
+                url = release.html_url.as_str()
+            ),
+            colored
+        )
+    );
+    if check {
+        return Ok(());
+    }
+
+    let exe = std::env::current_exe()?;
+    if let Some(command) = managed_install(&exe) {
+        return Err(GcopError::Other(
+            rust_i18n::t!("self_update.managed", command = command).to_string(),
+        ));
+    }
+    let asset = asset_name(release.version()).ok_or_else(|| {
+        GcopError::Other(
+            rust_i18n::t!(
+                "self_update.unsupported_platform",
+                os = std::env::consts::OS,
+                arch = std::env::consts::ARCH
+            )
+            .to_string(),
+        )
+    })?;
+
+    let spinner = ui::Spinner::new(
+        &rust_i18n::t!("self_update.downloading", name = asset.as_str()),
+        colored,
+    );
+    let binary = download_verified(&client, &release, &asset).await;
+    spinner.finish_and_clear();
+
+    replace_binary(&exe, &binary?)?;
+    ui::success(
+        &rust_i18n::t!(
+            "self_update.updated",
+            version = release.version(),
+            path = exe.display().to_string()
+        ),
+        colored,
+    );
+    Ok(())
+}
+
+/// Prints a hint when a newer release is out, at most once a week.
+///
+/// Does nothing when `[update] check` is off, outside of a terminal or in
+/// quiet mode. Network and state errors are ignored.
+pub async fn passive_check(config: &AppConfig) {
+    if !config.update.check || !ui::is_interactive() || ui::is_quiet() {
+        return;
+    }
+    let Some(path) = state_path() else {
+        return;
+    };
+    if !check_due(&load_state(&path), now()) {
+        return;
+    }
+
+    let latest = match http_client(PASSIVE_TIMEOUT_SECS) {
+        Ok(client) => fetch_latest(&client, LATEST_RELEASE_URL).await.ok(),
+        Err(_) => None,
+    };
+    // Recorded even on failure so that an offline machine is not retried on every run
+    save_state(&CheckState {
+        checked_at: now(),
+        latest: latest.as_ref().map(|release| release.version().to_string()),
+    });
+
+    if let Some(release) = latest
+        && is_newer(release.version(), env!("CARGO_PKG_VERSION"))
+    {
+        eprintln!();
+        eprintln!(
+            "{}",
+            ui::info(
+                &rust_i18n::t!(
+                    "self_update.hint",
+                    current = env!("CARGO_PKG_VERSION"),
+                    latest = release.version()
+                ),
+                config.ui.colored
+            )
+        );
+    }
+}
+
+fn http_client(timeout_secs: u64) -> Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent(format!(
+            "{}/{}",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        ))
+        .timeout(Duration::from_secs(timeout_secs))
+        .build()
+        .map_err(GcopError::Network)
+}
+
+async fn fetch_latest(client: &reqwest::Client, url: &str) -> Result<Release> {
+    let response = client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(response.json().await?)
+}
+
+/// Downloads `asset` of `release` and checks it against `<asset>.sha256`
+/// (an integrity check against broken downloads, not an authenticity check).
+async fn download_verified(
+    client: &reqwest::Client,
+    release: &Release,
+    asset: &str,
+) -> Result<Vec<u8>> {
+    let binary = release.asset(asset).ok_or_else(|| {
+        GcopError::Other(rust_i18n::t!("self_update.no_asset", name = asset).to_string())
+    })?;
+    let checksum_name = format!("{}.sha256", asset);
+    let checksum = release.asset(&checksum_name).ok_or_else(|| {
+        GcopError::Other(rust_i18n::t!("self_update.no_checksum", name = checksum_name).to_string())
+    })?;
+
+    let expected = client
+        .get(&checksum.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let expected = parse_checksum(&expected).ok_or_else(|| {
+        GcopError::Other(rust_i18n::t!("self_update.no_checksum", name = checksum_name).to_string())
+    })?;
+
+    let bytes = client
+        .get(&binary.browser_download_url)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    if sha256_hex(&bytes) != expected {
+        return Err(GcopError::Other(
+            rust_i18n::t!("self_update.checksum_mismatch", name = asset).to_string(),
+        ));
+    }
+    Ok(bytes.to_vec())
+}
+
+/// Hex digest of a `sha256sum` line (`<hex>  <name>`) or a bare digest.
+fn parse_checksum(content: &str) -> Option<String> {
+    let digest = content.split_whitespace().next()?.to_ascii_lowercase();
+    (digest.len() == 64 && digest.bytes().all(|b| b.is_ascii_hexdigit())).then_some(digest)
+}
+
+/// Whether `latest` is a higher `major.minor.patch` than `current`.
+///
+/// Pre-release and build suffixes are ignored; an unparsable version is never newer.
+fn is_newer(latest: &str, current: &str) -> bool {
+    fn parse(version: &str) -> Option<Vec<u64>> {
+        version
+            .split(['-', '+'])
+            .next()?
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect()
+    }
+    match (parse(latest), parse(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// Release asset of this platform, as named by the release workflow.
+fn asset_name(version: &str) -> Option<String> {
+    let platform = match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => "linux-amd64",
+        ("linux", "aarch64") => "linux-arm64",
+        ("macos", "x86_64") => "macos-amd64",
+        ("macos", "aarch64") => "macos-arm64",
+        ("windows", "x86_64") => "windows-amd64.exe",
+        ("windows", "aarch64") => "windows-aarch64.exe",
+        _ => return None,
+    };
+    Some(format!("gcop-rs-v{}-{}", version, platform))
+}
+
+/// Upgrade command of a package manager owning `exe`, if any.
+///
+/// Replacing such a binary would desynchronize the package manager.
+fn managed_install(exe: &Path) -> Option<&'static str> {
+    let path = crate::git::to_git_path(exe);
+    if path.contains("/Cellar/") || path.contains("/homebrew/") {
+        Some("brew upgrade gcop-rs")
+    } else if path.contains("/pipx/") || path.contains("site-packages") {
+        Some("pipx upgrade gcop-rs")
+    } else {
+        None
+    }
+}
+
+/// Replaces the executable at `exe` with `binary`.
+///
+/// The new file is written next to `exe` and renamed over it, so a failed
+/// write leaves the current binary intact. Windows cannot overwrite a running
+/// executable but can rename it: it is moved to `<exe>.old` first.
+fn replace_binary(exe: &Path, binary: &[u8]) -> Result<()> {
+    let dir = exe.parent().unwrap_or(Path::new("."));
+    let mut file = tempfile::Builder::new()
+        .prefix(".gcop-rs-update")
+        .tempfile_in(dir)?;
+    file.write_all(binary)?;
+    file.as_file().sync_all()?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    #[cfg(windows)]
+    {
+        let mut old = exe.as_os_str().to_owned();
+        old.push(".old");
+        let _ = std::fs::remove_file(&old);
+        std::fs::rename(exe, &old)?;
+    }
+
+    file.persist(exe).map_err(|e| GcopError::Io(e.error))?;
+    Ok(())
+}
+
+fn state_path() -> Option<PathBuf> {
+    crate::config::get_data_dir().map(|dir| dir.join(STATE_FILE))
+}
+
+/// Whether the last passive check is at least a week old.
+fn check_due(state: &CheckState, now: u64) -> bool {
+    now.saturating_sub(state.checked_at) >= CHECK_INTERVAL_SECS
+}
+
+fn load_state(path: &Path) -> CheckState {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Saves the passive check state; failures are logged.
+fn save_state(state: &CheckState) {
+    let Some(path) = state_path() else {
+        return;
+    };
+    let write = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| serde_json::to_string(state).map_err(std::io::Error::other))
+        .and_then(|content| std::fs::write(&path, content + "\n"));
+    if let Err(e) = write {
+        tracing::debug!("Failed to save {}: {}", path.display(), e);
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::provider::test_utils::ensure_crypto_provider;
+
+    #[test]
+    fn test_is_newer_compares_numerically() {
+        assert!(is_newer("0.14.0", "0.13.9"));
+        assert!(is_newer("0.13.10", "0.13.9"));
+        assert!(is_newer("1.0.0", "0.99.99"));
+        assert!(!is_newer("0.13.9", "0.13.9"));
+        assert!(!is_newer("0.13.8", "0.13.9"));
+        // 预发布后缀忽略，无法解析的版本不视为更新
+        assert!(!is_newer("0.13.9-rc.1", "0.13.9"));
+        assert!(!is_newer("nightly", "0.13.9"));
+    }
+
+    #[test]
+    fn test_parse_checksum() {
+        let digest = "a".repeat(64);
+        assert_eq!(
+            parse_checksum(&format!("{}  gcop-rs-v1.0.0-linux-amd64\n", digest)),
+            Some(digest.clone())
+        );
+        assert_eq!(parse_checksum(&digest.to_uppercase()), Some(digest));
+        assert_eq!(parse_checksum("not a digest"), None);
+        assert_eq!(parse_checksum(""), None);
+    }
+
+    #[test]
+    fn test_check_due_weekly() {
+        let state = CheckState {
+            checked_at: 1_000_000,
+            latest: None,
+        };
+        assert!(!check_due(&state, 1_000_000 + 3600));
+        assert!(check_due(&state, 1_000_000 + CHECK_INTERVAL_SECS));
+        assert!(check_due(&CheckState::default(), 1_000_000));
+    }
+
+    #[test]
+    fn test_managed_install() {
+        assert_eq!(
+            managed_install(Path::new("/opt/homebrew/Cellar/gcop-rs/0.13.9/bin/gcop-rs")),
+            Some("brew upgrade gcop-rs")
+        );
+        assert_eq!(
+            managed_install(Path::new("/home/u/.local/pipx/venvs/gcop-rs/bin/gcop-rs")),
+            Some("pipx upgrade gcop-rs")
+        );
+        assert_eq!(
+            managed_install(Path::new("/home/u/.cargo/bin/gcop-rs")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_replace_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("gcop-rs");
+        std::fs::write(&exe, b"old").unwrap();
+
+        replace_binary(&exe, b"new").unwrap();
+        assert_eq!(std::fs::read(&exe).unwrap(), b"new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&exe).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+    }
+
+    async fn release_on(
+        server: &mut mockito::Server,
+        checksum: &str,
+    ) -> (Release, Vec<mockito::Mock>) {
+        let binary = server.url() + "/gcop-rs-v9.0.0-test";
+        let mocks = vec![
+            server
+                .mock("GET", "/gcop-rs-v9.0.0-test")
+                .with_body("binary")
+                .create_async()
+                .await,
+            server
+                .mock("GET", "/gcop-rs-v9.0.0-test.sha256")
+                .with_body(format!("{}  gcop-rs-v9.0.0-test\n", checksum))
+                .create_async()
+                .await,
+        ];
+        let release = Release {
+            tag_name: "v9.0.0".to_string(),
+            html_url: "https://example.com".to_string(),
+            assets: vec![
+                Asset {
+                    name: "gcop-rs-v9.0.0-test".to_string(),
+                    browser_download_url: binary.clone(),
+                },
+                Asset {
+                    name: "gcop-rs-v9.0.0-test.sha256".to_string(),
+                    browser_download_url: binary + ".sha256",
+                },
+            ],
+        };
+        (release, mocks)
+    }
+
+    #[tokio::test]
+    async fn test_download_verified_checks_sha256() {
+        ensure_crypto_provider();
+        let client = http_client(5).unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let (release, _mocks) = release_on(&mut server, &sha256_hex(b"binary")).await;
+        let bytes = download_verified(&client, &release, "gcop-rs-v9.0.0-test")
+            .await
+            .unwrap();
+        assert_eq!(bytes, b"binary");
+
+        // 校验和不匹配时拒绝更新
+        let mut server = mockito::Server::new_async().await;
+        let (release, _mocks) = release_on(&mut server, &sha256_hex(b"tampered")).await;
+        let result = download_verified(&client, &release, "gcop-rs-v9.0.0-test").await;
+        assert!(matches!(result, Err(GcopError::Other(_))));
+
+        // 缺少校验文件时同样拒绝
+        let missing = Release {
+            assets: release.assets.into_iter().take(1).collect(),
+            ..release
+        };
+        let result = download_verified(&client, &missing, "gcop-rs-v9.0.0-test").await;
+        assert!(matches!(result, Err(GcopError::Other(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_latest_parses_release() {
+        ensure_crypto_provider();
+        let mut server = mockito::Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/releases/latest")
+            .with_body(
+                r#"{"tag_name":"v0.14.0","html_url":"https://github.com/x","assets":[{"name":"a","browser_download_url":"https://x/a"}]}"#,
+            )
+            .create_async()
+            .await;
+        let release = fetch_latest(
+            &http_client(5).unwrap(),
+            &(server.url() + "/releases/latest"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(release.version(), "0.14.0");
+        assert!(release.asset("a").is_some());
+    }
+}
@@ -15,8 +15,10 @@ use crate::config::AppConfig;
 use crate::error::{GcopError, Result};
 use crate::git::diff::{FileDiff, split_diff_by_file};
 use crate::git::{DiffStats, GitOperations};
-use crate::llm::{CommitContext, LLMProvider, ScopeInfo};
-use crate::ui;
+use crate::llm::ledger;
+use crate::llm::metrics::{self, LlmMetrics};
+use crate::llm::{CommitContext, LLMProvider, ProgressReporter, ScopeInfo};
+use crate::ui::{self, Reporter};
 
 /// A single commit group: files + message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +56,7 @@ pub async fn run_split_flow(
     config: &AppConfig,
     repo: &dyn GitOperations,
     provider: &Arc<dyn LLMProvider>,
+    reporter: &dyn Reporter,
 ) -> Result<()> {
     let colored = options.effective_colored(config);
 
@@ -65,62 +68,110 @@ pub async fn run_split_flow(
 
     // JSON mode
     if options.format.is_json() {
-        return handle_split_json_mode(options, config, repo, provider, &initial_feedbacks).await;
+        return handle_split_json_mode(
+            options,
+            config,
+            repo,
+            provider,
+            &initial_feedbacks,
+            reporter,
+        )
+        .await;
     }
 
+    // A failed verification can re-plan the changes that are still staged
+    let mut feedbacks = initial_feedbacks;
+    loop {
+        match run_split_plan(
+            options,
+            config,
+            repo,
+            provider,
+            feedbacks.clone(),
+            colored,
+            reporter,
+        )
+        .await?
+        {
+            SplitEnd::Done => return Ok(()),
+            SplitEnd::Replan(note) => feedbacks.push(note),
+        }
+    }
+}
+
+/// How one planning round of the split flow ended.
+enum SplitEnd {
+    Done,
+    /// Plan again with this note for the LLM.
+    Replan(String),
+}
+
+/// Plan, review and commit the staged changes once.
+async fn run_split_plan(
+    options: &CommitOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    mut feedbacks: Vec<String>,
+    colored: bool,
+    reporter: &dyn Reporter,
+) -> Result<SplitEnd> {
     // Check staged changes
-    if !repo.has_staged_changes()? {
-        ui::error(&rust_i18n::t!("commit.no_staged_changes"), colored);
+    if !super::commit::ensure_staged_changes(options, config, repo, reporter)? {
+        reporter.error(&rust_i18n::t!("commit.no_staged_changes"));
         return Err(GcopError::NoStagedChanges);
     }
 
     // Get diff and split by file
     let diff = repo.get_staged_diff()?;
     let stats = repo.get_diff_stats(&diff)?;
+    let diff = super::privacy::guard(diff, config, false, colored)?;
     let file_diffs = split_diff_by_file(&diff);
+    let trailers = options.trailers(config, repo)?;
 
     if file_diffs.is_empty() {
-        ui::error(&rust_i18n::t!("commit.no_staged_changes"), colored);
+        reporter.error(&rust_i18n::t!("commit.no_staged_changes"));
         return Err(GcopError::NoStagedChanges);
     }
 
     // Workspace scope detection
-    let scope_info = super::commit::compute_scope_info_pub(&stats.files_changed, config);
+    let scope_info = super::commit::compute_scope_info_pub(repo, &stats.files_changed, config);
+    let history_examples = super::commit::compute_history_examples(repo, config);
 
-    ui::step(
+    reporter.step(
         &rust_i18n::t!("commit.step1"),
         &rust_i18n::t!(
             "commit.analyzed",
             files = stats.files_changed.len(),
             changes = stats.insertions + stats.deletions
         ),
-        colored,
     );
 
     // If only 1 file, no need to split - just inform and suggest normal commit
     if file_diffs.len() == 1 {
-        ui::warning(&rust_i18n::t!("split.single_file"), colored);
+        reporter.warning(&rust_i18n::t!("split.single_file"));
         // Fall through to generate a single group anyway
     }
 
     let branch_name = repo.get_current_branch()?;
     let custom_prompt = config.commit.custom_prompt.clone();
-    let mut feedbacks = initial_feedbacks;
+    let verify = options
+        .verify
+        .or(config.commit.split_verify_command.as_deref())
+        .filter(|command| !command.trim().is_empty());
+    let interactive = !options.yes && ui::is_interactive();
     let max_retries = config.commit.max_retries;
     let mut attempt = 0usize;
 
     // Outer loop: generation
     loop {
         if attempt >= max_retries {
-            ui::warning(
-                &rust_i18n::t!("commit.max_retries", count = max_retries),
-                colored,
-            );
+            reporter.warning(&rust_i18n::t!("commit.max_retries", count = max_retries));
             return Err(GcopError::MaxRetriesExceeded(max_retries));
         }
 
         // Generate groups
-        let mut current_groups = generate_groups(
+        let (mut current_groups, _) = generate_groups(
             provider,
             &file_diffs,
             &stats,
@@ -130,22 +181,42 @@ pub async fn run_split_flow(
             &branch_name,
             &custom_prompt,
             &scope_info,
+            &history_examples,
             colored,
             attempt,
+            reporter,
         )
         .await?;
+        apply_group_trailers(&mut current_groups, &trailers);
 
         // Display groups
-        display_commit_groups(&current_groups, &file_diffs, colored);
+        display_commit_groups(&current_groups, &file_diffs, colored, reporter);
 
         // dry-run mode: just show and exit
         if options.dry_run {
-            return Ok(());
+            return Ok(SplitEnd::Done);
         }
 
         // auto-accept mode
         if options.yes {
-            return execute_split_commits(repo, &current_groups, colored);
+            let result = execute_split_commits(
+                repo,
+                &current_groups,
+                &file_diffs,
+                &trailers,
+                verify,
+                reporter,
+            );
+            return replan_on_verify_failure(result, interactive, reporter);
+        }
+
+        // Review the plan in the editor before choosing what to do with it
+        if config.commit.split_edit_plan
+            && config.commit.allow_edit
+            && !options.no_edit
+            && ui::is_interactive()
+        {
+            edit_plan(&mut current_groups, &stats, &file_diffs, colored, reporter)?;
         }
 
         // Inner loop: interaction (edit stays here, regenerate breaks to outer)
@@ -153,16 +224,18 @@ pub async fn run_split_flow(
             let action = split_action_menu(colored, attempt)?;
             match action {
                 SplitAction::AcceptAll => {
-                    return execute_split_commits(repo, &current_groups, colored);
+                    let result = execute_split_commits(
+                        repo,
+                        &current_groups,
+                        &file_diffs,
+                        &trailers,
+                        verify,
+                        reporter,
+                    );
+                    return replan_on_verify_failure(result, interactive, reporter);
                 }
                 SplitAction::Edit => {
-                    let edited = edit_groups_in_editor(&current_groups, &stats.files_changed)?;
-                    if let Some(edited_groups) = edited {
-                        current_groups = edited_groups;
-                        display_commit_groups(&current_groups, &file_diffs, colored);
-                    } else {
-                        ui::warning(&rust_i18n::t!("commit.edit_cancelled"), colored);
-                    }
+                    edit_plan(&mut current_groups, &stats, &file_diffs, colored, reporter)?;
                     // Stay in inner loop — user can edit again, accept, or regenerate
                     continue;
                 }
@@ -171,24 +244,65 @@ pub async fn run_split_flow(
                     break; // break inner → outer loop regenerates
                 }
                 SplitAction::RegenerateWithFeedback => {
-                    let new_feedback = ui::get_retry_feedback(colored)?;
+                    let new_feedback =
+                        ui::get_retry_feedback(colored, &config.commit.feedback_presets())?;
                     if let Some(fb) = new_feedback {
                         feedbacks.push(fb);
                     } else {
-                        ui::warning(&rust_i18n::t!("commit.feedback.empty"), colored);
+                        reporter.warning(&rust_i18n::t!("commit.feedback.empty"));
                     }
                     attempt += 1;
                     break; // break inner → outer loop regenerates
                 }
                 SplitAction::Quit => {
-                    ui::warning(&rust_i18n::t!("commit.cancelled"), colored);
-                    return Ok(());
+                    reporter.warning(&rust_i18n::t!("commit.cancelled"));
+                    return Ok(SplitEnd::Done);
                 }
             }
         }
     }
 }
 
+/// Offer to re-plan the still-staged changes after a failed verification.
+///
+/// Without a user to ask (`--yes`, no terminal) the failure is returned.
+fn replan_on_verify_failure(
+    result: Result<()>,
+    interactive: bool,
+    reporter: &dyn Reporter,
+) -> Result<SplitEnd> {
+    let error = match result {
+        Ok(()) => return Ok(SplitEnd::Done),
+        Err(error) => error,
+    };
+    let GcopError::SplitVerifyFailed {
+        group,
+        command,
+        detail,
+        ..
+    } = &error
+    else {
+        return Err(error);
+    };
+    if !interactive
+        || !reporter.confirm(
+            &rust_i18n::t!(
+                "split.replan_confirm",
+                group = group,
+                command = command.as_str()
+            ),
+            true,
+        )?
+    {
+        return Err(error);
+    }
+    Ok(SplitEnd::Replan(format!(
+        "The previous plan failed `{}` ({}) at commit {}. Regroup the remaining files so \
+         that every commit passes `{}` on its own, merging groups if needed.",
+        command, detail, group, command
+    )))
+}
+
 // --- Group generation --------------------------------------------------------
 
 /// Generate commit groups from staged diffs using the LLM.
@@ -203,18 +317,25 @@ async fn generate_groups(
     branch_name: &Option<String>,
     custom_prompt: &Option<String>,
     scope_info: &Option<ScopeInfo>,
+    history_examples: &[String],
     colored: bool,
     attempt: usize,
-) -> Result<Vec<CommitGroup>> {
+    reporter: &dyn Reporter,
+) -> Result<(Vec<CommitGroup>, LlmMetrics)> {
     let context = CommitContext {
         files_changed: stats.files_changed.clone(),
         insertions: stats.insertions,
         deletions: stats.deletions,
         branch_name: branch_name.clone(),
+        ticket: super::ticket::resolve_ticket(branch_name.as_deref(), &config.commit),
         custom_prompt: custom_prompt.clone(),
         user_feedback: feedbacks.to_vec(),
         convention: config.commit.convention.clone(),
         scope_info: scope_info.clone(),
+        history_examples: history_examples.to_vec(),
+        previous_message: None,
+        body: config.commit.body,
+        body_bullets: config.commit.body_bullets,
     };
 
     // Build split prompt (system + user)
@@ -258,19 +379,61 @@ async fn generate_groups(
     } else {
         rust_i18n::t!("spinner.regenerating")
     };
-    ui::step(&rust_i18n::t!("commit.step2"), &step_msg, colored);
+    reporter.step(&rust_i18n::t!("commit.step2"), &step_msg);
 
     let spinner_msg = rust_i18n::t!("split.generating_groups").to_string();
-    let mut spinner = ui::Spinner::new_with_cancel_hint(&spinner_msg, colored);
-    spinner.start_time_display();
+    let spinner = reporter.cancellable_spinner(&spinner_msg);
 
     // Direct query with pre-built prompts
-    let raw_response = provider.send_prompt(&system, &user, Some(&spinner)).await?;
+    metrics::start();
+    let progress = spinner.as_ref().map(|s| s as &dyn ProgressReporter);
+    let diff_size = file_diffs.iter().map(|fd| fd.content.len()).sum();
+    let raw_response = crate::llm::provider::tiered::diff_size_scope(
+        diff_size,
+        provider.send_prompt(&system, &user, progress),
+    )
+    .await;
+    if let Some(spinner) = &spinner {
+        spinner.finish_and_clear();
+    }
+    let raw_response = raw_response?;
+    let llm_metrics = metrics::finish();
+    ledger::record("commit", &llm_metrics, config);
 
-    spinner.finish_and_clear();
+    if verbose {
+        super::commit::print_verbose_metrics(&llm_metrics, true, colored);
+    }
 
     // Parse the response
-    parse_split_response(&raw_response, &stats.files_changed)
+    let groups = parse_split_response(&raw_response, &stats.files_changed)?;
+
+    // Commit definitions before their uses (before tickets, which merging would repeat)
+    let ordered = super::split_order::order_groups(groups, file_diffs);
+    if ordered.reordered || ordered.merged > 0 {
+        let note = if ordered.merged > 0 {
+            rust_i18n::t!("split.merged", count = ordered.merged)
+        } else {
+            rust_i18n::t!("split.reordered")
+        };
+        reporter.info(&note);
+    }
+    let mut groups = ordered.groups;
+    for group in &mut groups {
+        group.message = super::ticket::apply_ticket(
+            std::mem::take(&mut group.message),
+            context.ticket.as_deref(),
+            &config.commit,
+        );
+    }
+    Ok((groups, llm_metrics))
+}
+
+/// Append the requested trailers to every group message.
+fn apply_group_trailers(groups: &mut [CommitGroup], trailers: &[String]) {
+    for group in groups {
+        group.message =
+            super::trailers::apply_trailers(std::mem::take(&mut group.message), trailers);
+    }
 }
 
 // --- Response parsing --------------------------------------------------------
@@ -299,9 +462,31 @@ pub fn parse_split_response(raw: &str, expected_files: &[String]) -> Result<Vec<
         ));
     }
 
-    // Validate: collect all files from groups
+    let missing = validate_plan(&response.groups, expected_files)?;
+    if !missing.is_empty() {
+        // Not a hard error - LLM might use slightly different paths.
+        // Unclaimed files stay staged after the split commits.
+        tracing::warn!("Some staged files not covered by LLM groups: {:?}", missing);
+    }
+
+    Ok(response.groups)
+}
+
+/// Validate a commit plan against the staged files.
+///
+/// Every group needs files and a message, and a file may appear in only one
+/// group. Files outside the staging area are rejected: staging them would
+/// silently commit working-tree changes the user never staged. Returns the
+/// staged files no group claims.
+fn validate_plan(groups: &[CommitGroup], staged_files: &[String]) -> Result<Vec<String>> {
+    if groups.is_empty() {
+        return Err(GcopError::SplitParseFailed(
+            "Commit plan has no groups".to_string(),
+        ));
+    }
+
     let mut seen_files = std::collections::HashSet::new();
-    for group in &response.groups {
+    for group in groups {
         if group.files.is_empty() {
             return Err(GcopError::SplitParseFailed(format!(
                 "Group '{}' has no files",
@@ -314,7 +499,7 @@ pub fn parse_split_response(raw: &str, expected_files: &[String]) -> Result<Vec<
             ));
         }
         for file in &group.files {
-            if !seen_files.insert(file.clone()) {
+            if !seen_files.insert(file.as_str()) {
                 return Err(GcopError::SplitParseFailed(format!(
                     "File '{}' appears in multiple groups",
                     file
@@ -323,28 +508,22 @@ pub fn parse_split_response(raw: &str, expected_files: &[String]) -> Result<Vec<
         }
     }
 
-    // Check that all expected files are covered
-    let expected_set: std::collections::HashSet<_> = expected_files.iter().cloned().collect();
-    let missing: Vec<_> = expected_set.difference(&seen_files).collect();
-    if !missing.is_empty() {
-        // Not a hard error - LLM might use slightly different paths
-        // Log warning but proceed
-        tracing::warn!("Some staged files not covered by LLM groups: {:?}", missing);
-    }
-
-    // Check that LLM did not return files outside the staging area.
-    // Staging and committing such files would silently include unstaged working-tree
-    // changes that the user never intended to commit.
-    let mut extra: Vec<_> = seen_files.difference(&expected_set).cloned().collect();
+    let staged_set: std::collections::HashSet<_> =
+        staged_files.iter().map(String::as_str).collect();
+    let mut extra: Vec<_> = seen_files.difference(&staged_set).copied().collect();
     if !extra.is_empty() {
         extra.sort();
         return Err(GcopError::SplitParseFailed(format!(
-            "LLM returned files not in staging area: {:?}",
+            "Files not in staging area: {:?}",
             extra
         )));
     }
 
-    Ok(response.groups)
+    Ok(staged_files
+        .iter()
+        .filter(|file| !seen_files.contains(file.as_str()))
+        .cloned()
+        .collect())
 }
 
 /// Strip markdown code fences from a string.
@@ -371,17 +550,23 @@ fn strip_code_fences(s: &str) -> &str {
 // --- Commit execution --------------------------------------------------------
 
 /// Execute split commits sequentially with error recovery.
+///
+/// The plan is validated again before anything is unstaged, since it may
+/// have been edited. Staged files no group claims are staged again afterwards.
 fn execute_split_commits(
     repo: &dyn GitOperations,
     groups: &[CommitGroup],
-    colored: bool,
+    file_diffs: &[FileDiff],
+    trailers: &[String],
+    verify: Option<&str>,
+    reporter: &dyn Reporter,
 ) -> Result<()> {
     let total = groups.len();
+    let unassigned = validate_plan(groups, &staged_files(file_diffs))?;
 
-    ui::step(
+    reporter.step(
         &rust_i18n::t!("commit.step4"),
         &rust_i18n::t!("split.committing", total = total),
-        colored,
     );
 
     // Step 1: Unstage all files
@@ -390,32 +575,37 @@ fn execute_split_commits(
     // Step 2: Commit each group
     for (i, group) in groups.iter().enumerate() {
         // Stage this group's files
-        repo.stage_files(&group.files)?;
-
-        // Commit
-        match repo.commit(&group.message) {
-            Ok(()) => {
-                if colored {
-                    println!(
-                        "  {} {}/{}: {}",
-                        "✓".green().bold(),
-                        i + 1,
-                        total,
-                        group.message.yellow()
-                    );
-                } else {
-                    println!("  ✓ {}/{}: {}", i + 1, total, group.message);
-                }
-            }
-            Err(e) => {
-                // Recovery: re-stage remaining files
-                let remaining_files: Vec<String> =
-                    groups[i..].iter().flat_map(|g| g.files.clone()).collect();
+        repo.stage_files(&paths_to_stage(&group.files, file_diffs))?;
 
-                // Try to recover staging state
-                let _ = repo.unstage_all();
-                let _ = repo.stage_files(&remaining_files);
+        // Recovery: re-stage this group's and all later files
+        let restage_remaining = || {
+            let remaining_files: Vec<String> = groups[i..]
+                .iter()
+                .flat_map(|g| paths_to_stage(&g.files, file_diffs))
+                .chain(paths_to_stage(&unassigned, file_diffs))
+                .collect();
+            let _ = repo.unstage_all();
+            let _ = repo.stage_files(&remaining_files);
+        };
+
+        if let Some(command) = verify
+            && let Err(detail) = verify_staged(repo, command, i + 1, total, reporter)
+        {
+            restage_remaining();
+            return Err(GcopError::SplitVerifyFailed {
+                group: i + 1,
+                total,
+                command: command.to_string(),
+                detail,
+            });
+        }
 
+        // Commit (edits may have dropped requested trailers)
+        let message = super::trailers::apply_trailers(group.message.clone(), trailers);
+        match repo.commit(&message) {
+            Ok(()) => reporter.success(&format!("{}/{}: {}", i + 1, total, message)),
+            Err(e) => {
+                restage_remaining();
                 return Err(GcopError::SplitCommitPartial {
                     completed: i,
                     total,
@@ -425,49 +615,126 @@ fn execute_split_commits(
         }
     }
 
-    println!();
-    ui::success(&rust_i18n::t!("split.success", count = total), colored);
+    // Step 3: Leave unclaimed files staged, as they were before
+    if !unassigned.is_empty() {
+        repo.stage_files(&paths_to_stage(&unassigned, file_diffs))?;
+    }
+
+    reporter.success(&rust_i18n::t!("split.success", count = total));
     Ok(())
 }
 
+/// Run the verification command on the staged snapshot of group `index`.
+///
+/// The index is checked out into a temporary directory and the command runs
+/// there through the shell, so unstaged changes and the files of later
+/// groups cannot hide a broken intermediate commit. Failures are returned
+/// as the detail of the resulting [`GcopError::SplitVerifyFailed`].
+fn verify_staged(
+    repo: &dyn GitOperations,
+    command: &str,
+    index: usize,
+    total: usize,
+    reporter: &dyn Reporter,
+) -> std::result::Result<(), String> {
+    reporter.info(&rust_i18n::t!(
+        "split.verifying",
+        index = index,
+        total = total,
+        command = command
+    ));
+    let snapshot = tempfile::tempdir().map_err(|e| e.to_string())?;
+    repo.checkout_index(snapshot.path())
+        .map_err(|e| e.to_string())?;
+
+    let status = shell_command(command)
+        .current_dir(snapshot.path())
+        .status()
+        .map_err(|e| e.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(status.to_string())
+    }
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("cmd");
+    cmd.args(["/C", command]);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("sh");
+    cmd.args(["-c", command]);
+    cmd
+}
+
+/// Staged file paths, in diff order.
+fn staged_files(file_diffs: &[FileDiff]) -> Vec<String> {
+    file_diffs.iter().map(|fd| fd.filename.clone()).collect()
+}
+
+/// Paths to stage for a group's files.
+///
+/// A renamed file also needs its old path staged so the deletion side of the
+/// rename lands in the same commit.
+fn paths_to_stage(files: &[String], file_diffs: &[FileDiff]) -> Vec<String> {
+    let mut paths = Vec::with_capacity(files.len());
+    for file in files {
+        paths.push(file.clone());
+        if let Some(old) = file_diffs
+            .iter()
+            .find(|fd| fd.filename == *file)
+            .and_then(|fd| fd.old_filename.as_ref())
+        {
+            paths.push(old.clone());
+        }
+    }
+    paths
+}
+
 // --- Display -----------------------------------------------------------------
 
 /// Display commit groups in a formatted preview.
-fn display_commit_groups(groups: &[CommitGroup], file_diffs: &[FileDiff], colored: bool) {
+fn display_commit_groups(
+    groups: &[CommitGroup],
+    file_diffs: &[FileDiff],
+    colored: bool,
+    reporter: &dyn Reporter,
+) {
     let total = groups.len();
 
-    ui::step(
+    reporter.step(
         &rust_i18n::t!("commit.step2"),
         &rust_i18n::t!("split.generated_groups", count = total),
-        colored,
     );
-    println!();
 
+    let mut plan = Vec::new();
     for (i, group) in groups.iter().enumerate() {
+        let files = if group.files.len() == 1 {
+            "file"
+        } else {
+            "files"
+        };
         if colored {
-            println!(
+            plan.push(format!(
                 "  {} ({} {}):",
                 format!("Group {}", i + 1).cyan().bold(),
                 group.files.len(),
-                if group.files.len() == 1 {
-                    "file"
-                } else {
-                    "files"
-                }
-            );
-            println!("    {}", group.message.yellow());
+                files
+            ));
+            plan.push(format!("    {}", group.message.yellow()));
         } else {
-            println!(
+            plan.push(format!(
                 "  Group {} ({} {}):",
                 i + 1,
                 group.files.len(),
-                if group.files.len() == 1 {
-                    "file"
-                } else {
-                    "files"
-                }
-            );
-            println!("    {}", group.message);
+                files
+            ));
+            plan.push(format!("    {}", group.message));
         }
 
         for file in &group.files {
@@ -479,12 +746,25 @@ fn display_commit_groups(groups: &[CommitGroup], file_diffs: &[FileDiff], colore
                 .unwrap_or((0, 0));
 
             if colored {
-                println!("    {} {} (+{} -{})", "-".dimmed(), file, ins, del);
+                plan.push(format!("    {} {} (+{} -{})", "-".dimmed(), file, ins, del));
             } else {
-                println!("    - {} (+{} -{})", file, ins, del);
+                plan.push(format!("    - {} (+{} -{})", file, ins, del));
             }
         }
-        println!();
+        plan.push(String::new());
+    }
+    reporter.detail(plan.join("\n").trim_end());
+
+    let unassigned: Vec<&str> = file_diffs
+        .iter()
+        .map(|fd| fd.filename.as_str())
+        .filter(|file| !groups.iter().any(|g| g.files.iter().any(|f| f == file)))
+        .collect();
+    if !unassigned.is_empty() {
+        reporter.warning(&rust_i18n::t!(
+            "split.unassigned",
+            files = unassigned.join(", ")
+        ));
     }
 }
 
@@ -598,10 +878,21 @@ struct EditorGroups {
 }
 
 /// Format groups as TOML for editor editing.
-fn format_groups_for_editor(groups: &[CommitGroup]) -> String {
-    let header = "# Atomic Commit Groups\n\
-                  # Edit messages, move files between groups, or delete groups.\n\
-                  # Empty groups (no files) will be skipped.\n\n";
+///
+/// The header lists every staged file so files can be moved between groups.
+fn format_groups_for_editor(groups: &[CommitGroup], staged_files: &[String]) -> String {
+    let mut header = String::from(
+        "# Atomic Commit Groups\n\
+         # Edit messages, move files between groups, or delete groups.\n\
+         # Empty groups (no files) will be skipped.\n\
+         # Staged files not listed in any group stay staged.\n\
+         #\n\
+         # Staged files:\n",
+    );
+    for file in staged_files {
+        header.push_str(&format!("#   {}\n", file));
+    }
+    header.push('\n');
 
     let wrapper = EditorGroups {
         groups: groups.to_vec(),
@@ -649,22 +940,7 @@ fn parse_groups_from_editor(text: &str, expected_files: &[String]) -> Result<Vec
         ));
     }
 
-    // Validate: no duplicate files
-    let mut seen = std::collections::HashSet::new();
-    for group in &groups {
-        for file in &group.files {
-            if !seen.insert(file.clone()) {
-                return Err(GcopError::SplitParseFailed(format!(
-                    "File '{}' appears in multiple groups",
-                    file
-                )));
-            }
-        }
-    }
-
-    // Warn about missing files
-    let expected_set: std::collections::HashSet<_> = expected_files.iter().cloned().collect();
-    let missing: Vec<_> = expected_set.difference(&seen).collect();
+    let missing = validate_plan(&groups, expected_files)?;
     if !missing.is_empty() {
         tracing::warn!("Files removed from groups: {:?}", missing);
     }
@@ -673,20 +949,51 @@ fn parse_groups_from_editor(text: &str, expected_files: &[String]) -> Result<Vec
 }
 
 /// Open groups in editor for editing.
+///
+/// An invalid plan is reported and, if the user agrees, reopened with their
+/// edits so it can be fixed. Returns `None` when editing is abandoned.
 fn edit_groups_in_editor(
     groups: &[CommitGroup],
     expected_files: &[String],
+    reporter: &dyn Reporter,
 ) -> Result<Option<Vec<CommitGroup>>> {
-    let content = format_groups_for_editor(groups);
+    let mut content = format_groups_for_editor(groups, expected_files);
 
-    match ui::edit_text(&content) {
-        Ok(edited) => {
-            let parsed = parse_groups_from_editor(&edited, expected_files)?;
-            Ok(Some(parsed))
+    loop {
+        let edited = match ui::edit_text(&content) {
+            Ok(edited) => edited,
+            Err(GcopError::UserCancelled) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        match parse_groups_from_editor(&edited, expected_files) {
+            Ok(parsed) => return Ok(Some(parsed)),
+            Err(e) => {
+                reporter.error(&e.localized_message());
+                if !reporter.confirm(&rust_i18n::t!("split.edit_again"), true)? {
+                    return Ok(None);
+                }
+                content = edited;
+            }
+        }
+    }
+}
+
+/// Let the user edit the plan, keeping the current one when editing is abandoned.
+fn edit_plan(
+    groups: &mut Vec<CommitGroup>,
+    stats: &DiffStats,
+    file_diffs: &[FileDiff],
+    colored: bool,
+    reporter: &dyn Reporter,
+) -> Result<()> {
+    match edit_groups_in_editor(groups, &stats.files_changed, reporter)? {
+        Some(edited_groups) => {
+            *groups = edited_groups;
+            display_commit_groups(groups, file_diffs, colored, reporter);
         }
-        Err(GcopError::UserCancelled) => Ok(None),
-        Err(e) => Err(e),
+        None => reporter.warning(&rust_i18n::t!("commit.edit_cancelled")),
     }
+    Ok(())
 }
 
 // --- JSON mode ---------------------------------------------------------------
@@ -698,18 +1005,34 @@ async fn handle_split_json_mode(
     repo: &dyn GitOperations,
     provider: &Arc<dyn LLMProvider>,
     initial_feedbacks: &[String],
+    reporter: &dyn Reporter,
 ) -> Result<()> {
-    if !repo.has_staged_changes()? {
+    if !super::commit::ensure_staged_changes(options, config, repo, reporter)? {
         json::output_json_error::<SplitCommitData>(&GcopError::NoStagedChanges)?;
         return Err(GcopError::NoStagedChanges);
     }
 
     let diff = repo.get_staged_diff()?;
     let stats = repo.get_diff_stats(&diff)?;
+    let diff = match super::privacy::guard(diff, config, true, false) {
+        Ok(diff) => diff,
+        Err(e) => {
+            json::output_json_error::<SplitCommitData>(&e)?;
+            return Err(e);
+        }
+    };
     let file_diffs = split_diff_by_file(&diff);
+    let trailers = match options.trailers(config, repo) {
+        Ok(trailers) => trailers,
+        Err(e) => {
+            json::output_json_error::<SplitCommitData>(&e)?;
+            return Err(e);
+        }
+    };
     let branch_name = repo.get_current_branch()?;
     let custom_prompt = config.commit.custom_prompt.clone();
-    let scope_info = super::commit::compute_scope_info_pub(&stats.files_changed, config);
+    let scope_info = super::commit::compute_scope_info_pub(repo, &stats.files_changed, config);
+    let history_examples = super::commit::compute_history_examples(repo, config);
 
     match generate_groups(
         provider,
@@ -721,12 +1044,15 @@ async fn handle_split_json_mode(
         &branch_name,
         &custom_prompt,
         &scope_info,
+        &history_examples,
         false,
         0,
+        reporter,
     )
     .await
     {
-        Ok(groups) => {
+        Ok((mut groups, llm_metrics)) => {
+            apply_group_trailers(&mut groups, &trailers);
             let output = JsonOutput {
                 success: true,
                 data: Some(SplitCommitData {
@@ -735,6 +1061,7 @@ async fn handle_split_json_mode(
                     committed: false,
                 }),
                 error: None,
+                meta: Some(llm_metrics),
             };
             println!("{}", serde_json::to_string_pretty(&output)?);
             Ok(())
@@ -751,6 +1078,7 @@ async fn handle_split_json_mode(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::SilentReporter;
 
     #[test]
     fn test_strip_code_fences_json() {
@@ -829,12 +1157,14 @@ mod tests {
             },
         ];
 
-        let editor_text = format_groups_for_editor(&groups);
         let expected_files = vec![
             "src/main.rs".to_string(),
             "src/lib.rs".to_string(),
             "tests/test.rs".to_string(),
         ];
+        let editor_text = format_groups_for_editor(&groups, &expected_files);
+        // 暂存文件列表作为注释写入，解析时被忽略
+        assert!(editor_text.contains("#   tests/test.rs\n"));
         let parsed = parse_groups_from_editor(&editor_text, &expected_files).unwrap();
 
         assert_eq!(parsed.len(), 2);
@@ -851,10 +1181,10 @@ mod tests {
             message: "feat(auth): add login\n\nDetailed description.\n- bullet point".to_string(),
         }];
 
-        let editor_text = format_groups_for_editor(&groups);
+        let expected_files = vec!["src/auth.rs".to_string()];
+        let editor_text = format_groups_for_editor(&groups, &expected_files);
         assert!(editor_text.contains("\"\"\""));
 
-        let expected_files = vec!["src/auth.rs".to_string()];
         let parsed = parse_groups_from_editor(&editor_text, &expected_files).unwrap();
 
         assert_eq!(parsed.len(), 1);
@@ -908,4 +1238,173 @@ mod tests {
         let expected = vec!["a.rs".to_string(), "b.rs".to_string()];
         assert!(parse_split_response(raw, &expected).is_ok());
     }
+
+    #[test]
+    fn test_paths_to_stage_includes_rename_source() {
+        let file_diffs = vec![
+            FileDiff {
+                filename: "src/new.rs".to_string(),
+                old_filename: Some("old.rs".to_string()),
+                content: String::new(),
+                insertions: 0,
+                deletions: 0,
+            },
+            FileDiff {
+                filename: "b.rs".to_string(),
+                old_filename: None,
+                content: String::new(),
+                insertions: 1,
+                deletions: 0,
+            },
+        ];
+        let files = vec!["src/new.rs".to_string(), "b.rs".to_string()];
+        assert_eq!(
+            paths_to_stage(&files, &file_diffs),
+            vec!["src/new.rs", "old.rs", "b.rs"]
+        );
+    }
+
+    fn file_diff(name: &str) -> FileDiff {
+        FileDiff {
+            filename: name.to_string(),
+            old_filename: None,
+            content: String::new(),
+            insertions: 1,
+            deletions: 0,
+        }
+    }
+
+    #[test]
+    fn test_parse_editor_rejects_unstaged_file() {
+        let text = "[[groups]]\nfiles = [\"a.rs\", \"notes.txt\"]\nmessage = \"feat: a\"\n";
+        let err = parse_groups_from_editor(text, &["a.rs".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("not in staging area"));
+    }
+
+    #[test]
+    fn test_validate_plan_returns_unassigned_files() {
+        let groups = vec![CommitGroup {
+            files: vec!["a.rs".to_string()],
+            message: "feat: a".to_string(),
+        }];
+        let staged = vec!["a.rs".to_string(), "b.rs".to_string()];
+        assert_eq!(validate_plan(&groups, &staged).unwrap(), vec!["b.rs"]);
+    }
+
+    #[test]
+    fn test_execute_restages_unassigned_files() {
+        use crate::git::MockGitOperations;
+        use mockall::Sequence;
+
+        let groups = vec![CommitGroup {
+            files: vec!["a.rs".to_string()],
+            message: "feat: a".to_string(),
+        }];
+        let file_diffs = vec![file_diff("a.rs"), file_diff("b.rs")];
+
+        let mut seq = Sequence::new();
+        let mut repo = MockGitOperations::new();
+        repo.expect_unstage_all()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(()));
+        repo.expect_stage_files()
+            .withf(|files| files == ["a.rs"])
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+        repo.expect_commit()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+        // 未分组的文件在提交后重新暂存
+        repo.expect_stage_files()
+            .withf(|files| files == ["b.rs"])
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+
+        execute_split_commits(&repo, &groups, &file_diffs, &[], None, &SilentReporter).unwrap();
+    }
+
+    #[test]
+    fn test_execute_rejects_invalid_plan_before_unstaging() {
+        use crate::git::MockGitOperations;
+
+        let groups = vec![
+            CommitGroup {
+                files: vec!["a.rs".to_string()],
+                message: "feat: a".to_string(),
+            },
+            CommitGroup {
+                files: vec!["a.rs".to_string()],
+                message: "fix: a".to_string(),
+            },
+        ];
+        let mut repo = MockGitOperations::new();
+        repo.expect_unstage_all().never();
+
+        let err = execute_split_commits(
+            &repo,
+            &groups,
+            &[file_diff("a.rs")],
+            &[],
+            None,
+            &SilentReporter,
+        )
+        .unwrap_err();
+        assert!(matches!(err, GcopError::SplitParseFailed(_)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_verify_failure_restages_remaining() {
+        use crate::git::MockGitOperations;
+
+        let groups = vec![
+            CommitGroup {
+                files: vec!["a.rs".to_string()],
+                message: "feat: a".to_string(),
+            },
+            CommitGroup {
+                files: vec!["b.rs".to_string()],
+                message: "feat: b".to_string(),
+            },
+        ];
+        let file_diffs = vec![file_diff("a.rs"), file_diff("b.rs")];
+
+        let mut repo = MockGitOperations::new();
+        repo.expect_unstage_all().returning(|| Ok(()));
+        repo.expect_checkout_index().returning(|_| Ok(()));
+        repo.expect_stage_files()
+            .withf(|files| files == ["a.rs"])
+            .times(1)
+            .returning(|_| Ok(()));
+        // 验证失败：不提交，所有剩余文件重新暂存
+        repo.expect_commit().never();
+        repo.expect_stage_files()
+            .withf(|files| files == ["a.rs", "b.rs"])
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let err = execute_split_commits(
+            &repo,
+            &groups,
+            &file_diffs,
+            &[],
+            Some("exit 3"),
+            &SilentReporter,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            GcopError::SplitVerifyFailed {
+                group: 1,
+                total: 2,
+                ..
+            }
+        ));
+        // 非交互时不重新规划，直接返回错误
+        assert!(replan_on_verify_failure(Err(err), false, &SilentReporter).is_err());
+    }
 }
@@ -0,0 +1,299 @@
+//! Dependency-aware ordering of split commit groups.
+//!
+//! A group depends on another when its added lines use a symbol (function,
+//! type, constant, ...) that only the other group's added lines define.
+//! Groups are reordered so definitions are committed before their uses, and
+//! groups that depend on each other are merged, so every intermediate commit
+//! has what it needs as far as this heuristic can tell. The LLM order is kept
+//! wherever no dependency requires a change.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::split::CommitGroup;
+use crate::git::diff::FileDiff;
+
+/// Definitions across common languages: Rust, Python, JavaScript/TypeScript,
+/// Go (including method receivers), Java/C#/Kotlin classes and interfaces.
+static DEFINITION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b(?:fn|struct|enum|trait|type|const|static|union|class|interface|def|function|macro_rules!|func(?:\s*\([^)]*\))?)\s+([A-Za-z_][A-Za-z0-9_]*)",
+    )
+    .expect("valid definition regex")
+});
+
+static IDENTIFIER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").expect("valid identifier regex"));
+
+/// Names too generic to link two groups (constructors, entry points, ...).
+const GENERIC_NAMES: &[&str] = &[
+    "new", "default", "main", "init", "run", "test", "from", "into", "get", "set", "build",
+    "update", "render", "Error", "Result", "Config", "Options",
+];
+
+/// Result of [`order_groups`].
+#[derive(Debug)]
+pub struct OrderedGroups {
+    /// Groups in commit order.
+    pub groups: Vec<CommitGroup>,
+    /// Whether the order differs from the input order.
+    pub reordered: bool,
+    /// Number of groups folded into another group.
+    pub merged: usize,
+}
+
+/// Order `groups` so that no group uses a symbol introduced by a later one.
+///
+/// Mutually dependent groups are merged: files keep their order and the
+/// messages are joined, the first one providing the subject.
+pub fn order_groups(groups: Vec<CommitGroup>, file_diffs: &[FileDiff]) -> OrderedGroups {
+    let deps = dependencies(&groups, file_diffs);
+    let n = groups.len();
+
+    // Transitive closure; groups reaching each other form one component
+    let mut reach: Vec<Vec<bool>> = (0..n)
+        .map(|g| (0..n).map(|h| deps[g].contains(&h)).collect())
+        .collect();
+    for k in 0..n {
+        let via_k = reach[k].clone();
+        for row in reach.iter_mut().filter(|row| row[k]) {
+            for (cell, &via) in row.iter_mut().zip(&via_k) {
+                *cell |= via;
+            }
+        }
+    }
+    let component: Vec<usize> = (0..n)
+        .map(|g| {
+            (0..n)
+                .find(|&h| h == g || (reach[g][h] && reach[h][g]))
+                .unwrap_or(g)
+        })
+        .collect();
+
+    // Each component goes right after the components it depends on, so
+    // groups move only when a dependency requires it
+    let mut emitted = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    for g in 0..n {
+        emit(component[g], &component, &deps, &mut emitted, &mut order);
+    }
+
+    let reordered = order.windows(2).any(|pair| pair[0] > pair[1]);
+    let mut slots: Vec<Option<CommitGroup>> = groups.into_iter().map(Some).collect();
+    let mut ordered: Vec<CommitGroup> = Vec::new();
+    let mut merged = 0;
+    let mut last_component = None;
+    for g in order {
+        let group = slots[g].take().expect("each group is emitted once");
+        match ordered.last_mut() {
+            Some(target) if last_component == Some(component[g]) => {
+                target.files.extend(group.files);
+                target.message = format!("{}\n\n{}", target.message.trim_end(), group.message);
+                merged += 1;
+            }
+            _ => ordered.push(group),
+        }
+        last_component = Some(component[g]);
+    }
+
+    OrderedGroups {
+        groups: ordered,
+        reordered,
+        merged,
+    }
+}
+
+/// Append component `c` to `order` after the components it depends on.
+fn emit(
+    c: usize,
+    component: &[usize],
+    deps: &[HashSet<usize>],
+    emitted: &mut [bool],
+    order: &mut Vec<usize>,
+) {
+    if emitted[c] {
+        return;
+    }
+    // Components form a DAG, so marking first cannot hide a dependency
+    emitted[c] = true;
+    let members: Vec<usize> = (0..component.len())
+        .filter(|&g| component[g] == c)
+        .collect();
+    let mut required: Vec<usize> = members
+        .iter()
+        .flat_map(|&g| deps[g].iter().map(|&h| component[h]))
+        .filter(|&d| d != c)
+        .collect();
+    required.sort_unstable();
+    required.dedup();
+    for d in required {
+        emit(d, component, deps, emitted, order);
+    }
+    order.extend(members);
+}
+
+/// For each group, the groups whose new symbols it uses.
+fn dependencies(groups: &[CommitGroup], file_diffs: &[FileDiff]) -> Vec<HashSet<usize>> {
+    let group_of: HashMap<&str, usize> = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(i, group)| group.files.iter().map(move |file| (file.as_str(), i)))
+        .collect();
+
+    let mut added: Vec<Vec<&str>> = vec![Vec::new(); groups.len()];
+    let mut removed_defs = HashSet::new();
+    for file_diff in file_diffs {
+        let group = group_of.get(file_diff.filename.as_str()).copied();
+        for line in file_diff.content.lines() {
+            if let Some(code) = line.strip_prefix('+').filter(|_| !line.starts_with("+++")) {
+                if let Some(group) = group {
+                    added[group].push(code);
+                }
+            } else if let Some(code) = line.strip_prefix('-').filter(|_| !line.starts_with("---")) {
+                removed_defs.extend(definitions(code));
+            }
+        }
+    }
+
+    // Symbols introduced by exactly one group (and not merely moved or changed)
+    let mut introduced: HashMap<&str, Option<usize>> = HashMap::new();
+    for (group, lines) in added.iter().enumerate() {
+        for symbol in lines.iter().flat_map(|line| definitions(line)) {
+            if removed_defs.contains(symbol) || is_generic(symbol) {
+                continue;
+            }
+            introduced
+                .entry(symbol)
+                .and_modify(|owner| {
+                    if *owner != Some(group) {
+                        *owner = None;
+                    }
+                })
+                .or_insert(Some(group));
+        }
+    }
+
+    added
+        .iter()
+        .enumerate()
+        .map(|(group, lines)| {
+            lines
+                .iter()
+                .flat_map(|line| IDENTIFIER.find_iter(line))
+                .filter_map(|token| introduced.get(token.as_str()).copied().flatten())
+                .filter(|&owner| owner != group)
+                .collect()
+        })
+        .collect()
+}
+
+fn definitions(code: &str) -> impl Iterator<Item = &str> {
+    DEFINITION
+        .captures_iter(code)
+        .filter_map(|caps| caps.get(1).map(|m| m.as_str()))
+}
+
+fn is_generic(symbol: &str) -> bool {
+    symbol.len() < 3 || GENERIC_NAMES.contains(&symbol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, diff: &str) -> FileDiff {
+        FileDiff {
+            filename: name.to_string(),
+            old_filename: None,
+            content: format!("diff --git a/{name} b/{name}\n--- a/{name}\n+++ b/{name}\n{diff}"),
+            insertions: 0,
+            deletions: 0,
+        }
+    }
+
+    fn group(files: &[&str], message: &str) -> CommitGroup {
+        CommitGroup {
+            files: files.iter().map(|f| f.to_string()).collect(),
+            message: message.to_string(),
+        }
+    }
+
+    fn files(groups: &[CommitGroup]) -> Vec<Vec<&str>> {
+        groups
+            .iter()
+            .map(|g| g.files.iter().map(String::as_str).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_definition_moves_before_use() {
+        let diffs = vec![
+            file("src/cli.rs", "+    let token = parse_token(input)?;\n"),
+            file(
+                "src/auth.rs",
+                "+pub fn parse_token(s: &str) -> Result<Token> {\n",
+            ),
+            file("README.md", "+Docs\n"),
+        ];
+        let groups = vec![
+            group(&["src/cli.rs"], "feat(cli): read tokens"),
+            group(&["README.md"], "docs: update readme"),
+            group(&["src/auth.rs"], "feat(auth): add token parser"),
+        ];
+
+        let ordered = order_groups(groups, &diffs);
+        assert!(ordered.reordered);
+        assert_eq!(ordered.merged, 0);
+        // 只移动被依赖的分组，其余保持原顺序
+        assert_eq!(
+            files(&ordered.groups),
+            vec![vec!["src/auth.rs"], vec!["src/cli.rs"], vec!["README.md"]]
+        );
+    }
+
+    #[test]
+    fn test_mutual_dependency_merges_groups() {
+        let diffs = vec![
+            file("a.py", "+def load_user():\n+    return check_user()\n"),
+            file("b.py", "+def check_user():\n+    load_user()\n"),
+        ];
+        let groups = vec![
+            group(&["a.py"], "feat: add loader"),
+            group(&["b.py"], "feat: add checker"),
+        ];
+
+        let ordered = order_groups(groups, &diffs);
+        assert_eq!(ordered.merged, 1);
+        assert_eq!(files(&ordered.groups), vec![vec!["a.py", "b.py"]]);
+        assert_eq!(
+            ordered.groups[0].message,
+            "feat: add loader\n\nfeat: add checker"
+        );
+    }
+
+    #[test]
+    fn test_existing_and_generic_symbols_are_ignored() {
+        let diffs = vec![
+            // 定义只是被修改（删除行中也有），不是新符号
+            file(
+                "a.rs",
+                "-fn render_page() {}\n+fn render_page(ctx: &Ctx) {}\n+fn new() {}\n",
+            ),
+            file("b.rs", "+    render_page(&ctx);\n+    Foo::new();\n"),
+        ];
+        let groups = vec![group(&["b.rs"], "fix: b"), group(&["a.rs"], "refactor: a")];
+
+        let ordered = order_groups(groups, &diffs);
+        assert!(!ordered.reordered);
+        assert_eq!(files(&ordered.groups), vec![vec!["b.rs"], vec!["a.rs"]]);
+    }
+
+    #[test]
+    fn test_go_method_definition() {
+        let symbols: Vec<&str> =
+            definitions("func (s *Server) HandleLogin(w http.ResponseWriter) {").collect();
+        assert_eq!(symbols, vec!["HandleLogin"]);
+    }
+}
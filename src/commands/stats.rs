@@ -1,16 +1,21 @@
 use std::collections::{BTreeMap, HashMap};
 
-use chrono::{DateTime, Datelike, Duration, IsoWeek, Local, NaiveDate};
+use std::path::Path;
+
+use chrono::{DateTime, Datelike, Duration, IsoWeek, Local, NaiveDate, NaiveTime};
 use serde::Serialize;
+use std::fmt::Write;
 
 use colored::Colorize;
 
 use super::format::OutputFormat;
 use super::options::StatsOptions;
 use crate::commands::json::{self, JsonOutput};
-use crate::error::Result;
-use crate::git::{CommitInfo, GitOperations, repository::GitRepository};
-use crate::ui;
+use crate::config::{AppConfig, expand_home};
+use crate::convention::ConventionalMessage;
+use crate::error::{GcopError, Result};
+use crate::git::{CommitInfo, GitOperations, HistoryOptions, repository::GitRepository};
+use crate::ui::{self, Reporter};
 
 /// Author statistics
 #[derive(Debug, Clone, Serialize)]
@@ -23,6 +28,132 @@ pub struct AuthorStats {
     pub commits: usize,
 }
 
+/// Commit count for one Conventional Commits type
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeStats {
+    /// Commit type, lowercased (`feat`, `fix`, ...).
+    pub commit_type: String,
+    /// Number of commits with this type.
+    pub commits: usize,
+}
+
+/// Commit count for one Conventional Commits scope
+#[derive(Debug, Clone, Serialize)]
+pub struct ScopeStats {
+    /// Scope as written in the header.
+    pub scope: String,
+    /// Number of commits with this scope.
+    pub commits: usize,
+}
+
+/// Commit count for one repository of a multi-repository report
+#[derive(Debug, Clone, Serialize)]
+pub struct RepositoryStats {
+    /// Repository name (working directory name)
+    pub name: String,
+    /// Repository working directory
+    pub path: String,
+    /// Number of commits in scope
+    pub commits: usize,
+}
+
+/// Author identities merged into one canonical identity
+/// (`[stats.authors]` and `stats --merge-authors`)
+#[derive(Debug, Clone, Default)]
+pub struct AuthorAliases {
+    /// Lowercase alias (name or email) → canonical name and, when known, email
+    identities: HashMap<String, (String, Option<String>)>,
+}
+
+impl AuthorAliases {
+    /// Builds the alias table from `alias = "Name <email>"` (or bare name)
+    /// entries; malformed identities are skipped (config validation reports them).
+    pub fn new(aliases: &BTreeMap<String, String>) -> Self {
+        let mut table = Self::default();
+        for (alias, identity) in aliases {
+            table.insert(alias, identity);
+        }
+        table
+    }
+
+    /// Adds `--merge-authors` overrides (`Old=New`), which take precedence
+    /// over `[stats.authors]`.
+    pub fn with_overrides(mut self, overrides: &[String]) -> Result<Self> {
+        for entry in overrides {
+            let inserted = entry
+                .split_once('=')
+                .is_some_and(|(alias, identity)| self.insert(alias, identity));
+            if !inserted {
+                return Err(GcopError::InvalidInput(
+                    rust_i18n::t!("stats.invalid_merge_authors", value = entry).to_string(),
+                ));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Adds one alias; `identity` is `Name <email>` or a bare name.
+    fn insert(&mut self, alias: &str, identity: &str) -> bool {
+        let alias = alias.trim();
+        let identity = identity.trim();
+        let canonical = match crate::config::split_co_author(identity) {
+            Some((Some(name), email)) => (name.to_string(), Some(email.to_string())),
+            Some((None, _)) => return false,
+            None if crate::config::is_bare_author_name(identity) => (identity.to_string(), None),
+            None => return false,
+        };
+        if alias.is_empty() {
+            return false;
+        }
+        self.identities.insert(alias.to_lowercase(), canonical);
+        true
+    }
+
+    /// Gives name-only identities the email their name uses most often in
+    /// `commits`, so every alias folds into the same `Name <email>` author.
+    pub fn complete_emails(&mut self, commits: &[CommitInfo]) {
+        for (name, email) in self.identities.values_mut() {
+            if email.is_some() {
+                continue;
+            }
+            let mut counts: HashMap<&str, usize> = HashMap::new();
+            for commit in commits.iter().filter(|c| c.author_name == *name) {
+                *counts.entry(commit.author_email.as_str()).or_insert(0) += 1;
+            }
+            *email = counts
+                .into_iter()
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+                .map(|(email, _)| email.to_string());
+        }
+    }
+
+    /// Canonical identity of an author: matched by email first, then by name.
+    /// A name-only identity keeps the author's email.
+    pub fn resolve<'a>(&'a self, name: &'a str, email: &'a str) -> (&'a str, &'a str) {
+        match self
+            .identities
+            .get(&email.to_lowercase())
+            .or_else(|| self.identities.get(&name.to_lowercase()))
+        {
+            Some((canonical_name, canonical_email)) => (
+                canonical_name.as_str(),
+                canonical_email.as_deref().unwrap_or(email),
+            ),
+            None => (name, email),
+        }
+    }
+
+    /// Rewrites the author of `commit` to its canonical identity.
+    fn apply(&self, commit: &mut CommitInfo) {
+        let (name, email) = self.resolve(&commit.author_name, &commit.author_email);
+        if (name, email) != (commit.author_name.as_str(), commit.author_email.as_str()) {
+            let (name, email) = (name.to_string(), email.to_string());
+            commit.author_name = name;
+            commit.author_email = email;
+        }
+    }
+}
+
 /// Per-author line-level contribution statistics
 #[derive(Debug, Clone, Serialize)]
 pub struct AuthorContribStats {
@@ -55,6 +186,67 @@ pub struct ContribStats {
     pub authors: Vec<AuthorContribStats>,
 }
 
+/// Commits by one author in one ISO week
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorWeekStats {
+    /// ISO week key (`YYYY-Www`)
+    pub week: String,
+    /// Author display name
+    pub name: String,
+    /// Author email
+    pub email: String,
+    /// Number of commits in this week
+    pub commits: usize,
+}
+
+/// Commit counts for one file extension
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtensionStats {
+    /// Lowercase extension without the dot (`"(none)"` for files without one)
+    pub extension: String,
+    /// Number of commits touching at least one file with this extension
+    pub commits: usize,
+}
+
+/// Line-level statistics for one language
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageStats {
+    /// Language name derived from file extensions (`"Other"` when unknown)
+    pub language: String,
+    /// Number of commits touching at least one file in this language
+    pub commits: usize,
+    /// Lines inserted
+    pub insertions: usize,
+    /// Lines deleted
+    pub deletions: usize,
+}
+
+/// Churn statistics for one file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileChurnStats {
+    /// Repository-relative file path
+    pub path: String,
+    /// Number of commits touching this file
+    pub commits: usize,
+    /// Lines inserted
+    pub insertions: usize,
+    /// Lines deleted
+    pub deletions: usize,
+    /// Total lines changed (insertions + deletions)
+    pub churn: usize,
+}
+
+/// File and language statistics computed from commit diffs
+#[derive(Debug, Clone, Serialize)]
+pub struct DetailedStats {
+    /// Per-extension commit counts (sorted by commits descending)
+    pub extensions: Vec<ExtensionStats>,
+    /// Per-language line statistics (sorted by lines changed descending)
+    pub languages: Vec<LanguageStats>,
+    /// Most frequently changed files (sorted by churn descending, at most `HOT_FILES_LIMIT`)
+    pub hot_files: Vec<FileChurnStats>,
+}
+
 /// Repository statistics
 #[derive(Debug, Clone, Serialize)]
 pub struct RepoStats {
@@ -68,6 +260,12 @@ pub struct RepoStats {
     pub last_commit_date: Option<DateTime<Local>>,
     /// Per-author contribution statistics.
     pub authors: Vec<AuthorStats>,
+    /// Conventional commits per type (sorted by commits descending).
+    pub commit_types: Vec<TypeStats>,
+    /// Conventional commits per scope (sorted by commits descending).
+    pub commit_scopes: Vec<ScopeStats>,
+    /// Percentage of non-merge commits following Conventional Commits.
+    pub convention_compliance: f64,
     /// Commits grouped by ISO week key (`YYYY-Www`).
     pub commits_by_week: BTreeMap<String, usize>,
     /// Commits grouped by date (`YYYY-MM-DD`) for recent activity.
@@ -76,27 +274,31 @@ pub struct RepoStats {
     pub current_streak: usize,
     /// Longest historical consecutive-day commit streak.
     pub longest_streak: usize,
+    /// Commits per repository (only for `--repos` reports)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repositories: Vec<RepositoryStats>,
     /// Line-level contribution statistics (optional, enabled with --contrib flag)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contrib: Option<ContribStats>,
+    /// File and language statistics (optional, enabled with --detailed flag)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed: Option<DetailedStats>,
+    /// Commits per author per ISO week over the whole scope (CSV export)
+    #[serde(skip)]
+    pub author_weeks: Vec<AuthorWeekStats>,
+    /// Commits per calendar day over the whole scope (HTML heatmap)
+    #[serde(skip)]
+    pub commits_by_date: BTreeMap<NaiveDate, usize>,
 }
 
 impl RepoStats {
     /// Calculate statistics from commit history
     pub fn from_commits(commits: &[CommitInfo], author_filter: Option<&str>) -> Self {
         // Filter commits
-        let filtered: Vec<&CommitInfo> = if let Some(filter) = author_filter {
-            let filter_lower = filter.to_lowercase();
-            commits
-                .iter()
-                .filter(|c| {
-                    c.author_name.to_lowercase().contains(&filter_lower)
-                        || c.author_email.to_lowercase().contains(&filter_lower)
-                })
-                .collect()
-        } else {
-            commits.iter().collect()
-        };
+        let filtered: Vec<&CommitInfo> = commits
+            .iter()
+            .filter(|c| author_matches(&c.author_name, &c.author_email, author_filter))
+            .collect();
 
         // basic statistics
         let total_commits = filtered.len();
@@ -120,9 +322,64 @@ impl RepoStats {
         }
 
         let mut authors: Vec<AuthorStats> = author_map.into_values().collect();
-        authors.sort_by(|a, b| b.commits.cmp(&a.commits));
+        authors.sort_by_key(|a| std::cmp::Reverse(a.commits));
         let total_authors = authors.len();
 
+        // Conventional commit types and scopes (merge commits are not counted)
+        let mut type_map: HashMap<String, usize> = HashMap::new();
+        let mut scope_map: HashMap<String, usize> = HashMap::new();
+        let mut conventional = 0usize;
+        let mut non_merge = 0usize;
+        for commit in filtered.iter().filter(|c| c.parent_count <= 1) {
+            non_merge += 1;
+            let Some(parsed) = ConventionalMessage::parse(&commit.message) else {
+                continue;
+            };
+            conventional += 1;
+            *type_map
+                .entry(parsed.commit_type.to_lowercase())
+                .or_insert(0) += 1;
+            if let Some(scope) = parsed.scope {
+                *scope_map.entry(scope).or_insert(0) += 1;
+            }
+        }
+        let commit_types: Vec<TypeStats> = sorted_counts(type_map)
+            .into_iter()
+            .map(|(commit_type, commits)| TypeStats {
+                commit_type,
+                commits,
+            })
+            .collect();
+        let commit_scopes: Vec<ScopeStats> = sorted_counts(scope_map)
+            .into_iter()
+            .map(|(scope, commits)| ScopeStats { scope, commits })
+            .collect();
+        let convention_compliance = if non_merge > 0 {
+            (conventional as f64 / non_merge as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        // Per-author weekly counts over the whole scope
+        let mut author_week_map: BTreeMap<(String, String, String), usize> = BTreeMap::new();
+        for commit in &filtered {
+            let key = (
+                format_week(&commit.timestamp),
+                commit.author_name.clone(),
+                commit.author_email.clone(),
+            );
+            *author_week_map.entry(key).or_insert(0) += 1;
+        }
+        let author_weeks: Vec<AuthorWeekStats> = author_week_map
+            .into_iter()
+            .map(|((week, name, email), commits)| AuthorWeekStats {
+                week,
+                name,
+                email,
+                commits,
+            })
+            .collect();
+
         // Statistics for the last 4 weeks
         let now = Local::now();
         let four_weeks_ago = now - Duration::days(28);
@@ -157,6 +414,8 @@ impl RepoStats {
         let mut all_commit_dates: std::collections::BTreeSet<NaiveDate> =
             std::collections::BTreeSet::new();
 
+        let mut commits_by_date: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+
         for commit in &filtered {
             let date = commit.timestamp.date_naive();
             let date_key = date.format("%Y-%m-%d").to_string();
@@ -164,6 +423,7 @@ impl RepoStats {
             if let Some(count) = commits_by_day.get_mut(&date_key) {
                 *count += 1;
             }
+            *commits_by_date.entry(date).or_insert(0) += 1;
             all_commit_dates.insert(date);
         }
 
@@ -212,11 +472,18 @@ impl RepoStats {
             first_commit_date,
             last_commit_date,
             authors,
+            commit_types,
+            commit_scopes,
+            convention_compliance,
             commits_by_week,
             commits_by_day,
             current_streak,
             longest_streak,
+            repositories: Vec::new(),
             contrib: None,
+            detailed: None,
+            author_weeks,
+            commits_by_date,
         }
     }
 
@@ -227,6 +494,19 @@ impl RepoStats {
             _ => None,
         }
     }
+
+    /// Widen `commits_by_day` to the `days` days ending at `today` (`--calendar`)
+    pub fn extend_commits_by_day(&mut self, days: i64, today: NaiveDate) {
+        self.commits_by_day = (0..days)
+            .map(|i| {
+                let date = today - Duration::days(i);
+                (
+                    date.format("%Y-%m-%d").to_string(),
+                    self.commits_by_date.get(&date).copied().unwrap_or(0),
+                )
+            })
+            .collect();
+    }
 }
 
 /// Compute per-author line-level contribution statistics.
@@ -237,6 +517,7 @@ pub fn compute_contrib_stats(
     commits: &[CommitInfo],
     git: &dyn GitOperations,
     author_filter: Option<&str>,
+    history: &HistoryOptions,
 ) -> Result<ContribStats> {
     use std::collections::HashMap;
     use std::process::Command;
@@ -248,12 +529,15 @@ pub fn compute_contrib_stats(
     // Format: hash|author_name|author_email|parent_count
     let output = Command::new("git")
         .current_dir(workdir)
+        // Non-ASCII paths unquoted, as UTF-8
+        .args(["-c", "core.quotePath=false"])
         .args([
             "log",
             "--numstat",
-            "--pretty=format:%H|%an|%ae|%P", // hash|name|email|parents
+            "--pretty=format:%H|%aN|%aE|%P", // hash|name|email|parents (mailmap applied)
             "--no-merges",                   // Skip merge commits
         ])
+        .args(history_log_args(history))
         .output()
         .map_err(|e| crate::error::GcopError::GitCommand(format!("git log failed: {}", e)))?;
 
@@ -318,14 +602,27 @@ pub fn compute_contrib_stats(
         }
     }
 
+    // Count merge commits from original commit list
+    let merge_skipped = commits.iter().filter(|c| c.parent_count > 1).count();
+
+    Ok(contrib_from_authors(
+        author_map,
+        author_filter,
+        merge_skipped,
+    ))
+}
+
+/// Per-author totals: `Name <email>` → (name, email, insertions, deletions)
+type ContribTotals = HashMap<String, (String, String, usize, usize)>;
+
+/// Build contribution statistics from per-author totals, applying the author filter
+fn contrib_from_authors(
+    mut author_map: ContribTotals,
+    author_filter: Option<&str>,
+    merge_commits_skipped: usize,
+) -> ContribStats {
     // Apply author filter if specified
-    if let Some(filter) = author_filter {
-        let filter_lower = filter.to_lowercase();
-        author_map.retain(|_, (name, email, _, _)| {
-            name.to_lowercase().contains(&filter_lower)
-                || email.to_lowercase().contains(&filter_lower)
-        });
-    }
+    author_map.retain(|_, (name, email, _, _)| author_matches(name, email, author_filter));
 
     let total_ins: usize = author_map.values().map(|v| v.2).sum();
     let total_del: usize = author_map.values().map(|v| v.3).sum();
@@ -359,18 +656,427 @@ pub fn compute_contrib_stats(
             .then_with(|| a.email.cmp(&b.email))
     });
 
-    // Count merge commits from original commit list
-    let merge_skipped = commits.iter().filter(|c| c.parent_count > 1).count();
-
-    Ok(ContribStats {
+    ContribStats {
         total_insertions: total_ins,
         total_deletions: total_del,
         total_lines,
-        merge_commits_skipped: merge_skipped,
+        merge_commits_skipped,
         authors,
+    }
+}
+
+/// Merge contribution statistics of several repositories (unfiltered), folding
+/// aliased authors into their canonical identity before applying the author filter.
+pub fn merge_contrib_stats(
+    parts: Vec<ContribStats>,
+    aliases: &AuthorAliases,
+    author_filter: Option<&str>,
+) -> ContribStats {
+    let mut author_map: ContribTotals = HashMap::new();
+    let mut merge_skipped = 0;
+    for part in parts {
+        merge_skipped += part.merge_commits_skipped;
+        for author in part.authors {
+            let (name, email) = aliases.resolve(&author.name, &author.email);
+            let entry = author_map
+                .entry(format!("{} <{}>", name, email))
+                .or_insert_with(|| (name.to_string(), email.to_string(), 0, 0));
+            entry.2 += author.insertions;
+            entry.3 += author.deletions;
+        }
+    }
+    contrib_from_authors(author_map, author_filter, merge_skipped)
+}
+
+/// Maximum number of hot files reported by `--detailed`
+pub const HOT_FILES_LIMIT: usize = 10;
+
+/// Compute file, extension and language statistics from commit diffs.
+///
+/// Like [`compute_contrib_stats`], uses one `git log --numstat` pass (merge
+/// commits and rename detection disabled) scoped by `history`.
+pub fn compute_detailed_stats(
+    git: &dyn GitOperations,
+    author_filter: Option<&str>,
+    history: &HistoryOptions,
+) -> Result<DetailedStats> {
+    use std::process::Command;
+
+    let workdir = git.get_workdir()?;
+
+    let output = Command::new("git")
+        .current_dir(workdir)
+        // Non-ASCII paths unquoted, as UTF-8
+        .args(["-c", "core.quotePath=false"])
+        .args([
+            "log",
+            "--numstat",
+            "--no-renames",
+            "--pretty=format:%H|%aN|%aE",
+            "--no-merges",
+        ])
+        .args(history_log_args(history))
+        .output()
+        .map_err(|e| GcopError::GitCommand(format!("git log failed: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("does not have any commits yet")
+            || stderr.contains("bad default revision")
+        {
+            return Ok(aggregate_numstat("", author_filter));
+        }
+        return Err(GcopError::GitCommand(format!(
+            "git log failed: {}",
+            stderr.trim()
+        )));
+    }
+
+    Ok(aggregate_numstat(
+        &String::from_utf8_lossy(&output.stdout),
+        author_filter,
+    ))
+}
+
+/// Aggregate `git log --numstat --pretty=format:%H|%an|%ae` output
+fn aggregate_numstat(log: &str, author_filter: Option<&str>) -> DetailedStats {
+    use std::collections::HashSet;
+
+    #[derive(Default)]
+    struct Acc {
+        commits: usize,
+        insertions: usize,
+        deletions: usize,
+    }
+
+    let filter_lower = author_filter.map(str::to_lowercase);
+    let mut extensions: HashMap<String, usize> = HashMap::new();
+    let mut languages: HashMap<&'static str, Acc> = HashMap::new();
+    let mut files: HashMap<String, Acc> = HashMap::new();
+
+    // Per-commit sets so each commit is counted once per extension/language
+    let mut commit_extensions: HashSet<String> = HashSet::new();
+    let mut commit_languages: HashSet<&'static str> = HashSet::new();
+    let mut include_commit = false;
+
+    fn flush(
+        commit_extensions: &mut HashSet<String>,
+        commit_languages: &mut HashSet<&'static str>,
+        extensions: &mut HashMap<String, usize>,
+        languages: &mut HashMap<&'static str, Acc>,
+    ) {
+        for ext in commit_extensions.drain() {
+            *extensions.entry(ext).or_insert(0) += 1;
+        }
+        for lang in commit_languages.drain() {
+            languages.entry(lang).or_default().commits += 1;
+        }
+    }
+
+    for line in log.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if trimmed.len() > 40 && trimmed.chars().take(40).all(|c| c.is_ascii_hexdigit()) {
+            flush(
+                &mut commit_extensions,
+                &mut commit_languages,
+                &mut extensions,
+                &mut languages,
+            );
+            let mut parts = trimmed.splitn(3, '|').skip(1);
+            let name = parts.next().unwrap_or_default().to_lowercase();
+            let email = parts.next().unwrap_or_default().to_lowercase();
+            include_commit = filter_lower
+                .as_ref()
+                .is_none_or(|f| name.contains(f) || email.contains(f));
+            continue;
+        }
+
+        if !include_commit {
+            continue;
+        }
+        let mut parts = trimmed.splitn(3, '\t');
+        let (Some(ins), Some(del), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        // Binary files report "-" for both counts
+        let ins = ins.parse::<usize>().unwrap_or(0);
+        let del = del.parse::<usize>().unwrap_or(0);
+
+        let extension = file_extension(path);
+        let language = language_for_extension(&extension);
+
+        let lang = languages.entry(language).or_default();
+        lang.insertions += ins;
+        lang.deletions += del;
+
+        let file = files.entry(path.to_string()).or_default();
+        file.commits += 1;
+        file.insertions += ins;
+        file.deletions += del;
+
+        commit_extensions.insert(extension);
+        commit_languages.insert(language);
+    }
+    flush(
+        &mut commit_extensions,
+        &mut commit_languages,
+        &mut extensions,
+        &mut languages,
+    );
+
+    let extensions: Vec<ExtensionStats> = extensions
+        .into_iter()
+        .map(|(extension, commits)| ExtensionStats { extension, commits })
+        .collect();
+
+    let languages: Vec<LanguageStats> = languages
+        .into_iter()
+        .map(|(language, acc)| LanguageStats {
+            language: language.to_string(),
+            commits: acc.commits,
+            insertions: acc.insertions,
+            deletions: acc.deletions,
+        })
+        .collect();
+
+    let hot_files: Vec<FileChurnStats> = files
+        .into_iter()
+        .map(|(path, acc)| FileChurnStats {
+            path,
+            commits: acc.commits,
+            insertions: acc.insertions,
+            deletions: acc.deletions,
+            churn: acc.insertions + acc.deletions,
+        })
+        .collect();
+
+    DetailedStats::sorted(extensions, languages, hot_files)
+}
+
+impl DetailedStats {
+    /// Sorts each list and keeps the `HOT_FILES_LIMIT` hottest files
+    fn sorted(
+        mut extensions: Vec<ExtensionStats>,
+        mut languages: Vec<LanguageStats>,
+        mut hot_files: Vec<FileChurnStats>,
+    ) -> Self {
+        extensions.sort_by(|a, b| {
+            b.commits
+                .cmp(&a.commits)
+                .then_with(|| a.extension.cmp(&b.extension))
+        });
+        languages.sort_by(|a, b| {
+            (b.insertions + b.deletions)
+                .cmp(&(a.insertions + a.deletions))
+                .then_with(|| a.language.cmp(&b.language))
+        });
+        hot_files.sort_by(|a, b| {
+            b.churn
+                .cmp(&a.churn)
+                .then_with(|| b.commits.cmp(&a.commits))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        hot_files.truncate(HOT_FILES_LIMIT);
+
+        Self {
+            extensions,
+            languages,
+            hot_files,
+        }
+    }
+}
+
+/// Merge file statistics of several repositories; hot file paths are
+/// prefixed with the repository name.
+pub fn merge_detailed_stats(parts: Vec<(String, DetailedStats)>) -> DetailedStats {
+    let mut extensions: BTreeMap<String, usize> = BTreeMap::new();
+    let mut languages: BTreeMap<String, LanguageStats> = BTreeMap::new();
+    let mut hot_files = Vec::new();
+    for (name, part) in parts {
+        for ext in part.extensions {
+            *extensions.entry(ext.extension).or_insert(0) += ext.commits;
+        }
+        for lang in part.languages {
+            let entry = languages
+                .entry(lang.language.clone())
+                .or_insert_with(|| LanguageStats {
+                    language: lang.language,
+                    commits: 0,
+                    insertions: 0,
+                    deletions: 0,
+                });
+            entry.commits += lang.commits;
+            entry.insertions += lang.insertions;
+            entry.deletions += lang.deletions;
+        }
+        hot_files.extend(part.hot_files.into_iter().map(|file| FileChurnStats {
+            path: format!("{}/{}", name, file.path),
+            ..file
+        }));
+    }
+
+    DetailedStats::sorted(
+        extensions
+            .into_iter()
+            .map(|(extension, commits)| ExtensionStats { extension, commits })
+            .collect(),
+        languages.into_values().collect(),
+        hot_files,
+    )
+}
+
+/// Lowercase file extension without the dot, or `"(none)"`
+fn file_extension(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_else(|| "(none)".to_string())
+}
+
+/// Map a file extension to a language name
+fn language_for_extension(extension: &str) -> &'static str {
+    match extension {
+        "rs" => "Rust",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "js" | "jsx" | "mjs" | "cjs" => "JavaScript",
+        "py" | "pyi" => "Python",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "swift" => "Swift",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+        "cs" => "C#",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "scala" => "Scala",
+        "dart" => "Dart",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "ps1" => "PowerShell",
+        "sql" => "SQL",
+        "md" | "mdx" | "rst" | "txt" => "Docs",
+        "json" | "toml" | "yaml" | "yml" | "xml" | "ini" => "Config",
+        _ => "Other",
+    }
+}
+
+/// Whether an author matches the `--author` filter (case-insensitive substring of name or email)
+fn author_matches(name: &str, email: &str, filter: Option<&str>) -> bool {
+    filter.is_none_or(|filter| {
+        let filter = filter.to_lowercase();
+        name.to_lowercase().contains(&filter) || email.to_lowercase().contains(&filter)
+    })
+}
+
+/// Counts sorted by count descending, then key.
+fn sorted_counts(map: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = map.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Translate history scoping options into `git log` arguments
+fn history_log_args(history: &HistoryOptions) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(since) = history.since {
+        args.push(format!("--since={}", since.to_rfc3339()));
+    }
+    if let Some(until) = history.until {
+        args.push(format!("--until={}", until.to_rfc3339()));
+    }
+    if let Some(ref range) = history.range {
+        args.push(range.clone());
+    }
+    if let Some(ref path) = history.path {
+        args.push("--".to_string());
+        args.push(path.clone());
+    }
+    args
+}
+
+/// Parse a `--since`/`--until` value into a local timestamp.
+///
+/// Accepts `YYYY-MM-DD`, RFC 3339 timestamps and relative durations such as
+/// `14d`, `2w`, `3m` (30-day months) or `1y`. Date-only values resolve to the
+/// start of the day, or the end of the day when `end_of_day` is set.
+pub(crate) fn parse_date_bound(value: &str, end_of_day: bool) -> Result<DateTime<Local>> {
+    let value = value.trim();
+    let invalid =
+        || GcopError::InvalidInput(rust_i18n::t!("stats.invalid_date", value = value).to_string());
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Local));
+    }
+
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let time = if end_of_day {
+            NaiveTime::from_hms_opt(23, 59, 59)
+        } else {
+            NaiveTime::from_hms_opt(0, 0, 0)
+        }
+        .ok_or_else(invalid)?;
+        return date
+            .and_time(time)
+            .and_local_timezone(Local)
+            .earliest()
+            .ok_or_else(invalid);
+    }
+
+    let (amount, unit) = value.split_at(value.len().saturating_sub(1));
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let days = match unit {
+        "d" => amount,
+        "w" => amount * 7,
+        "m" => amount * 30,
+        "y" => amount * 365,
+        _ => return Err(invalid()),
+    };
+    Ok(Local::now() - Duration::days(days))
+}
+
+/// Build history scoping options from stats CLI options
+fn history_options(options: &StatsOptions<'_>, repo: &GitRepository) -> Result<HistoryOptions> {
+    Ok(HistoryOptions {
+        since: options
+            .since
+            .map(|v| parse_date_bound(v, false))
+            .transpose()?,
+        until: options
+            .until
+            .map(|v| parse_date_bound(v, true))
+            .transpose()?,
+        range: options.range.map(str::to_string),
+        path: options
+            .path
+            .map(|p| repo_relative_path(p, &repo.get_workdir()?))
+            .transpose()?
+            .filter(|p| !p.is_empty()),
     })
 }
 
+/// Resolve a user-supplied path (relative to the current directory) against the repository root
+fn repo_relative_path(path: &str, workdir: &Path) -> Result<String> {
+    let absolute = std::env::current_dir()?.join(path);
+    let absolute = absolute.canonicalize().unwrap_or(absolute);
+    let workdir = workdir
+        .canonicalize()
+        .unwrap_or_else(|_| workdir.to_path_buf());
+
+    let relative = absolute.strip_prefix(&workdir).map_err(|_| {
+        GcopError::InvalidInput(rust_i18n::t!("stats.path_outside_repo", path = path).to_string())
+    })?;
+    Ok(crate::git::to_git_path(relative))
+}
+
 /// Format week ID (e.g., "2025-W51")
 fn format_week(dt: &DateTime<Local>) -> String {
     let week: IsoWeek = dt.iso_week();
@@ -419,6 +1125,72 @@ fn render_heatmap_char(count: usize, max_count: usize, colored: bool) -> String
     }
 }
 
+/// Number of days covered by `stats --calendar`
+const CALENDAR_DAYS: i64 = 365;
+
+/// Render `commits_by_day` as a GitHub-style calendar: a month label row, then
+/// one row per weekday (Monday first) with one column per week.
+fn render_calendar_text(commits_by_day: &BTreeMap<String, usize>, colored: bool) -> Vec<String> {
+    let days: BTreeMap<NaiveDate, usize> = commits_by_day
+        .iter()
+        .filter_map(|(d, c)| Some((NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()?, *c)))
+        .collect();
+    let (Some((&first, _)), Some((&last, _))) = (days.first_key_value(), days.last_key_value())
+    else {
+        return Vec::new();
+    };
+    let max_count = days.values().copied().max().unwrap_or(0);
+    let start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+    let weeks = ((last - start).num_days() / 7 + 1) as usize;
+
+    // Month labels over the week containing the 1st; the partial first month
+    // is labelled only when its label fits before the next one
+    let mut labels: Vec<(usize, NaiveDate)> = (0..weeks)
+        .filter_map(|week| {
+            let monday = start + Duration::weeks(week as i64);
+            (0..7)
+                .map(|d| monday + Duration::days(d))
+                .find(|d| d.day() == 1 && *d >= first && *d <= last)
+                .map(|day| (week, day))
+        })
+        .collect();
+    if labels.first().is_none_or(|(week, _)| *week > 3) {
+        labels.insert(0, (0, first));
+    }
+    let mut months = vec![' '; weeks];
+    for (week, day) in labels {
+        for (i, c) in day.format("%b").to_string().chars().enumerate() {
+            if let Some(cell) = months.get_mut(week + i) {
+                *cell = c;
+            }
+        }
+    }
+
+    let mut lines = vec![format!(
+        "    {}",
+        months.iter().collect::<String>().trim_end()
+    )];
+    for (weekday, label) in ["Mon", "", "Wed", "", "Fri", "", "Sun"].iter().enumerate() {
+        let row: String = (0..weeks)
+            .map(|week| {
+                let day = start + Duration::days((week * 7 + weekday) as i64);
+                match days.get(&day) {
+                    Some(count) => render_heatmap_char(*count, max_count, colored),
+                    None => " ".to_string(),
+                }
+            })
+            .collect();
+        lines.push(format!("{:<4}{}", label, row.trim_end()));
+    }
+    lines.push(format!(
+        "{} ~ {}  peak: {}",
+        first.format("%Y-%m-%d"),
+        last.format("%Y-%m-%d"),
+        max_count
+    ));
+    lines
+}
+
 /// Render section title
 fn section_header(title: &str, colored: bool) {
     if colored {
@@ -509,72 +1281,193 @@ fn render_bar(count: usize, max_count: usize, max_width: usize, colored: bool) -
 }
 
 /// Run the stats command
-pub fn run(options: &StatsOptions<'_>, colored: bool) -> Result<()> {
-    let result = run_internal(options, colored);
+pub fn run(options: &StatsOptions<'_>, config: &AppConfig) -> Result<()> {
+    let result = run_internal(options, config);
     if let Err(ref e) = result
         && options.format.is_json()
     {
-        let _ = json::output_json_error::<RepoStats>(e);
+        let _ = json::output_error::<RepoStats>(e, options.format);
     }
     result
 }
 
-fn run_internal(options: &StatsOptions<'_>, colored: bool) -> Result<()> {
-    let repo = GitRepository::open(None)?;
-    let skip_ui = options.format.is_machine_readable();
-    let effective_colored = options.effective_colored(colored);
+/// Open the analyzed repositories: the current one, or each `--repos` path
+/// (`[stats] repos` when the flag has no value), labelled with its directory name.
+pub(crate) fn open_repositories(
+    options: &StatsOptions<'_>,
+    config: &AppConfig,
+) -> Result<Vec<(String, GitRepository)>> {
+    let Some(paths) = options.repos else {
+        return Ok(vec![(String::new(), GitRepository::open(None)?)]);
+    };
+    let paths = if paths.is_empty() {
+        &config.stats.repos[..]
+    } else {
+        paths
+    };
+    if paths.is_empty() {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("stats.no_repos").to_string(),
+        ));
+    }
 
-    let total_steps = if options.contrib { 3 } else { 2 };
+    paths
+        .iter()
+        .map(|path| {
+            let repo = GitRepository::open_at(&expand_home(path), None).map_err(|_| {
+                GcopError::InvalidInput(
+                    rust_i18n::t!("stats.repo_not_found", path = path).to_string(),
+                )
+            })?;
+            let workdir = repo.get_workdir()?;
+            let name = workdir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone());
+            Ok((name, repo))
+        })
+        .collect()
+}
 
-    if !skip_ui {
-        ui::step(
-            &format!("1/{}", total_steps),
-            &rust_i18n::t!("stats.analyzing"),
-            effective_colored,
-        );
-    }
-    let commits = repo.get_commit_history()?;
+fn run_internal(options: &StatsOptions<'_>, config: &AppConfig) -> Result<()> {
+    let repos = open_repositories(options, config)?;
+    let reporter = options.format.reporter(config.ui.colored);
+    let Some(stats) = compute(options, config, &repos, reporter.as_ref())? else {
+        return Ok(());
+    };
+    let effective_colored = options.effective_colored(config.ui.colored);
 
-    if commits.is_empty() {
-        if !skip_ui {
-            ui::warning(&rust_i18n::t!("stats.no_commits"), effective_colored);
-        }
-        return Ok(());
+    // output
+    match options.format {
+        OutputFormat::Json | OutputFormat::JsonStream => output_json(&stats, options.format)?,
+        OutputFormat::Markdown => output_markdown(&stats, effective_colored),
+        OutputFormat::Csv => print!("{}", render_csv(&stats)),
+        OutputFormat::Html => print!("{}", render_html(&stats)),
+        OutputFormat::Text
+        | OutputFormat::Annotated
+        | OutputFormat::Github
+        | OutputFormat::Junit => output_text(&stats, options.calendar, effective_colored),
     }
 
-    if !skip_ui {
-        ui::step(
-            &format!("2/{}", total_steps),
-            &rust_i18n::t!("stats.calculating"),
-            effective_colored,
-        );
+    Ok(())
+}
+
+/// Computes the statistics of `repos`; `None` when no commit is in scope.
+///
+/// Progress steps and the empty-scope warning go to `reporter`.
+pub(crate) fn compute(
+    options: &StatsOptions<'_>,
+    config: &AppConfig,
+    repos: &[(String, GitRepository)],
+    reporter: &dyn Reporter,
+) -> Result<Option<RepoStats>> {
+    let mut aliases =
+        AuthorAliases::new(&config.stats.authors).with_overrides(options.merge_authors)?;
+    let total_steps = 2 + usize::from(options.contrib) + usize::from(options.detailed);
+
+    reporter.step(
+        &format!("1/{}", total_steps),
+        &rust_i18n::t!("stats.analyzing"),
+    );
+    let mut histories = Vec::with_capacity(repos.len());
+    let mut repo_commits = Vec::with_capacity(repos.len());
+    for (_, repo) in repos {
+        let history = history_options(options, repo)?;
+        let mut commits = repo.get_commit_history(&history)?;
+        repo.apply_mailmap(&mut commits)?;
+        repo_commits.push(commits);
+        histories.push(history);
     }
+
+    let mut commits: Vec<CommitInfo> = repo_commits.iter().flatten().cloned().collect();
+    if commits.is_empty() {
+        let message = if histories.iter().all(|h| *h == HistoryOptions::default()) {
+            rust_i18n::t!("stats.no_commits")
+        } else {
+            rust_i18n::t!("stats.no_commits_in_scope")
+        };
+        reporter.warning(&message);
+        return Ok(None);
+    }
+    aliases.complete_emails(&commits);
+    for commit in &mut commits {
+        aliases.apply(commit);
+    }
+    // Newest first across repositories, as for a single history
+    commits.sort_by_key(|c| std::cmp::Reverse(c.timestamp));
+
+    reporter.step(
+        &format!("2/{}", total_steps),
+        &rust_i18n::t!("stats.calculating"),
+    );
     let mut stats = RepoStats::from_commits(&commits, options.author);
+    if options.calendar {
+        stats.extend_commits_by_day(CALENDAR_DAYS, Local::now().date_naive());
+    }
+    if options.repos.is_some() {
+        stats.repositories = repos
+            .iter()
+            .zip(&repo_commits)
+            .map(|((name, repo), commits)| {
+                Ok(RepositoryStats {
+                    name: name.clone(),
+                    path: repo
+                        .get_workdir()?
+                        .components()
+                        .collect::<std::path::PathBuf>()
+                        .display()
+                        .to_string(),
+                    commits: commits
+                        .iter()
+                        .filter(|c| {
+                            let (name, email) = aliases.resolve(&c.author_name, &c.author_email);
+                            author_matches(name, email, options.author)
+                        })
+                        .count(),
+                })
+            })
+            .collect::<Result<_>>()?;
+    }
 
+    let mut step = 2;
     if options.contrib {
-        if !skip_ui {
-            ui::step(
-                &format!("3/{}", total_steps),
-                &rust_i18n::t!("stats.contrib_calculating"),
-                effective_colored,
-            );
+        step += 1;
+        reporter.step(
+            &format!("{}/{}", step, total_steps),
+            &rust_i18n::t!("stats.contrib_calculating"),
+        );
+        let mut parts = Vec::with_capacity(repos.len());
+        for (((_, repo), commits), history) in repos.iter().zip(&repo_commits).zip(&histories) {
+            parts.push(compute_contrib_stats(commits, repo, None, history)?);
         }
-        let contrib = compute_contrib_stats(&commits, &repo, options.author)?;
-        stats.contrib = Some(contrib);
+        stats.contrib = Some(merge_contrib_stats(parts, &aliases, options.author));
     }
 
-    // output
-    match options.format {
-        OutputFormat::Json => output_json(&stats)?,
-        OutputFormat::Markdown => output_markdown(&stats, effective_colored),
-        OutputFormat::Text => output_text(&stats, effective_colored),
+    if options.detailed {
+        step += 1;
+        reporter.step(
+            &format!("{}/{}", step, total_steps),
+            &rust_i18n::t!("stats.detailed_calculating"),
+        );
+        let mut parts = Vec::with_capacity(repos.len());
+        for ((name, repo), history) in repos.iter().zip(&histories) {
+            parts.push((
+                name.clone(),
+                compute_detailed_stats(repo, options.author, history)?,
+            ));
+        }
+        stats.detailed = Some(if options.repos.is_some() {
+            merge_detailed_stats(parts)
+        } else {
+            parts.remove(0).1
+        });
     }
 
-    Ok(())
+    Ok(Some(stats))
 }
 
 /// Text format output
-fn output_text(stats: &RepoStats, colored: bool) {
+fn output_text(stats: &RepoStats, calendar: bool, colored: bool) {
     println!();
     println!("{}", ui::info(&rust_i18n::t!("stats.title"), colored));
     println!("{}", "─".repeat(40));
@@ -592,6 +1485,11 @@ fn output_text(stats: &RepoStats, colored: bool) {
         pad_display(&rust_i18n::t!("stats.contributors"), 16),
         stats.total_authors
     );
+    println!(
+        "    {} {:.1}%",
+        pad_display(&rust_i18n::t!("stats.convention_compliance"), 16),
+        stats.convention_compliance
+    );
 
     if let (Some(first), Some(last)) = (stats.first_commit_date, stats.last_commit_date) {
         let days = stats.days_span().unwrap_or(0);
@@ -605,6 +1503,21 @@ fn output_text(stats: &RepoStats, colored: bool) {
         );
     }
 
+    // Repositories (multi-repository reports)
+    if !stats.repositories.is_empty() {
+        println!();
+        section_header(&rust_i18n::t!("stats.repositories"), colored);
+        for repo in &stats.repositories {
+            println!(
+                "    {} {} {}  {}",
+                pad_display(&truncate_middle(&repo.name, 24), 24),
+                repo.commits,
+                rust_i18n::t!("stats.commits"),
+                repo.path
+            );
+        }
+    }
+
     // Top Contributors
     if !stats.authors.is_empty() {
         println!();
@@ -640,6 +1553,45 @@ fn output_text(stats: &RepoStats, colored: bool) {
         }
     }
 
+    // Commit Types
+    if !stats.commit_types.is_empty() {
+        println!();
+        section_header(&rust_i18n::t!("stats.commit_types"), colored);
+
+        let max_count = stats.commit_types[0].commits;
+        for entry in &stats.commit_types {
+            println!(
+                "    {} {} {}",
+                pad_display(&entry.commit_type, 12),
+                render_bar(entry.commits, max_count, 30, colored),
+                entry.commits
+            );
+        }
+    }
+
+    // Commit Scopes
+    if !stats.commit_scopes.is_empty() {
+        println!();
+        section_header(&rust_i18n::t!("stats.commit_scopes"), colored);
+
+        let max_count = stats.commit_scopes[0].commits;
+        for entry in stats.commit_scopes.iter().take(10) {
+            println!(
+                "    {} {} {}",
+                pad_display(&truncate_middle(&entry.scope, 20), 20),
+                render_bar(entry.commits, max_count, 30, colored),
+                entry.commits
+            );
+        }
+
+        if stats.commit_scopes.len() > 10 {
+            println!(
+                "    {}",
+                rust_i18n::t!("stats.and_more", count = stats.commit_scopes.len() - 10)
+            );
+        }
+    }
+
     // Contribution Statistics (line-level)
     if let Some(ref contrib) = stats.contrib {
         println!();
@@ -691,6 +1643,10 @@ fn output_text(stats: &RepoStats, colored: bool) {
         }
     }
 
+    if let Some(ref detailed) = stats.detailed {
+        output_detailed_text(detailed, colored);
+    }
+
     // Recent Activity (last 4 weeks)
     if !stats.commits_by_week.is_empty() {
         println!();
@@ -725,8 +1681,16 @@ fn output_text(stats: &RepoStats, colored: bool) {
         }
     }
 
+    // Commit Calendar (last 365 days) - weeks x days grid
+    if calendar {
+        println!();
+        section_header(&rust_i18n::t!("stats.commit_calendar_year"), colored);
+        for line in render_calendar_text(&stats.commits_by_day, colored) {
+            println!("    {}", line);
+        }
+    }
     // Commit Activity (last 30 days) - Horizontal heat map
-    if !stats.commits_by_day.is_empty() {
+    else if !stats.commits_by_day.is_empty() {
         println!();
         section_header(&rust_i18n::t!("stats.commit_activity"), colored);
 
@@ -781,6 +1745,116 @@ fn output_text(stats: &RepoStats, colored: bool) {
     println!();
 }
 
+/// Text output for `--detailed` file and language statistics
+fn output_detailed_text(detailed: &DetailedStats, colored: bool) {
+    const MAX_ROWS: usize = 10;
+
+    if !detailed.languages.is_empty() {
+        println!();
+        section_header(&rust_i18n::t!("stats.languages"), colored);
+        for lang in detailed.languages.iter().take(MAX_ROWS) {
+            println!(
+                "    {} {:>5} {}  +{} / -{}",
+                pad_display(&lang.language, 14),
+                lang.commits,
+                rust_i18n::t!("stats.commits"),
+                format_number(lang.insertions),
+                format_number(lang.deletions),
+            );
+        }
+    }
+
+    if !detailed.extensions.is_empty() {
+        println!();
+        section_header(&rust_i18n::t!("stats.extensions"), colored);
+        let max_commits = detailed.extensions.first().map(|e| e.commits).unwrap_or(0);
+        for ext in detailed.extensions.iter().take(MAX_ROWS) {
+            println!(
+                "    {} {} {}",
+                pad_display(&ext.extension, 10),
+                render_bar(ext.commits, max_commits, 20, colored),
+                ext.commits
+            );
+        }
+    }
+
+    if !detailed.hot_files.is_empty() {
+        println!();
+        section_header(&rust_i18n::t!("stats.hot_files"), colored);
+        for (i, file) in detailed.hot_files.iter().enumerate() {
+            println!(
+                "    #{:<2} {} {:>4} {}  +{} / -{}",
+                i + 1,
+                pad_display(&truncate_middle(&file.path, 50), 50),
+                file.commits,
+                rust_i18n::t!("stats.commits"),
+                format_number(file.insertions),
+                format_number(file.deletions),
+            );
+        }
+    }
+}
+
+/// Markdown output for `--detailed` file and language statistics
+fn output_detailed_markdown(detailed: &DetailedStats) {
+    if !detailed.languages.is_empty() {
+        println!("\n{}\n", rust_i18n::t!("stats.md_languages"));
+        println!(
+            "| {} | {} | {} | {} |",
+            rust_i18n::t!("stats.md_language"),
+            rust_i18n::t!("stats.md_commits"),
+            rust_i18n::t!("stats.md_insertions"),
+            rust_i18n::t!("stats.md_deletions")
+        );
+        println!("|----------|---------|------------|-----------|");
+        for lang in &detailed.languages {
+            println!(
+                "| {} | {} | +{} | -{} |",
+                lang.language,
+                lang.commits,
+                format_number(lang.insertions),
+                format_number(lang.deletions)
+            );
+        }
+    }
+
+    if !detailed.extensions.is_empty() {
+        println!("\n{}\n", rust_i18n::t!("stats.md_extensions"));
+        println!(
+            "| {} | {} |",
+            rust_i18n::t!("stats.md_extension"),
+            rust_i18n::t!("stats.md_commits")
+        );
+        println!("|-----------|---------|");
+        for ext in &detailed.extensions {
+            println!("| {} | {} |", ext.extension, ext.commits);
+        }
+    }
+
+    if !detailed.hot_files.is_empty() {
+        println!("\n{}\n", rust_i18n::t!("stats.md_hot_files"));
+        println!(
+            "| {} | {} | {} | {} | {} |",
+            rust_i18n::t!("stats.md_rank"),
+            rust_i18n::t!("stats.md_file"),
+            rust_i18n::t!("stats.md_commits"),
+            rust_i18n::t!("stats.md_insertions"),
+            rust_i18n::t!("stats.md_deletions")
+        );
+        println!("|------|------|---------|------------|-----------|");
+        for (i, file) in detailed.hot_files.iter().enumerate() {
+            println!(
+                "| {} | `{}` | {} | +{} | -{} |",
+                i + 1,
+                file.path,
+                file.commits,
+                format_number(file.insertions),
+                format_number(file.deletions)
+            );
+        }
+    }
+}
+
 /// Markdown format output
 fn output_markdown(stats: &RepoStats, _colored: bool) {
     println!("{}\n", rust_i18n::t!("stats.md_title"));
@@ -802,6 +1876,11 @@ fn output_markdown(stats: &RepoStats, _colored: bool) {
         rust_i18n::t!("stats.md_contributors"),
         stats.total_authors
     );
+    println!(
+        "| {} | {:.1}% |",
+        rust_i18n::t!("stats.md_convention_compliance"),
+        stats.convention_compliance
+    );
 
     if let (Some(first), Some(last)) = (stats.first_commit_date, stats.last_commit_date) {
         let days = stats.days_span().unwrap_or(0);
@@ -815,6 +1894,20 @@ fn output_markdown(stats: &RepoStats, _colored: bool) {
         );
     }
 
+    if !stats.repositories.is_empty() {
+        println!("\n{}\n", rust_i18n::t!("stats.md_repositories"));
+        println!(
+            "| {} | {} | {} |",
+            rust_i18n::t!("stats.md_repository"),
+            rust_i18n::t!("stats.md_commits"),
+            rust_i18n::t!("stats.md_path")
+        );
+        println!("|------------|---------|------|");
+        for repo in &stats.repositories {
+            println!("| {} | {} | {} |", repo.name, repo.commits, repo.path);
+        }
+    }
+
     if !stats.authors.is_empty() {
         println!("\n{}\n", rust_i18n::t!("stats.md_top_contributors"));
         println!(
@@ -844,6 +1937,42 @@ fn output_markdown(stats: &RepoStats, _colored: bool) {
         }
     }
 
+    if !stats.commit_types.is_empty() {
+        println!("\n{}\n", rust_i18n::t!("stats.md_commit_types"));
+        println!(
+            "| {} | {} | {} |",
+            rust_i18n::t!("stats.md_type"),
+            rust_i18n::t!("stats.md_commits"),
+            rust_i18n::t!("stats.md_percent")
+        );
+        println!("|------|---------|---|");
+        for entry in &stats.commit_types {
+            let percentage = (entry.commits as f64 / stats.total_commits as f64) * 100.0;
+            println!(
+                "| {} | {} | {:.1}% |",
+                entry.commit_type, entry.commits, percentage
+            );
+        }
+    }
+
+    if !stats.commit_scopes.is_empty() {
+        println!("\n{}\n", rust_i18n::t!("stats.md_commit_scopes"));
+        println!(
+            "| {} | {} | {} |",
+            rust_i18n::t!("stats.md_scope"),
+            rust_i18n::t!("stats.md_commits"),
+            rust_i18n::t!("stats.md_percent")
+        );
+        println!("|-------|---------|---|");
+        for entry in stats.commit_scopes.iter().take(10) {
+            let percentage = (entry.commits as f64 / stats.total_commits as f64) * 100.0;
+            println!(
+                "| {} | {} | {:.1}% |",
+                entry.scope, entry.commits, percentage
+            );
+        }
+    }
+
     if let Some(ref contrib) = stats.contrib {
         println!("\n{}\n", rust_i18n::t!("stats.md_contrib_title"));
         println!(
@@ -879,6 +2008,10 @@ fn output_markdown(stats: &RepoStats, _colored: bool) {
         }
     }
 
+    if let Some(ref detailed) = stats.detailed {
+        output_detailed_markdown(detailed);
+    }
+
     if !stats.commits_by_week.is_empty() {
         println!("\n{}\n", rust_i18n::t!("stats.md_recent"));
         println!(
@@ -944,13 +2077,651 @@ fn output_markdown(stats: &RepoStats, _colored: bool) {
     );
 }
 
+/// Render CSV export: one row per author per ISO week
+fn render_csv(stats: &RepoStats) -> String {
+    let mut out = String::from("week,author_name,author_email,commits\n");
+    for row in &stats.author_weeks {
+        let _ = writeln!(
+            out,
+            "{},{},{},{}",
+            csv_field(&row.week),
+            csv_field(&row.name),
+            csv_field(&row.email),
+            row.commits
+        );
+    }
+    out
+}
+
+/// Quote a CSV field when it contains separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escape text for HTML element content and attribute values
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Number of weeks shown in the HTML calendar heatmap
+const HEATMAP_WEEKS: i64 = 53;
+
+/// Render the commit calendar (Monday-first columns per week) as inline SVG
+fn render_heatmap_svg(commits_by_date: &BTreeMap<NaiveDate, usize>, end: NaiveDate) -> String {
+    const CELL: i64 = 11;
+    const STEP: i64 = 13;
+    const COLORS: [&str; 5] = ["#ebedf0", "#9be9a8", "#40c463", "#30a14e", "#216e39"];
+
+    let start = end
+        - Duration::days(end.weekday().num_days_from_monday() as i64)
+        - Duration::weeks(HEATMAP_WEEKS - 1);
+    let max_count = commits_by_date
+        .range(start..=end)
+        .map(|(_, c)| *c)
+        .max()
+        .unwrap_or(0);
+
+    let width = HEATMAP_WEEKS * STEP;
+    let height = 7 * STEP;
+    let mut svg = format!(
+        "<svg class=\"heatmap\" xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n"
+    );
+
+    let mut day = start;
+    while day <= end {
+        let offset = (day - start).num_days();
+        let count = commits_by_date.get(&day).copied().unwrap_or(0);
+        let level = if count == 0 || max_count == 0 {
+            0
+        } else {
+            // 1..=4, same quartiles as the terminal heatmap
+            (((count as f64 / max_count as f64) * 4.0).ceil() as usize).clamp(1, 4)
+        };
+        let _ = writeln!(
+            svg,
+            "  <rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" rx=\"2\" fill=\"{}\"><title>{}: {}</title></rect>",
+            (offset / 7) * STEP,
+            (offset % 7) * STEP,
+            COLORS[level],
+            day.format("%Y-%m-%d"),
+            count
+        );
+        day += Duration::days(1);
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Render a self-contained HTML report
+fn render_html(stats: &RepoStats) -> String {
+    let t = |key: &str| html_escape(&rust_i18n::t!(key));
+    let mut out = String::new();
+
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+<style>\n\
+body {{ font-family: -apple-system, BlinkMacSystemFont, \"Segoe UI\", sans-serif; margin: 2rem auto; max-width: 960px; color: #1f2328; }}\n\
+table {{ border-collapse: collapse; margin: 0.5rem 0 1.5rem; }}\n\
+th, td {{ border: 1px solid #d0d7de; padding: 4px 10px; text-align: left; }}\n\
+th {{ background: #f6f8fa; }}\n\
+td.num {{ text-align: right; }}\n\
+.heatmap {{ display: block; margin: 0.5rem 0 1.5rem; }}\n\
+</style>\n</head>\n<body>\n<h1>{title}</h1>\n",
+        title = t("stats.title")
+    );
+
+    // Overview
+    let _ = writeln!(out, "<h2>{}</h2>\n<table>", t("stats.overview"));
+    let _ = writeln!(
+        out,
+        "<tr><th>{}</th><td class=\"num\">{}</td></tr>",
+        t("stats.md_total_commits"),
+        stats.total_commits
+    );
+    let _ = writeln!(
+        out,
+        "<tr><th>{}</th><td class=\"num\">{}</td></tr>",
+        t("stats.md_contributors"),
+        stats.total_authors
+    );
+    if let (Some(first), Some(last)) = (stats.first_commit_date, stats.last_commit_date) {
+        let _ = writeln!(
+            out,
+            "<tr><th>{}</th><td>{} ~ {} ({} {})</td></tr>",
+            t("stats.md_time_span"),
+            first.format("%Y-%m-%d"),
+            last.format("%Y-%m-%d"),
+            stats.days_span().unwrap_or(0),
+            t("stats.days")
+        );
+    }
+    let _ = writeln!(
+        out,
+        "<tr><th>{}</th><td>{} {}</td></tr>",
+        t("stats.current_streak"),
+        stats.current_streak,
+        t("stats.days")
+    );
+    let _ = writeln!(
+        out,
+        "<tr><th>{}</th><td>{} {}</td></tr>\n</table>",
+        t("stats.longest_streak"),
+        stats.longest_streak,
+        t("stats.days")
+    );
+
+    // Calendar heatmap, ending at the newest commit in scope
+    let end = stats
+        .last_commit_date
+        .map(|d| d.date_naive())
+        .unwrap_or_else(|| Local::now().date_naive());
+    let _ = writeln!(
+        out,
+        "<h2>{}</h2>\n{}",
+        t("stats.commit_calendar"),
+        render_heatmap_svg(&stats.commits_by_date, end)
+    );
+
+    // Top contributors
+    if !stats.authors.is_empty() {
+        let _ = writeln!(
+            out,
+            "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+            t("stats.top_contributors"),
+            t("stats.md_rank"),
+            t("stats.md_name"),
+            t("stats.md_email"),
+            t("stats.md_commits"),
+            t("stats.md_percent")
+        );
+        for (i, author) in stats.authors.iter().enumerate() {
+            let percentage = if stats.total_commits > 0 {
+                (author.commits as f64 / stats.total_commits as f64) * 100.0
+            } else {
+                0.0
+            };
+            let _ = writeln!(
+                out,
+                "<tr><td class=\"num\">{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{:.1}%</td></tr>",
+                i + 1,
+                html_escape(&author.name),
+                html_escape(&author.email),
+                author.commits,
+                percentage
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    if let Some(ref contrib) = stats.contrib {
+        let _ = writeln!(
+            out,
+            "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+            t("stats.contrib_title"),
+            t("stats.md_rank"),
+            t("stats.md_name"),
+            t("stats.md_insertions"),
+            t("stats.md_deletions"),
+            t("stats.md_percent")
+        );
+        for (i, author) in contrib.authors.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "<tr><td class=\"num\">{}</td><td>{} &lt;{}&gt;</td><td class=\"num\">+{}</td><td class=\"num\">-{}</td><td class=\"num\">{:.1}%</td></tr>",
+                i + 1,
+                html_escape(&author.name),
+                html_escape(&author.email),
+                format_number(author.insertions),
+                format_number(author.deletions),
+                author.percentage
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    if let Some(ref detailed) = stats.detailed {
+        if !detailed.languages.is_empty() {
+            let _ = writeln!(
+                out,
+                "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+                t("stats.languages"),
+                t("stats.md_language"),
+                t("stats.md_commits"),
+                t("stats.md_insertions"),
+                t("stats.md_deletions")
+            );
+            for lang in &detailed.languages {
+                let _ = writeln!(
+                    out,
+                    "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">+{}</td><td class=\"num\">-{}</td></tr>",
+                    html_escape(&lang.language),
+                    lang.commits,
+                    format_number(lang.insertions),
+                    format_number(lang.deletions)
+                );
+            }
+            out.push_str("</table>\n");
+        }
+
+        if !detailed.hot_files.is_empty() {
+            let _ = writeln!(
+                out,
+                "<h2>{}</h2>\n<table>\n<tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr>",
+                t("stats.hot_files"),
+                t("stats.md_rank"),
+                t("stats.md_file"),
+                t("stats.md_commits"),
+                t("stats.md_insertions"),
+                t("stats.md_deletions")
+            );
+            for (i, file) in detailed.hot_files.iter().enumerate() {
+                let _ = writeln!(
+                    out,
+                    "<tr><td class=\"num\">{}</td><td><code>{}</code></td><td class=\"num\">{}</td><td class=\"num\">+{}</td><td class=\"num\">-{}</td></tr>",
+                    i + 1,
+                    html_escape(&file.path),
+                    file.commits,
+                    format_number(file.insertions),
+                    format_number(file.deletions)
+                );
+            }
+            out.push_str("</table>\n");
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
 /// JSON format output
-fn output_json(stats: &RepoStats) -> Result<()> {
+fn output_json(stats: &RepoStats, format: OutputFormat) -> Result<()> {
     let output = JsonOutput {
         success: true,
         data: Some(stats.clone()),
         error: None,
+        meta: None,
     };
-    println!("{}", serde_json::to_string_pretty(&output)?);
-    Ok(())
+    json::print_output(&output, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_bound_date_only() {
+        let start = parse_date_bound("2026-03-01", false).unwrap();
+        assert_eq!(
+            start.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2026-03-01 00:00:00"
+        );
+
+        let end = parse_date_bound("2026-03-01", true).unwrap();
+        assert_eq!(
+            end.format("%Y-%m-%d %H:%M:%S").to_string(),
+            "2026-03-01 23:59:59"
+        );
+    }
+
+    #[test]
+    fn test_parse_date_bound_rfc3339() {
+        let dt = parse_date_bound("2026-03-01T12:00:00Z", false).unwrap();
+        assert_eq!(dt.timestamp(), 1_772_366_400);
+    }
+
+    #[test]
+    fn test_parse_date_bound_relative() {
+        let two_weeks = parse_date_bound("2w", false).unwrap();
+        let expected = Local::now() - Duration::days(14);
+        assert!((two_weeks - expected).num_seconds().abs() < 5);
+    }
+
+    #[test]
+    fn test_parse_date_bound_invalid() {
+        for value in ["", "yesterday", "2026-13-01", "5x", "d"] {
+            assert!(
+                matches!(
+                    parse_date_bound(value, false),
+                    Err(GcopError::InvalidInput(_))
+                ),
+                "{value:?} should be rejected"
+            );
+        }
+    }
+
+    const NUMSTAT_LOG: &str = "\
+1111111111111111111111111111111111111111|Alice|alice@example.com
+10\t2\tsrc/main.rs
+3\t0\tsrc/lib.rs
+1\t1\tREADME.md
+
+2222222222222222222222222222222222222222|Bob|bob@example.com
+5\t5\tsrc/main.rs
+-\t-\tassets/logo.png
+4\t0\tMakefile
+";
+
+    #[test]
+    fn test_aggregate_numstat() {
+        let detailed = aggregate_numstat(NUMSTAT_LOG, None);
+
+        let rust = &detailed.languages[0];
+        assert_eq!(rust.language, "Rust");
+        assert_eq!(rust.commits, 2);
+        assert_eq!((rust.insertions, rust.deletions), (18, 7));
+
+        let rs = detailed
+            .extensions
+            .iter()
+            .find(|e| e.extension == "rs")
+            .unwrap();
+        assert_eq!(rs.commits, 2);
+        assert!(detailed.extensions.iter().any(|e| e.extension == "(none)"));
+
+        let hottest = &detailed.hot_files[0];
+        assert_eq!(hottest.path, "src/main.rs");
+        assert_eq!((hottest.commits, hottest.churn), (2, 22));
+    }
+
+    #[test]
+    fn test_aggregate_numstat_author_filter() {
+        let detailed = aggregate_numstat(NUMSTAT_LOG, Some("BOB"));
+
+        assert_eq!(detailed.hot_files.len(), 3);
+        assert!(detailed.hot_files.iter().all(|f| f.path != "src/lib.rs"));
+        assert!(detailed.languages.iter().all(|l| l.language != "Docs"));
+    }
+
+    #[test]
+    fn test_aggregate_numstat_hot_files_limit() {
+        let mut log =
+            String::from("3333333333333333333333333333333333333333|Dev|dev@example.com\n");
+        for i in 0..(HOT_FILES_LIMIT + 5) {
+            log.push_str(&format!("{}\t0\tfile{}.txt\n", i + 1, i));
+        }
+        let detailed = aggregate_numstat(&log, None);
+        assert_eq!(detailed.hot_files.len(), HOT_FILES_LIMIT);
+        assert_eq!(
+            detailed.hot_files[0].path,
+            format!("file{}.txt", HOT_FILES_LIMIT + 4)
+        );
+    }
+
+    #[test]
+    fn test_language_for_extension() {
+        assert_eq!(
+            language_for_extension(&file_extension("a/b.TSX")),
+            "TypeScript"
+        );
+        assert_eq!(language_for_extension(&file_extension("Makefile")), "Other");
+        assert_eq!(file_extension("Makefile"), "(none)");
+    }
+
+    #[test]
+    fn test_history_log_args() {
+        assert!(history_log_args(&HistoryOptions::default()).is_empty());
+
+        let history = HistoryOptions {
+            since: None,
+            until: None,
+            range: Some("v1.0..v2.0".to_string()),
+            path: Some("src/git".to_string()),
+        };
+        assert_eq!(
+            history_log_args(&history),
+            vec!["v1.0..v2.0", "--", "src/git"]
+        );
+    }
+
+    fn commit_at(name: &str, email: &str, date: &str) -> CommitInfo {
+        CommitInfo {
+            hash: "0".repeat(40),
+            parent_count: 1,
+            author_name: name.to_string(),
+            author_email: email.to_string(),
+            timestamp: parse_date_bound(date, false).unwrap(),
+            message: "chore: test".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_from_commits_convention_breakdown() {
+        let mut commits: Vec<CommitInfo> = [
+            "fix(cli): a",
+            "Feat(cli): b",
+            "feat(api)!: c",
+            "Update docs",
+        ]
+        .iter()
+        .map(|message| CommitInfo {
+            message: message.to_string(),
+            ..commit_at("Alice", "alice@example.com", "2026-03-03")
+        })
+        .collect();
+        commits.push(commit_at("Bob", "bob@example.com", "2026-03-04"));
+        // 合并提交不计入规范占比
+        commits.push(CommitInfo {
+            parent_count: 2,
+            message: "Merge branch 'main'".to_string(),
+            ..commit_at("Bob", "bob@example.com", "2026-03-05")
+        });
+
+        let stats = RepoStats::from_commits(&commits, None);
+        let types: Vec<(&str, usize)> = stats
+            .commit_types
+            .iter()
+            .map(|t| (t.commit_type.as_str(), t.commits))
+            .collect();
+        assert_eq!(types, vec![("feat", 2), ("chore", 1), ("fix", 1)]);
+        let scopes: Vec<(&str, usize)> = stats
+            .commit_scopes
+            .iter()
+            .map(|s| (s.scope.as_str(), s.commits))
+            .collect();
+        assert_eq!(scopes, vec![("cli", 2), ("api", 1)]);
+        assert!((stats.convention_compliance - 80.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_from_commits_empty_compliance() {
+        let stats = RepoStats::from_commits(&[], None);
+        assert!(stats.commit_types.is_empty());
+        assert_eq!(stats.convention_compliance, 0.0);
+    }
+
+    #[test]
+    fn test_author_aliases_resolve() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "JANE@old.example.com".to_string(),
+            "Jane Doe <jane@example.com>".to_string(),
+        );
+        map.insert(
+            "jdoe".to_string(),
+            "Jane Doe <jane@example.com>".to_string(),
+        );
+        map.insert("broken".to_string(), "jane@example.com".to_string());
+        let aliases = AuthorAliases::new(&map);
+
+        assert_eq!(
+            aliases.resolve("Someone", "jane@OLD.example.com"),
+            ("Jane Doe", "jane@example.com")
+        );
+        assert_eq!(
+            aliases.resolve("JDoe", "jd@laptop.local"),
+            ("Jane Doe", "jane@example.com")
+        );
+        assert_eq!(aliases.resolve("broken", "b@x.io"), ("broken", "b@x.io"));
+
+        let commits: Vec<CommitInfo> = [
+            ("jdoe", "jd@laptop.local"),
+            ("Jane Doe", "jane@example.com"),
+        ]
+        .iter()
+        .map(|(name, email)| {
+            let mut commit = commit_at(name, email, "2026-03-03");
+            aliases.apply(&mut commit);
+            commit
+        })
+        .collect();
+        let stats = RepoStats::from_commits(&commits, None);
+        assert_eq!(stats.total_authors, 1);
+        assert_eq!(stats.authors[0].commits, 2);
+    }
+
+    #[test]
+    fn test_merge_authors_overrides() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "jdoe".to_string(),
+            "Jane Doe <jane@example.com>".to_string(),
+        );
+        let mut aliases = AuthorAliases::new(&map)
+            .with_overrides(&[
+                "jdoe=J. Doe <jd@example.com>".to_string(),
+                "Old Jane = Jane Doe".to_string(),
+            ])
+            .unwrap();
+
+        // 命令行覆盖优先于配置
+        assert_eq!(
+            aliases.resolve("jdoe", "x@y.z"),
+            ("J. Doe", "jd@example.com")
+        );
+
+        // 仅名字的目标使用该名字最常用的邮箱
+        let commits = vec![
+            commit_at("Jane Doe", "jane@example.com", "2026-03-01"),
+            commit_at("Jane Doe", "jane@example.com", "2026-03-02"),
+            commit_at("Jane Doe", "jane@laptop.local", "2026-03-03"),
+            commit_at("Old Jane", "old@example.com", "2026-03-04"),
+        ];
+        aliases.complete_emails(&commits);
+        assert_eq!(
+            aliases.resolve("old jane", "old@example.com"),
+            ("Jane Doe", "jane@example.com")
+        );
+
+        for bad in ["no-separator", "=Jane Doe", "jdoe=jane@example.com"] {
+            let err = AuthorAliases::default()
+                .with_overrides(&[bad.to_string()])
+                .unwrap_err();
+            assert!(matches!(err, GcopError::InvalidInput(_)), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_calendar_covers_a_year() {
+        let commits = vec![
+            commit_at("a", "a@x.io", "2026-01-01"),
+            commit_at("a", "a@x.io", "2026-01-01"),
+            commit_at("a", "a@x.io", "2026-03-10"),
+        ];
+        let mut stats = RepoStats::from_commits(&commits, None);
+        let today = NaiveDate::from_ymd_opt(2026, 6, 30).unwrap();
+        stats.extend_commits_by_day(CALENDAR_DAYS, today);
+        assert_eq!(stats.commits_by_day.len(), 365);
+        assert_eq!(stats.commits_by_day["2026-01-01"], 2);
+        assert_eq!(stats.commits_by_day["2026-03-10"], 1);
+        assert_eq!(stats.commits_by_day["2025-07-01"], 0);
+
+        let lines = render_calendar_text(&stats.commits_by_day, false);
+        // month labels + 7 weekdays + peak line
+        assert_eq!(lines.len(), 9);
+        assert!(lines[0].starts_with("    Jul"));
+        assert!(lines[0].contains("Jan") && lines[0].contains("Jun"));
+        assert!(lines[1].starts_with("Mon "));
+        assert!(lines[7].starts_with("Sun "));
+        // 2025-07-01 is a Tuesday, 2026-06-30 a Tuesday: 53 week columns
+        assert_eq!(lines[2].chars().count(), 4 + 53);
+        // 2026-01-01 (Thursday) is the busiest day
+        let week = (NaiveDate::from_ymd_opt(2026, 1, 1).unwrap()
+            - NaiveDate::from_ymd_opt(2025, 6, 30).unwrap())
+        .num_days() as usize
+            / 7;
+        assert_eq!(lines[4].chars().nth(4 + week), Some('█'));
+        assert_eq!(lines[8], "2025-07-01 ~ 2026-06-30  peak: 2");
+    }
+
+    #[test]
+    fn test_merge_detailed_stats() {
+        let part = |path: &str, churn: usize| DetailedStats {
+            extensions: vec![ExtensionStats {
+                extension: "rs".to_string(),
+                commits: 2,
+            }],
+            languages: vec![LanguageStats {
+                language: "Rust".to_string(),
+                commits: 2,
+                insertions: churn,
+                deletions: 0,
+            }],
+            hot_files: vec![FileChurnStats {
+                path: path.to_string(),
+                commits: 1,
+                insertions: churn,
+                deletions: 0,
+                churn,
+            }],
+        };
+        let merged = merge_detailed_stats(vec![
+            ("api".to_string(), part("src/lib.rs", 5)),
+            ("web".to_string(), part("src/main.rs", 9)),
+        ]);
+
+        assert_eq!(merged.extensions[0].commits, 4);
+        assert_eq!(merged.languages[0].insertions, 14);
+        let paths: Vec<&str> = merged.hot_files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["web/src/main.rs", "api/src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("alice"), "alice");
+        assert_eq!(csv_field("Doe, John"), "\"Doe, John\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_render_csv_rows_per_author_week() {
+        let commits = vec![
+            commit_at("Bob", "bob@example.com", "2026-03-03"),
+            commit_at("Alice", "alice@example.com", "2026-03-04"),
+            commit_at("Alice", "alice@example.com", "2026-03-05"),
+            commit_at("Alice", "alice@example.com", "2026-03-10"),
+        ];
+        let stats = RepoStats::from_commits(&commits, None);
+        assert_eq!(
+            render_csv(&stats),
+            "week,author_name,author_email,commits\n\
+             2026-W10,Alice,alice@example.com,2\n\
+             2026-W10,Bob,bob@example.com,1\n\
+             2026-W11,Alice,alice@example.com,1\n"
+        );
+    }
+
+    #[test]
+    fn test_render_html_is_escaped_and_has_heatmap() {
+        let commits = vec![commit_at("<script>", "x&y@example.com", "2026-03-03")];
+        let stats = RepoStats::from_commits(&commits, None);
+        let html = render_html(&stats);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<svg class=\"heatmap\""));
+        assert!(html.contains("<title>2026-03-03: 1</title>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("x&amp;y@example.com"));
+        assert!(!html.contains("<script>"));
+    }
 }
@@ -0,0 +1,388 @@
+//! Annotated tag creation with AI-generated release messages.
+//!
+//! Summarizes the commits since the previous tag into a tag message and
+//! creates the tag with `git tag -a`, optionally pushing it afterwards.
+
+use std::sync::Arc;
+
+use super::options::TagOptions;
+use crate::config::{AppConfig, CommandLlmConfig};
+use crate::error::{GcopError, Result};
+use crate::git::{GitOperations, HistoryOptions, repository::GitRepository};
+use crate::llm::provider::base::response::process_commit_response;
+use crate::llm::{LLMProvider, ProgressReporter, provider::create_provider};
+use crate::ui::{Reporter, TerminalReporter};
+
+/// Maximum number of commit subjects included in the prompt.
+const MAX_PROMPT_COMMITS: usize = 200;
+
+/// Execute tag command
+///
+/// # Arguments
+/// * `options` - Tag command options
+/// * `config` - application configuration
+pub async fn run(options: &TagOptions<'_>, config: &AppConfig) -> Result<()> {
+    let repo = GitRepository::open(None)?;
+    let provider = create_provider(
+        config,
+        &CommandLlmConfig::default(),
+        options.provider_override,
+        options.model_override,
+    )?;
+
+    let reporter = TerminalReporter::new(config.ui.colored);
+    run_with_deps(options, config, &repo, &provider, &reporter).await
+}
+
+/// Creates the tag, reporting progress to `reporter`.
+async fn run_with_deps(
+    options: &TagOptions<'_>,
+    config: &AppConfig,
+    repo: &dyn GitOperations,
+    provider: &Arc<dyn LLMProvider>,
+    reporter: &dyn Reporter,
+) -> Result<()> {
+    let colored = config.ui.colored;
+
+    if !is_valid_tag_name(options.name) {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("tag.invalid_name", name = options.name).to_string(),
+        ));
+    }
+    if repo.is_empty()? {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("tag.no_commits").to_string(),
+        ));
+    }
+    if repo.tag_exists(options.name)? {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("tag.exists", name = options.name).to_string(),
+        ));
+    }
+
+    let previous = match options.from {
+        Some(from) => Some(from.to_string()),
+        None => repo.get_latest_tag()?,
+    };
+    let subjects = commit_subjects(repo, previous.as_deref())?;
+    if subjects.is_empty() {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!(
+                "tag.no_changes",
+                from = previous.as_deref().unwrap_or("HEAD")
+            )
+            .to_string(),
+        ));
+    }
+
+    let range_label = match previous.as_deref() {
+        Some(from) => format!("{}..HEAD", from),
+        None => "HEAD".to_string(),
+    };
+    reporter.step(
+        &rust_i18n::t!("tag.step1"),
+        &rust_i18n::t!("tag.analyzed", count = subjects.len(), range = range_label),
+    );
+
+    let shown = subjects.len().min(MAX_PROMPT_COMMITS);
+    let (system, user) = crate::llm::prompt::build_tag_prompt(
+        options.name,
+        previous.as_deref(),
+        &subjects[..shown],
+        subjects.len() - shown,
+    );
+    if options.verbose {
+        super::commit::print_verbose_prompt(&system, &user, false, colored);
+    }
+
+    reporter.step(
+        &rust_i18n::t!("tag.step2"),
+        &rust_i18n::t!("spinner.generating"),
+    );
+    let spinner = reporter.cancellable_spinner(&rust_i18n::t!("tag.generating"));
+    let message = provider
+        .send_prompt(
+            &system,
+            &user,
+            spinner.as_ref().map(|s| s as &dyn ProgressReporter),
+        )
+        .await;
+    if let Some(spinner) = &spinner {
+        spinner.finish_and_clear();
+    }
+    let message = message?;
+    let message = process_commit_response(message);
+    if message.trim().is_empty() {
+        return Err(GcopError::Llm(
+            rust_i18n::t!("tag.empty_message").to_string(),
+        ));
+    }
+
+    if options.dry_run {
+        // The bare message is the result of --dry-run, whatever the reporter
+        reporter.info(&rust_i18n::t!("tag.generated"));
+        println!("{}", message);
+        return Ok(());
+    }
+    reporter.preview(&rust_i18n::t!("tag.generated"), &message);
+
+    if !options.yes
+        && !reporter.confirm(&rust_i18n::t!("tag.confirm", name = options.name), true)?
+    {
+        reporter.warning(&rust_i18n::t!("tag.cancelled"));
+        return Err(GcopError::UserCancelled);
+    }
+
+    reporter.step(
+        &rust_i18n::t!("tag.step3"),
+        &rust_i18n::t!("tag.creating", name = options.name),
+    );
+    repo.create_tag(options.name, &message)?;
+    reporter.success(&rust_i18n::t!("tag.created", name = options.name));
+
+    if options.push {
+        repo.push_tag(options.remote, options.name)?;
+        reporter.success(&rust_i18n::t!(
+            "tag.pushed",
+            name = options.name,
+            remote = options.remote
+        ));
+    }
+
+    Ok(())
+}
+
+/// Subjects of the non-merge commits since `previous` (newest first).
+fn commit_subjects(repo: &dyn GitOperations, previous: Option<&str>) -> Result<Vec<String>> {
+    let history = HistoryOptions {
+        range: previous.map(|from| format!("{}..HEAD", from)),
+        ..Default::default()
+    };
+    Ok(repo
+        .get_commit_history(&history)?
+        .into_iter()
+        .filter(|commit| commit.parent_count <= 1)
+        .map(|commit| commit.message)
+        .collect())
+}
+
+/// Whether `name` is a valid tag name (`git check-ref-format` rules).
+fn is_valid_tag_name(name: &str) -> bool {
+    !name.starts_with('-') && git2::Reference::is_valid_name(&format!("refs/tags/{}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{CommitInfo, MockGitOperations};
+    use crate::llm::provider::test_utils::ScriptedProvider;
+    use crate::ui::SilentReporter;
+    use chrono::Local;
+
+    const REPLY: &str = "```\nv1.2.0: tags\n\n- Add tag command\n```";
+
+    fn commit(message: &str, parent_count: usize) -> CommitInfo {
+        CommitInfo {
+            hash: "abc".to_string(),
+            parent_count,
+            author_name: "Dev".to_string(),
+            author_email: "dev@example.com".to_string(),
+            timestamp: Local::now(),
+            message: message.to_string(),
+        }
+    }
+
+    fn options(name: &str) -> TagOptions<'_> {
+        TagOptions {
+            name,
+            from: None,
+            dry_run: false,
+            yes: true,
+            push: false,
+            remote: "origin",
+            verbose: false,
+            provider_override: None,
+            model_override: None,
+        }
+    }
+
+    fn tagged_repo() -> MockGitOperations {
+        let mut repo = MockGitOperations::new();
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_tag_exists().returning(|_| Ok(false));
+        repo.expect_get_latest_tag()
+            .returning(|| Ok(Some("v1.1.0".to_string())));
+        repo.expect_get_commit_history()
+            .withf(|history| history.range.as_deref() == Some("v1.1.0..HEAD"))
+            .returning(|_| {
+                Ok(vec![
+                    commit("feat: add tag command", 1),
+                    commit("Merge branch 'feature'", 2),
+                    commit("fix: crash", 1),
+                ])
+            });
+        repo
+    }
+
+    fn config() -> AppConfig {
+        let mut config = AppConfig::default();
+        config.ui.colored = false;
+        config
+    }
+
+    #[tokio::test]
+    async fn test_tag_creates_annotated_tag() {
+        let mut repo = tagged_repo();
+        repo.expect_create_tag()
+            .withf(|name, message| {
+                name == "v1.2.0" && message == "v1.2.0: tags\n\n- Add tag command"
+            })
+            .times(1)
+            .returning(|_, _| Ok(()));
+        repo.expect_push_tag().never();
+        let provider = Arc::new(ScriptedProvider::new(&[REPLY]));
+        let dyn_provider: Arc<dyn LLMProvider> = provider.clone();
+
+        run_with_deps(
+            &options("v1.2.0"),
+            &config(),
+            &repo,
+            &dyn_provider,
+            &SilentReporter,
+        )
+        .await
+        .unwrap();
+
+        let prompts = provider.prompts();
+        assert!(prompts[0].contains("Previous tag: v1.1.0"));
+        assert!(
+            prompts[0].contains("### Features\n- feat: add tag command\n\n### Fixes\n- fix: crash")
+        );
+        assert!(!prompts[0].contains("Merge branch"));
+    }
+
+    #[tokio::test]
+    async fn test_tag_dry_run_and_push() {
+        let mut repo = tagged_repo();
+        repo.expect_create_tag().never();
+        let provider: Arc<dyn LLMProvider> = Arc::new(ScriptedProvider::new(&[REPLY]));
+        let dry_run = TagOptions {
+            dry_run: true,
+            push: true,
+            ..options("v1.2.0")
+        };
+        run_with_deps(&dry_run, &config(), &repo, &provider, &SilentReporter)
+            .await
+            .unwrap();
+
+        let mut repo = tagged_repo();
+        repo.expect_create_tag().times(1).returning(|_, _| Ok(()));
+        repo.expect_push_tag()
+            .withf(|remote, name| remote == "upstream" && name == "v1.2.0")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let push = TagOptions {
+            push: true,
+            remote: "upstream",
+            ..options("v1.2.0")
+        };
+        run_with_deps(&push, &config(), &repo, &provider, &SilentReporter)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tag_uses_from_and_first_release() {
+        let mut repo = MockGitOperations::new();
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_tag_exists().returning(|_| Ok(false));
+        repo.expect_get_latest_tag().never();
+        repo.expect_get_commit_history()
+            .withf(|history| history.range.as_deref() == Some("v0.9.0..HEAD"))
+            .returning(|_| Ok(vec![commit("feat: x", 1)]));
+        let provider: Arc<dyn LLMProvider> = Arc::new(ScriptedProvider::new(&[REPLY]));
+        let from = TagOptions {
+            from: Some("v0.9.0"),
+            dry_run: true,
+            ..options("v1.0.0")
+        };
+        run_with_deps(&from, &config(), &repo, &provider, &SilentReporter)
+            .await
+            .unwrap();
+
+        // 没有任何标签时汇总整个历史
+        let mut repo = MockGitOperations::new();
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_tag_exists().returning(|_| Ok(false));
+        repo.expect_get_latest_tag().returning(|| Ok(None));
+        repo.expect_get_commit_history()
+            .withf(|history| history.range.is_none())
+            .returning(|_| Ok(vec![commit("feat: init", 0)]));
+        let provider = Arc::new(ScriptedProvider::new(&[REPLY]));
+        let dyn_provider: Arc<dyn LLMProvider> = provider.clone();
+        let first = TagOptions {
+            dry_run: true,
+            ..options("v0.1.0")
+        };
+        run_with_deps(&first, &config(), &repo, &dyn_provider, &SilentReporter)
+            .await
+            .unwrap();
+        assert!(provider.prompts()[0].contains("first release"));
+    }
+
+    #[tokio::test]
+    async fn test_tag_errors() {
+        let provider: Arc<dyn LLMProvider> = Arc::new(ScriptedProvider::new(&[REPLY]));
+
+        let repo = MockGitOperations::new();
+        let err = run_with_deps(
+            &options("-bad"),
+            &config(),
+            &repo,
+            &provider,
+            &SilentReporter,
+        )
+        .await;
+        assert!(matches!(err, Err(GcopError::InvalidInput(_))));
+
+        let mut repo = MockGitOperations::new();
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_tag_exists().returning(|_| Ok(true));
+        let err = run_with_deps(
+            &options("v1.1.0"),
+            &config(),
+            &repo,
+            &provider,
+            &SilentReporter,
+        )
+        .await;
+        assert!(matches!(err, Err(GcopError::InvalidInput(msg)) if msg.contains("v1.1.0")));
+
+        // 上一个标签之后没有新提交
+        let mut repo = MockGitOperations::new();
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_tag_exists().returning(|_| Ok(false));
+        repo.expect_get_latest_tag()
+            .returning(|| Ok(Some("v1.1.0".to_string())));
+        repo.expect_get_commit_history().returning(|_| Ok(vec![]));
+        let err = run_with_deps(
+            &options("v1.2.0"),
+            &config(),
+            &repo,
+            &provider,
+            &SilentReporter,
+        )
+        .await;
+        assert!(matches!(err, Err(GcopError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_is_valid_tag_name() {
+        assert!(is_valid_tag_name("v1.2.0"));
+        assert!(is_valid_tag_name("release/2026-10"));
+        assert!(!is_valid_tag_name("-v1"));
+        assert!(!is_valid_tag_name("v1..2"));
+        assert!(!is_valid_tag_name("bad name"));
+        assert!(!is_valid_tag_name(""));
+    }
+}
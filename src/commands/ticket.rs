@@ -0,0 +1,247 @@
+//! Ticket ID extraction and injection.
+//!
+//! Extracts an issue-tracker ticket ID (Jira, Linear, ...) from the current
+//! branch name and guarantees it appears in generated commit messages.
+
+use std::sync::{LazyLock, Mutex};
+
+use regex::Regex;
+
+use crate::config::{CommitConfig, TicketPlacement};
+
+/// Trailer key used when the ticket is placed in the footer.
+const TICKET_TRAILER_KEY: &str = "Refs";
+
+/// Last compiled `commit.ticket_pattern`, recompiled only when the pattern changes.
+static TICKET_RE: LazyLock<Mutex<Option<Regex>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Extract the ticket ID from the branch name using `commit.ticket_pattern`.
+///
+/// Returns `None` when the pattern is empty or invalid, the branch is unknown,
+/// or nothing matches. The first capture group wins over the whole match.
+pub(crate) fn resolve_ticket(branch_name: Option<&str>, config: &CommitConfig) -> Option<String> {
+    if config.ticket_pattern.is_empty() {
+        return None;
+    }
+    let branch = branch_name?;
+
+    let mut cached = TICKET_RE.lock().unwrap_or_else(|e| e.into_inner());
+    if cached
+        .as_ref()
+        .is_none_or(|re| re.as_str() != config.ticket_pattern)
+    {
+        match Regex::new(&config.ticket_pattern) {
+            Ok(re) => *cached = Some(re),
+            Err(e) => {
+                tracing::debug!("Invalid commit.ticket_pattern: {}", e);
+                return None;
+            }
+        }
+    }
+    let re = cached.as_ref()?;
+
+    let captures = re.captures(branch)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map(|m| m.as_str().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// Ensure `ticket` appears in `message`, adding it at `placement` if missing.
+pub(crate) fn ensure_ticket(message: &str, ticket: &str, placement: TicketPlacement) -> String {
+    if contains_ticket(message, ticket) {
+        return message.to_string();
+    }
+
+    let message = message.trim_end();
+    match placement {
+        TicketPlacement::Subject => {
+            let (subject, rest) = match message.split_once('\n') {
+                Some((subject, rest)) => (subject, Some(rest)),
+                None => (message, None),
+            };
+            let subject = format!("{} ({})", subject.trim_end(), ticket);
+            match rest {
+                Some(rest) => format!("{}\n{}", subject, rest),
+                None => subject,
+            }
+        }
+        TicketPlacement::Footer => {
+            let trailer = format!("{}: {}", TICKET_TRAILER_KEY, ticket);
+            if ends_with_trailers(message) {
+                format!("{}\n{}", message, trailer)
+            } else {
+                format!("{}\n\n{}", message, trailer)
+            }
+        }
+    }
+}
+
+/// Whether `ticket` occurs in `message` as a whole word (`ABC-12` is not in `ABC-123`).
+fn contains_ticket(message: &str, ticket: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    message.match_indices(ticket).any(|(start, _)| {
+        let before = message[..start].chars().next_back();
+        let after = message[start + ticket.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// Apply [`ensure_ticket`] when a ticket is known.
+pub(crate) fn apply_ticket(message: String, ticket: Option<&str>, config: &CommitConfig) -> String {
+    match ticket {
+        Some(ticket) => ensure_ticket(&message, ticket, config.ticket_placement),
+        None => message,
+    }
+}
+
+/// Whether the last paragraph (after the subject) is a git trailer block.
+pub(crate) fn ends_with_trailers(message: &str) -> bool {
+    let Some((_, body)) = message.split_once("\n\n") else {
+        return false;
+    };
+    let last_paragraph = body.rsplit("\n\n").next().unwrap_or_default();
+    !last_paragraph.is_empty() && last_paragraph.lines().all(is_trailer_line)
+}
+
+fn is_trailer_line(line: &str) -> bool {
+    super::breaking::is_footer_line(line)
+        || line.split_once(": ").is_some_and(|(key, _)| {
+            !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_pattern(pattern: &str) -> CommitConfig {
+        CommitConfig {
+            ticket_pattern: pattern.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_ticket_default_pattern() {
+        let config = CommitConfig::default();
+        assert_eq!(
+            resolve_ticket(Some("feature/ABC-123-login-form"), &config),
+            Some("ABC-123".to_string())
+        );
+        assert_eq!(resolve_ticket(Some("feature/login"), &config), None);
+        assert_eq!(resolve_ticket(None, &config), None);
+    }
+
+    #[test]
+    fn test_resolve_ticket_uses_capture_group() {
+        let config = config_with_pattern(r"^(?:feat|fix)/(\d+)-");
+        assert_eq!(
+            resolve_ticket(Some("fix/4521-null-check"), &config),
+            Some("4521".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_ticket_disabled_or_invalid() {
+        assert_eq!(
+            resolve_ticket(Some("ABC-1"), &config_with_pattern("")),
+            None
+        );
+        assert_eq!(
+            resolve_ticket(Some("ABC-1"), &config_with_pattern("(")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ensure_ticket_already_present() {
+        let message = "feat: add login (ABC-123)";
+        assert_eq!(
+            ensure_ticket(message, "ABC-123", TicketPlacement::Footer),
+            message
+        );
+    }
+
+    #[test]
+    fn test_ensure_ticket_matches_whole_ticket_only() {
+        // ABC-12 不能因为消息中含有 ABC-123 而被视为已存在
+        assert_eq!(
+            ensure_ticket(
+                "feat: add login (ABC-123)",
+                "ABC-12",
+                TicketPlacement::Subject
+            ),
+            "feat: add login (ABC-123) (ABC-12)"
+        );
+        assert_eq!(
+            ensure_ticket("fix: XABC-12 typo", "ABC-12", TicketPlacement::Footer),
+            "fix: XABC-12 typo\n\nRefs: ABC-12"
+        );
+        let message = "feat: add login\n\nRefs: ABC-12";
+        assert_eq!(
+            ensure_ticket(message, "ABC-12", TicketPlacement::Footer),
+            message
+        );
+    }
+
+    #[test]
+    fn test_resolve_ticket_recompiles_changed_pattern() {
+        let default = CommitConfig::default();
+        let custom = config_with_pattern(r"^fix/(\d+)-");
+        assert_eq!(resolve_ticket(Some("fix/42-typo"), &default), None);
+        assert_eq!(
+            resolve_ticket(Some("fix/42-typo"), &custom),
+            Some("42".to_string())
+        );
+        assert_eq!(
+            resolve_ticket(Some("fix/ABC-7-typo"), &default),
+            Some("ABC-7".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ensure_ticket_footer() {
+        assert_eq!(
+            ensure_ticket("feat: add login\n", "ABC-123", TicketPlacement::Footer),
+            "feat: add login\n\nRefs: ABC-123"
+        );
+        assert_eq!(
+            ensure_ticket(
+                "feat: add login\n\nAdd form validation.",
+                "ABC-123",
+                TicketPlacement::Footer
+            ),
+            "feat: add login\n\nAdd form validation.\n\nRefs: ABC-123"
+        );
+    }
+
+    #[test]
+    fn test_ensure_ticket_footer_joins_trailer_block() {
+        assert_eq!(
+            ensure_ticket(
+                "feat: add login\n\nBody.\n\nSigned-off-by: Dev <dev@example.com>",
+                "ABC-123",
+                TicketPlacement::Footer
+            ),
+            "feat: add login\n\nBody.\n\nSigned-off-by: Dev <dev@example.com>\nRefs: ABC-123"
+        );
+    }
+
+    #[test]
+    fn test_ensure_ticket_subject() {
+        assert_eq!(
+            ensure_ticket("feat: add login", "ABC-123", TicketPlacement::Subject),
+            "feat: add login (ABC-123)"
+        );
+        assert_eq!(
+            ensure_ticket(
+                "feat: add login\n\nBody.",
+                "ABC-123",
+                TicketPlacement::Subject
+            ),
+            "feat: add login (ABC-123)\n\nBody."
+        );
+    }
+}
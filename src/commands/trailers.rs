@@ -0,0 +1,233 @@
+//! Commit message trailers (`Signed-off-by`, `Co-authored-by`).
+//!
+//! Trailers come from `[commit.trailers]` and the `--signoff` / `--co-author`
+//! flags. They are resolved once per run and appended to generated messages
+//! after post-processing, then checked again right before committing so an
+//! edit cannot drop them.
+
+use crate::config::{CommitTrailers, split_co_author};
+use crate::error::{GcopError, Result};
+use crate::git::{GitOperations, HistoryOptions};
+
+const SIGNED_OFF_BY: &str = "Signed-off-by";
+const CO_AUTHORED_BY: &str = "Co-authored-by";
+
+/// Resolve the trailer lines for this run.
+///
+/// Co-authors from the config come first, then those given with
+/// `--co-author`; duplicates (by email) are dropped. `Signed-off-by` is last,
+/// using git's `user.name` / `user.email`.
+pub(crate) fn resolve_trailers(
+    repo: &dyn GitOperations,
+    config: &CommitTrailers,
+    signoff: bool,
+    co_authors: &[String],
+) -> Result<Vec<String>> {
+    let mut trailers = Vec::new();
+    let mut seen_emails: Vec<String> = Vec::new();
+    let mut history_authors: Option<Vec<(String, String)>> = None;
+
+    for value in config.co_authors.iter().chain(co_authors) {
+        let (name, email) = split_co_author(value).ok_or_else(|| {
+            GcopError::InvalidInput(
+                rust_i18n::t!("trailers.invalid_co_author", value = value).to_string(),
+            )
+        })?;
+        let email_key = email.to_ascii_lowercase();
+        if seen_emails.contains(&email_key) {
+            continue;
+        }
+        seen_emails.push(email_key);
+
+        let name = match name {
+            Some(name) => name.to_string(),
+            None => {
+                let authors = match history_authors.as_mut() {
+                    Some(authors) => authors,
+                    None => history_authors.insert(history_authors_of(repo)?),
+                };
+                author_name(authors, email).ok_or_else(|| {
+                    GcopError::InvalidInput(
+                        rust_i18n::t!("trailers.unknown_co_author", email = email).to_string(),
+                    )
+                })?
+            }
+        };
+        trailers.push(format!("{}: {} <{}>", CO_AUTHORED_BY, name, email));
+    }
+
+    if config.signoff || signoff {
+        let identity = repo.get_user_identity()?.ok_or_else(|| {
+            GcopError::InvalidInput(rust_i18n::t!("trailers.no_identity").to_string())
+        })?;
+        trailers.push(format!("{}: {}", SIGNED_OFF_BY, identity));
+    }
+
+    Ok(trailers)
+}
+
+/// `(name, email)` of every commit author, newest first.
+fn history_authors_of(repo: &dyn GitOperations) -> Result<Vec<(String, String)>> {
+    if repo.is_empty()? {
+        return Ok(Vec::new());
+    }
+    Ok(repo
+        .get_commit_history(&HistoryOptions::default())?
+        .into_iter()
+        .map(|commit| (commit.author_name, commit.author_email))
+        .collect())
+}
+
+/// Most recent author name used with `email`.
+fn author_name(authors: &[(String, String)], email: &str) -> Option<String> {
+    authors
+        .iter()
+        .find(|(name, author_email)| !name.is_empty() && author_email.eq_ignore_ascii_case(email))
+        .map(|(name, _)| name.clone())
+}
+
+/// Append the trailers missing from `message`.
+///
+/// Trailers are added to an existing trailer block (such as a `Refs:` ticket
+/// footer) or as a new paragraph. A trailer already present is not repeated.
+pub(crate) fn apply_trailers(message: String, trailers: &[String]) -> String {
+    let missing: Vec<&String> = trailers
+        .iter()
+        .filter(|trailer| !has_trailer(&message, trailer))
+        .collect();
+    if missing.is_empty() {
+        return message;
+    }
+
+    let message = message.trim_end();
+    let separator = if super::ticket::ends_with_trailers(message) {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    let block = missing
+        .iter()
+        .map(|trailer| trailer.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{}{}{}", message, separator, block)
+}
+
+fn has_trailer(message: &str, trailer: &str) -> bool {
+    message
+        .lines()
+        .any(|line| line.trim().eq_ignore_ascii_case(trailer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{CommitInfo, MockGitOperations};
+    use chrono::Local;
+
+    fn commit_by(name: &str, email: &str) -> CommitInfo {
+        CommitInfo {
+            hash: "abc".to_string(),
+            parent_count: 1,
+            author_name: name.to_string(),
+            author_email: email.to_string(),
+            timestamp: Local::now(),
+            message: "feat: x".to_string(),
+        }
+    }
+
+    fn repo() -> MockGitOperations {
+        let mut repo = MockGitOperations::new();
+        repo.expect_get_user_identity()
+            .returning(|| Ok(Some("Dev <dev@example.com>".to_string())));
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_get_commit_history()
+            .returning(|_| Ok(vec![commit_by("Jane Doe", "Jane@Example.com")]));
+        repo
+    }
+
+    #[test]
+    fn test_resolve_trailers_order_and_dedup() {
+        let config = CommitTrailers {
+            signoff: false,
+            co_authors: vec!["Bob <bob@example.com>".to_string()],
+        };
+        let trailers = resolve_trailers(
+            &repo(),
+            &config,
+            true,
+            &[
+                "jane@example.com".to_string(),
+                "Robert <BOB@example.com>".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            trailers,
+            vec![
+                "Co-authored-by: Bob <bob@example.com>",
+                "Co-authored-by: Jane Doe <jane@example.com>",
+                "Signed-off-by: Dev <dev@example.com>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_trailers_none_configured() {
+        let mut repo = MockGitOperations::new();
+        repo.expect_get_user_identity().never();
+        let trailers = resolve_trailers(&repo, &CommitTrailers::default(), false, &[]).unwrap();
+        assert!(trailers.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_trailers_errors() {
+        let config = CommitTrailers::default();
+        // 历史中找不到的裸邮箱
+        let err = resolve_trailers(&repo(), &config, false, &["x@example.com".to_string()]);
+        assert!(matches!(err, Err(GcopError::InvalidInput(_))));
+        // 格式错误
+        let err = resolve_trailers(&repo(), &config, false, &["nobody".to_string()]);
+        assert!(matches!(err, Err(GcopError::InvalidInput(_))));
+        // 未配置 git 身份
+        let mut repo = MockGitOperations::new();
+        repo.expect_get_user_identity().returning(|| Ok(None));
+        let err = resolve_trailers(&repo, &config, true, &[]);
+        assert!(matches!(err, Err(GcopError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_apply_trailers_new_paragraph() {
+        let trailers = vec!["Signed-off-by: Dev <dev@example.com>".to_string()];
+        assert_eq!(
+            apply_trailers("feat: add login\n".to_string(), &trailers),
+            "feat: add login\n\nSigned-off-by: Dev <dev@example.com>"
+        );
+        assert_eq!(
+            apply_trailers("feat: add login\n\nBody.".to_string(), &trailers),
+            "feat: add login\n\nBody.\n\nSigned-off-by: Dev <dev@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_apply_trailers_joins_block_and_skips_present() {
+        let trailers = vec![
+            "Co-authored-by: Jane <jane@example.com>".to_string(),
+            "Signed-off-by: Dev <dev@example.com>".to_string(),
+        ];
+        let message =
+            "feat: add login\n\nBody.\n\nRefs: ABC-1\nsigned-off-by: Dev <dev@example.com>";
+        assert_eq!(
+            apply_trailers(message.to_string(), &trailers),
+            format!("{}\nCo-authored-by: Jane <jane@example.com>", message)
+        );
+        // 已全部存在时不修改
+        let full = apply_trailers("feat: x".to_string(), &trailers);
+        assert_eq!(apply_trailers(full.clone(), &trailers), full);
+    }
+
+    #[test]
+    fn test_apply_trailers_empty() {
+        assert_eq!(apply_trailers("feat: x\n".to_string(), &[]), "feat: x\n");
+    }
+}
@@ -0,0 +1,150 @@
+//! Undoing the last gcop-rs commit (`gcop-rs undo`).
+//!
+//! Equivalent to `git reset --soft HEAD~1`, but only when `HEAD` was created
+//! by gcop-rs: its reflog entry carries [`REFLOG_ACTION`]. The commit's
+//! changes are staged again and its message is saved as a commit session, so
+//! the next `gcop-rs commit` offers to resume with it.
+
+use super::commit_session::{self, CommitSession};
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::git::commit::REFLOG_ACTION;
+use crate::git::{GitOperations, repository::GitRepository};
+use crate::ui;
+
+/// Execute undo command
+///
+/// # Arguments
+/// * `yes` - Skip confirmation
+/// * `config` - application configuration
+pub fn run(yes: bool, config: &AppConfig) -> Result<()> {
+    let repo = GitRepository::open(Some(&config.file))?;
+    run_with_deps(yes, config, &repo)
+}
+
+fn run_with_deps(yes: bool, config: &AppConfig, repo: &dyn GitOperations) -> Result<()> {
+    let colored = config.ui.colored;
+
+    if repo.is_empty()? {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("undo.no_commits").to_string(),
+        ));
+    }
+    let head = repo.get_commit_info("HEAD")?;
+    let short = &head.hash[..head.hash.len().min(7)];
+    if !is_gcop_commit(repo.head_reflog_message()?.as_deref()) {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("undo.not_gcop_commit", hash = short).to_string(),
+        ));
+    }
+    if head.parent_count == 0 {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("undo.root_commit", hash = short).to_string(),
+        ));
+    }
+    let message = repo.get_commit_message("HEAD")?;
+
+    println!(
+        "{}",
+        ui::info(
+            &rust_i18n::t!("undo.target", hash = short, subject = head.message),
+            colored
+        )
+    );
+    if !yes && !ui::confirm(&rust_i18n::t!("undo.confirm"), false)? {
+        ui::warning(&rust_i18n::t!("undo.cancelled"), colored);
+        return Err(GcopError::UserCancelled);
+    }
+
+    repo.reset_soft("HEAD~1")?;
+
+    // Offer the undone message when the same changes are committed again
+    let mut session = CommitSession::new(&repo.get_staged_diff()?);
+    session.message = Some(message.trim().to_string());
+    session.save(&commit_session::state_path(&repo.get_git_dir()?));
+
+    ui::success(&rust_i18n::t!("undo.done", hash = short), colored);
+    if !ui::is_quiet() {
+        println!("{}", ui::info(&rust_i18n::t!("undo.retry_hint"), colored));
+    }
+    Ok(())
+}
+
+/// Whether a `HEAD` reflog message was written by a gcop-rs commit.
+fn is_gcop_commit(reflog_message: Option<&str>) -> bool {
+    reflog_message.is_some_and(|message| {
+        message
+            .strip_prefix(REFLOG_ACTION)
+            .is_some_and(|rest| rest.starts_with(':'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::{CommitInfo, MockGitOperations};
+    use chrono::Local;
+    use tempfile::TempDir;
+
+    const DIFF: &str = "diff --git a/a.rs b/a.rs\n+fn a() {}\n";
+
+    fn repo(reflog: Option<&'static str>, git_dir: &std::path::Path) -> MockGitOperations {
+        let mut repo = MockGitOperations::new();
+        repo.expect_is_empty().returning(|| Ok(false));
+        repo.expect_get_commit_info().returning(|_| {
+            Ok(CommitInfo {
+                hash: "abc1234def".to_string(),
+                parent_count: 1,
+                author_name: "Test".to_string(),
+                author_email: "test@example.com".to_string(),
+                timestamp: Local::now(),
+                message: "feat: add a".to_string(),
+            })
+        });
+        repo.expect_head_reflog_message()
+            .returning(move || Ok(reflog.map(str::to_string)));
+        repo.expect_get_commit_message()
+            .returning(|_| Ok("feat: add a\n\nBody\n".to_string()));
+        repo.expect_get_staged_diff()
+            .returning(|| Ok(DIFF.to_string()));
+        let git_dir = git_dir.to_path_buf();
+        repo.expect_get_git_dir()
+            .returning(move || Ok(git_dir.clone()));
+        repo
+    }
+
+    #[test]
+    fn test_undo_restores_session() {
+        let dir = TempDir::new().unwrap();
+        let mut repo = repo(Some("commit (gcop-rs): feat: add a"), dir.path());
+        repo.expect_reset_soft()
+            .withf(|rev| rev == "HEAD~1")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        run_with_deps(true, &AppConfig::default(), &repo).unwrap();
+
+        let path = commit_session::state_path(dir.path());
+        let session = CommitSession::load(&path, &CommitSession::new(DIFF).diff_hash).unwrap();
+        assert_eq!(session.message.as_deref(), Some("feat: add a\n\nBody"));
+    }
+
+    #[test]
+    fn test_undo_refuses_foreign_commit() {
+        let dir = TempDir::new().unwrap();
+        // 用户自己用 git commit 创建的提交
+        let mut repo = repo(Some("commit: feat: add a"), dir.path());
+        repo.expect_reset_soft().never();
+
+        let err = run_with_deps(true, &AppConfig::default(), &repo).unwrap_err();
+        assert!(matches!(err, GcopError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_is_gcop_commit() {
+        assert!(is_gcop_commit(Some("commit (gcop-rs): fix: x")));
+        assert!(!is_gcop_commit(Some("commit (amend): fix: x")));
+        assert!(!is_gcop_commit(Some("commit (gcop-rs)x: fix")));
+        assert!(!is_gcop_commit(None));
+    }
+}
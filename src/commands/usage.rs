@@ -0,0 +1,305 @@
+//! LLM usage report (`gcop-rs usage`).
+//!
+//! Aggregates the local usage ledger (see [`crate::llm::ledger`]) per day or
+//! month, provider and model, so token spend on commit generation and
+//! reviews can be tracked over time.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use serde::Serialize;
+
+use super::format::OutputFormat;
+use crate::cli::UsagePeriod;
+use crate::commands::json::{self, JsonOutput};
+use crate::config::AppConfig;
+use crate::error::{GcopError, Result};
+use crate::llm::ledger::{self, UsageRecord};
+use crate::ui;
+
+/// Aggregated usage of one period, provider and model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageRow {
+    /// `YYYY-MM-DD` (daily) or `YYYY-MM` (monthly), in local time.
+    pub period: String,
+    /// Provider name.
+    pub provider: String,
+    /// Model name.
+    pub model: String,
+    /// Generations recorded.
+    pub generations: u64,
+    /// LLM calls.
+    pub calls: u64,
+    /// Prompt tokens.
+    pub prompt_tokens: u64,
+    /// Completion tokens.
+    pub completion_tokens: u64,
+    /// Estimated cost; `None` when no record had pricing configured.
+    pub cost: Option<f64>,
+}
+
+impl UsageRow {
+    fn add(&mut self, record: &UsageRecord) {
+        self.generations += 1;
+        self.calls += u64::from(record.calls);
+        self.prompt_tokens += record.prompt_tokens;
+        self.completion_tokens += record.completion_tokens;
+        if let Some(cost) = record.cost {
+            *self.cost.get_or_insert(0.0) += cost;
+        }
+    }
+}
+
+/// Usage report (JSON `data` payload).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageReport {
+    /// `daily` or `monthly`.
+    pub period: &'static str,
+    /// Rows ordered by period, provider and model.
+    pub rows: Vec<UsageRow>,
+    /// Sum of all rows (`period`, `provider` and `model` are empty).
+    pub total: UsageRow,
+}
+
+/// Runs the `usage` command, printing JSON errors when requested.
+pub fn run(
+    period: UsagePeriod,
+    since: Option<&str>,
+    format: OutputFormat,
+    config: &AppConfig,
+) -> Result<()> {
+    let result = report(period, since, format, config);
+    if let Err(ref e) = result
+        && format.is_json()
+    {
+        let _ = json::output_json_error::<UsageReport>(e);
+    }
+    result
+}
+
+fn report(
+    period: UsagePeriod,
+    since: Option<&str>,
+    format: OutputFormat,
+    config: &AppConfig,
+) -> Result<()> {
+    format.reject_stats_only()?;
+    let since = since
+        .map(|v| super::stats::parse_date_bound(v, false))
+        .transpose()?;
+    let path = ledger::ledger_path()
+        .ok_or_else(|| GcopError::Config(rust_i18n::t!("usage.no_data_dir").to_string()))?;
+    let records = ledger::load(&path)?;
+    let report = aggregate(&records, period, since);
+
+    if format.is_json() {
+        let output = JsonOutput {
+            success: true,
+            data: Some(&report),
+            error: None,
+            meta: None,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let colored = format.effective_colored(config.ui.colored);
+    if report.rows.is_empty() {
+        println!(
+            "{}",
+            ui::info(
+                &rust_i18n::t!("usage.empty", path = path.display()),
+                colored
+            )
+        );
+        return Ok(());
+    }
+    output_text(&report, colored);
+    Ok(())
+}
+
+/// Groups `records` by period, provider and model, skipping records before `since`.
+pub fn aggregate(
+    records: &[UsageRecord],
+    period: UsagePeriod,
+    since: Option<DateTime<Local>>,
+) -> UsageReport {
+    let key_format = match period {
+        UsagePeriod::Daily => "%Y-%m-%d",
+        UsagePeriod::Monthly => "%Y-%m",
+    };
+
+    let mut groups: BTreeMap<(String, String, String), UsageRow> = BTreeMap::new();
+    let mut total = UsageRow::default();
+    for record in records {
+        if since.is_some_and(|since| record.timestamp < since) {
+            continue;
+        }
+        let key = (
+            record.timestamp.format(key_format).to_string(),
+            record.provider.clone(),
+            record.model.clone(),
+        );
+        groups
+            .entry(key)
+            .or_insert_with_key(|(period, provider, model)| UsageRow {
+                period: period.clone(),
+                provider: provider.clone(),
+                model: model.clone(),
+                ..Default::default()
+            })
+            .add(record);
+        total.add(record);
+    }
+
+    UsageReport {
+        period: match period {
+            UsagePeriod::Daily => "daily",
+            UsagePeriod::Monthly => "monthly",
+        },
+        rows: groups.into_values().collect(),
+        total,
+    }
+}
+
+fn format_cost(cost: Option<f64>) -> String {
+    cost.map(|c| format!("{:.4}", c))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn output_text(report: &UsageReport, colored: bool) {
+    println!("{}", ui::info(&rust_i18n::t!("usage.title"), colored));
+    println!("{}", "─".repeat(72));
+
+    let period_width = report
+        .rows
+        .iter()
+        .map(|r| r.period.len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+    let provider_width = report
+        .rows
+        .iter()
+        .map(|r| r.provider.len())
+        .max()
+        .unwrap_or(0)
+        .max(8);
+    let model_width = report
+        .rows
+        .iter()
+        .map(|r| r.model.len())
+        .max()
+        .unwrap_or(0)
+        .max(5);
+
+    let header = format!(
+        "  {:<period_width$}  {:<provider_width$}  {:<model_width$}  {:>6}  {:>10}  {:>10}  {:>10}",
+        rust_i18n::t!("usage.col_period"),
+        rust_i18n::t!("usage.col_provider"),
+        rust_i18n::t!("usage.col_model"),
+        rust_i18n::t!("usage.col_calls"),
+        rust_i18n::t!("usage.col_prompt"),
+        rust_i18n::t!("usage.col_completion"),
+        rust_i18n::t!("usage.col_cost"),
+    );
+    if colored {
+        println!("{}", header.bold());
+    } else {
+        println!("{}", header);
+    }
+
+    for row in &report.rows {
+        println!(
+            "  {:<period_width$}  {:<provider_width$}  {:<model_width$}  {:>6}  {:>10}  {:>10}  {:>10}",
+            row.period,
+            row.provider,
+            row.model,
+            row.calls,
+            row.prompt_tokens,
+            row.completion_tokens,
+            format_cost(row.cost),
+        );
+    }
+
+    println!("{}", "─".repeat(72));
+    let total = &report.total;
+    println!(
+        "  {:<width$}  {:>6}  {:>10}  {:>10}  {:>10}",
+        rust_i18n::t!("usage.total"),
+        total.calls,
+        total.prompt_tokens,
+        total.completion_tokens,
+        format_cost(total.cost),
+        width = period_width + provider_width + model_width + 4,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn record(day: u32, provider: &str, tokens: u64, cost: Option<f64>) -> UsageRecord {
+        UsageRecord {
+            timestamp: Local.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+            command: "commit".to_string(),
+            provider: provider.to_string(),
+            model: format!("{}-model", provider),
+            calls: 1,
+            prompt_tokens: tokens,
+            completion_tokens: tokens / 10,
+            cost,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_daily_and_monthly() {
+        let records = vec![
+            record(2, "openai", 1_000, None),
+            record(1, "claude", 2_000, Some(0.01)),
+            record(1, "claude", 500, Some(0.002)),
+            record(2, "claude", 100, None),
+        ];
+
+        let daily = aggregate(&records, UsagePeriod::Daily, None);
+        let keys: Vec<_> = daily
+            .rows
+            .iter()
+            .map(|r| (r.period.as_str(), r.provider.as_str(), r.generations))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                ("2026-03-01", "claude", 2),
+                ("2026-03-02", "claude", 1),
+                ("2026-03-02", "openai", 1),
+            ]
+        );
+        assert_eq!(daily.rows[0].prompt_tokens, 2_500);
+        assert!((daily.rows[0].cost.unwrap() - 0.012).abs() < 1e-12);
+        // 没有价格信息的分组不显示费用
+        assert_eq!(daily.rows[2].cost, None);
+
+        let monthly = aggregate(&records, UsagePeriod::Monthly, None);
+        assert_eq!(monthly.period, "monthly");
+        assert_eq!(monthly.rows.len(), 2);
+        assert_eq!(monthly.rows[0].period, "2026-03");
+        assert_eq!(monthly.rows[0].calls, 3);
+        assert_eq!(monthly.total.calls, 4);
+        assert_eq!(monthly.total.prompt_tokens, 3_600);
+    }
+
+    #[test]
+    fn test_aggregate_since() {
+        let records = vec![
+            record(1, "claude", 2_000, None),
+            record(5, "claude", 100, None),
+        ];
+        let since = Local.with_ymd_and_hms(2026, 3, 3, 0, 0, 0).unwrap();
+        let report = aggregate(&records, UsagePeriod::Daily, Some(since));
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.total.prompt_tokens, 100);
+    }
+}
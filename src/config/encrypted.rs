@@ -0,0 +1,232 @@
+//! Encrypted user config (`config.toml.enc`), for dotfiles synced to public
+//! repositories.
+//!
+//! Encryption goes through the `age` or `gpg` command-line tools; the format
+//! is detected from the file header. The settings live in the environment,
+//! since the config itself is encrypted:
+//! - `GCOP_AGE_IDENTITY`: age identity file used to decrypt and re-encrypt
+//!   (to its recipient); without it age asks for the passphrase
+//! - `GCOP_GPG_RECIPIENT`: key to re-encrypt to (default: your default key);
+//!   gpg asks for passphrases through gpg-agent's pinentry
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::error::{GcopError, Result};
+
+/// File name of the encrypted user config, used when `config.toml` is absent
+pub const ENCRYPTED_CONFIG_FILE: &str = "config.toml.enc";
+
+/// Encryption tool of an encrypted config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cipher {
+    Age,
+    Gpg,
+}
+
+impl Cipher {
+    /// Detects the tool from the first bytes of the file (anything not age is gpg).
+    fn detect(header: &[u8]) -> Self {
+        if header.starts_with(b"age-encryption.org/")
+            || header.starts_with(b"-----BEGIN AGE ENCRYPTED FILE-----")
+        {
+            Cipher::Age
+        } else {
+            Cipher::Gpg
+        }
+    }
+
+    fn program(self) -> &'static str {
+        match self {
+            Cipher::Age => "age",
+            Cipher::Gpg => "gpg",
+        }
+    }
+
+    /// Arguments decrypting `path` to stdout
+    fn decrypt_args(self, path: &Path, age_identity: Option<&str>) -> Vec<String> {
+        let mut args: Vec<String> = match self {
+            Cipher::Age => {
+                let mut args = vec!["--decrypt".to_string()];
+                if let Some(identity) = age_identity {
+                    args.extend(["--identity".to_string(), identity.to_string()]);
+                }
+                args
+            }
+            Cipher::Gpg => vec!["--quiet".to_string(), "--decrypt".to_string()],
+        };
+        args.push(path.display().to_string());
+        args
+    }
+
+    /// Arguments encrypting stdin (armored) to `output`
+    fn encrypt_args(
+        self,
+        output: &Path,
+        age_identity: Option<&str>,
+        gpg_recipient: Option<&str>,
+    ) -> Vec<String> {
+        let mut args: Vec<String> = match self {
+            Cipher::Age => {
+                let mut args = vec!["--encrypt".to_string(), "--armor".to_string()];
+                match age_identity {
+                    Some(identity) => args.extend(["--identity".to_string(), identity.to_string()]),
+                    None => args.push("--passphrase".to_string()),
+                }
+                args
+            }
+            Cipher::Gpg => {
+                let mut args = ["--quiet", "--yes", "--armor", "--encrypt"]
+                    .map(String::from)
+                    .to_vec();
+                match gpg_recipient {
+                    Some(recipient) => {
+                        args.extend(["--recipient".to_string(), recipient.to_string()])
+                    }
+                    None => args.push("--default-recipient-self".to_string()),
+                }
+                args
+            }
+        };
+        args.extend(["--output".to_string(), output.display().to_string()]);
+        args
+    }
+}
+
+/// Whether `path` is an encrypted config file (`.enc`)
+pub fn is_encrypted(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "enc")
+}
+
+/// Reads a config file, decrypting `.enc` files.
+pub fn read_config_file(path: &Path) -> Result<String> {
+    if !is_encrypted(path) {
+        return Ok(std::fs::read_to_string(path)?);
+    }
+
+    let cipher = Cipher::detect(&std::fs::read(path)?);
+    let output = Command::new(cipher.program())
+        .args(cipher.decrypt_args(path, env_value("GCOP_AGE_IDENTITY").as_deref()))
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| tool_error(cipher, e))?;
+    if !output.status.success() {
+        return Err(GcopError::Config(
+            rust_i18n::t!(
+                "config.decrypt_failed",
+                path = path.display().to_string(),
+                error = String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .to_string(),
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| GcopError::Config(e.to_string()))
+}
+
+/// Writes a config file, re-encrypting `.enc` files with the tool they were
+/// encrypted with.
+///
+/// The ciphertext is written next to the file and renamed over it, so a
+/// failed encryption leaves the previous file intact.
+pub fn write_config_file(path: &Path, content: &str) -> Result<()> {
+    if !is_encrypted(path) {
+        return Ok(std::fs::write(path, content)?);
+    }
+
+    let cipher = match std::fs::read(path) {
+        Ok(existing) => Cipher::detect(&existing),
+        Err(_) if env_value("GCOP_AGE_IDENTITY").is_some() => Cipher::Age,
+        Err(_) => Cipher::Gpg,
+    };
+    let tmp = path.with_extension("enc.tmp");
+    let mut child = Command::new(cipher.program())
+        .args(cipher.encrypt_args(
+            &tmp,
+            env_value("GCOP_AGE_IDENTITY").as_deref(),
+            env_value("GCOP_GPG_RECIPIENT").as_deref(),
+        ))
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| tool_error(cipher, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(content.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(GcopError::Config(
+            rust_i18n::t!(
+                "config.encrypt_failed",
+                path = path.display().to_string(),
+                error = String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .to_string(),
+        ));
+    }
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+fn tool_error(cipher: Cipher, error: std::io::Error) -> GcopError {
+    GcopError::Config(
+        rust_i18n::t!(
+            "config.encryption_tool_failed",
+            tool = cipher.program(),
+            error = error.to_string()
+        )
+        .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cipher_detection_and_arguments() {
+        assert_eq!(
+            Cipher::detect(b"age-encryption.org/v1\n-> X25519 abc"),
+            Cipher::Age
+        );
+        assert_eq!(
+            Cipher::detect(b"-----BEGIN AGE ENCRYPTED FILE-----\n"),
+            Cipher::Age
+        );
+        assert_eq!(
+            Cipher::detect(b"-----BEGIN PGP MESSAGE-----\n"),
+            Cipher::Gpg
+        );
+
+        let path = Path::new("config.toml.enc");
+        assert!(is_encrypted(path));
+        assert!(!is_encrypted(Path::new("config.toml")));
+        assert_eq!(
+            Cipher::Age.decrypt_args(path, Some("key.txt")),
+            ["--decrypt", "--identity", "key.txt", "config.toml.enc"]
+        );
+        assert_eq!(
+            Cipher::Age.encrypt_args(Path::new("out"), None, None),
+            ["--encrypt", "--armor", "--passphrase", "--output", "out"]
+        );
+        assert_eq!(
+            Cipher::Gpg.encrypt_args(Path::new("out"), None, Some("me@example.com")),
+            [
+                "--quiet",
+                "--yes",
+                "--armor",
+                "--encrypt",
+                "--recipient",
+                "me@example.com",
+                "--output",
+                "out"
+            ]
+        );
+    }
+}
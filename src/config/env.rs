@@ -0,0 +1,241 @@
+//! Environment variables recognized by gcop-rs (`config env`).
+//!
+//! Three families are easy to mix up:
+//! - `GCOP__SECTION__KEY` overrides any config key (`__` separates the path)
+//! - `GCOP_*` (single underscore) are standalone switches such as CI mode
+//! - provider-native variables (`ANTHROPIC_BASE_URL`, `GITLAB_TOKEN`, ...) are
+//!   fallbacks used only when the matching config field is unset
+
+use serde::Serialize;
+
+use crate::llm::provider::utils::mask_api_key;
+
+/// Family of an environment variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvVarGroup {
+    /// `GCOP__*` config key overrides
+    Override,
+    /// `CI=1` with `GCOP_CI_*`
+    Ci,
+    /// Other `GCOP_*` switches
+    Gcop,
+    /// Provider and forge variables used as fallbacks
+    Native,
+    /// Git, editor and terminal variables
+    System,
+}
+
+/// A recognized environment variable and its current value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EnvVarStatus {
+    /// Variable name
+    pub name: String,
+    /// Variable family
+    pub group: EnvVarGroup,
+    /// Config field, flag or behavior it maps to
+    pub target: String,
+    /// Whether the value is a secret (masked in `value`)
+    pub secret: bool,
+    /// Current value (masked for secrets); `None` when unset
+    pub value: Option<String>,
+}
+
+/// Fixed variables: name, family, target and whether the value is secret
+const KNOWN_VARS: &[(&str, EnvVarGroup, &str, bool)] = &[
+    ("CI", EnvVarGroup::Ci, "CI mode when `1`", false),
+    (
+        "GCOP_CI_PROVIDER",
+        EnvVarGroup::Ci,
+        "llm.providers.ci.api_style",
+        false,
+    ),
+    (
+        "GCOP_CI_API_KEY",
+        EnvVarGroup::Ci,
+        "llm.providers.ci.api_key",
+        true,
+    ),
+    (
+        "GCOP_CI_MODEL",
+        EnvVarGroup::Ci,
+        "llm.providers.ci.model",
+        false,
+    ),
+    (
+        "GCOP_CI_ENDPOINT",
+        EnvVarGroup::Ci,
+        "llm.providers.ci.endpoint",
+        false,
+    ),
+    ("GCOP_PROFILE", EnvVarGroup::Gcop, "--profile", false),
+    (
+        "GCOP_INTERACTIVE",
+        EnvVarGroup::Gcop,
+        "terminal detection (`0` / `1`)",
+        false,
+    ),
+    (
+        "GCOP_AGE_IDENTITY",
+        EnvVarGroup::Gcop,
+        "age identity for config.toml.enc",
+        false,
+    ),
+    (
+        "GCOP_GPG_RECIPIENT",
+        EnvVarGroup::Gcop,
+        "gpg key config.toml.enc is encrypted to",
+        false,
+    ),
+    (
+        "ANTHROPIC_BASE_URL",
+        EnvVarGroup::Native,
+        "endpoint of `claude` providers",
+        false,
+    ),
+    (
+        "OPENAI_BASE_URL",
+        EnvVarGroup::Native,
+        "endpoint of `openai` providers",
+        false,
+    ),
+    (
+        "OLLAMA_BASE_URL",
+        EnvVarGroup::Native,
+        "endpoint of `ollama` providers",
+        false,
+    ),
+    (
+        "GEMINI_BASE_URL",
+        EnvVarGroup::Native,
+        "endpoint of `gemini` providers",
+        false,
+    ),
+    ("GITLAB_TOKEN", EnvVarGroup::Native, "forge.token", true),
+    ("GIT_EDITOR", EnvVarGroup::System, "message editor", false),
+    ("VISUAL", EnvVarGroup::System, "message editor", false),
+    ("EDITOR", EnvVarGroup::System, "message editor", false),
+    (
+        "GIT_WORK_TREE",
+        EnvVarGroup::System,
+        "repository work tree",
+        false,
+    ),
+    (
+        "CLICOLOR_FORCE",
+        EnvVarGroup::System,
+        "colors without a terminal",
+        false,
+    ),
+    (
+        "RUST_LOG",
+        EnvVarGroup::System,
+        "terminal log filter",
+        false,
+    ),
+];
+
+/// Status of every recognized variable in the current environment
+pub fn env_status() -> Vec<EnvVarStatus> {
+    let vars: Vec<(String, String)> = std::env::vars_os()
+        .filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?)))
+        .collect();
+    env_status_from(&vars)
+}
+
+/// Status of every recognized variable in `vars`
+///
+/// Fixed variables are always listed; `GCOP__*` overrides only when set.
+pub fn env_status_from(vars: &[(String, String)]) -> Vec<EnvVarStatus> {
+    let lookup = |name: &str| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+
+    let mut overrides: Vec<EnvVarStatus> = vars
+        .iter()
+        .filter_map(|(name, value)| {
+            let path = name.strip_prefix("GCOP__")?;
+            let target = path
+                .split("__")
+                .map(str::to_lowercase)
+                .collect::<Vec<_>>()
+                .join(".");
+            let secret = target.ends_with("api_key") || target.ends_with("token");
+            Some(EnvVarStatus {
+                name: name.clone(),
+                group: EnvVarGroup::Override,
+                target,
+                secret,
+                value: Some(display_value(value, secret)),
+            })
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.name.cmp(&b.name));
+
+    overrides
+        .into_iter()
+        .chain(
+            KNOWN_VARS
+                .iter()
+                .map(|&(name, group, target, secret)| EnvVarStatus {
+                    name: name.to_string(),
+                    group,
+                    target: target.to_string(),
+                    secret,
+                    value: lookup(name).map(|v| display_value(&v, secret)),
+                }),
+        )
+        .collect()
+}
+
+fn display_value(value: &str, secret: bool) -> String {
+    if secret {
+        mask_api_key(value)
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_env_status_maps_and_masks() {
+        let status = env_status_from(&vars(&[
+            ("GCOP__UI__COLORED", "false"),
+            (
+                "GCOP__LLM__PROVIDERS__CLAUDE__API_KEY",
+                "sk-ant-api03-abcdefgh",
+            ),
+            ("GCOP_CI_API_KEY", "sk-1234567890"),
+            ("OPENAI_BASE_URL", "https://gateway.example.com"),
+            ("HOME", "/root"),
+        ]));
+
+        // Overrides come first, sorted by name
+        assert_eq!(status[0].name, "GCOP__LLM__PROVIDERS__CLAUDE__API_KEY");
+        assert_eq!(status[0].target, "llm.providers.claude.api_key");
+        assert_eq!(status[0].value.as_deref(), Some("sk-a...efgh"));
+        assert_eq!(status[1].target, "ui.colored");
+        assert_eq!(status[1].value.as_deref(), Some("false"));
+
+        let find = |name: &str| status.iter().find(|s| s.name == name).unwrap();
+        assert_eq!(
+            find("GCOP_CI_API_KEY").value.as_deref(),
+            Some("sk-1...7890")
+        );
+        assert_eq!(
+            find("OPENAI_BASE_URL").value.as_deref(),
+            Some("https://gateway.example.com")
+        );
+        assert_eq!(find("GCOP_PROFILE").value, None);
+        assert!(!status.iter().any(|s| s.name == "HOME"));
+        assert_eq!(status.len(), 2 + KNOWN_VARS.len());
+    }
+}
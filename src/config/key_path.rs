@@ -0,0 +1,240 @@
+//! Dotted key path access for `config get/set/unset`.
+//!
+//! Writes go through [`toml_edit`] so comments, ordering and formatting of
+//! the user's config file survive the edit. Reads work on the effective
+//! configuration serialized as a [`toml::Value`].
+
+use toml_edit::{DocumentMut, Item, Table, TableLike, Value};
+
+use crate::error::{GcopError, Result};
+
+/// Splits a dotted key path (`llm.providers.openai.model`) into segments.
+pub(crate) fn parse_key(key: &str) -> Result<Vec<&str>> {
+    let segments: Vec<&str> = key.split('.').map(str::trim).collect();
+    if segments.iter().any(|segment| segment.is_empty()) {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("config.key_invalid", key = key).to_string(),
+        ));
+    }
+    Ok(segments)
+}
+
+/// Parses a command-line value as a TOML literal.
+///
+/// `true`, `42`, `0.3` and `["a", "b"]` keep their TOML types; anything that
+/// is not a valid TOML value is treated as a plain string.
+pub(crate) fn parse_value(raw: &str) -> Value {
+    raw.trim()
+        .parse::<Value>()
+        .map(|mut value| {
+            value.decor_mut().clear();
+            value
+        })
+        .unwrap_or_else(|_| Value::from(raw))
+}
+
+/// Sets `path` to `value`, creating intermediate tables as needed.
+///
+/// New intermediate tables are implicit, so only the innermost one gets a
+/// `[section]` header. Replacing an existing table is refused.
+pub(crate) fn set_value(doc: &mut DocumentMut, path: &[&str], value: Value) -> Result<()> {
+    let (leaf, parents) = path.split_last().expect("key path is never empty");
+    let mut table = doc.as_table_mut();
+    for (depth, segment) in parents.iter().enumerate() {
+        let entry = table.entry(segment).or_insert_with(|| {
+            let mut new_table = Table::new();
+            new_table.set_implicit(true);
+            Item::Table(new_table)
+        });
+        table = entry.as_table_mut().ok_or_else(|| {
+            GcopError::InvalidInput(
+                rust_i18n::t!(
+                    "config.key_not_table",
+                    key = path.join("."),
+                    parent = path[..=depth].join(".")
+                )
+                .to_string(),
+            )
+        })?;
+    }
+    // The innermost table carries the key, so it gets a header of its own.
+    table.set_implicit(false);
+
+    if table.get(leaf).is_some_and(|item| item.is_table_like()) {
+        return Err(GcopError::InvalidInput(
+            rust_i18n::t!("config.key_is_table", key = path.join(".")).to_string(),
+        ));
+    }
+    match table.get_mut(leaf).and_then(Item::as_value_mut) {
+        // Keep the existing decor (inline comments, spacing).
+        Some(existing) => {
+            let decor = existing.decor().clone();
+            *existing = value;
+            *existing.decor_mut() = decor;
+        }
+        None => {
+            table.insert(leaf, Item::Value(value));
+        }
+    }
+    Ok(())
+}
+
+/// Removes `path`; returns `false` when the key does not exist.
+pub(crate) fn unset_value(doc: &mut DocumentMut, path: &[&str]) -> bool {
+    let (leaf, parents) = path.split_last().expect("key path is never empty");
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    for segment in parents {
+        match table.get_mut(segment).and_then(Item::as_table_like_mut) {
+            Some(inner) => table = inner,
+            None => return false,
+        }
+    }
+    table.remove(leaf).is_some()
+}
+
+/// Looks up `path` in a serialized configuration.
+pub(crate) fn lookup<'a>(value: &'a toml::Value, path: &[&str]) -> Option<&'a toml::Value> {
+    path.iter()
+        .try_fold(value, |current, segment| current.get(*segment))
+}
+
+/// Renders the value found at `path` for display.
+///
+/// Strings are printed raw, everything else as a TOML literal. Tables are
+/// printed as a TOML document under their full path, so nested sections keep
+/// their real headers (`[commit.convention]`, not `[convention]`).
+pub(crate) fn format_value(path: &[&str], value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        toml::Value::Table(_) => {
+            let wrapped = path.iter().rev().fold(value.clone(), |inner, segment| {
+                let mut table = toml::Table::new();
+                table.insert(segment.to_string(), inner);
+                toml::Value::Table(table)
+            });
+            toml::to_string(&wrapped)
+                .map(|s| s.trim_end().to_string())
+                .unwrap_or_default()
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const CONFIG: &str = r#"# gcop-rs config
+[llm]
+default_provider = "claude" # main provider
+
+[llm.providers.claude]
+api_key = "sk-test"
+"#;
+
+    fn doc() -> DocumentMut {
+        CONFIG.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("ui.language").unwrap(), vec!["ui", "language"]);
+        assert!(parse_key("ui..language").is_err());
+        assert!(parse_key("").is_err());
+    }
+
+    #[test]
+    fn test_parse_value_types() {
+        assert_eq!(parse_value("true").as_bool(), Some(true));
+        assert_eq!(parse_value("42").as_integer(), Some(42));
+        assert_eq!(parse_value("0.3").as_float(), Some(0.3));
+        assert_eq!(
+            parse_value("[\"a\", \"b\"]").as_array().map(|a| a.len()),
+            Some(2)
+        );
+        assert_eq!(parse_value("\"quoted\"").as_str(), Some("quoted"));
+        // 非法 TOML 字面量按字符串处理
+        assert_eq!(parse_value("openai").as_str(), Some("openai"));
+        assert_eq!(parse_value("use Chinese").as_str(), Some("use Chinese"));
+    }
+
+    #[test]
+    fn test_set_existing_key_preserves_comments() {
+        let mut doc = doc();
+        set_value(
+            &mut doc,
+            &["llm", "default_provider"],
+            parse_value("openai"),
+        )
+        .unwrap();
+        let out = doc.to_string();
+        assert!(out.starts_with("# gcop-rs config\n"));
+        assert!(out.contains("default_provider = \"openai\" # main provider"));
+        assert!(out.contains("api_key = \"sk-test\""));
+    }
+
+    #[test]
+    fn test_set_creates_tables() {
+        let mut doc = doc();
+        set_value(&mut doc, &["ui", "streaming"], parse_value("false")).unwrap();
+        set_value(
+            &mut doc,
+            &["llm", "providers", "openai", "model"],
+            parse_value("gpt-4o"),
+        )
+        .unwrap();
+        let out = doc.to_string();
+        assert!(out.contains("[ui]\nstreaming = false\n"));
+        assert!(out.contains("[llm.providers.openai]\nmodel = \"gpt-4o\"\n"));
+        assert!(!out.contains("[llm.providers]\n"));
+    }
+
+    #[test]
+    fn test_set_rejects_table_conflicts() {
+        let mut doc = doc();
+        // 不能覆盖整个表
+        assert!(set_value(&mut doc, &["llm"], parse_value("x")).is_err());
+        // 不能穿过标量
+        assert!(
+            set_value(
+                &mut doc,
+                &["llm", "default_provider", "x"],
+                parse_value("1")
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unset() {
+        let mut doc = doc();
+        assert!(unset_value(
+            &mut doc,
+            &["llm", "providers", "claude", "api_key"]
+        ));
+        assert!(!doc.to_string().contains("api_key"));
+        assert!(!unset_value(&mut doc, &["llm", "missing"]));
+        assert!(!unset_value(&mut doc, &["nope", "key"]));
+    }
+
+    #[test]
+    fn test_lookup_and_format() {
+        let value: toml::Value = toml::from_str(CONFIG).unwrap();
+        let provider = lookup(&value, &["llm", "default_provider"]).unwrap();
+        assert_eq!(
+            format_value(&["llm", "default_provider"], provider),
+            "claude"
+        );
+        let path = ["llm", "providers"];
+        let table = lookup(&value, &path).unwrap();
+        assert_eq!(
+            format_value(&path, table),
+            "[llm.providers.claude]\napi_key = \"sk-test\""
+        );
+        assert!(lookup(&value, &["llm", "missing"]).is_none());
+
+        let number = toml::Value::Integer(3);
+        assert_eq!(format_value(&["n"], &number), "3");
+    }
+}
@@ -0,0 +1,141 @@
+//! Provider API keys in the OS keychain.
+//!
+//! Goes through the platform's command-line tool instead of linking a native
+//! library: `security` on macOS and `secret-tool` (libsecret) on Linux.
+//! Elsewhere there is no keychain support and keys stay in the config file.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::error::{GcopError, Result};
+
+/// Service the keys are stored under; the account is the provider name
+const SERVICE: &str = "gcop-rs";
+
+/// Keychain tool of this platform, if installed
+fn tool() -> Option<&'static str> {
+    let name = if cfg!(target_os = "macos") {
+        "security"
+    } else if cfg!(target_os = "linux") {
+        "secret-tool"
+    } else {
+        return None;
+    };
+    which::which(name).ok().map(|_| name)
+}
+
+/// Whether API keys can be stored in the keychain
+pub fn is_available() -> bool {
+    tool().is_some()
+}
+
+/// Stores `key` as the API key of `provider`, replacing a previous one.
+///
+/// `provider` is the name under `[llm.providers]`, which is what
+/// [`lookup`] is called with. The key is passed on stdin, never on the
+/// command line where other users could see it.
+pub fn store(provider: &str, key: &str) -> Result<()> {
+    #[cfg(test)]
+    {
+        test_store::with(|keys| keys.insert(provider.to_string(), key.to_string()));
+        return Ok(());
+    }
+    #[cfg_attr(test, allow(unreachable_code))]
+    let tool = tool()
+        .ok_or_else(|| GcopError::Config(rust_i18n::t!("keychain.unavailable").to_string()))?;
+    let label = format!("{} {}", SERVICE, provider);
+    let mut command = Command::new(tool);
+    let input = if cfg!(target_os = "macos") {
+        // `security -i` reads its command from stdin, so `-w <key>` stays out of argv
+        command.arg("-i");
+        format!(
+            "add-generic-password -U -s {} -a {} -w {}\n",
+            security_quote(SERVICE),
+            security_quote(provider),
+            security_quote(key)
+        )
+    } else {
+        // secret-tool reads the secret from stdin
+        command.args([
+            "store", "--label", &label, "service", SERVICE, "provider", provider,
+        ]);
+        key.to_string()
+    };
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(GcopError::Config(
+            rust_i18n::t!(
+                "keychain.store_failed",
+                error = String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Double-quotes `value` for a `security -i` command line.
+fn security_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// API key of `provider` from the keychain, if one is stored
+pub fn lookup(provider: &str) -> Option<String> {
+    #[cfg(test)]
+    return test_store::with(|keys| keys.get(provider).cloned());
+    #[cfg_attr(test, allow(unreachable_code))]
+    let tool = tool()?;
+    let mut command = Command::new(tool);
+    if cfg!(target_os = "macos") {
+        command.args(["find-generic-password", "-s", SERVICE, "-a", provider, "-w"]);
+    } else {
+        command.args(["lookup", "service", SERVICE, "provider", provider]);
+    }
+
+    let output = command
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let key = String::from_utf8(output.stdout).ok()?;
+    let key = key.trim_end_matches(['\r', '\n']);
+    (!key.is_empty()).then(|| key.to_string())
+}
+
+/// In-memory keychain for tests, so they never touch the real one.
+#[cfg(test)]
+pub(crate) mod test_store {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    thread_local! {
+        static KEYS: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+    }
+
+    pub(crate) fn with<T>(f: impl FnOnce(&mut HashMap<String, String>) -> T) -> T {
+        KEYS.with(|keys| f(&mut keys.borrow_mut()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_quote() {
+        assert_eq!(security_quote("sk-1"), "\"sk-1\"");
+        assert_eq!(security_quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
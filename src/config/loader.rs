@@ -3,37 +3,81 @@
 //! Configuration is assembled from user/project files, environment variables,
 //! and optional CI overrides.
 
-use config::{Config, Environment, File};
+use config::builder::DefaultState;
+use config::{Config, ConfigBuilder, Environment, File, FileFormat};
 use directories::ProjectDirs;
 use std::path::{Path, PathBuf};
 
+use super::encrypted::{ENCRYPTED_CONFIG_FILE, is_encrypted, read_config_file};
+use super::remote::{PRESET_CACHE_DIR, extends_of, load_preset};
 use super::structs::{AppConfig, ProviderConfig};
-use crate::error::Result;
+use crate::error::{GcopError, Result};
 
 /// Loads application configuration.
 ///
 /// Effective precedence (high to low):
 /// 1. CI overrides (`CI=1` + `GCOP_CI_*`, applied after deserialization)
-/// 2. Environment variables (`GCOP__*`, with `__` as nesting separator)
+/// 2. Selected profile (`[profiles.<name>]`, see [`load_config_with_profile`])
+/// 3. Environment variables (`GCOP__*`, with `__` as nesting separator)
 ///    - For example: `GCOP__LLM__DEFAULT_PROVIDER=openai`
 ///    - For example: `GCOP__UI__COLORED=false`
-/// 3. Project config (`.gcop/config.toml`, discovered from repo root)
-/// 4. User config file (`config.toml` in platform config directory)
-/// 5. Rust defaults (`Default` + `serde(default)`)
+/// 4. Project config (`.gcop/config.toml`, discovered from repo root)
+/// 5. Remote preset the project config `extends` (fetched and cached)
+/// 6. User config file (`config.toml` in platform config directory, or the
+///    encrypted `config.toml.enc` when there is no `config.toml`)
+/// 7. Rust defaults (`Default` + `serde(default)`)
 ///
-/// Sources are added from low to high priority (`user -> project -> env`)
+/// Sources are added from low to high priority (`user -> preset -> project -> env`)
 /// because later `config-rs` sources override earlier ones.
-/// CI overrides are applied last.
+/// The profile and CI overrides are applied last.
+///
+/// Loading warnings are dropped; see [`load_config_with_warnings`].
 pub fn load_config() -> Result<AppConfig> {
-    load_config_from_path(get_config_path(), find_project_config())
+    load_config_with_profile(None)
+}
+
+/// Loads application configuration with a named profile merged on top.
+///
+/// `profile` comes from `--profile`; when `None`, the `GCOP_PROFILE`
+/// environment variable is used. Without either, no profile is applied.
+pub fn load_config_with_profile(profile: Option<&str>) -> Result<AppConfig> {
+    Ok(load_config_with_warnings(profile)?.0)
+}
+
+/// Like [`load_config_with_profile`], also returning the warnings raised
+/// while loading (`api_key` in project config, stale remote preset).
+///
+/// Nothing is printed; showing the warnings is up to the caller.
+pub fn load_config_with_warnings(profile: Option<&str>) -> Result<(AppConfig, Vec<String>)> {
+    let mut warnings = Vec::new();
+    let config = load_config_from_path_with_profile(
+        user_config_path(),
+        find_project_config(),
+        profile,
+        &mut warnings,
+    )?;
+    Ok((config, warnings))
 }
 
 /// Loads configuration from explicit paths (test-friendly entrypoint).
 ///
 /// Passing `None` skips the corresponding file source.
+#[cfg(test)]
 pub(crate) fn load_config_from_path(
     config_path: Option<PathBuf>,
     project_config_path: Option<PathBuf>,
+) -> Result<AppConfig> {
+    load_config_from_path_with_profile(config_path, project_config_path, None, &mut Vec::new())
+}
+
+/// Loads configuration from explicit paths with an optional explicit profile.
+///
+/// Loading warnings are added to `warnings`.
+pub(crate) fn load_config_from_path_with_profile(
+    config_path: Option<PathBuf>,
+    project_config_path: Option<PathBuf>,
+    profile: Option<&str>,
+    warnings: &mut Vec<String>,
 ) -> Result<AppConfig> {
     let mut builder = Config::builder();
 
@@ -41,7 +85,7 @@ pub(crate) fn load_config_from_path(
     if let Some(config_path) = config_path
         && config_path.exists()
     {
-        builder = builder.add_source(File::from(config_path));
+        builder = add_file_source(builder, &config_path, false, &mut Vec::new())?;
     }
 
     // Project config (overrides user config).
@@ -49,8 +93,19 @@ pub(crate) fn load_config_from_path(
         && project_path.exists()
     {
         // Security check: project config should not include `api_key`.
-        check_project_config_security(project_path);
-        builder = builder.add_source(File::from(project_path.clone()));
+        warnings.extend(check_project_config_security(project_path));
+
+        // Remote preset (between user and project config).
+        if let Some(preset) = extends_of(&read_config_file(project_path)?) {
+            let cache_dir = get_data_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join(PRESET_CACHE_DIR);
+            let content = load_preset(&preset, &cache_dir, warnings)?;
+            reject_exec_providers(&content, preset.url())?;
+            reject_user_only_settings(&content, preset.url())?;
+            builder = builder.add_source(File::from_str(&content, FileFormat::Toml));
+        }
+        builder = add_file_source(builder, project_path, true, &mut Vec::new())?;
     }
 
     // Environment variables (highest source priority in config-rs builder order).
@@ -66,6 +121,16 @@ pub(crate) fn load_config_from_path(
     let config = builder.build()?;
     let mut app_config: AppConfig = config.try_deserialize()?;
 
+    // Named profile (overrides file and environment sources).
+    let profile = profile.map(str::to_string).or_else(|| {
+        std::env::var("GCOP_PROFILE")
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+    });
+    if let Some(ref name) = profile {
+        apply_profile(&mut app_config, name)?;
+    }
+
     // CI mode overrides (highest effective priority).
     apply_ci_mode_overrides(&mut app_config)?;
 
@@ -75,6 +140,81 @@ pub(crate) fn load_config_from_path(
     Ok(app_config)
 }
 
+/// Adds a config file to `builder`, preceded by the files it `include`s.
+///
+/// Includes are added depth-first before the including file, so the
+/// including file wins and later includes override earlier ones. `chain`
+/// holds the files currently being included, to reject cycles. Project-level
+/// files (`project`, includes too) may not configure external commands.
+fn add_file_source(
+    mut builder: ConfigBuilder<DefaultState>,
+    path: &Path,
+    project: bool,
+    chain: &mut Vec<PathBuf>,
+) -> Result<ConfigBuilder<DefaultState>> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if chain.contains(&canonical) {
+        let cycle = chain
+            .iter()
+            .chain([&canonical])
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(GcopError::Config(
+            rust_i18n::t!("config.include_cycle", cycle = cycle).to_string(),
+        ));
+    }
+
+    let content = read_config_file(path)?;
+    if project {
+        reject_exec_providers(&content, &path.display().to_string())?;
+        reject_user_only_settings(&content, &path.display().to_string())?;
+    }
+    chain.push(canonical);
+    for include in includes_of(&content) {
+        let included = resolve_include(path, &include);
+        if !included.exists() {
+            return Err(GcopError::Config(
+                rust_i18n::t!(
+                    "config.include_not_found",
+                    include = included.display().to_string(),
+                    path = path.display().to_string()
+                )
+                .to_string(),
+            ));
+        }
+        builder = add_file_source(builder, &included, project, chain)?;
+    }
+    chain.pop();
+
+    // Plain files keep their path in parse errors
+    Ok(if is_encrypted(path) {
+        builder.add_source(File::from_str(&content, FileFormat::Toml))
+    } else {
+        builder.add_source(File::from(path.to_path_buf()).format(FileFormat::Toml))
+    })
+}
+
+/// `include` entries of a config file; malformed content is left to the
+/// full parse to report.
+fn includes_of(content: &str) -> Vec<String> {
+    toml::from_str::<toml::Table>(content)
+        .ok()
+        .and_then(|table| table.get("include").cloned())
+        .and_then(|value| value.try_into::<Vec<String>>().ok())
+        .unwrap_or_default()
+}
+
+/// Resolves an `include` entry: `~/` is the home directory, relative paths
+/// start at the including file's directory.
+fn resolve_include(from: &Path, include: &str) -> PathBuf {
+    let path = expand_home(include);
+    if path.is_absolute() {
+        return path;
+    }
+    from.parent().unwrap_or(Path::new(".")).join(path)
+}
+
 /// Finds project-level `.gcop/config.toml`.
 ///
 /// Resolves the repository root via [`crate::git::find_git_root`], then checks
@@ -89,27 +229,147 @@ pub(crate) fn find_project_config() -> Option<PathBuf> {
 
 /// Warns when project-level config contains secrets.
 ///
-/// If project config contains an `api_key`, prints warnings encouraging users to
-/// move secrets into user-level config or environment variables.
-fn check_project_config_security(path: &Path) {
-    if let Ok(content) = std::fs::read_to_string(path) {
-        // Detect `api_key` in non-comment lines.
-        let has_api_key = content.lines().any(|line| {
-            let trimmed = line.trim();
-            !trimmed.starts_with('#') && trimmed.contains("api_key")
-        });
-        if has_api_key {
-            eprintln!("{}", rust_i18n::t!("config.project_api_key_warning_line1"));
-            eprintln!("{}", rust_i18n::t!("config.project_api_key_warning_line2"));
-            eprintln!("{}", rust_i18n::t!("config.project_api_key_warning_line3"));
-        }
+/// If project config contains an `api_key`, returns a warning encouraging users
+/// to move secrets into user-level config or environment variables.
+fn check_project_config_security(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    // Detect `api_key` in non-comment lines.
+    let has_api_key = content.lines().any(|line| {
+        let trimmed = line.trim();
+        !trimmed.starts_with('#') && trimmed.contains("api_key")
+    });
+    has_api_key.then(|| {
+        [
+            rust_i18n::t!("config.project_api_key_warning_line1"),
+            rust_i18n::t!("config.project_api_key_warning_line2"),
+            rust_i18n::t!("config.project_api_key_warning_line3"),
+        ]
+        .join("\n")
+    })
+}
+
+/// Rejects providers running an external command (`api_style = "exec"`,
+/// `command`) in project-level config.
+///
+/// `.gcop/config.toml` comes with the repository, so honoring it would let a
+/// cloned repository run arbitrary programs on `commit` or in the commit hook.
+/// Exec providers belong in user config or environment variables.
+fn reject_exec_providers(content: &str, source: &str) -> Result<()> {
+    let Ok(table) = toml::from_str::<toml::Table>(content) else {
+        return Ok(());
+    };
+    let providers = table
+        .get("llm")
+        .and_then(|llm| llm.get("providers"))
+        .and_then(toml::Value::as_table);
+    let exec_provider = providers.into_iter().flatten().find(|(name, provider)| {
+        let api_style = provider.get("api_style").and_then(toml::Value::as_str);
+        provider.get("command").is_some()
+            || api_style == Some("exec")
+            || (api_style.is_none() && name.as_str() == "exec")
+    });
+    match exec_provider {
+        Some((name, _)) => Err(GcopError::Config(
+            rust_i18n::t!(
+                "config.project_exec_provider",
+                source = source,
+                provider = name.as_str()
+            )
+            .to_string(),
+        )),
+        None => Ok(()),
     }
 }
 
+/// Settings (dotted key paths) only honored from user config or environment variables.
+const USER_ONLY_SETTINGS: &[&str] = &[
+    "commit.split_verify_command",
+    "logging.file",
+    "aliases",
+    "forge.url",
+    "forge.token",
+    "plugins.wasm",
+];
+
+/// Rejects [`USER_ONLY_SETTINGS`] in project-level config.
+///
+/// Like exec providers, these run programs, write to arbitrary files or decide
+/// where credentials are sent, so a cloned repository must not be able to set them.
+fn reject_user_only_settings(content: &str, source: &str) -> Result<()> {
+    let Ok(table) = toml::from_str::<toml::Table>(content) else {
+        return Ok(());
+    };
+    let setting = USER_ONLY_SETTINGS.iter().find(|path| {
+        let mut keys = path.split('.');
+        let first = keys.next().and_then(|key| table.get(key));
+        keys.try_fold(first, |value, key| Some(value?.get(key)))
+            .flatten()
+            .is_some()
+    });
+    match setting {
+        Some(setting) => Err(GcopError::Config(
+            rust_i18n::t!(
+                "config.project_user_only_setting",
+                source = source,
+                setting = *setting
+            )
+            .to_string(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Merges the named profile on top of the loaded configuration.
+///
+/// Only fields set in `[profiles.<name>]` are overridden. `model` applies to
+/// the provider selected after the `provider` override.
+fn apply_profile(config: &mut AppConfig, name: &str) -> Result<()> {
+    let profile = config.profiles.get(name).cloned().ok_or_else(|| {
+        crate::error::GcopError::Config(
+            rust_i18n::t!("config.profile_not_found", profile = name).to_string(),
+        )
+    })?;
+
+    if let Some(provider) = profile.provider {
+        config.llm.default_provider = provider;
+    }
+
+    if let Some(model) = profile.model {
+        let provider_name = config.llm.default_provider.clone();
+        let provider = config
+            .llm
+            .providers
+            .get_mut(&provider_name)
+            .ok_or_else(|| {
+                crate::error::GcopError::Config(
+                    rust_i18n::t!(
+                        "config.profile_provider_not_found",
+                        profile = name,
+                        provider = provider_name.as_str()
+                    )
+                    .to_string(),
+                )
+            })?;
+        provider.model = model;
+    }
+
+    if let Some(convention) = profile.convention {
+        config.commit.convention = Some(convention);
+    }
+
+    if let Some(language) = profile.language {
+        config.ui.language = Some(language);
+    }
+
+    tracing::debug!("Applied config profile '{}'", name);
+
+    Ok(())
+}
+
 /// Applies CI-mode environment overrides.
 ///
 /// When `CI=1`, provider config is built from:
-/// - `GCOP_CI_PROVIDER`: "claude", "openai", "ollama" or "gemini" (required)
+/// - `GCOP_CI_PROVIDER`: "claude", "openai", "ollama", "gemini", "deepseek" or "moonshot" (required)
 /// - `GCOP_CI_API_KEY`: API key (required)
 /// - `GCOP_CI_MODEL`: model name (optional, has a provider-specific default)
 /// - `GCOP_CI_ENDPOINT`: custom endpoint (optional)
@@ -160,6 +420,13 @@ fn apply_ci_mode_overrides(config: &mut AppConfig) -> Result<()> {
         model,
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: Default::default(),
     };
 
@@ -175,11 +442,18 @@ fn apply_ci_mode_overrides(config: &mut AppConfig) -> Result<()> {
     Ok(())
 }
 
-/// Returns platform-specific config file path.
+/// Returns the user config file path.
 ///
-/// Path format: `<config_dir>/config.toml`.
-fn get_config_path() -> Option<PathBuf> {
-    ProjectDirs::from("", "", "gcop").map(|dirs| dirs.config_dir().join("config.toml"))
+/// `<config_dir>/config.toml`, or `<config_dir>/config.toml.enc` when only
+/// the encrypted file exists.
+pub fn user_config_path() -> Option<PathBuf> {
+    let dir = get_config_dir()?;
+    let plain = dir.join("config.toml");
+    let encrypted = dir.join(ENCRYPTED_CONFIG_FILE);
+    if !plain.exists() && encrypted.exists() {
+        return Some(encrypted);
+    }
+    Some(plain)
 }
 
 /// Returns platform-specific config directory path.
@@ -188,3 +462,20 @@ fn get_config_path() -> Option<PathBuf> {
 pub fn get_config_dir() -> Option<PathBuf> {
     ProjectDirs::from("", "", "gcop").map(|dirs| dirs.config_dir().to_path_buf())
 }
+
+/// Returns platform-specific data directory path.
+///
+/// Holds generated state such as the usage ledger.
+pub fn get_data_dir() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "gcop").map(|dirs| dirs.data_dir().to_path_buf())
+}
+
+/// Expands a leading `~/` to the home directory.
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(dirs) = directories::BaseDirs::new()
+    {
+        return dirs.home_dir().join(rest);
+    }
+    PathBuf::from(path)
+}
@@ -3,17 +3,35 @@
 //! This module exposes the public configuration API used across command flows,
 //! provider initialization, and runtime behavior.
 
+mod encrypted;
+mod env;
 mod global;
+pub(crate) mod key_path;
+pub(crate) mod keychain;
 mod loader;
+mod remote;
+mod schema;
 mod structs;
 
 #[cfg(test)]
 mod tests;
 
 // Public API exports.
+pub use encrypted::{ENCRYPTED_CONFIG_FILE, is_encrypted, read_config_file, write_config_file};
+pub use env::{EnvVarGroup, EnvVarStatus, env_status, env_status_from};
 pub use global::{get_config, init_config};
-pub use loader::{get_config_dir, load_config};
+pub(crate) use loader::{expand_home, find_project_config};
+pub use loader::{
+    get_config_dir, get_data_dir, load_config, load_config_with_profile, load_config_with_warnings,
+    user_config_path,
+};
+pub use schema::{ConfigDiagnostic, DiagnosticLevel, check_config_content, json_schema};
 pub use structs::{
-    ApiStyle, AppConfig, CommitConfig, CommitConvention, ConventionStyle, FileConfig, LLMConfig,
-    NetworkConfig, ProviderConfig, ReviewConfig, UIConfig,
+    ApiStyle, AppConfig, BodyMode, CommandLlmConfig, CommitConfig, CommitConstraints,
+    CommitConvention, CommitTrailers, ConventionStyle, FileConfig, ForgeConfig, ForgeKind,
+    GitmojiMode, HookConfig, HookFailure, LLMConfig, LogFormat, LoggingConfig, ModelPricing,
+    ModelTiers, NetworkConfig, NonInteractiveAction, PluginsConfig, PrivacyConfig, ProfileConfig,
+    ProviderConfig, ProviderNetworkConfig, ProviderStrategy, RedactionRule, RemotePreset,
+    ReviewConfig, SecretAction, StatsConfig, TicketPlacement, UIConfig, UpdateConfig,
 };
+pub(crate) use structs::{is_bare_author_name, split_co_author};
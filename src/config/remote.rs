@@ -0,0 +1,421 @@
+//! Remote convention presets (`extends` in `.gcop/config.toml`).
+//!
+//! A preset is fetched over HTTP(S), or from a git repository with a shallow
+//! `git clone`, and cached under `<data_dir>/presets/`. The cached copy is
+//! reused for `ttl_hours`; when fetching again fails, the stale copy is used
+//! with a warning so a policy server outage does not block commits. Presets
+//! pinned with `sha256` never go stale: the checksum already fixes their
+//! content.
+
+use std::path::{Component, Path};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use super::structs::RemotePreset;
+use crate::error::{GcopError, Result};
+use crate::llm::audit::sha256_hex;
+
+/// Directory under the data directory holding fetched presets
+pub(crate) const PRESET_CACHE_DIR: &str = "presets";
+
+/// Hours a fetched preset is reused when `ttl_hours` is unset
+const DEFAULT_TTL_HOURS: u64 = 24;
+
+/// Timeout of an HTTP preset fetch
+const FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// File read from a git preset when the URL has no `#<path>`
+const DEFAULT_GIT_PATH: &str = "config.toml";
+
+/// `extends` of a config file; malformed content is left to the full parse
+/// to report.
+pub(crate) fn extends_of(content: &str) -> Option<RemotePreset> {
+    toml::from_str::<toml::Table>(content)
+        .ok()?
+        .get("extends")
+        .cloned()?
+        .try_into()
+        .ok()
+}
+
+/// Content of `preset`: the cached copy while it is fresh, fetched otherwise.
+///
+/// Falling back to a stale copy adds a warning to `warnings`.
+pub(crate) fn load_preset(
+    preset: &RemotePreset,
+    cache_dir: &Path,
+    warnings: &mut Vec<String>,
+) -> Result<String> {
+    let url = preset.url();
+    let expected = preset.sha256().map(str::to_ascii_lowercase);
+    if let Some(ref checksum) = expected
+        && !(checksum.len() == 64 && checksum.chars().all(|c| c.is_ascii_hexdigit()))
+    {
+        return Err(GcopError::Config(
+            rust_i18n::t!("config.preset_invalid_checksum", url = url).to_string(),
+        ));
+    }
+
+    let key = format!("{}#{}", url, preset.rev().unwrap_or_default());
+    let cache = cache_dir.join(format!("{}.toml", sha256_hex(key.as_bytes())));
+    let cached = std::fs::read_to_string(&cache).ok().filter(|content| {
+        expected
+            .as_ref()
+            .is_none_or(|checksum| sha256_hex(content.as_bytes()) == *checksum)
+    });
+    let ttl = Duration::from_secs(preset.ttl_hours().unwrap_or(DEFAULT_TTL_HOURS) * 3600);
+    if let Some(ref content) = cached
+        && (expected.is_some() || is_fresh(&cache, ttl))
+    {
+        tracing::debug!("Using cached preset {} ({})", url, cache.display());
+        return Ok(content.clone());
+    }
+
+    let content = match fetch(preset) {
+        Ok(content) => content,
+        Err(e) => {
+            let error = rust_i18n::t!(
+                "config.preset_fetch_failed",
+                url = url,
+                error = e.to_string()
+            )
+            .to_string();
+            return match cached {
+                Some(content) => {
+                    warnings.push(
+                        rust_i18n::t!("config.preset_stale_warning", error = error.as_str())
+                            .to_string(),
+                    );
+                    Ok(content)
+                }
+                None => Err(GcopError::Config(error)),
+            };
+        }
+    };
+
+    let actual = sha256_hex(content.as_bytes());
+    if let Some(checksum) = expected
+        && actual != checksum
+    {
+        return Err(GcopError::Config(
+            rust_i18n::t!(
+                "config.preset_checksum_mismatch",
+                url = url,
+                expected = checksum.as_str(),
+                actual = actual.as_str()
+            )
+            .to_string(),
+        ));
+    }
+
+    // A read-only data directory only costs the cache
+    if let Err(e) =
+        std::fs::create_dir_all(cache_dir).and_then(|_| std::fs::write(&cache, &content))
+    {
+        tracing::warn!("Failed to cache preset {}: {}", url, e);
+    }
+    Ok(content)
+}
+
+/// Whether `path` was written less than `ttl` ago
+fn is_fresh(path: &Path, ttl: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < ttl)
+}
+
+/// Repository and file of a git preset URL, `None` for other URLs
+///
+/// Git URLs start with `git+` (stripped), `git@` or `ssh://`, or end with
+/// `.git`; `#<path>` selects the file (default: `config.toml`).
+fn git_source(url: &str) -> Option<(&str, &str)> {
+    let (repo, path) = url.split_once('#').unwrap_or((url, ""));
+    let repo = match repo.strip_prefix("git+") {
+        Some(repo) => repo,
+        None if repo.starts_with("git@")
+            || repo.starts_with("ssh://")
+            || repo.ends_with(".git") =>
+        {
+            repo
+        }
+        None => return None,
+    };
+    let path = if path.is_empty() {
+        DEFAULT_GIT_PATH
+    } else {
+        path
+    };
+    Some((repo, path))
+}
+
+fn fetch(preset: &RemotePreset) -> Result<String> {
+    let url = preset.url();
+    if let Some((repo, path)) = git_source(url) {
+        return fetch_git(repo, path, preset.rev());
+    }
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return fetch_http(url);
+    }
+    Err(GcopError::Config(
+        rust_i18n::t!("config.preset_unsupported_url", url = url).to_string(),
+    ))
+}
+
+/// Fetches `url` on a dedicated thread and runtime, since config loading is
+/// synchronous and may already run inside the main runtime.
+fn fetch_http(url: &str) -> Result<String> {
+    let url = url.to_string();
+    std::thread::spawn(move || -> Result<String> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(async {
+            let client = reqwest::Client::builder()
+                .user_agent(format!(
+                    "{}/{}",
+                    env!("CARGO_PKG_NAME"),
+                    env!("CARGO_PKG_VERSION")
+                ))
+                .timeout(Duration::from_secs(FETCH_TIMEOUT_SECS))
+                .build()
+                .map_err(GcopError::Network)?;
+            let response = client.get(&url).send().await.map_err(GcopError::Network)?;
+            let status = response.status();
+            if !status.is_success() {
+                return Err(GcopError::Config(format!("HTTP {}", status)));
+            }
+            response.text().await.map_err(GcopError::Network)
+        })
+    })
+    .join()
+    .unwrap_or_else(|_| Err(GcopError::Config("preset fetch panicked".to_string())))
+}
+
+/// Rejects git preset parts that would escape the clone: a repository or
+/// rev starting with `-` is parsed by git as an option (`--upload-pack`
+/// runs a program), and an absolute or `..` path reads files outside it.
+fn check_git_source(repo: &str, path: &str, rev: Option<&str>) -> Result<()> {
+    let option_like = repo.starts_with('-') || rev.is_some_and(|rev| rev.starts_with('-'));
+    let escapes = Path::new(path)
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir));
+    if option_like || escapes {
+        return Err(GcopError::Config(
+            rust_i18n::t!("config.preset_unsafe_git_url", repo = repo, path = path).to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Reads `path` from a shallow clone of `repo`.
+fn fetch_git(repo: &str, path: &str, rev: Option<&str>) -> Result<String> {
+    check_git_source(repo, path, rev)?;
+    let dir = tempfile::tempdir()?;
+    let mut command = Command::new("git");
+    command.args(["clone", "--quiet", "--depth", "1"]);
+    if let Some(rev) = rev {
+        command.args(["--branch", rev]);
+    }
+    let output = command
+        .arg("--")
+        .arg(repo)
+        .arg(dir.path())
+        // Fail instead of prompting for credentials in the middle of a commit
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()?;
+    if !output.status.success() {
+        return Err(GcopError::Config(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(std::fs::read_to_string(dir.path().join(path))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    use crate::llm::provider::test_utils::ensure_crypto_provider;
+
+    const PRESET: &str = "[commit]\nmax_retries = 7\n";
+
+    fn detailed(url: String, sha256: Option<String>, ttl_hours: u64) -> RemotePreset {
+        RemotePreset::Detailed {
+            url,
+            rev: None,
+            sha256,
+            ttl_hours: Some(ttl_hours),
+        }
+    }
+
+    #[test]
+    fn test_extends_and_git_source_parsing() {
+        assert_eq!(
+            extends_of("extends = \"https://example.com/backend.toml\""),
+            Some(RemotePreset::Url(
+                "https://example.com/backend.toml".to_string()
+            ))
+        );
+        let table =
+            extends_of("[extends]\nurl = \"git@github.com:acme/presets.git\"\nrev = \"v2\"");
+        assert_eq!(table.as_ref().map(RemotePreset::rev), Some(Some("v2")));
+        assert_eq!(extends_of("[commit]\nmax_retries = 3"), None);
+
+        assert_eq!(
+            git_source("git+https://github.com/acme/presets#team/backend.toml"),
+            Some(("https://github.com/acme/presets", "team/backend.toml"))
+        );
+        assert_eq!(
+            git_source("git@github.com:acme/presets.git"),
+            Some(("git@github.com:acme/presets.git", "config.toml"))
+        );
+        assert_eq!(git_source("https://example.com/backend.toml"), None);
+        assert!(
+            load_preset(
+                &RemotePreset::Url("ftp://example.com/x.toml".to_string()),
+                tempfile::tempdir().unwrap().path(),
+                &mut Vec::new()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_preset_is_cached_and_verified() {
+        ensure_crypto_provider();
+        let mut server = Server::new();
+        let mock = server
+            .mock("GET", "/backend.toml")
+            .with_status(200)
+            .with_body(PRESET)
+            .expect(1)
+            .create();
+        let cache = tempfile::tempdir().unwrap();
+        let preset = detailed(format!("{}/backend.toml", server.url()), None, 24);
+
+        // The second load is served from the cache
+        assert_eq!(
+            load_preset(&preset, cache.path(), &mut Vec::new()).unwrap(),
+            PRESET
+        );
+        assert_eq!(
+            load_preset(&preset, cache.path(), &mut Vec::new()).unwrap(),
+            PRESET
+        );
+        mock.assert();
+
+        let pinned = detailed(preset.url().to_string(), Some("0".repeat(64)), 0);
+        let err = load_preset(
+            &pinned,
+            tempfile::tempdir().unwrap().path(),
+            &mut Vec::new(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains(&sha256_hex(PRESET.as_bytes())));
+
+        let invalid = detailed(preset.url().to_string(), Some("abc".to_string()), 0);
+        assert!(load_preset(&invalid, cache.path(), &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_stale_preset_is_used_when_fetch_fails() {
+        ensure_crypto_provider();
+        let mut server = Server::new();
+        let ok = server
+            .mock("GET", "/backend.toml")
+            .with_status(200)
+            .with_body(PRESET)
+            .create();
+        let cache = tempfile::tempdir().unwrap();
+        let preset = detailed(format!("{}/backend.toml", server.url()), None, 0);
+        assert_eq!(
+            load_preset(&preset, cache.path(), &mut Vec::new()).unwrap(),
+            PRESET
+        );
+        ok.remove();
+
+        server
+            .mock("GET", "/backend.toml")
+            .with_status(503)
+            .create();
+        let mut warnings = Vec::new();
+        assert_eq!(
+            load_preset(&preset, cache.path(), &mut warnings).unwrap(),
+            PRESET
+        );
+        // The stale copy is reported to the caller instead of printed
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            load_preset(
+                &preset,
+                tempfile::tempdir().unwrap().path(),
+                &mut Vec::new()
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unsafe_git_sources_are_rejected() {
+        let safe = check_git_source("https://github.com/acme/presets", "team/backend.toml", None);
+        assert!(safe.is_ok());
+
+        // Option injection through the repository or the rev
+        let (repo, path) = git_source("git+--upload-pack=touch /tmp/pwned#config.toml").unwrap();
+        assert!(fetch_git(repo, path, None).is_err());
+        assert!(
+            fetch_git(
+                "https://github.com/acme/presets",
+                "config.toml",
+                Some("--upload-pack=touch /tmp/pwned")
+            )
+            .is_err()
+        );
+
+        // Paths escaping the clone
+        for path in ["/etc/passwd", "../../.ssh/id_ed25519", "team/../../secret"] {
+            assert!(
+                check_git_source("https://github.com/acme/presets", path, None).is_err(),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_git_preset_is_read_from_clone() {
+        let repo = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(repo.path())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "--quiet"]);
+        std::fs::create_dir(repo.path().join("team")).unwrap();
+        std::fs::write(repo.path().join("team/backend.toml"), PRESET).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "preset"]);
+
+        let url = format!("git+file://{}#team/backend.toml", repo.path().display());
+        let preset = detailed(url, Some(sha256_hex(PRESET.as_bytes())), 24);
+        assert_eq!(
+            load_preset(
+                &preset,
+                tempfile::tempdir().unwrap().path(),
+                &mut Vec::new()
+            )
+            .unwrap(),
+            PRESET
+        );
+    }
+}
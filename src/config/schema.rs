@@ -0,0 +1,200 @@
+//! JSON Schema export and line-level config file diagnostics.
+//!
+//! The schema is generated from the serde structs with [`schemars`], so it
+//! always matches what the loader accepts. Diagnostics deserialize a single
+//! config file with the `toml` crate (which keeps byte spans) and collect keys
+//! that serde would silently ignore via [`serde_ignored`].
+
+use std::fmt;
+
+use toml_edit::TableLike;
+
+use super::AppConfig;
+
+/// JSON Schema (draft 2020-12) describing `config.toml`.
+pub fn json_schema() -> serde_json::Value {
+    serde_json::to_value(schemars::schema_for!(AppConfig)).unwrap_or_default()
+}
+
+/// Severity of a config diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticLevel {
+    /// The file cannot be loaded.
+    Error,
+    /// The file loads, but part of it has no effect.
+    Warning,
+}
+
+/// A problem found in a config file, with its position when known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    /// Error or warning.
+    pub level: DiagnosticLevel,
+    /// 1-based line number.
+    pub line: Option<usize>,
+    /// 1-based column number (in characters).
+    pub column: Option<usize>,
+    /// Dotted key path the diagnostic refers to, if any.
+    pub key: Option<String>,
+    /// Human-readable description.
+    pub message: String,
+}
+
+impl fmt::Display for ConfigDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "{}:{}: {}", line, column, self.message),
+            (Some(line), None) => write!(f, "{}: {}", line, self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Checks one config file's content against the config schema.
+///
+/// Returns syntax and type errors (at most one, as parsing stops there) and a
+/// warning for every unknown key, ordered by position. An empty list means the
+/// file is clean.
+///
+/// The file is checked on its own, so errors about missing required fields
+/// may be resolved by another layer (for example a project file relying on
+/// the user config); callers should only surface errors when loading the
+/// merged configuration fails.
+pub fn check_config_content(content: &str) -> Vec<ConfigDiagnostic> {
+    let mut unknown = Vec::new();
+    let result = toml::Deserializer::parse(content).and_then(|deserializer| {
+        serde_ignored::deserialize(deserializer, |path| unknown.push(path.to_string()))
+            .map(|_: AppConfig| ())
+    });
+
+    let mut diagnostics = Vec::new();
+    if let Err(e) = result {
+        let (line, column) = e
+            .span()
+            .map(|span| line_column(content, span.start))
+            .unzip();
+        diagnostics.push(ConfigDiagnostic {
+            level: DiagnosticLevel::Error,
+            line,
+            column,
+            key: None,
+            message: e.message().trim_end().to_string(),
+        });
+        return diagnostics;
+    }
+
+    let document = toml_edit::Document::parse(content).ok();
+    for key in unknown {
+        let (line, column) = document
+            .as_ref()
+            .and_then(|doc| key_offset(doc.as_table(), &key))
+            .map(|offset| line_column(content, offset))
+            .unzip();
+        diagnostics.push(ConfigDiagnostic {
+            level: DiagnosticLevel::Warning,
+            line,
+            column,
+            message: rust_i18n::t!("config.unknown_key", key = key).to_string(),
+            key: Some(key),
+        });
+    }
+    diagnostics.sort_by_key(|d| (d.line.is_none(), d.line, d.column));
+    diagnostics
+}
+
+/// Byte offset of the deepest key of `path` present in the document.
+fn key_offset(root: &dyn TableLike, path: &str) -> Option<usize> {
+    let mut table = root;
+    let mut offset = None;
+    for segment in path.split('.') {
+        let Some((key, item)) = table.get_key_value(segment) else {
+            break;
+        };
+        offset = key.span().map(|span| span.start).or(offset);
+        match item.as_table_like() {
+            Some(inner) => table = inner,
+            None => break,
+        }
+    }
+    offset
+}
+
+/// Converts a byte offset into 1-based line and column numbers.
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_schema_describes_sections() {
+        let schema = json_schema();
+        let properties = &schema["properties"];
+        for section in ["llm", "commit", "review", "ui", "network"] {
+            assert!(properties.get(section).is_some(), "missing {section}");
+        }
+        assert!(schema.get("$defs").is_some());
+    }
+
+    #[test]
+    fn test_clean_file_has_no_diagnostics() {
+        let content = "[llm]\ndefault_provider = \"openai\"\n\n[ui]\nstreaming = false\n";
+        assert_eq!(check_config_content(content), vec![]);
+    }
+
+    #[test]
+    fn test_type_error_points_at_line() {
+        let content = "[ui]\ncolored = true\nstreaming = \"maybe\"\n";
+        let diagnostics = check_config_content(content);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+        assert_eq!(diagnostics[0].line, Some(3));
+        assert_eq!(diagnostics[0].column, Some(13));
+    }
+
+    #[test]
+    fn test_syntax_error_points_at_line() {
+        let diagnostics = check_config_content("[ui]\nstreaming = \n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+        assert_eq!(diagnostics[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_unknown_keys_are_warnings() {
+        let content = "[ui]\nstreaming = true\nstreming = false\n\n[typo]\nx = 1\n";
+        let diagnostics = check_config_content(content);
+        let keys: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.level, d.key.as_deref(), d.line))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (DiagnosticLevel::Warning, Some("ui.streming"), Some(3)),
+                (DiagnosticLevel::Warning, Some("typo"), Some(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provider_extras_are_not_unknown() {
+        // 提供商的额外参数通过 flatten 收集，不算未知键
+        let content = "[llm]\ndefault_provider = \"local\"\n\n[llm.providers.local]\nmodel = \"m.gguf\"\ncontext_size = 4096\n";
+        assert_eq!(check_config_content(content), vec![]);
+    }
+
+    #[test]
+    fn test_line_column() {
+        let content = "a\nbé c\n";
+        assert_eq!(line_column(content, 0), (1, 1));
+        assert_eq!(line_column(content, 2), (2, 1));
+        assert_eq!(line_column(content, "a\nbé ".len()), (2, 4));
+    }
+}
@@ -1,14 +1,22 @@
 //! Top-level application configuration and remaining command structures.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{GcopError, Result};
 
 use super::commit::CommitConfig;
-use super::llm::LLMConfig;
+use super::forge::ForgeConfig;
+use super::hook::HookConfig;
+use super::llm::{CommandLlmConfig, LLMConfig};
+use super::logging::LoggingConfig;
 use super::network::NetworkConfig;
+use super::plugins::PluginsConfig;
+use super::privacy::PrivacyConfig;
+use super::profile::ProfileConfig;
+use super::update::UpdateConfig;
 
 /// Application configuration.
 ///
@@ -17,9 +25,11 @@ use super::network::NetworkConfig;
 /// Effective configuration is merged from multiple sources (low to high):
 /// 1. Rust defaults (`Default` + `serde(default)`)
 /// 2. User-level config file (platform-specific config directory)
-/// 3. Project-level config (`.gcop/config.toml`, discovered from repository root)
-/// 4. `GCOP__*` environment variables
-/// 5. CI mode overrides (`CI=1` + `GCOP_CI_*`)
+/// 3. Remote preset the project config `extends` (see [`RemotePreset`])
+/// 4. Project-level config (`.gcop/config.toml`, discovered from repository root)
+/// 5. `GCOP__*` environment variables
+/// 6. Selected profile (`--profile <name>` or `GCOP_PROFILE`)
+/// 7. CI mode overrides (`CI=1` + `GCOP_CI_*`)
 ///
 /// # Configuration File Locations
 /// - Linux: `~/.config/gcop/config.toml`
@@ -44,8 +54,22 @@ use super::network::NetworkConfig;
 /// [ui]
 /// colored = true
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, JsonSchema)]
 pub struct AppConfig {
+    /// Config files merged underneath this one (`~/` and paths relative to
+    /// the including file are allowed).
+    ///
+    /// The including file overrides its includes; later entries override
+    /// earlier ones. Tables merge key by key, other values (arrays included)
+    /// are replaced.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Remote convention preset merged underneath the project config
+    /// (read from `.gcop/config.toml` only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<RemotePreset>,
+
     /// LLM provider and prompt settings.
     #[serde(default)]
     pub llm: LLMConfig,
@@ -73,6 +97,43 @@ pub struct AppConfig {
     /// Workspace detection and scope inference (monorepo support).
     #[serde(default)]
     pub workspace: WorkspaceConfig,
+
+    /// Code forge integration (`review mr`).
+    #[serde(default)]
+    pub forge: ForgeConfig,
+
+    /// `prepare-commit-msg` hook timeout and failure handling.
+    #[serde(default)]
+    pub hook: HookConfig,
+
+    /// Secret handling for diffs sent to providers.
+    #[serde(default)]
+    pub privacy: PrivacyConfig,
+
+    /// Optional log file with rotation.
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    /// Multi-repository reports and author identity merging for `stats`.
+    #[serde(default)]
+    pub stats: StatsConfig,
+
+    /// Weekly hint when a newer release is available.
+    #[serde(default)]
+    pub update: UpdateConfig,
+
+    /// WASM plugins transforming prompts and responses.
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+
+    /// Named profiles selectable with `--profile` / `GCOP_PROFILE`.
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileConfig>,
+
+    /// Custom git aliases installed by `gcop-rs alias` (`name = "gcop-rs arguments"`).
+    /// Only honored from user config: they end up in the global git config.
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
 }
 
 impl AppConfig {
@@ -101,11 +162,110 @@ impl AppConfig {
         for (name, provider) in &self.llm.providers {
             provider.validate(name)?;
         }
+        self.commit.llm.validate("commit", &self.llm)?;
+        self.review.llm.validate("review", &self.llm)?;
+        self.commit.validate()?;
         self.network.validate()?;
+        self.file.validate()?;
+        self.forge.validate()?;
+        self.privacy.validate()?;
+        self.logging.validate()?;
+        self.workspace.validate()?;
+        self.stats.validate()?;
+        self.plugins.validate()?;
+
+        for (name, command) in &self.aliases {
+            if !is_valid_alias_name(name) {
+                return Err(GcopError::Config(format!(
+                    "aliases: '{}' is not a valid git alias name (use letters, digits and '-', starting with a letter)",
+                    name
+                )));
+            }
+            if command.trim().is_empty() {
+                return Err(GcopError::Config(format!(
+                    "aliases.{}: command must not be empty",
+                    name
+                )));
+            }
+        }
         Ok(())
     }
 }
 
+/// Git config variable names: ASCII letter followed by letters, digits or `-`.
+fn is_valid_alias_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Remote convention preset a project config extends (`extends`).
+///
+/// Either a URL, or a table pinning the preset's checksum:
+/// ```toml
+/// extends = "https://example.com/gcop/backend.toml"
+///
+/// # or
+/// [extends]
+/// url = "git+https://github.com/acme/gcop-presets.git#backend.toml"
+/// rev = "v2"
+/// sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// ttl_hours = 168
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+pub enum RemotePreset {
+    /// HTTP(S) URL, or a git repository as `git+<repo>#<path>`
+    Url(String),
+    /// URL with checksum and cache settings
+    Detailed {
+        /// HTTP(S) URL, or a git repository as `git+<repo>#<path>`
+        url: String,
+        /// Branch or tag of a git preset (default: the remote's HEAD)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rev: Option<String>,
+        /// Expected SHA-256 of the preset (hex); a mismatch is an error
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sha256: Option<String>,
+        /// Hours a fetched preset is reused before fetching it again (default: 24)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_hours: Option<u64>,
+    },
+}
+
+impl RemotePreset {
+    /// URL of the preset
+    pub fn url(&self) -> &str {
+        match self {
+            RemotePreset::Url(url) | RemotePreset::Detailed { url, .. } => url,
+        }
+    }
+
+    /// Branch or tag of a git preset
+    pub fn rev(&self) -> Option<&str> {
+        match self {
+            RemotePreset::Url(_) => None,
+            RemotePreset::Detailed { rev, .. } => rev.as_deref(),
+        }
+    }
+
+    /// Expected SHA-256 of the preset
+    pub fn sha256(&self) -> Option<&str> {
+        match self {
+            RemotePreset::Url(_) => None,
+            RemotePreset::Detailed { sha256, .. } => sha256.as_deref(),
+        }
+    }
+
+    /// Hours a fetched preset is reused
+    pub fn ttl_hours(&self) -> Option<u64> {
+        match self {
+            RemotePreset::Url(_) => None,
+            RemotePreset::Detailed { ttl_hours, .. } => *ttl_hours,
+        }
+    }
+}
+
 /// Review command configuration.
 ///
 /// Controls code-review behavior.
@@ -113,14 +273,18 @@ impl AppConfig {
 /// # Fields
 /// - `min_severity`: minimum issue severity shown in text output (`"info"`, `"warning"`, `"critical"`)
 /// - `custom_prompt`: review system prompt override (optional; JSON constraints are always appended)
+/// - `structured_output`: request schema-constrained JSON from providers that support it (default: `false`)
+/// - `context_tokens`: budget for related files added by `review file --with-context` (default: `8000`)
+/// - `llm`: provider/model used for reviews instead of the defaults (see [`CommandLlmConfig`])
 ///
 /// # Example
 /// ```toml
 /// [review]
 /// min_severity = "warning"
 /// custom_prompt = "Focus on security issues"
+/// structured_output = true
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ReviewConfig {
     /// Minimum issue severity displayed in text output.
     ///
@@ -137,6 +301,35 @@ pub struct ReviewConfig {
     /// No placeholder substitution is performed (`{diff}` is passed literally).
     #[serde(default)]
     pub custom_prompt: Option<String>,
+
+    /// Whether to request schema-constrained JSON output for reviews.
+    ///
+    /// Supported by OpenAI-style (`response_format`) and Gemini-style
+    /// (`responseSchema`) providers. Other providers, or requests rejected by
+    /// the endpoint, fall back to prompt-only JSON parsing.
+    #[serde(default)]
+    pub structured_output: bool,
+
+    /// Approximate token budget for related files added by
+    /// `review file --with-context` (estimated at 4 bytes per token).
+    #[serde(default = "default_context_tokens")]
+    pub context_tokens: usize,
+
+    /// Maximum number of parts a diff larger than `[llm].max_diff_size` is
+    /// split into. Each part is reviewed on its own and the results are
+    /// merged by a final summarization request; `0` or `1` truncates the
+    /// diff instead.
+    #[serde(default = "default_max_chunks")]
+    pub max_chunks: usize,
+
+    /// Make `review changes` incremental by default: only what changed since
+    /// the last review of the branch is reviewed (`--full` overrides).
+    #[serde(default)]
+    pub incremental: bool,
+
+    /// Provider/model override for reviews (`[review.llm]`).
+    #[serde(default)]
+    pub llm: CommandLlmConfig,
 }
 
 impl Default for ReviewConfig {
@@ -144,6 +337,11 @@ impl Default for ReviewConfig {
         Self {
             min_severity: "info".to_string(),
             custom_prompt: None,
+            structured_output: false,
+            context_tokens: default_context_tokens(),
+            max_chunks: default_max_chunks(),
+            incremental: false,
+            llm: CommandLlmConfig::default(),
         }
     }
 }
@@ -164,7 +362,7 @@ impl Default for ReviewConfig {
 /// streaming = true
 /// language = "zh-CN"
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct UIConfig {
     /// Whether to enable color output.
     #[serde(default = "default_true")]
@@ -197,25 +395,65 @@ impl Default for UIConfig {
 /// # Fields
 /// - `max_size`: max file size in bytes (default: 10 MiB)
 ///   Used by `review file <PATH>` when reading workspace files.
+/// - `detect_renames`: detect renamed/moved files in diffs (default: `true`)
+/// - `rename_threshold`: similarity percentage for rename detection (default: `50`)
+/// - `submodule_log`: describe submodule pointer changes with the submodule's commit log (default: `true`)
 ///
 /// # Example
 /// ```toml
 /// [file]
 /// max_size = 10485760  # 10MB
+/// detect_renames = true
+/// rename_threshold = 50
+/// submodule_log = true
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct FileConfig {
     /// Maximum file size in bytes.
     ///
     /// Current read limit for `review file <PATH>`.
     #[serde(default = "default_max_file_size")]
     pub max_size: u64,
+
+    /// Whether to detect renamed/moved files in diffs (like `git diff -M`).
+    #[serde(default = "default_true")]
+    pub detect_renames: bool,
+
+    /// Minimum similarity (1-100, percent) for a delete + add pair to count as a rename.
+    #[serde(default = "default_rename_threshold")]
+    pub rename_threshold: u16,
+
+    /// Whether to append the submodule's commit log to submodule pointer changes
+    /// (like `git diff --submodule=log`).
+    #[serde(default = "default_true")]
+    pub submodule_log: bool,
+}
+
+impl FileConfig {
+    /// Validates file configuration (rename threshold range).
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=100).contains(&self.rename_threshold) {
+            return Err(GcopError::Config(format!(
+                "file.rename_threshold: must be between 1 and 100, got {}",
+                self.rename_threshold
+            )));
+        }
+        Ok(())
+    }
+
+    /// Rename detection threshold, or `None` when rename detection is disabled.
+    pub fn rename_threshold(&self) -> Option<u16> {
+        self.detect_renames.then_some(self.rename_threshold)
+    }
 }
 
 impl Default for FileConfig {
     fn default() -> Self {
         Self {
             max_size: default_max_file_size(),
+            detect_renames: true,
+            rename_threshold: default_rename_threshold(),
+            submodule_log: true,
         }
     }
 }
@@ -231,8 +469,9 @@ impl Default for FileConfig {
 /// enabled = true
 /// members = ["packages/*", "apps/*"]
 /// scope_mappings = { "packages/core" = "core", "packages/ui" = "ui" }
+/// symbol_scope = true
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct WorkspaceConfig {
     /// Whether workspace detection is enabled (default: `true`).
     #[serde(default = "default_true")]
@@ -249,6 +488,52 @@ pub struct WorkspaceConfig {
     /// When set, auto-detection is skipped and this list is used directly.
     #[serde(default)]
     pub members: Option<Vec<String>>,
+
+    /// Whether to infer scopes from source module structure (default: `false`).
+    ///
+    /// Used when no workspace package scope is found, including in non-monorepo repositories.
+    #[serde(default)]
+    pub symbol_scope: bool,
+
+    /// Whether to learn scopes from earlier commits touching the same files (default: `false`).
+    ///
+    /// The most frequent historical scopes are passed to the prompt and used
+    /// when no package or symbol scope is found.
+    #[serde(default)]
+    pub history_scope: bool,
+
+    /// Number of nested package levels kept in an inferred scope (default: `1`).
+    ///
+    /// With members `packages/*` and `packages/ui/components/*`, a change in
+    /// `packages/ui/components/button` gives `button` at depth 1 and `ui/button` at depth 2.
+    #[serde(default = "default_scope_depth")]
+    pub scope_depth: usize,
+
+    /// Separator between package levels in an inferred scope (default: `"/"`).
+    #[serde(default = "default_scope_joiner")]
+    pub scope_joiner: String,
+}
+
+impl WorkspaceConfig {
+    /// Validates scope hierarchy settings.
+    pub fn validate(&self) -> Result<()> {
+        if self.scope_depth == 0 {
+            return Err(GcopError::Config(
+                "workspace.scope_depth: must be at least 1".to_string(),
+            ));
+        }
+        if self.scope_joiner.is_empty()
+            || self
+                .scope_joiner
+                .contains(|c: char| c == ',' || c == '(' || c == ')' || c.is_whitespace())
+        {
+            return Err(GcopError::Config(format!(
+                "workspace.scope_joiner: '{}' cannot be empty or contain commas, parentheses or whitespace",
+                self.scope_joiner
+            )));
+        }
+        Ok(())
+    }
 }
 
 impl Default for WorkspaceConfig {
@@ -257,10 +542,79 @@ impl Default for WorkspaceConfig {
             enabled: true,
             scope_mappings: HashMap::new(),
             members: None,
+            symbol_scope: false,
+            history_scope: false,
+            scope_depth: default_scope_depth(),
+            scope_joiner: default_scope_joiner(),
+        }
+    }
+}
+
+/// Stats configuration.
+///
+/// # Fields
+/// - `repos`: repositories aggregated by `stats --repos` when no paths are given
+/// - `authors`: author aliases (name or email) mapped to one canonical `Name <email>` identity
+///
+/// # Example
+/// ```toml
+/// [stats]
+/// repos = ["~/src/api", "~/src/web"]
+///
+/// [stats.authors]
+/// "jane@old-company.com" = "Jane Doe <jane@example.com>"
+/// "jdoe" = "Jane Doe <jane@example.com>"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
+pub struct StatsConfig {
+    /// Repository paths aggregated by `stats --repos` (`~` is expanded).
+    #[serde(default)]
+    pub repos: Vec<String>,
+
+    /// Author aliases: an author name or email (case-insensitive) -> `Name <email>`
+    /// or a bare name (which keeps the email that name commits with most).
+    #[serde(default)]
+    pub authors: BTreeMap<String, String>,
+}
+
+impl StatsConfig {
+    /// Validates that every alias maps to a `Name <email>` identity or a bare name.
+    pub fn validate(&self) -> Result<()> {
+        for (alias, identity) in &self.authors {
+            if alias.trim().is_empty() {
+                return Err(GcopError::Config(
+                    "stats.authors: alias must not be empty".to_string(),
+                ));
+            }
+            let valid = match super::commit::split_co_author(identity) {
+                Some((name, _)) => name.is_some(),
+                None => is_bare_author_name(identity),
+            };
+            if !valid {
+                return Err(GcopError::Config(format!(
+                    "stats.authors.{}: '{}' is not a 'Name <email>' identity or a bare name",
+                    alias, identity
+                )));
+            }
         }
+        Ok(())
     }
 }
 
+/// Whether `value` is an author name without an email part.
+pub(crate) fn is_bare_author_name(value: &str) -> bool {
+    let value = value.trim();
+    !value.is_empty() && !value.contains(['<', '>', '@'])
+}
+
+fn default_scope_depth() -> usize {
+    1
+}
+
+fn default_scope_joiner() -> String {
+    "/".to_string()
+}
+
 fn default_true() -> bool {
     true
 }
@@ -269,6 +623,18 @@ fn default_severity() -> String {
     "info".to_string()
 }
 
+fn default_context_tokens() -> usize {
+    8000
+}
+
+fn default_max_chunks() -> usize {
+    8
+}
+
 fn default_max_file_size() -> u64 {
     10 * 1024 * 1024 // 10MB
 }
+
+fn default_rename_threshold() -> u16 {
+    50
+}
@@ -1,11 +1,18 @@
 //! Commit command configuration structures.
 
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{GcopError, Result};
+
+use super::llm::CommandLlmConfig;
+
 /// Commit message convention style.
 ///
 /// Controls the target format requested from the LLM.
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ConventionStyle {
     /// Conventional Commits: `type(scope): description`.
@@ -17,6 +24,91 @@ pub enum ConventionStyle {
     Custom,
 }
 
+/// Whether generated messages have a body below the subject line.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyMode {
+    /// The model decides.
+    #[default]
+    Auto,
+    /// Always add a body.
+    Always,
+    /// Subject line only (footers such as `BREAKING CHANGE` are kept).
+    Never,
+}
+
+/// How `style = "gitmoji"` headers are produced.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GitmojiMode {
+    /// The LLM writes the gitmoji header, guided by the mapping table.
+    #[default]
+    Prompt,
+    /// The LLM writes a Conventional Commits header, which post-processing
+    /// converts with the mapping table.
+    Convert,
+}
+
+/// Placement of the ticket ID extracted from the branch name.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TicketPlacement {
+    /// Trailer at the end of the message: `Refs: ABC-123`.
+    #[default]
+    Footer,
+    /// Suffix of the subject line: `feat: add login (ABC-123)`.
+    Subject,
+}
+
+/// What `commit` does when no interactive terminal is available (hooks, CI, pipes).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum NonInteractiveAction {
+    /// Commit the first generated message, as with `--yes --no-edit`.
+    #[default]
+    Commit,
+    /// Only print the generated message, as with `--dry-run`.
+    DryRun,
+    /// Refuse to run unless `--yes` or `--dry-run` is given.
+    Fail,
+}
+
+/// Built-in quick-pick feedback used when `[commit.feedback_presets]` is not set.
+pub const DEFAULT_FEEDBACK_PRESETS: &[(&str, &str)] = &[
+    ("shorter", "Make the message shorter and more concise"),
+    (
+        "more detail in body",
+        "Add a body explaining what changed and why",
+    ),
+    ("use Chinese", "Write the commit message in Chinese"),
+    (
+        "different type",
+        "Use a different commit type that better fits the changes",
+    ),
+];
+
+/// Trailers appended to every generated commit message.
+///
+/// # Example
+/// ```toml
+/// [commit.trailers]
+/// signoff = true
+/// co_authors = ["Jane Doe <jane@example.com>"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, JsonSchema)]
+pub struct CommitTrailers {
+    /// Add a `Signed-off-by` trailer (DCO) using git's `user.name` / `user.email`.
+    #[serde(default)]
+    pub signoff: bool,
+
+    /// Co-authors added as `Co-authored-by` trailers.
+    ///
+    /// Entries are `Name <email>` or a bare email; a bare email is resolved to
+    /// an author name from the repository history.
+    #[serde(default)]
+    pub co_authors: Vec<String>,
+}
+
 /// Commit convention configuration.
 ///
 /// Defines team-specific commit rules injected into prompt generation.
@@ -28,7 +120,19 @@ pub enum ConventionStyle {
 /// types = ["feat", "fix", "docs", "style", "refactor", "perf", "test", "chore", "ci"]
 /// extra_prompt = "All commit messages must be in English"
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq)]
+///
+/// Gitmoji with a team-specific emoji set, converted deterministically:
+/// ```toml
+/// [commit.convention]
+/// style = "gitmoji"
+/// gitmoji_mode = "convert"
+///
+/// [commit.convention.gitmoji]
+/// feat = ":sparkles:"
+/// fix = ":bug:"
+/// breaking = ":boom:"
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, JsonSchema)]
 pub struct CommitConvention {
     /// Convention style.
     #[serde(default)]
@@ -43,6 +147,63 @@ pub struct CommitConvention {
 
     /// Additional prompt text appended after built-in instructions.
     pub extra_prompt: Option<String>,
+
+    /// Commit type → emoji table (replaces the built-in table when set).
+    ///
+    /// The `breaking` key, when present, is used for breaking changes.
+    /// Messages are converted between conventional and gitmoji headers with
+    /// this table.
+    #[serde(default)]
+    pub gitmoji: Option<BTreeMap<String, String>>,
+
+    /// How gitmoji headers are produced (used when `style = "gitmoji"`).
+    #[serde(default)]
+    pub gitmoji_mode: GitmojiMode,
+}
+
+/// Hard limits generated messages must meet (`[commit.constraints]`).
+///
+/// A generated message breaking them is sent back to the model for repair,
+/// up to `max_repairs` times, before it is shown.
+///
+/// # Example
+/// ```toml
+/// [commit.constraints]
+/// max_subject_length = 72
+/// imperative = true
+/// no_trailing_period = true
+/// max_repairs = 2
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct CommitConstraints {
+    /// Maximum length of the subject line in characters (`0` disables).
+    #[serde(default = "default_max_subject_length")]
+    pub max_subject_length: usize,
+
+    /// Whether the subject must start with an imperative verb ("add", not
+    /// "added" or "adds").
+    #[serde(default = "default_true")]
+    pub imperative: bool,
+
+    /// Whether the subject must not end with a period.
+    #[serde(default = "default_true")]
+    pub no_trailing_period: bool,
+
+    /// Repair requests sent to the model before showing a message that
+    /// still breaks the limits.
+    #[serde(default = "default_max_repairs")]
+    pub max_repairs: usize,
+}
+
+impl Default for CommitConstraints {
+    fn default() -> Self {
+        Self {
+            max_subject_length: default_max_subject_length(),
+            imperative: true,
+            no_trailing_period: true,
+            max_repairs: default_max_repairs(),
+        }
+    }
 }
 
 /// Commit command configuration.
@@ -53,9 +214,25 @@ pub struct CommitConvention {
 /// - `show_diff_preview`: show diff preview before generation (default: `true`)
 /// - `allow_edit`: allow editing generated messages (default: `true`)
 /// - `split`: enable atomic split commit mode by default (default: `false`)
+/// - `split_edit_plan`: open the split commit plan in the editor before the action menu (default: `false`)
+/// - `split_verify_command`: command run on each split group's staged snapshot before committing it (optional)
+/// - `auto_stage`: stage tracked changes without asking when nothing is staged (default: `false`)
+/// - `review`: quick review of staged changes for critical issues before generation (default: `false`)
 /// - `custom_prompt`: prompt customization text (optional; normal mode replaces base system prompt, split mode appends constraints)
 /// - `max_retries`: maximum generation attempts, including the first one (default: `10`)
 /// - `convention`: optional commit convention config
+/// - `constraints`: optional hard limits with automatic repair (see [`CommitConstraints`])
+/// - `body`: whether messages have a body (`"auto"`, `"always"` or `"never"`, default: `"auto"`)
+/// - `body_bullets`: write the body as a bulleted list (default: `false`)
+/// - `learn_from_history`: include recent commit subjects as few-shot examples (default: `false`)
+/// - `history_sample_size`: maximum number of history examples (default: `10`)
+/// - `ticket_pattern`: regex extracting a ticket ID from the branch name (default: `[A-Z][A-Z0-9]+-[0-9]+`, empty disables)
+/// - `ticket_placement`: where the ticket ID is added when missing (`"footer"` or `"subject"`, default: `"footer"`)
+/// - `sign`: force (`true`) or disable (`false`) commit signing; unset follows git's `commit.gpgsign`
+/// - `trailers`: `Signed-off-by` / `Co-authored-by` trailers appended to generated messages
+/// - `non_interactive`: behavior without a terminal (`"commit"`, `"dry-run"` or `"fail"`, default: `"commit"`)
+/// - `feedback_presets`: quick-pick instructions for "retry with feedback" (`label = "instruction"`; default: built-in list)
+/// - `llm`: provider/model used for commit messages instead of the defaults (see [`CommandLlmConfig`])
 ///
 /// # Example
 /// ```toml
@@ -64,13 +241,15 @@ pub struct CommitConvention {
 /// allow_edit = true
 /// split = false
 /// max_retries = 10
+/// learn_from_history = true
+/// history_sample_size = 10
 /// custom_prompt = "Generate a concise commit message"
 ///
 /// [commit.convention]
 /// style = "conventional"
 /// types = ["feat", "fix", "docs", "refactor", "test", "chore"]
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct CommitConfig {
     /// Whether to show a diff preview before generation.
     #[serde(default = "default_true")]
@@ -84,12 +263,33 @@ pub struct CommitConfig {
     #[serde(default)]
     pub split: bool,
 
+    /// Whether split mode opens the generated commit plan in the editor
+    /// before asking what to do with it.
+    #[serde(default)]
+    pub split_edit_plan: bool,
+
+    /// Command run on each split group's staged snapshot before it is
+    /// committed (`commit --verify` overrides it). Empty disables. Only
+    /// honored from user config, never from project config.
+    #[serde(default)]
+    pub split_verify_command: Option<String>,
+
+    /// Whether to stage modified tracked files without asking when nothing is staged.
+    #[serde(default)]
+    pub auto_stage: bool,
+
+    /// Whether to review the staged changes for critical issues before
+    /// generating the message, like `commit --review`.
+    #[serde(default)]
+    pub review: bool,
+
     /// Prompt customization text for commit generation.
     ///
     /// Normal mode: replaces the built-in commit system prompt.
     /// Split mode: appended as additional grouping constraints.
     ///
-    /// No placeholder substitution is performed (`{diff}` is passed literally).
+    /// No placeholder substitution is performed (`{diff}` is passed literally);
+    /// the user message is customized through prompt templates instead.
     #[serde(default)]
     pub custom_prompt: Option<String>,
 
@@ -100,6 +300,127 @@ pub struct CommitConfig {
     /// Optional commit convention config, usually set in `.gcop/config.toml`.
     #[serde(default)]
     pub convention: Option<CommitConvention>,
+
+    /// Optional hard limits enforced on generated messages.
+    #[serde(default)]
+    pub constraints: Option<CommitConstraints>,
+
+    /// Whether generated messages have a body; `always` and `never` are
+    /// requested in the prompt and checked afterwards.
+    #[serde(default)]
+    pub body: BodyMode,
+
+    /// Whether the body must be a bulleted list (`- item` lines).
+    #[serde(default)]
+    pub body_bullets: bool,
+
+    /// Whether to show recent commit subjects to the LLM as style examples.
+    ///
+    /// Merge commits, fixup/squash commits and overly long subjects are skipped.
+    #[serde(default)]
+    pub learn_from_history: bool,
+
+    /// Maximum number of recent commit subjects used as examples.
+    #[serde(default = "default_history_sample_size")]
+    pub history_sample_size: usize,
+
+    /// Regex used to extract a ticket ID (Jira, Linear, ...) from the branch name.
+    ///
+    /// The first capture group is used when present, otherwise the whole match.
+    /// An empty pattern disables ticket injection.
+    #[serde(default = "default_ticket_pattern")]
+    pub ticket_pattern: String,
+
+    /// Where the ticket ID is added when the generated message lacks it.
+    #[serde(default)]
+    pub ticket_placement: TicketPlacement,
+
+    /// Commit signing override.
+    ///
+    /// `None` follows git configuration (`commit.gpgsign`), `Some(true)` always
+    /// signs (`git commit -S`) and `Some(false)` never signs.
+    #[serde(default)]
+    pub sign: Option<bool>,
+
+    /// Trailers appended after post-processing the generated message.
+    #[serde(default)]
+    pub trailers: CommitTrailers,
+
+    /// Behavior when stdin/stdout are not a terminal.
+    #[serde(default)]
+    pub non_interactive: NonInteractiveAction,
+
+    /// Quick-pick feedback offered by "retry with feedback", as `label = "instruction"`.
+    ///
+    /// `None` uses [`DEFAULT_FEEDBACK_PRESETS`]; an empty table goes straight
+    /// to free-text input.
+    #[serde(default)]
+    pub feedback_presets: Option<BTreeMap<String, String>>,
+
+    /// Provider/model override for commit message generation (`[commit.llm]`).
+    #[serde(default)]
+    pub llm: CommandLlmConfig,
+}
+
+impl CommitConfig {
+    /// Validates commit configuration (ticket regex and co-author entries).
+    pub fn validate(&self) -> Result<()> {
+        if !self.ticket_pattern.is_empty() {
+            regex::Regex::new(&self.ticket_pattern).map_err(|e| {
+                GcopError::Config(format!("commit.ticket_pattern: invalid regex: {}", e))
+            })?;
+        }
+        for co_author in &self.trailers.co_authors {
+            if split_co_author(co_author).is_none() {
+                return Err(GcopError::Config(format!(
+                    "commit.trailers.co_authors: '{}' is not `Name <email>` or an email address",
+                    co_author
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Quick-pick feedback as `(label, instruction)` pairs.
+    ///
+    /// Configured presets are listed by label; without `[commit.feedback_presets]`
+    /// the built-in list is used.
+    pub fn feedback_presets(&self) -> Vec<(String, String)> {
+        match &self.feedback_presets {
+            Some(presets) => presets
+                .iter()
+                .filter(|(_, instruction)| !instruction.trim().is_empty())
+                .map(|(label, instruction)| (label.clone(), instruction.trim().to_string()))
+                .collect(),
+            None => DEFAULT_FEEDBACK_PRESETS
+                .iter()
+                .map(|(label, instruction)| (label.to_string(), instruction.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// Splits a co-author entry into its optional name and email.
+///
+/// Accepts `Name <email>` or a bare `email`; returns `None` when malformed.
+pub(crate) fn split_co_author(value: &str) -> Option<(Option<&str>, &str)> {
+    let value = value.trim();
+    let (name, email) = match value.strip_suffix('>') {
+        Some(rest) => {
+            let (name, email) = rest.split_once('<')?;
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            (Some(name), email.trim())
+        }
+        None => (None, value),
+    };
+    let (local, domain) = email.split_once('@')?;
+    let well_formed = !local.is_empty()
+        && !domain.is_empty()
+        && !email.contains(|c: char| c.is_whitespace() || matches!(c, '<' | '>'));
+    well_formed.then_some((name, email))
 }
 
 impl Default for CommitConfig {
@@ -108,9 +429,25 @@ impl Default for CommitConfig {
             show_diff_preview: true,
             allow_edit: true,
             split: false,
+            split_edit_plan: false,
+            split_verify_command: None,
+            auto_stage: false,
+            review: false,
             custom_prompt: None,
             max_retries: default_commit_max_retries(),
             convention: None,
+            constraints: None,
+            body: BodyMode::default(),
+            body_bullets: false,
+            learn_from_history: false,
+            history_sample_size: default_history_sample_size(),
+            ticket_pattern: default_ticket_pattern(),
+            ticket_placement: TicketPlacement::default(),
+            sign: None,
+            trailers: CommitTrailers::default(),
+            non_interactive: NonInteractiveAction::default(),
+            feedback_presets: None,
+            llm: CommandLlmConfig::default(),
         }
     }
 }
@@ -122,3 +459,19 @@ fn default_true() -> bool {
 fn default_commit_max_retries() -> usize {
     10
 }
+
+fn default_max_subject_length() -> usize {
+    72
+}
+
+fn default_max_repairs() -> usize {
+    2
+}
+
+fn default_history_sample_size() -> usize {
+    10
+}
+
+fn default_ticket_pattern() -> String {
+    "[A-Z][A-Z0-9]+-[0-9]+".to_string()
+}
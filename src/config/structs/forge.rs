@@ -0,0 +1,89 @@
+//! Code forge integration settings.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GcopError, Result};
+
+/// Supported code forges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    /// GitLab (gitlab.com or self-managed).
+    GitLab,
+}
+
+/// Code forge configuration (`[forge]`).
+///
+/// Used by `review mr <iid>`. Every field is optional: the forge type,
+/// instance URL and project path are derived from the git remote.
+///
+/// # Fields
+/// - `kind`: forge type; auto-detected from the remote host when unset
+/// - `url`: instance base URL (default: `https://<remote host>`)
+/// - `project`: project path such as `group/sub/project` (default: from the remote)
+/// - `token`: API token (falls back to the `GITLAB_TOKEN` environment variable)
+/// - `remote`: git remote used for detection (default: `origin`)
+///
+/// # Example
+/// ```toml
+/// [forge]
+/// kind = "gitlab"
+/// url = "https://git.example.com"
+/// token = "glpat-..."
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct ForgeConfig {
+    /// Forge type override.
+    #[serde(default)]
+    pub kind: Option<ForgeKind>,
+
+    /// Instance base URL override. Only honored from user config, since the
+    /// token is sent to it.
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Project path override.
+    #[serde(default)]
+    pub project: Option<String>,
+
+    /// API token. Only honored from user config.
+    #[serde(default)]
+    pub token: Option<String>,
+
+    /// Git remote used to detect the forge and project.
+    #[serde(default = "default_remote")]
+    pub remote: String,
+}
+
+impl Default for ForgeConfig {
+    fn default() -> Self {
+        Self {
+            kind: None,
+            url: None,
+            project: None,
+            token: None,
+            remote: default_remote(),
+        }
+    }
+}
+
+impl ForgeConfig {
+    /// Validates forge configuration.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(url) = &self.url
+            && !url.starts_with("http://")
+            && !url.starts_with("https://")
+        {
+            return Err(GcopError::Config(format!(
+                "forge.url must start with http:// or https:// (got '{}')",
+                url
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn default_remote() -> String {
+    "origin".to_string()
+}
@@ -0,0 +1,69 @@
+//! `prepare-commit-msg` hook settings.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// What the hook does when no message can be generated in time.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HookFailure {
+    /// Leave the commit message file untouched and let `git commit` go on.
+    #[default]
+    Empty,
+    /// Exit with an error so `git commit` is aborted.
+    Abort,
+    /// Write a placeholder subject to edit, unless the file already holds a message.
+    Placeholder,
+}
+
+/// Hook configuration (`[hook]`).
+///
+/// Applies to `gcop-rs hook run`, called by the installed
+/// `prepare-commit-msg` hook. A slow or unreachable provider must not block
+/// `git commit`, so generation is bounded by `timeout_secs` and failures are
+/// handled by `on_failure`.
+///
+/// # Fields
+/// - `timeout_secs`: maximum seconds spent generating a message; `0` disables the limit (default: `30`)
+/// - `on_failure`: `"empty"`, `"abort"` or `"placeholder"` (default: `"empty"`)
+/// - `cache_ttl_secs`: seconds a generated message is reused when `git commit` is retried with the same staged changes; `0` disables the cache (default: `600`)
+///
+/// # Example
+/// ```toml
+/// [hook]
+/// timeout_secs = 15
+/// on_failure = "placeholder"
+/// cache_ttl_secs = 300
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct HookConfig {
+    /// Maximum seconds spent generating a message (`0` = no limit).
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Behavior when generation fails or times out.
+    #[serde(default)]
+    pub on_failure: HookFailure,
+
+    /// Seconds a generated message is reused for the same staged tree (`0` = no cache).
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for HookConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            on_failure: HookFailure::default(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+        }
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    600
+}
@@ -2,15 +2,17 @@
 
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::network::{ProviderNetworkConfig, redact_proxy_credentials, validate_proxy_url};
 use crate::error::Result;
 
 /// LLM API backend type.
 ///
 /// Determines which provider implementation to instantiate.
 /// If [`ProviderConfig::api_style`] is `None`, the style is inferred from the provider name.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ApiStyle {
     /// Anthropic Claude API.
@@ -22,6 +24,16 @@ pub enum ApiStyle {
     Ollama,
     /// Google Gemini API.
     Gemini,
+    /// DeepSeek preset (OpenAI-compatible, with DeepSeek default endpoint).
+    #[serde(rename = "deepseek")]
+    DeepSeek,
+    /// Moonshot (Kimi) preset (OpenAI-compatible, with Moonshot default endpoint).
+    #[serde(alias = "kimi")]
+    Moonshot,
+    /// In-process llama.cpp inference on a local GGUF file (cargo feature `local`).
+    Local,
+    /// External command speaking gcop-rs's JSON stdin/stdout protocol.
+    Exec,
 }
 
 impl std::fmt::Display for ApiStyle {
@@ -31,6 +43,10 @@ impl std::fmt::Display for ApiStyle {
             ApiStyle::OpenAI => write!(f, "openai"),
             ApiStyle::Ollama => write!(f, "ollama"),
             ApiStyle::Gemini => write!(f, "gemini"),
+            ApiStyle::DeepSeek => write!(f, "deepseek"),
+            ApiStyle::Moonshot => write!(f, "moonshot"),
+            ApiStyle::Local => write!(f, "local"),
+            ApiStyle::Exec => write!(f, "exec"),
         }
     }
 }
@@ -44,6 +60,10 @@ impl std::str::FromStr for ApiStyle {
             "openai" => Ok(ApiStyle::OpenAI),
             "ollama" => Ok(ApiStyle::Ollama),
             "gemini" => Ok(ApiStyle::Gemini),
+            "deepseek" => Ok(ApiStyle::DeepSeek),
+            "moonshot" | "kimi" => Ok(ApiStyle::Moonshot),
+            "local" => Ok(ApiStyle::Local),
+            "exec" => Ok(ApiStyle::Exec),
             _ => Err(format!("Unknown API style: '{}'", s)),
         }
     }
@@ -57,6 +77,10 @@ impl ApiStyle {
             ApiStyle::OpenAI => "gpt-4o-mini",
             ApiStyle::Ollama => "llama3.2",
             ApiStyle::Gemini => "gemini-3-flash-preview",
+            ApiStyle::DeepSeek => "deepseek-chat",
+            ApiStyle::Moonshot => "kimi-latest",
+            ApiStyle::Local => "model.gguf",
+            ApiStyle::Exec => "default",
         }
     }
 }
@@ -72,6 +96,11 @@ impl ApiStyle {
 /// - `model`: model name
 /// - `max_tokens`: maximum generated token count (optional)
 /// - `temperature`: sampling temperature in `0.0..=2.0` (optional)
+/// - `proxy`: proxy URL override; `""` connects directly (optional)
+/// - `network`: timeout/retry overrides for this provider (optional)
+/// - `pricing`: token prices used to estimate cost in the usage ledger (optional)
+/// - `tiers`: pick a stronger model for large diffs (optional, see [`ModelTiers`])
+/// - `allowed_models`: models accepted as per-run overrides such as `--model` (optional)
 /// - `extra`: additional provider-specific parameters
 ///
 /// # Example
@@ -81,9 +110,11 @@ impl ApiStyle {
 /// api_key = "sk-ant-..."
 /// max_tokens = 1000
 /// temperature = 0.7
+/// top_p = 0.9 # optional
+/// stop = ["\n\n\n"] # optional
 /// endpoint = "https://api.anthropic.com" # optional
 /// ```
-#[derive(Clone, Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ProviderConfig {
     /// API style used to select the backend implementation.
     ///
@@ -109,12 +140,50 @@ pub struct ProviderConfig {
     /// Model name.
     pub model: String,
 
-    /// Maximum generated token count.
+    /// Maximum generated token count (`max_output_tokens` is accepted as an alias).
+    ///
+    /// Sent as `max_tokens` (Claude, OpenAI), `maxOutputTokens` (Gemini) or
+    /// `num_predict` (Ollama).
+    #[serde(alias = "max_output_tokens")]
     pub max_tokens: Option<u32>,
 
     /// Sampling temperature in `0.0..=2.0`.
     pub temperature: Option<f32>,
 
+    /// Nucleus sampling probability in `0.0..=1.0` (`topP` for Gemini).
+    #[serde(default)]
+    pub top_p: Option<f32>,
+
+    /// Stop sequences (`stop_sequences` for Claude, `stopSequences` for Gemini).
+    #[serde(default)]
+    pub stop: Option<Vec<String>>,
+
+    /// Proxy URL override for this provider.
+    ///
+    /// Replaces `network.proxy`; an empty string connects directly.
+    #[serde(default)]
+    pub proxy: Option<String>,
+
+    /// Network overrides for this provider (`[llm.providers.<name>.network]`).
+    #[serde(default)]
+    pub network: Option<ProviderNetworkConfig>,
+
+    /// Token prices (`[llm.providers.<name>.pricing]`) for cost estimates.
+    #[serde(default)]
+    pub pricing: Option<ModelPricing>,
+
+    /// Diff-size based model selection (`[llm.providers.<name>.tiers]`).
+    ///
+    /// When set, `model` is only used as the small tier if `tiers.small` is omitted.
+    #[serde(default)]
+    pub tiers: Option<ModelTiers>,
+
+    /// Models accepted as `--model` / `[commit.llm]` / `[review.llm]` overrides.
+    ///
+    /// `None` accepts any model.
+    #[serde(default)]
+    pub allowed_models: Option<Vec<String>>,
+
     /// Additional provider-specific parameters.
     #[serde(flatten)]
     pub extra: HashMap<String, serde_json::Value>,
@@ -131,6 +200,16 @@ impl std::fmt::Debug for ProviderConfig {
             .field("model", &self.model)
             .field("max_tokens", &self.max_tokens)
             .field("temperature", &self.temperature)
+            .field("top_p", &self.top_p)
+            .field("stop", &self.stop)
+            .field(
+                "proxy",
+                &self.proxy.as_deref().map(redact_proxy_credentials),
+            )
+            .field("network", &self.network)
+            .field("pricing", &self.pricing)
+            .field("tiers", &self.tiers)
+            .field("allowed_models", &self.allowed_models)
             .finish()
     }
 }
@@ -151,6 +230,50 @@ impl ProviderConfig {
                 name, temp
             )));
         }
+        if let Some(top_p) = self.top_p
+            && !(0.0..=1.0).contains(&top_p)
+        {
+            return Err(GcopError::Config(format!(
+                "Provider '{}': top_p {} out of range [0.0, 1.0]",
+                name, top_p
+            )));
+        }
+        if let Some(stop) = &self.stop
+            && stop.iter().any(|s| s.is_empty())
+        {
+            return Err(GcopError::Config(format!(
+                "Provider '{}': stop sequences must not be empty",
+                name
+            )));
+        }
+        if let Some(proxy) = self.proxy.as_deref()
+            && !proxy.is_empty()
+        {
+            validate_proxy_url(&format!("Provider '{}': proxy", name), proxy)?;
+        }
+        if let Some(network) = &self.network {
+            network.validate(name)?;
+        }
+        if let Some(pricing) = &self.pricing
+            && (!pricing.input.is_finite()
+                || !pricing.output.is_finite()
+                || pricing.input < 0.0
+                || pricing.output < 0.0)
+        {
+            return Err(GcopError::Config(format!(
+                "Provider '{}': pricing must be non-negative numbers",
+                name
+            )));
+        }
+        if let Some(tiers) = &self.tiers
+            && (tiers.large.trim().is_empty()
+                || tiers.small.as_deref().is_some_and(|m| m.trim().is_empty()))
+        {
+            return Err(GcopError::Config(format!(
+                "Provider '{}': tier model names must not be empty",
+                name
+            )));
+        }
         if let Some(ref key) = self.api_key
             && key.trim().is_empty()
         {
@@ -163,6 +286,139 @@ impl ProviderConfig {
     }
 }
 
+/// Token prices of a provider's model, per million tokens.
+///
+/// The currency is whatever the prices are given in; the usage ledger only
+/// multiplies them with reported token counts.
+///
+/// # Example
+/// ```toml
+/// [llm.providers.claude.pricing]
+/// input = 3.0
+/// output = 15.0
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ModelPricing {
+    /// Price per million prompt (input) tokens.
+    pub input: f64,
+    /// Price per million completion (output) tokens.
+    pub output: f64,
+}
+
+impl ModelPricing {
+    /// Estimated cost of the given token counts.
+    pub fn cost(&self, prompt_tokens: u64, completion_tokens: u64) -> f64 {
+        (prompt_tokens as f64 * self.input + completion_tokens as f64 * self.output) / 1_000_000.0
+    }
+}
+
+/// Model tiers of a provider, selected by diff size.
+///
+/// Requests whose diff is larger than `threshold` bytes (after
+/// `max_diff_size` truncation) use `large`; smaller ones use `small`.
+/// Requests sent as a finished prompt (commit, split grouping, tags) are
+/// measured by the user message, which is dominated by the diff.
+///
+/// # Example
+/// ```toml
+/// [llm.providers.openai.tiers]
+/// small = "gpt-4o-mini"
+/// large = "gpt-4o"
+/// threshold = 20480
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct ModelTiers {
+    /// Model for small diffs; defaults to the provider's `model`.
+    #[serde(default)]
+    pub small: Option<String>,
+    /// Model for diffs larger than `threshold`.
+    pub large: String,
+    /// Diff size in bytes above which `large` is used (default: 20 KiB).
+    #[serde(default = "default_tier_threshold")]
+    pub threshold: usize,
+}
+
+/// Provider and model used by one command (`[commit.llm]`, `[review.llm]`).
+///
+/// CLI `--provider` / `--model` take precedence; `model` only applies to the
+/// provider it is configured for, and the fallback chain is unchanged.
+///
+/// # Example
+/// ```toml
+/// [commit.llm]
+/// provider = "gemini"
+/// model = "gemini-3-flash-preview"
+///
+/// [review.llm]
+/// provider = "claude"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct CommandLlmConfig {
+    /// Provider used instead of `llm.default_provider`.
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Model used instead of the provider's `model` (and `tiers`).
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+impl CommandLlmConfig {
+    /// Main provider and model override for a run.
+    ///
+    /// `cli_provider` wins over `provider`, which wins over `default_provider`;
+    /// `cli_model` wins over `model`, which is dropped when the CLI picks
+    /// another provider.
+    pub fn select<'a>(
+        &'a self,
+        default_provider: &'a str,
+        cli_provider: Option<&'a str>,
+        cli_model: Option<&'a str>,
+    ) -> (&'a str, Option<&'a str>) {
+        let configured = self.provider.as_deref().unwrap_or(default_provider);
+        let provider = cli_provider.unwrap_or(configured);
+        let model = cli_model.or(self.model.as_deref().filter(|_| provider == configured));
+        (provider, model)
+    }
+
+    /// Validates that the provider exists and the model is not empty.
+    pub(crate) fn validate(&self, section: &str, llm: &LLMConfig) -> Result<()> {
+        use crate::error::GcopError;
+        if let Some(provider) = &self.provider
+            && !llm.providers.is_empty()
+            && !llm.providers.contains_key(provider)
+        {
+            return Err(GcopError::Config(format!(
+                "{}.llm.provider: '{}' not found in [llm.providers]",
+                section, provider
+            )));
+        }
+        if self.model.as_deref().is_some_and(|m| m.trim().is_empty()) {
+            return Err(GcopError::Config(format!(
+                "{}.llm.model must not be empty",
+                section
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn default_tier_threshold() -> usize {
+    20 * 1024
+}
+
+/// How `default_provider` and `fallback_providers` are used.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderStrategy {
+    /// Try providers one after another until one succeeds.
+    #[default]
+    Fallback,
+    /// Send the request to the first two providers at once and use whichever
+    /// answers first; the slower request is cancelled. The remaining
+    /// providers are still tried in order if both fail.
+    Race,
+}
+
 /// LLM configuration.
 ///
 /// Selects providers and controls prompt input size.
@@ -170,14 +426,17 @@ impl ProviderConfig {
 /// # Fields
 /// - `default_provider`: provider name, matching a key under `[llm.providers.<name>]`
 /// - `fallback_providers`: providers to try in order if the primary provider fails
+/// - `strategy`: how the provider chain is used (see [`ProviderStrategy`], default: `fallback`)
 /// - `providers`: per-provider settings map
 /// - `max_diff_size`: maximum diff size sent to the LLM in bytes for commit/review/hook non-split flows (default: 100 KiB)
+/// - `offline_fallback`: generate a heuristic commit message when no provider can be reached (default: `false`)
 ///
 /// # Example
 /// ```toml
 /// [llm]
 /// default_provider = "claude"
 /// fallback_providers = ["openai", "gemini", "ollama"]
+/// strategy = "fallback"
 /// max_diff_size = 102400
 ///
 /// [llm.providers.claude]
@@ -188,7 +447,7 @@ impl ProviderConfig {
 /// api_key = "sk-..."
 /// model = "gpt-4"
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct LLMConfig {
     /// Provider name used by default.
     ///
@@ -199,6 +458,10 @@ pub struct LLMConfig {
     #[serde(default)]
     pub fallback_providers: Vec<String>,
 
+    /// How the provider chain is used.
+    #[serde(default)]
+    pub strategy: ProviderStrategy,
+
     /// Provider settings keyed by provider name.
     #[serde(default)]
     pub providers: HashMap<String, ProviderConfig>,
@@ -208,6 +471,11 @@ pub struct LLMConfig {
     /// Oversized diffs are truncated before prompt generation in commit/review/hook non-split flows.
     #[serde(default = "default_max_diff_size")]
     pub max_diff_size: usize,
+
+    /// Fall back to a heuristic commit message built from the diff when every
+    /// provider fails with a network or API error (commit and hook flows).
+    #[serde(default)]
+    pub offline_fallback: bool,
 }
 
 impl Default for LLMConfig {
@@ -215,8 +483,10 @@ impl Default for LLMConfig {
         Self {
             default_provider: "claude".to_string(),
             fallback_providers: Vec::new(),
+            strategy: ProviderStrategy::default(),
             providers: HashMap::new(),
             max_diff_size: default_max_diff_size(),
+            offline_fallback: false,
         }
     }
 }
@@ -0,0 +1,103 @@
+//! Log file configuration.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GcopError, Result};
+
+/// Line format of the log file.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines, like the terminal output without colors.
+    #[default]
+    Text,
+    /// One JSON object per line (`timestamp`, `level`, `target`, `message`, fields).
+    Json,
+}
+
+/// Log file configuration (`[logging]`).
+///
+/// Terminal output is unchanged; when `file` is set, the same events are also
+/// written to a file at `level`, independent of `--verbose`. Useful where
+/// stderr is not visible, such as the `prepare-commit-msg` hook.
+///
+/// # Fields
+/// - `file`: log file path, `~/` expanded (default: none, no file logging)
+/// - `format`: `text` or `json` (default: `text`)
+/// - `level`: minimum level of gcop-rs events (default: `debug`); other crates log warnings only
+/// - `max_size`: size in bytes after which the file is rotated (default: 5 MiB)
+/// - `max_files`: rotated files kept as `<file>.1` ... `<file>.N` (default: `3`)
+///
+/// # Example
+/// ```toml
+/// [logging]
+/// file = "~/.local/state/gcop/gcop.log"
+/// format = "json"
+/// level = "debug"
+/// max_size = 5242880
+/// max_files = 3
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct LoggingConfig {
+    /// Log file path; `None` disables file logging. Only honored from user
+    /// config, never from project config.
+    #[serde(default)]
+    pub file: Option<String>,
+
+    /// Line format: `text` or `json`.
+    #[serde(default)]
+    pub format: LogFormat,
+
+    /// Minimum level of gcop-rs events: `error`, `warn`, `info`, `debug` or `trace`.
+    #[serde(default = "default_log_level")]
+    pub level: String,
+
+    /// Size in bytes after which the log file is rotated.
+    #[serde(default = "default_log_max_size")]
+    pub max_size: u64,
+
+    /// Number of rotated files to keep (`0` truncates the file instead).
+    #[serde(default = "default_log_max_files")]
+    pub max_files: usize,
+}
+
+impl LoggingConfig {
+    /// Validates logging configuration (known level, non-zero size).
+    pub fn validate(&self) -> Result<()> {
+        if self.level.parse::<tracing::Level>().is_err() {
+            return Err(GcopError::Config(format!(
+                "logging.level: expected error, warn, info, debug or trace, got '{}'",
+                self.level
+            )));
+        }
+        if self.max_size == 0 {
+            return Err(GcopError::Config("logging.max_size cannot be 0".into()));
+        }
+        Ok(())
+    }
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            format: LogFormat::default(),
+            level: default_log_level(),
+            max_size: default_log_max_size(),
+            max_files: default_log_max_files(),
+        }
+    }
+}
+
+fn default_log_level() -> String {
+    "debug".to_string()
+}
+
+fn default_log_max_size() -> u64 {
+    5 * 1024 * 1024 // 5MB
+}
+
+fn default_log_max_files() -> usize {
+    3
+}
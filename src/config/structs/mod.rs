@@ -1,9 +1,31 @@
 mod app;
 mod commit;
+mod forge;
+mod hook;
 mod llm;
+mod logging;
 mod network;
+mod plugins;
+mod privacy;
+mod profile;
+mod update;
 
-pub use app::{AppConfig, FileConfig, ReviewConfig, UIConfig};
-pub use commit::{CommitConfig, CommitConvention, ConventionStyle};
-pub use llm::{ApiStyle, LLMConfig, ProviderConfig};
-pub use network::NetworkConfig;
+pub(crate) use app::is_bare_author_name;
+pub use app::{AppConfig, FileConfig, RemotePreset, ReviewConfig, StatsConfig, UIConfig};
+pub(crate) use commit::split_co_author;
+pub use commit::{
+    BodyMode, CommitConfig, CommitConstraints, CommitConvention, CommitTrailers, ConventionStyle,
+    GitmojiMode, NonInteractiveAction, TicketPlacement,
+};
+pub use forge::{ForgeConfig, ForgeKind};
+pub use hook::{HookConfig, HookFailure};
+pub use llm::{
+    ApiStyle, CommandLlmConfig, LLMConfig, ModelPricing, ModelTiers, ProviderConfig,
+    ProviderStrategy,
+};
+pub use logging::{LogFormat, LoggingConfig};
+pub use network::{NetworkConfig, ProviderNetworkConfig};
+pub use plugins::PluginsConfig;
+pub use privacy::{PrivacyConfig, RedactionRule, SecretAction};
+pub use profile::ProfileConfig;
+pub use update::UpdateConfig;
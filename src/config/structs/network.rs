@@ -1,5 +1,6 @@
 //! Network and HTTP configuration structures.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{GcopError, Result};
@@ -14,6 +15,12 @@ use crate::error::{GcopError, Result};
 /// - `max_retries`: max retries for LLM API requests (default: `3`)
 /// - `retry_delay_ms`: initial retry delay in milliseconds (default: `1000`)
 /// - `max_retry_delay_ms`: max retry delay in milliseconds (default: `60000`)
+/// - `circuit_breaker_threshold`: consecutive failures before a fallback-chain provider is skipped (default: `3`, `0` disables)
+/// - `circuit_breaker_cooldown_secs`: seconds an open provider is skipped before it is re-probed (default: `60`)
+/// - `total_attempts`: HTTP attempts one request may make across the whole fallback chain (default: `0`, unlimited)
+/// - `total_timeout`: seconds one request may take across the whole fallback chain (default: `0`, unlimited)
+/// - `proxy`: proxy URL for all HTTP requests (default: environment/system proxy)
+/// - `no_proxy`: comma-separated hosts that bypass `proxy`
 ///
 /// # Example
 /// ```toml
@@ -23,8 +30,14 @@ use crate::error::{GcopError, Result};
 /// max_retries = 3
 /// retry_delay_ms = 1000
 /// max_retry_delay_ms = 60000
+/// circuit_breaker_threshold = 3
+/// circuit_breaker_cooldown_secs = 60
+/// total_attempts = 4
+/// total_timeout = 180
+/// proxy = "socks5h://127.0.0.1:1080"
+/// no_proxy = "localhost,127.0.0.1,.internal.example.com"
 /// ```
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct NetworkConfig {
     /// HTTP request timeout in seconds.
     #[serde(default = "default_request_timeout")]
@@ -45,6 +58,38 @@ pub struct NetworkConfig {
     /// Maximum retry delay in milliseconds.
     #[serde(default = "default_max_retry_delay_ms")]
     pub max_retry_delay_ms: u64,
+
+    /// Consecutive failures after which a provider in the fallback chain is skipped.
+    ///
+    /// Only applies when `fallback_providers` is configured. `0` disables the breaker.
+    #[serde(default = "default_circuit_breaker_threshold")]
+    pub circuit_breaker_threshold: u32,
+
+    /// Seconds a tripped provider is skipped before it is tried again.
+    #[serde(default = "default_circuit_breaker_cooldown_secs")]
+    pub circuit_breaker_cooldown_secs: u64,
+
+    /// HTTP attempts (first tries and retries) one request may make across
+    /// all providers of the fallback chain. `0` means unlimited.
+    #[serde(default)]
+    pub total_attempts: usize,
+
+    /// Seconds one request may take across all providers of the fallback
+    /// chain, including retry delays. `0` means unlimited.
+    #[serde(default)]
+    pub total_timeout: u64,
+
+    /// Proxy URL (`http://`, `https://`, `socks5://` or `socks5h://`).
+    ///
+    /// When unset, the `HTTPS_PROXY` / `HTTP_PROXY` / `ALL_PROXY` environment
+    /// variables and system proxy settings apply.
+    #[serde(default)]
+    pub proxy: Option<String>,
+
+    /// Comma-separated hosts, domains and IP ranges that bypass `proxy`
+    /// (same syntax as the `NO_PROXY` environment variable).
+    #[serde(default)]
+    pub no_proxy: Option<String>,
 }
 
 impl Default for NetworkConfig {
@@ -55,6 +100,12 @@ impl Default for NetworkConfig {
             max_retries: default_network_max_retries(),
             retry_delay_ms: default_retry_delay_ms(),
             max_retry_delay_ms: default_max_retry_delay_ms(),
+            circuit_breaker_threshold: default_circuit_breaker_threshold(),
+            circuit_breaker_cooldown_secs: default_circuit_breaker_cooldown_secs(),
+            total_attempts: 0,
+            total_timeout: 0,
+            proxy: None,
+            no_proxy: None,
         }
     }
 }
@@ -72,7 +123,109 @@ impl NetworkConfig {
                 "network.connect_timeout cannot be 0".into(),
             ));
         }
+        if let Some(proxy) = &self.proxy {
+            validate_proxy_url("network.proxy", proxy)?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-provider network overrides (`[llm.providers.<name>.network]`).
+///
+/// Unset fields inherit the global `[network]` value. Circuit-breaker and
+/// proxy settings are not overridable here (use the provider's `proxy` field).
+///
+/// # Example
+/// ```toml
+/// [llm.providers.ollama.network]
+/// request_timeout = 600
+/// max_retries = 0
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct ProviderNetworkConfig {
+    /// HTTP request timeout in seconds.
+    #[serde(default)]
+    pub request_timeout: Option<u64>,
+
+    /// HTTP connect timeout in seconds.
+    #[serde(default)]
+    pub connect_timeout: Option<u64>,
+
+    /// Maximum retries for LLM API requests.
+    #[serde(default)]
+    pub max_retries: Option<usize>,
+
+    /// Initial retry delay in milliseconds.
+    #[serde(default)]
+    pub retry_delay_ms: Option<u64>,
+
+    /// Maximum retry delay in milliseconds.
+    #[serde(default)]
+    pub max_retry_delay_ms: Option<u64>,
+}
+
+impl ProviderNetworkConfig {
+    /// Validates the overrides for the named provider.
+    pub fn validate(&self, name: &str) -> Result<()> {
+        if self.request_timeout == Some(0) {
+            return Err(GcopError::Config(format!(
+                "Provider '{}': network.request_timeout cannot be 0",
+                name
+            )));
+        }
+        if self.connect_timeout == Some(0) {
+            return Err(GcopError::Config(format!(
+                "Provider '{}': network.connect_timeout cannot be 0",
+                name
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl NetworkConfig {
+    /// Returns the effective settings for a provider with optional overrides.
+    pub fn with_overrides(&self, overrides: Option<&ProviderNetworkConfig>) -> NetworkConfig {
+        let Some(overrides) = overrides else {
+            return self.clone();
+        };
+        NetworkConfig {
+            request_timeout: overrides.request_timeout.unwrap_or(self.request_timeout),
+            connect_timeout: overrides.connect_timeout.unwrap_or(self.connect_timeout),
+            max_retries: overrides.max_retries.unwrap_or(self.max_retries),
+            retry_delay_ms: overrides.retry_delay_ms.unwrap_or(self.retry_delay_ms),
+            max_retry_delay_ms: overrides
+                .max_retry_delay_ms
+                .unwrap_or(self.max_retry_delay_ms),
+            ..self.clone()
+        }
+    }
+}
+
+/// Proxy URL schemes supported by the HTTP client.
+const PROXY_SCHEMES: [&str; 4] = ["http://", "https://", "socks5://", "socks5h://"];
+
+/// Checks that a proxy URL uses a supported scheme.
+pub(crate) fn validate_proxy_url(field: &str, url: &str) -> Result<()> {
+    if PROXY_SCHEMES.iter().any(|scheme| url.starts_with(scheme)) {
         Ok(())
+    } else {
+        Err(GcopError::Config(format!(
+            "{} must start with http://, https://, socks5:// or socks5h:// (got '{}')",
+            field,
+            redact_proxy_credentials(url)
+        )))
+    }
+}
+
+/// Replaces the `user:password@` part of a proxy URL with `***@`.
+pub(crate) fn redact_proxy_credentials(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", url));
+    let authority_end = rest.find('/').unwrap_or(rest.len());
+    match rest[..authority_end].rfind('@') {
+        Some(at) if scheme.is_empty() => format!("***{}", &rest[at..]),
+        Some(at) => format!("{}://***{}", scheme, &rest[at..]),
+        None => url.to_string(),
     }
 }
 
@@ -95,3 +248,60 @@ fn default_retry_delay_ms() -> u64 {
 fn default_max_retry_delay_ms() -> u64 {
     60_000 // 60 seconds
 }
+
+fn default_circuit_breaker_threshold() -> u32 {
+    3
+}
+
+fn default_circuit_breaker_cooldown_secs() -> u64 {
+    60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_overrides_merges_set_fields() {
+        let global = NetworkConfig {
+            proxy: Some("http://proxy:3128".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(global.with_overrides(None).request_timeout, 120);
+
+        let overrides = ProviderNetworkConfig {
+            request_timeout: Some(600),
+            max_retries: Some(0),
+            ..Default::default()
+        };
+        let effective = global.with_overrides(Some(&overrides));
+        assert_eq!(effective.request_timeout, 600);
+        assert_eq!(effective.max_retries, 0);
+        assert_eq!(effective.connect_timeout, global.connect_timeout);
+        assert_eq!(effective.retry_delay_ms, global.retry_delay_ms);
+        assert_eq!(effective.proxy, global.proxy);
+    }
+
+    #[test]
+    fn test_provider_network_rejects_zero_timeouts() {
+        let overrides = ProviderNetworkConfig {
+            connect_timeout: Some(0),
+            ..Default::default()
+        };
+        assert!(overrides.validate("ollama").is_err());
+        assert!(ProviderNetworkConfig::default().validate("ollama").is_ok());
+    }
+
+    #[test]
+    fn test_redact_proxy_credentials() {
+        assert_eq!(
+            redact_proxy_credentials("http://user:pw@proxy.corp:3128/"),
+            "http://***@proxy.corp:3128/"
+        );
+        assert_eq!(
+            redact_proxy_credentials("socks5://127.0.0.1:1080"),
+            "socks5://127.0.0.1:1080"
+        );
+        assert_eq!(redact_proxy_credentials("u@host:1"), "***@host:1");
+    }
+}
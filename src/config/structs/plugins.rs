@@ -0,0 +1,71 @@
+//! WASM plugin settings.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GcopError, Result};
+
+/// WASM plugin configuration (`[plugins]`).
+///
+/// Each module may export `transform_prompt` and/or `transform_response`;
+/// modules run in the listed order before a request is sent and after its
+/// response arrives. Modules get no imports (no WASI), so they cannot touch
+/// the network or the file system. Requires the `wasm` build feature.
+///
+/// # Fields
+/// - `wasm`: paths of `.wasm` (or `.wat`) modules (default: empty)
+/// - `fuel`: instruction budget per hook call (default: `1000000000`)
+/// - `max_memory_mb`: linear memory limit per hook call (default: `64`)
+///
+/// # Example
+/// ```toml
+/// [plugins]
+/// wasm = ["~/.config/gcop/plugins/redact.wasm"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct PluginsConfig {
+    /// WASM modules, applied in order. Only honored from user config.
+    #[serde(default)]
+    pub wasm: Vec<String>,
+
+    /// Fuel (roughly, WASM instructions) a hook call may use before it is stopped.
+    #[serde(default = "default_fuel")]
+    pub fuel: u64,
+
+    /// Linear memory limit per hook call, in MiB.
+    #[serde(default = "default_max_memory_mb")]
+    pub max_memory_mb: u64,
+}
+
+impl Default for PluginsConfig {
+    fn default() -> Self {
+        Self {
+            wasm: Vec::new(),
+            fuel: default_fuel(),
+            max_memory_mb: default_max_memory_mb(),
+        }
+    }
+}
+
+impl PluginsConfig {
+    /// Validates plugin limits (non-zero fuel and memory).
+    pub fn validate(&self) -> Result<()> {
+        if self.fuel == 0 {
+            return Err(GcopError::Config("plugins.fuel cannot be 0".into()));
+        }
+        if self.max_memory_mb == 0 {
+            return Err(GcopError::Config(
+                "plugins.max_memory_mb cannot be 0".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_fuel() -> u64 {
+    1_000_000_000
+}
+
+fn default_max_memory_mb() -> u64 {
+    64
+}
@@ -0,0 +1,105 @@
+//! Privacy settings for content sent to providers.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{GcopError, Result};
+
+/// What to do when a diff about to be sent contains a possible secret.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretAction {
+    /// Replace each secret with `[REDACTED:<kind>]` and continue.
+    #[default]
+    Redact,
+    /// Stop before calling the provider.
+    Block,
+    /// Print a warning and send the diff unchanged.
+    Warn,
+}
+
+/// Built-in redaction rule.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RedactionRule {
+    /// Email addresses.
+    Email,
+    /// IPv4 and IPv6 addresses (loopback and unspecified addresses are kept).
+    Ip,
+    /// Hosts under internal TLDs (`.internal`, `.corp`, `.lan`, `.intranet`,
+    /// `.local`) or under [`PrivacyConfig::internal_domains`].
+    Hostname,
+}
+
+impl RedactionRule {
+    /// Label used in `[REDACTED:<label>]` markers.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Ip => "ip",
+            Self::Hostname => "hostname",
+        }
+    }
+}
+
+/// Privacy configuration (`[privacy]`).
+///
+/// Applied to diffs and file contents sent by `commit`, `review`, the commit
+/// hook and the `api` server. Diff header lines (`diff --git`, `---`, `+++`,
+/// `rename from/to`) are never rewritten, so file paths stay intact.
+///
+/// # Fields
+/// - `on_secret`: action when a possible secret is found (default: `redact`)
+/// - `rules`: built-in redaction rules to apply (default: none)
+/// - `internal_domains`: extra domains redacted by the `hostname` rule
+/// - `patterns`: custom regexes whose matches are redacted
+/// - `audit_log`: append a hash-only record of every provider request to
+///   `audit.jsonl` in the data directory (default: `false`)
+///
+/// # Example
+/// ```toml
+/// [privacy]
+/// on_secret = "block"
+/// rules = ["email", "ip", "hostname"]
+/// internal_domains = ["corp.example.com"]
+/// patterns = ['/home/[a-z]+', 'ACME-\d{6}']
+/// audit_log = true
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct PrivacyConfig {
+    /// Action for detected secrets: `redact`, `block` or `warn`.
+    #[serde(default)]
+    pub on_secret: SecretAction,
+
+    /// Built-in redaction rules: `email`, `ip`, `hostname`.
+    #[serde(default)]
+    pub rules: Vec<RedactionRule>,
+
+    /// Domains whose hosts are redacted by the `hostname` rule
+    /// (e.g. `"corp.example.com"` matches `build.corp.example.com`).
+    #[serde(default)]
+    pub internal_domains: Vec<String>,
+
+    /// Custom regexes (Rust `regex` syntax); matches become `[REDACTED:pattern]`.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// Record every provider request in the audit log (`gcop-rs audit`).
+    #[serde(default)]
+    pub audit_log: bool,
+}
+
+impl PrivacyConfig {
+    /// Validates privacy configuration (custom patterns must compile).
+    pub fn validate(&self) -> Result<()> {
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(GcopError::Config(format!(
+                    "privacy.patterns[{}]: invalid regex '{}': {}",
+                    index, pattern, e
+                )));
+            }
+        }
+        Ok(())
+    }
+}
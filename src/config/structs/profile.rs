@@ -0,0 +1,49 @@
+//! Named configuration profile structures.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::commit::CommitConvention;
+
+/// Named configuration profile.
+///
+/// Selected with `--profile <name>` or `GCOP_PROFILE`; every field that is set
+/// overrides the corresponding base configuration value.
+///
+/// # Fields
+/// - `provider`: overrides `llm.default_provider`
+/// - `model`: overrides the model of the selected provider
+/// - `convention`: overrides `commit.convention`
+/// - `language`: overrides `ui.language`
+///
+/// # Example
+/// ```toml
+/// [profiles.work]
+/// provider = "openai"
+/// model = "gpt-4o"
+/// language = "en"
+///
+/// [profiles.work.convention]
+/// style = "conventional"
+///
+/// [profiles.personal]
+/// provider = "ollama"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct ProfileConfig {
+    /// Provider name used instead of `llm.default_provider`.
+    #[serde(default)]
+    pub provider: Option<String>,
+
+    /// Model used for the selected provider.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Commit convention used instead of `commit.convention`.
+    #[serde(default)]
+    pub convention: Option<CommitConvention>,
+
+    /// UI language used instead of `ui.language`.
+    #[serde(default)]
+    pub language: Option<String>,
+}
@@ -0,0 +1,38 @@
+//! Update check settings.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Update check configuration (`[update]`).
+///
+/// Opt-in: at most once a week, an interactive run asks GitHub for the latest
+/// release and prints a hint when it is newer than the running binary. The
+/// check is skipped in hooks, CI and pipes, and never fails a command.
+/// `gcop-rs self update` installs the release.
+///
+/// # Fields
+/// - `check`: enable the weekly update hint (default: `false`)
+///
+/// # Example
+/// ```toml
+/// [update]
+/// check = true
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct UpdateConfig {
+    /// Print a hint when a newer release is available (checked weekly).
+    #[serde(default = "default_check")]
+    pub check: bool,
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            check: default_check(),
+        }
+    }
+}
+
+fn default_check() -> bool {
+    false
+}
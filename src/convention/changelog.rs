@@ -0,0 +1,102 @@
+//! Grouping of commit subjects into changelog sections.
+
+use serde::Serialize;
+
+use super::ConventionalMessage;
+
+/// Changelog sections in output order, with the types they collect.
+const SECTIONS: &[(&str, &[&str])] = &[
+    ("Features", &["feat"]),
+    ("Fixes", &["fix"]),
+    ("Performance", &["perf"]),
+    ("Reverts", &["revert"]),
+    ("Documentation", &["docs"]),
+];
+
+/// Title of the section holding breaking changes.
+const BREAKING_TITLE: &str = "Breaking Changes";
+
+/// Title of the section holding all other commits.
+const OTHER_TITLE: &str = "Other";
+
+/// Commits of one changelog section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangelogSection {
+    /// Section heading (`Features`, `Fixes`, ...).
+    pub title: &'static str,
+    /// Commit subjects, in input order.
+    pub commits: Vec<String>,
+}
+
+/// Groups commit subjects by type.
+///
+/// Breaking changes come first, then features, fixes and the other known
+/// types; non-conventional subjects and remaining types go to `Other`.
+/// Empty sections are omitted.
+pub fn group_by_type(subjects: &[String]) -> Vec<ChangelogSection> {
+    let titles = std::iter::once(BREAKING_TITLE)
+        .chain(SECTIONS.iter().map(|(title, _)| *title))
+        .chain(std::iter::once(OTHER_TITLE));
+    let mut sections: Vec<ChangelogSection> = titles
+        .map(|title| ChangelogSection {
+            title,
+            commits: Vec::new(),
+        })
+        .collect();
+
+    for subject in subjects {
+        let index = match ConventionalMessage::parse(subject) {
+            Some(parsed) if parsed.is_breaking() => 0,
+            Some(parsed) => SECTIONS
+                .iter()
+                .position(|(_, types)| types.contains(&parsed.commit_type.to_lowercase().as_str()))
+                .map_or(sections.len() - 1, |i| i + 1),
+            None => sections.len() - 1,
+        };
+        sections[index].commits.push(subject.clone());
+    }
+
+    sections.retain(|section| !section.commits.is_empty());
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_by_type() {
+        let subjects: Vec<String> = [
+            "fix(cli): handle EOF",
+            "feat: add tags",
+            "Merge pull request #3",
+            "feat(api)!: drop connect",
+            "chore: bump deps",
+            "Fix(parser): typo",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let sections = group_by_type(&subjects);
+        let titles: Vec<&str> = sections.iter().map(|s| s.title).collect();
+        assert_eq!(
+            titles,
+            vec!["Breaking Changes", "Features", "Fixes", "Other"]
+        );
+        assert_eq!(sections[0].commits, vec!["feat(api)!: drop connect"]);
+        assert_eq!(
+            sections[2].commits,
+            vec!["fix(cli): handle EOF", "Fix(parser): typo"]
+        );
+        assert_eq!(
+            sections[3].commits,
+            vec!["Merge pull request #3", "chore: bump deps"]
+        );
+    }
+
+    #[test]
+    fn test_group_by_type_empty() {
+        assert!(group_by_type(&[]).is_empty());
+    }
+}
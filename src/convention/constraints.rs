@@ -0,0 +1,279 @@
+//! Hard limits on generated messages (`[commit.constraints]`) and the body
+//! requirements of `commit.body` / `commit.body_bullets`.
+//!
+//! Unlike [`lint`](super::lint), these apply to every convention style: the
+//! subject is the header's description after any `type(scope): ` prefix or
+//! leading gitmoji, and the body is everything between the subject line and
+//! a trailing footer block.
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{HEADER, is_footer_block};
+use crate::config::{BodyMode, CommitConstraints};
+
+/// Leading `:shortcode:` or emoji of a gitmoji header
+static GITMOJI_PREFIX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?::[a-z0-9_+-]+:|[^\p{L}\p{N}\s])+\s*").expect("valid gitmoji regex")
+});
+
+/// Words ending in `-ed`, `-ing` or `-s` that are imperative anyway
+const IMPERATIVE_EXCEPTIONS: &[&str] = &[
+    "bring", "embed", "feed", "need", "proceed", "seed", "shed", "shred", "speed", "string",
+];
+
+/// A limit a message breaks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintIssue {
+    /// The subject line is longer than `max_subject_length`.
+    SubjectTooLong {
+        /// Subject line length in characters.
+        length: usize,
+        /// Configured maximum.
+        max: usize,
+    },
+    /// The subject does not start with an imperative verb.
+    NotImperative {
+        /// First word of the subject.
+        word: String,
+    },
+    /// The subject ends with a period.
+    TrailingPeriod,
+    /// `commit.body = "always"` but the message has no body.
+    MissingBody,
+    /// `commit.body = "never"` but the message has a body.
+    UnexpectedBody,
+    /// `commit.body_bullets` is on but a body line is not a `- ` item.
+    BodyNotBullets,
+}
+
+impl ConstraintIssue {
+    /// Instruction telling the model how to fix the issue (always English,
+    /// like the rest of the prompt).
+    pub fn instruction(&self) -> String {
+        match self {
+            Self::SubjectTooLong { length, max } => format!(
+                "The subject line is {} characters; shorten it to at most {}.",
+                length, max
+            ),
+            Self::NotImperative { word } => format!(
+                "Start the subject with an imperative verb (\"add\", not \"added\" or \"adds\"), not \"{}\".",
+                word
+            ),
+            Self::TrailingPeriod => "Remove the period at the end of the subject.".to_string(),
+            Self::MissingBody => {
+                "Add a body after a blank line explaining what changed and why.".to_string()
+            }
+            Self::UnexpectedBody => {
+                "Remove the body; keep only the subject line and any footers.".to_string()
+            }
+            Self::BodyNotBullets => {
+                "Rewrite the body as a bulleted list with one \"- \" line per change.".to_string()
+            }
+        }
+    }
+}
+
+impl fmt::Display for ConstraintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::SubjectTooLong { length, max } => {
+                rust_i18n::t!("convention.subject_too_long", length = length, max = max)
+            }
+            Self::NotImperative { word } => {
+                rust_i18n::t!("convention.not_imperative", word = word)
+            }
+            Self::TrailingPeriod => rust_i18n::t!("convention.subject_period"),
+            Self::MissingBody => rust_i18n::t!("convention.body_missing"),
+            Self::UnexpectedBody => rust_i18n::t!("convention.body_unexpected"),
+            Self::BodyNotBullets => rust_i18n::t!("convention.body_not_bullets"),
+        };
+        f.write_str(&text)
+    }
+}
+
+/// Checks `message` against `constraints`.
+pub fn check(message: &str, constraints: &CommitConstraints) -> Vec<ConstraintIssue> {
+    let header = message.lines().next().unwrap_or_default().trim_end();
+    let subject = subject_of(header);
+
+    let mut issues = Vec::new();
+    let length = header.chars().count();
+    if constraints.max_subject_length > 0 && length > constraints.max_subject_length {
+        issues.push(ConstraintIssue::SubjectTooLong {
+            length,
+            max: constraints.max_subject_length,
+        });
+    }
+    if constraints.imperative
+        && let Some(word) = subject.split_whitespace().next()
+        && !is_imperative(word)
+    {
+        issues.push(ConstraintIssue::NotImperative {
+            word: word.to_string(),
+        });
+    }
+    if constraints.no_trailing_period && subject.ends_with('.') {
+        issues.push(ConstraintIssue::TrailingPeriod);
+    }
+    issues
+}
+
+/// Checks the body of `message` against `commit.body` / `commit.body_bullets`.
+pub fn check_body(message: &str, mode: BodyMode, bullets: bool) -> Vec<ConstraintIssue> {
+    let body = body_of(message);
+    let mut issues = Vec::new();
+    match mode {
+        BodyMode::Always if body.is_empty() => issues.push(ConstraintIssue::MissingBody),
+        BodyMode::Never if !body.is_empty() => issues.push(ConstraintIssue::UnexpectedBody),
+        _ => {}
+    }
+    // Continuation lines of an item are indented
+    let is_item = |line: &&str| {
+        line.trim().is_empty()
+            || line.starts_with("- ")
+            || line.starts_with("* ")
+            || line.starts_with([' ', '\t'])
+    };
+    if bullets
+        && mode != BodyMode::Never
+        && !body
+            .iter()
+            .all(|paragraph| paragraph.lines().all(|line| is_item(&line)))
+    {
+        issues.push(ConstraintIssue::BodyNotBullets);
+    }
+    issues
+}
+
+/// Body paragraphs: between the subject line and a trailing footer block.
+fn body_of(message: &str) -> Vec<&str> {
+    let rest = message.split_once('\n').map_or("", |(_, rest)| rest);
+    let mut paragraphs: Vec<&str> = rest
+        .split("\n\n")
+        .map(|paragraph| paragraph.trim_matches('\n'))
+        .filter(|paragraph| !paragraph.trim().is_empty())
+        .collect();
+    if paragraphs.last().is_some_and(|last| is_footer_block(last)) {
+        paragraphs.pop();
+    }
+    paragraphs
+}
+
+/// Description part of a header: after `type(scope): ` or a leading gitmoji.
+fn subject_of(header: &str) -> &str {
+    if let Some(subject) = HEADER.captures(header).and_then(|caps| caps.get(4)) {
+        return subject.as_str().trim();
+    }
+    match GITMOJI_PREFIX.find(header) {
+        Some(prefix) => header[prefix.end()..].trim(),
+        None => header.trim(),
+    }
+}
+
+/// Heuristic: past tense (`added`), gerunds (`adding`) and third person
+/// (`adds`) are not imperative.
+fn is_imperative(word: &str) -> bool {
+    let word = word
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase();
+    if !word.chars().all(|c| c.is_ascii_alphabetic())
+        || IMPERATIVE_EXCEPTIONS.contains(&word.as_str())
+    {
+        return true;
+    }
+    let past = word.len() > 4 && word.ends_with("ed") && !word.ends_with("eed");
+    let gerund = word.len() > 5 && word.ends_with("ing");
+    let third_person = word.len() > 3
+        && word.ends_with('s')
+        && !["ss", "us", "is", "as", "os"]
+            .iter()
+            .any(|suffix| word.ends_with(suffix));
+    !(past || gerund || third_person)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_messages_pass() {
+        let constraints = CommitConstraints::default();
+        for message in [
+            "feat(cli): add --json flag\n\nBody.",
+            ":sparkles: add dark mode",
+            "✨ embed fonts",
+            "Process queued jobs",
+            "fix: bump 2 deps",
+        ] {
+            assert!(check(message, &constraints).is_empty(), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_each_issue_is_reported() {
+        let constraints = CommitConstraints::default();
+        assert_eq!(
+            check("feat: added login.", &constraints),
+            vec![
+                ConstraintIssue::NotImperative {
+                    word: "added".to_string()
+                },
+                ConstraintIssue::TrailingPeriod,
+            ]
+        );
+        assert_eq!(
+            check(":bug: fixes crash", &constraints),
+            vec![ConstraintIssue::NotImperative {
+                word: "fixes".to_string()
+            }]
+        );
+        assert_eq!(
+            check("Updating docs", &constraints),
+            vec![ConstraintIssue::NotImperative {
+                word: "Updating".to_string()
+            }]
+        );
+
+        let long = format!("feat: add {}", "x".repeat(70));
+        assert_eq!(
+            check(&long, &constraints),
+            vec![ConstraintIssue::SubjectTooLong {
+                length: 80,
+                max: 72
+            }]
+        );
+        let relaxed = CommitConstraints {
+            max_subject_length: 0,
+            imperative: false,
+            no_trailing_period: false,
+            ..Default::default()
+        };
+        assert!(check(&format!("{}.", long.replace("add", "added")), &relaxed).is_empty());
+    }
+
+    #[test]
+    fn test_body_rules() {
+        let with_body = "feat: add login\n\nUsers can sign in.\n\nRefs: ABC-1";
+        let bullets = "feat: add login\n\n- add form\n- add session\n  handling\n\nRefs: ABC-1";
+        let footers_only = "feat!: drop v1\n\nBREAKING CHANGE: v1 is gone";
+
+        assert!(check_body(with_body, BodyMode::Auto, false).is_empty());
+        assert_eq!(
+            check_body(footers_only, BodyMode::Always, false),
+            vec![ConstraintIssue::MissingBody]
+        );
+        assert!(check_body(footers_only, BodyMode::Never, true).is_empty());
+        assert_eq!(
+            check_body(with_body, BodyMode::Never, false),
+            vec![ConstraintIssue::UnexpectedBody]
+        );
+        assert_eq!(
+            check_body(with_body, BodyMode::Always, true),
+            vec![ConstraintIssue::BodyNotBullets]
+        );
+        assert!(check_body(bullets, BodyMode::Always, true).is_empty());
+    }
+}
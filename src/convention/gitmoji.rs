@@ -0,0 +1,179 @@
+//! Gitmoji headers (`:sparkles: add login`) and their mapping to
+//! Conventional Commits types.
+//!
+//! Conversion is table-driven: `type(scope)!: subject` becomes
+//! `<emoji> scope: subject`, using the `breaking` emoji for breaking changes
+//! when the table has one. The reverse direction looks the emoji up in the
+//! same table.
+
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{ConventionalMessage, replace_header};
+use crate::config::CommitConvention;
+
+/// Key of the emoji used for breaking changes
+pub const BREAKING_KEY: &str = "breaking";
+
+/// Built-in type → emoji table, used when `[commit.convention.gitmoji]` is unset
+pub const DEFAULT_GITMOJI: &[(&str, &str)] = &[
+    ("feat", ":sparkles:"),
+    ("fix", ":bug:"),
+    ("docs", ":memo:"),
+    ("style", ":art:"),
+    ("refactor", ":recycle:"),
+    ("perf", ":zap:"),
+    ("test", ":white_check_mark:"),
+    ("build", ":package:"),
+    ("ci", ":construction_worker:"),
+    ("chore", ":wrench:"),
+    ("revert", ":rewind:"),
+    (BREAKING_KEY, ":boom:"),
+];
+
+/// `<emoji> [scope: ]subject`
+static GITMOJI_HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\S+) (?:([A-Za-z0-9_./-]+): )?(.+)$").expect("valid gitmoji header regex")
+});
+
+/// Type → emoji table of `convention`: the configured one, or the built-in
+/// table.
+pub fn table(convention: Option<&CommitConvention>) -> BTreeMap<String, String> {
+    match convention.and_then(|c| c.gitmoji.as_ref()) {
+        Some(table) => table.clone(),
+        None => DEFAULT_GITMOJI
+            .iter()
+            .map(|(commit_type, emoji)| (commit_type.to_string(), emoji.to_string()))
+            .collect(),
+    }
+}
+
+/// Emoji for `commit_type`, falling back to `chore`'s.
+pub fn emoji_for<'a>(table: &'a BTreeMap<String, String>, commit_type: &str) -> Option<&'a str> {
+    table
+        .get(&commit_type.to_ascii_lowercase())
+        .or_else(|| table.get("chore"))
+        .map(String::as_str)
+}
+
+/// Converts a conventional header to gitmoji, keeping the rest of the
+/// message; a gitmoji header is normalized to the table's emoji.
+///
+/// Messages with neither header, or whose type has no emoji, are returned
+/// unchanged.
+pub fn to_gitmoji(message: String, table: &BTreeMap<String, String>) -> String {
+    let conventional = match from_gitmoji(&message, table) {
+        Some(converted) => converted,
+        None => message.clone(),
+    };
+    let Some(parsed) = ConventionalMessage::parse(&conventional) else {
+        return message;
+    };
+    let emoji = if parsed.breaking {
+        table.get(BREAKING_KEY).map(String::as_str)
+    } else {
+        None
+    }
+    .or_else(|| {
+        table
+            .get(&parsed.commit_type.to_ascii_lowercase())
+            .map(String::as_str)
+    });
+    let Some(emoji) = emoji else {
+        return message;
+    };
+
+    let header = match &parsed.scope {
+        Some(scope) if !scope.is_empty() => format!("{} {}: {}", emoji, scope, parsed.subject),
+        _ => format!("{} {}", emoji, parsed.subject),
+    };
+    match conventional.split_once('\n') {
+        Some((_, rest)) => format!("{}\n{}", header, rest),
+        None => header,
+    }
+}
+
+/// Converts a gitmoji header to a conventional one, keeping the rest of the
+/// message; `None` when the header does not start with an emoji of the
+/// table (or only the `breaking` one).
+pub fn from_gitmoji(message: &str, table: &BTreeMap<String, String>) -> Option<String> {
+    let header = message.lines().next()?.trim_end();
+    let caps = GITMOJI_HEADER.captures(header)?;
+    let commit_type = table
+        .iter()
+        .find(|(key, emoji)| key.as_str() != BREAKING_KEY && **emoji == caps[1])
+        .map(|(key, _)| key.clone())?;
+    let parsed = ConventionalMessage {
+        commit_type,
+        scope: caps.get(2).map(|scope| scope.as_str().to_string()),
+        breaking: false,
+        subject: caps[3].trim().to_string(),
+        body: None,
+        footers: Vec::new(),
+    };
+    Some(replace_header(message, &parsed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_body() {
+        let table = table(None);
+        let message = "feat(cli): add --json flag\n\nMachine-readable output.".to_string();
+        let gitmoji = to_gitmoji(message.clone(), &table);
+        assert_eq!(
+            gitmoji,
+            ":sparkles: cli: add --json flag\n\nMachine-readable output."
+        );
+        assert_eq!(
+            from_gitmoji(&gitmoji, &table).as_deref(),
+            Some(message.as_str())
+        );
+
+        assert_eq!(
+            to_gitmoji("fix!: drop v1 API".to_string(), &table),
+            ":boom: drop v1 API"
+        );
+        assert_eq!(
+            to_gitmoji("Update readme".to_string(), &table),
+            "Update readme"
+        );
+    }
+
+    #[test]
+    fn test_custom_table_enforces_exact_set() {
+        let convention = CommitConvention {
+            gitmoji: Some(BTreeMap::from([
+                ("feat".to_string(), "✨".to_string()),
+                ("fix".to_string(), "🐛".to_string()),
+            ])),
+            ..Default::default()
+        };
+        let table = table(Some(&convention));
+
+        assert_eq!(
+            to_gitmoji("feat: add login".to_string(), &table),
+            "✨ add login"
+        );
+        // A known emoji is normalized; breaking falls back to the type's emoji
+        assert_eq!(
+            to_gitmoji("🐛 fix crash".to_string(), &table),
+            "🐛 fix crash"
+        );
+        assert_eq!(
+            to_gitmoji("fix!: drop flag".to_string(), &table),
+            "🐛 drop flag"
+        );
+        // Types outside the table are left alone
+        assert_eq!(
+            to_gitmoji("docs: update guide".to_string(), &table),
+            "docs: update guide"
+        );
+        assert_eq!(from_gitmoji(":memo: update guide", &table), None);
+        assert_eq!(emoji_for(&table, "FEAT"), Some("✨"));
+    }
+}
@@ -0,0 +1,129 @@
+//! Conventional Commits checks for generated messages.
+
+use std::fmt;
+
+use super::ConventionalMessage;
+
+/// Maximum header length, as asked of the LLM.
+pub const MAX_HEADER_LEN: usize = 72;
+
+/// A rule a message breaks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintIssue {
+    /// The header is not `type(scope): subject`.
+    NotConventional,
+    /// The type is not in `[commit.convention] types`.
+    TypeNotAllowed {
+        /// Type used by the message.
+        commit_type: String,
+        /// Configured types.
+        allowed: Vec<String>,
+    },
+    /// The header is longer than [`MAX_HEADER_LEN`] characters.
+    HeaderTooLong {
+        /// Header length in characters.
+        length: usize,
+    },
+    /// The subject ends with a period.
+    SubjectEndsWithPeriod,
+    /// The header is not followed by a blank line.
+    MissingBlankLine,
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Self::NotConventional => rust_i18n::t!("convention.not_conventional"),
+            Self::TypeNotAllowed {
+                commit_type,
+                allowed,
+            } => rust_i18n::t!(
+                "convention.type_not_allowed",
+                commit_type = commit_type,
+                allowed = allowed.join(", ")
+            ),
+            Self::HeaderTooLong { length } => rust_i18n::t!(
+                "convention.header_too_long",
+                length = length,
+                max = MAX_HEADER_LEN
+            ),
+            Self::SubjectEndsWithPeriod => rust_i18n::t!("convention.subject_period"),
+            Self::MissingBlankLine => rust_i18n::t!("convention.missing_blank_line"),
+        };
+        f.write_str(&text)
+    }
+}
+
+/// Checks `message` against the Conventional Commits format.
+///
+/// `allowed_types` restricts the type when set (matched case-insensitively).
+pub fn lint(message: &str, allowed_types: Option<&[String]>) -> Vec<LintIssue> {
+    let Some(parsed) = ConventionalMessage::parse(message) else {
+        return vec![LintIssue::NotConventional];
+    };
+
+    let mut issues = Vec::new();
+    if let Some(allowed) = allowed_types
+        && !allowed.is_empty()
+        && !allowed
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(&parsed.commit_type))
+    {
+        issues.push(LintIssue::TypeNotAllowed {
+            commit_type: parsed.commit_type.clone(),
+            allowed: allowed.to_vec(),
+        });
+    }
+
+    let header = message.lines().next().unwrap_or_default().trim_end();
+    let length = header.chars().count();
+    if length > MAX_HEADER_LEN {
+        issues.push(LintIssue::HeaderTooLong { length });
+    }
+    if parsed.subject.ends_with('.') {
+        issues.push(LintIssue::SubjectEndsWithPeriod);
+    }
+    if message
+        .lines()
+        .nth(1)
+        .is_some_and(|line| !line.trim().is_empty())
+    {
+        issues.push(LintIssue::MissingBlankLine);
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_clean_message() {
+        assert!(lint("feat(cli): add flag\n\nBody.", None).is_empty());
+    }
+
+    #[test]
+    fn test_lint_reports_each_issue() {
+        assert_eq!(lint("Add flag", None), vec![LintIssue::NotConventional]);
+
+        let types = vec!["feat".to_string(), "fix".to_string()];
+        assert_eq!(
+            lint("chore: bump deps.\nmore", Some(&types)),
+            vec![
+                LintIssue::TypeNotAllowed {
+                    commit_type: "chore".to_string(),
+                    allowed: types.clone(),
+                },
+                LintIssue::SubjectEndsWithPeriod,
+                LintIssue::MissingBlankLine,
+            ]
+        );
+        assert!(lint("FIX: x", Some(&types)).is_empty());
+
+        let long = format!("feat: {}", "x".repeat(80));
+        assert_eq!(
+            lint(&long, None),
+            vec![LintIssue::HeaderTooLong { length: 86 }]
+        );
+    }
+}
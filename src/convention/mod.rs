@@ -0,0 +1,277 @@
+//! Conventional Commits messages.
+//!
+//! [`ConventionalMessage`] splits a message into type, scope, breaking flag,
+//! subject, body and footers (`type(scope)!: subject`, see
+//! <https://www.conventionalcommits.org>) and serializes it back. Commands use
+//! it instead of ad-hoc string matching: [`lint`] checks generated messages,
+//! [`constraints`] enforces `[commit.constraints]` on every style,
+//! [`changelog`] groups commits by type, [`gitmoji`] converts headers to and
+//! from gitmoji, and post-processing rewrites headers without touching the
+//! body.
+
+pub mod changelog;
+pub mod constraints;
+pub mod gitmoji;
+pub mod lint;
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Footer tokens marking a breaking change.
+pub const BREAKING_TOKENS: [&str; 2] = ["BREAKING CHANGE", "BREAKING-CHANGE"];
+
+/// `type(scope)!: subject`
+static HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([A-Za-z]+)(?:\(([^()\r\n]*)\))?(!?): (.*)$").expect("valid header regex")
+});
+
+/// `Token: value` or `Token #value` (git trailer or Conventional Commits footer)
+static FOOTER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(BREAKING CHANGE|BREAKING-CHANGE|[A-Za-z0-9][A-Za-z0-9-]*)(: | #)(.*)$")
+        .expect("valid footer regex")
+});
+
+/// A footer line such as `Refs: #123` or `BREAKING CHANGE: ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Footer {
+    /// Footer token (`Refs`, `Signed-off-by`, `BREAKING CHANGE`).
+    pub token: String,
+    /// Separator between token and value: `": "` or `" #"`.
+    pub separator: String,
+    /// Footer value; continuation lines are kept with their indentation.
+    pub value: String,
+}
+
+impl Footer {
+    /// Whether this footer describes a breaking change.
+    pub fn is_breaking(&self) -> bool {
+        BREAKING_TOKENS.contains(&self.token.as_str())
+    }
+}
+
+impl fmt::Display for Footer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.token, self.separator, self.value)
+    }
+}
+
+/// A parsed Conventional Commits message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalMessage {
+    /// Commit type (`feat`, `fix`, ...).
+    pub commit_type: String,
+    /// Optional scope inside the parentheses.
+    pub scope: Option<String>,
+    /// Whether the header carries `!` before the colon.
+    pub breaking: bool,
+    /// Header description after `: `.
+    pub subject: String,
+    /// Body paragraphs between header and footers.
+    pub body: Option<String>,
+    /// Trailing footer block.
+    pub footers: Vec<Footer>,
+}
+
+impl ConventionalMessage {
+    /// Parses `message`; returns None when the header is not conventional
+    /// or the subject is empty.
+    pub fn parse(message: &str) -> Option<Self> {
+        let (header, rest) = message.split_once('\n').unwrap_or((message, ""));
+        let caps = HEADER.captures(header.trim_end())?;
+        let subject = caps[4].trim();
+        if subject.is_empty() {
+            return None;
+        }
+
+        let rest = rest.trim_start_matches(['\r', '\n']).trim_end();
+        let (body, footer_block) = match rest.rsplit_once("\n\n") {
+            Some((body, last)) if is_footer_block(last) => (body, last),
+            None if is_footer_block(rest) => ("", rest),
+            _ => (rest, ""),
+        };
+        let body = body.trim();
+
+        Some(Self {
+            commit_type: caps[1].to_string(),
+            scope: caps
+                .get(2)
+                .map(|scope| scope.as_str().trim())
+                .filter(|scope| !scope.is_empty())
+                .map(str::to_string),
+            breaking: !caps[3].is_empty(),
+            subject: subject.to_string(),
+            body: (!body.is_empty()).then(|| body.to_string()),
+            footers: parse_footers(footer_block),
+        })
+    }
+
+    /// The header line: `type(scope)!: subject`.
+    pub fn header(&self) -> String {
+        let scope = self
+            .scope
+            .as_ref()
+            .map(|scope| format!("({scope})"))
+            .unwrap_or_default();
+        let marker = if self.breaking { "!" } else { "" };
+        format!("{}{}{}: {}", self.commit_type, scope, marker, self.subject)
+    }
+
+    /// Whether the message is breaking (`!` in the header or a `BREAKING CHANGE` footer).
+    pub fn is_breaking(&self) -> bool {
+        self.breaking || self.footers.iter().any(Footer::is_breaking)
+    }
+
+    /// Normalizes the header: lowercase type, no trailing period on the subject.
+    pub fn tidy(&mut self) {
+        self.commit_type.make_ascii_lowercase();
+        let subject = self.subject.trim_end_matches('.').trim_end();
+        if !subject.is_empty() {
+            self.subject = subject.to_string();
+        }
+    }
+}
+
+impl fmt::Display for ConventionalMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.header())?;
+        if let Some(body) = &self.body {
+            write!(f, "\n\n{body}")?;
+        }
+        for (i, footer) in self.footers.iter().enumerate() {
+            let separator = if i == 0 { "\n\n" } else { "\n" };
+            write!(f, "{separator}{footer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the first line of `message` with the header of `parsed`, keeping
+/// the rest of the message byte for byte.
+pub fn replace_header(message: &str, parsed: &ConventionalMessage) -> String {
+    match message.split_once('\n') {
+        Some((_, rest)) => format!("{}\n{}", parsed.header(), rest),
+        None => parsed.header(),
+    }
+}
+
+/// Post-processes a generated message: tidies a conventional header
+/// (see [`ConventionalMessage::tidy`]); other messages are returned unchanged.
+pub fn tidy_header(message: String) -> String {
+    match ConventionalMessage::parse(&message) {
+        Some(mut parsed) => {
+            parsed.tidy();
+            replace_header(&message, &parsed)
+        }
+        None => message,
+    }
+}
+
+/// Whether `line` starts a footer.
+pub fn is_footer_line(line: &str) -> bool {
+    FOOTER.is_match(line)
+}
+
+/// Whether a paragraph consists of footers (with indented continuation lines).
+fn is_footer_block(paragraph: &str) -> bool {
+    let mut lines = paragraph.lines();
+    lines.next().is_some_and(is_footer_line)
+        && lines.all(|line| is_footer_line(line) || line.starts_with([' ', '\t']))
+}
+
+fn parse_footers(block: &str) -> Vec<Footer> {
+    let mut footers: Vec<Footer> = Vec::new();
+    for line in block.lines() {
+        match FOOTER.captures(line) {
+            Some(caps) => footers.push(Footer {
+                token: caps[1].to_string(),
+                separator: caps[2].to_string(),
+                value: caps[3].to_string(),
+            }),
+            None => {
+                if let Some(last) = footers.last_mut() {
+                    last.value.push('\n');
+                    last.value.push_str(line);
+                }
+            }
+        }
+    }
+    footers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_message() {
+        let message = "feat(api)!: drop connect\n\nUse open() instead.\n\nSecond paragraph.\n\nBREAKING CHANGE: connect is gone\nRefs #42\nSigned-off-by: A <a@example.com>";
+        let parsed = ConventionalMessage::parse(message).unwrap();
+
+        assert_eq!(parsed.commit_type, "feat");
+        assert_eq!(parsed.scope.as_deref(), Some("api"));
+        assert!(parsed.breaking);
+        assert_eq!(parsed.subject, "drop connect");
+        assert_eq!(
+            parsed.body.as_deref(),
+            Some("Use open() instead.\n\nSecond paragraph.")
+        );
+        assert_eq!(parsed.footers.len(), 3);
+        assert!(parsed.footers[0].is_breaking());
+        assert_eq!(parsed.footers[1].separator, " #");
+        assert_eq!(parsed.footers[1].value, "42");
+        // 序列化后与原文一致
+        assert_eq!(parsed.to_string(), message);
+    }
+
+    #[test]
+    fn test_parse_header_only_and_footer_only() {
+        let parsed = ConventionalMessage::parse("fix: handle EOF").unwrap();
+        assert_eq!(parsed.scope, None);
+        assert!(!parsed.is_breaking());
+        assert_eq!(parsed.body, None);
+        assert!(parsed.footers.is_empty());
+
+        let parsed = ConventionalMessage::parse("fix: x\n\nBREAKING-CHANGE: y").unwrap();
+        assert_eq!(parsed.body, None);
+        assert!(parsed.is_breaking());
+        assert!(!parsed.breaking);
+    }
+
+    #[test]
+    fn test_parse_footer_continuation() {
+        let parsed =
+            ConventionalMessage::parse("fix: x\n\nBREAKING CHANGE: first line\n  second line")
+                .unwrap();
+        assert_eq!(parsed.footers.len(), 1);
+        assert_eq!(parsed.footers[0].value, "first line\n  second line");
+    }
+
+    #[test]
+    fn test_body_paragraph_is_not_a_footer_block() {
+        let parsed = ConventionalMessage::parse("fix: x\n\nThis explains why.\nRefs: #1").unwrap();
+        assert_eq!(parsed.body.as_deref(), Some("This explains why.\nRefs: #1"));
+        assert!(parsed.footers.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_non_conventional() {
+        assert!(ConventionalMessage::parse("Update README").is_none());
+        assert!(ConventionalMessage::parse("✨ add feature").is_none());
+        assert!(ConventionalMessage::parse("feat: ").is_none());
+        assert!(ConventionalMessage::parse("fixup! feat: x").is_none());
+        // 空 scope 视为无 scope
+        let parsed = ConventionalMessage::parse("feat( ): x").unwrap();
+        assert_eq!(parsed.scope, None);
+    }
+
+    #[test]
+    fn test_tidy_header_keeps_body() {
+        assert_eq!(
+            tidy_header("Feat(cli): add flag.\n\nBody text.\n".to_string()),
+            "feat(cli): add flag\n\nBody text.\n"
+        );
+        assert_eq!(tidy_header("Update docs.".to_string()), "Update docs.");
+    }
+}
@@ -1,8 +1,35 @@
 use thiserror::Error;
 
+use crate::llm::IssueSeverity;
+
 /// Result type alias, use [`GcopError`] as error type
 pub type Result<T> = std::result::Result<T, GcopError>;
 
+/// Process exit codes (see [`GcopError::exit_code`]).
+pub mod exit_code {
+    /// Command succeeded.
+    pub const SUCCESS: i32 = 0;
+    /// Any error without a more specific code (git, I/O, ...).
+    pub const GENERIC: i32 = 1;
+    /// The user cancelled at a prompt or menu.
+    pub const CANCELLED: i32 = 2;
+    /// `commit` found nothing staged.
+    pub const NO_STAGED_CHANGES: i32 = 3;
+    /// The LLM provider could not be reached or returned an error.
+    pub const PROVIDER_FAILURE: i32 = 4;
+    /// Invalid arguments, input or configuration, blocked secrets or a failed
+    /// split verification command.
+    pub const VALIDATION_FAILURE: i32 = 5;
+    /// Failed review gate (`review --fail-on`, `commit --review`), highest severity `critical`.
+    pub const GATE_CRITICAL: i32 = 10;
+    /// Failed review gate, highest severity `warning`.
+    pub const GATE_WARNING: i32 = 11;
+    /// Failed review gate, highest severity `info`.
+    pub const GATE_INFO: i32 = 12;
+    /// Interrupted with Ctrl+C, like a shell-killed process.
+    pub const INTERRUPTED: i32 = 130;
+}
+
 /// A wrapper type for git2::Error that provides more friendly error information
 ///
 /// Hide technical details of libgit2 (ErrorClass, ErrorCode, etc.),
@@ -39,7 +66,7 @@ impl From<git2::Error> for GcopError {
 /// - Git operation errors: [`GitCommand`], [`Git`]
 /// - LLM related errors: [`Llm`], [`LlmApi`]
 /// - Configuration errors: [`Config`], [`ConfigParse`]
-/// - User operations: [`UserCancelled`], [`InvalidInput`]
+/// - User operations: [`UserCancelled`], [`Interrupted`], [`InvalidInput`]
 /// - Others: [`Io`], [`Network`], [`Other`]
 ///
 /// # Example
@@ -63,6 +90,7 @@ impl From<git2::Error> for GcopError {
 /// [`Config`]: GcopError::Config
 /// [`ConfigParse`]: GcopError::ConfigParse
 /// [`UserCancelled`]: GcopError::UserCancelled
+/// [`Interrupted`]: GcopError::Interrupted
 /// [`InvalidInput`]: GcopError::InvalidInput
 /// [`Io`]: GcopError::Io
 /// [`Network`]: GcopError::Network
@@ -214,6 +242,12 @@ pub enum GcopError {
     #[error("Operation cancelled by user")]
     UserCancelled,
 
+    /// Interrupted by Ctrl+C
+    ///
+    /// The user pressed Ctrl+C while a request was in flight; the request was aborted.
+    #[error("Interrupted")]
+    Interrupted,
+
     /// Invalid input
     ///
     /// The user-supplied parameter does not conform to the expected format.
@@ -239,12 +273,61 @@ pub enum GcopError {
         detail: String,
     },
 
+    /// A split group failed the verification command.
+    ///
+    /// Earlier groups were committed; the remaining files are staged again.
+    #[error("Verification `{command}` failed at split group {group}/{total}: {detail}")]
+    SplitVerifyFailed {
+        /// 1-based index of the failing group.
+        group: usize,
+        /// Total number of groups.
+        total: usize,
+        /// The verification command.
+        command: String,
+        /// Exit status or launch error.
+        detail: String,
+    },
+
     /// Split response parsing failed.
     ///
     /// The LLM response could not be parsed as valid commit groups.
     #[error("Failed to parse split commit response: {0}")]
     SplitParseFailed(String),
 
+    /// Code forge API error
+    ///
+    /// Detection failed or the forge (GitLab) API returned an error.
+    #[error("Forge error: {0}")]
+    Forge(String),
+
+    /// Possible secrets in content about to be sent to a provider
+    ///
+    /// Raised with `privacy.on_secret = "block"`; the payload lists the findings.
+    #[error("Possible secrets detected: {0}")]
+    SecretsDetected(String),
+
+    /// No interactive terminal
+    ///
+    /// `commit` ran without a terminal with `commit.non_interactive = "fail"`.
+    #[error("No interactive terminal")]
+    NonInteractive,
+
+    /// Review gate failed
+    ///
+    /// `review --fail-on` found issues at or above the threshold.
+    /// The process exit code encodes the highest severity (see [`exit_code()`]).
+    ///
+    /// [`exit_code()`]: GcopError::exit_code
+    #[error("Review found {count} issue(s) at or above {}", threshold.as_str())]
+    ReviewGateFailed {
+        /// Highest severity among the reported issues.
+        highest: IssueSeverity,
+        /// `--fail-on` threshold.
+        threshold: IssueSeverity,
+        /// Number of issues at or above the threshold.
+        count: usize,
+    },
+
     /// Common error types
     ///
     /// Used for errors that do not fit into other categories.
@@ -357,6 +440,7 @@ impl GcopError {
             GcopError::Inquire(e) => rust_i18n::t!("error.ui", detail = e.to_string()).to_string(),
             GcopError::NoStagedChanges => rust_i18n::t!("error.no_staged_changes").to_string(),
             GcopError::UserCancelled => rust_i18n::t!("error.user_cancelled").to_string(),
+            GcopError::Interrupted => rust_i18n::t!("error.interrupted").to_string(),
             GcopError::InvalidInput(msg) => {
                 rust_i18n::t!("error.invalid_input", detail = msg.as_str()).to_string()
             }
@@ -374,13 +458,90 @@ impl GcopError {
                 detail = detail.as_str()
             )
             .to_string(),
+            GcopError::SplitVerifyFailed {
+                group,
+                total,
+                command,
+                detail,
+            } => rust_i18n::t!(
+                "error.split_verify_failed",
+                group = group,
+                total = total,
+                command = command.as_str(),
+                detail = detail.as_str()
+            )
+            .to_string(),
             GcopError::SplitParseFailed(msg) => {
                 rust_i18n::t!("error.split_parse_failed", detail = msg.as_str()).to_string()
             }
+            GcopError::Forge(msg) => {
+                rust_i18n::t!("error.forge", detail = msg.as_str()).to_string()
+            }
+            GcopError::SecretsDetected(findings) => {
+                rust_i18n::t!("error.secrets_detected", findings = findings.as_str()).to_string()
+            }
+            GcopError::NonInteractive => rust_i18n::t!("error.non_interactive").to_string(),
+            GcopError::ReviewGateFailed {
+                highest,
+                threshold,
+                count,
+            } => rust_i18n::t!(
+                "error.review_gate_failed",
+                count = count,
+                threshold = threshold.as_str(),
+                highest = highest.as_str()
+            )
+            .to_string(),
             GcopError::Other(msg) => msg.clone(),
         }
     }
 
+    /// Whether the error comes from reaching or calling an LLM provider
+    /// (network, timeout, HTTP status, malformed or blocked response) rather
+    /// than from git, configuration or user input.
+    pub fn is_provider_failure(&self) -> bool {
+        matches!(
+            self,
+            GcopError::Llm(_)
+                | GcopError::LlmStreamTruncated { .. }
+                | GcopError::LlmContentBlocked { .. }
+                | GcopError::LlmTimeout { .. }
+                | GcopError::LlmConnectionFailed { .. }
+                | GcopError::LlmApi { .. }
+                | GcopError::Network(_)
+        )
+    }
+
+    /// Process exit code for this error (see [`exit_code`](mod@exit_code)).
+    ///
+    /// Cancellation, empty staging area, provider failures
+    /// ([`is_provider_failure()`]) and validation failures get their own
+    /// codes; every other error exits with `1`. [`ReviewGateFailed`] encodes
+    /// the highest severity found as `10`/`11`/`12` (critical/warning/info),
+    /// a range no other error uses.
+    ///
+    /// [`is_provider_failure()`]: GcopError::is_provider_failure
+    /// [`ReviewGateFailed`]: GcopError::ReviewGateFailed
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            GcopError::Interrupted => exit_code::INTERRUPTED,
+            GcopError::UserCancelled => exit_code::CANCELLED,
+            GcopError::NoStagedChanges => exit_code::NO_STAGED_CHANGES,
+            GcopError::ReviewGateFailed { highest, .. } => match highest {
+                IssueSeverity::Critical => exit_code::GATE_CRITICAL,
+                IssueSeverity::Warning => exit_code::GATE_WARNING,
+                IssueSeverity::Info => exit_code::GATE_INFO,
+            },
+            e if e.is_provider_failure() => exit_code::PROVIDER_FAILURE,
+            GcopError::InvalidInput(_)
+            | GcopError::Config(_)
+            | GcopError::ConfigParse(_)
+            | GcopError::SecretsDetected(_)
+            | GcopError::SplitVerifyFailed { .. } => exit_code::VALIDATION_FAILURE,
+            _ => exit_code::GENERIC,
+        }
+    }
+
     /// Get localized solutions
     ///
     /// Returns user-friendly resolution suggestions based on the error type (if any).
@@ -473,9 +634,19 @@ impl GcopError {
             GcopError::SplitCommitPartial { .. } => {
                 Some(rust_i18n::t!("suggestion.split_partial").to_string())
             }
+            GcopError::SplitVerifyFailed { .. } => {
+                Some(rust_i18n::t!("suggestion.split_verify_failed").to_string())
+            }
             GcopError::SplitParseFailed(_) => {
                 Some(rust_i18n::t!("suggestion.split_parse_failed").to_string())
             }
+            GcopError::Forge(_) => Some(rust_i18n::t!("suggestion.forge").to_string()),
+            GcopError::SecretsDetected(_) => {
+                Some(rust_i18n::t!("suggestion.secrets_detected").to_string())
+            }
+            GcopError::NonInteractive => {
+                Some(rust_i18n::t!("suggestion.non_interactive").to_string())
+            }
             _ => None,
         }
     }
@@ -652,6 +823,7 @@ mod tests {
     fn test_suggestion_returns_none_for_other_errors() {
         let cases = vec![
             GcopError::UserCancelled,
+            GcopError::Interrupted,
             GcopError::InvalidInput("bad input".to_string()),
             GcopError::Other("random error".to_string()),
             GcopError::GitCommand("git failed".to_string()),
@@ -669,4 +841,70 @@ mod tests {
             );
         }
     }
+
+    // === Exit codes ===
+
+    #[test]
+    fn test_exit_codes() {
+        let cases = vec![
+            (GcopError::UserCancelled, exit_code::CANCELLED),
+            (GcopError::NoStagedChanges, exit_code::NO_STAGED_CHANGES),
+            (
+                GcopError::LlmApi {
+                    status: 503,
+                    message: "unavailable".to_string(),
+                },
+                exit_code::PROVIDER_FAILURE,
+            ),
+            (
+                GcopError::Llm("bad response".to_string()),
+                exit_code::PROVIDER_FAILURE,
+            ),
+            (
+                GcopError::Config("missing field".to_string()),
+                exit_code::VALIDATION_FAILURE,
+            ),
+            (
+                GcopError::InvalidInput("bad input".to_string()),
+                exit_code::VALIDATION_FAILURE,
+            ),
+            (
+                GcopError::ReviewGateFailed {
+                    highest: IssueSeverity::Info,
+                    threshold: IssueSeverity::Info,
+                    count: 1,
+                },
+                exit_code::GATE_INFO,
+            ),
+            (
+                GcopError::GitCommand("git failed".to_string()),
+                exit_code::GENERIC,
+            ),
+            (GcopError::Interrupted, exit_code::INTERRUPTED),
+        ];
+
+        for (err, code) in cases {
+            assert_eq!(err.exit_code(), code, "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_gate_exit_codes_are_distinct() {
+        let others = [
+            exit_code::SUCCESS,
+            exit_code::GENERIC,
+            exit_code::CANCELLED,
+            exit_code::NO_STAGED_CHANGES,
+            exit_code::PROVIDER_FAILURE,
+            exit_code::VALIDATION_FAILURE,
+            exit_code::INTERRUPTED,
+        ];
+        for gate in [
+            exit_code::GATE_CRITICAL,
+            exit_code::GATE_WARNING,
+            exit_code::GATE_INFO,
+        ] {
+            assert!(!others.contains(&gate), "{gate}");
+        }
+    }
 }
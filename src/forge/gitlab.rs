@@ -0,0 +1,416 @@
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder, Url};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+use super::{Forge, ForgeTarget, MergeRequest};
+use crate::config::NetworkConfig;
+use crate::error::{GcopError, Result};
+use crate::llm::provider::base::truncate_for_preview;
+use crate::llm::provider::create_http_client;
+use crate::llm::{IssueSeverity, ReviewIssue, ReviewResult};
+
+/// Commit SHAs GitLab needs to anchor a discussion to a diff line.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DiffRefs {
+    /// Merge base of source and target.
+    pub base_sha: String,
+    /// Tip of the source branch.
+    pub head_sha: String,
+    /// Tip of the target branch when the diff was created.
+    pub start_sha: String,
+}
+
+/// `GET /projects/:id/merge_requests/:iid/changes` response (fields used).
+#[derive(Debug, Deserialize)]
+struct MergeRequestChanges {
+    iid: u64,
+    title: String,
+    source_branch: String,
+    target_branch: String,
+    web_url: String,
+    #[serde(default)]
+    diff_refs: Option<DiffRefs>,
+    #[serde(default)]
+    changes: Vec<Change>,
+}
+
+/// A single file change in a merge request.
+#[derive(Debug, Deserialize)]
+struct Change {
+    old_path: String,
+    new_path: String,
+    #[serde(default)]
+    new_file: bool,
+    #[serde(default)]
+    deleted_file: bool,
+    #[serde(default)]
+    diff: String,
+}
+
+/// Text position of a diff discussion.
+#[derive(Debug, Serialize)]
+struct Position<'a> {
+    position_type: &'static str,
+    base_sha: &'a str,
+    head_sha: &'a str,
+    start_sha: &'a str,
+    old_path: &'a str,
+    new_path: &'a str,
+    new_line: usize,
+}
+
+/// Request body for notes and discussions.
+#[derive(Debug, Serialize)]
+struct NoteBody<'a> {
+    body: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<Position<'a>>,
+}
+
+/// GitLab REST API (v4) client scoped to one project.
+pub struct GitLabClient {
+    client: Client,
+    api_base: Url,
+    project: String,
+    token: Option<String>,
+}
+
+impl GitLabClient {
+    /// Creates a client for `target`.
+    ///
+    /// `token` is sent as `PRIVATE-TOKEN`; it is optional for reading public
+    /// projects but required for posting discussions.
+    pub fn new(
+        target: &ForgeTarget,
+        token: Option<String>,
+        network: &NetworkConfig,
+    ) -> Result<Self> {
+        let api_base = Url::parse(&format!("{}/api/v4/", target.base_url)).map_err(|e| {
+            GcopError::Forge(
+                rust_i18n::t!(
+                    "forge.invalid_url",
+                    url = target.base_url.as_str(),
+                    error = e
+                )
+                .to_string(),
+            )
+        })?;
+        Ok(Self {
+            client: create_http_client(network)?,
+            api_base,
+            project: target.project.clone(),
+            token,
+        })
+    }
+
+    /// Builds `<api>/projects/<encoded project>/merge_requests/<iid>/<tail...>`.
+    fn merge_request_url(&self, iid: u64, tail: &[&str]) -> Url {
+        let mut url = self.api_base.clone();
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .expect("http(s) URLs always have path segments");
+            segments.pop_if_empty();
+            // The project path is one segment: `group/project` becomes `group%2Fproject`
+            segments
+                .push("projects")
+                .push(&self.project)
+                .push("merge_requests")
+                .push(&iid.to_string())
+                .extend(tail);
+        }
+        url
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.header("PRIVATE-TOKEN", token),
+            None => request,
+        }
+    }
+
+    async fn post_note(&self, iid: u64, tail: &str, body: &NoteBody<'_>) -> Result<()> {
+        let response = self
+            .authorize(self.client.post(self.merge_request_url(iid, &[tail])))
+            .json(body)
+            .send()
+            .await?;
+        check_status(response).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl Forge for GitLabClient {
+    async fn fetch_merge_request(&self, iid: u64) -> Result<MergeRequest> {
+        let response = self
+            .authorize(self.client.get(self.merge_request_url(iid, &["changes"])))
+            .send()
+            .await?;
+        let text = check_status(response).await?;
+        let changes: MergeRequestChanges = serde_json::from_str(&text).map_err(|e| {
+            GcopError::Forge(rust_i18n::t!("forge.invalid_response", error = e).to_string())
+        })?;
+
+        Ok(MergeRequest {
+            iid: changes.iid,
+            title: changes.title,
+            source_branch: changes.source_branch,
+            target_branch: changes.target_branch,
+            web_url: changes.web_url,
+            diff: build_unified_diff(&changes.changes),
+            diff_refs: changes.diff_refs,
+        })
+    }
+
+    async fn post_review(&self, mr: &MergeRequest, result: &ReviewResult) -> Result<usize> {
+        if self.token.is_none() {
+            return Err(GcopError::Forge(
+                rust_i18n::t!("forge.token_required").to_string(),
+            ));
+        }
+
+        self.post_note(
+            mr.iid,
+            "notes",
+            &NoteBody {
+                body: &summary_body(result),
+                position: None,
+            },
+        )
+        .await?;
+
+        let mut posted = 0;
+        for issue in &result.issues {
+            let body = issue_body(issue);
+            let position = mr
+                .diff_refs
+                .as_ref()
+                .and_then(|refs| issue_position(issue, refs));
+
+            if position.is_some() {
+                let positioned = NoteBody {
+                    body: &body,
+                    position,
+                };
+                match self.post_note(mr.iid, "discussions", &positioned).await {
+                    Ok(()) => {
+                        posted += 1;
+                        continue;
+                    }
+                    // The line is outside the diff; post an unanchored discussion instead
+                    Err(e) => tracing::debug!("Positioned discussion rejected: {}", e),
+                }
+            }
+
+            self.post_note(
+                mr.iid,
+                "discussions",
+                &NoteBody {
+                    body: &with_location(&body, issue),
+                    position: None,
+                },
+            )
+            .await?;
+            posted += 1;
+        }
+        Ok(posted)
+    }
+}
+
+/// Returns the response body, or a [`GcopError::Forge`] for non-2xx status codes.
+async fn check_status(response: reqwest::Response) -> Result<String> {
+    let status = response.status();
+    let text = response.text().await?;
+    if !status.is_success() {
+        return Err(GcopError::Forge(
+            rust_i18n::t!(
+                "forge.api_error",
+                status = status.as_u16(),
+                body = truncate_for_preview(&text)
+            )
+            .to_string(),
+        ));
+    }
+    Ok(text)
+}
+
+/// Reassembles GitLab's per-file hunks into one `git diff`-style unified diff.
+fn build_unified_diff(changes: &[Change]) -> String {
+    let mut diff = String::new();
+    for change in changes.iter().filter(|c| !c.diff.is_empty()) {
+        let old = if change.new_file {
+            "/dev/null".to_string()
+        } else {
+            format!("a/{}", change.old_path)
+        };
+        let new = if change.deleted_file {
+            "/dev/null".to_string()
+        } else {
+            format!("b/{}", change.new_path)
+        };
+        let _ = writeln!(
+            diff,
+            "diff --git a/{} b/{}",
+            change.old_path, change.new_path
+        );
+        let _ = writeln!(diff, "--- {}\n+++ {}", old, new);
+        diff.push_str(&change.diff);
+        if !change.diff.ends_with('\n') {
+            diff.push('\n');
+        }
+    }
+    diff
+}
+
+/// Anchors an issue to a new-side line when it has both file and line.
+fn issue_position<'a>(issue: &'a ReviewIssue, refs: &'a DiffRefs) -> Option<Position<'a>> {
+    let file = issue.file.as_deref()?;
+    Some(Position {
+        position_type: "text",
+        base_sha: &refs.base_sha,
+        head_sha: &refs.head_sha,
+        start_sha: &refs.start_sha,
+        old_path: file,
+        new_path: file,
+        new_line: issue.line?,
+    })
+}
+
+fn severity_label(severity: IssueSeverity) -> String {
+    match severity {
+        IssueSeverity::Critical => rust_i18n::t!("review.md.severity_critical"),
+        IssueSeverity::Warning => rust_i18n::t!("review.md.severity_warning"),
+        IssueSeverity::Info => rust_i18n::t!("review.md.severity_info"),
+    }
+    .to_string()
+}
+
+/// Discussion body for a single issue.
+fn issue_body(issue: &ReviewIssue) -> String {
+    let mut body = format!("{} {}", severity_label(issue.severity), issue.description);
+    if let Some(patch) = &issue.suggested_patch {
+        let _ = write!(
+            body,
+            "\n\n{}\n\n```diff\n{}\n```",
+            rust_i18n::t!("review.md.suggested_patch"),
+            patch.trim_end()
+        );
+    }
+    body
+}
+
+/// Prefix an unanchored discussion with the issue location.
+fn with_location(body: &str, issue: &ReviewIssue) -> String {
+    let location = match (&issue.file, issue.line) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.clone(),
+        _ => return body.to_string(),
+    };
+    format!(
+        "{}\n\n{}",
+        rust_i18n::t!("review.md.location", location = location),
+        body
+    )
+}
+
+/// Summary note posted once per review.
+fn summary_body(result: &ReviewResult) -> String {
+    let mut body = format!(
+        "{}\n\n{}",
+        rust_i18n::t!("forge.summary_title"),
+        result.summary
+    );
+    if !result.suggestions.is_empty() {
+        let _ = write!(body, "\n\n{}\n", rust_i18n::t!("review.md.suggestions"));
+        for suggestion in &result.suggestions {
+            let _ = write!(body, "\n- {}", suggestion);
+        }
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ForgeKind;
+
+    fn client() -> GitLabClient {
+        crate::llm::provider::test_utils::ensure_crypto_provider();
+        GitLabClient::new(
+            &ForgeTarget {
+                kind: ForgeKind::GitLab,
+                base_url: "https://gitlab.example.com".to_string(),
+                project: "group/sub/project".to_string(),
+            },
+            None,
+            &NetworkConfig::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_request_url_encodes_project() {
+        assert_eq!(
+            client().merge_request_url(7, &["changes"]).as_str(),
+            "https://gitlab.example.com/api/v4/projects/group%2Fsub%2Fproject/merge_requests/7/changes"
+        );
+    }
+
+    #[test]
+    fn test_parse_changes_and_build_diff() {
+        let json = r#"{
+            "iid": 7,
+            "title": "Add login",
+            "source_branch": "feature/login",
+            "target_branch": "main",
+            "web_url": "https://gitlab.example.com/group/sub/project/-/merge_requests/7",
+            "diff_refs": {"base_sha": "b", "head_sha": "h", "start_sha": "s"},
+            "changes": [
+                {"old_path": "src/a.rs", "new_path": "src/a.rs", "new_file": false,
+                 "deleted_file": false, "diff": "@@ -1 +1 @@\n-a\n+b\n"},
+                {"old_path": "src/new.rs", "new_path": "src/new.rs", "new_file": true,
+                 "deleted_file": false, "diff": "@@ -0,0 +1 @@\n+x"}
+            ]
+        }"#;
+        let changes: MergeRequestChanges = serde_json::from_str(json).unwrap();
+        assert_eq!(changes.iid, 7);
+        assert_eq!(
+            changes.diff_refs,
+            Some(DiffRefs {
+                base_sha: "b".to_string(),
+                head_sha: "h".to_string(),
+                start_sha: "s".to_string(),
+            })
+        );
+        assert_eq!(
+            build_unified_diff(&changes.changes),
+            "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\n\
+             diff --git a/src/new.rs b/src/new.rs\n--- /dev/null\n+++ b/src/new.rs\n@@ -0,0 +1 @@\n+x\n"
+        );
+    }
+
+    #[test]
+    fn test_issue_position_requires_file_and_line() {
+        let refs = DiffRefs {
+            base_sha: "b".to_string(),
+            head_sha: "h".to_string(),
+            start_sha: "s".to_string(),
+        };
+        let mut issue = ReviewIssue {
+            severity: IssueSeverity::Warning,
+            description: "d".to_string(),
+            file: Some("src/a.rs".to_string()),
+            line: Some(3),
+            suggested_patch: None,
+            category: None,
+        };
+        let position = serde_json::to_value(issue_position(&issue, &refs).unwrap()).unwrap();
+        assert_eq!(position["position_type"], "text");
+        assert_eq!(position["new_path"], "src/a.rs");
+        assert_eq!(position["new_line"], 3);
+
+        issue.line = None;
+        assert!(issue_position(&issue, &refs).is_none());
+    }
+}
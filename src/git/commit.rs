@@ -1,58 +1,221 @@
-use std::process::Command;
+use std::io::IsTerminal;
+use std::process::{Command, Stdio};
 
 use crate::error::{GcopError, Result};
 
+/// Reflog action recorded for commits created by gcop-rs.
+///
+/// The `HEAD` reflog entry reads `commit (gcop-rs): <subject>`, which is how
+/// `gcop-rs undo` recognizes its own commits.
+pub const REFLOG_ACTION: &str = "commit (gcop-rs)";
+
+/// Commit signing behavior.
+///
+/// # Variants
+/// - [`Auto`] - follow git configuration (`commit.gpgsign`, `gpg.format`, `user.signingkey`)
+/// - [`Sign`] - always sign (`git commit -S`)
+/// - [`NoSign`] - never sign (`git commit --no-gpg-sign`)
+///
+/// [`Auto`]: CommitSigning::Auto
+/// [`Sign`]: CommitSigning::Sign
+/// [`NoSign`]: CommitSigning::NoSign
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CommitSigning {
+    /// Follow git configuration.
+    #[default]
+    Auto,
+    /// Force signing regardless of `commit.gpgsign`.
+    Sign,
+    /// Disable signing regardless of `commit.gpgsign`.
+    NoSign,
+}
+
+impl CommitSigning {
+    /// Resolve signing mode from config (`commit.sign`) and the `--no-sign` flag.
+    ///
+    /// `--no-sign` always wins; otherwise `Some(true)` forces signing,
+    /// `Some(false)` disables it and `None` defers to git configuration.
+    pub fn resolve(config_sign: Option<bool>, no_sign: bool) -> Self {
+        match (no_sign, config_sign) {
+            (true, _) | (false, Some(false)) => Self::NoSign,
+            (false, Some(true)) => Self::Sign,
+            (false, None) => Self::Auto,
+        }
+    }
+
+    /// Extra `git commit` / `git rebase` argument for this mode, if any.
+    pub(super) fn git_arg(self) -> Option<&'static str> {
+        match self {
+            Self::Auto => None,
+            Self::Sign => Some("-S"),
+            Self::NoSign => Some("--no-gpg-sign"),
+        }
+    }
+}
+
+/// Stdin given to `git commit`, its hooks and the signing program.
+///
+/// # Variants
+/// - [`Inherit`] - share the process stdin, so a signer can prompt for a passphrase
+/// - [`Null`] - no stdin, for callers reading their own input from stdin (`gcop-rs api`)
+///
+/// [`Inherit`]: CommitStdin::Inherit
+/// [`Null`]: CommitStdin::Null
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommitStdin {
+    /// Share the process stdin.
+    Inherit,
+    /// Connect stdin to the null device.
+    Null,
+}
+
+impl CommitStdin {
+    /// `Inherit` when stdin is a terminal, `Null` otherwise.
+    pub fn detect() -> Self {
+        if std::io::stdin().is_terminal() {
+            Self::Inherit
+        } else {
+            Self::Null
+        }
+    }
+
+    pub(super) fn stdio(self) -> Stdio {
+        match self {
+            Self::Inherit => Stdio::inherit(),
+            Self::Null => Stdio::null(),
+        }
+    }
+}
+
 /// Execute git commit
 ///
 /// Use git CLI instead of git2 to support:
-/// - GPG signature (commit.gpgsign, user.signingkey)
+/// - GPG/SSH signature (commit.gpgsign, gpg.format, user.signingkey)
 /// - Git hooks (pre-commit, commit-msg, etc.)
 /// - All git config configurations
 ///
 /// # Arguments
 /// * `message` - Commit message
-pub fn commit_changes(message: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["commit", "-m", message])
-        .output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let error_msg = if stderr.trim().is_empty() {
-            // Some git errors are output to stdout instead of stderr
-            String::from_utf8_lossy(&output.stdout).trim().to_string()
-        } else {
-            stderr.trim().to_string()
-        };
-        return Err(GcopError::GitCommand(error_msg));
-    }
-
-    Ok(())
+/// * `signing` - Signing behavior
+/// * `stdin` - Stdin of git, its hooks and the signer
+pub fn commit_changes(message: &str, signing: CommitSigning, stdin: CommitStdin) -> Result<()> {
+    run_git_commit(
+        &build_commit_args(message, false, signing),
+        Some(REFLOG_ACTION),
+        stdin,
+    )
 }
 
 /// Execute git commit --amend
 ///
 /// Use git CLI instead of git2 to support:
-/// - GPG signature (commit.gpgsign, user.signingkey)
+/// - GPG/SSH signature (commit.gpgsign, gpg.format, user.signingkey)
 /// - Git hooks (pre-commit, commit-msg, etc.)
 /// - All git config configurations
 ///
 /// # Arguments
 /// * `message` - New commit message
-pub fn commit_amend_changes(message: &str) -> Result<()> {
-    let output = Command::new("git")
-        .args(["commit", "--amend", "-m", message])
-        .output()?;
+/// * `signing` - Signing behavior
+/// * `stdin` - Stdin of git, its hooks and the signer
+pub fn commit_amend_changes(
+    message: &str,
+    signing: CommitSigning,
+    stdin: CommitStdin,
+) -> Result<()> {
+    run_git_commit(&build_commit_args(message, true, signing), None, stdin)
+}
+
+/// Build `git commit` arguments (pure function, easy to test).
+fn build_commit_args(message: &str, amend: bool, signing: CommitSigning) -> Vec<&str> {
+    let mut args = vec!["commit"];
+    if amend {
+        args.push("--amend");
+    }
+    if let Some(arg) = signing.git_arg() {
+        args.push(arg);
+    }
+    args.extend(["-m", message]);
+    args
+}
+
+pub(super) fn run_git_commit(
+    args: &[&str],
+    reflog_action: Option<&str>,
+    stdin: CommitStdin,
+) -> Result<()> {
+    let mut command = Command::new("git");
+    command.args(args);
+    if let Some(action) = reflog_action {
+        command.env("GIT_REFLOG_ACTION", action);
+    }
+    let output = command.stdin(stdin.stdio()).output()?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        let error_msg = if stderr.trim().is_empty() {
+        let mut error_msg = if stderr.trim().is_empty() {
+            // Some git errors are output to stdout instead of stderr
             String::from_utf8_lossy(&output.stdout).trim().to_string()
         } else {
             stderr.trim().to_string()
         };
+        if is_signing_failure(&error_msg) {
+            error_msg.push('\n');
+            error_msg.push_str(&rust_i18n::t!("git.sign_failed_hint"));
+        }
         return Err(GcopError::GitCommand(error_msg));
     }
 
     Ok(())
 }
+
+/// Whether git output indicates that signing the commit failed.
+fn is_signing_failure(output: &str) -> bool {
+    output.contains("failed to sign the data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_signing() {
+        assert_eq!(CommitSigning::resolve(None, false), CommitSigning::Auto);
+        assert_eq!(
+            CommitSigning::resolve(Some(true), false),
+            CommitSigning::Sign
+        );
+        assert_eq!(
+            CommitSigning::resolve(Some(false), false),
+            CommitSigning::NoSign
+        );
+        assert_eq!(
+            CommitSigning::resolve(Some(true), true),
+            CommitSigning::NoSign
+        );
+        assert_eq!(CommitSigning::resolve(None, true), CommitSigning::NoSign);
+    }
+
+    #[test]
+    fn test_build_commit_args() {
+        assert_eq!(
+            build_commit_args("msg", false, CommitSigning::Auto),
+            vec!["commit", "-m", "msg"]
+        );
+        assert_eq!(
+            build_commit_args("msg", false, CommitSigning::Sign),
+            vec!["commit", "-S", "-m", "msg"]
+        );
+        assert_eq!(
+            build_commit_args("msg", true, CommitSigning::NoSign),
+            vec!["commit", "--amend", "--no-gpg-sign", "-m", "msg"]
+        );
+    }
+
+    #[test]
+    fn test_is_signing_failure() {
+        assert!(is_signing_failure(
+            "error: gpg failed to sign the data\nfatal: failed to write commit object"
+        ));
+        assert!(!is_signing_failure("nothing to commit, working tree clean"));
+    }
+}
@@ -4,8 +4,10 @@ use crate::git::DiffStats;
 /// diff information for a single file
 #[derive(Debug, Clone)]
 pub struct FileDiff {
-    /// Filename (relative to repository root)
+    /// Filename (relative to repository root; the new path for renames)
     pub filename: String,
+    /// Previous path when the file was renamed or moved
+    pub old_filename: Option<String>,
     /// A complete diff patch of this file (from "diff --git" to the next file boundary)
     pub content: String,
     /// Number of new rows
@@ -45,7 +47,19 @@ fn extract_filename_from_diff_header(line: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Path from a `rename from <path>` / `rename to <path>` header line
+fn rename_header_path<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    let path = line.strip_prefix(prefix)?;
+    Some(
+        path.strip_prefix('"')
+            .and_then(|p| p.strip_suffix('"'))
+            .unwrap_or(path),
+    )
+}
+
 /// Extract statistics from diff text
+///
+/// Renamed files are listed under their new path.
 pub fn parse_diff_stats(diff: &str) -> Result<DiffStats> {
     let mut files_changed = Vec::new();
     let mut insertions = 0;
@@ -56,6 +70,10 @@ pub fn parse_diff_stats(diff: &str) -> Result<DiffStats> {
             if let Some(filename) = extract_filename_from_diff_header(line) {
                 files_changed.push(filename);
             }
+        } else if let Some(new_path) = rename_header_path(line, "rename to ") {
+            if let Some(last) = files_changed.last_mut() {
+                *last = new_path.to_string();
+            }
         } else if line.starts_with('+') && !line.starts_with("+++") {
             insertions += 1;
         } else if line.starts_with('-') && !line.starts_with("---") {
@@ -81,6 +99,7 @@ pub fn split_diff_by_file(diff: &str) -> Vec<FileDiff> {
 
     let mut files: Vec<FileDiff> = Vec::new();
     let mut current_filename: Option<String> = None;
+    let mut current_old_filename: Option<String> = None;
     let mut current_lines: Vec<&str> = Vec::new();
     let mut current_insertions = 0usize;
     let mut current_deletions = 0usize;
@@ -92,6 +111,7 @@ pub fn split_diff_by_file(diff: &str) -> Vec<FileDiff> {
                 let content = current_lines.join("\n");
                 files.push(FileDiff {
                     filename,
+                    old_filename: current_old_filename.take(),
                     content,
                     insertions: current_insertions,
                     deletions: current_deletions,
@@ -103,8 +123,12 @@ pub fn split_diff_by_file(diff: &str) -> Vec<FileDiff> {
             current_filename = extract_filename_from_diff_header(line);
             current_lines.push(line);
         } else {
-            if current_filename.is_some() {
-                if line.starts_with('+') && !line.starts_with("+++") {
+            if let Some(filename) = current_filename.as_mut() {
+                if let Some(old_path) = rename_header_path(line, "rename from ") {
+                    current_old_filename = Some(old_path.to_string());
+                } else if let Some(new_path) = rename_header_path(line, "rename to ") {
+                    *filename = new_path.to_string();
+                } else if line.starts_with('+') && !line.starts_with("+++") {
                     current_insertions += 1;
                 } else if line.starts_with('-') && !line.starts_with("---") {
                     current_deletions += 1;
@@ -119,6 +143,7 @@ pub fn split_diff_by_file(diff: &str) -> Vec<FileDiff> {
         let content = current_lines.join("\n");
         files.push(FileDiff {
             filename,
+            old_filename: current_old_filename,
             content,
             insertions: current_insertions,
             deletions: current_deletions,
@@ -128,6 +153,363 @@ pub fn split_diff_by_file(diff: &str) -> Vec<FileDiff> {
     files
 }
 
+/// One side (old or new) of a binary file change
+#[derive(Debug, Clone, Copy)]
+pub struct BinaryVersion<'a> {
+    /// Size of the file in bytes
+    pub size: u64,
+    /// Leading bytes of the content (used to sniff image headers)
+    pub header: &'a [u8],
+}
+
+/// Number of leading bytes worth reading for [`summarize_binary`].
+///
+/// Large enough to get past EXIF blocks with embedded thumbnails in JPEG files.
+pub const BINARY_HEADER_LIMIT: usize = 256 * 1024;
+
+/// Image metadata sniffed from a file header
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImageInfo {
+    format: &'static str,
+    width: u32,
+    height: u32,
+    /// Short EXIF summary (camera make/model), JPEG only
+    exif: Option<String>,
+}
+
+/// Replace git's "Binary files a/x and b/x differ" with a structured one-line summary
+///
+/// The summary lists the change kind, the file type, the size (and size delta)
+/// and, for PNG/JPEG/GIF/WebP/BMP images, the dimensions and a short EXIF
+/// summary. `None` means the file does not exist on that side (added or
+/// deleted). The line never starts with `+` or `-`, so diff statistics are
+/// unaffected.
+pub fn summarize_binary(
+    path: &str,
+    old: Option<BinaryVersion<'_>>,
+    new: Option<BinaryVersion<'_>>,
+) -> String {
+    let old_image = old.and_then(|v| sniff_image(v.header));
+    let new_image = new.and_then(|v| sniff_image(v.header));
+
+    let kind = match (old, new) {
+        (None, Some(_)) => "added",
+        (Some(_), None) => "deleted",
+        _ => "modified",
+    };
+    let file_type = match new_image.as_ref().or(old_image.as_ref()) {
+        Some(image) => format!("{} image", image.format),
+        None => match path.rsplit_once('.') {
+            Some((_, ext)) if !ext.is_empty() && !ext.contains('/') => {
+                format!("{} file", ext.to_ascii_uppercase())
+            }
+            _ => "binary file".to_string(),
+        },
+    };
+
+    let mut details = vec![file_type];
+    match (old, new) {
+        (Some(old), Some(new)) => {
+            let delta = new.size as i64 - old.size as i64;
+            let sign = if delta < 0 { "-" } else { "+" };
+            details.push(format!(
+                "{} -> {} ({}{})",
+                format_size(old.size),
+                format_size(new.size),
+                sign,
+                format_size(delta.unsigned_abs())
+            ));
+        }
+        (Some(version), None) | (None, Some(version)) => details.push(format_size(version.size)),
+        (None, None) => {}
+    }
+    match (&old_image, &new_image) {
+        (Some(old), Some(new)) if (old.width, old.height) != (new.width, new.height) => {
+            details.push(format!(
+                "{}x{} -> {}x{}",
+                old.width, old.height, new.width, new.height
+            ));
+        }
+        (_, Some(image)) | (Some(image), None) => {
+            details.push(format!("{}x{}", image.width, image.height));
+        }
+        _ => {}
+    }
+    if let Some(exif) = new_image
+        .as_ref()
+        .or(old_image.as_ref())
+        .and_then(|i| i.exif.clone())
+    {
+        details.push(format!("EXIF: {}", exif));
+    }
+
+    format!("Binary file {}: {} ({})", kind, path, details.join(", "))
+}
+
+/// Maximum number of commit subjects listed per direction in a submodule log
+pub const SUBMODULE_LOG_LIMIT: usize = 20;
+
+/// Prefix of the header line written by [`SubmoduleLog::render`]
+const SUBMODULE_LOG_PREFIX: &str = "Submodule ";
+
+/// Commit log for a submodule pointer change (like `git diff --submodule=log`)
+#[derive(Debug, Clone, Default)]
+pub struct SubmoduleLog {
+    /// Submodule path (relative to repository root)
+    pub path: String,
+    /// Previous commit (`None` when the submodule was added)
+    pub old: Option<String>,
+    /// New commit (`None` when the submodule was removed)
+    pub new: Option<String>,
+    /// Subjects of the commits reachable from `new` but not `old` (newest first);
+    /// `None` when the submodule is not checked out
+    pub added: Option<Vec<String>>,
+    /// Subjects of the commits dropped by a rewind (newest first)
+    pub removed: Vec<String>,
+}
+
+impl SubmoduleLog {
+    /// Render the log block appended after the `Subproject commit` lines
+    ///
+    /// Lines never start with `+` or `-`, so diff statistics are unaffected.
+    pub fn render(&self) -> String {
+        let short = |id: &Option<String>| {
+            id.as_deref()
+                .map(|id| id.chars().take(7).collect::<String>())
+                .unwrap_or_else(|| "0000000".to_string())
+        };
+        let range = format!("{}..{}", short(&self.old), short(&self.new));
+        let status = match (&self.old, &self.new, &self.added) {
+            (None, Some(_), _) => "new submodule".to_string(),
+            (Some(_), None, _) => "submodule removed".to_string(),
+            (_, _, None) => "commits not checked out".to_string(),
+            (_, _, Some(added)) if self.removed.is_empty() => {
+                format!("{} new commit{}", added.len(), plural(added.len()))
+            }
+            (_, _, Some(added)) => format!(
+                "{} new, {} removed commit{}",
+                added.len(),
+                self.removed.len(),
+                plural(self.removed.len())
+            ),
+        };
+
+        let mut out = format!(
+            "{}{} {} ({})",
+            SUBMODULE_LOG_PREFIX, self.path, range, status
+        );
+        let listed = [
+            (">", self.added.as_deref().unwrap_or_default()),
+            ("<", &self.removed),
+        ];
+        for (marker, subjects) in listed {
+            for subject in subjects.iter().take(SUBMODULE_LOG_LIMIT) {
+                out.push_str(&format!("\n  {} {}", marker, subject));
+            }
+            if subjects.len() > SUBMODULE_LOG_LIMIT {
+                out.push_str(&format!(
+                    "\n  {} ... and {} more",
+                    marker,
+                    subjects.len() - SUBMODULE_LOG_LIMIT
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// Whether `diff` contains a submodule log written by [`SubmoduleLog::render`]
+pub fn has_submodule_log(diff: &str) -> bool {
+    diff.lines().any(|line| {
+        line.strip_prefix(SUBMODULE_LOG_PREFIX)
+            .is_some_and(|rest| rest.contains(" (") && rest.contains(".."))
+    })
+}
+
+fn plural(count: usize) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Human-readable byte size (`512 B`, `1.5 KiB`, `2.0 MiB`)
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+fn sniff_image(data: &[u8]) -> Option<ImageInfo> {
+    let info = |format, width, height| {
+        Some(ImageInfo {
+            format,
+            width,
+            height,
+            exif: None,
+        })
+    };
+
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") && data.get(12..16) == Some(b"IHDR") {
+        return info("PNG", be_u32(data, 16)?, be_u32(data, 20)?);
+    }
+    if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        return info("GIF", le_u16(data, 6)? as u32, le_u16(data, 8)? as u32);
+    }
+    if data.starts_with(b"BM") && data.len() >= 26 {
+        let width = i32::from_le_bytes(data[18..22].try_into().ok()?);
+        let height = i32::from_le_bytes(data[22..26].try_into().ok()?);
+        return info("BMP", width.unsigned_abs(), height.unsigned_abs());
+    }
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        let (width, height) = match data.get(12..16)? {
+            b"VP8 " => (
+                (le_u16(data, 26)? & 0x3fff) as u32,
+                (le_u16(data, 28)? & 0x3fff) as u32,
+            ),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+                ((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1)
+            }
+            b"VP8X" => (le_u24(data, 24)? + 1, le_u24(data, 27)? + 1),
+            _ => return None,
+        };
+        return info("WebP", width, height);
+    }
+    if data.starts_with(&[0xFF, 0xD8]) {
+        return sniff_jpeg(data);
+    }
+    None
+}
+
+/// Walk JPEG segments up to the first SOF marker, collecting EXIF on the way.
+fn sniff_jpeg(data: &[u8]) -> Option<ImageInfo> {
+    let mut exif = None;
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = data[pos + 1];
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        if marker == 0x01 || (0xD0..=0xD9).contains(&marker) {
+            pos += 2;
+            continue;
+        }
+        let length = be_u16(data, pos + 2)? as usize;
+        let segment = data.get(pos + 4..(pos + 2 + length).min(data.len()))?;
+        match marker {
+            0xE1 if segment.starts_with(b"Exif\0\0") => {
+                exif = parse_exif(&segment[6..]);
+            }
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some(ImageInfo {
+                    format: "JPEG",
+                    width: be_u16(segment, 3)? as u32,
+                    height: be_u16(segment, 1)? as u32,
+                    exif,
+                });
+            }
+            _ => {}
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+/// Camera make/model from the first IFD of a TIFF-encoded EXIF block.
+fn parse_exif(tiff: &[u8]) -> Option<String> {
+    let little = match tiff.get(0..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let u16_at = |offset: usize| -> Option<u16> {
+        let bytes: [u8; 2] = tiff.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if little {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+    let u32_at = |offset: usize| -> Option<u32> {
+        let bytes: [u8; 4] = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if little {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    };
+
+    let ifd = u32_at(4)? as usize;
+    let mut make = None;
+    let mut model = None;
+    for index in 0..u16_at(ifd)? as usize {
+        let entry = ifd + 2 + index * 12;
+        let tag = u16_at(entry)?;
+        // ASCII values longer than 4 bytes live at an offset
+        if !matches!(tag, 0x010F | 0x0110) || u16_at(entry + 2)? != 2 {
+            continue;
+        }
+        let count = u32_at(entry + 4)? as usize;
+        let start = if count > 4 {
+            u32_at(entry + 8)? as usize
+        } else {
+            entry + 8
+        };
+        let value = String::from_utf8_lossy(tiff.get(start..start + count)?)
+            .trim_end_matches('\0')
+            .trim()
+            .to_string();
+        if value.is_empty() {
+            continue;
+        }
+        if tag == 0x010F {
+            make = Some(value);
+        } else {
+            model = Some(value);
+        }
+    }
+
+    match (make, model) {
+        // Models often repeat the make ("Canon" / "Canon EOS R5")
+        (Some(make), Some(model)) if model.starts_with(&make) => Some(model),
+        (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+        (make, model) => make.or(model),
+    }
+}
+
+fn be_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn le_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn le_u24(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 3)?;
+    Some(u32::from(bytes[0]) | u32::from(bytes[1]) << 8 | u32::from(bytes[2]) << 16)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,4 +700,196 @@ Binary files a/image.png and b/image.png differ
         assert_eq!(files[0].insertions, 0);
         assert_eq!(files[0].deletions, 0);
     }
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        data
+    }
+
+    fn jpeg_with_exif(width: u16, height: u16) -> Vec<u8> {
+        // 大端 TIFF：IFD0 含 Make ("Canon") 与 Model ("Canon EOS R5")
+        let mut tiff = b"MM\0\x2a\0\0\0\x08\0\x02".to_vec();
+        tiff.extend_from_slice(&[0x01, 0x0F, 0, 2, 0, 0, 0, 6, 0, 0, 0, 38]);
+        tiff.extend_from_slice(&[0x01, 0x10, 0, 2, 0, 0, 0, 13, 0, 0, 0, 44]);
+        tiff.extend_from_slice(&[0, 0, 0, 0]);
+        tiff.extend_from_slice(b"Canon\0Canon EOS R5\0");
+
+        let mut data = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        data.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        data.extend_from_slice(b"Exif\0\0");
+        data.extend_from_slice(&tiff);
+        data.extend_from_slice(&[0xFF, 0xC0, 0, 17, 8]);
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&[3; 10]);
+        data
+    }
+
+    #[test]
+    fn test_summarize_binary_modified_image() {
+        let (old, new) = (png(64, 64), png(128, 96));
+        let summary = summarize_binary(
+            "assets/logo.png",
+            Some(BinaryVersion {
+                size: 2048,
+                header: &old,
+            }),
+            Some(BinaryVersion {
+                size: 1536,
+                header: &new,
+            }),
+        );
+        assert_eq!(
+            summary,
+            "Binary file modified: assets/logo.png (PNG image, 2.0 KiB -> 1.5 KiB (-512 B), 64x64 -> 128x96)"
+        );
+    }
+
+    #[test]
+    fn test_summarize_binary_added_and_deleted() {
+        let jpeg = jpeg_with_exif(4000, 3000);
+        assert_eq!(
+            summarize_binary(
+                "photo.jpg",
+                None,
+                Some(BinaryVersion {
+                    size: 3 * 1024 * 1024,
+                    header: &jpeg,
+                }),
+            ),
+            "Binary file added: photo.jpg (JPEG image, 3.0 MiB, 4000x3000, EXIF: Canon EOS R5)"
+        );
+        assert_eq!(
+            summarize_binary(
+                "docs/manual.pdf",
+                Some(BinaryVersion {
+                    size: 100,
+                    header: b"%PDF-1.7",
+                }),
+                None,
+            ),
+            "Binary file deleted: docs/manual.pdf (PDF file, 100 B)"
+        );
+        assert_eq!(
+            summarize_binary(
+                "bin/tool",
+                None,
+                Some(BinaryVersion {
+                    size: 1,
+                    header: b"\0"
+                })
+            ),
+            "Binary file added: bin/tool (binary file, 1 B)"
+        );
+    }
+
+    #[test]
+    fn test_sniff_image_formats() {
+        let gif = b"GIF89a\x0a\x00\x14\x00";
+        assert_eq!(
+            sniff_image(gif).map(|i| (i.format, i.width, i.height)),
+            Some(("GIF", 10, 20))
+        );
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\0\0\0\0\0\0\0\0".to_vec();
+        webp.extend_from_slice(&[0x1F, 0x03, 0x00, 0xDF, 0x01, 0x00]);
+        assert_eq!(
+            sniff_image(&webp).map(|i| (i.format, i.width, i.height)),
+            Some(("WebP", 800, 480))
+        );
+        // 截断的头部不会 panic
+        assert!(sniff_image(&png(1, 1)[..18]).is_none());
+        assert!(sniff_image(&jpeg_with_exif(1, 1)[..30]).is_none());
+        assert!(sniff_image(b"plain").is_none());
+    }
+
+    #[test]
+    fn test_renames_use_new_path() {
+        let diff = "diff --git a/old.rs b/src/new.rs\n\
+                    similarity index 90%\n\
+                    rename from old.rs\n\
+                    rename to src/new.rs\n\
+                    --- a/old.rs\n\
+                    +++ b/src/new.rs\n\
+                    @@ -1 +1 @@\n\
+                    -a\n\
+                    +b\n\
+                    diff --git a/c.rs b/c.rs\n\
+                    +c";
+        let files = split_diff_by_file(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].filename, "src/new.rs");
+        assert_eq!(files[0].old_filename.as_deref(), Some("old.rs"));
+        assert_eq!((files[0].insertions, files[0].deletions), (1, 1));
+        assert_eq!(files[1].filename, "c.rs");
+        assert_eq!(files[1].old_filename, None);
+
+        let stats = parse_diff_stats(diff).unwrap();
+        assert_eq!(stats.files_changed, vec!["src/new.rs", "c.rs"]);
+    }
+
+    #[test]
+    fn test_submodule_log_render() {
+        let log = SubmoduleLog {
+            path: "libs/foo".to_string(),
+            old: Some("1111111aaaa".to_string()),
+            new: Some("2222222bbbb".to_string()),
+            added: Some(vec!["feat: b".to_string(), "fix: a".to_string()]),
+            removed: vec![],
+        };
+        let rendered = log.render();
+        assert_eq!(
+            rendered,
+            "Submodule libs/foo 1111111..2222222 (2 new commits)\n  > feat: b\n  > fix: a"
+        );
+        assert!(has_submodule_log(&rendered));
+        assert!(!has_submodule_log("Submodules are great"));
+
+        let rewind = SubmoduleLog {
+            added: Some(vec!["x".to_string()]),
+            removed: vec!["y".to_string()],
+            ..log.clone()
+        };
+        assert!(
+            rewind
+                .render()
+                .contains("(1 new, 1 removed commit)\n  > x\n  < y")
+        );
+
+        let unavailable = SubmoduleLog {
+            added: None,
+            ..log.clone()
+        };
+        assert!(unavailable.render().ends_with("(commits not checked out)"));
+
+        let added = SubmoduleLog {
+            old: None,
+            added: None,
+            ..log
+        };
+        assert_eq!(
+            added.render(),
+            "Submodule libs/foo 0000000..2222222 (new submodule)"
+        );
+    }
+
+    #[test]
+    fn test_submodule_log_limit() {
+        let log = SubmoduleLog {
+            path: "sub".to_string(),
+            old: Some("a".to_string()),
+            new: Some("b".to_string()),
+            added: Some((0..25).map(|i| format!("commit {}", i)).collect()),
+            removed: vec![],
+        };
+        let rendered = log.render();
+        assert!(rendered.starts_with("Submodule sub a..b (25 new commits)"));
+        assert_eq!(
+            rendered.matches("\n  > commit").count(),
+            SUBMODULE_LOG_LIMIT
+        );
+        assert!(rendered.ends_with("\n  > ... and 5 more"));
+    }
 }
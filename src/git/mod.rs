@@ -9,10 +9,14 @@ pub mod commit;
 pub mod diff;
 /// `git2`-backed repository implementation of [`GitOperations`].
 pub mod repository;
+/// Rewording existing commits (amend or automated rebase).
+pub mod reword;
+/// Annotated tag helpers.
+pub mod tag;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::error::Result;
+use crate::error::{GcopError, Result};
 use chrono::{DateTime, Local};
 use serde::Serialize;
 
@@ -43,11 +47,39 @@ pub struct CommitInfo {
     /// Commit timestamp in local timezone.
     pub timestamp: DateTime<Local>,
     /// First line of the commit message.
-    #[allow(dead_code)]
-    // Reserved for future commit-message analytics.
     pub message: String,
 }
 
+/// Commit history scoping options.
+///
+/// Used by [`GitOperations::get_commit_history`] to narrow the walked history.
+/// The default value walks every commit reachable from `HEAD`.
+///
+/// # Fields
+/// - `since`: only include commits at or after this time
+/// - `until`: only include commits at or before this time
+/// - `range`: revision (`v1.0`) or range (`v1.0..v2.0`, `main...feature`) to walk instead of `HEAD`
+/// - `path`: only include commits touching this repository-relative path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryOptions {
+    /// Lower time bound (inclusive).
+    pub since: Option<DateTime<Local>>,
+    /// Upper time bound (inclusive).
+    pub until: Option<DateTime<Local>>,
+    /// Revision or revision range to walk instead of `HEAD`.
+    pub range: Option<String>,
+    /// Repository-relative path (file or directory) commits must touch.
+    pub path: Option<String>,
+}
+
+impl HistoryOptions {
+    /// Whether the commit timestamp falls inside the `since`/`until` window.
+    pub fn contains_time(&self, timestamp: &DateTime<Local>) -> bool {
+        self.since.is_none_or(|since| *timestamp >= since)
+            && self.until.is_none_or(|until| *timestamp <= until)
+    }
+}
+
 /// Unified interface for Git operations.
 ///
 /// This trait abstracts all Git repository operations, making it easier to test and extend.
@@ -94,6 +126,28 @@ pub trait GitOperations {
     /// - `Err(_)` - git operation failed
     fn get_uncommitted_diff(&self) -> Result<String>;
 
+    /// Returns the changes to tracked files since a snapshot taken with
+    /// [`snapshot_workdir`](Self::snapshot_workdir), staged or not.
+    ///
+    /// # Parameters
+    /// - `tree`: tree hash returned by `snapshot_workdir`
+    ///
+    /// # Returns
+    /// - `Ok(diff)` - diff text (possibly empty)
+    /// - `Err(_)` - the tree no longer exists or git operation failed
+    fn get_snapshot_diff(&self, tree: &str) -> Result<String>;
+
+    /// Writes the working tree content of tracked files as a tree object.
+    ///
+    /// Uses a temporary index, so neither the index nor the working tree
+    /// change. Equivalent to `git add -u` into a copy of the index followed
+    /// by `git write-tree`.
+    ///
+    /// # Returns
+    /// - `Ok(hash)` - hash of the written tree
+    /// - `Err(_)` - git operation failed
+    fn snapshot_workdir(&self) -> Result<String>;
+
     /// Returns the diff for a specific commit.
     ///
     /// Equivalent to `git diff <commit_hash>^!` (returns only the diff content).
@@ -112,6 +166,9 @@ pub trait GitOperations {
     /// - `HEAD~3..HEAD` - last 3 commits
     /// - `main..feature` - difference between branches
     /// - `abc123..def456` - difference between two commits
+    /// - `main...feature` - changes of `feature` since its merge-base with `main`
+    ///
+    /// An omitted side (`main...`) means `HEAD`.
     ///
     /// # Parameters
     /// - `range`: Git range expression
@@ -121,6 +178,16 @@ pub trait GitOperations {
     /// - `Err(_)` - invalid range or git operation failed
     fn get_range_diff(&self, range: &str) -> Result<String>;
 
+    /// Returns the branch other branches are reviewed against.
+    ///
+    /// Reads the target of `origin/HEAD`, falling back to a local or
+    /// `origin` `main`/`master` branch.
+    ///
+    /// # Returns
+    /// - `Ok(Some(name))` - branch name (for example `origin/main`)
+    /// - `Ok(None)` - no default branch could be determined
+    fn default_branch(&self) -> Result<Option<String>>;
+
     /// Reads the complete content of a file.
     ///
     /// Reads file contents from the working tree (not from git objects).
@@ -230,14 +297,27 @@ pub trait GitOperations {
     ///
     /// Returns commit entries in reverse chronological order.
     ///
+    /// # Parameters
+    /// - `options`: time window, revision range and path filters
+    ///   (`HistoryOptions::default()` walks everything reachable from `HEAD`)
+    ///
     /// # Returns
     /// - `Ok(history)` - commit list (newest first)
-    /// - `Err(_)` - git operation failed
+    /// - `Err(_)` - invalid range or git operation failed
     ///
     /// # Notes
-    /// - Only includes history reachable from the current branch HEAD.
+    /// - Without `range`, only includes history reachable from the current branch HEAD.
     /// - Empty repositories return an empty list.
-    fn get_commit_history(&self) -> Result<Vec<CommitInfo>>;
+    fn get_commit_history(&self, options: &HistoryOptions) -> Result<Vec<CommitInfo>>;
+
+    /// Returns recent non-merge commits that touched any of `paths`.
+    ///
+    /// Walks at most `max_scan` commits back from `HEAD`, newest first.
+    /// Paths are matched literally (no pathspec globbing).
+    ///
+    /// # Notes
+    /// - Empty repositories and an empty `paths` list return an empty list.
+    fn get_commits_touching(&self, paths: &[String], max_scan: usize) -> Result<Vec<CommitInfo>>;
 
     /// Returns line-level diff statistics for a single commit.
     ///
@@ -252,6 +332,50 @@ pub trait GitOperations {
     /// - `Err(_)` - commit not found or git error
     fn get_commit_line_stats(&self, hash: &str) -> Result<(usize, usize)>;
 
+    /// Resolves a revision (`HEAD~2`, a short SHA, a branch) to its commit summary.
+    ///
+    /// # Returns
+    /// - `Ok(info)` - full hash, author, timestamp and subject line
+    /// - `Err(GcopError::InvalidInput)` - the revision does not name a commit
+    fn get_commit_info(&self, rev: &str) -> Result<CommitInfo>;
+
+    /// Returns the full message of a commit (subject and body).
+    ///
+    /// # Returns
+    /// - `Ok(message)` - raw commit message
+    /// - `Err(GcopError::InvalidInput)` - the revision does not name a commit
+    fn get_commit_message(&self, rev: &str) -> Result<String>;
+
+    /// Returns whether `ancestor` is `descendant` or one of its ancestors.
+    ///
+    /// Equivalent to `git merge-base --is-ancestor`.
+    fn is_ancestor(&self, ancestor: &str, descendant: &str) -> Result<bool>;
+
+    /// Replaces the message of the commit `hash`, leaving its changes alone.
+    ///
+    /// `HEAD` is amended (`git commit --amend --only`, staged changes are not
+    /// included); an older commit is reworded with an automated
+    /// `git rebase -i`, which rewrites every commit after it.
+    ///
+    /// # Returns
+    /// - `Ok(())` - the commit was reworded
+    /// - `Err(GcopError::GitCommand)` - hook failure, dirty working tree, or rebase failure
+    ///   (the rebase is aborted)
+    fn reword_commit(&self, hash: &str, message: &str) -> Result<()>;
+
+    /// Returns the message of the newest `HEAD` reflog entry.
+    ///
+    /// # Returns
+    /// - `Ok(Some(message))` - the entry that moved `HEAD` to its current commit
+    /// - `Ok(None)` - no reflog, or its newest entry does not point at `HEAD`
+    fn head_reflog_message(&self) -> Result<Option<String>>;
+
+    /// Moves `HEAD` to `rev`, keeping the index and working tree.
+    ///
+    /// Equivalent to `git reset --soft <rev>`: the changes of the commits
+    /// left behind end up staged.
+    fn reset_soft(&self, rev: &str) -> Result<()>;
+
     /// Checks whether the repository has no commits.
     ///
     /// # Returns
@@ -276,12 +400,70 @@ pub trait GitOperations {
     /// Equivalent to `git add <files>`.
     fn stage_files(&self, files: &[String]) -> Result<()>;
 
+    /// Stages modifications and deletions of tracked files.
+    ///
+    /// Equivalent to `git add -u` (what `git commit -a` stages). Untracked
+    /// files are left alone.
+    fn stage_tracked(&self) -> Result<()>;
+
+    /// Writes the staged snapshot of every file into `dir`.
+    ///
+    /// Equivalent to `git checkout-index --all --prefix=<dir>/`; the working
+    /// tree and the index are left untouched.
+    fn checkout_index(&self, dir: &std::path::Path) -> Result<()>;
+
+    /// Applies a unified diff to the working tree.
+    ///
+    /// Equivalent to `git apply` with the patch on stdin. With `check_only`,
+    /// only verifies that the patch applies cleanly (`git apply --check`).
+    fn apply_patch(&self, patch: &str, check_only: bool) -> Result<()>;
+
+    /// Returns the URL of the named remote.
+    ///
+    /// # Returns
+    /// - `Ok(Some(url))` - remote exists and has a URL
+    /// - `Ok(None)` - remote does not exist
+    /// - `Err(_)` - git operation failed
+    fn get_remote_url(&self, name: &str) -> Result<Option<String>>;
+
     /// Returns the repository working directory path.
     ///
     /// # Returns
     /// - `Ok(path)` - absolute path to the repository working directory
     /// - `Err(_)` - bare repository or git operation failed
     fn get_workdir(&self) -> Result<PathBuf>;
+
+    /// Returns the path of the `.git` directory.
+    ///
+    /// For linked worktrees this is the worktree's private git directory.
+    fn get_git_dir(&self) -> Result<PathBuf>;
+
+    /// Returns the configured committer identity as `Name <email>`.
+    ///
+    /// # Returns
+    /// - `Ok(Some(identity))` - `user.name` and `user.email` are configured
+    /// - `Ok(None)` - identity is not configured
+    fn get_user_identity(&self) -> Result<Option<String>>;
+
+    /// Returns the most recent tag reachable from `HEAD`.
+    ///
+    /// Equivalent to `git describe --tags --abbrev=0`.
+    ///
+    /// # Returns
+    /// - `Ok(Some(tag))` - nearest tag
+    /// - `Ok(None)` - no tag is reachable (or the repository has no commits)
+    fn get_latest_tag(&self) -> Result<Option<String>>;
+
+    /// Returns whether `refs/tags/<name>` exists.
+    fn tag_exists(&self, name: &str) -> Result<bool>;
+
+    /// Creates an annotated tag on `HEAD` (`git tag -a <name> -m <message>`).
+    ///
+    /// Signing follows git configuration (`tag.gpgSign`).
+    fn create_tag(&self, name: &str, message: &str) -> Result<()>;
+
+    /// Pushes the tag `name` to `remote`.
+    fn push_tag(&self, remote: &str, name: &str) -> Result<()>;
 }
 
 /// Diff statistics.
@@ -314,19 +496,73 @@ pub struct DiffStats {
     pub deletions: usize,
 }
 
-/// Finds the git repository root by walking upward from the current directory.
+/// Finds the root of the working tree containing the current directory.
 ///
-/// Equivalent to `git rev-parse --show-toplevel`.
-/// Checks whether `.git` (directory or file, for submodule/worktree compatibility)
-/// exists at each level.
+/// Equivalent to `git rev-parse --show-toplevel`: honors `GIT_DIR` /
+/// `GIT_WORK_TREE` and resolves linked worktrees and submodules to their own
+/// working tree. Returns `None` outside a repository and for bare repositories.
 pub fn find_git_root() -> Option<PathBuf> {
-    let mut dir = std::env::current_dir().ok()?;
-    loop {
-        if dir.join(".git").exists() {
-            return Some(dir);
-        }
-        if !dir.pop() {
-            return None;
-        }
+    let repo = open_repository().ok()?;
+    let workdir = repo.workdir()?;
+    // git2 keeps the trailing separator; strip it like `--show-toplevel`
+    Some(workdir.components().collect())
+}
+
+/// Hooks directory of the repository whose working tree is `git_root`.
+///
+/// Equivalent to `git rev-parse --git-path hooks`: honors `core.hooksPath`
+/// (relative to the working tree) and uses the common git directory, so
+/// linked worktrees and submodules (where `.git` is a file) share the hooks
+/// git actually runs.
+pub fn hooks_dir(git_root: &Path) -> PathBuf {
+    let Ok(repo) = git2::Repository::open(git_root) else {
+        return git_root.join(".git").join("hooks");
+    };
+    let configured = repo
+        .config()
+        .and_then(|config| config.get_path("core.hooksPath"));
+    match configured {
+        Ok(path) => git_root.join(path),
+        Err(_) => repo.commondir().join("hooks"),
+    }
+}
+
+/// Path as git spells it in pathspecs and diffs: `/`-separated, without a
+/// leading `./`. Windows paths typed with `\` then match diff file names.
+pub fn to_git_path(path: &Path) -> String {
+    path.components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Expands the range of `review range`: no range reviews `HEAD` against the
+/// default branch (`<default>...HEAD`), and a bare ref `feature` becomes
+/// `<default>...feature`. Explicit `a..b` / `a...b` ranges are kept.
+pub fn expand_range(git: &dyn GitOperations, range: Option<&str>) -> Result<String> {
+    let head = match range {
+        Some(range) if range.contains("..") => return Ok(range.to_string()),
+        Some(range) => range,
+        None => "HEAD",
+    };
+    let base = git.default_branch()?.ok_or_else(|| {
+        GcopError::InvalidInput(rust_i18n::t!("git.no_default_branch").to_string())
+    })?;
+    Ok(format!("{}...{}", base, head))
+}
+
+/// Opens the repository for the current directory the way git does.
+///
+/// Discovery walks upward from the current directory (linked worktrees and
+/// bare repositories included) unless `GIT_DIR` is set. `GIT_WORK_TREE`
+/// overrides the working tree, and `GIT_CEILING_DIRECTORIES` is respected.
+pub(crate) fn open_repository() -> Result<git2::Repository> {
+    let repo = git2::Repository::open_from_env()?;
+    // libgit2 does not read GIT_WORK_TREE itself
+    if let Some(work_tree) = std::env::var_os("GIT_WORK_TREE").filter(|v| !v.is_empty()) {
+        let work_tree = std::path::absolute(PathBuf::from(work_tree))?;
+        repo.set_workdir(&work_tree, false)?;
     }
+    Ok(repo)
 }
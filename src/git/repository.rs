@@ -1,18 +1,27 @@
 use chrono::{DateTime, Local, TimeZone};
 use git2::{DiffOptions, Repository, Sort};
 use std::io::Write;
+use std::path::Path;
 
 use crate::config::FileConfig;
 use crate::error::{GcopError, Result};
-use crate::git::{CommitInfo, DiffStats, GitOperations};
+use crate::git::commit::{CommitSigning, CommitStdin};
+use crate::git::{CommitInfo, DiffStats, GitOperations, HistoryOptions};
 
 /// Default maximum file size (10MB)
 const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
+/// Default rename similarity threshold (same as `git diff -M`)
+const DEFAULT_RENAME_THRESHOLD: u16 = 50;
+
 /// `git2`-based repository implementation used by gcop-rs.
 pub struct GitRepository {
     pub(crate) repo: Repository,
     max_file_size: u64,
+    rename_threshold: Option<u16>,
+    submodule_log: bool,
+    signing: CommitSigning,
+    commit_stdin: CommitStdin,
 }
 
 impl GitRepository {
@@ -21,23 +30,187 @@ impl GitRepository {
     /// # Arguments
     /// * `file_config` - optional file configuration, None uses default value
     pub fn open(file_config: Option<&FileConfig>) -> Result<Self> {
-        let repo = Repository::discover(".")?;
+        let repo = crate::git::open_repository()?;
+        Ok(Self::from_repository(repo, file_config))
+    }
+
+    /// Open the git repository containing `path` (searching parent directories like git)
+    ///
+    /// # Arguments
+    /// * `path` - a directory inside the repository
+    /// * `file_config` - optional file configuration, None uses default value
+    pub fn open_at(path: &Path, file_config: Option<&FileConfig>) -> Result<Self> {
+        let repo = Repository::discover(path)?;
+        Ok(Self::from_repository(repo, file_config))
+    }
+
+    fn from_repository(repo: Repository, file_config: Option<&FileConfig>) -> Self {
         let max_file_size = file_config
             .map(|c| c.max_size)
             .unwrap_or(DEFAULT_MAX_FILE_SIZE);
-        Ok(Self {
+        let rename_threshold = file_config
+            .map(|c| c.rename_threshold())
+            .unwrap_or(Some(DEFAULT_RENAME_THRESHOLD));
+        let submodule_log = file_config.is_none_or(|c| c.submodule_log);
+        Self {
             repo,
             max_file_size,
-        })
+            rename_threshold,
+            submodule_log,
+            signing: CommitSigning::default(),
+            commit_stdin: CommitStdin::detect(),
+        }
+    }
+
+    /// Rewrite commit authors to their canonical identity from `.mailmap`
+    /// (and `mailmap.file` / `mailmap.blob`), like `git log --use-mailmap`
+    pub fn apply_mailmap(&self, commits: &mut [CommitInfo]) -> Result<()> {
+        let mailmap = self.repo.mailmap()?;
+        let time = git2::Time::new(0, 0);
+        for commit in commits {
+            // Identities git cannot represent (e.g. an empty name) are left as is
+            let Ok(signature) =
+                git2::Signature::new(&commit.author_name, &commit.author_email, &time)
+            else {
+                continue;
+            };
+            let Ok(resolved) = mailmap.resolve_signature(&signature) else {
+                continue;
+            };
+            if let (Some(name), Some(email)) = (resolved.name(), resolved.email()) {
+                commit.author_name = name.to_string();
+                commit.author_email = email.to_string();
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the signing behavior used by `commit` and `commit_amend`
+    pub fn with_signing(mut self, signing: CommitSigning) -> Self {
+        self.signing = signing;
+        self
+    }
+
+    /// Set the stdin of `git commit`, its hooks and the signer (default:
+    /// inherited when stdin is a terminal)
+    pub fn with_commit_stdin(mut self, stdin: CommitStdin) -> Self {
+        self.commit_stdin = stdin;
+        self
+    }
+
+    /// Path to the repository's `.git` directory (or the worktree's git dir)
+    pub fn git_dir(&self) -> &Path {
+        self.repo.path()
+    }
+
+    /// Hash of the tree recorded by the index (`git write-tree`)
+    pub fn staged_tree(&self) -> Result<String> {
+        Ok(self.repo.index()?.write_tree()?.to_string())
+    }
+
+    /// Resolve a revision to a commit (`InvalidInput` if it names none)
+    fn find_commit(&self, rev: &str) -> Result<git2::Commit<'_>> {
+        self.repo
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|_| {
+                GcopError::InvalidInput(
+                    rust_i18n::t!("git.invalid_commit_hash", hash = rev).to_string(),
+                )
+            })
+    }
+
+    /// Push a revision or revision range (`a..b`, `a...b`) onto a revwalk
+    fn push_revspec(&self, revwalk: &mut git2::Revwalk<'_>, spec: &str) -> Result<()> {
+        let invalid = || {
+            GcopError::InvalidInput(rust_i18n::t!("git.invalid_revision", spec = spec).to_string())
+        };
+        let revspec = self.repo.revparse(spec).map_err(|_| invalid())?;
+        let peel = |obj: Option<&git2::Object<'_>>| -> Result<Option<git2::Oid>> {
+            obj.map(|o| o.peel_to_commit().map(|c| c.id()))
+                .transpose()
+                .map_err(|_| invalid())
+        };
+        let from = peel(revspec.from())?;
+        let to = peel(revspec.to())?;
+        let mode = revspec.mode();
+
+        if mode.contains(git2::RevparseMode::SINGLE) {
+            if let Some(from) = from {
+                revwalk.push(from)?;
+            }
+            return Ok(());
+        }
+
+        if let Some(to) = to {
+            revwalk.push(to)?;
+        }
+        match (from, to) {
+            // `a...b`: commits reachable from either side but not from both
+            (Some(from), Some(to)) if mode.contains(git2::RevparseMode::MERGE_BASE) => {
+                revwalk.push(from)?;
+                revwalk.hide(self.repo.merge_base(from, to)?)?;
+            }
+            (Some(from), _) => revwalk.hide(from)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Whether a commit changes files under `path` (compared with its first parent)
+    fn commit_touches_path(&self, commit: &git2::Commit<'_>, path: &str) -> Result<bool> {
+        let commit_tree = commit.tree()?;
+        let parent_tree = if commit.parent_count() > 0 {
+            Some(commit.parent(0)?.tree()?)
+        } else {
+            None
+        };
+
+        let mut opts = DiffOptions::new();
+        opts.pathspec(path);
+        let diff = self.repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit_tree),
+            Some(&mut opts),
+        )?;
+        Ok(diff.deltas().len() > 0)
+    }
+
+    /// Collapse delete + add pairs into renames (like `git diff -M`)
+    ///
+    /// No-op when rename detection is disabled in `[file]`.
+    fn find_renames(&self, diff: &mut git2::Diff<'_>) -> Result<()> {
+        if let Some(threshold) = self.rename_threshold {
+            let mut opts = git2::DiffFindOptions::new();
+            opts.renames(true).rename_threshold(threshold);
+            diff.find_similar(Some(&mut opts))?;
+        }
+        Ok(())
     }
 
     /// Convert git2::Diff to string
+    ///
+    /// Binary deltas are replaced with a structured summary
+    /// (see [`crate::git::diff::summarize_binary`]).
     fn diff_to_string(&self, diff: &git2::Diff) -> Result<String> {
         let mut output = Vec::new();
-        diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+        diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
             // Get the type tag (origin) of the row
             let origin = line.origin();
 
+            if origin == 'B' {
+                let _ = writeln!(output, "{}", self.binary_summary(&delta));
+                return true;
+            }
+
+            // Submodule log goes after the last `Subproject commit` line of the delta
+            let submodule_log = self.submodule_log
+                && is_submodule(&delta)
+                && match delta.status() {
+                    git2::Delta::Deleted => origin == '-',
+                    _ => origin == '+',
+                };
+
             // If origin is a printable character (+, -, space, etc.), write it first
             match origin {
                 '+' | '-' | ' ' => {
@@ -48,12 +221,166 @@ impl GitRepository {
 
             // Then write the row content
             let _ = output.write_all(line.content());
+            if submodule_log {
+                let _ = writeln!(output, "{}", self.submodule_log_for(&delta).render());
+            }
             true
         })?;
         Ok(String::from_utf8_lossy(&output).to_string())
     }
 }
 
+impl GitRepository {
+    /// Summary line for a binary delta, reading both sides' size and header.
+    fn binary_summary(&self, delta: &git2::DiffDelta<'_>) -> String {
+        use crate::git::diff::BinaryVersion;
+
+        let old = match delta.status() {
+            git2::Delta::Added | git2::Delta::Untracked => None,
+            _ => self.binary_side(&delta.old_file()),
+        };
+        let new = match delta.status() {
+            git2::Delta::Deleted => None,
+            _ => self.binary_side(&delta.new_file()),
+        };
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let old = old.as_ref().map(|(size, header)| BinaryVersion {
+            size: *size,
+            header,
+        });
+        let new = new.as_ref().map(|(size, header)| BinaryVersion {
+            size: *size,
+            header,
+        });
+        crate::git::diff::summarize_binary(&path, old, new)
+    }
+
+    /// Commit log between the old and new commits of a submodule delta
+    fn submodule_log_for(&self, delta: &git2::DiffDelta<'_>) -> crate::git::diff::SubmoduleLog {
+        let side = |file: git2::DiffFile<'_>| (!file.id().is_zero()).then(|| file.id());
+        let old = side(delta.old_file()).filter(|_| delta.status() != git2::Delta::Added);
+        let new = side(delta.new_file()).filter(|_| delta.status() != git2::Delta::Deleted);
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let mut log = crate::git::diff::SubmoduleLog {
+            old: old.map(|id| id.to_string()),
+            new: new.map(|id| id.to_string()),
+            ..Default::default()
+        };
+        if let (Some(old), Some(new)) = (old, new)
+            && let Some(submodule) = self
+                .repo
+                .workdir()
+                .and_then(|workdir| Repository::open(workdir.join(&path)).ok())
+        {
+            let walk = |from, hide| submodule_subjects(&submodule, from, hide);
+            if let (Some(added), Some(removed)) = (walk(new, old), walk(old, new)) {
+                log.added = Some(added);
+                log.removed = removed;
+            }
+        }
+        log.path = path;
+        log
+    }
+
+    /// Size and leading bytes of one side of a delta (blob, or working tree file).
+    fn binary_side(&self, file: &git2::DiffFile<'_>) -> Option<(u64, Vec<u8>)> {
+        use std::io::Read;
+
+        let limit = crate::git::diff::BINARY_HEADER_LIMIT;
+        if !file.id().is_zero()
+            && let Ok(blob) = self.repo.find_blob(file.id())
+        {
+            let content = blob.content();
+            return Some((
+                content.len() as u64,
+                content[..content.len().min(limit)].to_vec(),
+            ));
+        }
+
+        // Working tree side: the blob is not in the object database
+        let path = self.repo.workdir()?.join(file.path()?);
+        let handle = std::fs::File::open(path).ok()?;
+        let size = handle.metadata().ok()?.len();
+        let mut header = Vec::new();
+        handle.take(limit as u64).read_to_end(&mut header).ok()?;
+        Some((size, header))
+    }
+}
+
+/// Whether either side of a delta is a gitlink (submodule commit pointer)
+fn is_submodule(delta: &git2::DiffDelta<'_>) -> bool {
+    delta.new_file().mode() == git2::FileMode::Commit
+        || delta.old_file().mode() == git2::FileMode::Commit
+}
+
+/// Subjects of the commits reachable from `from` but not from `hide` (newest first)
+///
+/// `None` when either commit is missing from the submodule (not fetched).
+fn submodule_subjects(repo: &Repository, from: git2::Oid, hide: git2::Oid) -> Option<Vec<String>> {
+    let mut revwalk = repo.revwalk().ok()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME).ok()?;
+    revwalk.push(from).ok()?;
+    revwalk.hide(hide).ok()?;
+    revwalk
+        .map(|oid| {
+            let commit = repo.find_commit(oid.ok()?).ok()?;
+            Some(commit.summary().unwrap_or_default().to_string())
+        })
+        .collect()
+}
+
+/// Summary of a commit: hash, author, local timestamp and subject line
+fn commit_info(commit: &git2::Commit<'_>) -> CommitInfo {
+    let hash = commit.id().to_string();
+    let parent_count = commit.parent_count();
+    let author = commit.author();
+    let author_name = author.name().unwrap_or("Unknown").to_string();
+    let author_email = author.email().unwrap_or("").to_string();
+
+    // Convert git2::Time to chrono::DateTime<Local>
+    let git_time = commit.time();
+    let timestamp: DateTime<Local> = Local
+        .timestamp_opt(git_time.seconds(), 0)
+        .single()
+        .unwrap_or_else(|| {
+            tracing::warn!(
+                "Invalid git timestamp {} for commit {}",
+                git_time.seconds(),
+                commit.id()
+            );
+            Local::now()
+        });
+
+    let message = commit
+        .message()
+        .unwrap_or("")
+        .lines()
+        .next()
+        .unwrap_or("")
+        .to_string();
+
+    CommitInfo {
+        hash,
+        parent_count,
+        author_name,
+        author_email,
+        timestamp,
+        message,
+    }
+}
+
 impl GitOperations for GitRepository {
     fn get_staged_diff(&self) -> Result<String> {
         // Read index.
@@ -62,9 +389,10 @@ impl GitOperations for GitRepository {
         // For an empty repository, compare empty tree (None) against the index.
         if self.is_empty()? {
             let mut opts = DiffOptions::new();
-            let diff = self
+            let mut diff = self
                 .repo
                 .diff_tree_to_index(None, Some(&index), Some(&mut opts))?;
+            self.find_renames(&mut diff)?;
             return self.diff_to_string(&diff);
         }
 
@@ -74,9 +402,10 @@ impl GitOperations for GitRepository {
 
         // Create diff (HEAD tree vs index)
         let mut opts = DiffOptions::new();
-        let diff = self
-            .repo
-            .diff_tree_to_index(Some(&head_tree), Some(&index), Some(&mut opts))?;
+        let mut diff =
+            self.repo
+                .diff_tree_to_index(Some(&head_tree), Some(&index), Some(&mut opts))?;
+        self.find_renames(&mut diff)?;
 
         self.diff_to_string(&diff)
     }
@@ -94,6 +423,46 @@ impl GitOperations for GitRepository {
         self.diff_to_string(&diff)
     }
 
+    fn get_snapshot_diff(&self, tree: &str) -> Result<String> {
+        let tree = git2::Oid::from_str(tree).and_then(|oid| self.repo.find_tree(oid))?;
+
+        // The index adds files staged since the snapshot
+        let mut opts = DiffOptions::new();
+        let mut diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(Some(&tree), Some(&mut opts))?;
+        self.find_renames(&mut diff)?;
+
+        self.diff_to_string(&diff)
+    }
+
+    fn snapshot_workdir(&self) -> Result<String> {
+        use std::process::Command;
+
+        let workdir = self.get_workdir()?;
+        let temp = tempfile::tempdir()?;
+        let index_file = temp.path().join("index");
+        let index = self.repo.path().join("index");
+        if index.exists() {
+            std::fs::copy(&index, &index_file)?;
+        }
+
+        let git = |args: &[&str]| -> Result<String> {
+            let output = Command::new("git")
+                .current_dir(&workdir)
+                .env("GIT_INDEX_FILE", &index_file)
+                .args(args)
+                .output()?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(GcopError::GitCommand(stderr.trim().to_string()));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        git(&["add", "-u", "--", "."])?;
+        git(&["write-tree"])
+    }
+
     fn get_commit_diff(&self, commit_hash: &str) -> Result<String> {
         // Find commit — accept both hex SHA and refs (e.g. "HEAD")
         let commit = self
@@ -117,38 +486,85 @@ impl GitOperations for GitRepository {
 
         // Build diff.
         let mut opts = DiffOptions::new();
-        let diff = self.repo.diff_tree_to_tree(
+        let mut diff = self.repo.diff_tree_to_tree(
             parent_tree.as_ref(),
             Some(&commit_tree),
             Some(&mut opts),
         )?;
+        self.find_renames(&mut diff)?;
 
         self.diff_to_string(&diff)
     }
 
     fn get_range_diff(&self, range: &str) -> Result<String> {
-        // Parse range expression (for example "main..feature").
-        let parts: Vec<&str> = range.split("..").collect();
-        if parts.len() != 2 {
+        // Parse range expression: "main..feature" compares the two trees,
+        // "main...feature" compares feature with its merge-base with main.
+        let (base, head, merge_base) = match range.split_once("...") {
+            Some((base, head)) => (base, head, true),
+            None => match range.split_once("..") {
+                Some((base, head)) => (base, head, false),
+                None => {
+                    return Err(GcopError::InvalidInput(
+                        rust_i18n::t!("git.invalid_range_format", range = range).to_string(),
+                    ));
+                }
+            },
+        };
+        if base.contains("..") || head.contains("..") {
             return Err(GcopError::InvalidInput(
                 rust_i18n::t!("git.invalid_range_format", range = range).to_string(),
             ));
         }
 
-        let base_commit = self.repo.revparse_single(parts[0])?.peel_to_commit()?;
-        let head_commit = self.repo.revparse_single(parts[1])?.peel_to_commit()?;
+        // An omitted side means HEAD, as in git
+        let resolve = |spec: &str| {
+            let spec = if spec.is_empty() { "HEAD" } else { spec };
+            self.repo
+                .revparse_single(spec)
+                .and_then(|obj| obj.peel_to_commit())
+        };
+        let head_commit = resolve(head)?;
+        let base_commit = resolve(base)?;
+        let base_commit = if merge_base {
+            let oid = self.repo.merge_base(base_commit.id(), head_commit.id())?;
+            self.repo.find_commit(oid)?
+        } else {
+            base_commit
+        };
 
         let base_tree = base_commit.tree()?;
         let head_tree = head_commit.tree()?;
 
         let mut opts = DiffOptions::new();
-        let diff =
+        let mut diff =
             self.repo
                 .diff_tree_to_tree(Some(&base_tree), Some(&head_tree), Some(&mut opts))?;
+        self.find_renames(&mut diff)?;
 
         self.diff_to_string(&diff)
     }
 
+    fn default_branch(&self) -> Result<Option<String>> {
+        // `origin/HEAD` is set by clone and `git remote set-head`
+        if let Ok(reference) = self.repo.find_reference("refs/remotes/origin/HEAD")
+            && let Some(target) = reference.symbolic_target()
+            && let Some(name) = target.strip_prefix("refs/remotes/")
+        {
+            return Ok(Some(name.to_string()));
+        }
+        for (name, kind) in [
+            ("main", git2::BranchType::Local),
+            ("master", git2::BranchType::Local),
+            ("origin/main", git2::BranchType::Remote),
+            ("origin/master", git2::BranchType::Remote),
+        ] {
+            if self.repo.find_branch(name, kind).is_ok() {
+                return Ok(Some(name.to_string()));
+            }
+        }
+        Ok(None)
+    }
+
     fn get_file_content(&self, path: &str) -> Result<String> {
         let metadata = std::fs::metadata(path)?;
         if metadata.len() > self.max_file_size {
@@ -167,11 +583,11 @@ impl GitOperations for GitRepository {
     }
 
     fn commit(&self, message: &str) -> Result<()> {
-        crate::git::commit::commit_changes(message)
+        crate::git::commit::commit_changes(message, self.signing, self.commit_stdin)
     }
 
     fn commit_amend(&self, message: &str) -> Result<()> {
-        crate::git::commit::commit_amend_changes(message)
+        crate::git::commit::commit_amend_changes(message, self.signing, self.commit_stdin)
     }
 
     fn get_current_branch(&self) -> Result<Option<String>> {
@@ -201,14 +617,17 @@ impl GitOperations for GitRepository {
         Ok(!diff.trim().is_empty())
     }
 
-    fn get_commit_history(&self) -> Result<Vec<CommitInfo>> {
+    fn get_commit_history(&self, options: &HistoryOptions) -> Result<Vec<CommitInfo>> {
         // Empty repository has no history.
         if self.is_empty()? {
             return Ok(Vec::new());
         }
 
         let mut revwalk = self.repo.revwalk()?;
-        revwalk.push_head()?;
+        match options.range.as_deref() {
+            Some(range) => self.push_revspec(&mut revwalk, range)?,
+            None => revwalk.push_head()?,
+        }
         revwalk.set_sorting(Sort::TIME)?;
 
         let mut commits = Vec::new();
@@ -217,42 +636,57 @@ impl GitOperations for GitRepository {
             let oid = oid?;
             let commit = self.repo.find_commit(oid)?;
 
-            let hash = oid.to_string();
-            let parent_count = commit.parent_count();
-            let author = commit.author();
-            let author_name = author.name().unwrap_or("Unknown").to_string();
-            let author_email = author.email().unwrap_or("").to_string();
-
-            // Convert git2::Time to chrono::DateTime<Local>
-            let git_time = commit.time();
-            let timestamp: DateTime<Local> = Local
-                .timestamp_opt(git_time.seconds(), 0)
-                .single()
-                .unwrap_or_else(|| {
-                    tracing::warn!(
-                        "Invalid git timestamp {} for commit {}",
-                        git_time.seconds(),
-                        commit.id()
-                    );
-                    Local::now()
-                });
-
-            let message = commit
-                .message()
-                .unwrap_or("")
-                .lines()
-                .next()
-                .unwrap_or("")
-                .to_string();
-
-            commits.push(CommitInfo {
-                hash,
-                parent_count,
-                author_name,
-                author_email,
-                timestamp,
-                message,
-            });
+            if let Some(path) = options.path.as_deref()
+                && !self.commit_touches_path(&commit, path)?
+            {
+                continue;
+            }
+
+            let info = commit_info(&commit);
+
+            if !options.contains_time(&info.timestamp) {
+                continue;
+            }
+
+            commits.push(info);
+        }
+
+        Ok(commits)
+    }
+
+    fn get_commits_touching(&self, paths: &[String], max_scan: usize) -> Result<Vec<CommitInfo>> {
+        if paths.is_empty() || self.is_empty()? {
+            return Ok(Vec::new());
+        }
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(Sort::TIME)?;
+
+        let mut opts = DiffOptions::new();
+        opts.disable_pathspec_match(true);
+        for path in paths {
+            opts.pathspec(path);
+        }
+
+        let mut commits = Vec::new();
+        for oid in revwalk.take(max_scan) {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let parent_tree = match commit.parent_count() {
+                0 => None,
+                _ => Some(commit.parent(0)?.tree()?),
+            };
+            let diff = self.repo.diff_tree_to_tree(
+                parent_tree.as_ref(),
+                Some(&commit.tree()?),
+                Some(&mut opts),
+            )?;
+            if diff.deltas().len() > 0 {
+                commits.push(commit_info(&commit));
+            }
         }
 
         Ok(commits)
@@ -287,6 +721,61 @@ impl GitOperations for GitRepository {
         Ok((stats.insertions(), stats.deletions()))
     }
 
+    fn get_commit_info(&self, rev: &str) -> Result<CommitInfo> {
+        Ok(commit_info(&self.find_commit(rev)?))
+    }
+
+    fn get_commit_message(&self, rev: &str) -> Result<String> {
+        let commit = self.find_commit(rev)?;
+        Ok(String::from_utf8_lossy(commit.message_bytes()).into_owned())
+    }
+
+    fn is_ancestor(&self, ancestor: &str, descendant: &str) -> Result<bool> {
+        let ancestor = self.find_commit(ancestor)?.id();
+        let descendant = self.find_commit(descendant)?.id();
+        Ok(ancestor == descendant || self.repo.graph_descendant_of(descendant, ancestor)?)
+    }
+
+    fn reword_commit(&self, hash: &str, message: &str) -> Result<()> {
+        let commit = self.find_commit(hash)?;
+        if self.repo.head()?.peel_to_commit()?.id() == commit.id() {
+            return crate::git::reword::reword_head(message, self.signing, self.commit_stdin);
+        }
+
+        let hash = commit.id().to_string();
+        crate::git::reword::reword_commit(
+            &self.repo,
+            &hash,
+            commit.parent_count() == 0,
+            message,
+            self.signing,
+            self.commit_stdin,
+        )?;
+        // The rebase succeeds without changes if the todo entry was not rewritten
+        if self.is_ancestor(&hash, "HEAD")? {
+            return Err(GcopError::GitCommand(
+                rust_i18n::t!("git.reword_not_applied", hash = hash).to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn head_reflog_message(&self) -> Result<Option<String>> {
+        let head = self.repo.head()?.peel_to_commit()?.id();
+        let reflog = self.repo.reflog("HEAD")?;
+        Ok(reflog
+            .get(0)
+            .filter(|entry| entry.id_new() == head)
+            .and_then(|entry| entry.message().map(str::to_string)))
+    }
+
+    fn reset_soft(&self, rev: &str) -> Result<()> {
+        let target = self.find_commit(rev)?;
+        self.repo
+            .reset(target.as_object(), git2::ResetType::Soft, None)?;
+        Ok(())
+    }
+
     fn is_empty(&self) -> Result<bool> {
         // Detect unborn branch: if `head()` fails with `UnbornBranch`, the repository is empty.
         match self.repo.head() {
@@ -328,7 +817,7 @@ impl GitOperations for GitRepository {
             // Empty repo: no HEAD to reset to, use git rm --cached
             let output = Command::new("git")
                 .current_dir(workdir)
-                .args(["rm", "--cached", "-r", "."])
+                .args(["rm", "--cached", "-r", "-q", "--", "."])
                 .output()?;
             if !output.status.success() {
                 let stderr = String::from_utf8_lossy(&output.stderr);
@@ -339,7 +828,7 @@ impl GitOperations for GitRepository {
         } else {
             let output = Command::new("git")
                 .current_dir(workdir)
-                .args(["reset", "HEAD"])
+                .args(["reset", "-q", "HEAD", "--"])
                 .output()?;
             if !output.status.success() {
                 let stderr = String::from_utf8_lossy(&output.stderr);
@@ -352,7 +841,8 @@ impl GitOperations for GitRepository {
     }
 
     fn stage_files(&self, files: &[String]) -> Result<()> {
-        use std::process::Command;
+        use std::io::Write;
+        use std::process::{Command, Stdio};
 
         if files.is_empty() {
             return Ok(());
@@ -360,11 +850,46 @@ impl GitOperations for GitRepository {
 
         let workdir = self.get_workdir()?;
 
-        let output = Command::new("git")
+        // Paths go through stdin, NUL-separated: no command-line length limit
+        // (about 32K on Windows) and no quoting of spaces or leading dashes
+        let mut child = Command::new("git")
             .current_dir(workdir)
             .env("GIT_LITERAL_PATHSPECS", "1")
-            .arg("add")
-            .args(files)
+            .args(["add", "--pathspec-from-file=-", "--pathspec-file-nul"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            for file in files {
+                stdin.write_all(file.as_bytes())?;
+                stdin.write_all(b"\0")?;
+            }
+        }
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(crate::error::GcopError::GitCommand(
+                stderr.trim().to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn checkout_index(&self, dir: &Path) -> Result<()> {
+        use std::process::Command;
+
+        let workdir = self.get_workdir()?;
+        let mut prefix = dir.as_os_str().to_owned();
+        prefix.push(std::path::MAIN_SEPARATOR_STR);
+
+        let mut arg = std::ffi::OsString::from("--prefix=");
+        arg.push(prefix);
+        let output = Command::new("git")
+            .current_dir(workdir)
+            .args(["checkout-index", "--all"])
+            .arg(arg)
             .output()?;
 
         if !output.status.success() {
@@ -376,12 +901,120 @@ impl GitOperations for GitRepository {
         Ok(())
     }
 
+    fn stage_tracked(&self) -> Result<()> {
+        use std::process::Command;
+
+        let workdir = self.get_workdir()?;
+
+        let output = Command::new("git")
+            .current_dir(workdir)
+            .args(["add", "-u", "--", "."])
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(crate::error::GcopError::GitCommand(
+                stderr.trim().to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn apply_patch(&self, patch: &str, check_only: bool) -> Result<()> {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let workdir = self.get_workdir()?;
+
+        let mut cmd = Command::new("git");
+        cmd.current_dir(workdir).arg("apply");
+        if check_only {
+            cmd.arg("--check");
+        }
+        let mut child = cmd
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(patch.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(crate::error::GcopError::GitCommand(
+                stderr.trim().to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn get_remote_url(&self, name: &str) -> Result<Option<String>> {
+        match self.repo.find_remote(name) {
+            Ok(remote) => Ok(remote.url().map(str::to_string)),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     fn get_workdir(&self) -> Result<std::path::PathBuf> {
         self.repo
             .workdir()
             .ok_or_else(|| crate::error::GcopError::GitCommand("bare repository".to_string()))
             .map(|p| p.to_path_buf())
     }
+
+    fn get_git_dir(&self) -> Result<std::path::PathBuf> {
+        Ok(self.repo.path().to_path_buf())
+    }
+
+    fn get_user_identity(&self) -> Result<Option<String>> {
+        // `signature()` fails with NotFound when user.name/user.email are unset
+        match self.repo.signature() {
+            Ok(sig) => match (sig.name(), sig.email()) {
+                (Some(name), Some(email)) => Ok(Some(format!("{} <{}>", name, email))),
+                _ => Ok(None),
+            },
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn get_latest_tag(&self) -> Result<Option<String>> {
+        if self.is_empty()? {
+            return Ok(None);
+        }
+        let mut options = git2::DescribeOptions::new();
+        options.describe_tags();
+        match self.repo.describe(&options) {
+            Ok(describe) => {
+                let mut format = git2::DescribeFormatOptions::new();
+                format.abbreviated_size(0);
+                Ok(Some(describe.format(Some(&format))?))
+            }
+            // No tag can describe HEAD
+            Err(e) if e.class() == git2::ErrorClass::Describe => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn tag_exists(&self, name: &str) -> Result<bool> {
+        match self.repo.find_reference(&format!("refs/tags/{}", name)) {
+            Ok(_) => Ok(true),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn create_tag(&self, name: &str, message: &str) -> Result<()> {
+        crate::git::tag::create_annotated_tag(name, message)
+    }
+
+    fn push_tag(&self, remote: &str, name: &str) -> Result<()> {
+        crate::git::tag::push_tag(remote, name)
+    }
 }
 
 #[cfg(test)]
@@ -404,6 +1037,10 @@ mod tests {
         let git_repo = GitRepository {
             repo,
             max_file_size: DEFAULT_MAX_FILE_SIZE,
+            rename_threshold: Some(DEFAULT_RENAME_THRESHOLD),
+            submodule_log: true,
+            signing: CommitSigning::default(),
+            commit_stdin: CommitStdin::Null,
         };
 
         (dir, git_repo)
@@ -466,6 +1103,43 @@ mod tests {
         assert_eq!(git_repo.get_current_branch().unwrap(), None);
     }
 
+    #[test]
+    fn test_get_user_identity() {
+        let (_dir, git_repo) = create_test_repo();
+        assert_eq!(
+            git_repo.get_user_identity().unwrap().as_deref(),
+            Some("Test User <test@example.com>")
+        );
+    }
+
+    #[test]
+    fn test_get_latest_tag() {
+        let (dir, git_repo) = create_test_repo();
+        assert_eq!(git_repo.get_latest_tag().unwrap(), None);
+
+        create_file(dir.path(), "a.txt", "a");
+        stage_file(&git_repo.repo, "a.txt");
+        create_commit(&git_repo.repo, "feat: first");
+        assert_eq!(git_repo.get_latest_tag().unwrap(), None);
+
+        let head = git_repo.repo.head().unwrap().peel_to_commit().unwrap();
+        let sig = git_repo.repo.signature().unwrap();
+        git_repo
+            .repo
+            .tag("v0.1.0", head.as_object(), &sig, "v0.1.0", false)
+            .unwrap();
+        create_file(dir.path(), "b.txt", "b");
+        stage_file(&git_repo.repo, "b.txt");
+        create_commit(&git_repo.repo, "feat: second");
+
+        assert_eq!(
+            git_repo.get_latest_tag().unwrap().as_deref(),
+            Some("v0.1.0")
+        );
+        assert!(git_repo.tag_exists("v0.1.0").unwrap());
+        assert!(!git_repo.tag_exists("v0.2.0").unwrap());
+    }
+
     #[test]
     fn test_get_current_branch_normal() {
         let (dir, git_repo) = create_test_repo();
@@ -680,7 +1354,9 @@ mod tests {
     #[test]
     fn test_get_commit_history_empty_repo() {
         let (_dir, git_repo) = create_test_repo();
-        let commits = git_repo.get_commit_history().unwrap();
+        let commits = git_repo
+            .get_commit_history(&HistoryOptions::default())
+            .unwrap();
         assert!(commits.is_empty());
     }
 
@@ -696,7 +1372,9 @@ mod tests {
         stage_file(&git_repo.repo, "test.txt");
         create_commit(&git_repo.repo, "Second commit");
 
-        let commits = git_repo.get_commit_history().unwrap();
+        let commits = git_repo
+            .get_commit_history(&HistoryOptions::default())
+            .unwrap();
         assert_eq!(commits.len(), 2);
         assert_eq!(commits[0].message, "Second commit");
         assert_eq!(commits[1].message, "First commit");
@@ -704,6 +1382,97 @@ mod tests {
         assert_eq!(commits[0].author_email, "test@example.com");
     }
 
+    /// Create three commits: `a.txt`, `docs/b.md` (tagged `v1`), `a.txt` again
+    fn create_scoped_history() -> (TempDir, GitRepository) {
+        let (dir, git_repo) = create_test_repo();
+
+        create_file(dir.path(), "a.txt", "v1");
+        stage_file(&git_repo.repo, "a.txt");
+        create_commit(&git_repo.repo, "First commit");
+
+        fs::create_dir_all(dir.path().join("docs")).unwrap();
+        create_file(dir.path(), "docs/b.md", "docs");
+        stage_file(&git_repo.repo, "docs/b.md");
+        create_commit(&git_repo.repo, "Docs commit");
+        let head = git_repo.repo.head().unwrap().peel(git2::ObjectType::Commit);
+        git_repo
+            .repo
+            .tag_lightweight("v1", &head.unwrap(), false)
+            .unwrap();
+
+        create_file(dir.path(), "a.txt", "v2");
+        stage_file(&git_repo.repo, "a.txt");
+        create_commit(&git_repo.repo, "Third commit");
+
+        (dir, git_repo)
+    }
+
+    fn messages(commits: &[CommitInfo]) -> Vec<&str> {
+        commits.iter().map(|c| c.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_get_commit_history_with_range() {
+        let (_dir, git_repo) = create_scoped_history();
+
+        let options = HistoryOptions {
+            range: Some("v1..HEAD".to_string()),
+            ..Default::default()
+        };
+        let commits = git_repo.get_commit_history(&options).unwrap();
+        assert_eq!(messages(&commits), vec!["Third commit"]);
+
+        let options = HistoryOptions {
+            range: Some("v1".to_string()),
+            ..Default::default()
+        };
+        let commits = git_repo.get_commit_history(&options).unwrap();
+        assert_eq!(messages(&commits), vec!["Docs commit", "First commit"]);
+    }
+
+    #[test]
+    fn test_get_commit_history_invalid_range() {
+        let (_dir, git_repo) = create_scoped_history();
+        let options = HistoryOptions {
+            range: Some("nope..HEAD".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            git_repo.get_commit_history(&options),
+            Err(GcopError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_commit_history_with_path() {
+        let (_dir, git_repo) = create_scoped_history();
+
+        let options = HistoryOptions {
+            path: Some("docs".to_string()),
+            ..Default::default()
+        };
+        let commits = git_repo.get_commit_history(&options).unwrap();
+        assert_eq!(messages(&commits), vec!["Docs commit"]);
+    }
+
+    #[test]
+    fn test_get_commit_history_with_time_window() {
+        let (_dir, git_repo) = create_scoped_history();
+
+        let future = HistoryOptions {
+            since: Some(Local::now() + chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(git_repo.get_commit_history(&future).unwrap().is_empty());
+
+        let recent = HistoryOptions {
+            since: Some(Local::now() - chrono::Duration::days(1)),
+            until: Some(Local::now() + chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert_eq!(git_repo.get_commit_history(&recent).unwrap().len(), 3);
+    }
+
     // === Test get_diff_stats ===
 
     #[test]
@@ -773,6 +1542,31 @@ index 1234567..abcdefg 100644
         );
     }
 
+    #[test]
+    fn test_stage_files_spaces_and_leading_dash() {
+        // Paths are passed through stdin, so spaces, non-ASCII characters and
+        // a leading `-` are neither split nor parsed as options.
+        let (dir, git_repo) = create_test_repo();
+        create_file(dir.path(), "init.txt", "init");
+        stage_file(&git_repo.repo, "init.txt");
+        create_commit(&git_repo.repo, "initial");
+
+        let files = [
+            "my notes.txt".to_string(),
+            "-n".to_string(),
+            "文档.md".to_string(),
+        ];
+        for file in &files {
+            create_file(dir.path(), file, "content");
+        }
+        git_repo.stage_files(&files).unwrap();
+
+        let staged = git_repo.get_staged_files().unwrap();
+        for file in &files {
+            assert!(staged.contains(file), "expected {} to be staged", file);
+        }
+    }
+
     #[test]
     fn test_stage_files_glob_path_missing_literal_errors_not_sibling() {
         // When the literal `[locale]/page.tsx` does NOT exist but a sibling `l/page.tsx` does,
@@ -850,4 +1644,149 @@ index 1234567..abcdefg 100644
             "c.rs should NOT be staged (was never in the staging area)"
         );
     }
+
+    #[test]
+    fn test_stage_tracked_skips_untracked_files() {
+        let (dir, git_repo) = create_test_repo();
+
+        create_file(dir.path(), "a.rs", "v1");
+        create_file(dir.path(), "b.rs", "v1");
+        stage_file(&git_repo.repo, "a.rs");
+        stage_file(&git_repo.repo, "b.rs");
+        create_commit(&git_repo.repo, "initial");
+
+        create_file(dir.path(), "a.rs", "v2");
+        fs::remove_file(dir.path().join("b.rs")).unwrap();
+        create_file(dir.path(), "new.rs", "untracked");
+        assert!(!git_repo.has_staged_changes().unwrap());
+
+        git_repo.stage_tracked().unwrap();
+
+        let staged = git_repo.get_staged_files().unwrap();
+        assert!(staged.contains(&"a.rs".to_string()));
+        assert!(staged.contains(&"b.rs".to_string()));
+        assert!(!staged.contains(&"new.rs".to_string()));
+    }
+
+    #[test]
+    fn test_staged_diff_summarizes_binary_files() {
+        let (dir, git_repo) = create_test_repo();
+
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&[0, 0, 0, 32, 0, 0, 0, 16, 8, 6, 0, 0, 0]);
+        fs::write(dir.path().join("icon.png"), &png).unwrap();
+        stage_file(&git_repo.repo, "icon.png");
+
+        let diff = git_repo.get_staged_diff().unwrap();
+        assert!(
+            diff.contains("Binary file added: icon.png (PNG image, 29 B, 32x16)"),
+            "unexpected diff: {diff}"
+        );
+        assert!(!diff.contains("Binary files"));
+        assert_eq!(
+            git_repo.get_diff_stats(&diff).unwrap().files_changed,
+            vec!["icon.png"]
+        );
+    }
+
+    #[test]
+    fn test_staged_diff_detects_renames() {
+        let (dir, mut git_repo) = create_test_repo();
+
+        let content: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+        create_file(dir.path(), "old.rs", &content);
+        stage_file(&git_repo.repo, "old.rs");
+        create_commit(&git_repo.repo, "initial");
+
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::rename(dir.path().join("old.rs"), dir.path().join("src/new.rs")).unwrap();
+        let mut index = git_repo.repo.index().unwrap();
+        index.remove_path(Path::new("old.rs")).unwrap();
+        index.add_path(Path::new("src/new.rs")).unwrap();
+        index.write().unwrap();
+
+        let diff = git_repo.get_staged_diff().unwrap();
+        assert!(
+            diff.contains("rename from old.rs"),
+            "unexpected diff: {diff}"
+        );
+        assert!(diff.contains("rename to src/new.rs"));
+        assert!(!diff.contains("+line 1\n"));
+        let stats = git_repo.get_diff_stats(&diff).unwrap();
+        assert_eq!(stats.files_changed, vec!["src/new.rs"]);
+
+        // 关闭重命名检测后回到删除 + 新增
+        git_repo.rename_threshold = None;
+        let diff = git_repo.get_staged_diff().unwrap();
+        assert!(!diff.contains("rename from"));
+        assert_eq!(
+            git_repo.get_diff_stats(&diff).unwrap().files_changed.len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_staged_diff_includes_submodule_log() {
+        let (dir, mut git_repo) = create_test_repo();
+
+        // 子模块仓库：两次提交
+        let sub_path = dir.path().join("libs/foo");
+        fs::create_dir_all(&sub_path).unwrap();
+        let sub = Repository::init(&sub_path).unwrap();
+        let mut sub_config = sub.config().unwrap();
+        sub_config.set_str("user.name", "Test User").unwrap();
+        sub_config
+            .set_str("user.email", "test@example.com")
+            .unwrap();
+        create_file(&sub_path, "a.txt", "a");
+        stage_file(&sub, "a.txt");
+        create_commit(&sub, "feat: first");
+        let first = sub.head().unwrap().target().unwrap();
+        create_file(&sub_path, "b.txt", "b");
+        stage_file(&sub, "b.txt");
+        create_commit(&sub, "fix: second");
+        let second = sub.head().unwrap().target().unwrap();
+
+        let set_gitlink = |id: git2::Oid| {
+            let mut index = git_repo.repo.index().unwrap();
+            index
+                .add(&git2::IndexEntry {
+                    ctime: git2::IndexTime::new(0, 0),
+                    mtime: git2::IndexTime::new(0, 0),
+                    dev: 0,
+                    ino: 0,
+                    mode: 0o160000,
+                    uid: 0,
+                    gid: 0,
+                    file_size: 0,
+                    id,
+                    flags: 0,
+                    flags_extended: 0,
+                    path: b"libs/foo".to_vec(),
+                })
+                .unwrap();
+            index.write().unwrap();
+        };
+        set_gitlink(first);
+        create_commit(&git_repo.repo, "add submodule");
+        set_gitlink(second);
+
+        let diff = git_repo.get_staged_diff().unwrap();
+        let expected = format!(
+            "Submodule libs/foo {}..{} (1 new commit)\n  > fix: second",
+            &first.to_string()[..7],
+            &second.to_string()[..7]
+        );
+        assert!(diff.contains(&expected), "unexpected diff: {diff}");
+        let stats = git_repo.get_diff_stats(&diff).unwrap();
+        assert_eq!((stats.insertions, stats.deletions), (1, 1));
+
+        git_repo.submodule_log = false;
+        assert!(
+            !git_repo
+                .get_staged_diff()
+                .unwrap()
+                .contains("Submodule libs/foo")
+        );
+    }
 }
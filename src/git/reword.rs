@@ -0,0 +1,246 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use git2::RepositoryState;
+
+use super::commit::{CommitSigning, CommitStdin, run_git_commit};
+use crate::error::{GcopError, Result};
+
+/// Set while gcop-rs drives a reword rebase, so the `prepare-commit-msg` hook
+/// installed by gcop-rs does not generate a second message.
+pub const REWORD_VAR: &str = "GCOP_REWORD";
+
+/// Replace the message of `HEAD`
+///
+/// Uses `git commit --amend --only`, so staged changes stay staged and only
+/// the message changes. Hooks and signing apply as with `git commit`.
+///
+/// # Arguments
+/// * `message` - New commit message
+/// * `signing` - Signing behavior
+/// * `stdin` - Stdin of git, its hooks and the signer
+pub fn reword_head(message: &str, signing: CommitSigning, stdin: CommitStdin) -> Result<()> {
+    run_git_commit(&build_amend_args(message, signing), None, stdin)
+}
+
+/// Replace the message of an older commit with an automated `git rebase -i`
+///
+/// The sequence editor (the hidden `reword-todo` subcommand, see
+/// [`mark_for_reword`]) turns the target's `pick` into `reword` and the
+/// editor copies the prepared message over `COMMIT_EDITMSG`. Commits after
+/// the target are replayed unchanged (merges included). When the rebase
+/// stops, it is aborted so the branch is left as it was.
+///
+/// Refuses to run while another rebase, merge, cherry-pick etc. is in
+/// progress, so an abort never touches an operation the user started.
+///
+/// # Arguments
+/// * `repo` - Repository to reword in
+/// * `hash` - Full hash of the commit to reword (must not be a merge)
+/// * `root` - Whether the commit has no parent (`--root`)
+/// * `message` - New commit message
+/// * `signing` - Signing behavior for the rewritten commits
+pub fn reword_commit(
+    repo: &git2::Repository,
+    hash: &str,
+    root: bool,
+    message: &str,
+    signing: CommitSigning,
+    stdin: CommitStdin,
+) -> Result<()> {
+    if repo.state() != RepositoryState::Clean {
+        return Err(GcopError::GitCommand(
+            rust_i18n::t!("git.reword_operation_in_progress").to_string(),
+        ));
+    }
+
+    let mut message_file = tempfile::NamedTempFile::new()?;
+    message_file.write_all(message.as_bytes())?;
+    message_file.flush()?;
+    let message_path = message_file.path().to_string_lossy().to_string();
+
+    let upstream = format!("{}^", hash);
+    let output = Command::new("git")
+        .args(build_rebase_args(
+            if root { None } else { Some(&upstream) },
+            signing,
+        ))
+        .env("GIT_SEQUENCE_EDITOR", sequence_editor(hash)?)
+        .env("GIT_EDITOR", format!("cp {}", shell_quote(&message_path)))
+        .env(REWORD_VAR, "1")
+        .stdin(stdin.stdio())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error_msg = if stderr.trim().is_empty() {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        } else {
+            stderr.trim().to_string()
+        };
+        // The state was clean before, so a rebase in progress is ours
+        if repo.state() != RepositoryState::Clean {
+            let _ = Command::new("git")
+                .args(["rebase", "--abort"])
+                .stdin(Stdio::null())
+                .output();
+        }
+        return Err(GcopError::GitCommand(error_msg));
+    }
+
+    Ok(())
+}
+
+/// Build `git commit --amend` arguments for a message-only amend.
+fn build_amend_args(message: &str, signing: CommitSigning) -> Vec<&str> {
+    let mut args = vec!["commit", "--amend", "--only"];
+    if let Some(arg) = signing.git_arg() {
+        args.push(arg);
+    }
+    args.extend(["-m", message]);
+    args
+}
+
+/// Build `git rebase -i` arguments (pure function, easy to test).
+///
+/// Full hashes and unabbreviated commands in the todo list keep the sequence
+/// editor's match exact; `commit.cleanup=whitespace` keeps `#` lines of the
+/// new message; autosquash is disabled so no other commit moves.
+fn build_rebase_args(upstream: Option<&str>, signing: CommitSigning) -> Vec<&str> {
+    let mut args = vec![
+        "-c",
+        "core.abbrev=40",
+        "-c",
+        "rebase.abbreviateCommands=false",
+        "-c",
+        "commit.cleanup=whitespace",
+        "rebase",
+        "-i",
+        "--rebase-merges",
+        "--no-autosquash",
+    ];
+    if let Some(arg) = signing.git_arg() {
+        args.push(arg);
+    }
+    match upstream {
+        Some(upstream) => args.push(upstream),
+        None => args.push("--root"),
+    }
+    args
+}
+
+/// Sequence editor command: this executable's hidden `reword-todo` subcommand.
+fn sequence_editor(hash: &str) -> Result<String> {
+    let exe = std::env::current_exe()?;
+    Ok(format!(
+        "{} reword-todo {}",
+        shell_quote(&exe.to_string_lossy()),
+        hash
+    ))
+}
+
+/// Mark `hash` for `reword` in the rebase todo list at `todo_path`
+///
+/// Runs as `GIT_SEQUENCE_EDITOR` of [`reword_commit`]. Fails when the todo
+/// list has no `pick` line for `hash`, which makes git cancel the rebase.
+pub fn mark_for_reword(todo_path: &Path, hash: &str) -> Result<()> {
+    let todo = std::fs::read_to_string(todo_path)?;
+    let todo = mark_reword(&todo, hash).ok_or_else(|| {
+        GcopError::GitCommand(rust_i18n::t!("git.reword_not_applied", hash = hash).to_string())
+    })?;
+    std::fs::write(todo_path, todo)?;
+    Ok(())
+}
+
+/// Turn the `pick` line of `hash` into `reword` (pure function, easy to test).
+fn mark_reword(todo: &str, hash: &str) -> Option<String> {
+    let pick = format!("pick {} ", hash);
+    let mut found = false;
+    let marked = todo
+        .split_inclusive('\n')
+        .map(|line| match line.strip_prefix(&pick) {
+            Some(rest) if !found => {
+                found = true;
+                format!("reword {} {}", hash, rest)
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    found.then_some(marked)
+}
+
+/// Quote `value` for a POSIX shell (git runs editors through `sh`).
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_amend_args() {
+        assert_eq!(
+            build_amend_args("msg", CommitSigning::Auto),
+            vec!["commit", "--amend", "--only", "-m", "msg"]
+        );
+        assert_eq!(
+            build_amend_args("msg", CommitSigning::NoSign),
+            vec!["commit", "--amend", "--only", "--no-gpg-sign", "-m", "msg"]
+        );
+    }
+
+    #[test]
+    fn test_build_rebase_args() {
+        let args = build_rebase_args(Some("abc^"), CommitSigning::Sign);
+        assert_eq!(
+            &args[6..],
+            [
+                "rebase",
+                "-i",
+                "--rebase-merges",
+                "--no-autosquash",
+                "-S",
+                "abc^"
+            ]
+        );
+        // 根提交没有父提交可作为 upstream
+        let args = build_rebase_args(None, CommitSigning::Auto);
+        assert_eq!(args.last(), Some(&"--root"));
+    }
+
+    #[test]
+    fn test_mark_reword() {
+        let todo = "label onto\npick abc123 feat: a\npick def456 fix: b\n# pick abc123 x\n";
+        assert_eq!(
+            mark_reword(todo, "abc123").unwrap(),
+            "label onto\nreword abc123 feat: a\npick def456 fix: b\n# pick abc123 x\n"
+        );
+        // 目标提交不在待办列表中时不改写
+        assert_eq!(mark_reword(todo, "0000000"), None);
+    }
+
+    #[test]
+    fn test_mark_for_reword_rewrites_todo_file() {
+        let mut todo = tempfile::NamedTempFile::new().unwrap();
+        todo.write_all(b"pick abc123 feat: a\n").unwrap();
+        mark_for_reword(todo.path(), "abc123").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(todo.path()).unwrap(),
+            "reword abc123 feat: a\n"
+        );
+        assert!(mark_for_reword(todo.path(), "def456").is_err());
+    }
+
+    #[test]
+    fn test_sequence_editor_and_quoting() {
+        assert!(
+            sequence_editor("abc123")
+                .unwrap()
+                .ends_with(" reword-todo abc123")
+        );
+        assert_eq!(shell_quote("/tmp/a b"), "'/tmp/a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
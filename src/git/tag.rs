@@ -0,0 +1,63 @@
+use std::process::{Command, Stdio};
+
+use crate::error::{GcopError, Result};
+
+/// Create an annotated tag on `HEAD`
+///
+/// Use git CLI instead of git2 so `tag.gpgSign` and the signing
+/// configuration apply exactly as with `git tag -a`.
+///
+/// # Arguments
+/// * `name` - Tag name
+/// * `message` - Tag message
+pub fn create_annotated_tag(name: &str, message: &str) -> Result<()> {
+    run_git(&build_tag_args(name, message))
+}
+
+/// Push a single tag to `remote`
+///
+/// # Arguments
+/// * `remote` - Remote name (for example `origin`)
+/// * `name` - Tag name
+pub fn push_tag(remote: &str, name: &str) -> Result<()> {
+    let refspec = format!("refs/tags/{}", name);
+    run_git(&["push", remote, &refspec])
+}
+
+/// Build `git tag` arguments (pure function, easy to test).
+fn build_tag_args<'a>(name: &'a str, message: &'a str) -> Vec<&'a str> {
+    vec!["tag", "-a", name, "-m", message]
+}
+
+fn run_git(args: &[&str]) -> Result<()> {
+    // Inherit stdin so signing programs and credential helpers can prompt
+    let output = Command::new("git")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error_msg = if stderr.trim().is_empty() {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        } else {
+            stderr.trim().to_string()
+        };
+        return Err(GcopError::GitCommand(error_msg));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_tag_args() {
+        assert_eq!(
+            build_tag_args("v1.2.0", "v1.2.0: faster reviews"),
+            vec!["tag", "-a", "v1.2.0", "-m", "v1.2.0: faster reviews"]
+        );
+    }
+}
@@ -31,40 +31,38 @@
 //! ```
 //!
 //! ### Use as a library
-//! ```ignore
-//! use gcop_rs::git::repository::GitRepository;
-//! use gcop_rs::git::GitOperations;
-//! use gcop_rs::llm::provider::openai::OpenAIProvider;
-//! use gcop_rs::llm::LLMProvider;
-//! use gcop_rs::config::{ProviderConfig, NetworkConfig};
-//!
-//! # async fn example() -> anyhow::Result<()> {
-//! // 1. Initialize Git repository
-//! let repo = GitRepository::open(None)?;
-//! let diff = repo.get_staged_diff()?;
-//!
-//! // 2. Initialize LLM provider
-//! let config = ProviderConfig {
-//!     api_key: Some("sk-...".to_string()),
-//!     model: "gpt-4o-mini".to_string(),
-//!     ..Default::default()
-//! };
-//! let network_config = NetworkConfig::default();
-//! let provider = OpenAIProvider::new(&config, "openai", &network_config, false)?;
-//!
-//! // 3. Generate commit message
-//! let message = provider.generate_commit_message(&diff, None, None).await?;
-//! println!("Generated: {}", message);
+//! [`api::Gcop`] runs the same pipelines as the CLI and returns plain
+//! structs; nothing is printed.
+//! ```no_run
+//! use gcop_rs::api::{CommitSource, Gcop, ReviewTarget};
+//!
+//! # async fn example() -> gcop_rs::error::Result<()> {
+//! // Load ~/.config/gcop/config.toml and .gcop/config.toml, as the CLI does
+//! let gcop = Gcop::load()?.with_repo("/path/to/repo");
+//!
+//! // Commit message for the staged changes
+//! let commit = gcop.generate_commit_message(CommitSource::Staged).await?;
+//! println!("Generated: {}", commit.message);
+//!
+//! // Review uncommitted changes
+//! let review = gcop.review(ReviewTarget::Changes).await?;
+//! println!("{}", review.summary);
 //! # Ok(())
 //! # }
 //! ```
 //!
+//! Lower-level building blocks ([`git::GitOperations`], [`llm::LLMProvider`]
+//! and the provider backends) remain available for custom pipelines.
+//!
 //! ## Core Modules
+//! - [`api`] - High-level library API.
 //! - [`git`] - Git operation abstractions.
 //! - [`llm`] - LLM provider traits and implementations.
 //! - [`commands`] - CLI command implementations.
 //! - [`config`] - Configuration loading and management.
+//! - [`convention`] - Conventional Commits parsing, linting and changelog grouping.
 //! - [`error`] - Unified error types.
+//! - [`logging`] - Tracing setup and the `[logging]` log file.
 //! - [`ui`] - Terminal UI utilities.
 //!
 //! ## Configuration
@@ -92,18 +90,26 @@
 #[macro_use]
 extern crate rust_i18n;
 
+/// High-level library API (commit messages, reviews, stats) without terminal output.
+pub mod api;
 /// Command-line argument definitions and parsing.
 pub mod cli;
 /// CLI command implementations and shared helpers.
 pub mod commands;
 /// Configuration loading, defaults, and validation.
 pub mod config;
+/// Conventional Commits message parsing, serialization and linting.
+pub mod convention;
 /// Unified error types and localization helpers.
 pub mod error;
+/// Code forge (GitLab) integration for merge request review.
+pub mod forge;
 /// Git repository abstractions and operations.
 pub mod git;
 /// LLM traits, message types, prompts, and providers.
 pub mod llm;
+/// Tracing setup with an optional rotating log file.
+pub mod logging;
 /// Terminal UI helpers (colors, prompts, spinner, streaming output).
 pub mod ui;
 /// Workspace detection and commit scope inference for monorepos.
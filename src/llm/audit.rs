@@ -0,0 +1,264 @@
+//! Audit log of provider requests (`<data_dir>/audit.jsonl`).
+//!
+//! Opt-in with `[privacy].audit_log = true`. Every request sent to a provider
+//! appends one JSON line with the provider, model, SHA-256 hashes of the
+//! prompt as sent (after `[privacy]` redaction) and of the response, and the
+//! reported token counts. Prompts and responses themselves are never stored.
+//! `gcop-rs audit show/export` reads the file.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use super::metrics::{self, TokenUsage};
+use super::{StreamChunk, StreamHandle};
+use crate::error::Result;
+
+/// File name of the audit log inside the data directory.
+pub const AUDIT_FILE: &str = "audit.jsonl";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// One audit log line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// When the request finished.
+    pub timestamp: DateTime<Local>,
+    /// Provider name (key under `[llm.providers]`).
+    pub provider: String,
+    /// Model the request was sent to.
+    pub model: String,
+    /// SHA-256 of the system prompt, a NUL byte and the user prompt.
+    pub prompt_sha256: String,
+    /// SHA-256 of the response text; `None` when the request failed.
+    pub response_sha256: Option<String>,
+    /// Reported prompt tokens.
+    pub prompt_tokens: Option<u64>,
+    /// Reported completion tokens.
+    pub completion_tokens: Option<u64>,
+}
+
+impl AuditRecord {
+    /// Builds a record for a request to `provider`/`model`.
+    pub fn new(
+        provider: &str,
+        model: &str,
+        system: &str,
+        user: &str,
+        response: Option<&str>,
+        usage: Option<TokenUsage>,
+    ) -> Self {
+        Self {
+            timestamp: Local::now(),
+            provider: provider.to_string(),
+            model: model.to_string(),
+            prompt_sha256: prompt_hash(system, user),
+            response_sha256: response.map(|text| sha256_hex(text.as_bytes())),
+            prompt_tokens: usage.map(|u| u.prompt_tokens),
+            completion_tokens: usage.map(|u| u.completion_tokens),
+        }
+    }
+}
+
+/// Enables or disables the audit log for this process (`[privacy].audit_log`).
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether requests are being audited.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Default audit log path.
+pub fn audit_path() -> Option<PathBuf> {
+    crate::config::get_data_dir().map(|dir| dir.join(AUDIT_FILE))
+}
+
+/// Appends `record` to the audit log at `path`, creating it if needed.
+pub fn append(path: &Path, record: &AuditRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Reads all records from `path`; a missing file is an empty log.
+///
+/// Malformed lines are skipped with a warning.
+pub fn load(path: &Path) -> Result<Vec<AuditRecord>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                tracing::warn!("Skipping malformed audit record at line {}: {}", i + 1, e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Records one request in the default audit log when auditing is enabled.
+///
+/// Failures are logged and never abort the request.
+pub(crate) fn record(
+    provider: &str,
+    model: &str,
+    system: &str,
+    user: &str,
+    response: Option<&str>,
+    usage: Option<TokenUsage>,
+) {
+    if !is_enabled() {
+        return;
+    }
+    let Some(path) = audit_path() else {
+        return;
+    };
+    let record = AuditRecord::new(provider, model, system, user, response, usage);
+    if let Err(e) = append(&path, &record) {
+        tracing::warn!("Failed to write audit log {}: {}", path.display(), e);
+    }
+}
+
+/// Records a streamed request once its stream ends.
+///
+/// Deltas are forwarded unchanged; the response is hashed when `Done`
+/// arrives (text before a `Retry` is discarded). Returns `stream` untouched
+/// when auditing is disabled.
+pub(crate) fn record_stream(
+    provider: &str,
+    model: &str,
+    system: &str,
+    user: &str,
+    stream: Result<StreamHandle>,
+) -> Result<StreamHandle> {
+    if !is_enabled() {
+        return stream;
+    }
+    let mut upstream = match stream {
+        Ok(handle) => handle,
+        Err(e) => {
+            record(provider, model, system, user, None, None);
+            return Err(e);
+        }
+    };
+
+    let (provider, model) = (provider.to_string(), model.to_string());
+    let (system, user) = (system.to_string(), user.to_string());
+    let (tx, rx) = mpsc::channel(32);
+    tokio::spawn(async move {
+        let mut text = String::new();
+        while let Some(chunk) = upstream.receiver.recv().await {
+            match &chunk {
+                StreamChunk::Delta(delta) => text.push_str(delta),
+                StreamChunk::Retry => text.clear(),
+                // Written before forwarding so the entry exists when the caller finishes
+                StreamChunk::Done => record(
+                    &provider,
+                    &model,
+                    &system,
+                    &user,
+                    Some(&text),
+                    metrics::last_usage(),
+                ),
+                StreamChunk::Error(_) => record(&provider, &model, &system, &user, None, None),
+            }
+            if tx.send(chunk).await.is_err() {
+                return;
+            }
+        }
+    });
+    Ok(StreamHandle { receiver: rx })
+}
+
+fn prompt_hash(system: &str, user: &str) -> String {
+    let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+    context.update(system.as_bytes());
+    context.update(&[0]);
+    context.update(user.as_bytes());
+    hex(context.finish().as_ref())
+}
+
+/// Lowercase hex SHA-256 of `data`
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    hex(ring::digest::digest(&ring::digest::SHA256, data).as_ref())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_hashes_without_content() {
+        let usage = TokenUsage {
+            prompt_tokens: 812,
+            completion_tokens: 24,
+        };
+        let record = AuditRecord::new(
+            "openai",
+            "gpt-4o-mini",
+            "system",
+            "user diff",
+            Some("feat: add audit log"),
+            Some(usage),
+        );
+        assert_eq!(record.prompt_sha256.len(), 64);
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // system/user 边界参与哈希
+        assert_ne!(
+            record.prompt_sha256,
+            AuditRecord::new("openai", "m", "systemuser", " diff", None, None).prompt_sha256
+        );
+        assert_eq!(record.prompt_tokens, Some(812));
+
+        let line = serde_json::to_string(&record).unwrap();
+        assert!(!line.contains("user diff"));
+        assert!(!line.contains("audit log"));
+    }
+
+    #[test]
+    fn test_append_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(AUDIT_FILE);
+        assert!(load(&path).unwrap().is_empty());
+
+        let ok = AuditRecord::new("claude", "claude-sonnet-4-5", "s", "u", Some("r"), None);
+        let failed = AuditRecord::new("claude", "claude-sonnet-4-5", "s", "u", None, None);
+        append(&path, &ok).unwrap();
+        append(&path, &failed).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        assert_eq!(load(&path).unwrap(), vec![ok, failed]);
+    }
+}
@@ -0,0 +1,414 @@
+//! Breaking-change detection from a diff.
+//!
+//! Looks for removed lines that declare public API (Rust, TypeScript /
+//! JavaScript, Go, Python) and keys removed from configuration files. A
+//! declaration re-added elsewhere in the diff with the same signature counts
+//! as a move; with a different first line it counts as a signature change.
+//! The findings are hints for the prompt and the offline message, not proof.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::git::diff::split_diff_by_file;
+
+/// Findings reported at most, so a large refactor does not flood the prompt.
+const MAX_FINDINGS: usize = 10;
+
+static RUST_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^\s*pub(?:\s+(?:async|const|unsafe|extern\s+"[^"]*"))*\s+(fn|struct|enum|trait|type|const|static|mod|union)\s+([A-Za-z_][A-Za-z0-9_]*)"#,
+    )
+    .expect("valid regex")
+});
+static RUST_FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*pub\s+([a-z_][A-Za-z0-9_]*)\s*:[^:]").expect("valid regex"));
+static JS_EXPORT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^export\s+(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(function\*?|class|interface|type|const|let|var|enum)\s+([A-Za-z_$][A-Za-z0-9_$]*)",
+    )
+    .expect("valid regex")
+});
+static GO_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(func|type)\s+(?:\([^)]*\)\s*)?([A-Z][A-Za-z0-9_]*)").expect("valid regex")
+});
+static PYTHON_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:async\s+)?(def|class)\s+([A-Za-z][A-Za-z0-9_]*)").expect("valid regex")
+});
+static TOML_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*([A-Za-z0-9_.-]+|"[^"]+")\s*="#).expect("valid regex"));
+static YAML_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*([A-Za-z0-9_.-]+):(?:\s|$)").expect("valid regex"));
+static JSON_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*"([^"]+)"\s*:"#).expect("valid regex"));
+static ENV_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:export\s+)?([A-Z_][A-Z0-9_]*)=").expect("valid regex"));
+
+/// What a finding is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakingKind {
+    /// A public declaration was removed (or renamed).
+    Removed,
+    /// A public declaration kept its name but its first line changed.
+    SignatureChanged,
+    /// A key disappeared from a configuration file.
+    ConfigKeyRemoved,
+}
+
+/// One likely breaking change found in a diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakingChange {
+    /// Kind of change.
+    pub kind: BreakingKind,
+    /// Declaration kind (`function`, `struct`, ...) or `config key`.
+    pub item: &'static str,
+    /// Declaration or key name.
+    pub name: String,
+    /// File the change was found in.
+    pub file: String,
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            BreakingKind::Removed => write!(
+                f,
+                "removed public {} `{}` ({})",
+                self.item, self.name, self.file
+            ),
+            BreakingKind::SignatureChanged => write!(
+                f,
+                "changed signature of public {} `{}` ({})",
+                self.item, self.name, self.file
+            ),
+            BreakingKind::ConfigKeyRemoved => write!(
+                f,
+                "removed or renamed {} `{}` ({})",
+                self.item, self.name, self.file
+            ),
+        }
+    }
+}
+
+/// A public declaration found on a diff line.
+struct Declaration {
+    item: &'static str,
+    name: String,
+    signature: String,
+}
+
+/// Likely breaking changes in `diff`, in diff order (at most [`MAX_FINDINGS`]).
+pub fn detect(diff: &str) -> Vec<BreakingChange> {
+    let mut removed: Vec<(String, Declaration)> = Vec::new();
+    let mut added: HashMap<(&'static str, String), Vec<String>> = HashMap::new();
+    let mut config_keys: Vec<BreakingChange> = Vec::new();
+
+    for file in split_diff_by_file(diff) {
+        if super::heuristic::is_test(&file.filename) {
+            continue;
+        }
+        let config = is_config_file(&file.filename);
+        let mut removed_keys: Vec<String> = Vec::new();
+        let mut added_keys: HashSet<String> = HashSet::new();
+
+        for (sign, line) in changed_lines(&file.content) {
+            if let Some(parse) = config {
+                if let Some(key) = parse(line) {
+                    if sign == '-' {
+                        removed_keys.push(key);
+                    } else {
+                        added_keys.insert(key);
+                    }
+                }
+                continue;
+            }
+            let Some(declaration) = declaration(&file.filename, line) else {
+                continue;
+            };
+            if sign == '-' {
+                removed.push((file.filename.clone(), declaration));
+            } else {
+                added
+                    .entry((declaration.item, declaration.name))
+                    .or_default()
+                    .push(declaration.signature);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        config_keys.extend(
+            removed_keys
+                .into_iter()
+                .filter(|key| !added_keys.contains(key) && seen.insert(key.clone()))
+                .map(|name| BreakingChange {
+                    kind: BreakingKind::ConfigKeyRemoved,
+                    item: "config key",
+                    name,
+                    file: file.filename.clone(),
+                }),
+        );
+    }
+
+    let mut findings: Vec<BreakingChange> = Vec::new();
+    for (file, declaration) in removed {
+        let kind = match added.get(&(declaration.item, declaration.name.clone())) {
+            None => BreakingKind::Removed,
+            Some(signatures) if signatures.contains(&declaration.signature) => continue,
+            Some(_) => BreakingKind::SignatureChanged,
+        };
+        let finding = BreakingChange {
+            kind,
+            item: declaration.item,
+            name: declaration.name,
+            file,
+        };
+        if !findings.contains(&finding) {
+            findings.push(finding);
+        }
+    }
+    findings.extend(config_keys);
+    findings.truncate(MAX_FINDINGS);
+    findings
+}
+
+/// `(sign, content)` of the `+`/`-` lines inside the hunks of one file diff.
+fn changed_lines(content: &str) -> impl Iterator<Item = (char, &str)> {
+    content
+        .lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        .filter_map(|line| {
+            let mut chars = line.chars();
+            match chars.next() {
+                Some(sign @ ('+' | '-')) => Some((sign, chars.as_str())),
+                _ => None,
+            }
+        })
+}
+
+/// Public declaration on `line`, for the languages recognized by extension.
+fn declaration(path: &str, line: &str) -> Option<Declaration> {
+    let extension = Path::new(path).extension()?.to_str()?;
+    let (captures, item) = match extension {
+        "rs" => {
+            if let Some(captures) = RUST_ITEM.captures(line) {
+                let item = rust_item(captures.get(1)?.as_str());
+                (captures, item)
+            } else {
+                let captures = RUST_FIELD.captures(line)?;
+                return Some(Declaration {
+                    item: "field",
+                    name: captures.get(1)?.as_str().to_string(),
+                    signature: signature(line),
+                });
+            }
+        }
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => {
+            let captures = JS_EXPORT.captures(line)?;
+            let item = js_item(captures.get(1)?.as_str());
+            (captures, item)
+        }
+        "go" => {
+            let captures = GO_ITEM.captures(line)?;
+            let item = if &captures[1] == "func" {
+                "function"
+            } else {
+                "type"
+            };
+            (captures, item)
+        }
+        "py" => {
+            let captures = PYTHON_ITEM.captures(line)?;
+            let item = if &captures[1] == "class" {
+                "class"
+            } else {
+                "function"
+            };
+            (captures, item)
+        }
+        _ => return None,
+    };
+    Some(Declaration {
+        item,
+        name: captures.get(2)?.as_str().to_string(),
+        signature: signature(line),
+    })
+}
+
+fn rust_item(keyword: &str) -> &'static str {
+    match keyword {
+        "fn" => "function",
+        "struct" => "struct",
+        "enum" => "enum",
+        "trait" => "trait",
+        "type" => "type",
+        "mod" => "module",
+        "union" => "union",
+        _ => "constant",
+    }
+}
+
+fn js_item(keyword: &str) -> &'static str {
+    match keyword {
+        "function" | "function*" => "function",
+        "class" => "class",
+        "interface" => "interface",
+        "type" => "type",
+        "enum" => "enum",
+        _ => "export",
+    }
+}
+
+/// First line of a declaration with whitespace collapsed and the trailing
+/// body opener / separator dropped.
+fn signature(line: &str) -> String {
+    line.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['{', ';', ',', ':', ' '])
+        .to_string()
+}
+
+/// Key parser for configuration files; `None` for everything else.
+///
+/// Only files that look like user-facing configuration count, so dropping a
+/// dependency from `Cargo.toml` or an option from `tsconfig.json` does not.
+fn is_config_file(path: &str) -> Option<fn(&str) -> Option<String>> {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())?
+        .to_ascii_lowercase();
+    if name.starts_with(".env.") {
+        return Some(|line| capture(&ENV_KEY, line));
+    }
+    let in_config_dir = path.starts_with("config/") || path.contains("/config/");
+    let config_name = ["config", "settings"]
+        .iter()
+        .any(|word| name.contains(word))
+        && !["tsconfig", "jsconfig"]
+            .iter()
+            .any(|tool| name.starts_with(tool));
+    if !in_config_dir && !config_name {
+        return None;
+    }
+    match Path::new(&name).extension()?.to_str()? {
+        "toml" => {
+            Some(|line| capture(&TOML_KEY, line).map(|key| key.trim_matches('"').to_string()))
+        }
+        "yaml" | "yml" => Some(|line| capture(&YAML_KEY, line)),
+        "json" => Some(|line| capture(&JSON_KEY, line)),
+        _ => None,
+    }
+}
+
+fn capture(regex: &Regex, line: &str) -> Option<String> {
+    regex
+        .captures(line)
+        .and_then(|captures| captures.get(1))
+        .map(|key| key.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_diff(path: &str, lines: &[&str]) -> String {
+        format!(
+            "diff --git a/{path} b/{path}\nindex 1111111..2222222 100644\n--- a/{path}\n+++ b/{path}\n@@ -1,3 +1,3 @@\n{}\n",
+            lines.join("\n")
+        )
+    }
+
+    #[test]
+    fn test_detect_removed_and_changed_rust_api() {
+        let diff = file_diff(
+            "src/lib.rs",
+            &[
+                "-pub fn connect(url: &str) -> Client {",
+                "+pub fn connect(url: &str, timeout: Duration) -> Client {",
+                "-pub struct Legacy;",
+                "-pub(crate) fn internal() {}",
+                "-    pub name: String,",
+                " fn unchanged() {}",
+            ],
+        );
+        let findings = detect(&diff);
+        let rendered: Vec<String> = findings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "changed signature of public function `connect` (src/lib.rs)",
+                "removed public struct `Legacy` (src/lib.rs)",
+                "removed public field `name` (src/lib.rs)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_ignores_moves_and_tests() {
+        // 同签名移动到其他文件不算破坏性变更
+        let diff = format!(
+            "{}{}{}",
+            file_diff("src/a.rs", &["-pub fn helper(x: u32) -> u32 {"]),
+            file_diff("src/b.rs", &["+pub fn helper(x: u32) -> u32 {"]),
+            file_diff("tests/api_test.rs", &["-pub fn fixture() {}"]),
+        );
+        assert!(detect(&diff).is_empty());
+    }
+
+    #[test]
+    fn test_detect_other_languages() {
+        let diff = format!(
+            "{}{}{}",
+            file_diff(
+                "web/api.ts",
+                &[
+                    "-export async function fetchUser(id: string) {",
+                    "-const local = 1;"
+                ]
+            ),
+            file_diff(
+                "pkg/client.go",
+                &["-func (c *Client) Close() error {", "-func helper() {}"]
+            ),
+            file_diff(
+                "app/util.py",
+                &[
+                    "-def parse(text):",
+                    "-def _private():",
+                    "-    def method(self):"
+                ]
+            ),
+        );
+        let names: Vec<String> = detect(&diff).into_iter().map(|f| f.name).collect();
+        assert_eq!(names, vec!["fetchUser", "Close", "parse"]);
+    }
+
+    #[test]
+    fn test_detect_config_keys() {
+        let diff = format!(
+            "{}{}{}",
+            file_diff(
+                "config/default.toml",
+                &[
+                    "-request_timeout = 30",
+                    "+timeout = 30",
+                    "-retries = 3",
+                    "+retries = 5"
+                ]
+            ),
+            file_diff("Cargo.toml", &["-serde = \"1\""]),
+            file_diff(".env.example", &["-API_TOKEN=", "+API_KEY="]),
+        );
+        let findings = detect(&diff);
+        assert!(
+            findings
+                .iter()
+                .all(|f| f.kind == BreakingKind::ConfigKeyRemoved)
+        );
+        let names: Vec<&str> = findings.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["request_timeout", "API_TOKEN"]);
+    }
+}
@@ -0,0 +1,387 @@
+//! Deterministic commit messages without an LLM.
+//!
+//! Used by `commit --offline` and, with `[llm] offline_fallback = true`, when
+//! no provider could be reached. The message is derived from the changed
+//! paths, the kind of change (added, deleted, renamed, modified) and the
+//! inferred workspace scope, so a commit is never blocked on the network.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use async_trait::async_trait;
+
+use super::{CommitContext, LLMProvider, ProgressReporter, ReviewResult, ReviewType};
+use crate::config::ConventionStyle;
+use crate::convention::gitmoji;
+use crate::error::{GcopError, Result};
+use crate::git::diff::split_diff_by_file;
+
+/// Provider name reported in metrics for heuristic messages.
+pub const PROVIDER_NAME: &str = "offline";
+
+/// Files listed in the message body before the rest is summarized.
+const MAX_BODY_FILES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Added,
+    Deleted,
+    Renamed,
+    Modified,
+}
+
+/// Builds a commit message from the diff and its context.
+///
+/// `diff` may be truncated; files missing from it count as modified.
+pub fn commit_message(diff: &str, context: &CommitContext) -> String {
+    let changes = classify(diff, &context.files_changed);
+    if changes.is_empty() {
+        return header(context, "chore", "update files");
+    }
+
+    let commit_type = allowed_type(commit_type(&changes, context), context);
+    let subject = header(context, commit_type, &description(&changes));
+    if changes.len() == 1 {
+        return subject;
+    }
+
+    let mut body: Vec<String> = changes
+        .iter()
+        .take(MAX_BODY_FILES)
+        .map(|(path, _)| format!("- {}", path))
+        .collect();
+    if changes.len() > MAX_BODY_FILES {
+        body.push(format!(
+            "- ... and {} more files",
+            changes.len() - MAX_BODY_FILES
+        ));
+    }
+    format!("{}\n\n{}", subject, body.join("\n"))
+}
+
+/// Changed paths in `files` order with the kind of change found in `diff`.
+fn classify(diff: &str, files: &[String]) -> Vec<(String, Change)> {
+    let file_diffs = split_diff_by_file(diff);
+    let change_of = |path: &str| {
+        let Some(file) = file_diffs.iter().find(|f| f.filename == path) else {
+            return Change::Modified;
+        };
+        if file.old_filename.is_some() {
+            Change::Renamed
+        } else if file.content.lines().any(|l| l.starts_with("new file mode")) {
+            Change::Added
+        } else if file
+            .content
+            .lines()
+            .any(|l| l.starts_with("deleted file mode"))
+        {
+            Change::Deleted
+        } else {
+            Change::Modified
+        }
+    };
+
+    let mut seen = HashSet::new();
+    files
+        .iter()
+        .filter(|path| seen.insert(path.as_str()))
+        .map(|path| (path.clone(), change_of(path)))
+        .collect()
+}
+
+fn is_docs(path: &str) -> bool {
+    let name = file_name(path).to_ascii_lowercase();
+    path.starts_with("docs/")
+        || path.contains("/docs/")
+        || [".md", ".mdx", ".rst", ".adoc"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        || name.starts_with("license")
+}
+
+pub(crate) fn is_test(path: &str) -> bool {
+    let name = file_name(path);
+    path.starts_with("tests/")
+        || path.contains("/tests/")
+        || path.starts_with("test/")
+        || path.contains("/test/")
+        || path.contains("__tests__/")
+        || name.starts_with("test_")
+        || ["_test.", ".test.", ".spec.", "_spec."]
+            .iter()
+            .any(|marker| name.contains(marker))
+}
+
+fn is_ci(path: &str) -> bool {
+    path.starts_with(".github/workflows/")
+        || path.starts_with(".circleci/")
+        || path.starts_with(".gitlab-ci")
+        || path == "Jenkinsfile"
+        || path == ".travis.yml"
+        || path == "azure-pipelines.yml"
+}
+
+fn is_build(path: &str) -> bool {
+    matches!(
+        file_name(path),
+        "Cargo.toml"
+            | "Cargo.lock"
+            | "build.rs"
+            | "package.json"
+            | "package-lock.json"
+            | "yarn.lock"
+            | "pnpm-lock.yaml"
+            | "go.mod"
+            | "go.sum"
+            | "pyproject.toml"
+            | "requirements.txt"
+            | "Makefile"
+            | "CMakeLists.txt"
+            | "Dockerfile"
+    )
+}
+
+fn commit_type(changes: &[(String, Change)], context: &CommitContext) -> &'static str {
+    let all = |pred: fn(&str) -> bool| changes.iter().all(|(path, _)| pred(path));
+    if all(is_docs) {
+        "docs"
+    } else if all(is_test) {
+        "test"
+    } else if all(is_ci) {
+        "ci"
+    } else if all(is_build) {
+        "build"
+    } else if changes.iter().any(|(_, c)| *c == Change::Added)
+        && !changes.iter().any(|(_, c)| *c == Change::Deleted)
+    {
+        "feat"
+    } else if changes.iter().all(|(_, c)| *c == Change::Renamed)
+        || context.deletions > context.insertions
+    {
+        "refactor"
+    } else {
+        "chore"
+    }
+}
+
+/// Keeps `commit_type` if the convention allows it, otherwise falls back to
+/// `chore` or the first allowed type.
+fn allowed_type<'a>(commit_type: &'a str, context: &'a CommitContext) -> &'a str {
+    let Some(types) = context.convention.as_ref().and_then(|c| c.types.as_ref()) else {
+        return commit_type;
+    };
+    if types.is_empty() || types.iter().any(|t| t == commit_type) {
+        return commit_type;
+    }
+    types
+        .iter()
+        .find(|t| *t == "chore")
+        .or_else(|| types.first())
+        .map(String::as_str)
+        .unwrap_or(commit_type)
+}
+
+fn description(changes: &[(String, Change)]) -> String {
+    let first = changes[0].1;
+    let verb = if changes.iter().all(|(_, c)| *c == first) {
+        match first {
+            Change::Added => "add",
+            Change::Deleted => "remove",
+            Change::Renamed => "move",
+            Change::Modified => "update",
+        }
+    } else {
+        "update"
+    };
+
+    if let [(path, _)] = changes {
+        return format!("{} {}", verb, file_name(path));
+    }
+    match common_dir(changes.iter().map(|(path, _)| path.as_str())) {
+        Some(dir) => format!("{} {} files in {}", verb, changes.len(), dir),
+        None => format!("{} {} files", verb, changes.len()),
+    }
+}
+
+/// Longest directory shared by all paths, if any.
+fn common_dir<'a>(mut paths: impl Iterator<Item = &'a str>) -> Option<String> {
+    let parent = |path: &'a str| Path::new(path).parent().map(Path::to_path_buf);
+    let mut common = parent(paths.next()?)?;
+    for path in paths {
+        let dir = parent(path)?;
+        while !dir.starts_with(&common) {
+            common = common.parent()?.to_path_buf();
+        }
+    }
+    let common = common.to_string_lossy().to_string();
+    (!common.is_empty()).then_some(common)
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn header(context: &CommitContext, commit_type: &str, description: &str) -> String {
+    let scope = context
+        .scope_info
+        .as_ref()
+        .and_then(|s| s.suggested_scope.as_deref());
+    let style = context
+        .convention
+        .as_ref()
+        .map(|c| c.style.clone())
+        .unwrap_or_default();
+
+    match (style, scope) {
+        (ConventionStyle::Gitmoji, _) => {
+            let table = gitmoji::table(context.convention.as_ref());
+            match gitmoji::emoji_for(&table, commit_type) {
+                Some(emoji) => format!("{} {}", emoji, description),
+                None => description.to_string(),
+            }
+        }
+        (_, Some(scope)) => format!("{}({}): {}", commit_type, scope, description),
+        (_, None) => format!("{}: {}", commit_type, description),
+    }
+}
+
+/// Stand-in provider for `commit --offline`; every request fails without
+/// touching the network.
+pub struct OfflineProvider;
+
+impl OfflineProvider {
+    fn unavailable() -> GcopError {
+        GcopError::Llm(rust_i18n::t!("provider.offline_unavailable").to_string())
+    }
+}
+
+#[async_trait]
+impl LLMProvider for OfflineProvider {
+    async fn send_prompt(
+        &self,
+        _system_prompt: &str,
+        _user_prompt: &str,
+        _progress: Option<&dyn ProgressReporter>,
+    ) -> Result<String> {
+        Err(Self::unavailable())
+    }
+
+    async fn review_code(
+        &self,
+        _diff: &str,
+        _review_type: ReviewType,
+        _custom_prompt: Option<&str>,
+        _progress: Option<&dyn ProgressReporter>,
+    ) -> Result<ReviewResult> {
+        Err(Self::unavailable())
+    }
+
+    fn name(&self) -> &str {
+        PROVIDER_NAME
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Err(Self::unavailable())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CommitConvention;
+    use crate::llm::ScopeInfo;
+
+    fn context(files: &[&str], insertions: usize, deletions: usize) -> CommitContext {
+        CommitContext {
+            files_changed: files.iter().map(|f| f.to_string()).collect(),
+            insertions,
+            deletions,
+            ..Default::default()
+        }
+    }
+
+    fn new_file(path: &str) -> String {
+        format!(
+            "diff --git a/{0} b/{0}\nnew file mode 100644\n--- /dev/null\n+++ b/{0}\n@@ -0,0 +1 @@\n+x\n",
+            path
+        )
+    }
+
+    #[test]
+    fn test_single_new_file() {
+        let diff = new_file("src/llm/heuristic.rs");
+        let ctx = context(&["src/llm/heuristic.rs"], 1, 0);
+        assert_eq!(commit_message(&diff, &ctx), "feat: add heuristic.rs");
+    }
+
+    #[test]
+    fn test_type_from_paths() {
+        let ctx = context(&["README.md", "docs/guide/usage.md"], 3, 1);
+        let message = commit_message("", &ctx);
+        assert_eq!(
+            message,
+            "docs: update 2 files\n\n- README.md\n- docs/guide/usage.md"
+        );
+
+        let ctx = context(&["tests/a_test.rs", "tests/b_test.rs"], 3, 1);
+        assert!(commit_message("", &ctx).starts_with("test: update 2 files in tests\n"));
+
+        let ctx = context(&["Cargo.toml", "Cargo.lock"], 3, 1);
+        assert!(commit_message("", &ctx).starts_with("build: "));
+
+        let ctx = context(&["src/a.rs", "src/b.rs"], 2, 10);
+        assert!(commit_message("", &ctx).starts_with("refactor: update 2 files in src\n"));
+
+        let ctx = context(&["src/a.rs", "lib/b.rs"], 10, 2);
+        assert!(commit_message("", &ctx).starts_with("chore: update 2 files\n"));
+    }
+
+    #[test]
+    fn test_removed_and_renamed_files() {
+        let diff = "diff --git a/src/old.rs b/src/old.rs\ndeleted file mode 100644\n--- a/src/old.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-x\n";
+        let ctx = context(&["src/old.rs"], 0, 1);
+        assert_eq!(commit_message(diff, &ctx), "refactor: remove old.rs");
+
+        let diff = "diff --git a/src/a.rs b/src/io/a.rs\nsimilarity index 100%\nrename from src/a.rs\nrename to src/io/a.rs\n";
+        let ctx = context(&["src/io/a.rs"], 0, 0);
+        assert_eq!(commit_message(diff, &ctx), "refactor: move a.rs");
+    }
+
+    #[test]
+    fn test_scope_convention_and_body_limit() {
+        let files: Vec<String> = (0..12)
+            .map(|i| format!("crates/core/src/f{i}.rs"))
+            .collect();
+        let diff: String = files.iter().map(|f| new_file(f)).collect();
+        let mut ctx = CommitContext {
+            files_changed: files,
+            insertions: 12,
+            scope_info: Some(ScopeInfo {
+                suggested_scope: Some("core".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let message = commit_message(&diff, &ctx);
+        let mut lines = message.lines();
+        assert_eq!(
+            lines.next(),
+            Some("feat(core): add 12 files in crates/core/src")
+        );
+        assert_eq!(message.lines().last(), Some("- ... and 2 more files"));
+
+        ctx.convention = Some(CommitConvention {
+            style: ConventionStyle::Gitmoji,
+            ..Default::default()
+        });
+        assert!(commit_message(&diff, &ctx).starts_with(":sparkles: add 12 files"));
+
+        // 约定未允许的类型退回到 chore 或第一个允许的类型
+        ctx.convention = Some(CommitConvention {
+            types: Some(vec!["fix".to_string(), "chore".to_string()]),
+            ..Default::default()
+        });
+        assert!(commit_message(&diff, &ctx).starts_with("chore(core): "));
+    }
+}
@@ -0,0 +1,226 @@
+//! Local usage ledger (`<data_dir>/usage.jsonl`).
+//!
+//! Every generation that reached a provider appends one JSON line with the
+//! serving provider, model, token counts and an estimated cost (when the
+//! provider has `pricing` configured). `gcop-rs usage` aggregates the file.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::metrics::LlmMetrics;
+use crate::config::AppConfig;
+use crate::error::Result;
+
+/// File name of the ledger inside the data directory.
+pub const LEDGER_FILE: &str = "usage.jsonl";
+
+/// One ledger line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// When the generation finished.
+    pub timestamp: DateTime<Local>,
+    /// Command that triggered the generation (`commit`, `review`, ...).
+    pub command: String,
+    /// Provider name (key under `[llm.providers]`).
+    pub provider: String,
+    /// Model that served the generation (the tier model for tiered providers).
+    pub model: String,
+    /// LLM calls made by the generation.
+    pub calls: u32,
+    /// Reported prompt tokens (`0` when the provider reported none).
+    pub prompt_tokens: u64,
+    /// Reported completion tokens (`0` when the provider reported none).
+    pub completion_tokens: u64,
+    /// Estimated cost from `[llm.providers.<name>.pricing]`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+}
+
+impl UsageRecord {
+    /// Builds a record from collected metrics; `None` when no call was made.
+    pub fn from_metrics(command: &str, metrics: &LlmMetrics, config: &AppConfig) -> Option<Self> {
+        let provider = metrics.provider.as_deref().filter(|_| metrics.calls > 0)?;
+        let provider_config = config.llm.providers.get(provider);
+        let usage = metrics.usage.unwrap_or_default();
+        let cost = metrics.usage.and_then(|usage| {
+            provider_config
+                .and_then(|p| p.pricing)
+                .map(|pricing| pricing.cost(usage.prompt_tokens, usage.completion_tokens))
+        });
+
+        Some(Self {
+            timestamp: Local::now(),
+            command: command.to_string(),
+            provider: provider.to_string(),
+            model: metrics
+                .model
+                .clone()
+                .or_else(|| provider_config.map(|p| p.model.clone()))
+                .unwrap_or_default(),
+            calls: metrics.calls,
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cost,
+        })
+    }
+}
+
+/// Default ledger path.
+pub fn ledger_path() -> Option<PathBuf> {
+    crate::config::get_data_dir().map(|dir| dir.join(LEDGER_FILE))
+}
+
+/// Appends `record` to the ledger at `path`, creating it if needed.
+pub fn append(path: &Path, record: &UsageRecord) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    Ok(())
+}
+
+/// Reads all records from `path`; a missing file is an empty ledger.
+///
+/// Malformed lines are skipped with a warning.
+pub fn load(path: &Path) -> Result<Vec<UsageRecord>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(i, line)| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                tracing::warn!("Skipping malformed usage record at line {}: {}", i + 1, e);
+                None
+            }
+        })
+        .collect())
+}
+
+/// Records the metrics of a finished generation in the default ledger.
+///
+/// Failures are logged and never abort the command.
+pub fn record(command: &str, metrics: &LlmMetrics, config: &AppConfig) {
+    let Some(record) = UsageRecord::from_metrics(command, metrics, config) else {
+        return;
+    };
+    let Some(path) = ledger_path() else {
+        return;
+    };
+    if let Err(e) = append(&path, &record) {
+        tracing::warn!("Failed to write usage ledger {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ModelPricing;
+    use crate::llm::metrics::TokenUsage;
+    use crate::llm::provider::test_utils::test_provider_config;
+
+    fn metrics(provider: Option<&str>, calls: u32, usage: Option<TokenUsage>) -> LlmMetrics {
+        LlmMetrics {
+            provider: provider.map(str::to_string),
+            model: None,
+            calls,
+            usage,
+            latency_ms: 900,
+            retries: 0,
+        }
+    }
+
+    fn config_with_pricing() -> AppConfig {
+        let mut config = AppConfig::default();
+        let mut claude = test_provider_config(
+            String::new(),
+            None,
+            "claude-sonnet-4-5-20250929".to_string(),
+        );
+        claude.pricing = Some(ModelPricing {
+            input: 3.0,
+            output: 15.0,
+        });
+        let openai = test_provider_config(String::new(), None, "gpt-4o-mini".to_string());
+        config.llm.providers = [
+            ("claude".to_string(), claude),
+            ("openai".to_string(), openai),
+        ]
+        .into_iter()
+        .collect();
+        config
+    }
+
+    #[test]
+    fn test_from_metrics_estimates_cost() {
+        let config = config_with_pricing();
+        let usage = TokenUsage {
+            prompt_tokens: 1_000,
+            completion_tokens: 100,
+        };
+
+        let record =
+            UsageRecord::from_metrics("commit", &metrics(Some("claude"), 1, Some(usage)), &config)
+                .unwrap();
+        assert_eq!(record.model, "claude-sonnet-4-5-20250929");
+        assert_eq!(record.prompt_tokens, 1_000);
+        let cost = record.cost.unwrap();
+        assert!((cost - 0.0045).abs() < 1e-12);
+
+        // 未配置价格或未报告用量时不估算费用
+        let record =
+            UsageRecord::from_metrics("commit", &metrics(Some("openai"), 1, Some(usage)), &config)
+                .unwrap();
+        assert_eq!(record.cost, None);
+        let record =
+            UsageRecord::from_metrics("commit", &metrics(Some("claude"), 1, None), &config)
+                .unwrap();
+        assert_eq!((record.prompt_tokens, record.cost), (0, None));
+
+        // 没有实际调用时不记录
+        assert!(UsageRecord::from_metrics("commit", &metrics(None, 0, None), &config).is_none());
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join(LEDGER_FILE);
+        assert!(load(&path).unwrap().is_empty());
+
+        let config = config_with_pricing();
+        let record = UsageRecord::from_metrics(
+            "review",
+            &metrics(
+                Some("claude"),
+                2,
+                TokenUsage::from_counts(Some(10), Some(5)),
+            ),
+            &config,
+        )
+        .unwrap();
+        append(&path, &record).unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut f| writeln!(f, "not json"))
+            .unwrap();
+        append(&path, &record).unwrap();
+
+        let records = load(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0], record);
+    }
+}
@@ -0,0 +1,234 @@
+//! Token usage, latency and retry metrics of LLM calls.
+//!
+//! Backends return provider-reported usage with each response and the HTTP
+//! retry loop counts retries; both land in a process-wide collector. Commands
+//! call [`start`] before generating and [`finish`] once the response is
+//! complete to get an [`LlmMetrics`] for the summary line / JSON `meta`.
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Serialize;
+
+/// Token counts reported by the provider.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TokenUsage {
+    /// Input (prompt) tokens.
+    pub prompt_tokens: u64,
+    /// Output (completion) tokens.
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    /// Builds usage from optional counts; `None` when the provider reported neither.
+    pub(crate) fn from_counts(prompt: Option<u64>, completion: Option<u64>) -> Option<Self> {
+        (prompt.is_some() || completion.is_some()).then(|| Self {
+            prompt_tokens: prompt.unwrap_or(0),
+            completion_tokens: completion.unwrap_or(0),
+        })
+    }
+}
+
+/// Metrics of one generation (possibly several calls, e.g. structured-output fallback).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LlmMetrics {
+    /// Provider (key under `[llm.providers]`) that served the last call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Model selected by a tiered provider; `None` means the provider's `model`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Completed LLM calls.
+    pub calls: u32,
+    /// Summed usage; `None` when no call reported usage.
+    pub usage: Option<TokenUsage>,
+    /// Wall-clock time from [`start`] to [`finish`], including retries.
+    pub latency_ms: u64,
+    /// HTTP and stream retries.
+    pub retries: u32,
+}
+
+impl LlmMetrics {
+    const EMPTY: Self = Self {
+        provider: None,
+        model: None,
+        calls: 0,
+        usage: None,
+        latency_ms: 0,
+        retries: 0,
+    };
+
+    fn record_call(&mut self, usage: Option<TokenUsage>) {
+        self.calls += 1;
+        if let Some(usage) = usage {
+            let total = self.usage.get_or_insert_with(TokenUsage::default);
+            total.prompt_tokens += usage.prompt_tokens;
+            total.completion_tokens += usage.completion_tokens;
+        }
+    }
+
+    /// One-line localized summary, e.g. `LLM: 812 prompt + 24 completion tokens, 1.4s, retries: 0`.
+    pub fn summary(&self) -> String {
+        let tokens = match self.usage {
+            Some(usage) => rust_i18n::t!(
+                "metrics.tokens",
+                prompt = usage.prompt_tokens,
+                completion = usage.completion_tokens
+            ),
+            None => rust_i18n::t!("metrics.tokens_unknown"),
+        };
+        rust_i18n::t!(
+            "metrics.summary",
+            tokens = tokens,
+            latency = format!("{:.1}", self.latency_ms as f64 / 1000.0),
+            retries = self.retries
+        )
+        .to_string()
+    }
+}
+
+struct Collector {
+    metrics: LlmMetrics,
+    started: Option<Instant>,
+    /// `(provider, model)` choices of tiered providers; resolved against the
+    /// serving provider in [`finish`].
+    models: Vec<(String, String)>,
+    /// Usage reported by the most recent call.
+    last_usage: Option<TokenUsage>,
+}
+
+static COLLECTOR: Mutex<Collector> = Mutex::new(Collector {
+    metrics: LlmMetrics::EMPTY,
+    started: None,
+    models: Vec::new(),
+    last_usage: None,
+});
+
+fn with_collector<R>(f: impl FnOnce(&mut Collector) -> R) -> R {
+    let mut collector = COLLECTOR.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut collector)
+}
+
+/// Resets the collector and starts the latency clock.
+pub fn start() {
+    with_collector(|c| {
+        c.metrics = LlmMetrics::default();
+        c.started = Some(Instant::now());
+        c.models.clear();
+        c.last_usage = None;
+    });
+}
+
+/// Returns the metrics collected since [`start`] and resets the collector.
+pub fn finish() -> LlmMetrics {
+    with_collector(|c| {
+        let mut metrics = std::mem::take(&mut c.metrics);
+        if let Some(started) = c.started.take() {
+            metrics.latency_ms = started.elapsed().as_millis() as u64;
+        }
+        let models = std::mem::take(&mut c.models);
+        metrics.model = metrics
+            .provider
+            .as_deref()
+            .and_then(|provider| tier_model(models, provider));
+        metrics
+    })
+}
+
+/// Last model recorded for `provider`; choices of other (failed or losing)
+/// providers are ignored.
+fn tier_model(models: Vec<(String, String)>, provider: &str) -> Option<String> {
+    models
+        .into_iter()
+        .rev()
+        .find(|(p, _)| p == provider)
+        .map(|(_, model)| model)
+}
+
+/// Records the provider about to be called; with fallback the last one wins.
+pub(crate) fn record_provider(name: &str) {
+    with_collector(|c| c.metrics.provider = Some(name.to_string()));
+}
+
+/// Records the model a tiered provider selected for its next call.
+pub(crate) fn record_model(provider: &str, model: &str) {
+    with_collector(|c| c.models.push((provider.to_string(), model.to_string())));
+}
+
+/// Records a completed call and its reported usage.
+pub(crate) fn record_call(usage: Option<TokenUsage>) {
+    with_collector(|c| {
+        c.metrics.record_call(usage);
+        c.last_usage = usage;
+    });
+}
+
+/// Usage reported by the most recently completed call.
+pub(crate) fn last_usage() -> Option<TokenUsage> {
+    with_collector(|c| c.last_usage)
+}
+
+/// Records one retry.
+pub(crate) fn record_retry() {
+    with_collector(|c| c.metrics.retries += 1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_call_sums_reported_usage() {
+        let mut metrics = LlmMetrics::default();
+        metrics.record_call(None);
+        assert_eq!(metrics.usage, None);
+
+        metrics.record_call(TokenUsage::from_counts(Some(100), Some(20)));
+        metrics.record_call(TokenUsage::from_counts(Some(50), None));
+        assert_eq!(metrics.calls, 3);
+        assert_eq!(
+            metrics.usage,
+            Some(TokenUsage {
+                prompt_tokens: 150,
+                completion_tokens: 20
+            })
+        );
+        assert_eq!(TokenUsage::from_counts(None, None), None);
+    }
+
+    #[test]
+    fn test_tier_model_follows_serving_provider() {
+        let models = vec![
+            ("openai".to_string(), "gpt-4o-mini".to_string()),
+            ("ollama".to_string(), "llama3.2".to_string()),
+            ("openai".to_string(), "gpt-4o".to_string()),
+        ];
+        assert_eq!(
+            tier_model(models.clone(), "openai").as_deref(),
+            Some("gpt-4o")
+        );
+        assert_eq!(tier_model(models, "claude"), None);
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut metrics = LlmMetrics {
+            provider: None,
+            model: None,
+            calls: 1,
+            usage: Some(TokenUsage {
+                prompt_tokens: 812,
+                completion_tokens: 24,
+            }),
+            latency_ms: 1440,
+            retries: 1,
+        };
+        assert_eq!(
+            metrics.summary(),
+            "LLM: 812 prompt + 24 completion tokens, 1.4s, retries: 1"
+        );
+
+        metrics.usage = None;
+        assert!(metrics.summary().contains("tokens not reported"));
+    }
+}
@@ -3,10 +3,30 @@
 //! This module defines the provider interface used by commit generation
 //! and code review flows.
 
+/// Opt-in audit log of provider requests.
+pub mod audit;
+/// Breaking-change detection from a diff.
+pub mod breaking;
+/// Deterministic commit messages for offline use.
+pub mod heuristic;
+/// Local usage ledger of LLM calls.
+pub mod ledger;
+/// Token usage, latency and retry metrics of LLM calls.
+pub mod metrics;
+/// WASM plugin hooks transforming prompts and responses.
+pub mod plugins;
 /// Prompt-building utilities for commit/review flows.
 pub mod prompt;
 /// Built-in provider implementations and factory helpers.
 pub mod provider;
+/// `[privacy]` redaction rules and patterns.
+pub mod redact;
+/// Incremental parsing of streamed review responses.
+pub mod review_stream;
+/// Secret detection and redaction for outgoing diffs.
+pub mod secrets;
+/// Tera prompt templates (embedded defaults plus file overrides).
+pub mod template;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -254,9 +274,27 @@ pub trait LLMProvider: Send + Sync {
         );
         self.send_prompt_streaming(&system, &user).await
     }
+
+    /// Convenience: runs code review as a stream of raw response text.
+    ///
+    /// Builds the prompt via [`build_review_prompt_split`](crate::llm::prompt::build_review_prompt_split),
+    /// then delegates to [`send_prompt_streaming`](Self::send_prompt_streaming).
+    /// Callers parse the collected text with
+    /// [`process_review_response`](crate::llm::provider::base::process_review_response);
+    /// [`review_stream`] can render progress while chunks arrive.
+    async fn review_code_streaming(
+        &self,
+        diff: &str,
+        review_type: ReviewType,
+        custom_prompt: Option<&str>,
+    ) -> Result<StreamHandle> {
+        let (system, user) =
+            crate::llm::prompt::build_review_prompt_split(diff, &review_type, custom_prompt);
+        self.send_prompt_streaming(&system, &user).await
+    }
 }
 
-use crate::config::CommitConvention;
+use crate::config::{BodyMode, CommitConvention};
 
 /// Workspace scope metadata for monorepos.
 ///
@@ -267,7 +305,8 @@ use crate::config::CommitConvention;
 /// - `packages`: list of affected package paths
 /// - `suggested_scope`: suggested scope string (may be `None`)
 /// - `has_root_changes`: whether root-level (non-package) files were changed
-#[derive(Debug, Clone, Default)]
+/// - `history_scopes`: scopes of earlier commits touching the same files, most frequent first
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ScopeInfo {
     /// Detected workspace systems.
     pub workspace_types: Vec<String>,
@@ -277,6 +316,8 @@ pub struct ScopeInfo {
     pub suggested_scope: Option<String>,
     /// Whether there are root-level changes.
     pub has_root_changes: bool,
+    /// Scopes used by earlier commits touching the same files.
+    pub history_scopes: Vec<String>,
 }
 
 /// Context passed to commit-message generation.
@@ -288,23 +329,33 @@ pub struct ScopeInfo {
 /// - `insertions`: number of inserted lines
 /// - `deletions`: number of deleted lines
 /// - `branch_name`: current branch name (may be `None`, for example detached HEAD)
+/// - `ticket`: ticket ID extracted from the branch name, if any
 /// - `custom_prompt`: user-defined prompt customization (normal commit replaces base prompt, split commit appends additional constraints)
 /// - `user_feedback`: user feedback (used when regenerating, supports accumulation)
 /// - `convention`: optional commit-convention config
+/// - `history_examples`: recent commit subjects used as few-shot style examples
+/// - `previous_message`: message being replaced when amending or rewording
+/// - `body` / `body_bullets`: body requirements from `commit.body` / `commit.body_bullets`
 ///
 /// # Example
 /// ```
+/// use gcop_rs::config::BodyMode;
 /// use gcop_rs::llm::CommitContext;
 ///
 /// let context = CommitContext {
 ///     files_changed: vec!["src/main.rs".to_string()],
 ///     insertions: 10,
 ///     deletions: 3,
-///     branch_name: Some("feature/login".to_string()),
+///     branch_name: Some("feature/ABC-123-login".to_string()),
+///     ticket: Some("ABC-123".to_string()),
 ///     custom_prompt: Some("Focus on security changes".to_string()),
 ///     user_feedback: vec!["Be more specific".to_string()],
 ///     convention: None,
 ///     scope_info: None,
+///     history_examples: vec![],
+///     previous_message: None,
+///     body: BodyMode::Auto,
+///     body_bullets: false,
 /// };
 /// ```
 #[derive(Debug, Clone, Default)]
@@ -317,6 +368,8 @@ pub struct CommitContext {
     pub deletions: usize,
     /// Current branch name, if available.
     pub branch_name: Option<String>,
+    /// Ticket ID extracted from the branch name via `commit.ticket_pattern`.
+    pub ticket: Option<String>,
     /// Optional user-provided prompt customization.
     ///
     /// Normal commit mode treats this as a system prompt override.
@@ -328,6 +381,14 @@ pub struct CommitContext {
     pub convention: Option<CommitConvention>,
     /// Workspace scope metadata (`None` when detection is disabled or not applicable).
     pub scope_info: Option<ScopeInfo>,
+    /// Recent commit subjects from the repository (empty unless `commit.learn_from_history` is on).
+    pub history_examples: Vec<String>,
+    /// Existing message of the commit being amended or reworded, if any.
+    pub previous_message: Option<String>,
+    /// Whether the message must (or must not) have a body.
+    pub body: BodyMode,
+    /// Whether the body must be a bulleted list.
+    pub body_bullets: bool,
 }
 
 /// Review target type.
@@ -338,12 +399,14 @@ pub struct CommitContext {
 /// - [`UncommittedChanges`] - unstaged working tree changes (`index -> workdir`)
 /// - [`SingleCommit`] - one commit by hash
 /// - [`CommitRange`] - commit range (for example `HEAD~3..HEAD`)
-/// - [`FileOrDir`] - one file path (directories are currently unsupported)
+/// - [`FileOrDir`] - a file or directory, optionally with related files as context
+/// - [`MergeRequest`] - forge merge request by IID
 ///
 /// [`UncommittedChanges`]: ReviewType::UncommittedChanges
 /// [`SingleCommit`]: ReviewType::SingleCommit
 /// [`CommitRange`]: ReviewType::CommitRange
 /// [`FileOrDir`]: ReviewType::FileOrDir
+/// [`MergeRequest`]: ReviewType::MergeRequest
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub enum ReviewType {
@@ -353,8 +416,15 @@ pub enum ReviewType {
     SingleCommit(String),
     /// Review a commit range (`A..B`).
     CommitRange(String),
-    /// Review a single file path (directory recursion is not supported).
-    FileOrDir(String),
+    /// Review a file or directory.
+    FileOrDir {
+        /// Path as given on the command line.
+        path: String,
+        /// Related files rendered for reference only (`--with-context`).
+        context: Option<String>,
+    },
+    /// Review a forge merge request by IID.
+    MergeRequest(u64),
 }
 
 /// Structured result returned by code review.
@@ -378,6 +448,8 @@ pub enum ReviewType {
 ///             description: "Potential SQL injection".to_string(),
 ///             file: Some("db.rs".to_string()),
 ///             line: Some(42),
+///             suggested_patch: None,
+///             category: Some("injection".to_string()),
 ///         },
 ///     ],
 ///     suggestions: vec!["Use parameterized queries".to_string()],
@@ -400,6 +472,8 @@ pub struct ReviewResult {
 /// - `description`: issue description
 /// - `file`: related file path (optional)
 /// - `line`: related line number (optional)
+/// - `suggested_patch`: unified diff fixing the issue (optional)
+/// - `category`: kind of issue, e.g. `injection` or `naming` (optional)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewIssue {
     /// Severity level assigned to this issue.
@@ -412,6 +486,75 @@ pub struct ReviewIssue {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Optional 1-based line number related to the issue.
     pub line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional unified diff that fixes the issue (applied by `review changes --apply`).
+    pub suggested_patch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Optional issue category; restricted to the preset's list with `review --preset`.
+    pub category: Option<String>,
+}
+
+/// Focused review preset selected with `review --preset`.
+///
+/// A preset replaces the review system prompt with a specialized one and
+/// restricts [`ReviewIssue::category`] to a fixed list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewPreset {
+    /// Vulnerabilities: injection, leaked secrets, authorization, unsafe code.
+    Security,
+    /// Runtime cost: allocations, complexity, I/O, concurrency.
+    Performance,
+    /// Readability: naming, formatting, documentation, idioms.
+    Style,
+}
+
+impl ReviewPreset {
+    /// Category used when the model picks none or one outside the list.
+    pub const FALLBACK_CATEGORY: &'static str = "other";
+
+    /// Parses the CLI string (`security`, `performance`, `style`).
+    pub fn from_cli_str(s: &str) -> Option<Self> {
+        match s {
+            "security" => Some(Self::Security),
+            "performance" => Some(Self::Performance),
+            "style" => Some(Self::Style),
+            _ => None,
+        }
+    }
+
+    /// Returns the CLI string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Security => "security",
+            Self::Performance => "performance",
+            Self::Style => "style",
+        }
+    }
+
+    /// Issue categories allowed by this preset, [`FALLBACK_CATEGORY`](Self::FALLBACK_CATEGORY) last.
+    pub fn categories(&self) -> &'static [&'static str] {
+        match self {
+            Self::Security => &["injection", "secrets", "authz", "unsafe-code", "other"],
+            Self::Performance => &["allocation", "complexity", "io", "concurrency", "other"],
+            Self::Style => &["naming", "formatting", "documentation", "idiom", "other"],
+        }
+    }
+
+    /// Normalizes issue categories to this preset's list.
+    ///
+    /// Categories are matched case-insensitively (`_` and spaces count as
+    /// `-`); missing or unknown ones become `other`.
+    pub fn categorize(&self, issues: &mut [ReviewIssue]) {
+        for issue in issues {
+            let category = issue
+                .category
+                .as_deref()
+                .map(|c| c.trim().to_lowercase().replace(['_', ' '], "-"))
+                .filter(|c| self.categories().contains(&c.as_str()))
+                .unwrap_or_else(|| Self::FALLBACK_CATEGORY.to_string());
+            issue.category = Some(category);
+        }
+    }
 }
 
 /// Issue severity level.
@@ -420,7 +563,7 @@ pub struct ReviewIssue {
 /// - `Critical` - severe issue (security/correctness risk)
 /// - `Warning` - notable issue (performance/maintainability concern)
 /// - `Info` - informational suggestion
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum IssueSeverity {
     /// Critical issue (e.g., correctness/security risk).
@@ -450,6 +593,15 @@ impl IssueSeverity {
         }
     }
 
+    /// Returns the config/CLI string (`critical`, `warning`, `info`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+
     /// Returns localized label text.
     pub fn label(&self, colored: bool) -> String {
         match (self, colored) {
@@ -0,0 +1,103 @@
+//! WASM plugin hooks for prompt and response transformation (`[plugins]`).
+//!
+//! Modules listed in `[plugins].wasm` are compiled once by [`install`] and run
+//! around every provider request:
+//! - `transform_prompt` receives `{"system": ..., "user": ...}` as JSON and
+//!   returns the same shape (missing fields keep their value)
+//! - `transform_response` receives the response text and returns the new text
+//!
+//! Hooks run in order, each on a fresh instance with a fuel and memory limit.
+//! Modules may not import anything, so they have no network or file access.
+//! A failing hook fails the request rather than sending an unfiltered prompt.
+//!
+//! The runtime needs the `wasm` build feature; builds without it reject a
+//! non-empty `[plugins].wasm`.
+//!
+//! # Module ABI
+//! - export `memory` and `gcop_alloc(len: i32) -> i32`, which returns a buffer
+//!   the input is written to
+//! - export `transform_prompt(ptr: i32, len: i32) -> i64` and/or
+//!   `transform_response(ptr: i32, len: i32) -> i64`, returning the output as
+//!   `(ptr << 32) | len`, or a negative value to leave the input unchanged
+
+use std::borrow::Cow;
+
+use crate::config::PluginsConfig;
+use crate::error::{GcopError, Result};
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "wasm")]
+static PLUGINS: std::sync::OnceLock<wasm::PluginSet> = std::sync::OnceLock::new();
+
+/// Loads the configured plugins for this process.
+///
+/// Does nothing when `[plugins].wasm` is empty or plugins are already loaded.
+pub fn install(config: &PluginsConfig) -> Result<()> {
+    if config.wasm.is_empty() {
+        return Ok(());
+    }
+    #[cfg(feature = "wasm")]
+    {
+        if PLUGINS.get().is_none() {
+            let _ = PLUGINS.set(wasm::PluginSet::load(config)?);
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "wasm"))]
+    Err(GcopError::Config(
+        rust_i18n::t!("plugins.feature_disabled").to_string(),
+    ))
+}
+
+/// Whether a loaded plugin rewrites responses.
+///
+/// Streaming output cannot be rewritten chunk by chunk, so providers fall
+/// back to a non-streaming request when this is `true`.
+pub(crate) fn transforms_responses() -> bool {
+    #[cfg(feature = "wasm")]
+    if let Some(plugins) = PLUGINS.get() {
+        return plugins.transforms_responses();
+    }
+    false
+}
+
+/// Runs the `transform_prompt` hooks on a prompt pair.
+pub(crate) fn transform_prompt<'a>(
+    system: &'a str,
+    user: &'a str,
+) -> Result<(Cow<'a, str>, Cow<'a, str>)> {
+    #[cfg(feature = "wasm")]
+    if let Some(plugins) = PLUGINS.get()
+        && let Some((system, user)) = plugins.transform_prompt(system, user)?
+    {
+        return Ok((Cow::Owned(system), Cow::Owned(user)));
+    }
+    Ok((Cow::Borrowed(system), Cow::Borrowed(user)))
+}
+
+/// Runs the `transform_response` hooks on a response text.
+pub(crate) fn transform_response(text: String) -> Result<String> {
+    #[cfg(feature = "wasm")]
+    if let Some(plugins) = PLUGINS.get()
+        && let Some(text) = plugins.transform_response(&text)?
+    {
+        return Ok(text);
+    }
+    Ok(text)
+}
+
+/// Error for a hook that trapped, ran out of fuel or returned invalid output.
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+fn hook_error(plugin: &str, hook: &str, error: impl std::fmt::Display) -> GcopError {
+    GcopError::Other(
+        rust_i18n::t!(
+            "plugins.hook_failed",
+            plugin = plugin,
+            hook = hook,
+            error = error.to_string()
+        )
+        .to_string(),
+    )
+}
@@ -0,0 +1,346 @@
+//! wasmtime runtime for `[plugins]` modules.
+
+use serde::{Deserialize, Serialize};
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::hook_error;
+use crate::config::PluginsConfig;
+use crate::error::{GcopError, Result};
+
+const PROMPT_HOOK: &str = "transform_prompt";
+const RESPONSE_HOOK: &str = "transform_response";
+const ALLOC_EXPORT: &str = "gcop_alloc";
+const MEMORY_EXPORT: &str = "memory";
+
+/// Compiled plugins, in `[plugins].wasm` order.
+pub(super) struct PluginSet {
+    engine: Engine,
+    plugins: Vec<Plugin>,
+    fuel: u64,
+    max_memory: usize,
+}
+
+struct Plugin {
+    /// File name, used in error messages.
+    name: String,
+    module: Module,
+    prompt: bool,
+    response: bool,
+}
+
+#[derive(Serialize)]
+struct PromptInput<'a> {
+    system: &'a str,
+    user: &'a str,
+}
+
+#[derive(Deserialize)]
+struct PromptOutput {
+    #[serde(default)]
+    system: Option<String>,
+    #[serde(default)]
+    user: Option<String>,
+}
+
+impl PluginSet {
+    /// Compiles and checks every configured module.
+    pub(super) fn load(config: &PluginsConfig) -> Result<Self> {
+        let mut set = Self::new(config)?;
+        for path in &config.wasm {
+            let path = crate::config::expand_home(path);
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            let module = Module::from_file(&set.engine, &path)
+                .map_err(|e| load_error(&path.display().to_string(), format!("{:#}", e)))?;
+            set.add(name, module)?;
+        }
+        Ok(set)
+    }
+
+    fn new(config: &PluginsConfig) -> Result<Self> {
+        let mut engine_config = Config::new();
+        engine_config.consume_fuel(true);
+        let engine =
+            Engine::new(&engine_config).map_err(|e| load_error("wasmtime", format!("{:#}", e)))?;
+        Ok(Self {
+            engine,
+            plugins: Vec::new(),
+            fuel: config.fuel,
+            max_memory: usize::try_from(config.max_memory_mb.saturating_mul(1024 * 1024))
+                .unwrap_or(usize::MAX),
+        })
+    }
+
+    /// Adds a compiled module after checking its imports and exports.
+    fn add(&mut self, name: String, module: Module) -> Result<()> {
+        // No imports are provided, which is what keeps plugins sandboxed
+        if let Some(import) = module.imports().next() {
+            return Err(load_error(
+                &name,
+                rust_i18n::t!(
+                    "plugins.import_not_allowed",
+                    import = format!("{}::{}", import.module(), import.name())
+                ),
+            ));
+        }
+        let exports = |export: &str| module.get_export(export).is_some();
+        let prompt = exports(PROMPT_HOOK);
+        let response = exports(RESPONSE_HOOK);
+        if !prompt && !response {
+            return Err(load_error(&name, rust_i18n::t!("plugins.no_hooks")));
+        }
+        if !exports(ALLOC_EXPORT) || !exports(MEMORY_EXPORT) {
+            return Err(load_error(&name, rust_i18n::t!("plugins.missing_exports")));
+        }
+        tracing::debug!(
+            "Loaded plugin '{}' (prompt: {}, response: {})",
+            name,
+            prompt,
+            response
+        );
+        self.plugins.push(Plugin {
+            name,
+            module,
+            prompt,
+            response,
+        });
+        Ok(())
+    }
+
+    pub(super) fn transforms_responses(&self) -> bool {
+        self.plugins.iter().any(|plugin| plugin.response)
+    }
+
+    /// Returns the rewritten prompt pair, or `None` when no hook changed it.
+    pub(super) fn transform_prompt(
+        &self,
+        system: &str,
+        user: &str,
+    ) -> Result<Option<(String, String)>> {
+        let mut current: Option<(String, String)> = None;
+        for plugin in self.plugins.iter().filter(|plugin| plugin.prompt) {
+            let (system, user) = current
+                .as_ref()
+                .map_or((system, user), |(s, u)| (s.as_str(), u.as_str()));
+            let input = serde_json::to_vec(&PromptInput { system, user })?;
+            let Some(output) = self.call(plugin, PROMPT_HOOK, &input)? else {
+                continue;
+            };
+            let output: PromptOutput = serde_json::from_slice(&output)
+                .map_err(|e| hook_error(&plugin.name, PROMPT_HOOK, e))?;
+            current = Some((
+                output.system.unwrap_or_else(|| system.to_string()),
+                output.user.unwrap_or_else(|| user.to_string()),
+            ));
+        }
+        Ok(current)
+    }
+
+    /// Returns the rewritten response, or `None` when no hook changed it.
+    pub(super) fn transform_response(&self, text: &str) -> Result<Option<String>> {
+        let mut current: Option<String> = None;
+        for plugin in self.plugins.iter().filter(|plugin| plugin.response) {
+            let input = current.as_deref().unwrap_or(text);
+            if let Some(output) = self.call(plugin, RESPONSE_HOOK, input.as_bytes())? {
+                current = Some(
+                    String::from_utf8(output)
+                        .map_err(|e| hook_error(&plugin.name, RESPONSE_HOOK, e))?,
+                );
+            }
+        }
+        Ok(current)
+    }
+
+    /// Runs `hook` on a fresh instance of `plugin`.
+    fn call(&self, plugin: &Plugin, hook: &str, input: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.call_inner(plugin, hook, input)
+            .map_err(|e| hook_error(&plugin.name, hook, format!("{:#}", e)))
+    }
+
+    fn call_inner(
+        &self,
+        plugin: &Plugin,
+        hook: &str,
+        input: &[u8],
+    ) -> wasmtime::Result<Option<Vec<u8>>> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.max_memory)
+            .instances(1)
+            .build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.fuel)?;
+
+        let instance = Instance::new(&mut store, &plugin.module, &[])?;
+        let memory = instance
+            .get_memory(&mut store, MEMORY_EXPORT)
+            .ok_or_else(|| wasmtime::Error::msg("`memory` is not a memory export"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, ALLOC_EXPORT)?;
+        let transform = instance.get_typed_func::<(i32, i32), i64>(&mut store, hook)?;
+
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, u32::try_from(ptr)? as usize, input)?;
+
+        let packed = transform.call(&mut store, (ptr, len))?;
+        if packed < 0 {
+            return Ok(None);
+        }
+        let out_ptr = (packed >> 32) as usize;
+        let out_len = (packed & 0xffff_ffff) as usize;
+        // The length comes from the plugin: check it before allocating for it
+        if out_ptr
+            .checked_add(out_len)
+            .is_none_or(|end| end > memory.data_size(&store))
+        {
+            return Err(wasmtime::Error::msg(format!(
+                "output {}..+{} is outside the module's memory",
+                out_ptr, out_len
+            )));
+        }
+        let mut output = vec![0; out_len];
+        memory.read(&store, out_ptr, &mut output)?;
+        Ok(Some(output))
+    }
+}
+
+fn load_error(plugin: &str, error: impl std::fmt::Display) -> GcopError {
+    GcopError::Config(
+        rust_i18n::t!(
+            "plugins.load_failed",
+            plugin = plugin,
+            error = error.to_string()
+        )
+        .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 把输入中的小写 a 替换成 *，演示 prompt/response 改写
+    const MASK_A: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "gcop_alloc") (param $len i32) (result i32) (i32.const 1024))
+          (func $mask (param $ptr i32) (param $len i32) (result i64)
+            (local $i i32)
+            (block $done
+              (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $len)))
+                (if (i32.eq (i32.load8_u (i32.add (local.get $ptr) (local.get $i))) (i32.const 97))
+                  (then (i32.store8 (i32.add (local.get $ptr) (local.get $i)) (i32.const 42))))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)))
+            (i64.or
+              (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+              (i64.extend_i32_u (local.get $len))))
+          (export "transform_prompt" (func $mask))
+          (export "transform_response" (func $mask)))
+    "#;
+
+    fn plugin_set(modules: &[(&str, &str)]) -> Result<PluginSet> {
+        let mut set = PluginSet::new(&PluginsConfig::default())?;
+        for (name, wat) in modules {
+            let module = Module::new(&set.engine, wat).unwrap();
+            set.add(name.to_string(), module)?;
+        }
+        Ok(set)
+    }
+
+    #[test]
+    fn test_transform_prompt_and_response() {
+        let set = plugin_set(&[("mask.wat", MASK_A)]).unwrap();
+        assert!(set.transforms_responses());
+
+        // JSON 键名不含 a，输出仍是合法 JSON
+        let (system, user) = set
+            .transform_prompt("be a reviewer", "diff a/b")
+            .unwrap()
+            .unwrap();
+        assert_eq!(system, "be * reviewer");
+        assert_eq!(user, "diff */b");
+
+        let text = set.transform_response("feat: add cache").unwrap();
+        assert_eq!(text.as_deref(), Some("fe*t: *dd c*che"));
+    }
+
+    #[test]
+    fn test_negative_result_keeps_input() {
+        let keep = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "gcop_alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "transform_response") (param i32 i32) (result i64) (i64.const -1)))
+        "#;
+        let set = plugin_set(&[("keep.wat", keep)]).unwrap();
+        assert_eq!(set.transform_response("text").unwrap(), None);
+        assert_eq!(set.transform_prompt("s", "u").unwrap(), None);
+    }
+
+    #[test]
+    fn test_oversized_output_is_rejected() {
+        // 声称输出长度约 4 GiB：应在分配缓冲区之前报错
+        let huge = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "gcop_alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "transform_response") (param i32 i32) (result i64)
+                (i64.const 0xffffffff)))
+        "#;
+        let set = plugin_set(&[("huge.wat", huge)]).unwrap();
+        let err = set.transform_response("text").unwrap_err();
+        assert!(err.to_string().contains("huge.wat"));
+        assert!(err.to_string().contains("outside the module's memory"));
+    }
+
+    #[test]
+    fn test_imports_are_rejected() {
+        // 任何导入（包括 WASI）都会被拒绝，插件无法访问网络或文件
+        let wasi = r#"
+            (module
+              (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))
+              (memory (export "memory") 1)
+              (func (export "gcop_alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "transform_response") (param i32 i32) (result i64) (i64.const -1)))
+        "#;
+        let err = plugin_set(&[("wasi.wat", wasi)]).err().unwrap();
+        assert!(matches!(err, GcopError::Config(_)));
+        assert!(err.to_string().contains("fd_write"));
+    }
+
+    #[test]
+    fn test_module_without_hooks_is_rejected() {
+        let empty = r#"(module (memory (export "memory") 1))"#;
+        assert!(matches!(
+            plugin_set(&[("empty.wat", empty)]),
+            Err(GcopError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_infinite_loop_runs_out_of_fuel() {
+        let spin = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "gcop_alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "transform_response") (param i32 i32) (result i64)
+                (loop $forever (br $forever))
+                (i64.const -1)))
+        "#;
+        let config = PluginsConfig {
+            fuel: 10_000,
+            ..Default::default()
+        };
+        let mut set = PluginSet::new(&config).unwrap();
+        let module = Module::new(&set.engine, spin).unwrap();
+        set.add("spin.wat".to_string(), module).unwrap();
+
+        let err = set.transform_response("text").unwrap_err();
+        assert!(matches!(err, GcopError::Other(_)));
+        assert!(err.to_string().contains("spin.wat"));
+    }
+}
@@ -1,5 +1,9 @@
-use crate::config::{CommitConvention, ConventionStyle};
-use crate::llm::{CommitContext, ReviewType, ScopeInfo};
+use serde::Serialize;
+
+use crate::config::{BodyMode, CommitConvention, ConventionStyle, GitmojiMode};
+use crate::convention::gitmoji;
+use crate::llm::template::{PromptTemplate, render};
+use crate::llm::{CommitContext, ReviewPreset, ReviewResult, ReviewType};
 
 /// Static system directives (cacheable) - for use in system/user split mode
 const COMMIT_SYSTEM_PROMPT: &str = r#"You are a git commit message generator.
@@ -8,6 +12,7 @@ Rules:
 - Use conventional commits: type(scope): description
 - First line max 72 chars
 - Common types: feat, fix, docs, style, refactor, test, chore
+- Breaking changes (removed or changed public API, renamed config keys): add ! before the colon and a "BREAKING CHANGE: <description>" footer
 - Output ONLY the commit message, no explanation"#;
 
 /// Review basic system commands (can be overridden by customization)
@@ -26,20 +31,88 @@ const REVIEW_JSON_CONSTRAINT: &str = r#"
 Output JSON format:
 {
   "summary": "Brief assessment",
-  "issues": [{"severity": "critical|warning|info", "description": "...", "file": "...", "line": N}],
+  "issues": [{"severity": "critical|warning|info", "description": "...", "file": "...", "line": N, "suggested_patch": "...", "category": "..."}],
   "suggestions": ["..."]
-}"#;
+}
 
-/// Format user feedback list
-fn format_feedbacks(feedbacks: &[String]) -> String {
-    if feedbacks.is_empty() {
-        return String::new();
-    }
-    let mut result = String::from("\n\n## User Requirements:\n");
-    for (i, fb) in feedbacks.iter().enumerate() {
-        result.push_str(&format!("{}. {}\n", i + 1, fb));
-    }
-    result
+"suggested_patch": when the fix is small and unambiguous, a unified diff (--- a/<file>, +++ b/<file>, @@ hunks) against the current code that fixes only this issue; otherwise null.
+"category": a short lowercase label for the kind of issue (e.g. "correctness", "security"), or null."#;
+
+/// System prompt of the final pass of a chunked review
+const REVIEW_MERGE_PROMPT: &str = r#"You are an expert code reviewer merging the reviews of several parts of one large change.
+
+Each part was reviewed separately. Combine them into a single review:
+1. Write one summary that assesses the change as a whole
+2. Keep every distinct issue; report an issue found in several parts only once
+3. Keep the severity, file, line, suggested_patch and category of each issue unchanged
+4. Merge the suggestions and drop duplicates"#;
+
+/// System prompt of the `commit --review` gate
+const COMMIT_REVIEW_PROMPT: &str = r#"You are a code reviewer checking staged changes right before they are committed.
+
+Report only critical problems that should stop the commit:
+1. Bugs: crashes, wrong results, broken control flow
+2. Security: injection, leaked secrets, missing authorization
+3. Data loss: destructive operations without safeguards
+4. Leftovers: debugging code, conflict markers, unfinished code that breaks the build
+
+Rate these critical. Ignore style, naming and minor improvements; an empty issue list is a good result. Keep the summary to one sentence."#;
+
+/// System prompt of `review --preset security`
+const REVIEW_SECURITY_PROMPT: &str = r#"You are an application security reviewer.
+
+Focus only on security:
+1. Injection: SQL, shell, path traversal, template and HTML injection from untrusted input
+2. Secrets: credentials, tokens or keys committed or logged
+3. Authorization: missing or bypassable authentication and permission checks
+4. Unsafe code: memory safety, unchecked unsafe blocks, unsound FFI, dangerous deserialization
+
+Ignore style and performance. Rate exploitable issues critical."#;
+
+/// System prompt of `review --preset performance`
+const REVIEW_PERFORMANCE_PROMPT: &str = r#"You are a performance-focused code reviewer.
+
+Focus only on runtime cost:
+1. Allocation: unnecessary clones, copies and heap allocations in hot paths
+2. Complexity: quadratic loops, repeated work, missing caching or indexing
+3. I/O: blocking or unbatched I/O, N+1 queries, missing buffering
+4. Concurrency: lock contention, blocking in async code, needless serialization
+
+Ignore style. Only report issues with a plausible measurable impact."#;
+
+/// System prompt of `review --preset style`
+const REVIEW_STYLE_PROMPT: &str = r#"You are a code reviewer focused on readability.
+
+Focus only on style:
+1. Naming: unclear, inconsistent or misleading names
+2. Formatting: layout that hurts reading and is not handled by a formatter
+3. Documentation: missing or outdated comments and doc comments on public items
+4. Idiom: non-idiomatic constructs where the language offers a clearer one
+
+Do not report bugs or security issues. Rate style issues info or warning."#;
+
+/// Base system prompt of a review preset, with its category rule.
+///
+/// Used in place of `[review].custom_prompt`; the JSON constraints are
+/// still appended by [`build_review_prompt_split`].
+pub fn review_preset_prompt(preset: ReviewPreset) -> String {
+    let base = match preset {
+        ReviewPreset::Security => REVIEW_SECURITY_PROMPT,
+        ReviewPreset::Performance => REVIEW_PERFORMANCE_PROMPT,
+        ReviewPreset::Style => REVIEW_STYLE_PROMPT,
+    };
+    format!(
+        "{}\n\nSet \"category\" of every issue to exactly one of: {}.",
+        base,
+        preset.categories().join(", ")
+    )
+}
+
+/// Base system prompt of the quick review run by `commit --review`.
+///
+/// The JSON constraints are appended by [`build_review_prompt_split`].
+pub fn commit_review_prompt() -> &'static str {
+    COMMIT_REVIEW_PROMPT
 }
 
 /// Formatting convention constraint to prompt fragment
@@ -51,7 +124,34 @@ fn format_convention(convention: &CommitConvention) -> String {
             parts.push("Follow conventional commits format: type(scope): description".to_string());
         }
         ConventionStyle::Gitmoji => {
-            parts.push("Use gitmoji format: :emoji: description".to_string());
+            let table = gitmoji::table(Some(convention));
+            match convention.gitmoji_mode {
+                // Converted to gitmoji in post-processing
+                GitmojiMode::Convert => {
+                    parts.push(
+                        "Follow conventional commits format: type(scope): description".to_string(),
+                    );
+                    if convention.types.is_none() {
+                        let types: Vec<&str> = table
+                            .keys()
+                            .map(String::as_str)
+                            .filter(|key| *key != gitmoji::BREAKING_KEY)
+                            .collect();
+                        parts.push(format!("Allowed types: {}", types.join(", ")));
+                    }
+                }
+                GitmojiMode::Prompt => {
+                    parts.push("Use gitmoji format: :emoji: description".to_string());
+                    let emojis: Vec<String> = table
+                        .iter()
+                        .map(|(key, emoji)| format!("{} = {}", key, emoji))
+                        .collect();
+                    parts.push(format!(
+                        "Use only these emojis (change type = emoji): {}",
+                        emojis.join(", ")
+                    ));
+                }
+            }
         }
         ConventionStyle::Custom => {}
     }
@@ -75,59 +175,77 @@ fn format_convention(convention: &CommitConvention) -> String {
     format!("\n\n## Convention:\n{}", parts.join("\n"))
 }
 
-/// Format workspace scope information into prompt fragment
-fn format_scope_info(scope: &ScopeInfo) -> String {
+/// Body requirements (`commit.body` / `commit.body_bullets`) to prompt fragment
+fn format_body(context: &CommitContext) -> String {
     let mut parts = Vec::new();
-
-    if !scope.workspace_types.is_empty() {
-        parts.push(format!(
-            "Monorepo type: {}",
-            scope.workspace_types.join(", ")
-        ));
-    }
-
-    if !scope.packages.is_empty() {
-        parts.push(format!("Affected packages: {}", scope.packages.join(", ")));
-    }
-
-    if let Some(ref suggested) = scope.suggested_scope {
-        parts.push(format!(
-            "Suggested scope for commit message: \"{}\"",
-            suggested
-        ));
-    }
-
-    if scope.has_root_changes {
-        parts.push("Note: Some changes are in root-level files (outside any package)".to_string());
+    match context.body {
+        BodyMode::Always => parts.push(
+            "Always add a body after a blank line, explaining what changed and why.".to_string(),
+        ),
+        BodyMode::Never => parts.push(
+            "Do not add a body: output only the subject line (plus a BREAKING CHANGE footer when needed)."
+                .to_string(),
+        ),
+        BodyMode::Auto => {}
+    }
+    if context.body_bullets && context.body != BodyMode::Never {
+        parts.push(
+            "Write the body as a bulleted list: one \"- \" line per change, no prose paragraphs."
+                .to_string(),
+        );
     }
 
     if parts.is_empty() {
         return String::new();
     }
+    format!("\n\n## Body:\n{}", parts.join("\n"))
+}
 
-    format!("\n\n## Workspace:\n{}", parts.join("\n"))
+/// Template variables shared by the commit and split templates.
+fn context_vars(context: &CommitContext, convention: Option<&CommitConvention>) -> tera::Context {
+    let mut vars = tera::Context::new();
+    vars.insert("branch", &context.branch_name);
+    vars.insert("ticket", &context.ticket);
+    vars.insert("scope", &context.scope_info);
+    vars.insert("convention", &convention);
+    vars.insert("feedback", &context.user_feedback);
+    vars.insert("history", &context.history_examples);
+    vars.insert("previous_message", &context.previous_message);
+    vars
 }
 
-/// Build context section shared by both normal and split commit prompts.
-fn build_context_section(context: &CommitContext) -> String {
-    let branch_info = context
-        .branch_name
-        .as_ref()
-        .map(|b| format!("\nBranch: {}", b))
-        .unwrap_or_default();
+/// Split-template view of one file diff.
+#[derive(Serialize)]
+struct TemplateFile<'a> {
+    name: &'a str,
+    old_name: Option<&'a str>,
+    insertions: usize,
+    deletions: usize,
+    diff: &'a str,
+}
 
-    let scope_section = context
-        .scope_info
-        .as_ref()
-        .map(format_scope_info)
-        .unwrap_or_default();
+/// Variables of the `commit` template.
+pub(crate) fn commit_vars(
+    diff: &str,
+    context: &CommitContext,
+    convention: Option<&CommitConvention>,
+) -> tera::Context {
+    let mut vars = context_vars(context, convention);
+    vars.insert("diff", diff);
+    vars.insert("files", &context.files_changed);
+    vars.insert("insertions", &context.insertions);
+    vars.insert("deletions", &context.deletions);
+    vars.insert("has_submodules", &crate::git::diff::has_submodule_log(diff));
+    vars.insert("breaking", &breaking_hints(diff));
+    vars
+}
 
-    format!(
-        "{}{}{}",
-        branch_info,
-        scope_section,
-        format_feedbacks(&context.user_feedback)
-    )
+/// Likely breaking changes in `diff`, rendered for the `breaking` variable.
+fn breaking_hints(diff: &str) -> Vec<String> {
+    crate::llm::breaking::detect(diff)
+        .iter()
+        .map(ToString::to_string)
+        .collect()
 }
 
 /// Build normal commit prompt in system/user split format.
@@ -148,20 +266,25 @@ pub fn build_commit_prompt_split(
     if let Some(conv) = convention {
         system.push_str(&format_convention(conv));
     }
+    system.push_str(&format_body(context));
 
     // user message contains dynamic content
-    let user = format!(
-        "## Diff:\n```\n{}\n```\n\n## Context:\nFiles: {}\nChanges: +{} -{}{}",
-        diff,
-        context.files_changed.join(", "),
-        context.insertions,
-        context.deletions,
-        build_context_section(context)
+    let user = render(
+        PromptTemplate::Commit,
+        &commit_vars(diff, context, convention),
     );
 
     (system, user)
 }
 
+/// Variables of the `review` template.
+pub(crate) fn review_vars(diff: &str, context: Option<&str>) -> tera::Context {
+    let mut vars = tera::Context::new();
+    vars.insert("diff", diff);
+    vars.insert("context", &context);
+    vars
+}
+
 /// Build review prompt in system/user split format.
 ///
 /// Return (system_prompt, user_message)
@@ -169,18 +292,44 @@ pub fn build_commit_prompt_split(
 /// - user_message: Code to be reviewed
 pub fn build_review_prompt_split(
     diff: &str,
-    _review_type: &ReviewType,
+    review_type: &ReviewType,
     custom_template: Option<&str>,
 ) -> (String, String) {
     // Custom template used as base system prompt, always appended with JSON constraints
     let base = custom_template.unwrap_or(REVIEW_SYSTEM_PROMPT_BASE);
     let system = format!("{}{}", base, REVIEW_JSON_CONSTRAINT);
 
-    let user = format!("## Code to Review:\n```\n{}\n```", diff);
+    let context = match review_type {
+        ReviewType::FileOrDir { context, .. } => context.as_deref(),
+        _ => None,
+    };
+    let user = render(PromptTemplate::Review, &review_vars(diff, context));
 
     (system, user)
 }
 
+/// Build the prompt merging the reviews of a chunked diff.
+///
+/// Return (system_prompt, user_message)
+/// - system_prompt: merge directives + JSON format constraints
+/// - user_message: the partial reviews as JSON, one section per part
+pub fn build_review_merge_prompt(parts: &[ReviewResult]) -> (String, String) {
+    let system = format!("{}{}", REVIEW_MERGE_PROMPT, REVIEW_JSON_CONSTRAINT);
+    let user = render(PromptTemplate::ReviewMerge, &review_merge_vars(parts));
+    (system, user)
+}
+
+/// Variables of the `review_merge` template.
+pub(crate) fn review_merge_vars(parts: &[ReviewResult]) -> tera::Context {
+    let parts: Vec<String> = parts
+        .iter()
+        .map(|part| serde_json::to_string_pretty(part).unwrap_or_default())
+        .collect();
+    let mut vars = tera::Context::new();
+    vars.insert("parts", &parts);
+    vars
+}
+
 /// System prompt for split commit grouping
 /// Additional system directives for split (atomic) commit mode.
 /// Appended after `COMMIT_SYSTEM_PROMPT` to add grouping + JSON output requirements.
@@ -195,7 +344,8 @@ CRITICAL CONSTRAINTS (violating these will cause hard errors):
 Grouping rules:
 - Group related file changes together into logical commits
 - Each group represents ONE logical change (feature, bugfix, refactor, etc.)
-- Order groups by dependency (foundational changes first)
+- Order groups by dependency (foundational changes first): a group must not use a function, type or constant that a later group introduces
+- If two changes need each other to build, put them in the same group
 - If all files are logically related, put them in a single group
 - Output ONLY valid JSON, no explanation or markdown fences
 
@@ -209,6 +359,35 @@ Output format:
   ]
 }"#;
 
+/// Variables of the `split` template.
+pub(crate) fn split_vars(
+    file_diffs: &[crate::git::diff::FileDiff],
+    context: &CommitContext,
+    convention: Option<&CommitConvention>,
+) -> tera::Context {
+    let files: Vec<TemplateFile> = file_diffs
+        .iter()
+        .map(|fd| TemplateFile {
+            name: &fd.filename,
+            old_name: fd.old_filename.as_deref(),
+            insertions: fd.insertions,
+            deletions: fd.deletions,
+            diff: &fd.content,
+        })
+        .collect();
+    let mut vars = context_vars(context, convention);
+    vars.insert("files", &files);
+    vars.insert(
+        "insertions",
+        &file_diffs.iter().map(|f| f.insertions).sum::<usize>(),
+    );
+    vars.insert(
+        "deletions",
+        &file_diffs.iter().map(|f| f.deletions).sum::<usize>(),
+    );
+    vars
+}
+
 /// Build split commit prompt (system + user)
 ///
 /// Returns `(system_prompt, user_message)`.
@@ -232,40 +411,102 @@ pub fn build_split_commit_prompt(
     if let Some(conv) = convention {
         system.push_str(&format_convention(conv));
     }
+    system.push_str(&format_body(context));
 
     // Build user message with per-file diffs
-    // Prepend a complete file list so the LLM sees the full partition set upfront.
-    let mut user =
-        String::from("## Complete file list (each file must appear in EXACTLY ONE group):\n");
-    for fd in file_diffs {
-        user.push_str(&format!("- {}\n", fd.filename));
-    }
-    user.push_str("\n## File diffs:\n\n");
+    let user = render(
+        PromptTemplate::Split,
+        &split_vars(file_diffs, context, convention),
+    );
 
-    for fd in file_diffs {
-        user.push_str(&format!(
-            "### File: {} (+{} -{})\n```diff\n{}\n```\n\n",
-            fd.filename, fd.insertions, fd.deletions, fd.content
-        ));
-    }
+    (system, user)
+}
 
-    let total_insertions: usize = file_diffs.iter().map(|f| f.insertions).sum();
-    let total_deletions: usize = file_diffs.iter().map(|f| f.deletions).sum();
+/// System prompt for annotated tag (release) messages
+const TAG_SYSTEM_PROMPT: &str = r#"You are a release notes writer for annotated git tags.
 
-    user.push_str(&format!(
-        "## Context:\nTotal files: {}\nTotal changes: +{} -{}{}",
-        file_diffs.len(),
-        total_insertions,
-        total_deletions,
-        build_context_section(context)
-    ));
+Rules:
+- First line: short release title including the tag name, max 72 chars
+- Then a blank line and a concise changelog as "- " bullet points
+- Group bullets under plain-text headings (Features, Fixes, Other) when more than one kind of change is present
+- Merge related commits into one bullet and skip trivial chores
+- Output ONLY the tag message, no explanation or markdown fences"#;
+
+/// Variables of the `tag` template.
+pub(crate) fn tag_vars(
+    tag: &str,
+    previous_tag: Option<&str>,
+    commits: &[String],
+    omitted: usize,
+) -> tera::Context {
+    let mut vars = tera::Context::new();
+    vars.insert("tag", tag);
+    vars.insert("previous_tag", &previous_tag);
+    vars.insert("commits", commits);
+    vars.insert(
+        "sections",
+        &crate::convention::changelog::group_by_type(commits),
+    );
+    vars.insert("omitted", &omitted);
+    vars.insert("total", &(commits.len() + omitted));
+    vars
+}
 
-    (system, user)
+/// Build tag message prompt (system + user)
+///
+/// Returns `(system_prompt, user_message)`.
+/// The user message lists the tag, the previous tag and the commit subjects since it.
+pub fn build_tag_prompt(
+    tag: &str,
+    previous_tag: Option<&str>,
+    commits: &[String],
+    omitted: usize,
+) -> (String, String) {
+    let user = render(
+        PromptTemplate::Tag,
+        &tag_vars(tag, previous_tag, commits, omitted),
+    );
+
+    (TAG_SYSTEM_PROMPT.to_string(), user)
+}
+
+/// System prompt for checking that staged changes belong to a fixup target
+const FIXUP_SYSTEM_PROMPT: &str = r#"You check whether staged changes are a follow-up fix to an earlier commit, to be squashed into it with `git rebase --autosquash`.
+
+Rules:
+- "related" is true when the staged changes fix, complete or adjust what the target commit introduced (same feature, files or code paths)
+- "related" is false when they are an unrelated change that deserves its own commit
+- "reason" is one short sentence
+
+Output JSON only:
+{"related": true, "reason": "..."}"#;
+
+/// Variables of the `fixup` template.
+pub(crate) fn fixup_vars(subject: &str, target_diff: &str, diff: &str) -> tera::Context {
+    let mut vars = tera::Context::new();
+    vars.insert("subject", subject);
+    vars.insert("target_diff", target_diff);
+    vars.insert("diff", diff);
+    vars
+}
+
+/// Build the fixup target check prompt (system + user)
+///
+/// Returns `(system_prompt, user_message)`.
+/// The user message holds the target commit's subject and diff, and the staged diff.
+pub fn build_fixup_prompt(subject: &str, target_diff: &str, diff: &str) -> (String, String) {
+    let user = render(
+        PromptTemplate::Fixup,
+        &fixup_vars(subject, target_diff, diff),
+    );
+
+    (FIXUP_SYSTEM_PROMPT.to_string(), user)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::llm::ScopeInfo;
     use pretty_assertions::assert_eq;
 
     fn create_context(
@@ -280,13 +521,36 @@ mod tests {
             insertions,
             deletions,
             branch_name: branch.map(String::from),
+            ticket: None,
             custom_prompt: None,
             user_feedback: feedbacks.into_iter().map(String::from).collect(),
             convention: None,
             scope_info: None,
+            history_examples: vec![],
+            previous_message: None,
+            body: BodyMode::Auto,
+            body_bullets: false,
         }
     }
 
+    // === build_review_merge_prompt test ===
+
+    #[test]
+    fn test_review_merge_prompt_lists_parts() {
+        let part = |summary: &str| ReviewResult {
+            summary: summary.to_string(),
+            issues: vec![],
+            suggestions: vec![],
+        };
+        let (system, user) = build_review_merge_prompt(&[part("first"), part("second")]);
+
+        assert!(system.contains("merging the reviews"));
+        assert!(system.contains("Output JSON format"));
+        assert!(user.starts_with("Reviews of the 2 parts:\n"));
+        assert!(user.contains("## Part 1\n```json\n{\n  \"summary\": \"first\""));
+        assert!(user.contains("## Part 2\n"));
+    }
+
     // === build_commit_prompt_split test ===
 
     #[test]
@@ -312,6 +576,15 @@ mod tests {
         assert!(user.contains("Branch: feature/test"));
     }
 
+    #[test]
+    fn test_commit_prompt_split_with_ticket() {
+        let mut ctx = create_context(vec!["a.rs"], 1, 1, Some("feature/ABC-42-x"), vec![]);
+        ctx.ticket = Some("ABC-42".to_string());
+        let (_, user) = build_commit_prompt_split("diff", &ctx, None, None);
+
+        assert!(user.contains("Ticket: ABC-42"));
+    }
+
     #[test]
     fn test_commit_prompt_split_with_feedback() {
         let ctx = create_context(
@@ -328,6 +601,40 @@ mod tests {
         assert!(user.contains("2. 不要超过50字符"));
     }
 
+    #[test]
+    fn test_commit_prompt_split_with_history_examples() {
+        let mut ctx = create_context(vec!["a.rs"], 1, 1, None, vec!["shorter"]);
+        ctx.history_examples = vec![
+            "feat(cli): add --profile flag".to_string(),
+            "fix(git): handle detached HEAD".to_string(),
+        ];
+        let (_, user) = build_commit_prompt_split("diff", &ctx, None, None);
+
+        assert!(user.contains("## Recent commits in this repository"));
+        assert!(user.contains("- feat(cli): add --profile flag"));
+        assert!(user.contains("- fix(git): handle detached HEAD"));
+        // Feedback stays last so it takes precedence over examples
+        assert!(user.find("Recent commits").unwrap() < user.find("User Requirements").unwrap());
+    }
+
+    #[test]
+    fn test_commit_prompt_split_with_previous_message() {
+        let mut ctx = create_context(vec!["a.rs"], 1, 1, None, vec![]);
+        ctx.previous_message = Some("fix: old wording".to_string());
+        let (_, user) = build_commit_prompt_split("diff", &ctx, None, None);
+
+        assert!(user.contains("## Previous commit message"));
+        assert!(user.contains("fix: old wording"));
+    }
+
+    #[test]
+    fn test_commit_prompt_split_without_history_examples() {
+        let ctx = create_context(vec!["a.rs"], 1, 1, None, vec![]);
+        let (_, user) = build_commit_prompt_split("diff", &ctx, None, None);
+
+        assert!(!user.contains("Recent commits"));
+    }
+
     #[test]
     fn test_commit_prompt_split_custom_template() {
         let ctx = create_context(vec!["a.rs"], 1, 1, None, vec![]);
@@ -365,6 +672,41 @@ mod tests {
         let (system, _) = build_commit_prompt_split("diff", &ctx, None, Some(&conv));
 
         assert!(system.contains("gitmoji"));
+        assert!(system.contains("feat = :sparkles:"));
+
+        // convert 模式请求 conventional 格式，类型取自映射表
+        let conv = CommitConvention {
+            style: ConventionStyle::Gitmoji,
+            gitmoji_mode: GitmojiMode::Convert,
+            gitmoji: Some(std::collections::BTreeMap::from([
+                ("feat".to_string(), "✨".to_string()),
+                ("breaking".to_string(), "💥".to_string()),
+            ])),
+            ..Default::default()
+        };
+        let (system, _) = build_commit_prompt_split("diff", &ctx, None, Some(&conv));
+        assert!(system.contains("conventional commits"));
+        assert!(system.contains("Allowed types: feat"));
+    }
+
+    #[test]
+    fn test_commit_prompt_split_with_body_rules() {
+        let mut ctx = create_context(vec!["a.rs"], 1, 1, None, vec![]);
+        let (system, _) = build_commit_prompt_split("diff", &ctx, None, None);
+        assert!(!system.contains("## Body:"));
+
+        ctx.body = BodyMode::Always;
+        ctx.body_bullets = true;
+        let (system, _) = build_commit_prompt_split("diff", &ctx, None, None);
+        assert!(system.contains("Always add a body"));
+        assert!(system.contains("bulleted list"));
+
+        // 自定义 system prompt 也保留正文要求
+        ctx.body = BodyMode::Never;
+        let (system, _) = build_commit_prompt_split("diff", &ctx, Some("Custom"), None);
+        assert!(system.starts_with("Custom"));
+        assert!(system.contains("Do not add a body"));
+        assert!(!system.contains("bulleted list"));
     }
 
     #[test]
@@ -397,6 +739,7 @@ mod tests {
         let ctx = create_context(vec!["a.rs"], 1, 1, None, vec![]);
         let diffs = vec![crate::git::diff::FileDiff {
             filename: "a.rs".to_string(),
+            old_filename: None,
             content: "+code".to_string(),
             insertions: 1,
             deletions: 1,
@@ -440,6 +783,34 @@ mod tests {
         assert!(system.contains("\"summary\""));
     }
 
+    #[test]
+    fn test_review_prompt_file_context() {
+        let review_type = ReviewType::FileOrDir {
+            path: "src/auth.rs".to_string(),
+            context: Some("--- src/mod.rs\n+++ src/mod.rs\npub mod auth;".to_string()),
+        };
+        let (_, user) = build_review_prompt_split("file body", &review_type, None);
+
+        let related = user.find("## Related files").unwrap();
+        assert!(user.find("file body").unwrap() < related);
+        assert!(user[related..].contains("pub mod auth;"));
+
+        let (_, user) =
+            build_review_prompt_split("file body", &ReviewType::UncommittedChanges, None);
+        assert!(!user.contains("Related files"));
+    }
+
+    #[test]
+    fn test_review_preset_prompt_replaces_base() {
+        let base = review_preset_prompt(ReviewPreset::Security);
+        let (system, _) =
+            build_review_prompt_split("diff", &ReviewType::UncommittedChanges, Some(&base));
+        assert!(system.starts_with("You are an application security reviewer."));
+        assert!(system.contains("exactly one of: injection, secrets, authz, unsafe-code, other."));
+        assert!(system.contains("\"category\""));
+        assert!(!system.contains("Review criteria:"));
+    }
+
     // === scope info injection test ===
 
     #[test]
@@ -449,6 +820,7 @@ mod tests {
             insertions: 5,
             deletions: 2,
             branch_name: None,
+            ticket: None,
             custom_prompt: None,
             user_feedback: vec![],
             convention: None,
@@ -457,7 +829,12 @@ mod tests {
                 packages: vec!["packages/core".into()],
                 suggested_scope: Some("core".into()),
                 has_root_changes: false,
+                history_scopes: vec![],
             }),
+            history_examples: vec![],
+            previous_message: None,
+            body: BodyMode::Auto,
+            body_bullets: false,
         };
         let (_, user) = build_commit_prompt_split("diff", &ctx, None, None);
 
@@ -483,6 +860,7 @@ mod tests {
             insertions: 3,
             deletions: 1,
             branch_name: None,
+            ticket: None,
             custom_prompt: None,
             user_feedback: vec![],
             convention: None,
@@ -491,10 +869,110 @@ mod tests {
                 packages: vec!["packages/core".into()],
                 suggested_scope: Some("core".into()),
                 has_root_changes: true,
+                history_scopes: vec![],
             }),
+            history_examples: vec![],
+            previous_message: None,
+            body: BodyMode::Auto,
+            body_bullets: false,
         };
         let (_, user) = build_commit_prompt_split("diff", &ctx, None, None);
 
         assert!(user.contains("root-level"));
     }
+
+    #[test]
+    fn test_tag_prompt() {
+        let commits = vec![
+            "feat: add tag command".to_string(),
+            "fix: crash".to_string(),
+        ];
+        let (system, user) = build_tag_prompt("v1.2.0", Some("v1.1.0"), &commits, 3);
+
+        assert!(system.contains("annotated git tags"));
+        assert!(user.contains("Tag: v1.2.0\nPrevious tag: v1.1.0\nCommits: 5"));
+        assert!(
+            user.contains("### Features\n- feat: add tag command\n\n### Fixes\n- fix: crash\n")
+        );
+        assert!(user.contains("... and 3 older commits"));
+    }
+
+    #[test]
+    fn test_commit_prompt_submodule_note() {
+        let ctx = create_context(vec!["libs/foo"], 1, 1, None, vec![]);
+        let diff = "-Subproject commit 1111111\n+Subproject commit 2222222\n\
+                    Submodule libs/foo 1111111..2222222 (1 new commit)\n  > fix: x";
+        let (_, user) = build_commit_prompt_split(diff, &ctx, None, None);
+        assert!(user.contains("## Submodules:"));
+
+        let (_, user) = build_commit_prompt_split("+code", &ctx, None, None);
+        assert!(!user.contains("## Submodules:"));
+    }
+
+    #[test]
+    fn test_tag_prompt_first_release() {
+        let (_, user) = build_tag_prompt("v0.1.0", None, &["feat: init".to_string()], 0);
+
+        assert!(user.contains("Previous tag: none (first release)"));
+        assert!(!user.contains("older commits"));
+    }
+
+    #[test]
+    fn test_fixup_prompt() {
+        let (system, user) = build_fixup_prompt("feat: add login", "+fn login() {}", "+// fix");
+
+        assert!(system.contains("\"related\""));
+        assert_eq!(
+            user,
+            "## Target commit:\nfeat: add login\n\n## Target commit diff:\n```\n+fn login() {}\n```\n\n\
+             ## Staged changes:\n```\n+// fix\n```\n"
+        );
+    }
+
+    #[test]
+    fn test_commit_prompt_full_layout() {
+        let mut ctx = create_context(vec!["a.rs", "b.rs"], 3, 1, Some("main"), vec!["short"]);
+        ctx.ticket = Some("ABC-1".to_string());
+        ctx.history_examples = vec!["feat: x".to_string()];
+        let (_, user) = build_commit_prompt_split("+code", &ctx, None, None);
+
+        assert_eq!(
+            user,
+            "## Diff:\n```\n+code\n```\n\n## Context:\nFiles: a.rs, b.rs\nChanges: +3 -1\n\
+             Branch: main\nTicket: ABC-1 (reference this ticket ID in the message)\n\n\
+             ## Recent commits in this repository (match their style and scope names):\n- feat: x\n\n\
+             ## User Requirements:\n1. short\n"
+        );
+    }
+
+    #[test]
+    fn test_split_commit_prompt_layout() {
+        let ctx = create_context(vec![], 0, 0, None, vec![]);
+        let diffs = vec![
+            crate::git::diff::FileDiff {
+                filename: "new.rs".to_string(),
+                old_filename: Some("old.rs".to_string()),
+                content: "+a".to_string(),
+                insertions: 1,
+                deletions: 0,
+            },
+            crate::git::diff::FileDiff {
+                filename: "b.rs".to_string(),
+                old_filename: None,
+                content: "-b".to_string(),
+                insertions: 0,
+                deletions: 2,
+            },
+        ];
+        let (_, user) = build_split_commit_prompt(&diffs, &ctx, None, None);
+
+        assert_eq!(
+            user,
+            "## Complete file list (each file must appear in EXACTLY ONE group):\n\
+             - new.rs (renamed from old.rs)\n- b.rs\n\n## File diffs:\n\n\
+             ### File: new.rs (+1 -0)\n```diff\n+a\n```\n\n\
+             ### File: b.rs (+0 -2)\n```diff\n-b\n```\n\n\
+             ## Context:\nTotal files: 2\nTotal changes: +1 -2\n"
+        );
+    }
 }
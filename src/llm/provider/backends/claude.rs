@@ -4,9 +4,11 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use super::super::base::{
-    ApiBackend, build_endpoint, extract_api_key, get_max_tokens, get_temperature, send_llm_request,
+    ApiBackend, ApiResponse, build_endpoint, extract_api_key, get_max_tokens, get_stop,
+    get_temperature, get_temperature_optional, get_top_p, send_llm_request,
     send_llm_request_streaming, validate_api_key, validate_http_endpoint,
 };
+use super::super::streaming::claude::ClaudeUsage;
 use super::super::streaming::process_claude_stream;
 use super::super::utils::{CLAUDE_API_SUFFIX, DEFAULT_CLAUDE_BASE};
 use crate::config::{NetworkConfig, ProviderConfig};
@@ -119,7 +121,9 @@ pub struct ClaudeProvider {
     endpoint: String,
     model: String,
     max_tokens: u32,
-    temperature: f32,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    stop: Option<Vec<String>>,
     max_retries: usize,
     retry_delay_ms: u64,
     max_retry_delay_ms: u64,
@@ -130,7 +134,12 @@ pub struct ClaudeProvider {
 struct ClaudeRequest {
     model: String,
     max_tokens: u32,
-    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     system: Vec<SystemBlock>,
     messages: Vec<MessagePayload>,
@@ -147,6 +156,8 @@ struct MessagePayload {
 #[derive(Deserialize)]
 struct ClaudeResponse {
     content: Vec<ContentBlock>,
+    #[serde(default)]
+    usage: Option<ClaudeUsage>,
 }
 
 #[derive(Deserialize)]
@@ -166,20 +177,31 @@ impl ClaudeProvider {
         network_config: &NetworkConfig,
         colored: bool,
     ) -> Result<Self> {
-        let api_key = extract_api_key(config, "Claude")?;
+        let api_key = extract_api_key(config, provider_name)?;
         let endpoint = build_endpoint(config, DEFAULT_CLAUDE_BASE, CLAUDE_API_SUFFIX);
         let model = config.model.clone();
         let max_tokens = get_max_tokens(config);
-        let temperature = get_temperature(config);
+        let top_p = get_top_p(config);
+        // Recent Claude models reject requests that set both temperature and
+        // top_p, so the default temperature is only sent without top_p
+        let temperature = match top_p {
+            Some(_) => get_temperature_optional(config),
+            None => Some(get_temperature(config)),
+        };
 
         Ok(Self {
             name: provider_name.to_string(),
-            client: super::super::create_http_client(network_config)?,
+            client: super::super::create_http_client_with_proxy(
+                network_config,
+                config.proxy.as_deref(),
+            )?,
             api_key,
             endpoint,
             model,
             max_tokens,
             temperature,
+            top_p,
+            stop: get_stop(config),
             max_retries: network_config.max_retries,
             retry_delay_ms: network_config.retry_delay_ms,
             max_retry_delay_ms: network_config.max_retry_delay_ms,
@@ -194,16 +216,22 @@ impl ApiBackend for ClaudeProvider {
         &self.name
     }
 
+    fn model(&self) -> &str {
+        &self.model
+    }
+
     async fn call_api(
         &self,
         system: &str,
         user_message: &str,
         progress: Option<&dyn crate::llm::ProgressReporter>,
-    ) -> Result<String> {
+    ) -> Result<ApiResponse> {
         let request = ClaudeRequest {
             model: self.model.clone(),
             max_tokens: self.max_tokens,
             temperature: self.temperature,
+            top_p: self.top_p,
+            stop_sequences: self.stop.clone(),
             system: vec![SystemBlock::cached(system)],
             messages: vec![MessagePayload {
                 role: "user".to_string(),
@@ -213,7 +241,7 @@ impl ApiBackend for ClaudeProvider {
         };
 
         tracing::debug!(
-            "Claude API request: model={}, max_tokens={}, temperature={}, system_len={}, user_len={}",
+            "Claude API request: model={}, max_tokens={}, temperature={:?}, system_len={}, user_len={}",
             self.model,
             self.max_tokens,
             self.temperature,
@@ -253,7 +281,10 @@ impl ApiBackend for ClaudeProvider {
             ));
         }
 
-        Ok(text)
+        Ok(ApiResponse {
+            text,
+            usage: response.usage.as_ref().and_then(ClaudeUsage::to_usage),
+        })
     }
 
     fn supports_streaming(&self) -> bool {
@@ -267,6 +298,8 @@ impl ApiBackend for ClaudeProvider {
             model: self.model.clone(),
             max_tokens: self.max_tokens,
             temperature: self.temperature,
+            top_p: self.top_p,
+            stop_sequences: self.stop.clone(),
             system: vec![SystemBlock::cached(system)],
             messages: vec![MessagePayload {
                 role: "user".to_string(),
@@ -276,7 +309,7 @@ impl ApiBackend for ClaudeProvider {
         };
 
         tracing::debug!(
-            "Claude Streaming API request: model={}, max_tokens={}, temperature={}, system_len={}, user_len={}",
+            "Claude Streaming API request: model={}, max_tokens={}, temperature={:?}, system_len={}, user_len={}",
             self.model,
             self.max_tokens,
             self.temperature,
@@ -353,7 +386,9 @@ impl ApiBackend for ClaudeProvider {
         let test_request = ClaudeRequest {
             model: self.model.clone(),
             max_tokens: 1, // Minimize API cost
-            temperature: 1.0,
+            temperature: Some(1.0),
+            top_p: None,
+            stop_sequences: None,
             system: vec![],
             messages: vec![MessagePayload {
                 role: "user".to_string(),
@@ -414,7 +449,7 @@ mod tests {
         .unwrap();
 
         let result = provider.call_api("system", "hi", None).await.unwrap();
-        assert_eq!(result, "Hello\nClaude");
+        assert_eq!(result.text, "Hello\nClaude");
         mock.assert_async().await;
     }
 
@@ -559,7 +594,14 @@ mod tests {
             .call_api("system", "generate commit", None)
             .await
             .unwrap();
-        assert_eq!(result, "feat: add user authentication");
+        assert_eq!(result.text, "feat: add user authentication");
+        assert_eq!(
+            result.usage,
+            Some(crate::llm::metrics::TokenUsage {
+                prompt_tokens: 100,
+                completion_tokens: 10
+            })
+        );
         mock.assert_async().await;
     }
 
@@ -610,7 +652,7 @@ mod tests {
             .await
             .unwrap();
         // thinking block 应该被忽略，只提取 text block
-        assert_eq!(result, "feat(auth): add JWT-based login endpoint");
+        assert_eq!(result.text, "feat(auth): add JWT-based login endpoint");
         mock.assert_async().await;
     }
 
@@ -664,7 +706,48 @@ mod tests {
 
         let result = provider.call_api("system", "hi", None).await.unwrap();
         // thinking 被忽略，两个 text block 用 \n 拼接
-        assert_eq!(result, "First part\nSecond part");
+        assert_eq!(result.text, "First part\nSecond part");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_claude_sampling_parameters() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/messages")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "max_tokens": 256,
+                "top_p": 0.5,
+                "stop_sequences": ["\n\n"]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"content":[{"type":"text","text":"ok"}]}"#)
+            .create_async()
+            .await;
+
+        let provider = ClaudeProvider::new(
+            &{
+                let mut config = test_provider_config(
+                    server.url(),
+                    Some("sk-ant-test".to_string()),
+                    "claude-3-haiku-20240307".to_string(),
+                );
+                config.max_tokens = Some(256);
+                config.top_p = Some(0.5);
+                config.stop = Some(vec!["\n\n".to_string()]);
+                config
+            },
+            "claude",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap();
+        // 只设置 top_p 时不发送默认 temperature
+        assert_eq!(provider.temperature, None);
+
+        provider.call_api("system", "hi", None).await.unwrap();
         mock.assert_async().await;
     }
 }
@@ -0,0 +1,559 @@
+use std::process::Stdio;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc;
+
+use super::super::base::{
+    ApiBackend, ApiResponse, get_max_tokens_optional, get_stop, get_temperature_optional, get_top_p,
+};
+use crate::config::{NetworkConfig, ProviderConfig};
+use crate::error::{GcopError, Result};
+use crate::llm::metrics::{self, TokenUsage};
+use crate::llm::{ProgressReporter, StreamChunk, StreamHandle};
+
+/// Version of the stdin/stdout protocol, sent with every request.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Environment variable holding the provider's `api_key` for the command.
+const API_KEY_VAR: &str = "GCOP_API_KEY";
+
+/// External command provider
+///
+/// Runs a user-supplied executable for every request, so internal gateways
+/// and proprietary APIs can be integrated without changing gcop-rs.
+///
+/// # Configuration example
+/// ```toml
+/// [llm]
+/// default_provider = "gateway"
+///
+/// [llm.providers.gateway]
+/// api_style = "exec"
+/// command = "corp-llm --profile prod"   # or ["corp-llm", "--profile", "prod"]
+/// model = "internal-large"
+/// api_key = "..."                       # optional, passed as $GCOP_API_KEY
+/// stream = true                         # optional, read `data:` chunks as they arrive
+/// ```
+///
+/// # Protocol
+/// One JSON object is written on stdin, which is then closed:
+/// `{"version": 1, "provider", "model", "system", "user", "stream",
+/// "params": {"max_tokens", "temperature", "top_p", "stop"}}`
+/// (unset params are omitted).
+///
+/// The command answers on stdout with one of:
+/// - a JSON object `{"text": "...", "usage": {"prompt_tokens", "completion_tokens"}}`
+///   (`usage` optional)
+/// - SSE-like lines `data: {"delta": "..."}`, ending with `data: [DONE]`;
+///   `data: {"error": "..."}` fails the request and `data: {"usage": {...}}`
+///   reports token counts
+/// - plain text, used as the completion as is
+///
+/// A non-zero exit status fails the request with the command's stderr.
+/// The whole run is bounded by `network.request_timeout`.
+pub struct ExecProvider {
+    name: String,
+    program: String,
+    args: Vec<String>,
+    model: String,
+    api_key: Option<String>,
+    params: ExecParams,
+    stream: bool,
+    timeout: Duration,
+}
+
+/// Generation settings forwarded to the command.
+#[derive(Debug, Clone, Serialize)]
+struct ExecParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct ExecRequest<'a> {
+    version: u32,
+    provider: &'a str,
+    model: &'a str,
+    system: &'a str,
+    user: &'a str,
+    stream: bool,
+    params: &'a ExecParams,
+}
+
+/// Whole-response output.
+#[derive(Deserialize)]
+struct ExecResponse {
+    text: String,
+    #[serde(default)]
+    usage: Option<ExecUsage>,
+}
+
+/// Payload of one `data:` line.
+#[derive(Deserialize)]
+struct ExecChunk {
+    #[serde(default)]
+    delta: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    usage: Option<ExecUsage>,
+}
+
+#[derive(Deserialize)]
+struct ExecUsage {
+    #[serde(default)]
+    prompt_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens: Option<u64>,
+}
+
+impl ExecUsage {
+    fn to_usage(&self) -> Option<TokenUsage> {
+        TokenUsage::from_counts(self.prompt_tokens, self.completion_tokens)
+    }
+}
+
+/// A parsed `data:` line.
+#[derive(Debug, PartialEq)]
+enum ChunkLine {
+    Delta(String),
+    Usage(Option<TokenUsage>),
+    Error(String),
+    Done,
+}
+
+impl ExecProvider {
+    /// Builds an exec provider from runtime configuration.
+    ///
+    /// `command` (in the provider's extra settings) is either a string split
+    /// like a shell command line or an array of arguments.
+    pub fn new(
+        config: &ProviderConfig,
+        provider_name: &str,
+        network_config: &NetworkConfig,
+    ) -> Result<Self> {
+        let mut words = command_words(config, provider_name)?.into_iter();
+        let program = words.next().ok_or_else(|| no_command(provider_name))?;
+        Ok(Self {
+            name: provider_name.to_string(),
+            program,
+            args: words.collect(),
+            model: config.model.clone(),
+            api_key: config.api_key.clone().filter(|key| !key.trim().is_empty()),
+            params: ExecParams {
+                max_tokens: get_max_tokens_optional(config),
+                temperature: get_temperature_optional(config),
+                top_p: get_top_p(config),
+                stop: get_stop(config),
+            },
+            stream: config
+                .extra
+                .get("stream")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            timeout: Duration::from_secs(network_config.request_timeout),
+        })
+    }
+
+    /// Starts the command and writes the request on its stdin in the background.
+    fn spawn(&self, system: &str, user: &str, stream: bool) -> Result<Child> {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(key) = &self.api_key {
+            command.env(API_KEY_VAR, key);
+        }
+        let mut child = command.spawn().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                self.not_found()
+            } else {
+                exec_error(&self.name, e)
+            }
+        })?;
+
+        let request = serde_json::to_vec(&ExecRequest {
+            version: PROTOCOL_VERSION,
+            provider: &self.name,
+            model: &self.model,
+            system,
+            user,
+            stream,
+            params: &self.params,
+        })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // A command that exits without reading stdin is not an error by itself
+            tokio::spawn(async move {
+                if let Err(e) = stdin.write_all(&request).await {
+                    tracing::debug!("Failed to write exec provider request: {}", e);
+                }
+            });
+        }
+        Ok(child)
+    }
+
+    fn not_found(&self) -> GcopError {
+        GcopError::Config(
+            rust_i18n::t!(
+                "provider.exec_not_found",
+                provider = self.name.as_str(),
+                program = self.program.as_str()
+            )
+            .to_string(),
+        )
+    }
+
+    fn timed_out(&self) -> GcopError {
+        exec_error(
+            &self.name,
+            rust_i18n::t!("provider.exec_timeout", seconds = self.timeout.as_secs()),
+        )
+    }
+}
+
+fn command_words(config: &ProviderConfig, provider_name: &str) -> Result<Vec<String>> {
+    match config.extra.get("command") {
+        Some(serde_json::Value::String(command)) => shell_words::split(command)
+            .map_err(|e| GcopError::Config(format!("{}: {}", no_command(provider_name), e))),
+        Some(serde_json::Value::Array(words)) => words
+            .iter()
+            .map(|word| word.as_str().map(str::to_string))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| no_command(provider_name)),
+        _ => Err(no_command(provider_name)),
+    }
+}
+
+fn no_command(provider_name: &str) -> GcopError {
+    GcopError::Config(
+        rust_i18n::t!("provider.exec_no_command", provider = provider_name).to_string(),
+    )
+}
+
+/// Error for a command that exited unsuccessfully.
+fn exit_error(provider_name: &str, status: std::process::ExitStatus, stderr: &[u8]) -> GcopError {
+    exec_error(
+        provider_name,
+        rust_i18n::t!(
+            "provider.exec_status",
+            status = status.to_string(),
+            stderr = String::from_utf8_lossy(stderr).trim()
+        ),
+    )
+}
+
+fn exec_error(provider_name: &str, error: impl std::fmt::Display) -> GcopError {
+    GcopError::Llm(
+        rust_i18n::t!(
+            "provider.exec_failed",
+            provider = provider_name,
+            error = error.to_string()
+        )
+        .to_string(),
+    )
+}
+
+/// Parses a `data:` line; `None` for any other line.
+fn parse_chunk_line(line: &str) -> Option<ChunkLine> {
+    let data = line.strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return Some(ChunkLine::Done);
+    }
+    Some(match serde_json::from_str::<ExecChunk>(data) {
+        Ok(ExecChunk {
+            error: Some(error), ..
+        }) => ChunkLine::Error(error),
+        Ok(ExecChunk {
+            delta: Some(delta), ..
+        }) => ChunkLine::Delta(delta),
+        Ok(ExecChunk { usage, .. }) => {
+            ChunkLine::Usage(usage.as_ref().and_then(ExecUsage::to_usage))
+        }
+        // Not JSON: the data is the text itself
+        Err(_) => ChunkLine::Delta(data.to_string()),
+    })
+}
+
+/// Reads the complete stdout of a non-streaming run.
+fn parse_output(provider_name: &str, stdout: &str) -> Result<ApiResponse> {
+    if stdout.lines().any(|line| line.starts_with("data:")) {
+        let mut text = String::new();
+        let mut usage = None;
+        for line in stdout.lines() {
+            match parse_chunk_line(line) {
+                Some(ChunkLine::Delta(delta)) => text.push_str(&delta),
+                Some(ChunkLine::Usage(reported)) => usage = reported.or(usage),
+                Some(ChunkLine::Error(error)) => return Err(exec_error(provider_name, error)),
+                Some(ChunkLine::Done) => break,
+                None => {}
+            }
+        }
+        return Ok(ApiResponse { text, usage });
+    }
+
+    if stdout.trim_start().starts_with('{')
+        && let Ok(response) = serde_json::from_str::<ExecResponse>(stdout)
+    {
+        return Ok(ApiResponse {
+            text: response.text,
+            usage: response.usage.as_ref().and_then(ExecUsage::to_usage),
+        });
+    }
+
+    Ok(ApiResponse {
+        text: stdout.trim_end().to_string(),
+        usage: None,
+    })
+}
+
+#[async_trait]
+impl ApiBackend for ExecProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn call_api(
+        &self,
+        system: &str,
+        user_message: &str,
+        _progress: Option<&dyn ProgressReporter>,
+    ) -> Result<ApiResponse> {
+        tracing::debug!(
+            "Exec provider: program={}, model={}, system_len={}, user_len={}",
+            self.program,
+            self.model,
+            system.len(),
+            user_message.len()
+        );
+        let child = self.spawn(system, user_message, false)?;
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| self.timed_out())?
+            .map_err(|e| exec_error(&self.name, e))?;
+        if !output.status.success() {
+            return Err(exit_error(&self.name, output.status, &output.stderr));
+        }
+        parse_output(&self.name, &String::from_utf8_lossy(&output.stdout))
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.stream
+    }
+
+    async fn call_api_streaming(&self, system: &str, user_message: &str) -> Result<StreamHandle> {
+        let mut child = self.spawn(system, user_message, true)?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| exec_error(&self.name, "no stdout"))?;
+        let mut stderr = child.stderr.take();
+        let (tx, rx) = mpsc::channel(64);
+
+        let provider = self.name.clone();
+        let timeout = self.timeout;
+        let timed_out = self.timed_out();
+        tokio::spawn(async move {
+            // Drained concurrently so a chatty command cannot block on a full pipe
+            let stderr_task = tokio::spawn(async move {
+                let mut buffer = Vec::new();
+                if let Some(stderr) = stderr.as_mut() {
+                    let _ = stderr.read_to_end(&mut buffer).await;
+                }
+                buffer
+            });
+
+            let result = tokio::time::timeout(timeout, async {
+                let mut lines = BufReader::new(stdout).lines();
+                let mut usage = None;
+                while let Some(line) = lines.next_line().await? {
+                    // The receiver is gone (e.g. this provider lost a race): stop reading.
+                    if tx.is_closed() {
+                        return Ok(false);
+                    }
+                    match parse_chunk_line(&line) {
+                        Some(ChunkLine::Delta(delta)) => {
+                            let _ = tx.send(StreamChunk::Delta(delta)).await;
+                        }
+                        Some(ChunkLine::Usage(reported)) => usage = reported.or(usage),
+                        Some(ChunkLine::Error(error)) => {
+                            let _ = tx.send(StreamChunk::Error(error)).await;
+                            return Ok(false);
+                        }
+                        Some(ChunkLine::Done) => break,
+                        // Plain text streams line by line
+                        None => {
+                            let _ = tx.send(StreamChunk::Delta(format!("{}\n", line))).await;
+                        }
+                    }
+                }
+                metrics::record_call(usage);
+                Ok::<bool, std::io::Error>(true)
+            })
+            .await;
+
+            let error = match result {
+                Err(_) => Some(timed_out.to_string()),
+                Ok(Err(e)) => Some(e.to_string()),
+                Ok(Ok(false)) => return,
+                Ok(Ok(true)) => match child.wait().await {
+                    Ok(status) if status.success() => None,
+                    Ok(status) => Some(
+                        exit_error(&provider, status, &stderr_task.await.unwrap_or_default())
+                            .to_string(),
+                    ),
+                    Err(e) => Some(e.to_string()),
+                },
+            };
+            let chunk = match error {
+                Some(error) => StreamChunk::Error(error),
+                None => StreamChunk::Done,
+            };
+            let _ = tx.send(chunk).await;
+        });
+
+        Ok(StreamHandle { receiver: rx })
+    }
+
+    async fn validate(&self) -> Result<()> {
+        which::which(&self.program)
+            .map(|_| ())
+            .map_err(|_| self.not_found())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::LLMProvider;
+    use crate::llm::provider::test_utils::test_provider_config;
+
+    fn exec_config(command: serde_json::Value) -> ProviderConfig {
+        let mut config = test_provider_config(String::new(), None, "internal".to_string());
+        config.extra.insert("command".to_string(), command);
+        config
+    }
+
+    fn provider(command: serde_json::Value) -> ExecProvider {
+        ExecProvider::new(&exec_config(command), "gateway", &NetworkConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_command_string_and_array() {
+        let p = provider(serde_json::json!("corp-llm --profile 'prod eu'"));
+        assert_eq!(p.program, "corp-llm");
+        assert_eq!(p.args, vec!["--profile", "prod eu"]);
+
+        let p = provider(serde_json::json!(["corp-llm", "--fast"]));
+        assert_eq!(p.args, vec!["--fast"]);
+
+        // 缺少 command 是配置错误
+        let config = test_provider_config(String::new(), None, "m".to_string());
+        assert!(matches!(
+            ExecProvider::new(&config, "gateway", &NetworkConfig::default()),
+            Err(GcopError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_output_formats() {
+        let json = parse_output(
+            "gateway",
+            r#"{"text": "feat: add exec", "usage": {"prompt_tokens": 10, "completion_tokens": 3}}"#,
+        )
+        .unwrap();
+        assert_eq!(json.text, "feat: add exec");
+        assert_eq!(json.usage.unwrap().completion_tokens, 3);
+
+        let chunks = parse_output(
+            "gateway",
+            "data: {\"delta\": \"feat: \"}\ndata: {\"delta\": \"add exec\"}\ndata: [DONE]\n",
+        )
+        .unwrap();
+        assert_eq!(chunks.text, "feat: add exec");
+
+        let plain = parse_output("gateway", "fix: plain text\n").unwrap();
+        assert_eq!(plain.text, "fix: plain text");
+
+        assert!(matches!(
+            parse_output("gateway", "data: {\"error\": \"quota exceeded\"}\n"),
+            Err(GcopError::Llm(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_send_prompt_round_trip_through_command() {
+        // 命令从 stdin 读取请求并原样回显 user 字段，验证协议两端
+        let p = provider(serde_json::json!([
+            "sh",
+            "-c",
+            "read -r line; printf '%s' \"$line\" | sed 's/.*\"user\":\"\\([^\"]*\\)\".*/\\1/'"
+        ]));
+        let text = p.send_prompt("system", "hello-exec", None).await.unwrap();
+        assert_eq!(text, "hello-exec");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_non_zero_exit_reports_stderr() {
+        let p = provider(serde_json::json!([
+            "sh",
+            "-c",
+            "echo gateway down >&2; exit 3"
+        ]));
+        let err = p.send_prompt("s", "u", None).await.unwrap_err();
+        assert!(err.to_string().contains("gateway down"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_streaming_chunks() {
+        let mut config = exec_config(serde_json::json!([
+            "sh",
+            "-c",
+            "cat >/dev/null; echo 'data: {\"delta\": \"a\"}'; echo 'data: {\"delta\": \"b\"}'; echo 'data: [DONE]'"
+        ]));
+        config
+            .extra
+            .insert("stream".to_string(), serde_json::json!(true));
+        let p = ExecProvider::new(&config, "gateway", &NetworkConfig::default()).unwrap();
+        assert!(ApiBackend::supports_streaming(&p));
+
+        let mut handle = p.call_api_streaming("s", "u").await.unwrap();
+        let mut text = String::new();
+        while let Some(chunk) = handle.receiver.recv().await {
+            match chunk {
+                StreamChunk::Delta(delta) => text.push_str(&delta),
+                StreamChunk::Done => break,
+                other => panic!("unexpected chunk: {:?}", other),
+            }
+        }
+        assert_eq!(text, "ab");
+    }
+
+    #[tokio::test]
+    async fn test_validate_missing_program() {
+        let p = provider(serde_json::json!("gcop-no-such-program-xyz"));
+        assert!(matches!(
+            ApiBackend::validate(&p).await,
+            Err(GcopError::Config(_))
+        ));
+    }
+}
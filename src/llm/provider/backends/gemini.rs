@@ -4,9 +4,11 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use super::super::base::{
-    ApiBackend, extract_api_key, get_max_tokens_optional, get_temperature, send_llm_request,
-    send_llm_request_streaming, validate_api_key, validate_http_endpoint,
+    ApiBackend, ApiResponse, extract_api_key, get_max_tokens_optional, get_stop, get_temperature,
+    get_top_p, resolve_base_url, send_llm_request, send_llm_request_streaming, validate_api_key,
+    validate_http_endpoint,
 };
+use super::super::streaming::gemini::GeminiUsageMetadata;
 use super::super::streaming::process_gemini_stream;
 use super::super::utils::DEFAULT_GEMINI_BASE;
 use crate::config::{NetworkConfig, ProviderConfig};
@@ -46,10 +48,13 @@ pub struct GeminiProvider {
     model: String,
     max_output_tokens: Option<u32>,
     temperature: f32,
+    top_p: Option<f32>,
+    stop_sequences: Option<Vec<String>>,
     max_retries: usize,
     retry_delay_ms: u64,
     max_retry_delay_ms: u64,
     colored: bool,
+    structured_output: bool,
 }
 
 // ============================================================================
@@ -83,11 +88,22 @@ struct GenerationConfig {
     temperature: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_json_schema: Option<serde_json::Value>,
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct GeminiResponse {
     candidates: Option<Vec<GeminiCandidate>>,
+    #[serde(default)]
+    usage_metadata: Option<GeminiUsageMetadata>,
 }
 
 #[derive(Deserialize)]
@@ -123,11 +139,8 @@ impl GeminiProvider {
         network_config: &NetworkConfig,
         colored: bool,
     ) -> Result<Self> {
-        let api_key = extract_api_key(config, "Gemini")?;
-        let base_url = config
-            .endpoint
-            .as_deref()
-            .unwrap_or(DEFAULT_GEMINI_BASE)
+        let api_key = extract_api_key(config, provider_name)?;
+        let base_url = resolve_base_url(config, DEFAULT_GEMINI_BASE)
             .trim_end_matches('/')
             .to_string();
         let model = config.model.clone();
@@ -136,19 +149,31 @@ impl GeminiProvider {
 
         Ok(Self {
             name: provider_name.to_string(),
-            client: super::super::create_http_client(network_config)?,
+            client: super::super::create_http_client_with_proxy(
+                network_config,
+                config.proxy.as_deref(),
+            )?,
             api_key,
             base_url,
             model,
             max_output_tokens,
             temperature,
+            top_p: get_top_p(config),
+            stop_sequences: get_stop(config),
             max_retries: network_config.max_retries,
             retry_delay_ms: network_config.retry_delay_ms,
             max_retry_delay_ms: network_config.max_retry_delay_ms,
             colored,
+            structured_output: false,
         })
     }
 
+    /// Enables `responseJsonSchema` constrained output for review requests.
+    pub fn with_structured_output(mut self, enabled: bool) -> Self {
+        self.structured_output = enabled;
+        self
+    }
+
     /// Non-streaming endpoint: /v1beta/models/{model}:generateContent
     fn generate_content_url(&self) -> String {
         format!(
@@ -182,40 +207,25 @@ impl GeminiProvider {
             generation_config: GenerationConfig {
                 temperature: self.temperature,
                 max_output_tokens: self.max_output_tokens,
+                top_p: self.top_p,
+                stop_sequences: self.stop_sequences.clone(),
+                response_mime_type: None,
+                response_json_schema: None,
             },
         }
     }
-}
-
-#[async_trait]
-impl ApiBackend for GeminiProvider {
-    fn name(&self) -> &str {
-        &self.name
-    }
 
-    async fn call_api(
+    async fn send_generate_content(
         &self,
-        system: &str,
-        user_message: &str,
+        request: &GeminiRequest,
         progress: Option<&dyn crate::llm::ProgressReporter>,
-    ) -> Result<String> {
-        let request = self.build_request(system, user_message);
-
-        tracing::debug!(
-            "Gemini API request: model={}, temperature={}, max_output_tokens={:?}, system_len={}, user_len={}",
-            self.model,
-            self.temperature,
-            self.max_output_tokens,
-            system.len(),
-            user_message.len()
-        );
-
+    ) -> Result<ApiResponse> {
         let endpoint = self.generate_content_url();
         let response: GeminiResponse = send_llm_request(
             &self.client,
             &endpoint,
             &[("x-goog-api-key", self.api_key.as_str())],
-            &request,
+            request,
             "Gemini",
             progress,
             self.max_retries,
@@ -224,6 +234,7 @@ impl ApiBackend for GeminiProvider {
         )
         .await?;
 
+        let usage = response.usage_metadata.as_ref().and_then(|u| u.to_usage());
         let candidate = response
             .candidates
             .and_then(|c| c.into_iter().next())
@@ -252,11 +263,70 @@ impl ApiBackend for GeminiProvider {
             .content
             .and_then(|c| c.parts)
             .and_then(|parts| parts.into_iter().next())
-            .map(|p| p.text)
+            .map(|p| ApiResponse {
+                text: p.text,
+                usage,
+            })
             .ok_or_else(|| {
                 GcopError::Llm(rust_i18n::t!("provider.gemini_no_candidates").to_string())
             })
     }
+}
+
+#[async_trait]
+impl ApiBackend for GeminiProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn call_api(
+        &self,
+        system: &str,
+        user_message: &str,
+        progress: Option<&dyn crate::llm::ProgressReporter>,
+    ) -> Result<ApiResponse> {
+        let request = self.build_request(system, user_message);
+
+        tracing::debug!(
+            "Gemini API request: model={}, temperature={}, max_output_tokens={:?}, system_len={}, user_len={}",
+            self.model,
+            self.temperature,
+            self.max_output_tokens,
+            system.len(),
+            user_message.len()
+        );
+
+        self.send_generate_content(&request, progress).await
+    }
+
+    fn structured_output_enabled(&self) -> bool {
+        self.structured_output
+    }
+
+    async fn call_api_structured(
+        &self,
+        system: &str,
+        user_message: &str,
+        schema: &serde_json::Value,
+        progress: Option<&dyn crate::llm::ProgressReporter>,
+    ) -> Result<ApiResponse> {
+        let mut request = self.build_request(system, user_message);
+        request.generation_config.response_mime_type = Some("application/json".to_string());
+        request.generation_config.response_json_schema = Some(schema.clone());
+
+        tracing::debug!(
+            "Gemini structured API request: model={}, system_len={}, user_len={}",
+            self.model,
+            system.len(),
+            user_message.len()
+        );
+
+        self.send_generate_content(&request, progress).await
+    }
 
     fn supports_streaming(&self) -> bool {
         true
@@ -346,6 +416,10 @@ impl ApiBackend for GeminiProvider {
             generation_config: GenerationConfig {
                 temperature: 1.0,
                 max_output_tokens: Some(1), // Minimize API cost
+                top_p: None,
+                stop_sequences: None,
+                response_mime_type: None,
+                response_json_schema: None,
             },
         };
         let endpoint = self.generate_content_url();
@@ -399,7 +473,49 @@ mod tests {
         .unwrap();
 
         let result = provider.call_api("system", "hi", None).await.unwrap();
-        assert_eq!(result, "Hello from Gemini");
+        assert_eq!(result.text, "Hello from Gemini");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_gemini_structured_review_sends_response_schema() {
+        use crate::llm::{LLMProvider, ReviewType};
+        use mockito::Matcher;
+
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1beta/models/gemini-3-flash-preview:generateContent")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "generationConfig": { "responseMimeType": "application/json" }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"candidates":[{"content":{"parts":[{"text":"{\"summary\":\"fine\",\"issues\":[],\"suggestions\":[\"add tests\"]}"}]},"finishReason":"STOP"}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = GeminiProvider::new(
+            &test_provider_config(
+                server.url(),
+                Some("AIza-test".to_string()),
+                "gemini-3-flash-preview".to_string(),
+            ),
+            "gemini",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap()
+        .with_structured_output(true);
+
+        let result = provider
+            .review_code("diff", ReviewType::UncommittedChanges, None, None)
+            .await
+            .unwrap();
+        assert_eq!(result.summary, "fine");
+        assert_eq!(result.suggestions, vec!["add tests".to_string()]);
         mock.assert_async().await;
     }
 
@@ -534,4 +650,49 @@ mod tests {
         assert!(matches!(err, GcopError::Llm(_)));
         mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn test_gemini_sampling_parameters() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock(
+                "POST",
+                "/v1beta/models/gemini-3-flash-preview:generateContent",
+            )
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "systemInstruction": { "parts": [{ "text": "system" }] },
+                "generationConfig": {
+                    "maxOutputTokens": 256,
+                    "topP": 0.5,
+                    "stopSequences": ["\n\n"]
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"candidates":[{"content":{"parts":[{"text":"ok"}]}}]}"#)
+            .create_async()
+            .await;
+
+        let provider = GeminiProvider::new(
+            &{
+                let mut config = test_provider_config(
+                    server.url(),
+                    Some("AIza-test".to_string()),
+                    "gemini-3-flash-preview".to_string(),
+                );
+                config.max_tokens = Some(256);
+                config.top_p = Some(0.5);
+                config.stop = Some(vec!["\n\n".to_string()]);
+                config
+            },
+            "gemini",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap();
+
+        provider.call_api("system", "hi", None).await.unwrap();
+        mock.assert_async().await;
+    }
 }
@@ -0,0 +1,348 @@
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use async_trait::async_trait;
+use llama_cpp_2::context::params::LlamaContextParams;
+use llama_cpp_2::llama_backend::LlamaBackend;
+use llama_cpp_2::llama_batch::LlamaBatch;
+use llama_cpp_2::model::params::LlamaModelParams;
+use llama_cpp_2::model::{AddBos, LlamaChatMessage, LlamaModel};
+use llama_cpp_2::sampling::LlamaSampler;
+use llama_cpp_2::token::LlamaToken;
+
+use super::super::base::{
+    ApiBackend, ApiResponse, extract_extra_u32, get_max_tokens, get_temperature,
+};
+use crate::config::{ProviderConfig, expand_home};
+use crate::error::{GcopError, Result};
+
+/// Default context window in tokens (capped by the model's training context)
+const DEFAULT_CONTEXT_SIZE: u32 = 8192;
+
+/// Process-wide llama.cpp backend (may only be initialized once)
+static BACKEND: OnceLock<std::result::Result<LlamaBackend, String>> = OnceLock::new();
+
+/// Local GGUF model provider (llama.cpp, in-process)
+///
+/// Runs inference inside the gcop-rs process through llama.cpp, so no HTTP
+/// server or network access is needed. Prompts go through the same pipeline
+/// as the remote providers; the model's embedded chat template formats them.
+///
+/// Only available when built with the `local` cargo feature.
+///
+/// # Configuration example
+/// ```toml
+/// [llm]
+/// default_provider = "local"
+///
+/// [llm.providers.local]
+/// api_style = "local"
+/// model = "~/models/qwen2.5-coder-7b-instruct-q4_k_m.gguf"
+/// max_tokens = 2000       # optional
+/// temperature = 0.3       # optional; 0 = greedy decoding
+/// context_size = 8192     # optional
+/// gpu_layers = 0          # optional; layers offloaded to the GPU
+/// threads = 8             # optional; defaults to llama.cpp's choice
+/// ```
+///
+/// # Features
+/// - No network access and no API key
+/// - The model is loaded on first use and kept for the rest of the process
+/// - No streaming support (the response is returned when generation ends)
+pub struct LocalProvider {
+    name: String,
+    model_path: PathBuf,
+    max_tokens: u32,
+    temperature: f32,
+    context_size: u32,
+    gpu_layers: u32,
+    threads: Option<u32>,
+    model: Mutex<Option<Arc<LlamaModel>>>,
+}
+
+impl LocalProvider {
+    /// Builds a local provider from runtime configuration.
+    ///
+    /// The model file is not read until the first request.
+    pub fn new(config: &ProviderConfig, provider_name: &str) -> Result<Self> {
+        Ok(Self {
+            name: provider_name.to_string(),
+            model_path: expand_home(&config.model),
+            max_tokens: get_max_tokens(config),
+            temperature: get_temperature(config),
+            context_size: extract_extra_u32(config, "context_size").unwrap_or(DEFAULT_CONTEXT_SIZE),
+            gpu_layers: extract_extra_u32(config, "gpu_layers").unwrap_or(0),
+            threads: extract_extra_u32(config, "threads"),
+            model: Mutex::new(None),
+        })
+    }
+
+    /// Returns the loaded model, loading it on first use.
+    fn load_model(&self) -> Result<Arc<LlamaModel>> {
+        let mut slot = self
+            .model
+            .lock()
+            .map_err(|_| local_error("model lock poisoned"))?;
+        if let Some(model) = slot.as_ref() {
+            return Ok(Arc::clone(model));
+        }
+
+        validate_model_path(&self.model_path)?;
+
+        tracing::debug!(
+            "Loading GGUF model {} (gpu_layers={})",
+            self.model_path.display(),
+            self.gpu_layers
+        );
+        let params = LlamaModelParams::default().with_n_gpu_layers(self.gpu_layers);
+        let model = LlamaModel::load_from_file(backend()?, &self.model_path, &params)
+            .map_err(local_error)?;
+        let model = Arc::new(model);
+        *slot = Some(Arc::clone(&model));
+        Ok(model)
+    }
+}
+
+/// Initializes the llama.cpp backend once, routing its logs to `tracing`.
+fn backend() -> Result<&'static LlamaBackend> {
+    BACKEND
+        .get_or_init(|| {
+            llama_cpp_2::send_logs_to_tracing(llama_cpp_2::LogOptions::default());
+            LlamaBackend::init().map_err(|e| e.to_string())
+        })
+        .as_ref()
+        .map_err(local_error)
+}
+
+fn local_error(error: impl std::fmt::Display) -> GcopError {
+    GcopError::Llm(rust_i18n::t!("provider.local_failed", error = error.to_string()).to_string())
+}
+
+/// Sampling and context settings for one generation.
+struct GenerationParams {
+    max_tokens: u32,
+    temperature: f32,
+    context_size: u32,
+    threads: Option<u32>,
+}
+
+/// Formats the prompt with the model's chat template.
+///
+/// Falls back to plain concatenation for models without a template.
+fn format_prompt(model: &LlamaModel, system: &str, user_message: &str) -> String {
+    let templated = model.chat_template(None).ok().and_then(|template| {
+        let messages = vec![
+            LlamaChatMessage::new("system".to_string(), system.to_string()).ok()?,
+            LlamaChatMessage::new("user".to_string(), user_message.to_string()).ok()?,
+        ];
+        model.apply_chat_template(&template, &messages, true).ok()
+    });
+    templated.unwrap_or_else(|| plain_prompt(system, user_message))
+}
+
+/// Prompt layout used when the model has no chat template.
+fn plain_prompt(system: &str, user_message: &str) -> String {
+    format!("{}\n\n{}\n\n", system.trim_end(), user_message.trim_end())
+}
+
+/// Runs prompt processing and token generation (blocking).
+fn generate(model: &LlamaModel, prompt: &str, params: &GenerationParams) -> Result<String> {
+    let backend = backend()?;
+    let tokens = model
+        .str_to_token(prompt, AddBos::Always)
+        .map_err(local_error)?;
+
+    let n_ctx = params.context_size.min(model.n_ctx_train()).max(1);
+    if tokens.len() + params.max_tokens as usize > n_ctx as usize {
+        return Err(GcopError::Llm(
+            rust_i18n::t!(
+                "provider.local_prompt_too_long",
+                tokens = tokens.len(),
+                max_tokens = params.max_tokens,
+                context = n_ctx
+            )
+            .to_string(),
+        ));
+    }
+
+    let mut ctx_params = LlamaContextParams::default()
+        .with_n_ctx(NonZeroU32::new(n_ctx))
+        .with_n_batch(n_ctx);
+    if let Some(threads) = params.threads {
+        let threads = threads as i32;
+        ctx_params = ctx_params
+            .with_n_threads(threads)
+            .with_n_threads_batch(threads);
+    }
+    let mut ctx = model
+        .new_context(backend, ctx_params)
+        .map_err(local_error)?;
+
+    let mut batch = LlamaBatch::new(n_ctx as usize, 1);
+    let last = tokens.len() - 1;
+    for (i, token) in tokens.iter().enumerate() {
+        batch
+            .add(*token, i as i32, &[0], i == last)
+            .map_err(local_error)?;
+    }
+    ctx.decode(&mut batch).map_err(local_error)?;
+
+    let mut sampler = if params.temperature <= 0.0 {
+        LlamaSampler::greedy()
+    } else {
+        LlamaSampler::chain_simple([
+            LlamaSampler::temp(params.temperature),
+            LlamaSampler::dist(rand_seed()),
+        ])
+    };
+
+    let mut output = Vec::new();
+    let mut pos = tokens.len() as i32;
+    for _ in 0..params.max_tokens {
+        let token = sampler.sample(&ctx, batch.n_tokens() - 1);
+        sampler.accept(token);
+        if model.is_eog_token(token) {
+            break;
+        }
+        output.extend(token_bytes(model, token)?);
+
+        batch.clear();
+        batch.add(token, pos, &[0], true).map_err(local_error)?;
+        ctx.decode(&mut batch).map_err(local_error)?;
+        pos += 1;
+    }
+
+    Ok(String::from_utf8_lossy(&output).into_owned())
+}
+
+/// Decodes a token to raw bytes (multi-byte characters may span tokens).
+fn token_bytes(model: &LlamaModel, token: LlamaToken) -> Result<Vec<u8>> {
+    use llama_cpp_2::TokenToStringError;
+    match model.token_to_piece_bytes(token, 32, false, None) {
+        Err(TokenToStringError::InsufficientBufferSpace(needed)) => model
+            .token_to_piece_bytes(token, needed.unsigned_abs() as usize, false, None)
+            .map_err(local_error),
+        other => other.map_err(local_error),
+    }
+}
+
+/// Sampling seed derived from the clock (a fixed seed would repeat outputs on retry).
+fn rand_seed() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default()
+}
+
+#[async_trait]
+impl ApiBackend for LocalProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn model(&self) -> &str {
+        self.model_path.to_str().unwrap_or_default()
+    }
+
+    async fn call_api(
+        &self,
+        system: &str,
+        user_message: &str,
+        _progress: Option<&dyn crate::llm::ProgressReporter>,
+    ) -> Result<ApiResponse> {
+        let model = self.load_model()?;
+        let params = GenerationParams {
+            max_tokens: self.max_tokens,
+            temperature: self.temperature,
+            context_size: self.context_size,
+            threads: self.threads,
+        };
+        let system = system.to_string();
+        let user_message = user_message.to_string();
+
+        tracing::debug!(
+            "Local inference: model={}, temperature={}, max_tokens={}, system_len={}, user_len={}",
+            self.model_path.display(),
+            self.temperature,
+            self.max_tokens,
+            system.len(),
+            user_message.len()
+        );
+
+        tokio::task::spawn_blocking(move || {
+            let prompt = format_prompt(&model, &system, &user_message);
+            generate(&model, &prompt, &params)
+        })
+        .await
+        .map_err(local_error)?
+        .map(|text| ApiResponse { text, usage: None })
+    }
+
+    async fn validate(&self) -> Result<()> {
+        validate_model_path(&self.model_path)
+    }
+}
+
+/// Checks that the configured GGUF file exists (without loading it).
+fn validate_model_path(path: &Path) -> Result<()> {
+    if path.is_file() {
+        Ok(())
+    } else {
+        Err(GcopError::Config(
+            rust_i18n::t!("provider.local_model_not_found", path = path.display()).to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::provider::test_utils::test_provider_config;
+
+    fn local_config(model: &str) -> ProviderConfig {
+        let mut config = test_provider_config(String::new(), None, model.to_string());
+        config.endpoint = None;
+        config
+    }
+
+    #[test]
+    fn test_new_reads_extra_settings() {
+        let mut config = local_config("/models/coder.gguf");
+        config
+            .extra
+            .insert("context_size".to_string(), serde_json::json!(4096));
+        config
+            .extra
+            .insert("gpu_layers".to_string(), serde_json::json!(20));
+
+        let provider = LocalProvider::new(&config, "local").unwrap();
+        assert_eq!(provider.model_path, PathBuf::from("/models/coder.gguf"));
+        assert_eq!(provider.context_size, 4096);
+        assert_eq!(provider.gpu_layers, 20);
+        assert_eq!(provider.threads, None);
+    }
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("/abs/m.gguf"), PathBuf::from("/abs/m.gguf"));
+        let expanded = expand_home("~/m.gguf");
+        assert!(expanded.ends_with("m.gguf"));
+        assert!(!expanded.starts_with("~"));
+    }
+
+    #[test]
+    fn test_plain_prompt() {
+        assert_eq!(plain_prompt("sys\n", "user"), "sys\n\nuser\n\n");
+    }
+
+    #[tokio::test]
+    async fn test_validate_missing_model_is_config_error() {
+        let config = local_config("/nonexistent/model.gguf");
+        let provider = LocalProvider::new(&config, "local").unwrap();
+        assert!(matches!(
+            provider.validate().await,
+            Err(GcopError::Config(_))
+        ));
+    }
+}
@@ -1,9 +1,15 @@
 pub mod claude;
+pub mod exec;
 pub mod gemini;
+#[cfg(feature = "local")]
+pub mod local;
 pub mod ollama;
 pub mod openai;
 
 pub use claude::ClaudeProvider;
+pub use exec::ExecProvider;
 pub use gemini::GeminiProvider;
+#[cfg(feature = "local")]
+pub use local::LocalProvider;
 pub use ollama::OllamaProvider;
 pub use openai::OpenAIProvider;
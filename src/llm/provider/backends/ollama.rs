@@ -1,11 +1,17 @@
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use super::super::base::{ApiBackend, build_endpoint, get_temperature_optional, send_llm_request};
+use super::super::base::{
+    ApiBackend, ApiResponse, build_endpoint, get_max_tokens_optional, get_stop,
+    get_temperature_optional, get_top_p, send_llm_request,
+};
 use super::super::utils::{DEFAULT_OLLAMA_BASE, OLLAMA_API_SUFFIX};
 use crate::config::{NetworkConfig, ProviderConfig};
 use crate::error::{GcopError, Result};
+use crate::llm::ProgressReporter;
+use crate::llm::metrics::TokenUsage;
 
 /// Ollama API provider
 ///
@@ -24,6 +30,7 @@ use crate::error::{GcopError, Result};
 /// model = "llama3.2"
 /// endpoint = "http://localhost:11434" # Optional base URL or full /api/generate path
 /// temperature = 0.7 # optional
+/// max_tokens = 500 # optional, sent as options.num_predict
 /// ```
 ///
 /// # Configuration method
@@ -38,6 +45,9 @@ use crate::error::{GcopError, Result};
 /// - Support custom models
 /// - Automatic retries (exponential backoff, default 3 times, configurable through `network.max_retries`)
 /// - No streaming support (planned)
+/// - Model management: [`list_models`](Self::list_models) and
+///   [`pull_model`](Self::pull_model) (`gcop-rs provider ollama list-models`,
+///   pull offer in `gcop-rs config validate`)
 ///
 /// # Prerequisites for use
 /// 1. Install Ollama: <https://ollama.ai>
@@ -71,6 +81,9 @@ pub struct OllamaProvider {
     endpoint: String,
     model: String,
     temperature: Option<f32>,
+    num_predict: Option<u32>,
+    top_p: Option<f32>,
+    stop: Option<Vec<String>>,
     max_retries: usize,
     retry_delay_ms: u64,
     max_retry_delay_ms: u64,
@@ -93,6 +106,12 @@ struct OllamaRequest {
 struct OllamaOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
@@ -100,6 +119,72 @@ struct OllamaResponse {
     response: String,
     #[allow(dead_code)] // Reserved for integrity verification
     done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+/// Installed model as reported by `/api/tags`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OllamaModel {
+    /// Model name including its tag, e.g. `llama3.2:latest`.
+    pub name: String,
+    /// Size on disk in bytes.
+    #[serde(default)]
+    pub size: u64,
+    /// Last modification time (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<OllamaModel>,
+}
+
+#[derive(Serialize)]
+struct PullRequest<'a> {
+    model: &'a str,
+    stream: bool,
+}
+
+/// One line of the streamed `/api/pull` response.
+#[derive(Deserialize)]
+struct PullStatus {
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    total: Option<u64>,
+    #[serde(default)]
+    completed: Option<u64>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl PullStatus {
+    /// Spinner suffix, e.g. `(pulling 8eeb52dfb3bb 42%)`.
+    fn describe(&self) -> String {
+        match (self.total, self.completed) {
+            (Some(total), Some(completed)) if total > 0 => {
+                format!("({} {}%)", self.status, completed * 100 / total)
+            }
+            _ => format!("({})", self.status),
+        }
+    }
+}
+
+impl OllamaModel {
+    /// Whether this is the model configured as `model`; an untagged name means `:latest`.
+    pub fn matches(&self, model: &str) -> bool {
+        self.name == model || (!model.contains(':') && self.name == format!("{}:latest", model))
+    }
+}
+
+/// Whether `model` is among `models`.
+fn model_installed(models: &[OllamaModel], model: &str) -> bool {
+    models.iter().any(|m| m.matches(model))
 }
 
 impl OllamaProvider {
@@ -117,16 +202,146 @@ impl OllamaProvider {
 
         Ok(Self {
             name: provider_name.to_string(),
-            client: super::super::create_http_client(network_config)?,
+            client: super::super::create_http_client_with_proxy(
+                network_config,
+                config.proxy.as_deref(),
+            )?,
             endpoint,
             model,
             temperature,
+            num_predict: get_max_tokens_optional(config),
+            top_p: get_top_p(config),
+            stop: get_stop(config),
             max_retries: network_config.max_retries,
             retry_delay_ms: network_config.retry_delay_ms,
             max_retry_delay_ms: network_config.max_retry_delay_ms,
             colored,
         })
     }
+
+    /// Configured model name.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// URL of another Ollama API endpoint on the same server.
+    fn api_url(&self, path: &str) -> String {
+        self.endpoint.replace(OLLAMA_API_SUFFIX, path)
+    }
+
+    /// Lists the models installed on the Ollama server (`/api/tags`).
+    pub async fn list_models(&self) -> Result<Vec<OllamaModel>> {
+        let response = self
+            .client
+            .get(self.api_url("/api/tags"))
+            .send()
+            .await
+            .map_err(GcopError::Network)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(GcopError::LlmApi {
+                status: status.as_u16(),
+                message: rust_i18n::t!(
+                    "provider.api_validation_failed",
+                    provider = "Ollama",
+                    body = body
+                )
+                .to_string(),
+            });
+        }
+
+        let tags: TagsResponse = response.json().await.map_err(|e| {
+            GcopError::Llm(
+                rust_i18n::t!("provider.ollama_parse_tags_failed", error = e.to_string())
+                    .to_string(),
+            )
+        })?;
+        Ok(tags.models)
+    }
+
+    /// Whether the configured model is installed on the server.
+    pub async fn has_model(&self) -> Result<bool> {
+        Ok(model_installed(&self.list_models().await?, &self.model))
+    }
+
+    /// Pulls the configured model (`/api/pull`), reporting streamed progress
+    /// as a suffix of the caller's spinner.
+    pub async fn pull_model(&self, progress: Option<&dyn ProgressReporter>) -> Result<()> {
+        let response = self
+            .client
+            .post(self.api_url("/api/pull"))
+            .json(&PullRequest {
+                model: &self.model,
+                stream: true,
+            })
+            // Downloads can take far longer than the request timeout
+            .timeout(std::time::Duration::from_secs(24 * 60 * 60))
+            .send()
+            .await
+            .map_err(GcopError::Network)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(GcopError::LlmApi {
+                status: status.as_u16(),
+                message: rust_i18n::t!(
+                    "provider.ollama_pull_failed",
+                    model = self.model,
+                    error = body
+                )
+                .to_string(),
+            });
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut succeeded = false;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(GcopError::Network)?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(pos) = buffer.find('\n') {
+                let line = buffer[..pos].trim().to_string();
+                buffer.drain(..=pos);
+                if line.is_empty() {
+                    continue;
+                }
+                let Ok(update) = serde_json::from_str::<PullStatus>(&line) else {
+                    tracing::debug!("Skipping unparsable Ollama pull line: {}", line);
+                    continue;
+                };
+                if let Some(error) = update.error {
+                    return Err(GcopError::Llm(
+                        rust_i18n::t!(
+                            "provider.ollama_pull_failed",
+                            model = self.model,
+                            error = error
+                        )
+                        .to_string(),
+                    ));
+                }
+                succeeded |= update.status == "success";
+                if let Some(progress) = progress {
+                    progress.append_suffix(&update.describe());
+                }
+            }
+        }
+
+        if !succeeded {
+            return Err(GcopError::Llm(
+                rust_i18n::t!(
+                    "provider.ollama_pull_failed",
+                    model = self.model,
+                    error = rust_i18n::t!("provider.ollama_pull_incomplete")
+                )
+                .to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -135,15 +350,27 @@ impl ApiBackend for OllamaProvider {
         &self.name
     }
 
+    fn model(&self) -> &str {
+        &self.model
+    }
+
     async fn call_api(
         &self,
         system: &str,
         user_message: &str,
         progress: Option<&dyn crate::llm::ProgressReporter>,
-    ) -> Result<String> {
-        let options = self.temperature.map(|temp| OllamaOptions {
-            temperature: Some(temp),
-        });
+    ) -> Result<ApiResponse> {
+        let options = OllamaOptions {
+            temperature: self.temperature,
+            num_predict: self.num_predict,
+            top_p: self.top_p,
+            stop: self.stop.clone(),
+        };
+        let options = (options.temperature.is_some()
+            || options.num_predict.is_some()
+            || options.top_p.is_some()
+            || options.stop.is_some())
+        .then_some(options);
 
         let request = OllamaRequest {
             model: self.model.clone(),
@@ -174,56 +401,17 @@ impl ApiBackend for OllamaProvider {
         )
         .await?;
 
-        Ok(response.response)
+        Ok(ApiResponse {
+            usage: TokenUsage::from_counts(response.prompt_eval_count, response.eval_count),
+            text: response.response,
+        })
     }
 
     async fn validate(&self) -> Result<()> {
         // Validate Ollama connection and model availability
         tracing::debug!("Validating Ollama connection...");
 
-        // Ollama health check endpoint: /api/tags
-        let health_endpoint = self.endpoint.replace("/api/generate", "/api/tags");
-
-        let response = self
-            .client
-            .get(&health_endpoint)
-            .send()
-            .await
-            .map_err(GcopError::Network)?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(GcopError::LlmApi {
-                status: status.as_u16(),
-                message: rust_i18n::t!(
-                    "provider.api_validation_failed",
-                    provider = "Ollama",
-                    body = body
-                )
-                .to_string(),
-            });
-        }
-
-        // Check if configured model exists
-        #[derive(Deserialize)]
-        struct TagsResponse {
-            models: Vec<ModelInfo>,
-        }
-
-        #[derive(Deserialize)]
-        struct ModelInfo {
-            name: String,
-        }
-
-        let tags: TagsResponse = response.json().await.map_err(|e| {
-            GcopError::Llm(
-                rust_i18n::t!("provider.ollama_parse_tags_failed", error = e.to_string())
-                    .to_string(),
-            )
-        })?;
-
-        if !tags.models.iter().any(|m| m.name.starts_with(&self.model)) {
+        if !self.has_model().await? {
             return Err(GcopError::Config(
                 rust_i18n::t!("provider.ollama_model_not_found", model = self.model).to_string(),
             ));
@@ -266,7 +454,7 @@ mod tests {
         .unwrap();
 
         let result = provider.call_api("system", "hi", None).await.unwrap();
-        assert_eq!(result, "Hello from Ollama");
+        assert_eq!(result.text, "Hello from Ollama");
         mock.assert_async().await;
     }
 
@@ -317,4 +505,158 @@ mod tests {
         assert!(matches!(err, GcopError::LlmApi { status: 429, .. }));
         mock.assert_async().await;
     }
+
+    fn provider(url: String, model: &str) -> OllamaProvider {
+        OllamaProvider::new(
+            &test_provider_config(url, None, model.to_string()),
+            "ollama",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap()
+    }
+
+    struct RecordingProgress(std::sync::Mutex<Vec<String>>);
+
+    impl ProgressReporter for RecordingProgress {
+        fn append_suffix(&self, suffix: &str) {
+            self.0.lock().unwrap().push(suffix.to_string());
+        }
+    }
+
+    #[test]
+    fn test_model_installed_defaults_to_latest_tag() {
+        let models: Vec<OllamaModel> = ["llama3.2:latest", "qwen2.5-coder:7b"]
+            .into_iter()
+            .map(|name| OllamaModel {
+                name: name.to_string(),
+                size: 0,
+                modified_at: None,
+            })
+            .collect();
+        assert!(model_installed(&models, "llama3.2"));
+        assert!(model_installed(&models, "llama3.2:latest"));
+        assert!(model_installed(&models, "qwen2.5-coder:7b"));
+        // 前缀相同但不是同一个模型
+        assert!(!model_installed(&models, "llama3"));
+        assert!(!model_installed(&models, "qwen2.5-coder"));
+    }
+
+    #[tokio::test]
+    async fn test_ollama_list_models_and_validate() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/tags")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"models":[{"name":"llama3.2:latest","size":2019393189,"modified_at":"2026-01-02T10:00:00Z"}]}"#,
+            )
+            .expect(3)
+            .create_async()
+            .await;
+
+        let models = provider(server.url(), "llama3.2")
+            .list_models()
+            .await
+            .unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].size, 2019393189);
+
+        assert!(provider(server.url(), "llama3.2").validate().await.is_ok());
+        let err = provider(server.url(), "mistral")
+            .validate()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GcopError::Config(_)));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_ollama_pull_reports_progress() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/pull")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"model": "llama3.2", "stream": true}),
+            ))
+            .with_status(200)
+            .with_body(concat!(
+                "{\"status\":\"pulling manifest\"}\n",
+                "{\"status\":\"pulling 8eeb52dfb3bb\",\"digest\":\"sha256:8eeb\",\"total\":200,\"completed\":50}\n",
+                "{\"status\":\"success\"}\n",
+            ))
+            .create_async()
+            .await;
+
+        let progress = RecordingProgress(std::sync::Mutex::new(Vec::new()));
+        provider(server.url(), "llama3.2")
+            .pull_model(Some(&progress))
+            .await
+            .unwrap();
+        assert_eq!(
+            *progress.0.lock().unwrap(),
+            vec![
+                "(pulling manifest)",
+                "(pulling 8eeb52dfb3bb 25%)",
+                "(success)"
+            ]
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_ollama_pull_error_line() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/pull")
+            .with_status(200)
+            .with_body("{\"status\":\"pulling manifest\"}\n{\"error\":\"file does not exist\"}\n")
+            .create_async()
+            .await;
+
+        let err = provider(server.url(), "no-such-model")
+            .pull_model(None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("file does not exist"));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_ollama_sampling_parameters() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/generate")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "system": "system",
+                "options": { "num_predict": 256, "top_p": 0.5, "stop": ["\n\n"] }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"response":"ok","done":true}"#)
+            .create_async()
+            .await;
+
+        let provider = OllamaProvider::new(
+            &{
+                let mut config = test_provider_config(server.url(), None, "llama3".to_string());
+                config.max_tokens = Some(256);
+                config.top_p = Some(0.5);
+                config.stop = Some(vec!["\n\n".to_string()]);
+                config
+            },
+            "ollama",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap();
+
+        provider.call_api("system", "hi", None).await.unwrap();
+        mock.assert_async().await;
+    }
 }
@@ -4,9 +4,11 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use super::super::base::{
-    ApiBackend, build_endpoint, extract_api_key, get_max_tokens_optional, get_temperature,
-    send_llm_request, send_llm_request_streaming, validate_api_key, validate_http_endpoint,
+    ApiBackend, ApiResponse, build_endpoint, extract_api_key, get_max_tokens_optional, get_stop,
+    get_temperature, get_top_p, send_llm_request, send_llm_request_streaming, validate_api_key,
+    validate_http_endpoint,
 };
+use super::super::streaming::openai::OpenAIUsage;
 use super::super::streaming::process_openai_stream;
 use super::super::utils::{DEFAULT_OPENAI_BASE, OPENAI_API_SUFFIX};
 use crate::config::{NetworkConfig, ProviderConfig};
@@ -85,10 +87,13 @@ pub struct OpenAIProvider {
     model: String,
     max_tokens: Option<u32>,
     temperature: f32,
+    top_p: Option<f32>,
+    stop: Option<Vec<String>>,
     max_retries: usize,
     retry_delay_ms: u64,
     max_retry_delay_ms: u64,
     colored: bool,
+    structured_output: bool,
 }
 
 #[derive(Clone, Serialize)]
@@ -99,7 +104,13 @@ struct OpenAIRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -111,6 +122,8 @@ struct MessagePayload {
 #[derive(Deserialize)]
 struct OpenAIResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<OpenAIUsage>,
 }
 
 #[derive(Deserialize)]
@@ -120,7 +133,12 @@ struct Choice {
 
 #[derive(Deserialize)]
 struct MessageContent {
-    content: String,
+    /// May be `null` when a reasoning model spends its whole budget thinking.
+    #[serde(default)]
+    content: Option<String>,
+    /// Chain-of-thought returned by reasoning models (DeepSeek, Moonshot); never used as output.
+    #[serde(default)]
+    reasoning_content: Option<String>,
 }
 
 impl OpenAIProvider {
@@ -131,41 +149,66 @@ impl OpenAIProvider {
         network_config: &NetworkConfig,
         colored: bool,
     ) -> Result<Self> {
-        let api_key = extract_api_key(config, "OpenAI")?;
-        let endpoint = build_endpoint(config, DEFAULT_OPENAI_BASE, OPENAI_API_SUFFIX);
+        Self::with_default_base(
+            config,
+            provider_name,
+            network_config,
+            colored,
+            DEFAULT_OPENAI_BASE,
+        )
+    }
+
+    /// Builds an OpenAI-compatible provider whose endpoint defaults to `default_base`.
+    ///
+    /// Used by presets such as DeepSeek and Moonshot that share the OpenAI
+    /// wire format but are hosted elsewhere.
+    pub fn with_default_base(
+        config: &ProviderConfig,
+        provider_name: &str,
+        network_config: &NetworkConfig,
+        colored: bool,
+        default_base: &str,
+    ) -> Result<Self> {
+        let api_key = extract_api_key(config, provider_name)?;
+        let endpoint = build_endpoint(config, default_base, OPENAI_API_SUFFIX);
         let model = config.model.clone();
         let max_tokens = get_max_tokens_optional(config);
         let temperature = get_temperature(config);
 
         Ok(Self {
             name: provider_name.to_string(),
-            client: super::super::create_http_client(network_config)?,
+            client: super::super::create_http_client_with_proxy(
+                network_config,
+                config.proxy.as_deref(),
+            )?,
             api_key,
             endpoint,
             model,
             max_tokens,
             temperature,
+            top_p: get_top_p(config),
+            stop: get_stop(config),
             max_retries: network_config.max_retries,
             retry_delay_ms: network_config.retry_delay_ms,
             max_retry_delay_ms: network_config.max_retry_delay_ms,
             colored,
+            structured_output: false,
         })
     }
-}
 
-#[async_trait]
-impl ApiBackend for OpenAIProvider {
-    fn name(&self) -> &str {
-        &self.name
+    /// Enables `response_format: json_schema` for review requests.
+    pub fn with_structured_output(mut self, enabled: bool) -> Self {
+        self.structured_output = enabled;
+        self
     }
 
-    async fn call_api(
+    fn build_request(
         &self,
         system: &str,
         user_message: &str,
-        progress: Option<&dyn crate::llm::ProgressReporter>,
-    ) -> Result<String> {
-        let request = OpenAIRequest {
+        stream: Option<bool>,
+    ) -> OpenAIRequest {
+        OpenAIRequest {
             model: self.model.clone(),
             messages: vec![
                 MessagePayload {
@@ -179,24 +222,24 @@ impl ApiBackend for OpenAIProvider {
             ],
             temperature: self.temperature,
             max_tokens: self.max_tokens,
-            stream: None,
-        };
-
-        tracing::debug!(
-            "OpenAI API request: model={}, temperature={}, max_tokens={:?}, system_len={}, user_len={}",
-            self.model,
-            self.temperature,
-            self.max_tokens,
-            system.len(),
-            user_message.len()
-        );
+            top_p: self.top_p,
+            stop: self.stop.clone(),
+            stream,
+            response_format: None,
+        }
+    }
 
+    async fn send_chat_request(
+        &self,
+        request: &OpenAIRequest,
+        progress: Option<&dyn crate::llm::ProgressReporter>,
+    ) -> Result<ApiResponse> {
         let auth_header = format!("Bearer {}", self.api_key);
         let response: OpenAIResponse = send_llm_request(
             &self.client,
             &self.endpoint,
             &[("Authorization", auth_header.as_str())],
-            &request,
+            request,
             "OpenAI",
             progress,
             self.max_retries,
@@ -205,12 +248,95 @@ impl ApiBackend for OpenAIProvider {
         )
         .await?;
 
-        response
+        let usage = response.usage.as_ref().and_then(OpenAIUsage::to_usage);
+        let message = response
             .choices
             .into_iter()
             .next()
-            .map(|choice| choice.message.content)
-            .ok_or_else(|| GcopError::Llm(rust_i18n::t!("provider.openai_no_choices").to_string()))
+            .map(|choice| choice.message)
+            .ok_or_else(|| {
+                GcopError::Llm(rust_i18n::t!("provider.openai_no_choices").to_string())
+            })?;
+
+        if let Some(ref reasoning) = message.reasoning_content {
+            tracing::debug!(
+                "Ignoring {} bytes of reasoning_content from {}",
+                reasoning.len(),
+                self.name
+            );
+        }
+
+        match message.content {
+            Some(content) if !content.trim().is_empty() => Ok(ApiResponse {
+                text: content,
+                usage,
+            }),
+            _ => Err(GcopError::Llm(
+                rust_i18n::t!("provider.openai_empty_content").to_string(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiBackend for OpenAIProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn call_api(
+        &self,
+        system: &str,
+        user_message: &str,
+        progress: Option<&dyn crate::llm::ProgressReporter>,
+    ) -> Result<ApiResponse> {
+        let request = self.build_request(system, user_message, None);
+
+        tracing::debug!(
+            "OpenAI API request: model={}, temperature={}, max_tokens={:?}, system_len={}, user_len={}",
+            self.model,
+            self.temperature,
+            self.max_tokens,
+            system.len(),
+            user_message.len()
+        );
+
+        self.send_chat_request(&request, progress).await
+    }
+
+    fn structured_output_enabled(&self) -> bool {
+        self.structured_output
+    }
+
+    async fn call_api_structured(
+        &self,
+        system: &str,
+        user_message: &str,
+        schema: &serde_json::Value,
+        progress: Option<&dyn crate::llm::ProgressReporter>,
+    ) -> Result<ApiResponse> {
+        let mut request = self.build_request(system, user_message, None);
+        request.response_format = Some(serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "review_result",
+                "strict": true,
+                "schema": schema,
+            }
+        }));
+
+        tracing::debug!(
+            "OpenAI structured API request: model={}, system_len={}, user_len={}",
+            self.model,
+            system.len(),
+            user_message.len()
+        );
+
+        self.send_chat_request(&request, progress).await
     }
 
     fn supports_streaming(&self) -> bool {
@@ -220,22 +346,7 @@ impl ApiBackend for OpenAIProvider {
     async fn call_api_streaming(&self, system: &str, user_message: &str) -> Result<StreamHandle> {
         let (tx, rx) = mpsc::channel(64);
 
-        let request = OpenAIRequest {
-            model: self.model.clone(),
-            messages: vec![
-                MessagePayload {
-                    role: "system".to_string(),
-                    content: system.to_string(),
-                },
-                MessagePayload {
-                    role: "user".to_string(),
-                    content: user_message.to_string(),
-                },
-            ],
-            temperature: self.temperature,
-            max_tokens: self.max_tokens,
-            stream: Some(true),
-        };
+        let request = self.build_request(system, user_message, Some(true));
 
         tracing::debug!(
             "OpenAI Streaming API request: model={}, temperature={}, max_tokens={:?}, system_len={}, user_len={}",
@@ -317,7 +428,10 @@ impl ApiBackend for OpenAIProvider {
             }],
             temperature: 1.0,
             max_tokens: Some(1), // Minimize API cost
+            top_p: None,
+            stop: None,
             stream: None,
+            response_format: None,
         };
 
         let auth_header = format!("Bearer {}", self.api_key);
@@ -351,7 +465,9 @@ mod tests {
             .mock("POST", "/v1/chat/completions")
             .with_status(200)
             .with_header("content-type", "application/json")
-            .with_body(r#"{"choices":[{"message":{"content":"Hello from OpenAI"}}]}"#)
+            .with_body(
+                r#"{"choices":[{"message":{"content":"Hello from OpenAI"}}],"usage":{"prompt_tokens":12,"completion_tokens":4,"total_tokens":16}}"#,
+            )
             .create_async()
             .await;
 
@@ -368,10 +484,192 @@ mod tests {
         .unwrap();
 
         let result = provider.call_api("system", "hi", None).await.unwrap();
-        assert_eq!(result, "Hello from OpenAI");
+        assert_eq!(result.text, "Hello from OpenAI");
+        assert_eq!(
+            result.usage,
+            Some(crate::llm::metrics::TokenUsage {
+                prompt_tokens: 12,
+                completion_tokens: 4,
+            })
+        );
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_openai_ignores_reasoning_content() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"choices":[{"message":{"reasoning_content":"Let me think...","content":"feat: add login"}}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = OpenAIProvider::new(
+            &test_provider_config(
+                server.url(),
+                Some("sk-test".to_string()),
+                "deepseek-reasoner".to_string(),
+            ),
+            "deepseek",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap();
+
+        let result = provider.call_api("system", "hi", None).await.unwrap();
+        assert_eq!(result.text, "feat: add login");
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn test_openai_null_content_with_only_reasoning_is_error() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"choices":[{"message":{"reasoning_content":"Still thinking...","content":null}}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = OpenAIProvider::new(
+            &test_provider_config(
+                server.url(),
+                Some("sk-test".to_string()),
+                "kimi-latest".to_string(),
+            ),
+            "moonshot",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap();
+
+        let err = provider.call_api("system", "hi", None).await.unwrap_err();
+        assert!(matches!(err, GcopError::Llm(_)));
+    }
+
+    #[test]
+    fn test_with_default_base_uses_preset_endpoint() {
+        ensure_crypto_provider();
+        let mut config = test_provider_config(
+            String::new(),
+            Some("sk-test".to_string()),
+            "deepseek-chat".to_string(),
+        );
+        config.endpoint = None;
+
+        let provider = OpenAIProvider::with_default_base(
+            &config,
+            "deepseek",
+            &test_network_config_no_retry(),
+            false,
+            crate::llm::provider::utils::DEFAULT_DEEPSEEK_BASE,
+        )
+        .unwrap();
+        assert_eq!(
+            provider.endpoint,
+            "https://api.deepseek.com/v1/chat/completions"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_openai_structured_review_sends_json_schema() {
+        use crate::llm::{LLMProvider, ReviewType};
+        use mockito::Matcher;
+
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::PartialJson(serde_json::json!({
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": { "name": "review_result", "strict": true }
+                }
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"choices":[{"message":{"content":"{\"summary\":\"LGTM\",\"issues\":[],\"suggestions\":[]}"}}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = OpenAIProvider::new(
+            &test_provider_config(
+                server.url(),
+                Some("sk-test".to_string()),
+                "gpt-4o-mini".to_string(),
+            ),
+            "openai",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap()
+        .with_structured_output(true);
+
+        let result = provider
+            .review_code("diff", ReviewType::UncommittedChanges, None, None)
+            .await
+            .unwrap();
+        assert_eq!(result.summary, "LGTM");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_openai_structured_review_falls_back_on_400() {
+        use crate::llm::{LLMProvider, ReviewType};
+        use mockito::Matcher;
+
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let rejected = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(Matcher::Regex("response_format".to_string()))
+            .with_status(400)
+            .with_body("response_format not supported")
+            .create_async()
+            .await;
+        let plain = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"choices":[{"message":{"content":"```json\n{\"summary\":\"ok\",\"issues\":[],\"suggestions\":[]}\n```"}}]}"#,
+            )
+            .create_async()
+            .await;
+
+        let provider = OpenAIProvider::new(
+            &test_provider_config(
+                server.url(),
+                Some("sk-test".to_string()),
+                "gpt-4o-mini".to_string(),
+            ),
+            "openai",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap()
+        .with_structured_output(true);
+
+        let result = provider
+            .review_code("diff", ReviewType::UncommittedChanges, None, None)
+            .await
+            .unwrap();
+        assert_eq!(result.summary, "ok");
+        rejected.assert_async().await;
+        plain.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_openai_api_error_401() {
         ensure_crypto_provider();
@@ -427,4 +725,43 @@ mod tests {
         assert!(matches!(err, GcopError::LlmApi { status: 429, .. }));
         mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn test_openai_sampling_parameters() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "max_tokens": 256,
+                "top_p": 0.5,
+                "stop": ["\n\n"]
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices":[{"message":{"content":"ok"}}]}"#)
+            .create_async()
+            .await;
+
+        let provider = OpenAIProvider::new(
+            &{
+                let mut config = test_provider_config(
+                    server.url(),
+                    Some("sk-test".to_string()),
+                    "gpt-4o-mini".to_string(),
+                );
+                config.max_tokens = Some(256);
+                config.top_p = Some(0.5);
+                config.stop = Some(vec!["\n\n".to_string()]);
+                config
+            },
+            "openai",
+            &test_network_config_no_retry(),
+            false,
+        )
+        .unwrap();
+
+        provider.call_api("system", "hi", None).await.unwrap();
+        mock.assert_async().await;
+    }
 }
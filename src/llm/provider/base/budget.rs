@@ -0,0 +1,154 @@
+//! Retry budget shared by all attempts of one request
+//!
+//! Without a budget every provider of the fallback chain runs its own full
+//! retry schedule. [`scope`] installs a budget (`network.total_attempts` /
+//! `network.total_timeout`) for the current task; the HTTP retry loop and the
+//! fallback loop both draw from it, and streaming tasks inherit it.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::config::NetworkConfig;
+
+tokio::task_local! {
+    static BUDGET: Arc<RetryBudget>;
+}
+
+/// Remaining attempts and deadline of one request
+#[derive(Debug)]
+pub(crate) struct RetryBudget {
+    /// Attempts left; `None` means unlimited.
+    attempts: Option<AtomicUsize>,
+    deadline: Option<Instant>,
+}
+
+impl RetryBudget {
+    /// Starts a budget; `0` disables the respective limit.
+    pub(crate) fn new(total_attempts: usize, total_timeout: Duration) -> Self {
+        Self {
+            attempts: (total_attempts > 0).then(|| AtomicUsize::new(total_attempts)),
+            deadline: (!total_timeout.is_zero()).then(|| Instant::now() + total_timeout),
+        }
+    }
+
+    /// Starts the budget configured in `[network]`; `None` when both limits are off.
+    pub(crate) fn from_config(network: &NetworkConfig) -> Option<Arc<Self>> {
+        (network.total_attempts > 0 || network.total_timeout > 0).then(|| {
+            Arc::new(Self::new(
+                network.total_attempts,
+                Duration::from_secs(network.total_timeout),
+            ))
+        })
+    }
+
+    fn attempts_left(&self) -> bool {
+        self.attempts
+            .as_ref()
+            .is_none_or(|a| a.load(Ordering::SeqCst) > 0)
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|d| d.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// Runs `future` under `budget`, unless a budget is already active (nested
+/// calls share the outer one) or `budget` is `None`.
+pub(crate) async fn scope<F: Future>(budget: Option<Arc<RetryBudget>>, future: F) -> F::Output {
+    match budget {
+        Some(budget) if current().is_none() => BUDGET.scope(budget, future).await,
+        _ => future.await,
+    }
+}
+
+/// The budget of the current task, to hand over to spawned tasks.
+pub(crate) fn current() -> Option<Arc<RetryBudget>> {
+    BUDGET.try_with(Arc::clone).ok()
+}
+
+/// Consumes one attempt; `false` when the budget is exhausted.
+pub(crate) fn take_attempt() -> bool {
+    BUDGET
+        .try_with(|b| {
+            if b.remaining().is_some_and(|r| r.is_zero()) {
+                return false;
+            }
+            match &b.attempts {
+                Some(attempts) => attempts
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok(),
+                None => true,
+            }
+        })
+        .unwrap_or(true)
+}
+
+/// Whether another attempt after waiting `delay` still fits the budget.
+pub(crate) fn allows_retry(delay: Duration) -> bool {
+    BUDGET
+        .try_with(|b| b.attempts_left() && b.remaining().is_none_or(|r| r > delay))
+        .unwrap_or(true)
+}
+
+/// Whether nothing is left for another attempt.
+pub(crate) fn is_exhausted() -> bool {
+    !allows_retry(Duration::ZERO)
+}
+
+/// Time left until the deadline, if one is set.
+pub(crate) fn remaining() -> Option<Duration> {
+    BUDGET.try_with(|b| b.remaining()).ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_attempts_are_shared_by_nested_scopes() {
+        // 没有预算时不做限制
+        assert!(take_attempt());
+        assert!(!is_exhausted());
+
+        let budget = Arc::new(RetryBudget::new(2, Duration::ZERO));
+        scope(Some(budget), async {
+            assert!(take_attempt());
+            // 嵌套的 scope 沿用外层预算
+            scope(
+                Some(Arc::new(RetryBudget::new(10, Duration::ZERO))),
+                async {
+                    assert!(take_attempt());
+                },
+            )
+            .await;
+            assert!(is_exhausted());
+            assert!(!take_attempt());
+            assert_eq!(remaining(), None);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_deadline_limits_retry_delay() {
+        let budget = Arc::new(RetryBudget::new(0, Duration::from_secs(5)));
+        scope(Some(budget), async {
+            assert!(allows_retry(Duration::from_secs(1)));
+            assert!(!allows_retry(Duration::from_secs(10)));
+            assert!(remaining().unwrap() <= Duration::from_secs(5));
+        })
+        .await;
+    }
+
+    #[test]
+    fn test_from_config_disabled_by_default() {
+        assert!(RetryBudget::from_config(&NetworkConfig::default()).is_none());
+        let network = NetworkConfig {
+            total_attempts: 3,
+            ..Default::default()
+        };
+        assert!(RetryBudget::from_config(&network).is_some());
+    }
+}
@@ -2,10 +2,15 @@
 //!
 //! Provides helper functions to extract various parameters from ProviderConfig
 
+use tracing::debug;
+
 use crate::config::ProviderConfig;
 use crate::error::{GcopError, Result};
 
-use super::super::utils::complete_endpoint;
+use super::super::utils::{
+    DEFAULT_CLAUDE_BASE, DEFAULT_GEMINI_BASE, DEFAULT_OLLAMA_BASE, DEFAULT_OPENAI_BASE,
+    complete_endpoint,
+};
 
 /// Default max_tokens
 const DEFAULT_MAX_TOKENS: u32 = 2000;
@@ -16,33 +21,71 @@ const DEFAULT_TEMPERATURE: f32 = 0.3;
 /// Extract API key
 ///
 /// Read from configuration file. Ordinary users set it in config.toml, and CI mode uses `GCOP_CI_API_KEY`.
+/// Without one, the key stored in the OS keychain by `gcop-rs init` is used.
 ///
 /// # Arguments
 /// * `config` - Provider configuration
-/// * `provider_name` - Provider name (used for error prompts)
+/// * `provider_name` - Provider name (used for error prompts and the keychain lookup)
 pub fn extract_api_key(config: &ProviderConfig, provider_name: &str) -> Result<String> {
-    config.api_key.clone().ok_or_else(|| {
-        GcopError::Config(
-            rust_i18n::t!(
-                "provider.api_key_not_found_simple",
-                provider = provider_name
+    config
+        .api_key
+        .clone()
+        .or_else(|| crate::config::keychain::lookup(provider_name))
+        .ok_or_else(|| {
+            GcopError::Config(
+                rust_i18n::t!(
+                    "provider.api_key_not_found_simple",
+                    provider = provider_name
+                )
+                .to_string(),
             )
-            .to_string(),
-        )
-    })
+        })
+}
+
+/// Environment variable overriding a backend's default base URL
+///
+/// Presets with their own base URL (DeepSeek, Moonshot) have none.
+pub fn base_url_env_var(default_base: &str) -> Option<&'static str> {
+    match default_base {
+        DEFAULT_CLAUDE_BASE => Some("ANTHROPIC_BASE_URL"),
+        DEFAULT_OPENAI_BASE => Some("OPENAI_BASE_URL"),
+        DEFAULT_OLLAMA_BASE => Some("OLLAMA_BASE_URL"),
+        DEFAULT_GEMINI_BASE => Some("GEMINI_BASE_URL"),
+        _ => None,
+    }
+}
+
+/// Resolve the base URL of a provider
+///
+/// Precedence: configured `endpoint` (including `GCOP__*` / `GCOP_CI_ENDPOINT`
+/// overrides) > provider env var (`ANTHROPIC_BASE_URL`, ...) > `default_base`.
+/// The winning source is logged at debug level (`--verbose`).
+pub fn resolve_base_url(config: &ProviderConfig, default_base: &str) -> String {
+    if let Some(endpoint) = config.endpoint.as_deref() {
+        debug!("Endpoint from config: {}", endpoint);
+        return endpoint.to_string();
+    }
+    if let Some(var) = base_url_env_var(default_base)
+        && let Ok(value) = std::env::var(var)
+        && !value.trim().is_empty()
+    {
+        debug!("Endpoint from {}: {}", var, value.trim());
+        return value.trim().to_string();
+    }
+    debug!("Endpoint from built-in default: {}", default_base);
+    default_base.to_string()
 }
 
 /// Build a complete endpoint
 ///
-/// Read the endpoint from the configuration file, and use the default value if not configured.
+/// The base URL comes from [`resolve_base_url`].
 ///
 /// # Arguments
 /// * `config` - Provider configuration
 /// * `default_base` - default base URL
 /// * `suffix` - API path suffix
 pub fn build_endpoint(config: &ProviderConfig, default_base: &str, suffix: &str) -> String {
-    let base = config.endpoint.as_deref().unwrap_or(default_base);
-    complete_endpoint(base, suffix)
+    complete_endpoint(&resolve_base_url(config, default_base), suffix)
 }
 
 /// Extract u32 value from extra configuration
@@ -92,3 +135,72 @@ pub fn get_temperature_optional(config: &ProviderConfig) -> Option<f32> {
         .temperature
         .or_else(|| extract_extra_f32(config, "temperature"))
 }
+
+/// Get top_p from configuration (explicit field first, fallback to extra)
+pub fn get_top_p(config: &ProviderConfig) -> Option<f32> {
+    config.top_p.or_else(|| extract_extra_f32(config, "top_p"))
+}
+
+/// Get stop sequences from configuration (explicit field first, fallback to extra)
+///
+/// An empty list is treated as unset.
+pub fn get_stop(config: &ProviderConfig) -> Option<Vec<String>> {
+    config
+        .stop
+        .clone()
+        .or_else(|| {
+            config.extra.get("stop").and_then(|v| match v {
+                serde_json::Value::String(s) => Some(vec![s.clone()]),
+                serde_json::Value::Array(items) => items
+                    .iter()
+                    .map(|item| item.as_str().map(str::to_string))
+                    .collect(),
+                _ => None,
+            })
+        })
+        .filter(|stop| !stop.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::provider::test_utils::test_provider_config;
+    use crate::llm::provider::utils::DEFAULT_DEEPSEEK_BASE;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_resolve_base_url_precedence() {
+        let mut config = test_provider_config(String::new(), None, "m".to_string());
+        config.endpoint = None;
+        // SAFETY: tests touching the environment run serially
+        unsafe { std::env::set_var("ANTHROPIC_BASE_URL", " https://gateway.example.com ") };
+
+        assert_eq!(
+            resolve_base_url(&config, DEFAULT_CLAUDE_BASE),
+            "https://gateway.example.com"
+        );
+        assert_eq!(
+            build_endpoint(&config, DEFAULT_CLAUDE_BASE, "/v1/messages"),
+            "https://gateway.example.com/v1/messages"
+        );
+        // Other backends and presets ignore it
+        assert_eq!(
+            resolve_base_url(&config, DEFAULT_OPENAI_BASE),
+            DEFAULT_OPENAI_BASE
+        );
+        assert_eq!(
+            resolve_base_url(&config, DEFAULT_DEEPSEEK_BASE),
+            DEFAULT_DEEPSEEK_BASE
+        );
+
+        config.endpoint = Some("https://config.example.com".to_string());
+        assert_eq!(
+            resolve_base_url(&config, DEFAULT_CLAUDE_BASE),
+            "https://config.example.com"
+        );
+
+        // SAFETY: see above
+        unsafe { std::env::remove_var("ANTHROPIC_BASE_URL") };
+    }
+}
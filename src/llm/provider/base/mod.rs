@@ -3,12 +3,14 @@
 //! Extract the common logic of each Provider to reduce duplicate code.
 //!
 //! Module structure:
+//! - `budget` - retry budget shared across the fallback chain
 //! - `config` - configure extraction tool function
 //! - `response` - response handling and JSON sanitization
 //! - `retry` - HTTP request sending and retry logic
 //! - `validation` - API validation helper function
 //! - `ApiBackend` trait - each provider only needs to implement its unique part, and the common logic is provided by blanket impl
 
+pub(crate) mod budget;
 pub mod config;
 pub mod response;
 pub mod retry;
@@ -24,8 +26,49 @@ pub use validation::*;
 use async_trait::async_trait;
 
 use crate::error::{GcopError, Result};
+use crate::llm::audit;
+use crate::llm::metrics::{self, TokenUsage};
+use crate::llm::plugins;
 use crate::llm::{LLMProvider, ProgressReporter, ReviewResult, ReviewType, StreamHandle};
 
+/// Response text of a non-streaming call plus the usage reported by the provider.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ApiResponse {
+    /// Generated text.
+    pub text: String,
+    /// Token usage, when the provider reports it.
+    pub usage: Option<TokenUsage>,
+}
+
+impl ApiResponse {
+    /// Records the call in [`metrics`] and returns the text.
+    fn into_recorded_text(self) -> String {
+        metrics::record_call(self.usage);
+        self.text
+    }
+}
+
+/// Records a finished non-streaming call in the [`audit`] log.
+fn audit_call<T: ApiBackend + ?Sized>(
+    backend: &T,
+    system: &str,
+    user: &str,
+    result: &Result<ApiResponse>,
+) {
+    let (response, usage) = match result {
+        Ok(response) => (Some(response.text.as_str()), response.usage),
+        Err(_) => (None, None),
+    };
+    audit::record(
+        backend.name(),
+        backend.model(),
+        system,
+        user,
+        response,
+        usage,
+    );
+}
+
 /// Internal traits: Each provider only needs to implement its own unique part
 ///
 /// `LLMProvider` is automatically provided to all `ApiBackend` implementers via blanket impl.
@@ -35,13 +78,16 @@ pub(crate) trait ApiBackend: Send + Sync {
     /// Provider name
     fn name(&self) -> &str;
 
+    /// Model requests are sent to
+    fn model(&self) -> &str;
+
     /// Non-streaming API calls
     async fn call_api(
         &self,
         system: &str,
         user_message: &str,
         progress: Option<&dyn ProgressReporter>,
-    ) -> Result<String>;
+    ) -> Result<ApiResponse>;
 
     /// Whether to support streaming response
     fn supports_streaming(&self) -> bool {
@@ -53,6 +99,27 @@ pub(crate) trait ApiBackend: Send + Sync {
         Err(GcopError::Llm("Streaming not supported".into()))
     }
 
+    /// Whether schema-constrained output is enabled for this backend
+    ///
+    /// Only backends that implement [`call_api_structured`](Self::call_api_structured)
+    /// and were configured with `review.structured_output = true` return `true`.
+    fn structured_output_enabled(&self) -> bool {
+        false
+    }
+
+    /// Non-streaming API call constrained to a JSON schema
+    ///
+    /// Default: ignores the schema and delegates to [`call_api`](Self::call_api).
+    async fn call_api_structured(
+        &self,
+        system: &str,
+        user_message: &str,
+        _schema: &serde_json::Value,
+        progress: Option<&dyn ProgressReporter>,
+    ) -> Result<ApiResponse> {
+        self.call_api(system, user_message, progress).await
+    }
+
     /// Verify configuration
     async fn validate(&self) -> Result<()>;
 }
@@ -60,7 +127,9 @@ pub(crate) trait ApiBackend: Send + Sync {
 /// Blanket impl: every `ApiBackend` automatically becomes an `LLMProvider`.
 ///
 /// `send_prompt` delegates to `call_api`.
-/// `send_prompt_streaming` delegates to `call_api_streaming` (with non-streaming fallback).
+/// `send_prompt_streaming` delegates to `call_api_streaming` (with non-streaming fallback,
+/// also used when a [`plugins`] hook rewrites responses).
+/// Prompts and responses pass through the [`plugins`] hooks on the way.
 /// `generate_commit_message` and `generate_commit_message_streaming` use the trait defaults
 /// (build prompt → `send_prompt` / `send_prompt_streaming`).
 #[async_trait]
@@ -76,7 +145,11 @@ impl<T: ApiBackend> LLMProvider for T {
             system_prompt.len(),
             user_prompt.len()
         );
-        self.call_api(system_prompt, user_prompt, progress).await
+        let (system_prompt, user_prompt) = plugins::transform_prompt(system_prompt, user_prompt)?;
+        metrics::record_provider(ApiBackend::name(self));
+        let result = self.call_api(&system_prompt, &user_prompt, progress).await;
+        audit_call(self, &system_prompt, &user_prompt, &result);
+        plugins::transform_response(result?.into_recorded_text())
     }
 
     async fn send_prompt_streaming(
@@ -84,13 +157,23 @@ impl<T: ApiBackend> LLMProvider for T {
         system_prompt: &str,
         user_prompt: &str,
     ) -> Result<StreamHandle> {
-        if ApiBackend::supports_streaming(self) {
+        if LLMProvider::supports_streaming(self) {
             tracing::debug!(
                 "Streaming - system ({} chars), user ({} chars)",
                 system_prompt.len(),
                 user_prompt.len()
             );
-            self.call_api_streaming(system_prompt, user_prompt).await
+            let (system_prompt, user_prompt) =
+                plugins::transform_prompt(system_prompt, user_prompt)?;
+            metrics::record_provider(ApiBackend::name(self));
+            let stream = self.call_api_streaming(&system_prompt, &user_prompt).await;
+            audit::record_stream(
+                ApiBackend::name(self),
+                self.model(),
+                &system_prompt,
+                &user_prompt,
+                stream,
+            )
         } else {
             // Fallback to non-streaming, emit full response as single chunk.
             let (tx, rx) = tokio::sync::mpsc::channel(32);
@@ -124,8 +207,42 @@ impl<T: ApiBackend> LLMProvider for T {
             system.len(),
             user.len()
         );
-        let response = self.call_api(&system, &user, progress).await?;
-        process_review_response(&response)
+        let (system, user) = plugins::transform_prompt(&system, &user)?;
+        metrics::record_provider(ApiBackend::name(self));
+
+        if !ApiBackend::structured_output_enabled(self) {
+            let result = self.call_api(&system, &user, progress).await;
+            audit_call(self, &system, &user, &result);
+            return process_review_response(&plugins::transform_response(
+                result?.into_recorded_text(),
+            )?);
+        }
+
+        let schema = review_result_schema();
+        let result = self
+            .call_api_structured(&system, &user, &schema, progress)
+            .await;
+        audit_call(self, &system, &user, &result);
+        match result {
+            Ok(response) => process_structured_review_response(&plugins::transform_response(
+                response.into_recorded_text(),
+            )?),
+            // Endpoint/model rejected the schema constraint: retry unconstrained.
+            Err(GcopError::LlmApi {
+                status: 400,
+                message,
+            }) => {
+                tracing::debug!(
+                    "Structured output rejected by '{}', falling back to prompt-only JSON: {}",
+                    ApiBackend::name(self),
+                    message
+                );
+                let result = self.call_api(&system, &user, progress).await;
+                audit_call(self, &system, &user, &result);
+                process_review_response(&plugins::transform_response(result?.into_recorded_text())?)
+            }
+            Err(e) => Err(e),
+        }
     }
 
     fn name(&self) -> &str {
@@ -137,6 +254,6 @@ impl<T: ApiBackend> LLMProvider for T {
     }
 
     fn supports_streaming(&self) -> bool {
-        ApiBackend::supports_streaming(self)
+        ApiBackend::supports_streaming(self) && !plugins::transforms_responses()
     }
 }
@@ -111,10 +111,7 @@ pub fn strip_thinking_tags(text: &str) -> String {
     for tag in &["thinking", "think"] {
         let open = format!("<{}>", tag);
         let close = format!("</{}>", tag);
-        loop {
-            let Some(start) = result.find(&open) else {
-                break;
-            };
+        while let Some(start) = result.find(&open) {
             if let Some(rel_end) = result[start..].find(&close) {
                 let end = start + rel_end + close.len();
                 result = format!("{}{}", &result[..start], &result[end..]);
@@ -143,6 +140,66 @@ pub fn process_review_response(response: &str) -> Result<ReviewResult> {
     parse_review_response(&stripped)
 }
 
+/// Process schema-constrained review responses
+///
+/// The response is expected to be bare JSON matching [`review_result_schema`],
+/// so it is parsed directly. If that fails (e.g. the endpoint ignored the
+/// constraint), fall back to the sanitizing [`process_review_response`] path.
+pub fn process_structured_review_response(response: &str) -> Result<ReviewResult> {
+    match serde_json::from_str::<ReviewResult>(response.trim()) {
+        Ok(result) => {
+            tracing::debug!("Structured review response parsed directly");
+            Ok(result)
+        }
+        Err(e) => {
+            tracing::debug!(
+                "Structured review response not strict JSON ({}), falling back to sanitizing parser",
+                e
+            );
+            process_review_response(response)
+        }
+    }
+}
+
+/// JSON schema describing [`ReviewResult`]
+///
+/// Written in the strict subset accepted by OpenAI structured outputs:
+/// every property is listed in `required`, optional fields are nullable,
+/// and `additionalProperties` is disabled.
+pub fn review_result_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "summary": { "type": "string" },
+            "issues": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "severity": {
+                            "type": "string",
+                            "enum": ["critical", "warning", "info"]
+                        },
+                        "description": { "type": "string" },
+                        "file": { "type": ["string", "null"] },
+                        "line": { "type": ["integer", "null"] },
+                        "suggested_patch": { "type": ["string", "null"] },
+                        "category": { "type": ["string", "null"] }
+                    },
+                    "required": ["severity", "description", "file", "line", "suggested_patch", "category"],
+                    "additionalProperties": false
+                }
+            },
+            "suggestions": {
+                "type": "array",
+                "items": { "type": "string" }
+            }
+        },
+        "required": ["summary", "issues", "suggestions"],
+        "additionalProperties": false
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,6 +412,42 @@ Let me know if you need more."#;
         assert_eq!(result.issues[0].line, Some(42));
     }
 
+    // === structured review tests ===
+
+    #[test]
+    fn test_process_structured_review_strict_json() {
+        let json = r#"{"summary":"ok","issues":[{"severity":"warning","description":"d","file":null,"line":null}],"suggestions":[]}"#;
+        let result = process_structured_review_response(json).unwrap();
+        assert_eq!(result.summary, "ok");
+        assert_eq!(result.issues[0].file, None);
+        assert_eq!(result.issues[0].line, None);
+    }
+
+    #[test]
+    fn test_process_structured_review_falls_back_to_sanitizer() {
+        let wrapped = "```json\n{\"summary\":\"ok\",\"issues\":[],\"suggestions\":[]}\n```";
+        let result = process_structured_review_response(wrapped).unwrap();
+        assert_eq!(result.summary, "ok");
+    }
+
+    #[test]
+    fn test_review_result_schema_is_strict() {
+        let schema = review_result_schema();
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(
+            schema["required"],
+            serde_json::json!(["summary", "issues", "suggestions"])
+        );
+        assert_eq!(
+            schema["properties"]["issues"]["items"]["properties"]["severity"]["enum"],
+            serde_json::json!(["critical", "warning", "info"])
+        );
+        assert_eq!(
+            schema["properties"]["issues"]["items"]["required"][5],
+            "category"
+        );
+    }
+
     // === clean_commit_response tests ===
 
     #[test]
@@ -7,7 +7,9 @@ use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::time::{Duration, SystemTime};
 
+use super::budget;
 use crate::error::{GcopError, Result};
+use crate::llm::metrics;
 
 /// Determine whether the error should be retried
 pub(crate) fn is_retryable_error(error: &GcopError) -> bool {
@@ -220,6 +222,9 @@ pub async fn send_llm_request_streaming<Req: Serialize>(
 ///
 /// Both `send_llm_request` and `send_llm_request_streaming` delegate here;
 /// they differ only in what they do with the response on success.
+///
+/// Every attempt is drawn from the active [`budget`], and retries stop early
+/// once it cannot cover another attempt.
 #[allow(clippy::too_many_arguments)]
 async fn execute_with_retry<Req: Serialize>(
     client: &Client,
@@ -237,42 +242,67 @@ async fn execute_with_retry<Req: Serialize>(
     loop {
         attempt += 1;
 
-        let response =
-            match try_send_request(client, endpoint, headers, request_body, provider_name).await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    if !is_retryable_error(&e) || attempt > max_retries {
-                        return Err(e);
-                    }
+        if !budget::take_attempt() {
+            tracing::debug!(
+                "{} API request skipped: retry budget exhausted",
+                provider_name
+            );
+            return Err(GcopError::Llm(
+                rust_i18n::t!("provider.retry_budget_exhausted", provider = provider_name)
+                    .to_string(),
+            ));
+        }
 
-                    if let Some(p) = progress {
-                        let reason = match &e {
-                            GcopError::LlmTimeout { .. } => "timeout",
-                            GcopError::LlmConnectionFailed { .. } => "connection failed",
-                            _ => "network error",
-                        };
-                        p.append_suffix(&rust_i18n::t!(
-                            "provider.retrying_reason_suffix",
-                            attempt = attempt,
-                            max = max_retries,
-                            reason = reason
-                        ));
-                    }
+        let send = try_send_request(client, endpoint, headers, request_body, provider_name);
+        let result = match budget::remaining() {
+            Some(remaining) => tokio::time::timeout(remaining, send)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(GcopError::LlmTimeout {
+                        provider: provider_name.to_string(),
+                        detail: rust_i18n::t!("provider.retry_budget_timeout").to_string(),
+                    })
+                }),
+            None => send.await,
+        };
 
-                    let delay =
-                        calculate_exponential_backoff(attempt, retry_delay_ms, max_retry_delay_ms);
-                    tracing::debug!(
-                        "{} API network error (attempt {}/{}): {}. Retrying in {:.1}s...",
-                        provider_name,
-                        attempt,
-                        max_retries + 1,
-                        e,
-                        delay.as_secs_f64()
-                    );
-                    tokio::time::sleep(delay).await;
-                    continue;
+        let response = match result {
+            Ok(resp) => resp,
+            Err(e) => {
+                let delay =
+                    calculate_exponential_backoff(attempt, retry_delay_ms, max_retry_delay_ms);
+                if !is_retryable_error(&e) || attempt > max_retries || !budget::allows_retry(delay)
+                {
+                    return Err(e);
                 }
-            };
+
+                if let Some(p) = progress {
+                    let reason = match &e {
+                        GcopError::LlmTimeout { .. } => "timeout",
+                        GcopError::LlmConnectionFailed { .. } => "connection failed",
+                        _ => "network error",
+                    };
+                    p.append_suffix(&rust_i18n::t!(
+                        "provider.retrying_reason_suffix",
+                        attempt = attempt,
+                        max = max_retries,
+                        reason = reason
+                    ));
+                }
+
+                tracing::debug!(
+                    "{} API network error (attempt {}/{}): {}. Retrying in {:.1}s...",
+                    provider_name,
+                    attempt,
+                    max_retries + 1,
+                    e,
+                    delay.as_secs_f64()
+                );
+                metrics::record_retry();
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+        };
 
         let status = response.status();
 
@@ -301,20 +331,12 @@ async fn execute_with_retry<Req: Serialize>(
                 retry_after
             );
 
+            let rate_limited = || GcopError::LlmApi {
+                status: 429,
+                message: format!("{}: {}", provider_name, body),
+            };
             if attempt > max_retries {
-                return Err(GcopError::LlmApi {
-                    status: 429,
-                    message: format!("{}: {}", provider_name, body),
-                });
-            }
-
-            if let Some(p) = progress {
-                p.append_suffix(&rust_i18n::t!(
-                    "provider.retrying_reason_suffix",
-                    attempt = attempt,
-                    max = max_retries,
-                    reason = "429 rate limited"
-                ));
+                return Err(rate_limited());
             }
 
             let delay = if let Some(secs) = retry_after {
@@ -335,6 +357,18 @@ async fn execute_with_retry<Req: Serialize>(
             } else {
                 calculate_exponential_backoff(attempt, retry_delay_ms, max_retry_delay_ms)
             };
+            if !budget::allows_retry(delay) {
+                return Err(rate_limited());
+            }
+
+            if let Some(p) = progress {
+                p.append_suffix(&rust_i18n::t!(
+                    "provider.retrying_reason_suffix",
+                    attempt = attempt,
+                    max = max_retries,
+                    reason = "429 rate limited"
+                ));
+            }
 
             tracing::debug!(
                 "{} API rate limited (attempt {}/{}). Retrying in {:.1}s...",
@@ -343,6 +377,7 @@ async fn execute_with_retry<Req: Serialize>(
                 max_retries + 1,
                 delay.as_secs_f64()
             );
+            metrics::record_retry();
             tokio::time::sleep(delay).await;
             continue;
         }
@@ -354,7 +389,8 @@ async fn execute_with_retry<Req: Serialize>(
                 format!("<body read error: {}>", e)
             });
 
-            if attempt > max_retries {
+            let delay = calculate_exponential_backoff(attempt, retry_delay_ms, max_retry_delay_ms);
+            if attempt > max_retries || !budget::allows_retry(delay) {
                 return Err(GcopError::LlmApi {
                     status: status.as_u16(),
                     message: format!("{}: {}", provider_name, response_text),
@@ -370,7 +406,6 @@ async fn execute_with_retry<Req: Serialize>(
                 ));
             }
 
-            let delay = calculate_exponential_backoff(attempt, retry_delay_ms, max_retry_delay_ms);
             tracing::debug!(
                 "{} API server error {} (attempt {}/{}). Retrying in {:.1}s...",
                 provider_name,
@@ -379,6 +414,7 @@ async fn execute_with_retry<Req: Serialize>(
                 max_retries + 1,
                 delay.as_secs_f64()
             );
+            metrics::record_retry();
             tokio::time::sleep(delay).await;
             continue;
         }
@@ -440,7 +476,9 @@ pub(crate) fn spawn_stream_with_retry<ProcessFn, ProcessFut, ResendFn, ResendFut
 {
     use crate::llm::StreamChunk;
 
-    tokio::spawn(async move {
+    let budget = budget::current();
+    let quiet = crate::ui::is_quiet();
+    let task = budget::scope(budget, async move {
         let mut current_response = initial_response;
         let mut stream_attempt = 0usize;
 
@@ -450,7 +488,15 @@ pub(crate) fn spawn_stream_with_retry<ProcessFn, ProcessFut, ResendFn, ResendFut
                 Ok(()) => return,
                 Err(e) => {
                     stream_attempt += 1;
-                    if !is_retryable_error(&e) || stream_attempt > max_retries {
+                    let delay = calculate_exponential_backoff(
+                        stream_attempt,
+                        retry_delay_ms,
+                        max_retry_delay_ms,
+                    );
+                    if !is_retryable_error(&e)
+                        || stream_attempt > max_retries
+                        || !budget::allows_retry(delay)
+                    {
                         crate::ui::colors::error(
                             &rust_i18n::t!(
                                 "provider.stream_processing_error",
@@ -462,11 +508,6 @@ pub(crate) fn spawn_stream_with_retry<ProcessFn, ProcessFut, ResendFn, ResendFut
                         return;
                     }
 
-                    let delay = calculate_exponential_backoff(
-                        stream_attempt,
-                        retry_delay_ms,
-                        max_retry_delay_ms,
-                    );
                     tracing::warn!(
                         "{} stream truncated (attempt {}/{}). Retrying in {:.1}s...",
                         provider_name,
@@ -474,6 +515,7 @@ pub(crate) fn spawn_stream_with_retry<ProcessFn, ProcessFut, ResendFn, ResendFut
                         max_retries,
                         delay.as_secs_f64()
                     );
+                    metrics::record_retry();
                     tokio::time::sleep(delay).await;
 
                     match resend_request().await {
@@ -500,6 +542,7 @@ pub(crate) fn spawn_stream_with_retry<ProcessFn, ProcessFut, ResendFn, ResendFut
             }
         }
     });
+    tokio::spawn(crate::ui::quiet_scope(quiet, task));
 }
 
 /// Calculate exponential backoff delay
@@ -0,0 +1,156 @@
+//! Per-process circuit breaker for the fallback provider chain
+//!
+//! Tracks consecutive failures per provider. Once a provider reaches the
+//! failure threshold it is "open" and skipped for the cooldown period; after
+//! the cooldown it is tried again (half-open), and a single further failure
+//! re-opens it immediately.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Circuit breaker state for an indexed list of providers
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    states: Mutex<Vec<BreakerState>>,
+}
+
+impl CircuitBreaker {
+    /// Creates a breaker for `len` providers
+    ///
+    /// A `threshold` of `0` disables the breaker (every provider is always available).
+    pub fn new(len: usize, threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            states: Mutex::new(vec![BreakerState::default(); len]),
+        }
+    }
+
+    /// Creates a breaker that never opens
+    pub fn disabled(len: usize) -> Self {
+        Self::new(len, 0, Duration::ZERO)
+    }
+
+    fn with_states<R>(&self, f: impl FnOnce(&mut Vec<BreakerState>) -> R) -> R {
+        let mut guard = self.states.lock().unwrap_or_else(|e| e.into_inner());
+        f(&mut guard)
+    }
+
+    /// Whether the provider at `index` may be tried now (closed or half-open)
+    pub fn is_available(&self, index: usize) -> bool {
+        if self.threshold == 0 {
+            return true;
+        }
+        self.with_states(
+            |states| match states.get(index).and_then(|s| s.open_until) {
+                Some(until) => Instant::now() >= until,
+                None => true,
+            },
+        )
+    }
+
+    /// Returns the indices to try, in order
+    ///
+    /// Open providers are skipped. If every provider is open, all of them are
+    /// returned so the request is still attempted rather than failing outright.
+    pub fn ordered_candidates(&self, len: usize) -> Vec<usize> {
+        let available: Vec<usize> = (0..len).filter(|&i| self.is_available(i)).collect();
+        if available.is_empty() {
+            (0..len).collect()
+        } else {
+            available
+        }
+    }
+
+    /// Resets the failure count after a successful call
+    pub fn record_success(&self, index: usize) {
+        self.with_states(|states| {
+            if let Some(state) = states.get_mut(index) {
+                *state = BreakerState::default();
+            }
+        });
+    }
+
+    /// Records a failure; opens the breaker once the threshold is reached
+    ///
+    /// Returns `true` if this failure opened (or re-opened) the breaker.
+    pub fn record_failure(&self, index: usize) -> bool {
+        if self.threshold == 0 {
+            return false;
+        }
+        let threshold = self.threshold;
+        let cooldown = self.cooldown;
+        self.with_states(|states| {
+            let Some(state) = states.get_mut(index) else {
+                return false;
+            };
+            state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+            if state.consecutive_failures >= threshold {
+                state.open_until = Some(Instant::now() + cooldown);
+                true
+            } else {
+                false
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(2, 2, Duration::from_secs(60));
+        assert!(!breaker.record_failure(0));
+        assert!(breaker.is_available(0));
+        assert!(breaker.record_failure(0));
+        assert!(!breaker.is_available(0));
+        assert!(breaker.is_available(1));
+        assert_eq!(breaker.ordered_candidates(2), vec![1]);
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let breaker = CircuitBreaker::new(1, 2, Duration::from_secs(60));
+        breaker.record_failure(0);
+        breaker.record_success(0);
+        assert!(!breaker.record_failure(0));
+        assert!(breaker.is_available(0));
+    }
+
+    #[test]
+    fn test_half_open_after_cooldown() {
+        let breaker = CircuitBreaker::new(1, 1, Duration::ZERO);
+        assert!(breaker.record_failure(0));
+        // Zero cooldown: immediately half-open
+        assert!(breaker.is_available(0));
+        // A further failure re-opens right away
+        assert!(breaker.record_failure(0));
+    }
+
+    #[test]
+    fn test_all_open_returns_every_candidate() {
+        let breaker = CircuitBreaker::new(2, 1, Duration::from_secs(60));
+        breaker.record_failure(0);
+        breaker.record_failure(1);
+        assert_eq!(breaker.ordered_candidates(2), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_disabled_never_opens() {
+        let breaker = CircuitBreaker::disabled(1);
+        for _ in 0..10 {
+            assert!(!breaker.record_failure(0));
+        }
+        assert!(breaker.is_available(0));
+    }
+}
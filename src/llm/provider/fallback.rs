@@ -1,28 +1,205 @@
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
+use futures_util::future::{Either, select};
 use tokio::sync::mpsc;
 use tracing::debug;
 
-use crate::config::AppConfig;
+use crate::config::{AppConfig, ProviderStrategy};
 use crate::error::{GcopError, Result};
 use crate::llm::{
-    LLMProvider, ProgressReporter, ReviewResult, ReviewType, StreamChunk, StreamHandle,
+    LLMProvider, ProgressReporter, ReviewResult, ReviewType, StreamChunk, StreamHandle, metrics,
 };
 use crate::ui::colors;
 
+use super::base::budget::{self, RetryBudget};
+use super::circuit_breaker::CircuitBreaker;
 use super::create_single_provider;
 
 /// Fallback Provider - wraps multiple providers and automatically switches when failure occurs
+///
+/// A per-process [`CircuitBreaker`] skips providers that keep failing, so
+/// repeated calls (e.g. split mode) don't pay the primary's timeout every time.
+///
+/// With [`ProviderStrategy::Race`] the first two candidates are called
+/// concurrently and the first successful answer wins.
+///
+/// Each call runs under a fresh [`RetryBudget`] when `network.total_attempts`
+/// or `network.total_timeout` is set, so the chain as a whole stays bounded.
 pub struct FallbackProvider {
     providers: Vec<Arc<dyn LLMProvider>>,
     colored: bool,
+    breaker: CircuitBreaker,
+    strategy: ProviderStrategy,
+    total_attempts: usize,
+    total_timeout: Duration,
 }
 
 impl FallbackProvider {
     /// Creates a fallback wrapper from a prepared provider chain.
+    ///
+    /// The circuit breaker is disabled; use [`with_circuit_breaker`](Self::with_circuit_breaker) to enable it.
     pub fn new(providers: Vec<Arc<dyn LLMProvider>>, colored: bool) -> Self {
-        Self { providers, colored }
+        let breaker = CircuitBreaker::disabled(providers.len());
+        Self {
+            providers,
+            colored,
+            breaker,
+            strategy: ProviderStrategy::default(),
+            total_attempts: 0,
+            total_timeout: Duration::ZERO,
+        }
+    }
+
+    /// Bounds each call by a total attempt count and duration across the chain
+    ///
+    /// `0` / [`Duration::ZERO`] disables the respective limit.
+    pub fn with_retry_budget(mut self, total_attempts: usize, total_timeout: Duration) -> Self {
+        self.total_attempts = total_attempts;
+        self.total_timeout = total_timeout;
+        self
+    }
+
+    /// Fresh budget for one call, if any limit is set
+    fn start_budget(&self) -> Option<Arc<RetryBudget>> {
+        (self.total_attempts > 0 || !self.total_timeout.is_zero())
+            .then(|| Arc::new(RetryBudget::new(self.total_attempts, self.total_timeout)))
+    }
+
+    /// Whether the chain should stop before the next candidate
+    fn budget_exhausted(&self) -> bool {
+        let exhausted = budget::is_exhausted();
+        if exhausted {
+            debug!("Retry budget exhausted, not trying further providers");
+        }
+        exhausted
+    }
+
+    /// Sets how the provider chain is used
+    pub fn with_strategy(mut self, strategy: ProviderStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Enables the circuit breaker
+    ///
+    /// After `threshold` consecutive failures a provider is skipped for `cooldown`.
+    /// A `threshold` of `0` keeps the breaker disabled.
+    pub fn with_circuit_breaker(mut self, threshold: u32, cooldown: Duration) -> Self {
+        self.breaker = CircuitBreaker::new(self.providers.len(), threshold, cooldown);
+        self
+    }
+
+    /// Provider indices to try for this call, skipping open circuits
+    fn candidates(&self) -> Vec<usize> {
+        let candidates = self.breaker.ordered_candidates(self.providers.len());
+        if candidates.len() < self.providers.len() {
+            for (i, provider) in self.providers.iter().enumerate() {
+                if !candidates.contains(&i) {
+                    debug!("Circuit open for provider '{}', skipping", provider.name());
+                }
+            }
+        }
+        candidates
+    }
+
+    fn record_failure(&self, index: usize) {
+        if self.breaker.record_failure(index) {
+            debug!(
+                "Circuit opened for provider '{}' after repeated failures",
+                self.providers[index].name()
+            );
+        }
+    }
+
+    /// The two candidates to race, if racing is enabled and possible
+    fn race_pair(&self, candidates: &[usize]) -> Option<(usize, usize)> {
+        match candidates {
+            [a, b, ..] if self.strategy == ProviderStrategy::Race => Some((*a, *b)),
+            _ => None,
+        }
+    }
+
+    /// Runs `run` on providers `a` and `b` concurrently
+    ///
+    /// The first success wins and the slower call is dropped (cancelling its
+    /// request). If both fail, the errors are returned in completion order.
+    async fn race<'a, T, F, Fut>(
+        &'a self,
+        a: usize,
+        b: usize,
+        run: F,
+    ) -> std::result::Result<T, Vec<(usize, GcopError)>>
+    where
+        F: Fn(&'a dyn LLMProvider) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        debug!(
+            "Racing providers '{}' and '{}'",
+            self.providers[a].name(),
+            self.providers[b].name()
+        );
+        let first = std::pin::pin!(run(self.providers[a].as_ref()));
+        let second = std::pin::pin!(run(self.providers[b].as_ref()));
+
+        let (result, index, other, other_index) = match select(first, second).await {
+            Either::Left((result, other)) => (result, a, other, b),
+            Either::Right((result, other)) => (result, b, other, a),
+        };
+        let first_error = match result {
+            Ok(value) => return Ok(self.race_won(index, value)),
+            Err(e) => {
+                self.record_failure(index);
+                e
+            }
+        };
+
+        match other.await {
+            Ok(value) => {
+                self.warn_failed(index, &first_error);
+                Ok(self.race_won(other_index, value))
+            }
+            Err(e) => {
+                self.record_failure(other_index);
+                Err(vec![(index, first_error), (other_index, e)])
+            }
+        }
+    }
+
+    fn race_won<T>(&self, index: usize, value: T) -> T {
+        let name = self.providers[index].name();
+        debug!("Provider '{}' won the race", name);
+        self.breaker.record_success(index);
+        // Both providers recorded themselves when they started.
+        metrics::record_provider(name);
+        value
+    }
+
+    fn warn_failed(&self, index: usize, error: &GcopError) {
+        colors::warning(
+            &rust_i18n::t!(
+                "provider.fallback_provider_failed",
+                provider = self.providers[index].name(),
+                error = error.to_string()
+            ),
+            self.colored,
+        );
+    }
+
+    /// Warns about both failures of a lost race, except the final error of the
+    /// chain; returns the last error.
+    fn race_failed(&self, failures: Vec<(usize, GcopError)>, more: bool) -> Option<GcopError> {
+        let count = failures.len();
+        let mut last_error = None;
+        for (n, (index, e)) in failures.into_iter().enumerate() {
+            if more || n + 1 < count {
+                self.warn_failed(index, &e);
+            }
+            last_error = Some(e);
+        }
+        last_error
     }
 
     /// Create FallbackProvider from configuration
@@ -35,14 +212,26 @@ impl FallbackProvider {
     ) -> Result<Arc<dyn LLMProvider>> {
         let colored = config.ui.colored;
         let main_name = provider_name.unwrap_or(&config.llm.default_provider);
+        // Plugin errors must not be mistaken for an unusable provider below
+        crate::llm::plugins::install(&config.plugins)?;
 
         // Collect all provider names to try
         let mut provider_names: Vec<&str> = vec![main_name];
         provider_names.extend(config.llm.fallback_providers.iter().map(String::as_str));
 
-        // If there is only one provider (no fallback), create it directly
+        // If there is only one provider (no fallback), create it directly;
+        // it is only wrapped to enforce a retry budget
         if provider_names.len() == 1 {
-            return create_single_provider(config, provider_names[0], colored);
+            let provider = create_single_provider(config, provider_names[0], colored)?;
+            if RetryBudget::from_config(&config.network).is_none() {
+                return Ok(provider);
+            }
+            return Ok(Arc::new(
+                Self::new(vec![provider], colored).with_retry_budget(
+                    config.network.total_attempts,
+                    Duration::from_secs(config.network.total_timeout),
+                ),
+            ));
         }
 
         // Create all providers and record debug logs on failure
@@ -68,7 +257,7 @@ impl FallbackProvider {
         }
 
         // If exactly one provider is available, return it directly.
-        if providers.len() == 1 {
+        if providers.len() == 1 && RetryBudget::from_config(&config.network).is_none() {
             // SAFETY: len() == 1 guarantees that there are elements
             return Ok(providers
                 .into_iter()
@@ -76,69 +265,72 @@ impl FallbackProvider {
                 .expect("providers is non-empty: len() == 1"));
         }
 
-        Ok(Arc::new(Self::new(providers, colored)))
+        Ok(Arc::new(
+            Self::new(providers, colored)
+                .with_circuit_breaker(
+                    config.network.circuit_breaker_threshold,
+                    Duration::from_secs(config.network.circuit_breaker_cooldown_secs),
+                )
+                .with_strategy(config.llm.strategy)
+                .with_retry_budget(
+                    config.network.total_attempts,
+                    Duration::from_secs(config.network.total_timeout),
+                ),
+        ))
     }
 }
 
-#[async_trait]
-impl LLMProvider for FallbackProvider {
-    fn name(&self) -> &str {
-        "fallback"
-    }
-
-    fn supports_streaming(&self) -> bool {
-        self.providers
-            .first()
-            .map(|p| p.supports_streaming())
-            .unwrap_or(false)
-    }
-
-    async fn validate(&self) -> Result<()> {
-        if self.providers.is_empty() {
-            return Err(GcopError::Config(
-                rust_i18n::t!("provider.no_providers_configured").to_string(),
-            ));
+/// Re-wraps a stream whose first chunk was already received
+fn prepend_chunk(first: StreamChunk, mut handle: StreamHandle) -> StreamHandle {
+    let (tx, rx) = mpsc::channel(32);
+    tokio::spawn(async move {
+        let done = matches!(first, StreamChunk::Done);
+        if tx.send(first).await.is_err() || done {
+            return;
         }
-
-        let mut all_failed = true;
-
-        for provider in &self.providers {
-            tracing::debug!("Validating provider '{}'...", provider.name());
-
-            match provider.validate().await {
-                Ok(_) => {
-                    all_failed = false;
-                    tracing::debug!("Provider '{}' validated successfully", provider.name());
-                }
-                Err(e) => {
-                    tracing::debug!("Provider '{}' validation failed: {}", provider.name(), e);
-                }
+        while let Some(chunk) = handle.receiver.recv().await {
+            if tx.send(chunk).await.is_err() {
+                break;
             }
         }
+    });
+    StreamHandle { receiver: rx }
+}
 
-        if all_failed {
-            return Err(GcopError::Config(
-                rust_i18n::t!(
-                    "provider.all_providers_failed_validation",
-                    count = self.providers.len()
-                )
-                .to_string(),
-            ));
-        }
-
-        Ok(())
-    }
-
-    async fn send_prompt(
+/// Chain loops behind the [`LLMProvider`] methods, which install the retry budget
+impl FallbackProvider {
+    async fn send_prompt_chain(
         &self,
         system_prompt: &str,
         user_prompt: &str,
         progress: Option<&dyn ProgressReporter>,
     ) -> Result<String> {
         let mut last_error = None;
+        let candidates = self.candidates();
+        let mut raced = 0;
 
-        for (i, provider) in self.providers.iter().enumerate() {
-            if i > 0
+        if let Some((a, b)) = self.race_pair(&candidates) {
+            match self
+                .race(a, b, |p| {
+                    p.send_prompt(system_prompt, user_prompt, progress)
+                })
+                .await
+            {
+                Ok(msg) => return Ok(msg),
+                Err(failures) => {
+                    let more = candidates.len() > 2 && !budget::is_exhausted();
+                    last_error = self.race_failed(failures, more);
+                }
+            }
+            raced = 2;
+        }
+
+        for (pos, &i) in candidates.iter().enumerate().skip(raced) {
+            if last_error.is_some() && self.budget_exhausted() {
+                break;
+            }
+            let provider = &self.providers[i];
+            if pos > 0
                 && let Some(p) = progress
             {
                 p.append_suffix(&rust_i18n::t!(
@@ -151,17 +343,14 @@ impl LLMProvider for FallbackProvider {
                 .send_prompt(system_prompt, user_prompt, progress)
                 .await
             {
-                Ok(msg) => return Ok(msg),
+                Ok(msg) => {
+                    self.breaker.record_success(i);
+                    return Ok(msg);
+                }
                 Err(e) => {
-                    if i < self.providers.len() - 1 {
-                        colors::warning(
-                            &rust_i18n::t!(
-                                "provider.fallback_provider_failed",
-                                provider = provider.name(),
-                                error = e.to_string()
-                            ),
-                            self.colored,
-                        );
+                    self.record_failure(i);
+                    if pos < candidates.len() - 1 && !budget::is_exhausted() {
+                        self.warn_failed(i, &e);
                     }
                     last_error = Some(e);
                 }
@@ -173,26 +362,64 @@ impl LLMProvider for FallbackProvider {
         }))
     }
 
-    async fn send_prompt_streaming(
+    async fn send_prompt_streaming_chain(
         &self,
         system_prompt: &str,
         user_prompt: &str,
     ) -> Result<StreamHandle> {
         let mut last_error = None;
-        let mut tried_streaming = false;
+        let candidates: Vec<usize> = self
+            .candidates()
+            .into_iter()
+            .filter(|&i| self.providers[i].supports_streaming())
+            .collect();
+        let tried_streaming = !candidates.is_empty();
+        let mut raced = 0;
+
+        if let Some((a, b)) = self.race_pair(&candidates) {
+            // A stream wins with its first chunk; the loser's receiver is dropped.
+            let first_chunk = self.race(a, b, |p| async move {
+                let mut handle = p.send_prompt_streaming(system_prompt, user_prompt).await?;
+                match handle.receiver.recv().await {
+                    Some(StreamChunk::Error(e)) => Err(GcopError::Llm(e)),
+                    chunk => Ok((chunk.unwrap_or(StreamChunk::Done), handle)),
+                }
+            });
+            match first_chunk.await {
+                Ok((chunk, handle)) => return Ok(prepend_chunk(chunk, handle)),
+                Err(failures) => {
+                    for (i, e) in failures {
+                        colors::warning(
+                            &rust_i18n::t!(
+                                "provider.fallback_streaming_failed",
+                                provider = self.providers[i].name(),
+                                error = e.to_string()
+                            ),
+                            self.colored,
+                        );
+                        last_error = Some(e);
+                    }
+                }
+            }
+            raced = 2;
+        }
 
-        for provider in &self.providers {
-            if !provider.supports_streaming() {
-                continue;
+        for &i in candidates.iter().skip(raced) {
+            if last_error.is_some() && self.budget_exhausted() {
+                break;
             }
-            tried_streaming = true;
+            let provider = &self.providers[i];
 
             match provider
                 .send_prompt_streaming(system_prompt, user_prompt)
                 .await
             {
-                Ok(handle) => return Ok(handle),
+                Ok(handle) => {
+                    self.breaker.record_success(i);
+                    return Ok(handle);
+                }
                 Err(e) => {
+                    self.record_failure(i);
                     colors::warning(
                         &rust_i18n::t!(
                             "provider.fallback_streaming_failed",
@@ -230,9 +457,7 @@ impl LLMProvider for FallbackProvider {
         Ok(StreamHandle { receiver: rx })
     }
 
-    // generate_commit_message: trait default (build prompt → send_prompt with fallback)
-
-    async fn review_code(
+    async fn review_code_chain(
         &self,
         diff: &str,
         review_type: ReviewType,
@@ -240,9 +465,31 @@ impl LLMProvider for FallbackProvider {
         progress: Option<&dyn ProgressReporter>,
     ) -> Result<ReviewResult> {
         let mut last_error = None;
+        let candidates = self.candidates();
+        let mut raced = 0;
 
-        for (i, provider) in self.providers.iter().enumerate() {
-            if i > 0
+        if let Some((a, b)) = self.race_pair(&candidates) {
+            match self
+                .race(a, b, |p| {
+                    p.review_code(diff, review_type.clone(), custom_prompt, progress)
+                })
+                .await
+            {
+                Ok(result) => return Ok(result),
+                Err(failures) => {
+                    let more = candidates.len() > 2 && !budget::is_exhausted();
+                    last_error = self.race_failed(failures, more);
+                }
+            }
+            raced = 2;
+        }
+
+        for (pos, &i) in candidates.iter().enumerate().skip(raced) {
+            if last_error.is_some() && self.budget_exhausted() {
+                break;
+            }
+            let provider = &self.providers[i];
+            if pos > 0
                 && let Some(p) = progress
             {
                 p.append_suffix(&rust_i18n::t!(
@@ -255,17 +502,14 @@ impl LLMProvider for FallbackProvider {
                 .review_code(diff, review_type.clone(), custom_prompt, progress)
                 .await
             {
-                Ok(result) => return Ok(result),
+                Ok(result) => {
+                    self.breaker.record_success(i);
+                    return Ok(result);
+                }
                 Err(e) => {
-                    if i < self.providers.len() - 1 {
-                        colors::warning(
-                            &rust_i18n::t!(
-                                "provider.fallback_provider_failed",
-                                provider = provider.name(),
-                                error = e.to_string()
-                            ),
-                            self.colored,
-                        );
+                    self.record_failure(i);
+                    if pos < candidates.len() - 1 && !budget::is_exhausted() {
+                        self.warn_failed(i, &e);
                     }
                     last_error = Some(e);
                 }
@@ -276,6 +520,88 @@ impl LLMProvider for FallbackProvider {
             GcopError::Llm(rust_i18n::t!("provider.no_providers_available").to_string())
         }))
     }
+}
+
+#[async_trait]
+impl LLMProvider for FallbackProvider {
+    fn name(&self) -> &str {
+        "fallback"
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.providers
+            .first()
+            .map(|p| p.supports_streaming())
+            .unwrap_or(false)
+    }
+
+    async fn validate(&self) -> Result<()> {
+        if self.providers.is_empty() {
+            return Err(GcopError::Config(
+                rust_i18n::t!("provider.no_providers_configured").to_string(),
+            ));
+        }
+
+        let mut all_failed = true;
+
+        for provider in &self.providers {
+            tracing::debug!("Validating provider '{}'...", provider.name());
+
+            match provider.validate().await {
+                Ok(_) => {
+                    all_failed = false;
+                    tracing::debug!("Provider '{}' validated successfully", provider.name());
+                }
+                Err(e) => {
+                    tracing::debug!("Provider '{}' validation failed: {}", provider.name(), e);
+                }
+            }
+        }
+
+        if all_failed {
+            return Err(GcopError::Config(
+                rust_i18n::t!(
+                    "provider.all_providers_failed_validation",
+                    count = self.providers.len()
+                )
+                .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn send_prompt(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        progress: Option<&dyn ProgressReporter>,
+    ) -> Result<String> {
+        let chain = self.send_prompt_chain(system_prompt, user_prompt, progress);
+        budget::scope(self.start_budget(), chain).await
+    }
+
+    async fn send_prompt_streaming(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<StreamHandle> {
+        let chain = self.send_prompt_streaming_chain(system_prompt, user_prompt);
+        budget::scope(self.start_budget(), chain).await
+    }
+
+    // generate_commit_message: trait default (build prompt → send_prompt with fallback)
+
+    async fn review_code(
+        &self,
+        diff: &str,
+        review_type: ReviewType,
+        custom_prompt: Option<&str>,
+        progress: Option<&dyn ProgressReporter>,
+    ) -> Result<ReviewResult> {
+        let chain = self.review_code_chain(diff, review_type, custom_prompt, progress);
+        budget::scope(self.start_budget(), chain).await
+    }
 
     // generate_commit_message_streaming: trait default (build prompt → send_prompt_streaming with fallback)
 }
@@ -289,6 +615,7 @@ mod tests {
         should_fail: bool,
         supports_streaming: bool,
         message: String,
+        delay: Duration,
     }
 
     impl TestProvider {
@@ -298,9 +625,15 @@ mod tests {
                 should_fail: false,
                 supports_streaming: false,
                 message: format!("message from {}", name),
+                delay: Duration::ZERO,
             }
         }
 
+        fn with_delay(mut self, millis: u64) -> Self {
+            self.delay = Duration::from_millis(millis);
+            self
+        }
+
         fn with_failure(mut self) -> Self {
             self.should_fail = true;
             self
@@ -320,6 +653,7 @@ mod tests {
             _user_prompt: &str,
             _progress: Option<&dyn ProgressReporter>,
         ) -> Result<String> {
+            tokio::time::sleep(self.delay).await;
             if self.should_fail {
                 Err(GcopError::Llm(format!("{} failed", self.name)))
             } else {
@@ -332,6 +666,7 @@ mod tests {
             _system_prompt: &str,
             _user_prompt: &str,
         ) -> Result<StreamHandle> {
+            tokio::time::sleep(self.delay).await;
             if self.should_fail {
                 Err(GcopError::Llm(format!("{} streaming failed", self.name)))
             } else {
@@ -354,6 +689,7 @@ mod tests {
             _custom_prompt: Option<&str>,
             _progress: Option<&dyn ProgressReporter>,
         ) -> Result<ReviewResult> {
+            tokio::time::sleep(self.delay).await;
             if self.should_fail {
                 Err(GcopError::Llm(format!("{} failed", self.name)))
             } else {
@@ -492,6 +828,99 @@ mod tests {
         assert_eq!(result.unwrap().summary, "message from fallback");
     }
 
+    // === Test circuit breaker ===
+
+    struct CountingProvider {
+        name: String,
+        fail: bool,
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl LLMProvider for CountingProvider {
+        async fn send_prompt(
+            &self,
+            _system_prompt: &str,
+            _user_prompt: &str,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<String> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.fail {
+                Err(GcopError::Llm(format!("{} failed", self.name)))
+            } else {
+                Ok(format!("message from {}", self.name))
+            }
+        }
+
+        async fn review_code(
+            &self,
+            _diff: &str,
+            _review_type: ReviewType,
+            _custom_prompt: Option<&str>,
+            _progress: Option<&dyn ProgressReporter>,
+        ) -> Result<ReviewResult> {
+            unimplemented!()
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn validate(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn counting(name: &str, fail: bool) -> Arc<CountingProvider> {
+        Arc::new(CountingProvider {
+            name: name.to_string(),
+            fail,
+            calls: std::sync::atomic::AtomicUsize::new(0),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_skips_failing_primary() {
+        let primary = counting("primary", true);
+        let backup = counting("backup", false);
+        let fallback = FallbackProvider::new(vec![primary.clone(), backup.clone()], false)
+            .with_circuit_breaker(2, Duration::from_secs(60));
+
+        for _ in 0..5 {
+            let msg = fallback.send_prompt("s", "u", None).await.unwrap();
+            assert_eq!(msg, "message from backup");
+        }
+
+        // Primary is tried until the threshold is reached, then skipped.
+        assert_eq!(primary.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(backup.calls.load(std::sync::atomic::Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_disabled_by_default() {
+        let primary = counting("primary", true);
+        let backup = counting("backup", false);
+        let fallback = FallbackProvider::new(vec![primary.clone(), backup.clone()], false);
+
+        for _ in 0..3 {
+            fallback.send_prompt("s", "u", None).await.unwrap();
+        }
+        assert_eq!(primary.calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_all_open_still_tries() {
+        let primary = counting("primary", true);
+        let backup = counting("backup", true);
+        let fallback = FallbackProvider::new(vec![primary.clone(), backup.clone()], false)
+            .with_circuit_breaker(1, Duration::from_secs(60));
+
+        assert!(fallback.send_prompt("s", "u", None).await.is_err());
+        // Both circuits are open now, but the request is still attempted.
+        assert!(fallback.send_prompt("s", "u", None).await.is_err());
+        assert_eq!(primary.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
     // === Test generate_commit_message_streaming ===
 
     #[tokio::test]
@@ -530,4 +959,124 @@ mod tests {
             // OK
         }
     }
+
+    // === Test race strategy ===
+
+    fn racing(providers: Vec<TestProvider>) -> FallbackProvider {
+        let providers = providers
+            .into_iter()
+            .map(|p| Arc::new(p) as Arc<dyn LLMProvider>)
+            .collect();
+        FallbackProvider::new(providers, false).with_strategy(ProviderStrategy::Race)
+    }
+
+    #[tokio::test]
+    async fn test_race_takes_fastest_provider() {
+        let fallback = racing(vec![
+            TestProvider::new("slow").with_delay(500),
+            TestProvider::new("fast"),
+        ]);
+        let msg = fallback.send_prompt("s", "u", None).await.unwrap();
+        assert_eq!(msg, "message from fast");
+
+        let review = fallback
+            .review_code("diff", ReviewType::UncommittedChanges, None, None)
+            .await
+            .unwrap();
+        assert_eq!(review.summary, "message from fast");
+    }
+
+    #[tokio::test]
+    async fn test_race_waits_for_other_on_failure() {
+        let fallback = racing(vec![
+            TestProvider::new("broken").with_failure(),
+            TestProvider::new("slow").with_delay(50),
+        ]);
+        let msg = fallback.send_prompt("s", "u", None).await.unwrap();
+        assert_eq!(msg, "message from slow");
+    }
+
+    #[tokio::test]
+    async fn test_race_falls_back_to_remaining_providers() {
+        let fallback = racing(vec![
+            TestProvider::new("p1").with_failure(),
+            TestProvider::new("p2").with_failure(),
+            TestProvider::new("p3"),
+        ]);
+        let msg = fallback.send_prompt("s", "u", None).await.unwrap();
+        assert_eq!(msg, "message from p3");
+
+        let fallback = racing(vec![
+            TestProvider::new("p1").with_failure(),
+            TestProvider::new("p2").with_failure(),
+        ]);
+        let err = fallback.send_prompt("s", "u", None).await.unwrap_err();
+        assert!(err.to_string().contains("failed"));
+    }
+
+    #[tokio::test]
+    async fn test_race_streaming_takes_first_chunk() {
+        let fallback = racing(vec![
+            TestProvider::new("slow").with_streaming().with_delay(500),
+            TestProvider::new("fast").with_streaming(),
+        ]);
+        let mut handle = fallback.send_prompt_streaming("s", "u").await.unwrap();
+        let mut chunks = Vec::new();
+        while let Some(chunk) = handle.receiver.recv().await {
+            chunks.push(chunk);
+        }
+        assert!(
+            matches!(&chunks[..], [StreamChunk::Delta(msg), StreamChunk::Done] if msg == "message from fast")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_budget_shared_across_chain() {
+        use crate::config::NetworkConfig;
+        use crate::llm::provider::backends::OpenAIProvider;
+        use crate::llm::provider::test_utils::{ensure_crypto_provider, test_provider_config};
+
+        ensure_crypto_provider();
+        let mut server = mockito::Server::new_async().await;
+        // 每个 provider 最多重试 3 次，但整条链只允许 2 次请求
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(500)
+            .with_body("boom")
+            .expect(2)
+            .create_async()
+            .await;
+
+        let network = NetworkConfig {
+            max_retries: 3,
+            retry_delay_ms: 1,
+            ..Default::default()
+        };
+        let provider = |name: &str| -> Arc<dyn LLMProvider> {
+            let config = test_provider_config(
+                server.url(),
+                Some("sk-test".to_string()),
+                "gpt-4o-mini".to_string(),
+            );
+            Arc::new(OpenAIProvider::new(&config, name, &network, false).unwrap())
+        };
+        let fallback = FallbackProvider::new(vec![provider("a"), provider("b")], false)
+            .with_retry_budget(2, Duration::ZERO);
+
+        let err = fallback.send_prompt("s", "u", None).await.unwrap_err();
+        assert!(matches!(err, GcopError::LlmApi { status: 500, .. }));
+        mock.assert_async().await;
+
+        // 每次调用都有新的预算
+        server.reset();
+        let mock = server
+            .mock("POST", "/v1/chat/completions")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"choices":[{"message":{"content":"ok"}}]}"#)
+            .create_async()
+            .await;
+        assert_eq!(fallback.send_prompt("s", "u", None).await.unwrap(), "ok");
+        mock.assert_async().await;
+    }
 }
@@ -1,45 +1,120 @@
 pub mod backends;
 pub mod base;
+/// Per-process circuit breaker used by the fallback chain.
+pub mod circuit_breaker;
 /// Multi-provider fallback wrapper.
 pub mod fallback;
+/// Model listing for the setup wizard.
+pub mod models;
 pub mod streaming;
+/// Diff-size based model tiers of one provider.
+pub mod tiered;
 pub mod utils;
 
 #[cfg(test)]
 pub mod test_utils;
 
-use std::sync::{Arc, OnceLock};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 
-use reqwest::Client;
+use reqwest::{Client, NoProxy, Proxy};
+use tracing::debug;
 
-use crate::config::{ApiStyle, AppConfig, NetworkConfig, ProviderConfig};
+use crate::config::{ApiStyle, AppConfig, CommandLlmConfig, NetworkConfig, ProviderConfig};
 use crate::error::{GcopError, Result};
 use crate::llm::LLMProvider;
 
-/// Global HTTP client (shared connection pool)
-static HTTP_CLIENT: OnceLock<Client> = OnceLock::new();
+/// Proxy selection for one pooled HTTP client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ProxyMode {
+    /// Environment variables and system settings (reqwest default).
+    System,
+    /// No proxy at all.
+    Direct,
+    /// Explicit proxy URL with an optional bypass list.
+    Url {
+        url: String,
+        no_proxy: Option<String>,
+    },
+}
 
-/// Global HTTP client initialization error message
-///
-/// If the first creation fails, save the error string to avoid subsequent repeated creations and potential panics.
-static HTTP_CLIENT_ERROR: OnceLock<String> = OnceLock::new();
+/// Settings that distinguish pooled HTTP clients.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct HttpClientKey {
+    request_timeout: u64,
+    connect_timeout: u64,
+    proxy: ProxyMode,
+}
+
+impl HttpClientKey {
+    /// Builds the key from network settings and an optional per-provider proxy.
+    ///
+    /// `proxy_override` replaces `network.proxy`; an empty string means direct.
+    fn new(network_config: &NetworkConfig, proxy_override: Option<&str>) -> Self {
+        let proxy = match proxy_override.or(network_config.proxy.as_deref()) {
+            None => ProxyMode::System,
+            Some("") => ProxyMode::Direct,
+            Some(url) => ProxyMode::Url {
+                url: url.to_string(),
+                no_proxy: network_config.no_proxy.clone(),
+            },
+        };
+        Self {
+            request_timeout: network_config.request_timeout,
+            connect_timeout: network_config.connect_timeout,
+            proxy,
+        }
+    }
+}
 
-/// Get or create a global HTTP client
+/// HTTP client pool keyed by effective settings
 ///
-/// Use OnceLock to ensure it is created only once and all providers share the same connection pool.
-/// The first call to NetworkConfig determines the timeout configuration.
+/// Clients with the same settings share one connection pool. Creation errors are
+/// cached as strings so a broken configuration is not rebuilt on every call.
+static HTTP_CLIENTS: OnceLock<Mutex<HashMap<HttpClientKey, std::result::Result<Client, String>>>> =
+    OnceLock::new();
+
+/// Get or create a pooled HTTP client using `[network]` settings
 pub(crate) fn create_http_client(network_config: &NetworkConfig) -> Result<Client> {
-    if let Some(client) = HTTP_CLIENT.get() {
-        return Ok(client.clone());
-    }
+    create_http_client_with_proxy(network_config, None)
+}
 
-    if let Some(err_msg) = HTTP_CLIENT_ERROR.get() {
-        return Err(GcopError::Llm(
-            rust_i18n::t!("provider.http_client_init_failed", error = err_msg.as_str()).to_string(),
-        ));
+/// Get or create a pooled HTTP client with a per-provider proxy override
+pub(crate) fn create_http_client_with_proxy(
+    network_config: &NetworkConfig,
+    proxy_override: Option<&str>,
+) -> Result<Client> {
+    let key = HttpClientKey::new(network_config, proxy_override);
+    let mut pool = HTTP_CLIENTS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if let Some(entry) = pool.get(&key) {
+        return entry.clone().map_err(|err_msg| {
+            GcopError::Llm(
+                rust_i18n::t!("provider.http_client_init_failed", error = err_msg.as_str())
+                    .to_string(),
+            )
+        });
     }
 
+    let entry = build_http_client(&key);
+    pool.insert(key, entry.clone());
+    entry.map_err(|err_msg| {
+        GcopError::Llm(
+            rust_i18n::t!(
+                "provider.http_client_create_failed",
+                error = err_msg.as_str()
+            )
+            .to_string(),
+        )
+    })
+}
+
+/// Build a new HTTP client for the given settings
+fn build_http_client(key: &HttpClientKey) -> std::result::Result<Client, String> {
     let user_agent = format!(
         "{}/{} ({})",
         env!("CARGO_PKG_NAME"),
@@ -47,39 +122,65 @@ pub(crate) fn create_http_client(network_config: &NetworkConfig) -> Result<Clien
         std::env::consts::OS
     );
 
-    match Client::builder()
+    let mut builder = Client::builder()
         .user_agent(user_agent)
-        .timeout(Duration::from_secs(network_config.request_timeout))
-        .connect_timeout(Duration::from_secs(network_config.connect_timeout))
-        .build()
-    {
-        Ok(client) => {
-            let _ = HTTP_CLIENT.set(client.clone());
-            Ok(client)
-        }
-        Err(e) => {
-            let err_msg = e.to_string();
-            let _ = HTTP_CLIENT_ERROR.set(err_msg.clone());
-            Err(GcopError::Llm(
-                rust_i18n::t!(
-                    "provider.http_client_create_failed",
-                    error = err_msg.as_str()
-                )
-                .to_string(),
-            ))
+        .timeout(Duration::from_secs(key.request_timeout))
+        .connect_timeout(Duration::from_secs(key.connect_timeout));
+
+    match &key.proxy {
+        ProxyMode::System => {}
+        ProxyMode::Direct => builder = builder.no_proxy(),
+        ProxyMode::Url { url, no_proxy } => {
+            let proxy = Proxy::all(url.as_str())
+                .map_err(|e| e.to_string())?
+                .no_proxy(no_proxy.as_deref().and_then(NoProxy::from_string));
+            builder = builder.proxy(proxy);
         }
     }
+
+    builder.build().map_err(|e| e.to_string())
 }
 
 /// Create LLM Provider based on configuration
 ///
 /// If fallback_providers is configured, a FallbackProvider will be created to wrap multiple providers.
 /// When the main provider fails, providers in the fallback list are automatically tried.
+///
+/// `command` is the running command's `[commit.llm]` / `[review.llm]` section;
+/// `provider_name` and `model_name` (CLI `--provider` / `--model`) take
+/// precedence over it. A model override must be in the provider's
+/// `allowed_models` when that list is set.
 pub fn create_provider(
     config: &AppConfig,
+    command: &CommandLlmConfig,
     provider_name: Option<&str>,
+    model_name: Option<&str>,
 ) -> Result<Arc<dyn LLMProvider>> {
-    fallback::FallbackProvider::from_config(config, provider_name)
+    let (main, model) = command.select(&config.llm.default_provider, provider_name, model_name);
+    let Some(model) = model else {
+        return fallback::FallbackProvider::from_config(config, Some(main));
+    };
+
+    let mut config = config.clone();
+    if let Some(provider_config) = config.llm.providers.get_mut(main) {
+        if let Some(allowed) = &provider_config.allowed_models
+            && !allowed.iter().any(|m| m == model)
+        {
+            return Err(GcopError::Config(
+                rust_i18n::t!(
+                    "provider.model_not_allowed",
+                    model = model,
+                    provider = main,
+                    allowed = allowed.join(", ")
+                )
+                .to_string(),
+            ));
+        }
+        debug!("Using model '{}' for provider '{}'", model, main);
+        provider_config.model = model.to_string();
+        provider_config.tiers = None;
+    }
+    fallback::FallbackProvider::from_config(&config, Some(main))
 }
 
 /// Create a single Provider
@@ -88,11 +189,18 @@ pub fn create_single_provider(
     name: &str,
     colored: bool,
 ) -> Result<Arc<dyn LLMProvider>> {
+    crate::llm::plugins::install(&config.plugins)?;
     let provider_config = config.llm.providers.get(name).ok_or_else(|| {
         GcopError::Config(rust_i18n::t!("provider.provider_not_found", name = name).to_string())
     })?;
 
-    create_provider_from_config(provider_config, name, &config.network, colored)
+    create_provider_from_config(
+        provider_config,
+        name,
+        &config.network,
+        colored,
+        config.review.structured_output,
+    )
 }
 
 /// Create specific Provider implementation based on configuration
@@ -101,6 +209,7 @@ fn create_provider_from_config(
     name: &str,
     network_config: &NetworkConfig,
     colored: bool,
+    structured_output: bool,
 ) -> Result<Arc<dyn LLMProvider>> {
     // Decide which API style to use
     // Prefer using api_style field, otherwise infer from provider name (backward compatibility)
@@ -118,6 +227,21 @@ fn create_provider_from_config(
         })?,
     };
 
+    // Apply [llm.providers.<name>.network] overrides on top of [network]
+    let network_config = &network_config.with_overrides(provider_config.network.as_ref());
+
+    if let Some(tiers) = &provider_config.tiers {
+        let provider = tiered::TieredProvider::from_config(
+            provider_config,
+            tiers,
+            name,
+            network_config,
+            colored,
+            structured_output,
+        )?;
+        return Ok(Arc::new(provider));
+    }
+
     // Create corresponding Provider implementation according to API style (exhaustive matching)
     match api_style {
         ApiStyle::Claude => {
@@ -125,9 +249,20 @@ fn create_provider_from_config(
                 backends::ClaudeProvider::new(provider_config, name, network_config, colored)?;
             Ok(Arc::new(provider))
         }
-        ApiStyle::OpenAI => {
-            let provider =
-                backends::OpenAIProvider::new(provider_config, name, network_config, colored)?;
+        ApiStyle::OpenAI | ApiStyle::DeepSeek | ApiStyle::Moonshot => {
+            let default_base = match api_style {
+                ApiStyle::DeepSeek => utils::DEFAULT_DEEPSEEK_BASE,
+                ApiStyle::Moonshot => utils::DEFAULT_MOONSHOT_BASE,
+                _ => utils::DEFAULT_OPENAI_BASE,
+            };
+            let provider = backends::OpenAIProvider::with_default_base(
+                provider_config,
+                name,
+                network_config,
+                colored,
+                default_base,
+            )?
+            .with_structured_output(structured_output);
             Ok(Arc::new(provider))
         }
         ApiStyle::Ollama => {
@@ -137,8 +272,146 @@ fn create_provider_from_config(
         }
         ApiStyle::Gemini => {
             let provider =
-                backends::GeminiProvider::new(provider_config, name, network_config, colored)?;
+                backends::GeminiProvider::new(provider_config, name, network_config, colored)?
+                    .with_structured_output(structured_output);
+            Ok(Arc::new(provider))
+        }
+        ApiStyle::Exec => {
+            let provider = backends::ExecProvider::new(provider_config, name, network_config)?;
             Ok(Arc::new(provider))
         }
+        #[cfg(feature = "local")]
+        ApiStyle::Local => {
+            let provider = backends::LocalProvider::new(provider_config, name)?;
+            Ok(Arc::new(provider))
+        }
+        #[cfg(not(feature = "local"))]
+        ApiStyle::Local => Err(GcopError::Config(
+            rust_i18n::t!("provider.local_feature_disabled", provider = name).to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_client_key_proxy_mode() {
+        let mut network = NetworkConfig::default();
+        assert_eq!(HttpClientKey::new(&network, None).proxy, ProxyMode::System);
+
+        network.proxy = Some("http://proxy.corp:3128".to_string());
+        network.no_proxy = Some("localhost".to_string());
+        assert_eq!(
+            HttpClientKey::new(&network, None).proxy,
+            ProxyMode::Url {
+                url: "http://proxy.corp:3128".to_string(),
+                no_proxy: Some("localhost".to_string()),
+            }
+        );
+        assert_eq!(
+            HttpClientKey::new(&network, Some("socks5h://127.0.0.1:1080")).proxy,
+            ProxyMode::Url {
+                url: "socks5h://127.0.0.1:1080".to_string(),
+                no_proxy: Some("localhost".to_string()),
+            }
+        );
+        assert_eq!(
+            HttpClientKey::new(&network, Some("")).proxy,
+            ProxyMode::Direct
+        );
+    }
+
+    #[test]
+    fn test_http_client_pool_keys_by_settings() {
+        test_utils::ensure_crypto_provider();
+        let network = NetworkConfig {
+            proxy: Some("socks5://127.0.0.1:1080".to_string()),
+            ..Default::default()
+        };
+        assert!(create_http_client(&network).is_ok());
+        assert!(create_http_client_with_proxy(&network, Some("")).is_ok());
+
+        let pool = HTTP_CLIENTS.get().unwrap().lock().unwrap();
+        assert!(pool.contains_key(&HttpClientKey::new(&network, None)));
+        assert!(pool.contains_key(&HttpClientKey::new(&network, Some(""))));
+    }
+
+    #[test]
+    fn test_http_client_invalid_proxy_is_error() {
+        test_utils::ensure_crypto_provider();
+        let network = NetworkConfig {
+            proxy: Some("http://[invalid".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            create_http_client(&network),
+            Err(GcopError::Llm(_))
+        ));
+    }
+
+    #[test]
+    fn test_provider_network_overrides_select_client() {
+        test_utils::ensure_crypto_provider();
+        let mut provider_config = test_utils::test_provider_config(
+            "http://localhost:11434".to_string(),
+            None,
+            "llama3.2".to_string(),
+        );
+        provider_config.api_style = Some(ApiStyle::Ollama);
+        provider_config.network = Some(crate::config::ProviderNetworkConfig {
+            request_timeout: Some(611),
+            ..Default::default()
+        });
+        let network = NetworkConfig::default();
+        create_provider_from_config(&provider_config, "ollama", &network, false, false).unwrap();
+
+        let effective = network.with_overrides(provider_config.network.as_ref());
+        let pool = HTTP_CLIENTS.get().unwrap().lock().unwrap();
+        assert!(pool.contains_key(&HttpClientKey::new(&effective, None)));
+    }
+
+    #[test]
+    fn test_model_override_checked_against_allowlist() {
+        test_utils::ensure_crypto_provider();
+        let mut provider_config = test_utils::test_provider_config(
+            "http://localhost:11434".to_string(),
+            None,
+            "llama3.2".to_string(),
+        );
+        provider_config.api_style = Some(ApiStyle::Ollama);
+        provider_config.allowed_models = Some(vec!["qwen2.5-coder".to_string()]);
+        let mut config = AppConfig::default();
+        config.llm.default_provider = "ollama".to_string();
+        config
+            .llm
+            .providers
+            .insert("ollama".to_string(), provider_config);
+        let command = CommandLlmConfig::default();
+
+        assert!(create_provider(&config, &command, None, None).is_ok());
+        assert!(create_provider(&config, &command, None, Some("qwen2.5-coder")).is_ok());
+        let err = create_provider(&config, &command, None, Some("llama3.3"))
+            .err()
+            .unwrap();
+        assert!(matches!(err, GcopError::Config(_)));
+        assert!(err.to_string().contains("llama3.3"));
+    }
+
+    #[cfg(not(feature = "local"))]
+    #[test]
+    fn test_local_style_requires_feature() {
+        let mut provider_config =
+            test_utils::test_provider_config(String::new(), None, "/models/coder.gguf".to_string());
+        provider_config.api_style = Some(ApiStyle::Local);
+        let result = create_provider_from_config(
+            &provider_config,
+            "local",
+            &NetworkConfig::default(),
+            false,
+            false,
+        );
+        assert!(matches!(result, Err(GcopError::Config(_))));
     }
 }
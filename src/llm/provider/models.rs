@@ -0,0 +1,148 @@
+//! Model listing for the setup wizard (`gcop-rs init`).
+
+use serde_json::Value;
+
+use super::base::{build_endpoint, extract_api_key};
+use super::create_http_client;
+use super::utils::{
+    DEFAULT_CLAUDE_BASE, DEFAULT_DEEPSEEK_BASE, DEFAULT_GEMINI_BASE, DEFAULT_MOONSHOT_BASE,
+    DEFAULT_OLLAMA_BASE, DEFAULT_OPENAI_BASE, OLLAMA_API_SUFFIX,
+};
+use crate::config::{ApiStyle, NetworkConfig, ProviderConfig};
+use crate::error::{GcopError, Result};
+
+/// Lists the models the provider's API offers, sorted by name.
+///
+/// Local GGUF models and external commands have no API and return an empty list.
+pub async fn fetch_models(
+    name: &str,
+    style: ApiStyle,
+    config: &ProviderConfig,
+    network: &NetworkConfig,
+) -> Result<Vec<String>> {
+    let client = create_http_client(network)?;
+    let request = match style {
+        ApiStyle::Claude => client
+            .get(build_endpoint(config, DEFAULT_CLAUDE_BASE, "/v1/models"))
+            .header("x-api-key", extract_api_key(config, name)?)
+            .header("anthropic-version", "2023-06-01"),
+        ApiStyle::OpenAI | ApiStyle::DeepSeek | ApiStyle::Moonshot => {
+            let default_base = match style {
+                ApiStyle::DeepSeek => DEFAULT_DEEPSEEK_BASE,
+                ApiStyle::Moonshot => DEFAULT_MOONSHOT_BASE,
+                _ => DEFAULT_OPENAI_BASE,
+            };
+            client
+                .get(build_endpoint(config, default_base, "/v1/models"))
+                .bearer_auth(extract_api_key(config, name)?)
+        }
+        ApiStyle::Gemini => client
+            .get(build_endpoint(
+                config,
+                DEFAULT_GEMINI_BASE,
+                "/v1beta/models",
+            ))
+            .header("x-goog-api-key", extract_api_key(config, name)?),
+        ApiStyle::Ollama => client.get(
+            build_endpoint(config, DEFAULT_OLLAMA_BASE, OLLAMA_API_SUFFIX)
+                .replace(OLLAMA_API_SUFFIX, "/api/tags"),
+        ),
+        ApiStyle::Local | ApiStyle::Exec => return Ok(Vec::new()),
+    };
+
+    let response = request.send().await.map_err(GcopError::Network)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(GcopError::LlmApi {
+            status: status.as_u16(),
+            message: response.text().await.unwrap_or_default(),
+        });
+    }
+    let body: Value = response.json().await.map_err(GcopError::Network)?;
+    Ok(parse_models(&body))
+}
+
+/// Model names from a model-list response
+///
+/// OpenAI-compatible and Claude APIs return `data[].id`; Gemini and Ollama
+/// return `models[].name` (Gemini prefixed with `models/`). Gemini models
+/// that cannot generate content (embeddings) are skipped.
+fn parse_models(body: &Value) -> Vec<String> {
+    let entries = body
+        .get("data")
+        .or_else(|| body.get("models"))
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut models: Vec<String> = entries
+        .iter()
+        .filter(|entry| {
+            entry
+                .get("supportedGenerationMethods")
+                .and_then(Value::as_array)
+                .is_none_or(|methods| methods.iter().any(|m| m == "generateContent"))
+        })
+        .filter_map(|entry| {
+            let name = entry.get("id").or_else(|| entry.get("name"))?.as_str()?;
+            Some(name.strip_prefix("models/").unwrap_or(name).to_string())
+        })
+        .collect();
+    models.sort();
+    models.dedup();
+    models
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Server;
+
+    use crate::llm::provider::test_utils::{
+        ensure_crypto_provider, test_network_config_no_retry, test_provider_config,
+    };
+
+    #[test]
+    fn test_parse_models_shapes() {
+        let openai = serde_json::json!({"data": [{"id": "gpt-4o"}, {"id": "gpt-4o-mini"}]});
+        assert_eq!(parse_models(&openai), vec!["gpt-4o", "gpt-4o-mini"]);
+
+        let gemini = serde_json::json!({"models": [
+            {"name": "models/gemini-2.5-pro", "supportedGenerationMethods": ["generateContent"]},
+            {"name": "models/text-embedding-004", "supportedGenerationMethods": ["embedContent"]},
+        ]});
+        assert_eq!(parse_models(&gemini), vec!["gemini-2.5-pro"]);
+
+        let ollama = serde_json::json!({"models": [{"name": "qwen2.5-coder:7b"}, {"name": "llama3.2:latest"}]});
+        assert_eq!(
+            parse_models(&ollama),
+            vec!["llama3.2:latest", "qwen2.5-coder:7b"]
+        );
+        assert!(parse_models(&serde_json::json!({})).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_models_sends_key() {
+        ensure_crypto_provider();
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", "/v1/models")
+            .match_header("authorization", "Bearer sk-test")
+            .with_status(200)
+            .with_body(r#"{"data":[{"id":"deepseek-reasoner"},{"id":"deepseek-chat"}]}"#)
+            .create_async()
+            .await;
+
+        let config = test_provider_config(server.url(), Some("sk-test".to_string()), String::new());
+        let models = fetch_models(
+            "deepseek",
+            ApiStyle::DeepSeek,
+            &config,
+            &test_network_config_no_retry(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(models, vec!["deepseek-chat", "deepseek-reasoner"]);
+        mock.assert_async().await;
+    }
+}
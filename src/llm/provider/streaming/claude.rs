@@ -5,20 +5,60 @@ use tokio::sync::mpsc;
 
 use crate::error::{GcopError, Result};
 use crate::llm::StreamChunk;
+use crate::llm::metrics::{self, TokenUsage};
 use crate::ui::colors;
 
+/// Claude token usage (non-streaming responses, `message_start` and `message_delta` events)
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct ClaudeUsage {
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+}
+
+impl ClaudeUsage {
+    /// Prompt tokens include the cached system prompt, which Claude reports separately.
+    pub(crate) fn to_usage(&self) -> Option<TokenUsage> {
+        let cached = self.cache_creation_input_tokens + self.cache_read_input_tokens;
+        TokenUsage::from_counts(
+            self.input_tokens.map(|tokens| tokens + cached),
+            self.output_tokens,
+        )
+    }
+}
+
 /// Claude SSE event type
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type")]
 enum ClaudeSSEEvent {
+    #[serde(rename = "message_start")]
+    MessageStart {
+        #[serde(default)]
+        message: ClaudeStreamMessage,
+    },
     #[serde(rename = "content_block_delta")]
     ContentBlockDelta { delta: ClaudeTextDelta },
+    #[serde(rename = "message_delta")]
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<ClaudeUsage>,
+    },
     #[serde(rename = "message_stop")]
     MessageStop,
     #[serde(other)]
     Other,
 }
 
+/// `message` object of the `message_start` event
+#[derive(Debug, Default, Deserialize)]
+struct ClaudeStreamMessage {
+    #[serde(default)]
+    usage: Option<ClaudeUsage>,
+}
+
 /// Claude text increment
 #[derive(Debug, Deserialize)]
 struct ClaudeTextDelta {
@@ -49,8 +89,13 @@ pub async fn process_claude_stream(
     let mut stream = response.bytes_stream();
     let mut buffer = String::new();
     let mut parse_errors = 0usize;
+    let mut usage: Option<TokenUsage> = None;
 
     while let Some(chunk_result) = stream.next().await {
+        // The receiver is gone (e.g. this provider lost a race): stop reading.
+        if tx.is_closed() {
+            return Ok(());
+        }
         let chunk = chunk_result.map_err(GcopError::Network)?;
         buffer.push_str(&String::from_utf8_lossy(&chunk));
 
@@ -63,6 +108,15 @@ pub async fn process_claude_stream(
             for line in event_block.lines() {
                 if let Some(data) = line.strip_prefix("data: ") {
                     match serde_json::from_str::<ClaudeSSEEvent>(data) {
+                        Ok(ClaudeSSEEvent::MessageStart { message }) => {
+                            usage = message.usage.as_ref().and_then(ClaudeUsage::to_usage);
+                        }
+                        Ok(ClaudeSSEEvent::MessageDelta { usage: delta_usage }) => {
+                            // `output_tokens` in message_delta is cumulative
+                            if let Some(output) = delta_usage.and_then(|u| u.output_tokens) {
+                                usage.get_or_insert_default().completion_tokens = output;
+                            }
+                        }
                         Ok(ClaudeSSEEvent::ContentBlockDelta { delta }) => {
                             if delta.delta_type == "text_delta" && !delta.text.is_empty() {
                                 let _ = tx.send(StreamChunk::Delta(delta.text)).await;
@@ -78,6 +132,7 @@ pub async fn process_claude_stream(
                                     colored,
                                 );
                             }
+                            metrics::record_call(usage);
                             let _ = tx.send(StreamChunk::Done).await;
                             return Ok(());
                         }
@@ -6,12 +6,31 @@ use tokio::sync::mpsc;
 use super::parse_sse_line;
 use crate::error::{GcopError, Result};
 use crate::llm::StreamChunk;
+use crate::llm::metrics::{self, TokenUsage};
 use crate::ui::colors;
 
+/// Gemini `usageMetadata` (non-streaming responses and stream chunks)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GeminiUsageMetadata {
+    prompt_token_count: Option<u64>,
+    candidates_token_count: Option<u64>,
+}
+
+impl GeminiUsageMetadata {
+    pub(crate) fn to_usage(&self) -> Option<TokenUsage> {
+        TokenUsage::from_counts(self.prompt_token_count, self.candidates_token_count)
+    }
+}
+
 /// Gemini streaming response block
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct GeminiStreamChunk {
     pub candidates: Option<Vec<GeminiStreamCandidate>>,
+    /// Running totals; the last chunk carries the final counts.
+    #[serde(default)]
+    pub usage_metadata: Option<GeminiUsageMetadata>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -47,8 +66,13 @@ pub async fn process_gemini_stream(
     let mut stream = response.bytes_stream();
     let mut buffer = String::new();
     let mut parse_errors = 0usize;
+    let mut usage: Option<TokenUsage> = None;
 
     while let Some(chunk_result) = stream.next().await {
+        // The receiver is gone (e.g. this provider lost a race): stop reading.
+        if tx.is_closed() {
+            return Ok(());
+        }
         let chunk = chunk_result.map_err(GcopError::Network)?;
         buffer.push_str(&String::from_utf8_lossy(&chunk));
 
@@ -64,6 +88,11 @@ pub async fn process_gemini_stream(
             if let Some(data) = parse_sse_line(&line) {
                 match serde_json::from_str::<GeminiStreamChunk>(data) {
                     Ok(chunk) => {
+                        if let Some(reported) =
+                            chunk.usage_metadata.as_ref().and_then(|u| u.to_usage())
+                        {
+                            usage = Some(reported);
+                        }
                         if let Some(candidates) = &chunk.candidates
                             && let Some(candidate) = candidates.first()
                         {
@@ -113,6 +142,7 @@ pub async fn process_gemini_stream(
                                         colored,
                                     );
                                 }
+                                metrics::record_call(usage);
                                 let _ = tx.send(StreamChunk::Done).await;
                                 return Ok(());
                             }
@@ -134,6 +164,7 @@ pub async fn process_gemini_stream(
             colored,
         );
     }
+    metrics::record_call(usage);
     let _ = tx.send(StreamChunk::Done).await;
     Ok(())
 }
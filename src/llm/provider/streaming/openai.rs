@@ -5,12 +5,31 @@ use tokio::sync::mpsc;
 use super::parse_sse_line;
 use crate::error::{GcopError, Result};
 use crate::llm::StreamChunk;
+use crate::llm::metrics::{self, TokenUsage};
 use crate::ui::colors;
 
 /// delta structure of OpenAI streaming response
 #[derive(Debug, serde::Deserialize)]
 struct OpenAIDelta {
     pub choices: Vec<OpenAIDeltaChoice>,
+    /// Only sent by servers that report usage on streamed responses.
+    #[serde(default)]
+    pub usage: Option<OpenAIUsage>,
+}
+
+/// `usage` object of OpenAI chat completion responses.
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct OpenAIUsage {
+    #[serde(default)]
+    pub prompt_tokens: Option<u64>,
+    #[serde(default)]
+    pub completion_tokens: Option<u64>,
+}
+
+impl OpenAIUsage {
+    pub(crate) fn to_usage(&self) -> Option<TokenUsage> {
+        TokenUsage::from_counts(self.prompt_tokens, self.completion_tokens)
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -22,6 +41,9 @@ struct OpenAIDeltaChoice {
 #[derive(Debug, serde::Deserialize)]
 struct OpenAIDeltaContent {
     pub content: Option<String>,
+    /// Reasoning tokens streamed by DeepSeek/Moonshot reasoning models (not forwarded).
+    #[serde(default)]
+    pub reasoning_content: Option<String>,
 }
 
 /// Handling OpenAI streaming responses
@@ -42,8 +64,13 @@ pub async fn process_openai_stream(
     let mut stream = response.bytes_stream();
     let mut buffer = String::new();
     let mut parse_errors = 0usize;
+    let mut usage = None;
 
     while let Some(chunk_result) = stream.next().await {
+        // The receiver is gone (e.g. this provider lost a race): stop reading.
+        if tx.is_closed() {
+            return Ok(());
+        }
         let chunk = chunk_result.map_err(GcopError::Network)?;
         buffer.push_str(&String::from_utf8_lossy(&chunk));
 
@@ -67,6 +94,7 @@ pub async fn process_openai_stream(
                             colored,
                         );
                     }
+                    metrics::record_call(usage);
                     let _ = tx.send(StreamChunk::Done).await;
                     return Ok(());
                 }
@@ -74,7 +102,17 @@ pub async fn process_openai_stream(
                 // Parse JSON
                 match serde_json::from_str::<OpenAIDelta>(data) {
                     Ok(delta) => {
+                        if let Some(reported) = delta.usage.as_ref().and_then(OpenAIUsage::to_usage)
+                        {
+                            usage = Some(reported);
+                        }
                         if let Some(choice) = delta.choices.first() {
+                            if let Some(reasoning) = &choice.delta.reasoning_content {
+                                tracing::trace!(
+                                    "Skipping {} bytes of streamed reasoning_content",
+                                    reasoning.len()
+                                );
+                            }
                             if let Some(content) = &choice.delta.content
                                 && !content.is_empty()
                             {
@@ -90,6 +128,7 @@ pub async fn process_openai_stream(
                                         colored,
                                     );
                                 }
+                                metrics::record_call(usage);
                                 let _ = tx.send(StreamChunk::Done).await;
                                 return Ok(());
                             }
@@ -113,6 +152,7 @@ pub async fn process_openai_stream(
                 .to_string(),
         });
     }
+    metrics::record_call(usage);
     let _ = tx.send(StreamChunk::Done).await;
     Ok(())
 }
@@ -206,6 +246,25 @@ mod tests {
         assert_done(&chunks[1]);
     }
 
+    #[tokio::test]
+    async fn test_openai_reasoning_content_not_forwarded() {
+        // DeepSeek/Moonshot reasoning models stream chain-of-thought in `reasoning_content`
+        let body = concat!(
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":null,\"reasoning_content\":\"Thinking\"},\"finish_reason\":null}]}\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":null,\"reasoning_content\":\" more\"},\"finish_reason\":null}]}\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"fix: typo\",\"reasoning_content\":null},\"finish_reason\":null}]}\n",
+            "data: [DONE]\n",
+        );
+        let (tx, rx) = mpsc::channel(16);
+        let result = process_openai_stream(sse_response(body), tx, false).await;
+
+        assert!(result.is_ok());
+        let chunks = drain(rx).await;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(delta_text(&chunks[0]), "fix: typo");
+        assert_done(&chunks[1]);
+    }
+
     /// All lines fail to parse AND no [DONE] → LlmStreamTruncated.
     #[tokio::test]
     async fn test_openai_truncated_all_parse_errors() {
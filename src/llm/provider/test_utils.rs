@@ -1,10 +1,17 @@
 //! Test utilities for provider tests
 //!
-//! Provides common test configuration builders to reduce duplication
-//! across provider test suites.
+//! Provides common test configuration builders and a scripted provider to
+//! reduce duplication across provider and command test suites.
 
 use crate::config::{NetworkConfig, ProviderConfig};
-use std::collections::HashMap;
+use crate::error::{GcopError, Result};
+use crate::llm::{
+    LLMProvider, ProgressReporter, ReviewResult, ReviewType, StreamChunk, StreamHandle,
+};
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use tokio::sync::mpsc;
 
 /// Install rustls crypto provider in tests
 ///
@@ -70,10 +77,127 @@ pub fn test_provider_config(
         model,
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     }
 }
 
+/// Provider replying with scripted messages and recording the user prompts
+///
+/// Replies are returned in order and the last one repeats for any further
+/// prompt. With [`with_streaming`](Self::with_streaming), replies are
+/// streamed word by word.
+pub struct ScriptedProvider {
+    replies: Mutex<VecDeque<String>>,
+    failure: Option<String>,
+    review: Option<ReviewResult>,
+    streaming: bool,
+    prompts: Mutex<Vec<String>>,
+}
+
+impl ScriptedProvider {
+    /// Provider answering prompts with `replies`
+    pub fn new(replies: &[&str]) -> Self {
+        Self {
+            replies: Mutex::new(replies.iter().map(|r| r.to_string()).collect()),
+            failure: None,
+            review: None,
+            streaming: false,
+            prompts: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Provider failing every prompt with `GcopError::Llm(error)`
+    pub fn failing(error: &str) -> Self {
+        Self {
+            failure: Some(error.to_string()),
+            ..Self::new(&[])
+        }
+    }
+
+    /// Returns `review` from `review_code`, which panics otherwise
+    pub fn with_review(mut self, review: ReviewResult) -> Self {
+        self.review = Some(review);
+        self
+    }
+
+    /// Sets `supports_streaming` and streams replies word by word
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// User prompts received so far
+    pub fn prompts(&self) -> Vec<String> {
+        self.prompts.lock().unwrap().clone()
+    }
+
+    fn reply(&self, user: &str) -> Result<String> {
+        self.prompts.lock().unwrap().push(user.to_string());
+        if let Some(error) = &self.failure {
+            return Err(GcopError::Llm(error.clone()));
+        }
+        let mut replies = self.replies.lock().unwrap();
+        let reply = if replies.len() > 1 {
+            replies.pop_front()
+        } else {
+            replies.front().cloned()
+        };
+        Ok(reply.expect("no scripted reply"))
+    }
+}
+
+#[async_trait]
+impl LLMProvider for ScriptedProvider {
+    async fn send_prompt(
+        &self,
+        _system: &str,
+        user: &str,
+        _progress: Option<&dyn ProgressReporter>,
+    ) -> Result<String> {
+        self.reply(user)
+    }
+
+    async fn send_prompt_streaming(&self, _system: &str, user: &str) -> Result<StreamHandle> {
+        let reply = self.reply(user)?;
+        let words: Vec<&str> = reply.split_inclusive(' ').collect();
+        let (tx, rx) = mpsc::channel(words.len() + 1);
+        for word in words {
+            let _ = tx.send(StreamChunk::Delta(word.to_string())).await;
+        }
+        let _ = tx.send(StreamChunk::Done).await;
+        Ok(StreamHandle { receiver: rx })
+    }
+
+    async fn review_code(
+        &self,
+        _diff: &str,
+        _review_type: ReviewType,
+        _custom_prompt: Option<&str>,
+        _progress: Option<&dyn ProgressReporter>,
+    ) -> Result<ReviewResult> {
+        Ok(self.review.clone().expect("no scripted review"))
+    }
+
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    async fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.streaming
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
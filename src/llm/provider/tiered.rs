@@ -0,0 +1,238 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::config::{ModelTiers, NetworkConfig, ProviderConfig};
+use crate::error::Result;
+use crate::llm::{
+    CommitContext, LLMProvider, ProgressReporter, ReviewResult, ReviewType, StreamHandle, metrics,
+};
+
+use super::create_provider_from_config;
+
+tokio::task_local! {
+    static DIFF_SIZE: usize;
+}
+
+/// Runs `future` with `size` as the diff size of its finished-prompt requests.
+///
+/// The tier then follows the diff inside the prompt rather than the whole
+/// prompt, which custom prompts and history examples can inflate.
+pub async fn diff_size_scope<F: Future>(size: usize, future: F) -> F::Output {
+    DIFF_SIZE.scope(size, future).await
+}
+
+/// Diff size of a finished-prompt request: the scoped one, else the prompt size
+fn prompt_diff_size(user_prompt: &str) -> usize {
+    DIFF_SIZE
+        .try_with(|size| *size)
+        .unwrap_or(user_prompt.len())
+}
+
+/// Tiered Provider - one provider configured with a small and a large model
+///
+/// Diffs larger than the threshold go to the large model, everything else to
+/// the small one. Requests that only carry a finished prompt (commit, split
+/// grouping) are measured by the diff size set with [`diff_size_scope`], and
+/// by the size of the user prompt when there is none (tags).
+pub struct TieredProvider {
+    name: String,
+    small: Arc<dyn LLMProvider>,
+    small_model: String,
+    large: Arc<dyn LLMProvider>,
+    large_model: String,
+    threshold: usize,
+}
+
+impl TieredProvider {
+    /// Creates both tiers from `[llm.providers.<name>]` and its `tiers` table.
+    pub(super) fn from_config(
+        provider_config: &ProviderConfig,
+        tiers: &ModelTiers,
+        name: &str,
+        network_config: &NetworkConfig,
+        colored: bool,
+        structured_output: bool,
+    ) -> Result<Self> {
+        let small_model = tiers
+            .small
+            .clone()
+            .unwrap_or_else(|| provider_config.model.clone());
+        let large_model = tiers.large.clone();
+
+        let create = |model: &str| {
+            let tier_config = ProviderConfig {
+                model: model.to_string(),
+                tiers: None,
+                ..provider_config.clone()
+            };
+            create_provider_from_config(
+                &tier_config,
+                name,
+                network_config,
+                colored,
+                structured_output,
+            )
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            small: create(&small_model)?,
+            small_model,
+            large: create(&large_model)?,
+            large_model,
+            threshold: tiers.threshold,
+        })
+    }
+
+    /// Tier provider and model for an input of `size` bytes
+    fn tier(&self, size: usize) -> (&dyn LLMProvider, &str) {
+        if size > self.threshold {
+            (self.large.as_ref(), &self.large_model)
+        } else {
+            (self.small.as_ref(), &self.small_model)
+        }
+    }
+
+    /// Picks the tier for an input of `size` bytes and records its model
+    fn pick(&self, size: usize) -> &dyn LLMProvider {
+        let (provider, model) = self.tier(size);
+        debug!(
+            "Provider '{}': input of {} bytes (threshold {}), using model '{}'",
+            self.name, size, self.threshold, model
+        );
+        metrics::record_model(&self.name, model);
+        provider
+    }
+}
+
+#[async_trait]
+impl LLMProvider for TieredProvider {
+    async fn send_prompt(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        progress: Option<&dyn ProgressReporter>,
+    ) -> Result<String> {
+        self.pick(prompt_diff_size(user_prompt))
+            .send_prompt(system_prompt, user_prompt, progress)
+            .await
+    }
+
+    async fn send_prompt_streaming(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+    ) -> Result<StreamHandle> {
+        self.pick(prompt_diff_size(user_prompt))
+            .send_prompt_streaming(system_prompt, user_prompt)
+            .await
+    }
+
+    async fn generate_commit_message(
+        &self,
+        diff: &str,
+        context: Option<CommitContext>,
+        progress: Option<&dyn ProgressReporter>,
+    ) -> Result<String> {
+        self.pick(diff.len())
+            .generate_commit_message(diff, context, progress)
+            .await
+    }
+
+    async fn review_code(
+        &self,
+        diff: &str,
+        review_type: ReviewType,
+        custom_prompt: Option<&str>,
+        progress: Option<&dyn ProgressReporter>,
+    ) -> Result<ReviewResult> {
+        self.pick(diff.len())
+            .review_code(diff, review_type, custom_prompt, progress)
+            .await
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn validate(&self) -> Result<()> {
+        self.small.validate().await?;
+        self.large.validate().await
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.small.supports_streaming()
+    }
+
+    async fn generate_commit_message_streaming(
+        &self,
+        diff: &str,
+        context: Option<CommitContext>,
+    ) -> Result<StreamHandle> {
+        self.pick(diff.len())
+            .generate_commit_message_streaming(diff, context)
+            .await
+    }
+
+    async fn review_code_streaming(
+        &self,
+        diff: &str,
+        review_type: ReviewType,
+        custom_prompt: Option<&str>,
+    ) -> Result<StreamHandle> {
+        self.pick(diff.len())
+            .review_code_streaming(diff, review_type, custom_prompt)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::provider::test_utils::test_provider_config;
+
+    fn tiered(threshold: usize) -> TieredProvider {
+        let mut config = test_provider_config(
+            "https://api.openai.com".to_string(),
+            Some("sk-test".to_string()),
+            "gpt-4o-mini".to_string(),
+        );
+        config.api_style = Some(crate::config::ApiStyle::OpenAI);
+        let tiers = ModelTiers {
+            small: None,
+            large: "gpt-4o".to_string(),
+            threshold,
+        };
+        TieredProvider::from_config(
+            &config,
+            &tiers,
+            "openai",
+            &NetworkConfig::default(),
+            false,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tier_by_size() {
+        let provider = tiered(10);
+        assert_eq!(provider.name(), "openai");
+        // 未配置 small 时使用 provider 的 model
+        assert_eq!(provider.tier(0).1, "gpt-4o-mini");
+        assert_eq!(provider.tier(10).1, "gpt-4o-mini");
+        assert_eq!(provider.tier(11).1, "gpt-4o");
+    }
+
+    #[tokio::test]
+    async fn test_prompt_requests_measure_scoped_diff() {
+        // 长 prompt（自定义指令、历史示例）不应把小 diff 推到大模型
+        let prompt = "x".repeat(100);
+        assert_eq!(prompt_diff_size(&prompt), 100);
+        let size = diff_size_scope(5, async { prompt_diff_size(&prompt) }).await;
+        assert_eq!(size, 5);
+    }
+}
@@ -23,6 +23,12 @@ pub const DEFAULT_OLLAMA_BASE: &str = "http://localhost:11434";
 /// Gemini default base URL
 pub const DEFAULT_GEMINI_BASE: &str = "https://generativelanguage.googleapis.com";
 
+/// DeepSeek default base URL (OpenAI-compatible)
+pub const DEFAULT_DEEPSEEK_BASE: &str = "https://api.deepseek.com";
+
+/// Moonshot (Kimi) default base URL (OpenAI-compatible)
+pub const DEFAULT_MOONSHOT_BASE: &str = "https://api.moonshot.cn";
+
 /// Smart completion API endpoint
 ///
 /// # Behavior
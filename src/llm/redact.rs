@@ -0,0 +1,222 @@
+//! Rule-based redaction of outgoing text (`[privacy]` rules and patterns).
+//!
+//! Matches are replaced with `[REDACTED:<label>]`. Diff header lines are
+//! left untouched so that file paths in the diff still match the repository.
+
+use regex::Regex;
+
+use crate::config::{PrivacyConfig, RedactionRule};
+use crate::error::{GcopError, Result};
+
+/// Top-level domains treated as internal by the `hostname` rule.
+const INTERNAL_TLDS: &str = "internal|corp|lan|intranet|local";
+
+/// Diff lines that carry paths or metadata rather than content.
+const HEADER_PREFIXES: &[&str] = &[
+    "diff --git ",
+    "--- ",
+    "+++ ",
+    "rename from ",
+    "rename to ",
+    "copy from ",
+    "copy to ",
+    "index ",
+];
+
+/// Loopback and unspecified addresses, left unredacted by the `ip` rule.
+const KEPT_ADDRESSES: &[&str] = &["127.0.0.1", "0.0.0.0"];
+
+/// Compiled `[privacy]` redaction rules.
+#[derive(Debug)]
+pub struct Redactor {
+    rules: Vec<(&'static str, Regex)>,
+}
+
+/// Number of replacements made by one rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redaction {
+    /// Rule label (`email`, `ip`, `hostname` or `pattern`).
+    pub label: &'static str,
+    /// Number of replaced matches.
+    pub count: usize,
+}
+
+impl Redactor {
+    /// Builds the redactor for `config` (rules first, then custom patterns).
+    pub fn from_config(config: &PrivacyConfig) -> Result<Self> {
+        let mut rules = Vec::new();
+        for rule in &config.rules {
+            let pattern = match rule {
+                RedactionRule::Email => {
+                    r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b".to_string()
+                }
+                RedactionRule::Ip => concat!(
+                    r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b",
+                    r"|\b(?:[0-9A-Fa-f]{1,4}:){3,7}[0-9A-Fa-f]{1,4}\b",
+                    r"|\b(?:[0-9A-Fa-f]{1,4}:){1,6}:[0-9A-Fa-f]{1,4}\b",
+                )
+                .to_string(),
+                RedactionRule::Hostname => {
+                    // Configured domains first, so `a.corp.example.com` is not cut at `.corp`
+                    let mut suffixes: Vec<String> = config
+                        .internal_domains
+                        .iter()
+                        .map(|d| regex::escape(d))
+                        .collect();
+                    suffixes.push(format!("(?:{})", INTERNAL_TLDS));
+                    format!(
+                        r"\b(?:[A-Za-z0-9](?:[A-Za-z0-9-]{{0,61}}[A-Za-z0-9])?\.)+(?:{})\b",
+                        suffixes.join("|")
+                    )
+                }
+            };
+            rules.push((rule.label(), compile(&pattern)?));
+        }
+        for pattern in &config.patterns {
+            rules.push(("pattern", compile(pattern)?));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether no rule is configured.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Redacts `text`, returning the result and the replacements per rule
+    /// (rules without matches are omitted).
+    pub fn apply(&self, text: &str) -> (String, Vec<Redaction>) {
+        let mut counts = vec![0; self.rules.len()];
+        let mut redacted = String::with_capacity(text.len());
+        for line in text.split_inclusive('\n') {
+            if HEADER_PREFIXES
+                .iter()
+                .any(|prefix| line.starts_with(prefix))
+            {
+                redacted.push_str(line);
+                continue;
+            }
+            let mut line = line.to_string();
+            for (index, (label, regex)) in self.rules.iter().enumerate() {
+                let replaced = regex.replace_all(&line, |captures: &regex::Captures| {
+                    let matched = &captures[0];
+                    // Hex-only matches are identifiers such as `ab::cd`, not addresses
+                    if *label == "ip"
+                        && (KEPT_ADDRESSES.contains(&matched)
+                            || !matched.bytes().any(|b| b.is_ascii_digit()))
+                    {
+                        return matched.to_string();
+                    }
+                    counts[index] += 1;
+                    format!("[REDACTED:{}]", label)
+                });
+                line = replaced.into_owned();
+            }
+            redacted.push_str(&line);
+        }
+
+        let mut redactions: Vec<Redaction> = Vec::new();
+        for ((label, _), count) in self.rules.iter().zip(counts) {
+            if count == 0 {
+                continue;
+            }
+            match redactions.iter_mut().find(|r| r.label == *label) {
+                Some(existing) => existing.count += count,
+                None => redactions.push(Redaction { label, count }),
+            }
+        }
+        (redacted, redactions)
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| {
+        GcopError::Config(format!(
+            "privacy: invalid redaction regex '{}': {}",
+            pattern, e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor(rules: Vec<RedactionRule>, patterns: &[&str]) -> Redactor {
+        Redactor::from_config(&PrivacyConfig {
+            rules,
+            internal_domains: vec!["corp.example.com".to_string()],
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_builtin_rules() {
+        let redactor = redactor(
+            vec![
+                RedactionRule::Email,
+                RedactionRule::Ip,
+                RedactionRule::Hostname,
+            ],
+            &[],
+        );
+        let (text, redactions) = redactor.apply(
+            "+mail = \"ops@example.com\"\n+host = \"10.1.2.3\"\n+v6 = \"fe80::1\"\n+local = \"127.0.0.1\"\n+path = db::connect(ab::cd)\n+db = \"pg.prod.internal\"\n+ci = \"build.corp.example.com\"\n+site = \"example.com\"\n",
+        );
+        assert_eq!(
+            text,
+            "+mail = \"[REDACTED:email]\"\n+host = \"[REDACTED:ip]\"\n+v6 = \"[REDACTED:ip]\"\n+local = \"127.0.0.1\"\n+path = db::connect(ab::cd)\n+db = \"[REDACTED:hostname]\"\n+ci = \"[REDACTED:hostname]\"\n+site = \"example.com\"\n"
+        );
+        assert_eq!(
+            redactions,
+            vec![
+                Redaction {
+                    label: "email",
+                    count: 1
+                },
+                Redaction {
+                    label: "ip",
+                    count: 2
+                },
+                Redaction {
+                    label: "hostname",
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_patterns_skip_diff_headers() {
+        let redactor = redactor(vec![], &[r"/home/[a-z]+", r"ACME-\d+"]);
+        let diff = "diff --git a/home/alice/x b/home/alice/x\n--- a/home/alice/x\n+++ b/home/alice/x\n+path = \"/home/alice/data\" # ACME-42\n";
+        let (text, redactions) = redactor.apply(diff);
+        assert!(text.starts_with(
+            "diff --git a/home/alice/x b/home/alice/x\n--- a/home/alice/x\n+++ b/home/alice/x\n"
+        ));
+        assert!(text.ends_with("+path = \"[REDACTED:pattern]/data\" # [REDACTED:pattern]\n"));
+        // 自定义规则合并计数
+        assert_eq!(
+            redactions,
+            vec![Redaction {
+                label: "pattern",
+                count: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_config_error() {
+        let config = PrivacyConfig {
+            patterns: vec!["(".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            Redactor::from_config(&config),
+            Err(GcopError::Config(_))
+        ));
+        assert!(config.validate().is_err());
+    }
+}
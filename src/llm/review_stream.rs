@@ -0,0 +1,331 @@
+//! Incremental parsing of streamed review responses.
+//!
+//! Review responses are JSON objects whose `summary` comes first (see the
+//! prompt's output format). [`ReviewStreamParser`] scans chunks as they
+//! arrive, emitting the summary text character by character and each issue
+//! once its object is complete, so the terminal can show progress long
+//! before the full response is available. The final result is still parsed
+//! from the complete text by
+//! [`process_review_response`](crate::llm::provider::base::process_review_response).
+
+use super::ReviewIssue;
+
+/// Events produced while scanning a streamed review response.
+#[derive(Debug, Clone)]
+pub enum ReviewStreamEvent {
+    /// Decoded text appended to the `summary` value.
+    SummaryDelta(String),
+    /// The `summary` string value is complete.
+    SummaryDone,
+    /// One complete entry of the `issues` array.
+    Issue(ReviewIssue),
+}
+
+/// Where the scanner is relative to the interesting values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    /// Before the opening `{` (thinking tags, code fences, prose).
+    Preamble,
+    /// Scanning structure between values.
+    Body,
+    /// Inside the `summary` string value.
+    Summary,
+    /// Inside the `issues` array.
+    Issues,
+    /// Root object closed; further input is ignored.
+    Finished,
+}
+
+/// Stateful, string-aware scanner over a streamed review JSON response.
+#[derive(Debug)]
+pub struct ReviewStreamParser {
+    section: Section,
+    preamble: String,
+    depth: usize,
+    in_string: bool,
+    escape: bool,
+    unicode: Option<String>,
+    string_buf: String,
+    pending_key: Option<String>,
+    current_key: Option<String>,
+    issue_buf: String,
+}
+
+impl Default for ReviewStreamParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReviewStreamParser {
+    /// Creates a parser positioned before the response.
+    pub fn new() -> Self {
+        Self {
+            section: Section::Preamble,
+            preamble: String::new(),
+            depth: 0,
+            in_string: false,
+            escape: false,
+            unicode: None,
+            string_buf: String::new(),
+            pending_key: None,
+            current_key: None,
+            issue_buf: String::new(),
+        }
+    }
+
+    /// Feeds the next chunk and returns the events it completes.
+    pub fn feed(&mut self, chunk: &str) -> Vec<ReviewStreamEvent> {
+        let mut events = Vec::new();
+        let mut summary = String::new();
+
+        for c in chunk.chars() {
+            match self.section {
+                Section::Preamble => self.scan_preamble(c),
+                Section::Body => self.scan_body(c),
+                Section::Summary => {
+                    if let Some(text) = self.scan_summary(c) {
+                        summary.push_str(&text);
+                    } else if self.section == Section::Body {
+                        if !summary.is_empty() {
+                            events.push(ReviewStreamEvent::SummaryDelta(std::mem::take(
+                                &mut summary,
+                            )));
+                        }
+                        events.push(ReviewStreamEvent::SummaryDone);
+                    }
+                }
+                Section::Issues => {
+                    if let Some(issue) = self.scan_issues(c) {
+                        events.push(ReviewStreamEvent::Issue(issue));
+                    }
+                }
+                Section::Finished => break,
+            }
+        }
+
+        if !summary.is_empty() {
+            events.push(ReviewStreamEvent::SummaryDelta(summary));
+        }
+        events
+    }
+
+    /// Waits for the root `{`, skipping `<think>` blocks that may contain braces.
+    fn scan_preamble(&mut self, c: char) {
+        self.preamble.push(c);
+        if c != '{' {
+            return;
+        }
+        let in_thinking = self
+            .preamble
+            .rfind("<think>")
+            .is_some_and(|open| !self.preamble[open..].contains("</think>"));
+        if !in_thinking {
+            self.preamble.clear();
+            self.section = Section::Body;
+            self.depth = 1;
+        }
+    }
+
+    fn scan_body(&mut self, c: char) {
+        if self.in_string {
+            if self.escape {
+                self.escape = false;
+            } else if c == '\\' {
+                self.escape = true;
+            } else if c == '"' {
+                self.in_string = false;
+                if self.depth == 1 {
+                    self.pending_key = Some(std::mem::take(&mut self.string_buf));
+                }
+            } else if self.depth == 1 {
+                self.string_buf.push(c);
+            }
+            return;
+        }
+
+        match c {
+            '"' if self.depth == 1 && self.current_key.as_deref() == Some("summary") => {
+                self.current_key = None;
+                self.section = Section::Summary;
+            }
+            '"' => {
+                self.in_string = true;
+                self.string_buf.clear();
+            }
+            ':' if self.depth == 1 => self.current_key = self.pending_key.take(),
+            ',' if self.depth == 1 => self.current_key = None,
+            '[' if self.depth == 1 && self.current_key.as_deref() == Some("issues") => {
+                self.current_key = None;
+                self.depth += 1;
+                self.section = Section::Issues;
+            }
+            '{' | '[' => self.depth += 1,
+            '}' | ']' => {
+                self.depth = self.depth.saturating_sub(1);
+                if self.depth == 0 {
+                    self.section = Section::Finished;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Decodes one character of the summary string; `None` when nothing is emitted.
+    fn scan_summary(&mut self, c: char) -> Option<String> {
+        if let Some(hex) = self.unicode.as_mut() {
+            hex.push(c);
+            if hex.len() < 4 {
+                return None;
+            }
+            let decoded = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32);
+            self.unicode = None;
+            return Some(decoded.unwrap_or('\u{fffd}').to_string());
+        }
+        if self.escape {
+            self.escape = false;
+            let decoded = match c {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                'b' | 'f' => return None,
+                'u' => {
+                    self.unicode = Some(String::new());
+                    return None;
+                }
+                other => other,
+            };
+            return Some(decoded.to_string());
+        }
+        match c {
+            '\\' => {
+                self.escape = true;
+                None
+            }
+            '"' => {
+                self.section = Section::Body;
+                None
+            }
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Collects issue objects inside the `issues` array (depth 2).
+    fn scan_issues(&mut self, c: char) -> Option<ReviewIssue> {
+        if self.depth >= 3 {
+            self.issue_buf.push(c);
+        }
+
+        if self.in_string {
+            if self.escape {
+                self.escape = false;
+            } else if c == '\\' {
+                self.escape = true;
+            } else if c == '"' {
+                self.in_string = false;
+            }
+            return None;
+        }
+
+        match c {
+            '"' => self.in_string = true,
+            '{' | '[' => {
+                self.depth += 1;
+                if self.depth == 3 {
+                    self.issue_buf.clear();
+                    self.issue_buf.push(c);
+                }
+            }
+            '}' | ']' => {
+                self.depth -= 1;
+                if self.depth == 2 && c == '}' {
+                    let object = std::mem::take(&mut self.issue_buf);
+                    return serde_json::from_str(&object).ok();
+                }
+                if self.depth == 1 {
+                    self.section = Section::Body;
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::IssueSeverity;
+
+    const RESPONSE: &str = r#"{
+  "summary": "Looks \"mostly\" fine.\nOne café bug.",
+  "issues": [
+    {"severity": "warning", "description": "Unchecked {brace} in string", "file": "src/a.rs", "line": 3},
+    {"severity": "info", "description": "Nit", "file": null, "line": null, "suggested_patch": null}
+  ],
+  "suggestions": ["Add tests"]
+}"#;
+
+    fn collect(chunks: &[&str]) -> (String, bool, Vec<ReviewIssue>) {
+        let mut parser = ReviewStreamParser::new();
+        let mut summary = String::new();
+        let mut done = false;
+        let mut issues = Vec::new();
+        for chunk in chunks {
+            for event in parser.feed(chunk) {
+                match event {
+                    ReviewStreamEvent::SummaryDelta(text) => summary.push_str(&text),
+                    ReviewStreamEvent::SummaryDone => done = true,
+                    ReviewStreamEvent::Issue(issue) => issues.push(issue),
+                }
+            }
+        }
+        (summary, done, issues)
+    }
+
+    #[test]
+    fn test_parses_whole_response() {
+        let (summary, done, issues) = collect(&[RESPONSE]);
+        assert_eq!(summary, "Looks \"mostly\" fine.\nOne café bug.");
+        assert!(done);
+        assert_eq!(issues.len(), 2);
+        assert!(matches!(issues[0].severity, IssueSeverity::Warning));
+        assert_eq!(issues[0].description, "Unchecked {brace} in string");
+        assert_eq!(issues[0].line, Some(3));
+        assert_eq!(issues[1].file, None);
+    }
+
+    #[test]
+    fn test_char_by_char_matches_whole() {
+        let chars: Vec<String> = RESPONSE.chars().map(String::from).collect();
+        let chunks: Vec<&str> = chars.iter().map(String::as_str).collect();
+        let (summary, done, issues) = collect(&chunks);
+        let (expected_summary, _, expected_issues) = collect(&[RESPONSE]);
+        assert_eq!(summary, expected_summary);
+        assert!(done);
+        let descriptions = |issues: &[ReviewIssue]| {
+            issues
+                .iter()
+                .map(|i| i.description.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(descriptions(&issues), descriptions(&expected_issues));
+    }
+
+    #[test]
+    fn test_skips_thinking_and_fences() {
+        let response = format!("<think>maybe {{ x }}</think>\n```json\n{}\n```", RESPONSE);
+        let (summary, _, issues) = collect(&[&response]);
+        assert!(summary.starts_with("Looks"));
+        assert_eq!(issues.len(), 2);
+    }
+
+    #[test]
+    fn test_summary_after_issues_and_nested_keys() {
+        let response = r#"{"issues": [{"severity": "critical", "description": "x", "file": "f", "line": 1}], "meta": {"summary": "nested"}, "summary": "late"}"#;
+        let (summary, done, issues) = collect(&[response]);
+        assert_eq!(summary, "late");
+        assert!(done);
+        assert_eq!(issues.len(), 1);
+    }
+}
@@ -0,0 +1,248 @@
+//! Prompt templates rendered with [Tera](https://keats.github.io/tera/).
+//!
+//! The user message of every prompt is produced from a template. Built-in
+//! templates are embedded in the binary; a file named `<name>.tera` overrides
+//! one, looked up in this order:
+//!
+//! 1. `<config_dir>/templates/<name>.tera`
+//! 2. `<repo>/.gcop/templates/<name>.tera`
+//!
+//! Repository templates come from whoever wrote the repo, so Tera's `get_env`
+//! is disabled: a template must not be able to send environment secrets to
+//! the provider.
+//!
+//! System prompts are not templated: they stay controlled by `custom_prompt`
+//! and `[commit.convention]`.
+
+use std::path::PathBuf;
+
+use crate::error::{GcopError, Result};
+
+/// A templated prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PromptTemplate {
+    /// Commit message generation (`commit`, hook, API).
+    Commit,
+    /// Atomic split commit grouping (`commit --split`).
+    Split,
+    /// Code review.
+    Review,
+    /// Merge of the partial reviews of a chunked diff (`review`).
+    #[value(name = "review_merge")]
+    ReviewMerge,
+    /// Annotated tag messages (`tag`).
+    Tag,
+    /// Fixup target check (`commit --fixup` / `--squash`).
+    Fixup,
+}
+
+impl PromptTemplate {
+    /// Template name, also the override file stem.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Commit => "commit",
+            Self::Split => "split",
+            Self::Review => "review",
+            Self::ReviewMerge => "review_merge",
+            Self::Tag => "tag",
+            Self::Fixup => "fixup",
+        }
+    }
+
+    /// Embedded built-in template source.
+    pub fn builtin(self) -> &'static str {
+        match self {
+            Self::Commit => include_str!("templates/commit.tera"),
+            Self::Split => include_str!("templates/split.tera"),
+            Self::Review => include_str!("templates/review.tera"),
+            Self::ReviewMerge => include_str!("templates/review_merge.tera"),
+            Self::Tag => include_str!("templates/tag.tera"),
+            Self::Fixup => include_str!("templates/fixup.tera"),
+        }
+    }
+
+    fn file_name(self) -> String {
+        format!("{}.tera", self.name())
+    }
+}
+
+/// Where the effective template comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateOrigin {
+    /// Embedded built-in template.
+    Builtin,
+    /// User override file.
+    File(PathBuf),
+}
+
+/// Effective template source of a prompt.
+#[derive(Debug, Clone)]
+pub struct TemplateSource {
+    /// Which prompt the source belongs to.
+    pub template: PromptTemplate,
+    /// Built-in or override file.
+    pub origin: TemplateOrigin,
+    /// Tera source text.
+    pub source: String,
+}
+
+impl TemplateSource {
+    /// The embedded template.
+    pub fn builtin(template: PromptTemplate) -> Self {
+        Self {
+            template,
+            origin: TemplateOrigin::Builtin,
+            source: template.builtin().to_string(),
+        }
+    }
+
+    /// Renders the template with `context`.
+    pub fn render(&self, context: &tera::Context) -> Result<String> {
+        let name = self.template.file_name();
+        let mut tera = tera::Tera::default();
+        tera.register_function("get_env", disabled_get_env);
+        tera.add_raw_template(&name, &self.source)
+            .and_then(|_| tera.render(&name, context))
+            .map_err(|e| {
+                let location = match &self.origin {
+                    TemplateOrigin::Builtin => "built-in".to_string(),
+                    TemplateOrigin::File(path) => path.display().to_string(),
+                };
+                GcopError::Config(format!(
+                    "prompt template '{}' ({}): {}",
+                    self.template.name(),
+                    location,
+                    error_chain(&e)
+                ))
+            })
+    }
+}
+
+/// Replaces Tera's built-in `get_env`, which would expose the environment.
+fn disabled_get_env(
+    _args: &std::collections::HashMap<String, tera::Value>,
+) -> tera::Result<tera::Value> {
+    Err("get_env is not available in prompt templates".into())
+}
+
+/// Joins an error with its sources; Tera keeps the useful detail in the chain.
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Directories searched for override files, highest priority first.
+pub fn override_dirs() -> Vec<PathBuf> {
+    let user = crate::config::get_config_dir().map(|dir| dir.join("templates"));
+    let project = crate::git::find_git_root().map(|root| root.join(".gcop").join("templates"));
+    user.into_iter().chain(project).collect()
+}
+
+/// Loads the effective source of `template` from `dirs` (see [`override_dirs`]).
+pub fn load_from(template: PromptTemplate, dirs: &[PathBuf]) -> Result<TemplateSource> {
+    for dir in dirs {
+        let path = dir.join(template.file_name());
+        if path.is_file() {
+            let source = std::fs::read_to_string(&path)?;
+            return Ok(TemplateSource {
+                template,
+                origin: TemplateOrigin::File(path),
+                source,
+            });
+        }
+    }
+    Ok(TemplateSource::builtin(template))
+}
+
+/// Loads the effective source of `template`.
+pub fn load(template: PromptTemplate) -> Result<TemplateSource> {
+    load_from(template, &override_dirs())
+}
+
+/// Renders the effective template, falling back to the built-in one when an
+/// override cannot be read or rendered.
+pub(crate) fn render(template: PromptTemplate, context: &tera::Context) -> String {
+    let rendered = load(template).and_then(|source| source.render(context));
+    rendered.unwrap_or_else(|e| {
+        tracing::warn!("{}; using the built-in template", e);
+        TemplateSource::builtin(template)
+            .render(context)
+            .expect("built-in prompt templates render")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_from_prefers_first_dir() {
+        let user = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        std::fs::write(user.path().join("commit.tera"), "user").unwrap();
+        std::fs::write(project.path().join("commit.tera"), "project").unwrap();
+        std::fs::write(project.path().join("review.tera"), "project review").unwrap();
+        let dirs = [user.path().to_path_buf(), project.path().to_path_buf()];
+
+        let commit = load_from(PromptTemplate::Commit, &dirs).unwrap();
+        assert_eq!(commit.source, "user");
+        assert_eq!(
+            commit.origin,
+            TemplateOrigin::File(user.path().join("commit.tera"))
+        );
+
+        let review = load_from(PromptTemplate::Review, &dirs).unwrap();
+        assert_eq!(review.source, "project review");
+
+        // 没有覆盖文件时使用内置模板
+        let tag = load_from(PromptTemplate::Tag, &dirs).unwrap();
+        assert_eq!(tag.origin, TemplateOrigin::Builtin);
+        assert_eq!(tag.source, PromptTemplate::Tag.builtin());
+    }
+
+    #[test]
+    fn test_render_reports_errors() {
+        let mut context = tera::Context::new();
+        context.insert("diff", "+x");
+
+        let source = TemplateSource {
+            template: PromptTemplate::Review,
+            origin: TemplateOrigin::File(PathBuf::from("/tmp/review.tera")),
+            source: "Review:\n{{ diff }}\n\n".to_string(),
+        };
+        assert_eq!(source.render(&context).unwrap(), "Review:\n+x\n\n");
+
+        let broken = TemplateSource {
+            source: "{% if diff %}unterminated".to_string(),
+            ..source
+        };
+        let err = broken.render(&context).unwrap_err().to_string();
+        assert!(err.contains("prompt template 'review' (/tmp/review.tera)"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_render_rejects_get_env() {
+        // 仓库自带的模板不能把环境变量（如 API token）带进 prompt
+        // SAFETY: tests touching the environment run serially
+        unsafe { std::env::set_var("GCOP_TEST_TEMPLATE_SECRET", "sk-live-123") };
+        let source = TemplateSource {
+            template: PromptTemplate::Commit,
+            origin: TemplateOrigin::File(PathBuf::from(".gcop/templates/commit.tera")),
+            source: "leak={{ get_env(name=\"GCOP_TEST_TEMPLATE_SECRET\") }}".to_string(),
+        };
+        let err = source
+            .render(&tera::Context::new())
+            .unwrap_err()
+            .to_string();
+        unsafe { std::env::remove_var("GCOP_TEST_TEMPLATE_SECRET") };
+        assert!(err.contains("get_env is not available"), "{}", err);
+        assert!(!err.contains("sk-live-123"));
+    }
+}
@@ -0,0 +1,305 @@
+//! Tracing setup: terminal output plus the optional `[logging]` file.
+//!
+//! The terminal keeps its `--verbose` / `RUST_LOG` behavior. With
+//! `[logging].file` set, events are also appended to that file (plain text or
+//! JSON lines) at `[logging].level`, and the file is rotated to `<file>.1` ...
+//! `<file>.N` once it grows past `max_size` bytes.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::Local;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, MakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+
+use crate::config::{LogFormat, LoggingConfig, expand_home};
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Installs the global tracing subscriber.
+///
+/// `terminal_level` is the default terminal level (`RUST_LOG` adds to it);
+/// `stderr` sends terminal output to stderr instead of stdout (the `api`
+/// command keeps stdout for protocol events). A log file that cannot be
+/// opened is reported on stderr and skipped.
+pub fn init(config: &LoggingConfig, terminal_level: Level, stderr: bool) {
+    let terminal_filter = EnvFilter::from_default_env().add_directive(terminal_level.into());
+    let terminal = if stderr {
+        tracing_subscriber::fmt::layer()
+            .with_writer(io::stderr)
+            .with_filter(terminal_filter)
+            .boxed()
+    } else {
+        tracing_subscriber::fmt::layer()
+            .with_filter(terminal_filter)
+            .boxed()
+    };
+
+    let mut layers = vec![terminal];
+    if let Some(path) = config.file.as_deref() {
+        let path = expand_home(path);
+        match file_layer(&path, config) {
+            Ok(layer) => layers.push(layer),
+            Err(e) => eprintln!(
+                "{}",
+                rust_i18n::t!(
+                    "logging.open_failed",
+                    path = path.display(),
+                    error = e.to_string()
+                )
+            ),
+        }
+    }
+
+    tracing_subscriber::registry().with(layers).init();
+}
+
+fn file_layer(path: &Path, config: &LoggingConfig) -> io::Result<BoxedLayer> {
+    let writer = RotatingFile::open(path, config.max_size, config.max_files)?;
+    // gcop-rs events at the configured level; dependencies only when they warn
+    let filter = EnvFilter::new(format!("warn,gcop_rs={}", config.level));
+    Ok(match config.format {
+        LogFormat::Text => tracing_subscriber::fmt::layer()
+            .with_ansi(false)
+            .with_writer(writer)
+            .with_filter(filter)
+            .boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .event_format(JsonFormat)
+            .with_writer(writer)
+            .with_filter(filter)
+            .boxed(),
+    })
+}
+
+/// Log file that is rotated once it grows past a size limit.
+#[derive(Debug)]
+pub struct RotatingFile {
+    state: Mutex<RotationState>,
+}
+
+#[derive(Debug)]
+struct RotationState {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl RotatingFile {
+    /// Opens (or creates) `path` for appending, creating parent directories.
+    pub fn open(path: &Path, max_size: u64, max_files: usize) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = open_append(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            state: Mutex::new(RotationState {
+                path: path.to_path_buf(),
+                file,
+                size,
+                max_size,
+                max_files,
+            }),
+        })
+    }
+}
+
+impl RotationState {
+    /// Shifts `<file>.N-1` to `<file>.N`, ..., `<file>` to `<file>.1` and
+    /// starts a new file; with `max_files = 0` the file is truncated.
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.max_files == 0 {
+            self.file.set_len(0)?;
+            self.size = 0;
+            return Ok(());
+        }
+        // `rename` does not replace existing files on every platform
+        match std::fs::remove_file(rotated_path(&self.path, self.max_files)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+        for index in (1..self.max_files).rev() {
+            match std::fs::rename(
+                rotated_path(&self.path, index),
+                rotated_path(&self.path, index + 1),
+            ) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        std::fs::rename(&self.path, rotated_path(&self.path, 1))?;
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+}
+
+/// `<path>.<index>`
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = RotatingWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RotatingWriter(self.state.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Writer for one event; holds the file lock so lines never interleave.
+pub struct RotatingWriter<'a>(MutexGuard<'a, RotationState>);
+
+impl Write for RotatingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let state = &mut *self.0;
+        if state.size > 0 && state.size + buf.len() as u64 > state.max_size {
+            state.rotate()?;
+        }
+        let written = state.file.write(buf)?;
+        state.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.file.flush()
+    }
+}
+
+/// One JSON object per event with `timestamp`, `level`, `target` and the
+/// event fields (`message` included).
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        _ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let metadata = event.metadata();
+        let mut object = serde_json::Map::new();
+        object.insert("timestamp".into(), Local::now().to_rfc3339().into());
+        object.insert("level".into(), metadata.level().as_str().into());
+        object.insert("target".into(), metadata.target().into());
+        event.record(&mut JsonFields(&mut object));
+        writeln!(writer, "{}", serde_json::Value::Object(object))
+    }
+}
+
+struct JsonFields<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonFields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        let mut text = String::new();
+        let _ = write!(text, "{:?}", value);
+        self.0.insert(field.name().into(), text.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_keeps_max_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("gcop.log");
+        let file = RotatingFile::open(&path, 10, 2).unwrap();
+
+        for line in ["first-line\n", "second-line\n", "third-line\n", "fourth\n"] {
+            file.make_writer().write_all(line.as_bytes()).unwrap();
+        }
+
+        let read = |p: &Path| std::fs::read_to_string(p).unwrap();
+        assert_eq!(read(&path), "fourth\n");
+        assert_eq!(read(&rotated_path(&path, 1)), "third-line\n");
+        assert_eq!(read(&rotated_path(&path, 2)), "second-line\n");
+        // 超出 max_files 的最旧文件被覆盖
+        assert!(!rotated_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn test_rotation_without_backups_truncates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gcop.log");
+        std::fs::write(&path, "0123456789").unwrap();
+        let file = RotatingFile::open(&path, 10, 0).unwrap();
+
+        file.make_writer().write_all(b"new\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new\n");
+        assert!(!rotated_path(&path, 1).exists());
+    }
+
+    #[test]
+    fn test_json_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gcop.log");
+        let config = LoggingConfig {
+            file: Some(path.display().to_string()),
+            format: LogFormat::Json,
+            ..Default::default()
+        };
+        let subscriber = Registry::default().with(file_layer(&path, &config).unwrap());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(status = 503, retry = true, "Provider request failed");
+            tracing::trace!("below the configured level");
+        });
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let event: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(event["level"], "DEBUG");
+        assert_eq!(event["target"], "gcop_rs::logging::tests");
+        assert_eq!(event["message"], "Provider request failed");
+        assert_eq!(event["status"], 503);
+        assert_eq!(event["retry"], true);
+        assert!(event["timestamp"].is_string());
+    }
+}
@@ -6,7 +6,7 @@ use gcop_rs::*;
 
 use anyhow::Result;
 use clap::{CommandFactory, FromArgMatches};
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, ConfigAction};
 use tokio::runtime::Runtime;
 
 // Initialize i18n for binary crate
@@ -21,9 +21,27 @@ fn main() -> Result<()> {
         .install_default()
         .map_err(|_| anyhow::anyhow!("Failed to install rustls crypto provider"))?;
 
+    // 0.5 `-C/--repo <path>`: switch directories before anything reads the repository
+    //     (project config included), like `git -C`. Read from raw arguments for the
+    //     same reason as `--profile`.
+    if let Some(path) = repo_from_args(std::env::args()) {
+        std::env::set_current_dir(&path)
+            .map_err(|e| anyhow::anyhow!("Cannot change to '{}': {}", path, e))?;
+    }
+
     // 1. Load configuration (load once, reuse globally)
     //    Save the Result and reuse it when successful. When it fails, follow the command to decide whether to report an error.
-    let config_result = config::load_config();
+    //    `--profile` is read from raw arguments because config is needed before clap parsing.
+    //    Loading warnings (`api_key` in project config, stale preset) go to stderr.
+    let config_result = config::load_config_with_warnings(
+        profile_from_args(std::env::args()).as_deref(),
+    )
+    .map(|(config, warnings)| {
+        for warning in warnings {
+            eprintln!("{}", warning);
+        }
+        config
+    });
 
     // Locale initialization uses default values ​​to ensure that it does not fail due to configuration corruption.
     let early_config = config_result.as_ref().cloned().unwrap_or_default();
@@ -31,82 +49,145 @@ fn main() -> Result<()> {
     // 2. Initialize language (needs to be completed before CLI parsing, supports multi-language help text)
     init_locale(&early_config);
 
-    // 3. Parse CLI parameters and inject internationalized help text
+    // 3. Answer shell completion requests (`COMPLETE=<shell> gcop-rs ...`) and exit.
+    //    Must run before anything is written to stdout.
+    clap_complete::CompleteEnv::with_factory(|| {
+        commands::completions::with_dynamic_values(localized_command(), &early_config)
+    })
+    .var(commands::completions::COMPLETE_VAR)
+    .complete();
+
+    // 4. Parse CLI parameters and inject internationalized help text
     let cli = parse_cli_localized()?;
+    // `commit -o -` keeps stdout for the message alone
+    ui::set_quiet(
+        cli.quiet
+            || matches!(&cli.command, Commands::Commit(args)
+                if args.output.as_deref() == Some(std::path::Path::new("-"))),
+    );
 
-    // Set log level based on verbose flag
-    let log_level = if cli.verbose {
+    // Initialize tracing log (`api` keeps stdout for protocol events only),
+    // plus the `[logging]` file when configured
+    let terminal_level = if cli.verbose {
         tracing::Level::DEBUG
+    } else if cli.quiet {
+        tracing::Level::ERROR
     } else {
         tracing::Level::INFO
     };
+    logging::init(
+        &early_config.logging,
+        terminal_level,
+        matches!(cli.command, Commands::Api),
+    );
 
-    // Initialize tracing log
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env().add_directive(log_level.into()),
-        )
-        .init();
-
-    // 4. The commit/review command requires complete configuration (provider, etc.), and an error will occur if the configuration is damaged.
+    // 5. The commit/review command requires complete configuration (provider, etc.), and an error will occur if the configuration is damaged.
     //    Other commands can use the fallback default value.
-    let config = if matches!(
+    let mut config = if matches!(
         &cli.command,
-        Commands::Commit(..) | Commands::Review { .. } | Commands::Hook { .. }
+        Commands::Commit(..)
+            | Commands::Review(_)
+            | Commands::Tag(_)
+            | Commands::Reword(_)
+            | Commands::Hook { .. }
+            | Commands::Provider { .. }
+            | Commands::Alias { .. }
+            | Commands::Api
+            | Commands::Config {
+                action: Some(ConfigAction::Get { .. })
+            }
     ) {
-        config_result?
+        match config_result {
+            Ok(config) => config,
+            Err(e) => handle_command_error(&e, early_config.ui.colored),
+        }
     } else {
         early_config
     };
 
+    // Hooks, CI and pipes get plain output unless colors are forced
+    if !ui::is_interactive() && !ui::color_forced() {
+        config.ui.colored = false;
+        colored::control::set_override(false);
+    }
+
+    // Opt-in audit log of every request sent to a provider
+    llm::audit::set_enabled(config.privacy.audit_log);
+
+    // Weekly update hint after commands a person runs by hand
+    let update_hint = !matches!(
+        cli.command,
+        Commands::Hook { .. }
+            | Commands::RewordTodo { .. }
+            | Commands::Api
+            | Commands::Completions { .. }
+            | Commands::SelfCmd { .. }
+    );
+
     // Create tokio runtime
     let rt = Runtime::new()?;
 
-    // Route based on subcommand
-    rt.block_on(async {
+    // Route based on subcommand. Ctrl+C drops the running command (aborting any
+    // request and clearing spinners) instead of killing the process mid-render.
+    let outcome = rt.block_on(ui::interruptible(async {
         match cli.command {
             Commands::Commit(ref args) => {
-                let options = commands::CommitOptions::from_cli(&cli, args, &config);
+                let options = match commands::CommitOptions::from_cli(&cli, args, &config)
+                    .for_terminal(ui::is_interactive(), &config)
+                {
+                    Ok(options) => options,
+                    Err(e) => handle_command_error(&e, config.ui.colored),
+                };
                 let is_json = options.format.is_json();
                 if let Err(e) = commands::commit::run(&options, &config).await {
                     if is_json {
                         // JSON errors are printed inside the commit command
-                        std::process::exit(1);
+                        std::process::exit(e.exit_code());
                     }
                     match e {
-                        error::GcopError::UserCancelled => std::process::exit(0),
-                        error::GcopError::NoStagedChanges => std::process::exit(1),
+                        // Already reported by the commit command
+                        error::GcopError::UserCancelled | error::GcopError::NoStagedChanges => {
+                            std::process::exit(e.exit_code())
+                        }
                         _ => handle_command_error(&e, config.ui.colored),
                     }
                 }
                 Ok(())
             }
-            Commands::Review {
-                ref target,
-                ref format,
-                json,
-            } => {
-                let options = commands::ReviewOptions::from_cli(&cli, target, format, json);
+            Commands::Review(ref args) => {
+                let options = commands::ReviewOptions::from_cli(&cli, args);
                 if let Err(e) = commands::review::run(&options, &config).await {
                     if options.format.is_json() {
-                        // JSON errors are printed inside the review command
-                        std::process::exit(1);
+                        // JSON errors (and the review gate result) are printed inside the review command
+                        std::process::exit(e.exit_code());
                     }
-                    if matches!(e, error::GcopError::UserCancelled) {
-                        std::process::exit(0);
+                    match e {
+                        error::GcopError::UserCancelled => std::process::exit(e.exit_code()),
+                        error::GcopError::ReviewGateFailed { .. } => {
+                            ui::error(&e.localized_message(), config.ui.colored);
+                            std::process::exit(e.exit_code());
+                        }
+                        _ => handle_command_error(&e, config.ui.colored),
                     }
-                    handle_command_error(&e, config.ui.colored);
                 }
                 Ok(())
             }
-            Commands::Init { force, project } => {
-                if let Err(e) = commands::init::run(force, project, config.ui.colored) {
+            Commands::Init {
+                force,
+                project,
+                template,
+                from_gcop,
+            } => {
+                if let Err(e) =
+                    commands::init::run(force, project, template, from_gcop, config.ui.colored)
+                        .await
+                {
                     handle_command_error(&e, config.ui.colored);
                 }
                 Ok(())
             }
             Commands::Config { action } => {
-                if let Err(e) = commands::config::run(action, config.ui.colored).await {
+                if let Err(e) = commands::config::run(action, &config).await {
                     handle_command_error(&e, config.ui.colored);
                 }
                 Ok(())
@@ -116,28 +197,64 @@ fn main() -> Result<()> {
                 list,
                 remove,
             } => {
-                if let Err(e) = commands::alias::run(force, list, remove, config.ui.colored) {
+                if let Err(e) =
+                    commands::alias::run(force, list, remove, &config.aliases, config.ui.colored)
+                {
                     handle_command_error(&e, config.ui.colored);
                 }
                 Ok(())
             }
-            Commands::Stats {
-                ref format,
-                json,
-                ref author,
-                contrib,
-            } => {
-                let options =
-                    commands::StatsOptions::from_cli(format, json, author.as_deref(), contrib);
-                if let Err(e) = commands::stats::run(&options, config.ui.colored) {
+            Commands::Stats(ref args) => {
+                let options = commands::StatsOptions::from_cli(args);
+                if let Err(e) = commands::stats::run(&options, &config) {
                     if options.format.is_json() {
                         // JSON errors have been printed inside the stats command
-                        std::process::exit(1);
+                        std::process::exit(e.exit_code());
+                    }
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
+            Commands::Tag(ref args) => {
+                let options = commands::TagOptions::from_cli(&cli, args);
+                if let Err(e) = commands::tag::run(&options, &config).await {
+                    match e {
+                        error::GcopError::UserCancelled => std::process::exit(e.exit_code()),
+                        _ => handle_command_error(&e, config.ui.colored),
+                    }
+                }
+                Ok(())
+            }
+            Commands::Reword(ref args) => {
+                let options = commands::RewordOptions::from_cli(&cli, args);
+                if let Err(e) = commands::reword::run(&options, &config).await {
+                    match e {
+                        error::GcopError::UserCancelled => std::process::exit(e.exit_code()),
+                        _ => handle_command_error(&e, config.ui.colored),
                     }
+                }
+                Ok(())
+            }
+            Commands::RewordTodo {
+                ref hash,
+                ref todo_file,
+            } => {
+                // Git cancels the rebase when the sequence editor fails
+                if let Err(e) = git::reword::mark_for_reword(std::path::Path::new(todo_file), hash)
+                {
                     handle_command_error(&e, config.ui.colored);
                 }
                 Ok(())
             }
+            Commands::Undo { yes } => {
+                if let Err(e) = commands::undo::run(yes, &config) {
+                    match e {
+                        error::GcopError::UserCancelled => std::process::exit(e.exit_code()),
+                        _ => handle_command_error(&e, config.ui.colored),
+                    }
+                }
+                Ok(())
+            }
             Commands::Hook { ref action } => {
                 match action {
                     cli::HookAction::Install { force } => {
@@ -155,21 +272,107 @@ fn main() -> Result<()> {
                         source,
                         sha,
                     } => {
-                        commands::hook::run_hook_safe(
+                        // The error is already reported on stderr
+                        if let Err(e) = commands::hook::run_hook_safe(
                             commit_msg_file,
                             source,
                             sha,
                             &config,
                             cli.verbose,
                             cli.provider.as_deref(),
+                            cli.model.as_deref(),
                         )
-                        .await;
+                        .await
+                        {
+                            std::process::exit(e.exit_code());
+                        }
                     }
                 }
                 Ok(())
             }
+            Commands::Api => {
+                if let Err(e) = commands::api::run(&config).await {
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
+            Commands::Completions { shell } => {
+                if let Err(e) = commands::completions::run(shell) {
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
+            Commands::Prompt { ref action } => {
+                if let Err(e) = commands::prompt::run(action, &config) {
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
+            Commands::Usage {
+                period,
+                ref since,
+                ref format,
+                json,
+            } => {
+                let format = commands::OutputFormat::from_cli(format, json);
+                if let Err(e) = commands::usage::run(period, since.as_deref(), format, &config) {
+                    if format.is_json() {
+                        // JSON errors are printed inside the usage command
+                        std::process::exit(e.exit_code());
+                    }
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
+            Commands::Audit { ref action } => {
+                if let Err(e) = commands::audit::run(action, &config) {
+                    if let cli::AuditAction::Show { format, json, .. } = action
+                        && commands::OutputFormat::from_cli(format, *json).is_json()
+                    {
+                        // JSON errors are printed inside the audit command
+                        std::process::exit(e.exit_code());
+                    }
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
+            Commands::SelfCmd {
+                action: cli::SelfAction::Update { check },
+            } => {
+                if let Err(e) = commands::self_update::run(check, config.ui.colored).await {
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
+            Commands::Provider { ref action } => {
+                if let Err(e) =
+                    commands::provider::run(action, cli.provider.as_deref(), &config).await
+                {
+                    let is_json = match action {
+                        cli::ProviderAction::Test { format, json }
+                        | cli::ProviderAction::Ollama {
+                            action: cli::OllamaAction::ListModels { format, json },
+                        } => commands::OutputFormat::from_cli(format, *json).is_json(),
+                    };
+                    if is_json {
+                        // JSON errors are printed inside the provider command
+                        std::process::exit(e.exit_code());
+                    }
+                    handle_command_error(&e, config.ui.colored);
+                }
+                Ok(())
+            }
         }
-    })
+    }));
+    match outcome {
+        Ok(result) => {
+            if update_hint {
+                rt.block_on(commands::self_update::passive_check(&config));
+            }
+            result
+        }
+        Err(e) => handle_command_error(&e, config.ui.colored),
+    }
 }
 
 /// Parse CLI arguments with localized help text
@@ -179,16 +382,45 @@ fn main() -> Result<()> {
 /// 2. Override help text at runtime with rust_i18n::t!()
 /// 3. Parse and reconstruct the Cli struct
 fn parse_cli_localized() -> Result<Cli> {
-    let cmd = Cli::command()
+    let matches = match localized_command().try_get_matches() {
+        Ok(matches) => matches,
+        // Usage errors are validation failures; --help/--version still exit 0
+        Err(e) if e.use_stderr() => {
+            let _ = e.print();
+            std::process::exit(error::exit_code::VALIDATION_FAILURE);
+        }
+        Err(e) => e.exit(),
+    };
+    Cli::from_arg_matches(&matches)
+        .map_err(|e| anyhow::anyhow!("Failed to parse CLI arguments: {}", e))
+}
+
+/// Build the CLI definition with localized help text
+fn localized_command() -> clap::Command {
+    Cli::command()
         .about(rust_i18n::t!("cli.about").to_string())
         .mut_arg("verbose", |arg| {
             arg.help(rust_i18n::t!("cli.verbose").to_string())
         })
+        .mut_arg("quiet", |arg| {
+            arg.help(rust_i18n::t!("cli.quiet").to_string())
+        })
+        .mut_arg("profile", |arg| {
+            arg.help(rust_i18n::t!("cli.profile").to_string())
+        })
+        .mut_arg("repo", |arg| {
+            arg.help(rust_i18n::t!("cli.repo").to_string())
+        })
         .mut_arg("provider", |arg| {
             arg.help(rust_i18n::t!("cli.provider").to_string())
         })
+        .mut_arg("model", |arg| {
+            arg.help(rust_i18n::t!("cli.model").to_string())
+        })
+        .after_help(rust_i18n::t!("cli.non_interactive_help").to_string())
         .mut_subcommand("commit", |cmd| {
             cmd.about(rust_i18n::t!("cli.commit").to_string())
+                .after_help(rust_i18n::t!("cli.commit.non_interactive_help").to_string())
                 .mut_arg("no_edit", |arg| {
                     arg.help(rust_i18n::t!("cli.commit.no_edit").to_string())
                 })
@@ -204,12 +436,51 @@ fn parse_cli_localized() -> Result<Cli> {
                 .mut_arg("json", |arg| {
                     arg.help(rust_i18n::t!("cli.commit.json").to_string())
                 })
+                .mut_arg("json_stream", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.json_stream").to_string())
+                })
                 .mut_arg("split", |arg| {
                     arg.help(rust_i18n::t!("cli.commit.split").to_string())
                 })
                 .mut_arg("amend", |arg| {
                     arg.help(rust_i18n::t!("cli.commit.amend").to_string())
                 })
+                .mut_arg("fixup", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.fixup").to_string())
+                })
+                .mut_arg("squash", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.squash").to_string())
+                })
+                .mut_arg("all", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.all").to_string())
+                })
+                .mut_arg("no_sign", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.no_sign").to_string())
+                })
+                .mut_arg("signoff", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.signoff").to_string())
+                })
+                .mut_arg("co_authors", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.co_author").to_string())
+                })
+                .mut_arg("offline", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.offline").to_string())
+                })
+                .mut_arg("show_redacted", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.show_redacted").to_string())
+                })
+                .mut_arg("output", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.output").to_string())
+                })
+                .mut_arg("message_file", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.message_file").to_string())
+                })
+                .mut_arg("verify", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.verify").to_string())
+                })
+                .mut_arg("review", |arg| {
+                    arg.help(rust_i18n::t!("cli.commit.review").to_string())
+                })
                 .mut_arg("feedback", |arg| {
                     arg.help(rust_i18n::t!("cli.commit.feedback").to_string())
                 })
@@ -222,6 +493,33 @@ fn parse_cli_localized() -> Result<Cli> {
                 .mut_arg("json", |arg| {
                     arg.help(rust_i18n::t!("cli.review.json").to_string())
                 })
+                .mut_arg("json_stream", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.json_stream").to_string())
+                })
+                .mut_arg("update_baseline", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.update_baseline").to_string())
+                })
+                .mut_arg("fail_on", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.fail_on").to_string())
+                })
+                .mut_arg("preset", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.preset").to_string())
+                })
+                .mut_arg("show_redacted", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.show_redacted").to_string())
+                })
+                .mut_arg("apply", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.apply").to_string())
+                })
+                .mut_arg("incremental", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.incremental").to_string())
+                })
+                .mut_arg("full", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.full").to_string())
+                })
+                .mut_arg("no_cache", |arg| {
+                    arg.help(rust_i18n::t!("cli.review.no_cache").to_string())
+                })
                 .mut_subcommand("changes", |s| {
                     s.about(rust_i18n::t!("cli.review.changes").to_string())
                 })
@@ -242,6 +540,18 @@ fn parse_cli_localized() -> Result<Cli> {
                         .mut_arg("path", |arg| {
                             arg.help(rust_i18n::t!("cli.review.file.path").to_string())
                         })
+                        .mut_arg("with_context", |arg| {
+                            arg.help(rust_i18n::t!("cli.review.file.with_context").to_string())
+                        })
+                })
+                .mut_subcommand("mr", |s| {
+                    s.about(rust_i18n::t!("cli.review.mr").to_string())
+                        .mut_arg("iid", |arg| {
+                            arg.help(rust_i18n::t!("cli.review.mr.iid").to_string())
+                        })
+                        .mut_arg("post", |arg| {
+                            arg.help(rust_i18n::t!("cli.review.mr.post").to_string())
+                        })
                 })
         })
         .mut_subcommand("init", |cmd| {
@@ -252,6 +562,12 @@ fn parse_cli_localized() -> Result<Cli> {
                 .mut_arg("project", |arg| {
                     arg.help(rust_i18n::t!("cli.init.project").to_string())
                 })
+                .mut_arg("template", |arg| {
+                    arg.help(rust_i18n::t!("cli.init.template").to_string())
+                })
+                .mut_arg("from_gcop", |arg| {
+                    arg.help(rust_i18n::t!("cli.init.from_gcop").to_string())
+                })
         })
         .mut_subcommand("config", |cmd| {
             cmd.about(rust_i18n::t!("cli.config").to_string())
@@ -261,6 +577,48 @@ fn parse_cli_localized() -> Result<Cli> {
                 .mut_subcommand("validate", |s| {
                     s.about(rust_i18n::t!("cli.config.validate").to_string())
                 })
+                .mut_subcommand("schema", |s| {
+                    s.about(rust_i18n::t!("cli.config.schema").to_string())
+                })
+                .mut_subcommand("get", |s| {
+                    s.about(rust_i18n::t!("cli.config.get").to_string())
+                        .mut_arg("key", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.key").to_string())
+                        })
+                })
+                .mut_subcommand("set", |s| {
+                    s.about(rust_i18n::t!("cli.config.set").to_string())
+                        .mut_arg("key", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.key").to_string())
+                        })
+                        .mut_arg("value", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.value").to_string())
+                        })
+                        .mut_arg("project", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.project").to_string())
+                        })
+                })
+                .mut_subcommand("unset", |s| {
+                    s.about(rust_i18n::t!("cli.config.unset").to_string())
+                        .mut_arg("key", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.key").to_string())
+                        })
+                        .mut_arg("project", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.project").to_string())
+                        })
+                })
+                .mut_subcommand("env", |s| {
+                    s.about(rust_i18n::t!("cli.config.env").to_string())
+                        .mut_arg("json", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.env.json").to_string())
+                        })
+                })
+                .mut_subcommand("doctor", |s| {
+                    s.about(rust_i18n::t!("cli.config.doctor").to_string())
+                        .mut_arg("online", |arg| {
+                            arg.help(rust_i18n::t!("cli.config.doctor.online").to_string())
+                        })
+                })
         })
         .mut_subcommand("alias", |cmd| {
             cmd.about(rust_i18n::t!("cli.alias").to_string())
@@ -274,6 +632,48 @@ fn parse_cli_localized() -> Result<Cli> {
                     arg.help(rust_i18n::t!("cli.alias.remove").to_string())
                 })
         })
+        .mut_subcommand("tag", |cmd| {
+            cmd.about(rust_i18n::t!("cli.tag").to_string())
+                .mut_arg("name", |arg| {
+                    arg.help(rust_i18n::t!("cli.tag.name").to_string())
+                })
+                .mut_arg("from", |arg| {
+                    arg.help(rust_i18n::t!("cli.tag.from").to_string())
+                })
+                .mut_arg("dry_run", |arg| {
+                    arg.help(rust_i18n::t!("cli.tag.dry_run").to_string())
+                })
+                .mut_arg("yes", |arg| {
+                    arg.help(rust_i18n::t!("cli.tag.yes").to_string())
+                })
+                .mut_arg("push", |arg| {
+                    arg.help(rust_i18n::t!("cli.tag.push").to_string())
+                })
+                .mut_arg("remote", |arg| {
+                    arg.help(rust_i18n::t!("cli.tag.remote").to_string())
+                })
+        })
+        .mut_subcommand("reword", |cmd| {
+            cmd.about(rust_i18n::t!("cli.reword").to_string())
+                .mut_arg("commit", |arg| {
+                    arg.help(rust_i18n::t!("cli.reword.commit").to_string())
+                })
+                .mut_arg("dry_run", |arg| {
+                    arg.help(rust_i18n::t!("cli.reword.dry_run").to_string())
+                })
+                .mut_arg("yes", |arg| {
+                    arg.help(rust_i18n::t!("cli.reword.yes").to_string())
+                })
+                .mut_arg("feedback", |arg| {
+                    arg.help(rust_i18n::t!("cli.reword.feedback").to_string())
+                })
+        })
+        .mut_subcommand("undo", |cmd| {
+            cmd.about(rust_i18n::t!("cli.undo").to_string())
+                .mut_arg("yes", |arg| {
+                    arg.help(rust_i18n::t!("cli.undo.yes").to_string())
+                })
+        })
         .mut_subcommand("stats", |cmd| {
             cmd.about(rust_i18n::t!("cli.stats").to_string())
                 .mut_arg("format", |arg| {
@@ -282,12 +682,30 @@ fn parse_cli_localized() -> Result<Cli> {
                 .mut_arg("json", |arg| {
                     arg.help(rust_i18n::t!("cli.stats.json").to_string())
                 })
+                .mut_arg("json_stream", |arg| {
+                    arg.help(rust_i18n::t!("cli.stats.json_stream").to_string())
+                })
                 .mut_arg("author", |arg| {
                     arg.help(rust_i18n::t!("cli.stats.author").to_string())
                 })
                 .mut_arg("contrib", |arg| {
                     arg.help(rust_i18n::t!("cli.stats.contrib").to_string())
                 })
+                .mut_arg("detailed", |arg| {
+                    arg.help(rust_i18n::t!("cli.stats.detailed").to_string())
+                })
+                .mut_arg("since", |arg| {
+                    arg.help(rust_i18n::t!("cli.stats.since").to_string())
+                })
+                .mut_arg("until", |arg| {
+                    arg.help(rust_i18n::t!("cli.stats.until").to_string())
+                })
+                .mut_arg("range", |arg| {
+                    arg.help(rust_i18n::t!("cli.stats.range").to_string())
+                })
+                .mut_arg("path", |arg| {
+                    arg.help(rust_i18n::t!("cli.stats.path").to_string())
+                })
         })
         .mut_subcommand("hook", |cmd| {
             cmd.about(rust_i18n::t!("cli.hook").to_string())
@@ -300,11 +718,151 @@ fn parse_cli_localized() -> Result<Cli> {
                 .mut_subcommand("uninstall", |s| {
                     s.about(rust_i18n::t!("cli.hook.uninstall").to_string())
                 })
-        });
+        })
+        .mut_subcommand("provider", |cmd| {
+            cmd.about(rust_i18n::t!("cli.provider_cmd").to_string())
+                .mut_subcommand("test", |s| {
+                    s.about(rust_i18n::t!("cli.provider_cmd.test").to_string())
+                        .mut_arg("format", |arg| {
+                            arg.help(rust_i18n::t!("cli.provider_cmd.test.format").to_string())
+                        })
+                        .mut_arg("json", |arg| {
+                            arg.help(rust_i18n::t!("cli.provider_cmd.test.json").to_string())
+                        })
+                })
+                .mut_subcommand("ollama", |s| {
+                    s.about(rust_i18n::t!("cli.provider_cmd.ollama").to_string())
+                        .mut_subcommand("list-models", |s| {
+                            s.about(
+                                rust_i18n::t!("cli.provider_cmd.ollama.list_models").to_string(),
+                            )
+                            .mut_arg("format", |arg| {
+                                arg.help(
+                                    rust_i18n::t!("cli.provider_cmd.ollama.list_models.format")
+                                        .to_string(),
+                                )
+                            })
+                            .mut_arg("json", |arg| {
+                                arg.help(rust_i18n::t!("cli.provider_cmd.test.json").to_string())
+                            })
+                        })
+                })
+        })
+        .mut_subcommand("api", |cmd| cmd.about(rust_i18n::t!("cli.api").to_string()))
+        .mut_subcommand("completions", |cmd| {
+            cmd.about(rust_i18n::t!("cli.completions").to_string())
+                .mut_arg("shell", |arg| {
+                    arg.help(rust_i18n::t!("cli.completions.shell").to_string())
+                })
+        })
+        .mut_subcommand("prompt", |cmd| {
+            cmd.about(rust_i18n::t!("cli.prompt").to_string())
+                .mut_subcommand("show", |s| {
+                    s.about(rust_i18n::t!("cli.prompt.show").to_string())
+                        .mut_arg("template", |arg| {
+                            arg.help(rust_i18n::t!("cli.prompt.show.template").to_string())
+                        })
+                        .mut_arg("source", |arg| {
+                            arg.help(rust_i18n::t!("cli.prompt.show.source").to_string())
+                        })
+                })
+        })
+        .mut_subcommand("usage", |cmd| {
+            cmd.about(rust_i18n::t!("cli.usage").to_string())
+                .mut_arg("period", |arg| {
+                    arg.help(rust_i18n::t!("cli.usage.period").to_string())
+                })
+                .mut_arg("since", |arg| {
+                    arg.help(rust_i18n::t!("cli.usage.since").to_string())
+                })
+                .mut_arg("format", |arg| {
+                    arg.help(rust_i18n::t!("cli.usage.format").to_string())
+                })
+                .mut_arg("json", |arg| {
+                    arg.help(rust_i18n::t!("cli.usage.json").to_string())
+                })
+        })
+        .mut_subcommand("self", |cmd| {
+            cmd.about(rust_i18n::t!("cli.self").to_string())
+                .mut_subcommand("update", |s| {
+                    s.about(rust_i18n::t!("cli.self.update").to_string())
+                        .mut_arg("check", |arg| {
+                            arg.help(rust_i18n::t!("cli.self.update.check").to_string())
+                        })
+                })
+        })
+        .mut_subcommand("audit", |cmd| {
+            cmd.about(rust_i18n::t!("cli.audit").to_string())
+                .mut_subcommand("show", |s| {
+                    s.about(rust_i18n::t!("cli.audit.show").to_string())
+                        .mut_arg("since", |arg| {
+                            arg.help(rust_i18n::t!("cli.usage.since").to_string())
+                        })
+                        .mut_arg("limit", |arg| {
+                            arg.help(rust_i18n::t!("cli.audit.show.limit").to_string())
+                        })
+                        .mut_arg("format", |arg| {
+                            arg.help(rust_i18n::t!("cli.usage.format").to_string())
+                        })
+                        .mut_arg("json", |arg| {
+                            arg.help(rust_i18n::t!("cli.usage.json").to_string())
+                        })
+                })
+                .mut_subcommand("export", |s| {
+                    s.about(rust_i18n::t!("cli.audit.export").to_string())
+                        .mut_arg("since", |arg| {
+                            arg.help(rust_i18n::t!("cli.usage.since").to_string())
+                        })
+                        .mut_arg("format", |arg| {
+                            arg.help(rust_i18n::t!("cli.audit.export.format").to_string())
+                        })
+                        .mut_arg("output", |arg| {
+                            arg.help(rust_i18n::t!("cli.audit.export.output").to_string())
+                        })
+                })
+        })
+}
 
-    let matches = cmd.get_matches();
-    Cli::from_arg_matches(&matches)
-        .map_err(|e| anyhow::anyhow!("Failed to parse CLI arguments: {}", e))
+/// Extract `--profile <name>` / `--profile=<name>` from raw arguments
+fn profile_from_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    global_arg_from_args(args, "--profile", None)
+}
+
+/// Extract `-C <path>` / `--repo <path>` / `--repo=<path>` from raw arguments
+fn repo_from_args(args: impl IntoIterator<Item = String>) -> Option<String> {
+    global_arg_from_args(args, "--repo", Some("-C"))
+}
+
+/// Extract the value of a global option from raw arguments
+///
+/// Scanning stops at `--` so trailing feedback words are never treated as flags.
+fn global_arg_from_args(
+    args: impl IntoIterator<Item = String>,
+    long: &str,
+    short: Option<&str>,
+) -> Option<String> {
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            break;
+        }
+        if arg == long || Some(arg.as_str()) == short {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(long)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+        if let Some(value) = short
+            .and_then(|short| arg.strip_prefix(short))
+            .filter(|value| !value.is_empty())
+        {
+            return Some(value.to_string());
+        }
+    }
+    None
 }
 
 /// Initialize locale from loaded config
@@ -336,10 +894,16 @@ fn detect_system_locale() -> Option<String> {
 
 /// Show error message + suggestions, then exit
 fn handle_command_error(e: &error::GcopError, colored: bool) -> ! {
+    if let error::GcopError::Interrupted = e {
+        // Start below whatever was being streamed or echoed as `^C`
+        eprintln!();
+        ui::error(&e.localized_message(), colored);
+        std::process::exit(e.exit_code());
+    }
     ui::error(&e.localized_message(), colored);
     if let Some(suggestion) = e.localized_suggestion() {
-        println!();
-        println!("{}", ui::info(&suggestion, colored));
+        eprintln!();
+        eprintln!("{}", ui::info(&suggestion, colored));
     }
-    std::process::exit(1);
+    std::process::exit(e.exit_code());
 }
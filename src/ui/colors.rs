@@ -2,8 +2,11 @@ use colored::Colorize;
 
 use crate::git::DiffStats;
 
-/// Show success message (green ✓)
+/// Show success message (green ✓); hidden in quiet mode
 pub fn success(msg: &str, colored: bool) {
+    if super::is_quiet() {
+        return;
+    }
     if colored {
         println!("{} {}", "✓".green().bold(), msg.green());
     } else {
@@ -20,8 +23,11 @@ pub fn error(msg: &str, colored: bool) {
     }
 }
 
-/// Show warning message (yellow ⚠)
+/// Show warning message (yellow ⚠); hidden in quiet mode
 pub fn warning(msg: &str, colored: bool) {
+    if super::is_quiet() {
+        return;
+    }
     if colored {
         println!("{} {}", "⚠".yellow().bold(), msg.yellow());
     } else {
@@ -38,8 +44,11 @@ pub fn info(msg: &str, colored: bool) -> String {
     }
 }
 
-/// Show step prompts (gray)
+/// Show step prompts (gray); hidden in quiet mode
 pub fn step(step: &str, msg: &str, colored: bool) {
+    if super::is_quiet() {
+        return;
+    }
     if colored {
         println!(
             "{} {}",
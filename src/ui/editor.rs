@@ -1,10 +1,18 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
 use crate::error::{GcopError, Result};
 
+/// Git's scissors line; everything from it to the end of the file is dropped.
+pub const SCISSORS_LINE: &str = "# ------------------------ >8 ------------------------";
+
 /// Call the system editor to edit text
 ///
-/// Use the `edit` crate to automatically select editors by priority:
-/// $VISUAL > $EDITOR > Platform default list (nano/vim/vi/emacs/...)
-/// If the editor pointed to by the environment variable does not exist, it will automatically fall back to the next available editor.
+/// Editors are selected like git does: `$GIT_EDITOR` > `core.editor` >
+/// `$VISUAL` > `$EDITOR` > platform default list (nano/vim/vi/emacs/...).
+/// If the editor pointed to by `$VISUAL`/`$EDITOR` does not exist, it will
+/// automatically fall back to the next available editor.
 ///
 /// # Arguments
 /// * `initial_content` - initial content
@@ -14,7 +22,7 @@ use crate::error::{GcopError, Result};
 /// * `Err(GcopError::UserCancelled)` - The user cleared the content
 /// * `Err(_)` - other errors
 pub fn edit_text(initial_content: &str) -> Result<String> {
-    let edited = edit::edit(initial_content)?;
+    let edited = edit_in_file(initial_content, None)?;
 
     // Remove leading and trailing whitespace and check if it is empty
     let trimmed = edited.trim();
@@ -26,3 +34,289 @@ pub fn edit_text(initial_content: &str) -> Result<String> {
     // Returns the edited content (preserving the user's formatting)
     Ok(edited)
 }
+
+/// Edit a commit message with git-style commented guidance
+///
+/// The buffer holds the message, then a scissors line followed by `guidance`
+/// as `#` comments (diff stat, convention rules). Everything from the
+/// scissors line down is stripped on save.
+///
+/// # Arguments
+/// * `message` - current commit message
+/// * `guidance` - lines shown below the scissors line (without the `# ` prefix)
+/// * `path` - message file to round-trip through (e.g. `.git/COMMIT_EDITMSG`
+///   so editors pick git commit syntax); a temporary file when `None`
+///
+/// # Returns
+/// * `Ok(String)` - edited message
+/// * `Err(GcopError::UserCancelled)` - The user cleared the message
+/// * `Err(_)` - other errors
+pub fn edit_commit_message(
+    message: &str,
+    guidance: &[String],
+    path: Option<&Path>,
+) -> Result<String> {
+    let edited = edit_in_file(&render_message_template(message, guidance), path)?;
+    let message = strip_message_template(&edited);
+
+    if message.trim().is_empty() {
+        return Err(GcopError::UserCancelled);
+    }
+    Ok(message)
+}
+
+/// Build the editor buffer: message, scissors line, commented guidance.
+fn render_message_template(message: &str, guidance: &[String]) -> String {
+    let mut buffer = format!("{}\n\n{}\n", message.trim_end(), SCISSORS_LINE);
+    let hints = [
+        rust_i18n::t!("editor.scissors_keep").to_string(),
+        rust_i18n::t!("editor.scissors_ignored").to_string(),
+        String::new(),
+    ];
+    for line in hints.iter().chain(guidance) {
+        if line.is_empty() {
+            buffer.push_str("#\n");
+        } else {
+            buffer.push_str(&format!("# {}\n", line));
+        }
+    }
+    buffer
+}
+
+/// Drop the scissors line and everything below it, plus trailing blank lines.
+fn strip_message_template(content: &str) -> String {
+    let kept = match content
+        .lines()
+        .position(|line| line.trim_end() == SCISSORS_LINE)
+    {
+        Some(index) => content.lines().take(index).collect::<Vec<_>>().join("\n"),
+        None => content.to_string(),
+    };
+    kept.trim_end().to_string()
+}
+
+/// Write `content` to `path` (or a temporary file), open the editor and read it back.
+fn edit_in_file(content: &str, path: Option<&Path>) -> Result<String> {
+    let temp;
+    let path: PathBuf = match path {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            path.to_path_buf()
+        }
+        None => {
+            let mut file = tempfile::Builder::new()
+                .prefix("gcop-")
+                .suffix(".txt")
+                .tempfile()?;
+            file.write_all(content.as_bytes())?;
+            file.flush()?;
+            temp = file.into_temp_path();
+            temp.to_path_buf()
+        }
+    };
+
+    open_editor(&path)?;
+    Ok(std::fs::read_to_string(&path)?)
+}
+
+/// Edit text that may hold secrets (e.g. a decrypted config) in `$VISUAL` / `$EDITOR`
+///
+/// The copy is a `0600` file inside a private `0700` directory, and it is
+/// removed when the editor exits, on errors, and when Ctrl+C is pressed
+/// during editing. The content is not checked for emptiness.
+///
+/// # Arguments
+/// * `content` - initial content
+/// * `suffix` - file name suffix, so editors pick the right syntax (e.g. `.toml`)
+pub fn edit_private_text(content: &str, suffix: &str) -> Result<String> {
+    let (_dir, path) = private_copy(content, suffix)?;
+    // Ctrl+C reaches the editor too; gcop-rs outlives it so `_dir` is still dropped
+    let _ctrl_c = hold_ctrl_c();
+    edit::edit_file(&path)?;
+    Ok(std::fs::read_to_string(&path)?)
+}
+
+/// Write `content` to a `0600` file in a fresh private directory.
+///
+/// The directory (and the file) is deleted when the returned [`tempfile::TempDir`] drops.
+fn private_copy(content: &str, suffix: &str) -> Result<(tempfile::TempDir, PathBuf)> {
+    let dir = tempfile::Builder::new().prefix("gcop-").tempdir()?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o700))?;
+    }
+
+    let path = dir.path().join(format!("edit{}", suffix));
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    file.write_all(content.as_bytes())?;
+    file.flush()?;
+    Ok((dir, path))
+}
+
+/// Replace the default Ctrl+C action (exiting) with a no-op while the guard lives.
+///
+/// Needs a Tokio runtime; without one Ctrl+C keeps its default behaviour.
+fn hold_ctrl_c() -> Option<impl Sized> {
+    let runtime = tokio::runtime::Handle::try_current().ok()?;
+    let _enter = runtime.enter();
+    #[cfg(unix)]
+    let listener = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::interrupt()).ok();
+    #[cfg(windows)]
+    let listener = tokio::signal::windows::ctrl_c().ok();
+    #[cfg(not(any(unix, windows)))]
+    let listener = None::<()>;
+    listener
+}
+
+/// Run the configured editor on `path` and wait for it to exit.
+fn open_editor(path: &Path) -> Result<()> {
+    let Some((program, args)) = git_editor().as_deref().and_then(split_editor_command) else {
+        // $VISUAL > $EDITOR > platform defaults, handled by the `edit` crate
+        edit::edit_file(path)?;
+        return Ok(());
+    };
+
+    let status = Command::new(&program).args(&args).arg(path).status()?;
+    if !status.success() {
+        return Err(std::io::Error::other(format!(
+            "editor '{}' exited with error: {}",
+            program, status
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// `$GIT_EDITOR`, then `core.editor` from the current repository or global config.
+fn git_editor() -> Option<String> {
+    let non_empty = |editor: &String| !editor.trim().is_empty();
+    std::env::var("GIT_EDITOR")
+        .ok()
+        .filter(non_empty)
+        .or_else(|| {
+            let config = git2::Repository::open_from_env()
+                .and_then(|repo| repo.config())
+                .or_else(|_| git2::Config::open_default())
+                .ok()?;
+            config.get_string("core.editor").ok().filter(non_empty)
+        })
+}
+
+/// Editor command gcop-rs launches: `$GIT_EDITOR`, `core.editor`, `$VISUAL`, then `$EDITOR`.
+///
+/// `None` means the `edit` crate falls back to a platform default.
+pub fn configured_editor() -> Option<String> {
+    git_editor().or_else(|| {
+        ["VISUAL", "EDITOR"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|editor| !editor.trim().is_empty())
+    })
+}
+
+/// Program name of an editor command line (`code --wait` -> `code`).
+pub fn editor_program(command: &str) -> Option<String> {
+    split_editor_command(command).map(|(program, _)| program)
+}
+
+/// Split an editor command line into program and arguments, honouring shell
+/// quoting (`"C:/Program Files/Editor/editor.exe" --wait`).
+fn split_editor_command(command: &str) -> Option<(String, Vec<String>)> {
+    let mut words = shell_words::split(command)
+        .unwrap_or_else(|_| command.split_whitespace().map(String::from).collect());
+    if words.is_empty() {
+        return None;
+    }
+    let program = words.remove(0);
+    Some((program, words))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_editor_command_with_quotes() {
+        let (program, args) =
+            split_editor_command(r#""/Applications/Sublime Text.app/subl" -w --new-window"#)
+                .unwrap();
+        assert_eq!(program, "/Applications/Sublime Text.app/subl");
+        assert_eq!(args, vec!["-w", "--new-window"]);
+
+        let (program, args) = split_editor_command("code --wait").unwrap();
+        assert_eq!(program, "code");
+        assert_eq!(args, vec!["--wait"]);
+
+        assert!(split_editor_command("   ").is_none());
+    }
+
+    #[test]
+    fn test_split_editor_command_unbalanced_quote_falls_back() {
+        let (program, args) = split_editor_command("vim 'broken").unwrap();
+        assert_eq!(program, "vim");
+        assert_eq!(args, vec!["'broken"]);
+    }
+
+    #[test]
+    fn test_private_copy_is_owner_only_and_removed() {
+        let (dir, path) = private_copy("api_key = \"sk-secret\"\n", ".toml").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "api_key = \"sk-secret\"\n"
+        );
+        assert!(path.starts_with(dir.path()));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(dir.path()), 0o700);
+            assert_eq!(mode(&path), 0o600);
+        }
+
+        // 目录随 TempDir 一起删除，解密后的明文不留在磁盘上
+        let dir_path = dir.path().to_path_buf();
+        drop(dir);
+        assert!(!dir_path.exists());
+    }
+
+    #[test]
+    fn test_message_template_round_trip() {
+        let guidance = vec![
+            "1 file changed".to_string(),
+            String::new(),
+            "  src/main.rs".to_string(),
+        ];
+        let buffer = render_message_template("feat: add login\n\nBody line\n", &guidance);
+
+        assert!(buffer.starts_with("feat: add login\n\nBody line\n\n"));
+        assert!(buffer.contains(&format!("\n{}\n", SCISSORS_LINE)));
+        assert!(buffer.contains("# 1 file changed\n#\n#   src/main.rs\n"));
+        assert_eq!(
+            strip_message_template(&buffer),
+            "feat: add login\n\nBody line"
+        );
+    }
+
+    #[test]
+    fn test_strip_message_template_keeps_user_comments_above_scissors() {
+        // 剪刀线以上的 `#` 行属于用户内容（例如 issue 引用），不能删
+        let content = format!(
+            "fix: handle #123\n\n# keep me\n{}\n# dropped\n",
+            SCISSORS_LINE
+        );
+        assert_eq!(
+            strip_message_template(&content),
+            "fix: handle #123\n\n# keep me"
+        );
+        assert_eq!(strip_message_template("docs: typo\n\n"), "docs: typo");
+    }
+}
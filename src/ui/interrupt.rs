@@ -0,0 +1,48 @@
+//! Ctrl+C handling for in-flight work
+//!
+//! Once a Ctrl+C listener is installed the default SIGINT behaviour (killing
+//! the process) is gone, so the awaited future is dropped instead: the HTTP
+//! request is aborted and spinners clean up their line in `Drop`.
+
+use std::future::Future;
+
+use crate::error::{GcopError, Result};
+
+/// Runs `future` until it completes or the user presses Ctrl+C.
+///
+/// On Ctrl+C the future is dropped and [`GcopError::Interrupted`] is returned.
+/// Nested calls are fine: every listener is notified, and the innermost one
+/// wins as long as the outer `select!` polls its future first.
+pub async fn interruptible<F: Future>(future: F) -> Result<F::Output> {
+    interruptible_with(future, tokio::signal::ctrl_c()).await
+}
+
+/// [`interruptible`] with an explicit interrupt signal (the Ctrl+C listener in production).
+pub(crate) async fn interruptible_with<F: Future, S: Future>(
+    future: F,
+    signal: S,
+) -> Result<F::Output> {
+    tokio::select! {
+        biased;
+        output = future => Ok(output),
+        _ = signal => Err(GcopError::Interrupted),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_interruptible_returns_output() {
+        let result = interruptible(async { 42 }).await;
+        assert!(matches!(result, Ok(42)));
+    }
+
+    #[tokio::test]
+    async fn test_interruptible_returns_interrupted_on_signal() {
+        // 模拟 Ctrl+C：信号先于 future 完成
+        let result = interruptible_with(std::future::pending::<i32>(), async {}).await;
+        assert!(matches!(result, Err(GcopError::Interrupted)));
+    }
+}
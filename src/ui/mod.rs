@@ -5,9 +5,12 @@
 //! # Modules
 //! - `colors` - Colored output helpers.
 //! - `editor` - External editor integration.
+//! - `interrupt` - Ctrl+C handling for in-flight requests.
 //! - `prompt` - Interactive prompts (confirm/menu/input).
+//! - `reporter` - Status output sink of command runners (terminal, silent, JSON).
 //! - `spinner` - Progress spinner.
 //! - `streaming` - Streaming text renderer (typewriter effect).
+//! - `terminal` - Interactive terminal detection and quiet mode.
 //!
 //! # Example
 //! ```ignore
@@ -26,15 +29,29 @@
 pub mod colors;
 /// External editor integration utilities.
 pub mod editor;
+/// Ctrl+C cancellation of in-flight requests.
+pub mod interrupt;
 /// Interactive prompt helpers for commit/review flows.
 pub mod prompt;
+/// Status output sink injected into command runners.
+pub mod reporter;
 /// Spinner/progress indicator implementation.
 pub mod spinner;
 /// Streaming text output helpers.
 pub mod streaming;
+/// Interactive terminal detection (hooks, CI, pipes) and quiet mode.
+pub mod terminal;
 
 pub use colors::*;
 pub use editor::*;
-pub use prompt::{CommitAction, commit_action_menu, confirm, get_retry_feedback};
+pub use interrupt::interruptible;
+pub use prompt::{
+    CommitAction, ReviewGateAction, commit_action_menu, confirm, get_retry_feedback,
+    review_gate_menu,
+};
+pub use reporter::{JsonReporter, Reporter, SilentReporter, TerminalReporter, forward_stream};
 pub use spinner::*;
 pub use streaming::*;
+pub use terminal::{
+    color_forced, is_interactive, is_quiet, quiet_scope, quiet_scope_sync, set_quiet,
+};
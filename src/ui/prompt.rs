@@ -158,15 +158,110 @@ pub fn commit_action_menu(
     Ok(action)
 }
 
+/// User's choice after `commit --review` found critical issues
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewGateAction {
+    /// Generate the commit message anyway.
+    Continue,
+    /// Fix the issues, then review the staged changes again.
+    Fix,
+    /// Exit without committing.
+    Abort,
+}
+
+/// Ask how to proceed after the commit-time review found critical issues
+///
+/// # Returns
+/// * `Ok(ReviewGateAction)` - the action selected by the user (ESC / Ctrl+C aborts)
+/// * `Err(_)` - An error occurred
+pub fn review_gate_menu(colored: bool) -> Result<ReviewGateAction> {
+    use rust_i18n::t;
+
+    let labels = [
+        ("→", t!("commit.review.menu.continue")),
+        ("✎", t!("commit.review.menu.fix")),
+        ("✕", t!("commit.review.menu.abort")),
+    ];
+    let options: Vec<String> = labels
+        .iter()
+        .map(|(icon, label)| {
+            if colored {
+                format!("{} {}", icon.bold(), label)
+            } else {
+                format!("{} {}", icon, label)
+            }
+        })
+        .collect();
+    let prompt = if colored {
+        format!(
+            "{} {}",
+            t!("commit.review.menu.choose").cyan().bold(),
+            t!("messages.esc_to_quit").dimmed()
+        )
+    } else {
+        format!(
+            "{} {}",
+            t!("commit.review.menu.choose"),
+            t!("messages.esc_to_quit")
+        )
+    };
+
+    match inquire::Select::new(&prompt, options)
+        .with_starting_cursor(1)
+        .raw_prompt()
+    {
+        Ok(choice) => Ok(match choice.index {
+            0 => ReviewGateAction::Continue,
+            1 => ReviewGateAction::Fix,
+            _ => ReviewGateAction::Abort,
+        }),
+        Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => {
+            Ok(ReviewGateAction::Abort)
+        }
+        Err(_) => Err(GcopError::UserCancelled),
+    }
+}
+
 /// Get user feedback on retries
 ///
+/// With `presets` (`(label, instruction)` pairs), a quick-pick list is shown
+/// first; its last entry falls back to free-text input.
+///
 /// # Returns
-/// * `Ok(Some(String))` - user-entered feedback
+/// * `Ok(Some(String))` - selected or user-entered feedback
 /// * `Ok(None)` - user did not enter or canceled
 /// * `Err(_)` - An error occurred
-pub fn get_retry_feedback(colored: bool) -> Result<Option<String>> {
+pub fn get_retry_feedback(colored: bool, presets: &[(String, String)]) -> Result<Option<String>> {
     use rust_i18n::t;
 
+    if !presets.is_empty() {
+        let mut options: Vec<String> = presets
+            .iter()
+            .map(|(label, instruction)| {
+                if colored {
+                    format!("{} {}", label, format!("({})", instruction).dimmed())
+                } else {
+                    format!("{} ({})", label, instruction)
+                }
+            })
+            .collect();
+        options.push(format!("✎ {}", t!("commit.feedback.custom")));
+
+        let prompt = t!("commit.feedback.choose");
+        let prompt = if colored {
+            prompt.cyan().bold().to_string()
+        } else {
+            prompt.to_string()
+        };
+        let selection = inquire::Select::new(&prompt, options)
+            .with_starting_cursor(0)
+            .raw_prompt()
+            .map_err(|_| GcopError::UserCancelled)?;
+        if let Some((_, instruction)) = presets.get(selection.index) {
+            return Ok(Some(instruction.clone()));
+        }
+    }
+
     let hint = t!("commit.feedback.hint");
     if colored {
         println!("\n{}", hint.cyan());
@@ -214,7 +309,12 @@ pub fn get_retry_feedback(colored: bool) -> Result<Option<String>> {
 /// * `Ok(true)` - user selected Yes
 /// * `Ok(false)` - user selected No
 /// * `Err(_)` - An error occurred
+///
+/// Without an interactive terminal the prompt is skipped and `default` returned.
 pub fn confirm(message: &str, default: bool) -> Result<bool> {
+    if !super::is_interactive() {
+        return Ok(default);
+    }
     let result = inquire::Confirm::new(message)
         .with_default(default)
         .prompt()?;
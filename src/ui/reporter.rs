@@ -0,0 +1,345 @@
+//! Status output of command runners.
+//!
+//! Commands report steps, messages, progress and streamed text through a
+//! [`Reporter`] instead of calling the terminal helpers directly, so the same
+//! logic can run behind the terminal UI, silently (machine-readable formats,
+//! library use) or as newline-delimited JSON events for wrapping tools.
+//!
+//! The output a command was asked for (reports, JSON payloads) is not part of
+//! the reporter: it is still written by the command itself.
+
+use std::io::Write;
+use std::sync::Mutex;
+
+use colored::Colorize;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+use super::Spinner;
+use crate::error::{GcopError, Result};
+use crate::llm::StreamChunk;
+
+/// Receives the status output of a command run.
+pub trait Reporter: Send + Sync {
+    /// Numbered step, e.g. `[1/2] Analyzing staged changes...`.
+    fn step(&self, step: &str, message: &str);
+
+    /// Informational message.
+    fn info(&self, message: &str);
+
+    /// Success message.
+    fn success(&self, message: &str);
+
+    /// Warning message.
+    fn warning(&self, message: &str);
+
+    /// Error message; the error itself is still returned by the command.
+    fn error(&self, message: &str);
+
+    /// Titled text shown for review, e.g. a generated commit message.
+    fn preview(&self, title: &str, text: &str);
+
+    /// Preformatted block shown as is, e.g. diff stats or a split plan.
+    fn detail(&self, text: &str);
+
+    /// Starts a progress indicator for a long-running request.
+    ///
+    /// Returns `None` when this reporter shows no spinner.
+    fn spinner(&self, message: &str) -> Option<Spinner>;
+
+    /// Like [`Reporter::spinner`], for a request the user can cancel with
+    /// Ctrl+C: the terminal shows the hint and the elapsed time.
+    fn cancellable_spinner(&self, message: &str) -> Option<Spinner> {
+        self.spinner(message)
+    }
+
+    /// Asks a yes/no question; reporters that cannot ask answer `default`.
+    fn confirm(&self, message: &str, default: bool) -> Result<bool>;
+
+    /// Text delta of a streaming response.
+    fn delta(&self, _text: &str) {}
+
+    /// The streaming response is being retried; earlier deltas are void.
+    fn retry(&self) {}
+}
+
+/// Collects a streaming response, forwarding each delta to `reporter`.
+pub async fn forward_stream(
+    mut receiver: mpsc::Receiver<StreamChunk>,
+    reporter: &dyn Reporter,
+) -> Result<String> {
+    let mut response = String::new();
+    while let Some(chunk) = receiver.recv().await {
+        match chunk {
+            StreamChunk::Delta(text) => {
+                reporter.delta(&text);
+                response.push_str(&text);
+            }
+            StreamChunk::Retry => {
+                response.clear();
+                reporter.retry();
+            }
+            StreamChunk::Error(e) => return Err(GcopError::Llm(e)),
+            StreamChunk::Done => break,
+        }
+    }
+    Ok(response)
+}
+
+/// Terminal UI: colored messages on stdout/stderr, spinners and prompts.
+///
+/// Respects quiet mode and non-interactive detection like the `ui` helpers.
+#[derive(Debug, Clone, Copy)]
+pub struct TerminalReporter {
+    colored: bool,
+}
+
+impl TerminalReporter {
+    /// Creates a terminal reporter.
+    pub fn new(colored: bool) -> Self {
+        Self { colored }
+    }
+}
+
+impl Reporter for TerminalReporter {
+    fn step(&self, step: &str, message: &str) {
+        super::step(step, message, self.colored);
+    }
+
+    fn info(&self, message: &str) {
+        if !super::is_quiet() {
+            println!("{}", super::info(message, self.colored));
+        }
+    }
+
+    fn success(&self, message: &str) {
+        super::success(message, self.colored);
+    }
+
+    fn warning(&self, message: &str) {
+        super::warning(message, self.colored);
+    }
+
+    fn error(&self, message: &str) {
+        super::error(message, self.colored);
+    }
+
+    fn preview(&self, title: &str, text: &str) {
+        if super::is_quiet() {
+            return;
+        }
+        println!("\n{}", super::info(title, self.colored));
+        if self.colored {
+            println!("{}", text.yellow());
+        } else {
+            println!("{}", text);
+        }
+    }
+
+    fn detail(&self, text: &str) {
+        if !super::is_quiet() {
+            println!("\n{}", text);
+        }
+    }
+
+    fn spinner(&self, message: &str) -> Option<Spinner> {
+        Some(Spinner::new(message, self.colored))
+    }
+
+    fn cancellable_spinner(&self, message: &str) -> Option<Spinner> {
+        let mut spinner = Spinner::new_with_cancel_hint(message, self.colored);
+        spinner.start_time_display();
+        Some(spinner)
+    }
+
+    fn confirm(&self, message: &str, default: bool) -> Result<bool> {
+        super::confirm(message, default)
+    }
+}
+
+/// Discards all status output; confirmations take their default.
+///
+/// Used for machine-readable formats and by the library API.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SilentReporter;
+
+impl Reporter for SilentReporter {
+    fn step(&self, _step: &str, _message: &str) {}
+
+    fn info(&self, _message: &str) {}
+
+    fn success(&self, _message: &str) {}
+
+    fn warning(&self, _message: &str) {}
+
+    fn error(&self, _message: &str) {}
+
+    fn preview(&self, _title: &str, _text: &str) {}
+
+    fn detail(&self, _text: &str) {}
+
+    fn spinner(&self, _message: &str) -> Option<Spinner> {
+        None
+    }
+
+    fn confirm(&self, _message: &str, default: bool) -> Result<bool> {
+        Ok(default)
+    }
+}
+
+/// Writes status output as newline-delimited JSON events.
+///
+/// Steps and spinners become `{"event":"progress",...}`, messages and detail
+/// blocks `{"event":"message","level":...}`, previews
+/// `{"event":"preview","title":...,"text":...}` and streamed text
+/// `{"event":"delta","text":...}`; a stream retry is announced as
+/// `{"event":"progress","stage":"retrying"}`. Confirmations take their default.
+pub struct JsonReporter<W: Write + Send> {
+    writer: Mutex<W>,
+}
+
+impl JsonReporter<std::io::Stdout> {
+    /// Creates a reporter writing to stdout.
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout())
+    }
+}
+
+impl<W: Write + Send> JsonReporter<W> {
+    /// Creates a reporter writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Writes one event line; write errors (closed pipe) are ignored.
+    pub fn emit(&self, event: &Value) {
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writeln!(writer, "{}", event);
+        let _ = writer.flush();
+    }
+
+    /// Consumes the reporter and returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn message(&self, level: &str, message: &str) {
+        self.emit(&json!({ "event": "message", "level": level, "message": message }));
+    }
+}
+
+impl<W: Write + Send> Reporter for JsonReporter<W> {
+    fn step(&self, step: &str, message: &str) {
+        self.emit(&json!({ "event": "progress", "step": step, "message": message }));
+    }
+
+    fn info(&self, message: &str) {
+        self.message("info", message);
+    }
+
+    fn success(&self, message: &str) {
+        self.message("success", message);
+    }
+
+    fn warning(&self, message: &str) {
+        self.message("warning", message);
+    }
+
+    fn error(&self, message: &str) {
+        self.message("error", message);
+    }
+
+    fn preview(&self, title: &str, text: &str) {
+        self.emit(&json!({ "event": "preview", "title": title, "text": text }));
+    }
+
+    fn detail(&self, text: &str) {
+        self.message("detail", text);
+    }
+
+    fn spinner(&self, message: &str) -> Option<Spinner> {
+        self.emit(&json!({ "event": "progress", "message": message }));
+        None
+    }
+
+    fn confirm(&self, _message: &str, default: bool) -> Result<bool> {
+        Ok(default)
+    }
+
+    fn delta(&self, text: &str) {
+        self.emit(&json!({ "event": "delta", "text": text }));
+    }
+
+    fn retry(&self) {
+        self.emit(&json!({ "event": "progress", "stage": "retrying" }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(reporter: JsonReporter<Vec<u8>>) -> Vec<Value> {
+        String::from_utf8(reporter.into_inner())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_json_reporter_events() {
+        let reporter = JsonReporter::new(Vec::new());
+        reporter.step("1/2", "Analyzing");
+        reporter.warning("Diff truncated");
+        assert!(reporter.spinner("Reviewing").is_none());
+        reporter.delta("fe");
+        reporter.preview("Generated message:", "feat: x");
+        reporter.detail("1 file changed");
+
+        let events = lines(reporter);
+        assert_eq!(
+            events,
+            vec![
+                json!({"event": "progress", "step": "1/2", "message": "Analyzing"}),
+                json!({"event": "message", "level": "warning", "message": "Diff truncated"}),
+                json!({"event": "progress", "message": "Reviewing"}),
+                json!({"event": "delta", "text": "fe"}),
+                json!({"event": "preview", "title": "Generated message:", "text": "feat: x"}),
+                json!({"event": "message", "level": "detail", "message": "1 file changed"}),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forward_stream_restarts_on_retry() {
+        let (tx, rx) = mpsc::channel(8);
+        for chunk in [
+            StreamChunk::Delta("fea".to_string()),
+            StreamChunk::Retry,
+            StreamChunk::Delta("feat: x".to_string()),
+            StreamChunk::Done,
+        ] {
+            tx.send(chunk).await.unwrap();
+        }
+        let reporter = JsonReporter::new(Vec::new());
+        let response = forward_stream(rx, &reporter).await.unwrap();
+        assert_eq!(response, "feat: x");
+
+        let events = lines(reporter);
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1], json!({"event": "progress", "stage": "retrying"}));
+    }
+
+    #[test]
+    fn test_non_interactive_reporters_take_confirm_default() {
+        assert!(SilentReporter.confirm("Apply?", true).unwrap());
+        assert!(
+            !JsonReporter::new(Vec::new())
+                .confirm("Apply?", false)
+                .unwrap()
+        );
+    }
+}
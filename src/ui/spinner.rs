@@ -44,6 +44,7 @@ pub struct Spinner {
     running: Arc<AtomicBool>,
     /// 上一次渲染的显示宽度，用于 finish/drop 时清除残留
     prev_width: Arc<AtomicUsize>,
+    /// `None` when not rendering (finished, or no interactive terminal)
     spin_task: Option<JoinHandle<()>>,
     time_task: Option<JoinHandle<()>>,
     #[allow(dead_code)]
@@ -56,20 +57,27 @@ impl Spinner {
         let msg = Arc::new(Mutex::new(message.to_string()));
         let running = Arc::new(AtomicBool::new(true));
         let prev_width = Arc::new(AtomicUsize::new(0));
-        let spin_task =
-            Self::spawn_render_loop(msg.clone(), running.clone(), prev_width.clone(), colored);
+        // Without a terminal the animation would only litter logs
+        let spin_task = Self::should_render().then(|| {
+            Self::spawn_render_loop(msg.clone(), running.clone(), prev_width.clone(), colored)
+        });
 
         Self {
             message: msg,
             base_message: message.to_string(),
             running,
             prev_width,
-            spin_task: Some(spin_task),
+            spin_task,
             time_task: None,
             colored,
         }
     }
 
+    /// Spinners need a terminal and are hidden in quiet mode
+    fn should_render() -> bool {
+        super::is_interactive() && !super::is_quiet()
+    }
+
     /// Create a spinner with cancellation prompt
     pub fn new_with_cancel_hint(message: &str, colored: bool) -> Self {
         use rust_i18n::t;
@@ -78,15 +86,17 @@ impl Spinner {
         let msg = Arc::new(Mutex::new(display_message));
         let running = Arc::new(AtomicBool::new(true));
         let prev_width = Arc::new(AtomicUsize::new(0));
-        let spin_task =
-            Self::spawn_render_loop(msg.clone(), running.clone(), prev_width.clone(), colored);
+        // Without a terminal the animation would only litter logs
+        let spin_task = Self::should_render().then(|| {
+            Self::spawn_render_loop(msg.clone(), running.clone(), prev_width.clone(), colored)
+        });
 
         Self {
             message: msg,
             base_message: message.to_string(),
             running,
             prev_width,
-            spin_task: Some(spin_task),
+            spin_task,
             time_task: None,
             colored,
         }
@@ -178,9 +188,13 @@ impl Spinner {
 
     /// 清除 spinner 残留（考虑 reflow）
     fn clear_output(&self) {
+        let old_w = self.prev_width.load(Ordering::SeqCst);
+        // 从未渲染过（例如非交互模式）时无需清除
+        if old_w == 0 {
+            return;
+        }
         let mut stderr = io::stderr();
         let term_width = console::Term::stderr().size().1 as usize;
-        let old_w = self.prev_width.load(Ordering::SeqCst);
         let old_lines = physical_lines(old_w, term_width);
         move_up_and_clear(&mut stderr, old_lines.saturating_sub(1));
         let _ = stderr.flush();
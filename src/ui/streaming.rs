@@ -9,6 +9,7 @@ use tokio::sync::mpsc;
 
 use crate::error::{GcopError, Result};
 use crate::llm::StreamChunk;
+use crate::llm::review_stream::{ReviewStreamEvent, ReviewStreamParser};
 
 /// Streaming text output
 pub struct StreamingOutput {
@@ -103,6 +104,134 @@ impl StreamingOutput {
     }
 }
 
+/// Streaming review output
+///
+/// Prints the review summary as it streams in, followed by a running count of
+/// issues. The caller parses the returned raw response for the final report.
+pub struct ReviewStreamingOutput {
+    buffer: String,
+    colored: bool,
+    parser: ReviewStreamParser,
+    summary: String,
+    summary_open: bool,
+    issues: usize,
+}
+
+impl ReviewStreamingOutput {
+    /// Creates a review stream renderer with optional colored output.
+    pub fn new(colored: bool) -> Self {
+        Self {
+            buffer: String::new(),
+            colored,
+            parser: ReviewStreamParser::new(),
+            summary: String::new(),
+            summary_open: false,
+            issues: 0,
+        }
+    }
+
+    /// Summary text shown while streaming (empty if none was recognized).
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// Process the review stream, rendering progress in real time
+    ///
+    /// Return the complete raw response text
+    pub async fn process(&mut self, mut receiver: mpsc::Receiver<StreamChunk>) -> Result<String> {
+        while let Some(chunk) = receiver.recv().await {
+            match chunk {
+                StreamChunk::Delta(text) => {
+                    self.buffer.push_str(&text);
+                    for event in self.parser.feed(&text) {
+                        self.render(event);
+                    }
+                    io::stdout().flush().ok();
+                }
+                StreamChunk::Done => break,
+                StreamChunk::Error(e) => {
+                    self.finish_line();
+                    if self.colored {
+                        eprintln!(
+                            "{} {}",
+                            "✗".red(),
+                            rust_i18n::t!("stream.error", error = e.as_str()).red()
+                        );
+                    } else {
+                        eprintln!("✗ {}", rust_i18n::t!("stream.error", error = e.as_str()));
+                    }
+                    return Err(GcopError::Llm(e));
+                }
+                StreamChunk::Retry => {
+                    // Partial output stays on screen; start a fresh section below it
+                    self.finish_line();
+                    println!();
+                    self.buffer.clear();
+                    self.summary.clear();
+                    self.parser = ReviewStreamParser::new();
+                    self.issues = 0;
+                }
+            }
+        }
+
+        self.finish_line();
+        Ok(self.buffer.clone())
+    }
+
+    fn render(&mut self, event: ReviewStreamEvent) {
+        match event {
+            ReviewStreamEvent::SummaryDelta(text) => {
+                if !self.summary_open && self.summary.is_empty() {
+                    self.clear_status();
+                    println!("{}", rust_i18n::t!("review.summary_title"));
+                }
+                self.summary_open = true;
+                self.summary.push_str(&text);
+                if self.colored {
+                    print!("{}", text.yellow());
+                } else {
+                    print!("{}", text);
+                }
+            }
+            ReviewStreamEvent::SummaryDone => {
+                if self.summary_open {
+                    println!("\n");
+                }
+                self.summary_open = false;
+            }
+            ReviewStreamEvent::Issue(_) => {
+                self.issues += 1;
+                if self.summary_open {
+                    return;
+                }
+                let status = rust_i18n::t!("review.stream_issues", count = self.issues);
+                if self.colored {
+                    print!("\r\x1b[2K{}", status.dimmed());
+                } else {
+                    print!("\r\x1b[2K{}", status);
+                }
+            }
+        }
+    }
+
+    /// Erase the running issue count line, if any.
+    fn clear_status(&self) {
+        if self.issues > 0 {
+            print!("\r\x1b[2K");
+        }
+    }
+
+    /// End an unterminated summary line and remove the status line.
+    fn finish_line(&mut self) {
+        if self.summary_open {
+            println!();
+            self.summary_open = false;
+        }
+        self.clear_status();
+        io::stdout().flush().ok();
+    }
+}
+
 /// Calculate how many terminal lines to erase for a raw streamed buffer.
 ///
 /// Each `\n` in the buffer produced a visible line break, and `process()`
@@ -0,0 +1,95 @@
+//! Interactive terminal detection and quiet mode.
+//!
+//! A run is interactive when both stdin and stdout are terminals. Hooks, CI
+//! jobs and pipes are not: there, colors and spinners are turned off, menus
+//! and confirmations are skipped and `commit` follows
+//! `[commit].non_interactive`. `GCOP_INTERACTIVE=0|1` overrides the detection.
+//!
+//! Quiet mode (`--quiet`) additionally hides spinners, progress, status
+//! messages and previews; errors and the output a command was asked for
+//! (`--dry-run` message, JSON, reports) are still printed. The library API
+//! turns it on for its own calls only, with [`quiet_scope`].
+
+use std::future::Future;
+use std::io::IsTerminal;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Environment variable overriding terminal detection (`0`/`false` or `1`/`true`).
+pub const INTERACTIVE_VAR: &str = "GCOP_INTERACTIVE";
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+tokio::task_local! {
+    static SCOPED_QUIET: bool;
+}
+
+static INTERACTIVE: LazyLock<bool> = LazyLock::new(|| {
+    parse_override(std::env::var(INTERACTIVE_VAR).ok().as_deref())
+        .unwrap_or_else(|| std::io::stdin().is_terminal() && std::io::stdout().is_terminal())
+});
+
+/// Whether the user can answer prompts and see terminal effects.
+pub fn is_interactive() -> bool {
+    *INTERACTIVE
+}
+
+/// Enables or disables quiet mode for this process (`--quiet`).
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether non-error status output is suppressed.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed) || SCOPED_QUIET.try_with(|quiet| *quiet).unwrap_or(false)
+}
+
+/// Runs `future` with quiet mode set to `quiet` for the current task only,
+/// leaving the process-wide setting alone. Spawned tasks do not inherit it;
+/// pass [`is_quiet`] on to them.
+pub async fn quiet_scope<F: Future>(quiet: bool, future: F) -> F::Output {
+    SCOPED_QUIET.scope(quiet, future).await
+}
+
+/// Synchronous variant of [`quiet_scope`].
+pub fn quiet_scope_sync<R>(quiet: bool, f: impl FnOnce() -> R) -> R {
+    SCOPED_QUIET.sync_scope(quiet, f)
+}
+
+/// Whether colors are kept without a terminal (`CLICOLOR_FORCE` set and not `0`).
+pub fn color_forced() -> bool {
+    std::env::var("CLICOLOR_FORCE").is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+fn parse_override(value: Option<&str>) -> Option<bool> {
+    match value?.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" => Some(true),
+        "0" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(parse_override(Some("1")), Some(true));
+        assert_eq!(parse_override(Some("FALSE")), Some(false));
+        // 未设置或无法识别时回退到终端检测
+        assert_eq!(parse_override(Some("auto")), None);
+        assert_eq!(parse_override(None), None);
+    }
+
+    #[tokio::test]
+    async fn test_quiet_scope_is_task_local() {
+        assert!(!is_quiet());
+        assert!(quiet_scope(true, async { is_quiet() }).await);
+        assert!(quiet_scope_sync(true, is_quiet));
+        // 作用域结束后不影响进程级状态
+        assert!(!is_quiet());
+        let other = tokio::spawn(quiet_scope(false, async { is_quiet() }));
+        assert!(!other.await.unwrap());
+    }
+}
@@ -1,6 +1,9 @@
 //! Workspace configuration file detection and parsing
 
 use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
 
 use crate::error::Result;
 
@@ -43,6 +46,36 @@ pub fn detect_workspace(root: &Path) -> Result<Option<WorkspaceInfo>> {
         members.extend(lerna_members);
     }
 
+    // .gitmodules
+    if let Some(submodule_members) = detect_git_submodules(root)? {
+        workspace_types.push(WorkspaceType::Submodule);
+        members.extend(submodule_members);
+    }
+
+    // settings.gradle[.kts] include(...)
+    if let Some(gradle_members) = detect_gradle_workspace(root)? {
+        workspace_types.push(WorkspaceType::Gradle);
+        members.extend(gradle_members);
+    }
+
+    // pom.xml <modules>
+    if let Some(maven_members) = detect_maven_workspace(root)? {
+        workspace_types.push(WorkspaceType::Maven);
+        members.extend(maven_members);
+    }
+
+    // WORKSPACE / MODULE.bazel + BUILD files
+    if let Some(bazel_members) = detect_bazel_workspace(root) {
+        workspace_types.push(WorkspaceType::Bazel);
+        members.extend(bazel_members);
+    }
+
+    // go.work use directives
+    if let Some(go_members) = detect_go_workspace(root)? {
+        workspace_types.push(WorkspaceType::Go);
+        members.extend(go_members);
+    }
+
     if workspace_types.is_empty() {
         return Ok(None);
     }
@@ -236,6 +269,259 @@ fn detect_lerna_workspace(root: &Path) -> Result<Option<Vec<WorkspaceMember>>> {
     }
 }
 
+/// Detect git submodule paths from .gitmodules
+fn detect_git_submodules(root: &Path) -> Result<Option<Vec<WorkspaceMember>>> {
+    let gitmodules_path = root.join(".gitmodules");
+    if !gitmodules_path.exists() {
+        return Ok(None);
+    }
+
+    let config = match git2::Config::open(&gitmodules_path) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!("Failed to parse .gitmodules: {}", e);
+            return Ok(None);
+        }
+    };
+
+    let mut members = Vec::new();
+    let mut entries = config.entries(Some(r"submodule\..*\.path"))?;
+    while let Some(entry) = entries.next() {
+        if let Some(path) = entry?.value() {
+            let path = path.trim_matches('/');
+            members.push(WorkspaceMember {
+                prefix: glob_pattern_to_prefix(path),
+                pattern: path.to_string(),
+            });
+        }
+    }
+
+    if members.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(members))
+    }
+}
+
+/// Member for a directory path relative to the root (`None` outside of it)
+fn directory_member(path: &str) -> Option<WorkspaceMember> {
+    let path = path.trim().trim_start_matches("./").trim_matches('/');
+    if path.is_empty() || path == "." || path.split('/').any(|part| part == "..") {
+        return None;
+    }
+    Some(WorkspaceMember {
+        prefix: glob_pattern_to_prefix(path),
+        pattern: path.to_string(),
+    })
+}
+
+static GRADLE_INCLUDE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\binclude\b\s*(?:\(([^)]*)\)|([^\n]*))").expect("valid Gradle include regex")
+});
+
+static GRADLE_PROJECT_DIR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"project\(\s*["']([^"']+)["']\s*\)\.projectDir\s*=\s*(?:file|new\s+File)\(\s*(?:(?:rootDir|rootProject\.projectDir|settingsDir)\s*,\s*)?["']([^"']+)["']"#,
+    )
+    .expect("valid Gradle projectDir regex")
+});
+
+static QUOTED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"["']([^"']+)["']"#).expect("valid quoted string regex"));
+
+/// Detect Gradle subprojects from `include` in settings.gradle / settings.gradle.kts
+///
+/// Project paths map to directories (`:libs:core` → `libs/core`) unless a
+/// `project(":x").projectDir = file("...")` line relocates them.
+fn detect_gradle_workspace(root: &Path) -> Result<Option<Vec<WorkspaceMember>>> {
+    let Some(settings_path) = ["settings.gradle.kts", "settings.gradle"]
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.exists())
+    else {
+        return Ok(None);
+    };
+
+    let content = strip_line_comments(&std::fs::read_to_string(&settings_path)?, "//");
+    let relocated: std::collections::HashMap<String, String> = GRADLE_PROJECT_DIR
+        .captures_iter(&content)
+        .map(|caps| (gradle_project_dir(&caps[1]), caps[2].to_string()))
+        .collect();
+
+    let members: Vec<WorkspaceMember> = GRADLE_INCLUDE
+        .captures_iter(&content)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .flat_map(|args| QUOTED.captures_iter(args.as_str()))
+        .filter_map(|caps| {
+            let dir = gradle_project_dir(&caps[1]);
+            directory_member(relocated.get(&dir).unwrap_or(&dir))
+        })
+        .collect();
+
+    if members.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(members))
+    }
+}
+
+/// Default directory of a Gradle project path (`:libs:core` → `libs/core`)
+fn gradle_project_dir(project_path: &str) -> String {
+    project_path.trim_start_matches(':').replace(':', "/")
+}
+
+static MAVEN_MODULE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<module>\s*([^<]+?)\s*</module>").expect("valid Maven module regex")
+});
+
+static XML_COMMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<!--.*?-->").expect("valid XML comment regex"));
+
+/// Maximum depth of nested Maven aggregator POMs followed.
+const MAVEN_MAX_DEPTH: usize = 4;
+
+/// Detect Maven modules from `<modules>` in pom.xml, following nested aggregators
+fn detect_maven_workspace(root: &Path) -> Result<Option<Vec<WorkspaceMember>>> {
+    if !root.join("pom.xml").exists() {
+        return Ok(None);
+    }
+
+    let mut members = Vec::new();
+    let mut pending = vec![(String::new(), 0usize)];
+    while let Some((dir, depth)) = pending.pop() {
+        let pom_path = root.join(&dir).join("pom.xml");
+        let Ok(content) = std::fs::read_to_string(&pom_path) else {
+            continue;
+        };
+        let content = XML_COMMENT.replace_all(&content, "");
+        for caps in MAVEN_MODULE.captures_iter(&content) {
+            // A module may point at its POM file instead of its directory
+            let module = caps[1].trim_end_matches(".xml");
+            let module = match module.rsplit_once('/') {
+                Some((parent, file)) if file.starts_with("pom") => parent,
+                _ => module,
+            };
+            let path = if dir.is_empty() {
+                module.to_string()
+            } else {
+                format!("{}/{}", dir, module)
+            };
+            if let Some(member) = directory_member(&path) {
+                if depth + 1 < MAVEN_MAX_DEPTH {
+                    pending.push((member.pattern.clone(), depth + 1));
+                }
+                members.push(member);
+            }
+        }
+    }
+
+    if members.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(members))
+    }
+}
+
+/// Files marking the root of a Bazel workspace.
+const BAZEL_ROOT_FILES: &[&str] = &["MODULE.bazel", "WORKSPACE", "WORKSPACE.bazel"];
+
+/// Files marking a Bazel package.
+const BAZEL_BUILD_FILES: &[&str] = &["BUILD", "BUILD.bazel"];
+
+/// Maximum number of directory entries walked when looking for BUILD files.
+const BAZEL_MAX_ENTRIES: usize = 50_000;
+
+/// Detect Bazel packages: directories below the root that contain a BUILD file
+///
+/// Ignored and hidden paths are skipped, as are the `bazel-*` output symlinks
+/// (links are not followed).
+fn detect_bazel_workspace(root: &Path) -> Option<Vec<WorkspaceMember>> {
+    if !BAZEL_ROOT_FILES.iter().any(|name| root.join(name).exists()) {
+        return None;
+    }
+
+    let mut members = Vec::new();
+    let walker = ignore::WalkBuilder::new(root).require_git(false).build();
+    for entry in walker.take(BAZEL_MAX_ENTRIES) {
+        let Ok(entry) = entry else { continue };
+        let is_build_file = entry.file_type().is_some_and(|kind| kind.is_file())
+            && BAZEL_BUILD_FILES
+                .iter()
+                .any(|name| entry.file_name() == std::ffi::OsStr::new(name));
+        if !is_build_file {
+            continue;
+        }
+        let Some(dir) = entry
+            .path()
+            .parent()
+            .and_then(|dir| dir.strip_prefix(root).ok())
+        else {
+            continue;
+        };
+        let dir = crate::git::to_git_path(dir);
+        members.extend(directory_member(&dir));
+    }
+
+    if members.is_empty() {
+        None
+    } else {
+        Some(members)
+    }
+}
+
+static GO_USE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^\s*use\s*(?:\(([^)]*)\)|(\S+))").expect("valid go.work use regex")
+});
+
+/// Detect Go workspace modules from `use` directives in go.work
+fn detect_go_workspace(root: &Path) -> Result<Option<Vec<WorkspaceMember>>> {
+    let go_work_path = root.join("go.work");
+    if !go_work_path.exists() {
+        return Ok(None);
+    }
+
+    let content = strip_line_comments(&std::fs::read_to_string(&go_work_path)?, "//");
+    let members: Vec<WorkspaceMember> = GO_USE
+        .captures_iter(&content)
+        .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .flat_map(|paths| {
+            paths
+                .as_str()
+                .split_whitespace()
+                .map(|path| path.trim_matches('"').to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter_map(|path| directory_member(&path))
+        .collect();
+
+    if members.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(members))
+    }
+}
+
+/// Remove `marker` line comments (not inside quotes; good enough for build files)
+fn strip_line_comments(content: &str, marker: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            let mut quote = None;
+            for (i, c) in line.char_indices() {
+                match quote {
+                    Some(q) if c == q => quote = None,
+                    Some(_) => {}
+                    None if c == '"' || c == '\'' => quote = Some(c),
+                    None if line[i..].starts_with(marker) => return &line[..i],
+                    None => {}
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -392,4 +678,151 @@ version = "0.1.0"
             .count();
         assert_eq!(count, 1);
     }
+
+    #[test]
+    fn test_detect_git_submodules() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".gitmodules"),
+            "[submodule \"foo\"]\n\tpath = libs/foo\n\turl = https://example.com/foo.git\n\
+             [submodule \"bar\"]\n\tpath = vendor/bar\n\turl = ../bar.git\n",
+        )
+        .unwrap();
+
+        let info = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(info.workspace_types, vec![WorkspaceType::Submodule]);
+        let prefixes: Vec<&str> = info.members.iter().map(|m| m.prefix.as_str()).collect();
+        assert_eq!(prefixes, vec!["libs/foo/", "vendor/bar/"]);
+
+        let scope = crate::workspace::scope::infer_scope(
+            &["libs/foo".to_string()],
+            &info,
+            None,
+            &Default::default(),
+        );
+        assert_eq!(scope.suggested_scope.as_deref(), Some("foo"));
+    }
+
+    fn prefixes(members: &[WorkspaceMember]) -> Vec<&str> {
+        members.iter().map(|m| m.prefix.as_str()).collect()
+    }
+
+    #[test]
+    fn test_detect_gradle_workspace() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("settings.gradle.kts"),
+            r#"
+rootProject.name = "shop"
+include(":app", ":libs:core")
+include(
+    ":libs:ui", // UI components
+)
+// include(":disabled")
+includeBuild("build-logic")
+project(":legacy").projectDir = file("old/legacy-module")
+include(":legacy")
+"#,
+        )
+        .unwrap();
+
+        let result = detect_gradle_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(
+            prefixes(&result),
+            vec!["app/", "libs/core/", "libs/ui/", "old/legacy-module/"]
+        );
+    }
+
+    #[test]
+    fn test_detect_gradle_groovy_include() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("settings.gradle"),
+            "include 'api', ':service'\n",
+        )
+        .unwrap();
+
+        let result = detect_gradle_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(prefixes(&result), vec!["api/", "service/"]);
+    }
+
+    #[test]
+    fn test_detect_maven_nested_modules() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("pom.xml"),
+            "<project><modules>\n  <module>core</module>\n  <!-- <module>old</module> -->\n  \
+             <module>services</module>\n  <module>../outside</module>\n</modules></project>",
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("services")).unwrap();
+        std::fs::write(
+            dir.path().join("services/pom.xml"),
+            "<project><modules><module>billing/pom.xml</module></modules></project>",
+        )
+        .unwrap();
+
+        let mut result = detect_maven_workspace(dir.path()).unwrap().unwrap();
+        result.sort_by(|a, b| a.prefix.cmp(&b.prefix));
+        assert_eq!(
+            prefixes(&result),
+            vec!["core/", "services/", "services/billing/"]
+        );
+    }
+
+    #[test]
+    fn test_detect_bazel_packages() {
+        let dir = tempdir().unwrap();
+        let write = |path: &str| {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        };
+        write("MODULE.bazel");
+        write("BUILD.bazel");
+        write("services/auth/BUILD");
+        write("services/auth/internal/BUILD.bazel");
+        write("tools/lint.sh");
+
+        let info = detect_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(info.workspace_types, vec![WorkspaceType::Bazel]);
+        assert_eq!(
+            prefixes(&info.members),
+            vec!["services/auth/", "services/auth/internal/"]
+        );
+
+        // 嵌套包按最具体的成员推断 scope
+        let scope = crate::workspace::scope::infer_scope(
+            &["services/auth/internal/token.go".to_string()],
+            &info,
+            None,
+            &Default::default(),
+        );
+        assert_eq!(scope.suggested_scope.as_deref(), Some("internal"));
+    }
+
+    #[test]
+    fn test_bazel_requires_workspace_file() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("pkg")).unwrap();
+        std::fs::write(dir.path().join("pkg/BUILD"), "").unwrap();
+
+        assert!(detect_bazel_workspace(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_detect_go_workspace() {
+        let dir = tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("go.work"),
+            "go 1.22\n\nuse (\n\t.\n\t./services/api // HTTP API\n\t./libs/auth\n)\n\nuse ./tools\n",
+        )
+        .unwrap();
+
+        let result = detect_go_workspace(dir.path()).unwrap().unwrap();
+        assert_eq!(
+            prefixes(&result),
+            vec!["services/api/", "libs/auth/", "tools/"]
+        );
+    }
 }
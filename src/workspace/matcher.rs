@@ -7,34 +7,58 @@ use super::WorkspaceMember;
 /// Match a single file to the package it belongs to
 ///
 /// Returns the package path (such as `"packages/core"`), or None if there is no match.
+/// When members are nested (Bazel, Maven), the most specific one wins.
 pub fn match_file_to_package(file_path: &str, members: &[WorkspaceMember]) -> Option<String> {
-    for member in members {
-        if member.prefix.is_empty() {
-            continue;
-        }
-        if file_path.starts_with(&member.prefix) {
-            let rest = &file_path[member.prefix.len()..];
-            let is_glob = member.pattern.contains('*') || member.pattern.contains('?');
-
-            if is_glob {
-                // Glob pattern (such as packages/*): there must be subdirectories in rest
-                // packages/core/src/lib.rs → rest = "core/src/lib.rs" → package "packages/core"
-                // packages/README.md → rest = "README.md" → does not match (not in sub-package)
-                if let Some(slash_pos) = rest.find('/') {
-                    let package_dir = &rest[..slash_pos];
-                    if !package_dir.is_empty() {
-                        let prefix_base = member.prefix.trim_end_matches('/');
-                        return Some(format!("{prefix_base}/{package_dir}"));
-                    }
-                }
-            } else {
-                // Exact path (e.g. apps/cli): the file belongs directly to this package
-                let prefix_base = member.prefix.trim_end_matches('/');
-                return Some(prefix_base.to_string());
-            }
+    match_package_chain(file_path, members).pop()
+}
+
+/// All packages containing a file, outermost first
+///
+/// With members `packages/*` and `packages/ui/components/*`,
+/// `packages/ui/components/button/index.ts` yields
+/// `["packages/ui", "packages/ui/components/button"]`.
+pub fn match_package_chain(file_path: &str, members: &[WorkspaceMember]) -> Vec<String> {
+    let mut chain: Vec<String> = members
+        .iter()
+        .filter_map(|member| match_member(file_path, member))
+        .collect();
+    chain.sort_by_key(|pkg| pkg.len());
+    chain.dedup();
+    chain
+}
+
+/// Packages containing a package directory (itself included), outermost first
+pub fn package_chain(package: &str, members: &[WorkspaceMember]) -> Vec<String> {
+    // Glob members only match paths below the package directory
+    match_package_chain(&format!("{package}/"), members)
+}
+
+/// Package path of `file_path` within a single member, if it belongs to it
+fn match_member(file_path: &str, member: &WorkspaceMember) -> Option<String> {
+    if member.prefix.is_empty() {
+        return None;
+    }
+    // The package directory itself changed (e.g. a submodule pointer)
+    let is_glob = member.pattern.contains('*') || member.pattern.contains('?');
+    if !is_glob && file_path == member.prefix.trim_end_matches('/') {
+        return Some(file_path.to_string());
+    }
+    let rest = file_path.strip_prefix(&member.prefix)?;
+    let prefix_base = member.prefix.trim_end_matches('/');
+
+    if is_glob {
+        // Glob pattern (such as packages/*): there must be subdirectories in rest
+        // packages/core/src/lib.rs → rest = "core/src/lib.rs" → package "packages/core"
+        // packages/README.md → rest = "README.md" → does not match (not in sub-package)
+        let (package_dir, _) = rest.split_once('/')?;
+        if package_dir.is_empty() {
+            return None;
         }
+        Some(format!("{prefix_base}/{package_dir}"))
+    } else {
+        // Exact path (e.g. apps/cli): the file belongs directly to this package
+        Some(prefix_base.to_string())
     }
-    None
 }
 
 /// Map all changed files to corresponding packages
@@ -127,6 +151,124 @@ mod tests {
             match_file_to_package("apps/cli/main.rs", &members),
             Some("apps/cli".to_string())
         );
+        // The directory entry itself (submodule pointer)
+        assert_eq!(
+            match_file_to_package("apps/cli", &members),
+            Some("apps/cli".to_string())
+        );
+        assert_eq!(match_file_to_package("apps/client", &members), None);
+    }
+
+    #[test]
+    fn test_match_nested_member_wins() {
+        // Bazel/Maven 的嵌套包：选择最具体的成员
+        let members = vec![
+            WorkspaceMember {
+                pattern: "services".into(),
+                prefix: "services/".into(),
+            },
+            WorkspaceMember {
+                pattern: "services/auth".into(),
+                prefix: "services/auth/".into(),
+            },
+        ];
+        assert_eq!(
+            match_file_to_package("services/auth/BUILD", &members),
+            Some("services/auth".to_string())
+        );
+        assert_eq!(
+            match_file_to_package("services/README.md", &members),
+            Some("services".to_string())
+        );
+    }
+
+    fn nested_members() -> Vec<WorkspaceMember> {
+        vec![
+            WorkspaceMember {
+                pattern: "packages/*".into(),
+                prefix: "packages/".into(),
+            },
+            WorkspaceMember {
+                pattern: "packages/ui/components/*".into(),
+                prefix: "packages/ui/components/".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_match_overlapping_glob_members() {
+        let members = nested_members();
+        assert_eq!(
+            match_file_to_package("packages/ui/components/button/index.ts", &members),
+            Some("packages/ui/components/button".to_string())
+        );
+        // 不在嵌套成员内的文件仍归属外层包
+        assert_eq!(
+            match_file_to_package("packages/ui/src/theme.ts", &members),
+            Some("packages/ui".to_string())
+        );
+        assert_eq!(
+            match_file_to_package("packages/ui/components/README.md", &members),
+            Some("packages/ui".to_string())
+        );
+    }
+
+    #[test]
+    fn test_match_package_chain_outermost_first() {
+        let members = nested_members();
+        assert_eq!(
+            match_package_chain("packages/ui/components/button/index.ts", &members),
+            vec!["packages/ui", "packages/ui/components/button"]
+        );
+        assert_eq!(
+            match_package_chain("packages/core/lib.ts", &members),
+            vec!["packages/core"]
+        );
+        assert!(match_package_chain("README.md", &members).is_empty());
+    }
+
+    #[test]
+    fn test_match_package_chain_order_independent() {
+        // 成员声明顺序不影响结果
+        let mut members = nested_members();
+        members.reverse();
+        assert_eq!(
+            match_package_chain("packages/ui/components/button/index.ts", &members),
+            vec!["packages/ui", "packages/ui/components/button"]
+        );
+    }
+
+    #[test]
+    fn test_match_sibling_prefix_is_not_nested() {
+        // packages/ui 不是 packages/ui-kit 的前缀包
+        let members = vec![
+            WorkspaceMember {
+                pattern: "packages/ui".into(),
+                prefix: "packages/ui/".into(),
+            },
+            WorkspaceMember {
+                pattern: "packages/ui-kit".into(),
+                prefix: "packages/ui-kit/".into(),
+            },
+        ];
+        assert_eq!(
+            match_package_chain("packages/ui-kit/src/index.ts", &members),
+            vec!["packages/ui-kit"]
+        );
+        assert_eq!(
+            match_package_chain("packages/ui/src/index.ts", &members),
+            vec!["packages/ui"]
+        );
+    }
+
+    #[test]
+    fn test_package_chain_of_package_dir() {
+        let members = nested_members();
+        assert_eq!(
+            package_chain("packages/ui/components/button", &members),
+            vec!["packages/ui", "packages/ui/components/button"]
+        );
+        assert_eq!(package_chain("packages/ui", &members), vec!["packages/ui"]);
     }
 
     #[test]
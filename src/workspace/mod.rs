@@ -1,6 +1,7 @@
 //! Monorepo workspace detection and scope inference
 //!
-//! Automatically detect monorepo structures such as Cargo workspace, pnpm, npm/yarn, Lerna, etc.
+//! Automatically detect monorepo structures such as Cargo workspace, pnpm, npm/yarn, Lerna,
+//! Gradle, Maven, Bazel and Go workspaces.
 //! Map changed files to corresponding packages and infer commit scope.
 
 pub mod detector;
@@ -27,6 +28,16 @@ pub enum WorkspaceType {
     Nx,
     /// Turborepo workspace (`turbo.json`).
     Turbo,
+    /// Git submodules (`.gitmodules`), each submodule is a package.
+    Submodule,
+    /// Gradle multi-project build (`include` in `settings.gradle[.kts]`).
+    Gradle,
+    /// Maven multi-module build (`<modules>` in `pom.xml`).
+    Maven,
+    /// Bazel workspace, each directory with a `BUILD` file is a package.
+    Bazel,
+    /// Go workspace (`use` directives in `go.work`).
+    Go,
 }
 
 impl std::fmt::Display for WorkspaceType {
@@ -38,6 +49,11 @@ impl std::fmt::Display for WorkspaceType {
             Self::Lerna => write!(f, "lerna"),
             Self::Nx => write!(f, "nx"),
             Self::Turbo => write!(f, "turbo"),
+            Self::Submodule => write!(f, "submodule"),
+            Self::Gradle => write!(f, "gradle"),
+            Self::Maven => write!(f, "maven"),
+            Self::Bazel => write!(f, "bazel"),
+            Self::Go => write!(f, "go"),
         }
     }
 }
@@ -1,7 +1,38 @@
 //! Commit scope inference
 
-use super::matcher::map_files_to_packages;
-use super::{PackageScope, WorkspaceInfo};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use super::matcher::{map_files_to_packages, package_chain};
+use super::{PackageScope, WorkspaceInfo, WorkspaceMember};
+use crate::convention::ConventionalMessage;
+
+/// File stems that name an entry point rather than a module
+const ENTRY_STEMS: &[&str] = &["lib", "main", "mod", "index", "__init__", "__main__"];
+
+/// Top-level directories that never yield a module scope
+const IGNORED_DIRS: &[&str] = &["tests", "test", "benches", "examples", "docs", "scripts"];
+
+/// Maximum source file size read when looking for module declarations
+const MAX_SYMBOL_FILE_SIZE: u64 = 256 * 1024;
+
+/// How package scopes are named when members are nested
+#[derive(Debug, Clone, Copy)]
+pub struct ScopeStyle<'a> {
+    /// Number of package levels kept, innermost first (at least 1)
+    pub depth: usize,
+    /// Separator between levels (`ui/button`, `ui-button`)
+    pub joiner: &'a str,
+}
+
+impl Default for ScopeStyle<'_> {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            joiner: "/",
+        }
+    }
+}
 
 /// Infer commit scope from changed files and workspace information
 ///
@@ -10,10 +41,14 @@ use super::{PackageScope, WorkspaceInfo};
 /// - 1 package → scope = package short name (last segment of path)
 /// - 2-3 packages → scope = comma separated short names
 /// - 4+ packages or root files only → None
+///
+/// Short names of nested packages include up to `style.depth` enclosing
+/// package names, joined by `style.joiner`.
 pub fn infer_scope(
     files_changed: &[String],
     workspace: &WorkspaceInfo,
     manual_scope: Option<&str>,
+    style: &ScopeStyle,
 ) -> PackageScope {
     if let Some(scope) = manual_scope {
         return PackageScope {
@@ -27,16 +62,10 @@ pub fn infer_scope(
     let packages: Vec<String> = package_files.keys().cloned().collect();
 
     let suggested_scope = match packages.len() {
-        0 => None,
-        1 => {
-            let pkg = &packages[0];
-            let short_name = pkg.rsplit('/').next().unwrap_or(pkg);
-            Some(short_name.to_string())
-        }
-        2..=3 => {
-            let short_names: Vec<&str> = packages
+        1..=3 => {
+            let short_names: Vec<String> = packages
                 .iter()
-                .map(|p| p.rsplit('/').next().unwrap_or(p.as_str()))
+                .map(|p| package_scope_name(p, &workspace.members, style))
                 .collect();
             Some(short_names.join(","))
         }
@@ -50,6 +79,150 @@ pub fn infer_scope(
     }
 }
 
+/// Scope name of a package: its short name, prefixed by enclosing packages up to `style.depth`
+///
+/// `packages/ui/components/button` inside `packages/ui` → `button` (depth 1), `ui/button` (depth 2)
+fn package_scope_name(package: &str, members: &[WorkspaceMember], style: &ScopeStyle) -> String {
+    let chain = package_chain(package, members);
+    let names: Vec<&str> = chain
+        .iter()
+        .map(|p| p.rsplit('/').next().unwrap_or(p.as_str()))
+        .collect();
+    if names.is_empty() {
+        return package.rsplit('/').next().unwrap_or(package).to_string();
+    }
+    let start = names.len().saturating_sub(style.depth.max(1));
+    names[start..].join(style.joiner)
+}
+
+/// Scopes used by earlier conventional commits, most frequent first
+///
+/// `subjects` are commit subjects, newest first; ties keep the most recent scope first.
+/// Subjects without a conventional scope are ignored. Returns at most `limit` scopes.
+pub fn history_scopes<'a>(
+    subjects: impl IntoIterator<Item = &'a str>,
+    limit: usize,
+) -> Vec<String> {
+    // scope → (count, first position)
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+    for (position, subject) in subjects.into_iter().enumerate() {
+        let Some(scope) = ConventionalMessage::parse(subject).and_then(|parsed| parsed.scope)
+        else {
+            continue;
+        };
+        counts.entry(scope).or_insert((0, position)).0 += 1;
+    }
+
+    let mut scopes: Vec<(String, (usize, usize))> = counts.into_iter().collect();
+    scopes.sort_by_key(|&(_, (count, position))| (std::cmp::Reverse(count), position));
+    scopes
+        .into_iter()
+        .take(limit)
+        .map(|(scope, _)| scope)
+        .collect()
+}
+
+/// Infer commit scope from module structure of changed source files
+///
+/// Works without a monorepo layout. Supported languages:
+/// - Rust: module path under `src/` (`src/auth/mod.rs`, `src/parser.rs`)
+/// - TypeScript/JavaScript: top-level `namespace X` / `declare module "x"`,
+///   otherwise the module directory under `src/`
+/// - Python: sub-package below the top-level package (directories with `__init__.py`)
+///
+/// rule (same as package scopes):
+/// - 1 module → scope = module name
+/// - 2-3 modules → scope = comma separated names
+/// - 4+ modules or no recognizable source files → None
+pub fn infer_symbol_scope(files_changed: &[String], root: &Path) -> Option<String> {
+    let modules: BTreeSet<String> = files_changed
+        .iter()
+        .filter_map(|file| module_scope_for_file(file, root))
+        .collect();
+
+    match modules.len() {
+        1..=3 => Some(modules.into_iter().collect::<Vec<_>>().join(",")),
+        _ => None,
+    }
+}
+
+/// Module scope for a single changed file (None for unsupported or entry files)
+fn module_scope_for_file(file: &str, root: &Path) -> Option<String> {
+    let ext = Path::new(file).extension()?.to_str()?;
+    match ext {
+        "rs" => path_module_scope(file, false),
+        "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => read_source(root, file)
+            .and_then(|content| ts_declared_module(&content))
+            .or_else(|| path_module_scope(file, false)),
+        "py" => {
+            let top_is_package = file
+                .split('/')
+                .next()
+                .is_some_and(|top| root.join(top).join("__init__.py").is_file());
+            path_module_scope(file, top_is_package)
+        }
+        _ => None,
+    }
+}
+
+/// Derive a module name from the file path.
+///
+/// Skips a leading `src/` (and the top-level package when `skip_package` is set),
+/// then takes the next directory, or the file stem for files directly inside.
+fn path_module_scope(file: &str, skip_package: bool) -> Option<String> {
+    let mut parts: Vec<&str> = file.split('/').filter(|p| !p.is_empty()).collect();
+    if parts
+        .first()
+        .is_some_and(|first| IGNORED_DIRS.contains(first))
+    {
+        return None;
+    }
+    if parts.first() == Some(&"src") || skip_package {
+        parts.remove(0);
+    }
+
+    match parts.as_slice() {
+        [] => None,
+        [name] => {
+            let stem = Path::new(name).file_stem()?.to_str()?;
+            (!ENTRY_STEMS.contains(&stem)).then(|| stem.to_string())
+        }
+        [dir, ..] => Some(dir.to_string()),
+    }
+}
+
+/// Read a changed source file from the working tree (skipping large/unreadable files)
+fn read_source(root: &Path, file: &str) -> Option<String> {
+    let path = root.join(file);
+    let meta = std::fs::metadata(&path).ok()?;
+    if meta.len() > MAX_SYMBOL_FILE_SIZE {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
+}
+
+/// Find a top-level `namespace X`, `module X {` or `declare module "x"` declaration
+fn ts_declared_module(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        // Top-level only: declarations are not indented
+        if line.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let rest = line.strip_prefix("export ").unwrap_or(line);
+        let rest = rest.strip_prefix("declare ").unwrap_or(rest);
+        let rest = rest
+            .strip_prefix("namespace ")
+            .or_else(|| rest.strip_prefix("module "))?;
+        let name: String = rest
+            .trim_start_matches(['"', '\''])
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/' | '@'))
+            .collect();
+        let name = name.rsplit(['/', '.']).next().unwrap_or(&name).to_string();
+        (!name.is_empty()).then_some(name)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,7 +253,7 @@ mod tests {
             "crates/core/src/lib.rs".into(),
             "crates/core/Cargo.toml".into(),
         ];
-        let scope = infer_scope(&files, &ws, None);
+        let scope = infer_scope(&files, &ws, None, &ScopeStyle::default());
 
         assert_eq!(scope.suggested_scope, Some("core".to_string()));
         assert_eq!(scope.packages.len(), 1);
@@ -91,7 +264,7 @@ mod tests {
     fn test_two_packages_scope() {
         let ws = make_workspace();
         let files = vec!["crates/core/src/lib.rs".into(), "apps/cli/main.rs".into()];
-        let scope = infer_scope(&files, &ws, None);
+        let scope = infer_scope(&files, &ws, None, &ScopeStyle::default());
 
         assert_eq!(scope.suggested_scope, Some("cli,core".to_string()));
         assert_eq!(scope.packages.len(), 2);
@@ -113,7 +286,7 @@ mod tests {
             "packages/c/index.ts".into(),
             "packages/d/index.ts".into(),
         ];
-        let scope = infer_scope(&files, &ws, None);
+        let scope = infer_scope(&files, &ws, None, &ScopeStyle::default());
 
         assert!(scope.suggested_scope.is_none());
         assert_eq!(scope.packages.len(), 4);
@@ -123,7 +296,7 @@ mod tests {
     fn test_root_only_no_scope() {
         let ws = make_workspace();
         let files = vec!["README.md".into(), "Cargo.toml".into()];
-        let scope = infer_scope(&files, &ws, None);
+        let scope = infer_scope(&files, &ws, None, &ScopeStyle::default());
 
         assert!(scope.suggested_scope.is_none());
         assert!(scope.packages.is_empty());
@@ -134,16 +307,204 @@ mod tests {
     fn test_manual_scope_override() {
         let ws = make_workspace();
         let files = vec!["crates/core/src/lib.rs".into()];
-        let scope = infer_scope(&files, &ws, Some("my-scope"));
+        let scope = infer_scope(&files, &ws, Some("my-scope"), &ScopeStyle::default());
 
         assert_eq!(scope.suggested_scope, Some("my-scope".to_string()));
     }
 
+    fn make_nested_workspace() -> WorkspaceInfo {
+        WorkspaceInfo {
+            workspace_types: vec![WorkspaceType::Pnpm],
+            members: vec![
+                WorkspaceMember {
+                    pattern: "packages/*".into(),
+                    prefix: "packages/".into(),
+                },
+                WorkspaceMember {
+                    pattern: "packages/ui/components/*".into(),
+                    prefix: "packages/ui/components/".into(),
+                },
+            ],
+            root: PathBuf::from("/tmp/test"),
+        }
+    }
+
+    #[test]
+    fn test_nested_package_scope_depth() {
+        let ws = make_nested_workspace();
+        let files = vec!["packages/ui/components/button/index.tsx".into()];
+
+        let scope = infer_scope(&files, &ws, None, &ScopeStyle::default());
+        assert_eq!(scope.packages, vec!["packages/ui/components/button"]);
+        assert_eq!(scope.suggested_scope, Some("button".to_string()));
+
+        let style = ScopeStyle {
+            depth: 2,
+            joiner: "/",
+        };
+        let scope = infer_scope(&files, &ws, None, &style);
+        assert_eq!(scope.suggested_scope, Some("ui/button".to_string()));
+
+        // 深度超过层级数时使用完整层级
+        let style = ScopeStyle {
+            depth: 5,
+            joiner: "-",
+        };
+        let scope = infer_scope(&files, &ws, None, &style);
+        assert_eq!(scope.suggested_scope, Some("ui-button".to_string()));
+    }
+
+    #[test]
+    fn test_nested_and_outer_package_scope() {
+        let ws = make_nested_workspace();
+        let files = vec![
+            "packages/ui/components/button/index.tsx".into(),
+            "packages/ui/src/theme.ts".into(),
+        ];
+        let style = ScopeStyle {
+            depth: 2,
+            joiner: "-",
+        };
+        let scope = infer_scope(&files, &ws, None, &style);
+        assert_eq!(scope.suggested_scope, Some("ui,ui-button".to_string()));
+    }
+
+    // === history scope tests ===
+
+    #[test]
+    fn test_history_scopes_by_frequency() {
+        let subjects = [
+            "fix(parser): handle empty input",
+            "feat(cli): add flag",
+            "refactor(parser)!: drop legacy API",
+            "docs: update readme",
+            "Merge branch 'main'",
+            "feat(cli): add another flag",
+            "chore(deps): bump",
+        ];
+        // cli 与 parser 次数相同时，较新的 parser 在前
+        assert_eq!(history_scopes(subjects, 3), vec!["parser", "cli", "deps"]);
+        assert_eq!(history_scopes(subjects, 1), vec!["parser"]);
+    }
+
+    #[test]
+    fn test_history_scopes_ignores_non_conventional() {
+        let subjects = [
+            "fixup! feat(cli): add flag",
+            "Revert \"feat(cli): add flag\"",
+            "feat( ): empty scope",
+            "update stuff",
+        ];
+        assert!(history_scopes(subjects, 3).is_empty());
+    }
+
+    // === symbol scope tests ===
+
+    #[test]
+    fn test_symbol_scope_rust_modules() {
+        let root = tempfile::tempdir().unwrap();
+        let files = vec![
+            "src/auth/mod.rs".into(),
+            "src/auth/token.rs".into(),
+            "src/main.rs".into(),
+        ];
+        assert_eq!(
+            infer_symbol_scope(&files, root.path()),
+            Some("auth".to_string())
+        );
+
+        let files = vec!["src/parser.rs".into(), "src/auth/mod.rs".into()];
+        assert_eq!(
+            infer_symbol_scope(&files, root.path()),
+            Some("auth,parser".to_string())
+        );
+    }
+
+    #[test]
+    fn test_symbol_scope_ignores_tests_and_unknown_files() {
+        let root = tempfile::tempdir().unwrap();
+        let files = vec![
+            "tests/it.rs".into(),
+            "README.md".into(),
+            "src/lib.rs".into(),
+        ];
+        assert_eq!(infer_symbol_scope(&files, root.path()), None);
+    }
+
+    #[test]
+    fn test_symbol_scope_too_many_modules() {
+        let root = tempfile::tempdir().unwrap();
+        let files = vec![
+            "src/a.rs".into(),
+            "src/b.rs".into(),
+            "src/c.rs".into(),
+            "src/d.rs".into(),
+        ];
+        assert_eq!(infer_symbol_scope(&files, root.path()), None);
+    }
+
+    #[test]
+    fn test_symbol_scope_typescript_namespace() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("src/util")).unwrap();
+        std::fs::write(
+            root.path().join("src/util/strings.ts"),
+            "// helpers\nexport namespace Parser {\n  export const x = 1;\n}\n",
+        )
+        .unwrap();
+
+        let files = vec!["src/util/strings.ts".into()];
+        assert_eq!(
+            infer_symbol_scope(&files, root.path()),
+            Some("Parser".to_string())
+        );
+        // Missing file falls back to path
+        let files = vec!["src/api/client.ts".into()];
+        assert_eq!(
+            infer_symbol_scope(&files, root.path()),
+            Some("api".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ts_declared_module_variants() {
+        assert_eq!(
+            ts_declared_module("declare module \"@acme/auth\" {\n}"),
+            Some("auth".to_string())
+        );
+        assert_eq!(
+            ts_declared_module("module Billing {\n}"),
+            Some("Billing".to_string())
+        );
+        assert_eq!(ts_declared_module("  namespace Nested {}"), None);
+        assert_eq!(ts_declared_module("import x from 'y';"), None);
+    }
+
+    #[test]
+    fn test_symbol_scope_python_package() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("mypkg/auth")).unwrap();
+        std::fs::write(root.path().join("mypkg/__init__.py"), "").unwrap();
+
+        let files = vec!["mypkg/auth/login.py".into()];
+        assert_eq!(
+            infer_symbol_scope(&files, root.path()),
+            Some("auth".to_string())
+        );
+        let files = vec!["mypkg/cli.py".into()];
+        assert_eq!(
+            infer_symbol_scope(&files, root.path()),
+            Some("cli".to_string())
+        );
+        let files = vec!["mypkg/__init__.py".into()];
+        assert_eq!(infer_symbol_scope(&files, root.path()), None);
+    }
+
     #[test]
     fn test_mixed_package_and_root() {
         let ws = make_workspace();
         let files = vec!["crates/core/src/lib.rs".into(), "README.md".into()];
-        let scope = infer_scope(&files, &ws, None);
+        let scope = infer_scope(&files, &ws, None, &ScopeStyle::default());
 
         assert_eq!(scope.suggested_scope, Some("core".to_string()));
         assert_eq!(scope.packages.len(), 1);
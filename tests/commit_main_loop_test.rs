@@ -16,7 +16,7 @@
 use async_trait::async_trait;
 use gcop_rs::config::AppConfig;
 use gcop_rs::error::{GcopError, Result};
-use gcop_rs::git::{CommitInfo, DiffStats, GitOperations};
+use gcop_rs::git::{CommitInfo, DiffStats, GitOperations, HistoryOptions};
 use gcop_rs::llm::{CommitContext, LLMProvider, ReviewResult, ReviewType, StreamChunk};
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -78,6 +78,14 @@ impl GitOperations for MockGitOps {
         Ok(String::new())
     }
 
+    fn get_snapshot_diff(&self, _tree: &str) -> Result<String> {
+        Ok(String::new())
+    }
+
+    fn snapshot_workdir(&self) -> Result<String> {
+        Ok(String::new())
+    }
+
     fn get_commit_diff(&self, _commit: &str) -> Result<String> {
         Ok(String::new())
     }
@@ -86,6 +94,10 @@ impl GitOperations for MockGitOps {
         Ok(String::new())
     }
 
+    fn default_branch(&self) -> Result<Option<String>> {
+        Ok(Some("main".to_string()))
+    }
+
     fn get_file_content(&self, _path: &str) -> Result<String> {
         Ok(String::new())
     }
@@ -114,7 +126,11 @@ impl GitOperations for MockGitOps {
         })
     }
 
-    fn get_commit_history(&self) -> Result<Vec<CommitInfo>> {
+    fn get_commit_history(&self, _options: &HistoryOptions) -> Result<Vec<CommitInfo>> {
+        Ok(vec![])
+    }
+
+    fn get_commits_touching(&self, _paths: &[String], _max_scan: usize) -> Result<Vec<CommitInfo>> {
         Ok(vec![])
     }
 
@@ -122,6 +138,17 @@ impl GitOperations for MockGitOps {
         Ok((0, 0))
     }
 
+    fn get_commit_info(&self, rev: &str) -> Result<CommitInfo> {
+        Ok(CommitInfo {
+            hash: format!("{rev}0000000"),
+            parent_count: 1,
+            author_name: "Test".to_string(),
+            author_email: "test@example.com".to_string(),
+            timestamp: chrono::Local::now(),
+            message: "feat: add login".to_string(),
+        })
+    }
+
     fn get_staged_files(&self) -> Result<Vec<String>> {
         if self.has_staged {
             Ok(vec!["test.rs".to_string()])
@@ -138,9 +165,69 @@ impl GitOperations for MockGitOps {
         Ok(())
     }
 
+    fn checkout_index(&self, _dir: &std::path::Path) -> Result<()> {
+        Ok(())
+    }
+
+    fn stage_tracked(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn apply_patch(&self, _patch: &str, _check_only: bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_remote_url(&self, _name: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
     fn get_workdir(&self) -> Result<std::path::PathBuf> {
         Ok(std::path::PathBuf::from("/tmp/test"))
     }
+
+    fn get_git_dir(&self) -> Result<std::path::PathBuf> {
+        Ok(std::path::PathBuf::from("/tmp/test/.git"))
+    }
+
+    fn get_user_identity(&self) -> Result<Option<String>> {
+        Ok(Some("Test User <test@example.com>".to_string()))
+    }
+
+    fn get_latest_tag(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn tag_exists(&self, _name: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn create_tag(&self, _name: &str, _message: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn push_tag(&self, _remote: &str, _name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_commit_message(&self, _rev: &str) -> Result<String> {
+        Ok("feat: add login".to_string())
+    }
+
+    fn is_ancestor(&self, _ancestor: &str, _descendant: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn reword_commit(&self, _hash: &str, _message: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn head_reflog_message(&self) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn reset_soft(&self, _rev: &str) -> Result<()> {
+        Ok(())
+    }
 }
 
 // === Mock LLMProvider ===
@@ -248,9 +335,21 @@ async fn test_commit_dry_run_mode() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Text,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -276,9 +375,21 @@ async fn test_commit_no_staged_changes() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Text,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -305,9 +416,21 @@ async fn test_commit_llm_failure() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Text,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -331,9 +454,21 @@ async fn test_commit_git_failure() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Text,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -356,9 +491,21 @@ async fn test_commit_json_output_mode() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Json,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -381,9 +528,21 @@ async fn test_commit_verbose_mode() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Text,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: true, // 启用 verbose
     };
 
@@ -406,9 +565,21 @@ async fn test_commit_with_feedback() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Text,
         feedback: &feedback_vec,
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -441,9 +612,21 @@ async fn test_display_message_no_panic() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Text,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -461,9 +644,21 @@ async fn test_handle_json_mode_no_panic() {
         no_edit: false,
         split: false,
         amend: false,
+        fixup: None,
+        all: false,
+        no_sign: false,
+        signoff: false,
+        co_authors: &[],
+        offline: false,
+        show_redacted: false,
+        output: None,
+        message_file: None,
+        verify: None,
+        review: false,
         format: gcop_rs::commands::format::OutputFormat::Json,
         feedback: &[],
         provider_override: None,
+        model_override: None,
         verbose: false,
     };
 
@@ -1,4 +1,4 @@
-use gcop_rs::git::commit::commit_changes;
+use gcop_rs::git::commit::{CommitSigning, CommitStdin, commit_changes};
 use serial_test::serial;
 use std::fs;
 use std::path::Path;
@@ -59,7 +59,7 @@ fn test_commit_success() {
     // 切换到仓库目录执行 commit
     std::env::set_current_dir(repo_path).unwrap();
 
-    let result = commit_changes("test commit");
+    let result = commit_changes("test commit", CommitSigning::Auto, CommitStdin::Null);
     assert!(result.is_ok());
 
     // 验证 commit 成功
@@ -84,7 +84,7 @@ fn test_commit_with_multiline_message() {
 
     let message =
         "feat: add feature\n\nThis is the body.\n\nCo-Authored-By: Test <test@example.com>";
-    let result = commit_changes(message);
+    let result = commit_changes(message, CommitSigning::Auto, CommitStdin::Null);
     assert!(result.is_ok());
 
     // 验证多行消息
@@ -110,7 +110,7 @@ fn test_commit_no_staged_changes() {
 
     std::env::set_current_dir(repo_path).unwrap();
 
-    let result = commit_changes("test commit");
+    let result = commit_changes("test commit", CommitSigning::Auto, CommitStdin::Null);
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -130,7 +130,7 @@ fn test_commit_empty_message() {
     create_and_stage_file(repo_path, "test.txt", "hello");
     std::env::set_current_dir(repo_path).unwrap();
 
-    let result = commit_changes("");
+    let result = commit_changes("", CommitSigning::Auto, CommitStdin::Null);
     assert!(result.is_err());
 
     let err = result.unwrap_err();
@@ -172,7 +172,7 @@ fn test_commit_hook_failure() {
     create_and_stage_file(repo_path, "test.txt", "hello");
     std::env::set_current_dir(repo_path).unwrap();
 
-    let result = commit_changes("test commit");
+    let result = commit_changes("test commit", CommitSigning::Auto, CommitStdin::Null);
 
     #[cfg(unix)]
     {
@@ -188,6 +188,38 @@ fn test_commit_hook_failure() {
     }
 }
 
+#[cfg(unix)]
+#[test]
+#[serial]
+fn test_commit_hook_does_not_read_stdin_when_null() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = setup_git_repo();
+    let repo_path = temp_dir.path();
+
+    // 读到任何输入就失败的 hook：api 模式下 stdin 上排队的是 JSON-RPC 请求
+    let hooks_dir = repo_path.join(".git").join("hooks");
+    fs::create_dir_all(&hooks_dir).unwrap();
+    let hook_path = hooks_dir.join("pre-commit");
+    fs::write(
+        &hook_path,
+        "#!/bin/sh
+if read line; then exit 1; fi
+exit 0
+",
+    )
+    .unwrap();
+    let mut perms = fs::metadata(&hook_path).unwrap().permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&hook_path, perms).unwrap();
+
+    create_and_stage_file(repo_path, "test.txt", "hello");
+    std::env::set_current_dir(repo_path).unwrap();
+
+    let result = commit_changes("test commit", CommitSigning::Auto, CommitStdin::Null);
+    assert!(result.is_ok(), "{:?}", result);
+}
+
 // === GPG 签名场景（可选，需要 GPG 配置）===
 
 #[test]
@@ -206,7 +238,7 @@ fn test_commit_respects_git_config() {
     create_and_stage_file(repo_path, "test.txt", "hello");
     std::env::set_current_dir(repo_path).unwrap();
 
-    let result = commit_changes("test commit");
+    let result = commit_changes("test commit", CommitSigning::Auto, CommitStdin::Null);
     assert!(result.is_ok());
 
     // 验证 commit author
@@ -219,3 +251,44 @@ fn test_commit_respects_git_config() {
     let author = String::from_utf8_lossy(&output.stdout);
     assert!(author.contains("Custom User"));
 }
+
+/// 配置一个必定失败的签名程序，模拟 `commit.gpgsign = true` 的强制签名策略
+fn enable_broken_signing(repo_path: &Path) {
+    for (key, value) in [("commit.gpgsign", "true"), ("gpg.program", "false")] {
+        Command::new("git")
+            .args(["config", key, value])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+    }
+}
+
+#[test]
+#[serial]
+fn test_commit_follows_gpgsign_config() {
+    let temp_dir = setup_git_repo();
+    let repo_path = temp_dir.path();
+    enable_broken_signing(repo_path);
+
+    create_and_stage_file(repo_path, "test.txt", "hello");
+    std::env::set_current_dir(repo_path).unwrap();
+
+    // Auto 模式遵循 commit.gpgsign，签名失败应返回错误
+    let result = commit_changes("test commit", CommitSigning::Auto, CommitStdin::Null);
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("failed to sign"));
+}
+
+#[test]
+#[serial]
+fn test_commit_no_sign_overrides_gpgsign_config() {
+    let temp_dir = setup_git_repo();
+    let repo_path = temp_dir.path();
+    enable_broken_signing(repo_path);
+
+    create_and_stage_file(repo_path, "test.txt", "hello");
+    std::env::set_current_dir(repo_path).unwrap();
+
+    let result = commit_changes("test commit", CommitSigning::NoSign, CommitStdin::Null);
+    assert!(result.is_ok());
+}
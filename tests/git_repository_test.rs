@@ -11,7 +11,7 @@
 
 use gcop_rs::config::FileConfig;
 use gcop_rs::error::{GcopError, Result};
-use gcop_rs::git::{GitOperations, repository::GitRepository};
+use gcop_rs::git::{GitOperations, HistoryOptions, repository::GitRepository};
 use serial_test::serial;
 use std::env;
 use std::fs;
@@ -170,7 +170,10 @@ fn test_get_file_content_respects_custom_max_size() -> Result<()> {
     env::set_current_dir(repo_path)?;
 
     // 自定义 max_size = 512 bytes
-    let file_config = FileConfig { max_size: 512 };
+    let file_config = FileConfig {
+        max_size: 512,
+        ..Default::default()
+    };
     let git_repo = GitRepository::open(Some(&file_config))?;
     let result = git_repo.get_file_content("small.txt");
 
@@ -362,7 +365,7 @@ fn test_get_commit_history_normal() -> Result<()> {
     env::set_current_dir(repo_path)?;
 
     let git_repo = GitRepository::open(None)?;
-    let history = git_repo.get_commit_history()?;
+    let history = git_repo.get_commit_history(&HistoryOptions::default())?;
 
     assert_eq!(history.len(), 2);
     assert_eq!(history[0].message, "Second commit");
@@ -373,6 +376,48 @@ fn test_get_commit_history_normal() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn test_get_commits_touching_paths() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path();
+    let repo = init_git_repo(repo_path)?;
+
+    create_test_file(repo_path, "a.txt", "a1")?;
+    add_file_to_index(&repo, "a.txt")?;
+    let first = create_commit(&repo, "feat(a): add a", vec![])?;
+
+    create_test_file(repo_path, "b.txt", "b1")?;
+    add_file_to_index(&repo, "b.txt")?;
+    let second = create_commit(&repo, "feat(b): add b", vec![&repo.find_commit(first)?])?;
+
+    create_test_file(repo_path, "a.txt", "a2")?;
+    add_file_to_index(&repo, "a.txt")?;
+    create_commit(&repo, "fix(a): update a", vec![&repo.find_commit(second)?])?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(repo_path)?;
+
+    let git_repo = GitRepository::open(None)?;
+    let touching = git_repo.get_commits_touching(&["a.txt".to_string()], 100)?;
+    let subjects: Vec<&str> = touching.iter().map(|c| c.message.as_str()).collect();
+    assert_eq!(subjects, vec!["fix(a): update a", "feat(a): add a"]);
+
+    // 扫描上限只覆盖最新的提交
+    let touching = git_repo.get_commits_touching(&["a.txt".to_string()], 1)?;
+    assert_eq!(touching.len(), 1);
+    // 路径按字面匹配
+    assert!(
+        git_repo
+            .get_commits_touching(&["*.txt".to_string()], 100)?
+            .is_empty()
+    );
+    assert!(git_repo.get_commits_touching(&[], 100)?.is_empty());
+
+    env::set_current_dir(original_dir)?;
+    Ok(())
+}
+
 // ========== has_staged_changes 测试 ==========
 
 #[test]
@@ -497,3 +542,291 @@ fn test_get_commit_line_stats_invalid_hash() -> Result<()> {
     env::set_current_dir(original_dir)?;
     Ok(())
 }
+
+// ========== apply_patch 测试 ==========
+
+#[test]
+#[serial]
+fn test_apply_patch_check_and_apply() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path();
+    init_git_repo(repo_path)?;
+    create_test_file(repo_path, "lib.rs", "fn main() {\n    let x = 1;\n}\n")?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(repo_path)?;
+
+    let git_repo = GitRepository::open(None)?;
+    let patch = "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,3 +1,3 @@\n fn main() {\n-    let x = 1;\n+    let _x = 1;\n }\n";
+
+    // --check 不修改工作区
+    git_repo.apply_patch(patch, true)?;
+    assert!(fs::read_to_string(repo_path.join("lib.rs"))?.contains("let x = 1;"));
+
+    git_repo.apply_patch(patch, false)?;
+    assert!(fs::read_to_string(repo_path.join("lib.rs"))?.contains("let _x = 1;"));
+
+    // 已应用的补丁无法再次应用
+    let result = git_repo.apply_patch(patch, true);
+    assert!(matches!(result, Err(GcopError::GitCommand(_))));
+
+    env::set_current_dir(original_dir)?;
+    Ok(())
+}
+
+// ========== worktree / GIT_DIR 测试 ==========
+
+#[test]
+#[serial]
+fn test_open_in_linked_worktree() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("main");
+    let repo = init_git_repo(&repo_path)?;
+    create_test_file(&repo_path, "test.txt", "content")?;
+    add_file_to_index(&repo, "test.txt")?;
+    create_commit(&repo, "Initial commit", vec![])?;
+
+    let worktree_path = temp_dir.path().join("feature-wt");
+    repo.worktree("feature-wt", &worktree_path, None)?;
+    fs::create_dir_all(worktree_path.join("nested"))?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(worktree_path.join("nested"))?;
+
+    let root = gcop_rs::git::find_git_root();
+    let git_repo = GitRepository::open(None)?;
+    let branch = git_repo.get_current_branch()?;
+
+    env::set_current_dir(original_dir)?;
+
+    assert_eq!(root, Some(worktree_path.canonicalize()?));
+    assert_eq!(branch.as_deref(), Some("feature-wt"));
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_open_with_git_dir_and_work_tree_env() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("repo");
+    let repo = init_git_repo(&repo_path)?;
+    create_test_file(&repo_path, "test.txt", "content")?;
+    add_file_to_index(&repo, "test.txt")?;
+
+    let elsewhere = temp_dir.path().join("elsewhere");
+    fs::create_dir_all(&elsewhere)?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(&elsewhere)?;
+    unsafe {
+        env::set_var("GIT_DIR", repo_path.join(".git"));
+        env::set_var("GIT_WORK_TREE", &repo_path);
+    }
+
+    let root = gcop_rs::git::find_git_root();
+    let diff = GitRepository::open(None).and_then(|r| r.get_staged_diff());
+
+    unsafe {
+        env::remove_var("GIT_DIR");
+        env::remove_var("GIT_WORK_TREE");
+    }
+    env::set_current_dir(original_dir)?;
+
+    assert_eq!(
+        root.map(|p| p.canonicalize().unwrap()),
+        Some(repo_path.canonicalize()?)
+    );
+    assert!(diff?.contains("+content"));
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_get_range_diff_three_dot_uses_merge_base() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path();
+    let repo = init_git_repo(repo_path)?;
+    create_test_file(repo_path, "base.txt", "base")?;
+    add_file_to_index(&repo, "base.txt")?;
+    let base_oid = create_commit(&repo, "Initial commit", vec![])?;
+    let base = repo.find_commit(base_oid)?;
+    repo.branch("trunk", &base, false)?;
+
+    // feature: base + feature.txt (HEAD)
+    create_test_file(repo_path, "feature.txt", "feature")?;
+    add_file_to_index(&repo, "feature.txt")?;
+    create_commit(&repo, "Add feature", vec![&base])?;
+
+    // trunk 在分叉后又前进了一个提交
+    let sig = git2::Signature::now("Test User", "test@example.com")?;
+    let mut builder = repo.treebuilder(Some(&base.tree()?))?;
+    builder.insert("trunk.txt", repo.blob(b"trunk")?, 0o100644)?;
+    let tree = repo.find_tree(builder.write()?)?;
+    repo.commit(
+        Some("refs/heads/trunk"),
+        &sig,
+        &sig,
+        "Advance trunk",
+        &tree,
+        &[&base],
+    )?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(repo_path)?;
+    let git_repo = GitRepository::open(None)?;
+    let two_dot = git_repo.get_range_diff("trunk..HEAD");
+    let three_dot = git_repo.get_range_diff("trunk...");
+    env::set_current_dir(original_dir)?;
+
+    let (two_dot, three_dot) = (two_dot?, three_dot?);
+    assert!(two_dot.contains("feature.txt"));
+    assert!(two_dot.contains("trunk.txt"));
+    assert!(three_dot.contains("feature.txt"));
+    assert!(!three_dot.contains("trunk.txt"));
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_default_branch_from_origin_head() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path();
+    let repo = init_git_repo(repo_path)?;
+    create_test_file(repo_path, "test.txt", "content")?;
+    add_file_to_index(&repo, "test.txt")?;
+    let oid = create_commit(&repo, "Initial commit", vec![])?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(repo_path)?;
+    let git_repo = GitRepository::open(None)?;
+
+    // 没有 origin/HEAD 时回退到本地 main/master
+    let local = git_repo.default_branch()?;
+    repo.reference("refs/remotes/origin/trunk", oid, false, "test")?;
+    repo.reference_symbolic(
+        "refs/remotes/origin/HEAD",
+        "refs/remotes/origin/trunk",
+        false,
+        "test",
+    )?;
+    let remote = gcop_rs::git::expand_range(&git_repo, None);
+    let bare = gcop_rs::git::expand_range(&git_repo, Some("feature"));
+    let explicit = gcop_rs::git::expand_range(&git_repo, Some("a..b"));
+    env::set_current_dir(original_dir)?;
+
+    let head = repo.head()?.shorthand().unwrap_or_default().to_string();
+    assert_eq!(local, Some(head));
+    assert_eq!(remote?, "origin/trunk...HEAD");
+    assert_eq!(bare?, "origin/trunk...feature");
+    assert_eq!(explicit?, "a..b");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_snapshot_workdir_and_diff_since_snapshot() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path();
+    let repo = init_git_repo(repo_path)?;
+    create_test_file(repo_path, "a.txt", "one\n")?;
+    create_test_file(repo_path, "b.txt", "one\n")?;
+    add_file_to_index(&repo, "a.txt")?;
+    add_file_to_index(&repo, "b.txt")?;
+    create_commit(&repo, "Initial commit", vec![])?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(repo_path)?;
+    let git_repo = GitRepository::open(None)?;
+
+    create_test_file(repo_path, "a.txt", "two\n")?;
+    let snapshot = git_repo.snapshot_workdir();
+    // 快照之后的修改
+    create_test_file(repo_path, "b.txt", "two\n")?;
+    let since = snapshot
+        .as_ref()
+        .map_err(|e| GcopError::GitCommand(e.to_string()))
+        .and_then(|tree| git_repo.get_snapshot_diff(tree));
+    let unstaged = git_repo.get_uncommitted_diff();
+    env::set_current_dir(original_dir)?;
+
+    let since = since?;
+    assert!(since.contains("b.txt"));
+    assert!(!since.contains("a.txt"));
+    // 快照不改动索引
+    assert!(unstaged?.contains("a.txt"));
+    Ok(())
+}
+
+// ========== hooks 目录 / 路径规范化测试 ==========
+
+#[test]
+fn test_hooks_dir_linked_worktree_and_hooks_path() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path().join("main");
+    let repo = init_git_repo(&repo_path)?;
+    create_test_file(&repo_path, "test.txt", "content")?;
+    add_file_to_index(&repo, "test.txt")?;
+    create_commit(&repo, "Initial commit", vec![])?;
+
+    // 链接 worktree 的 .git 是文件，hooks 位于主仓库的 common dir
+    let worktree_path = temp_dir.path().join("feature-wt");
+    repo.worktree("feature-wt", &worktree_path, None)?;
+    let hooks = gcop_rs::git::hooks_dir(&worktree_path);
+    assert_eq!(
+        hooks.canonicalize()?,
+        repo_path.join(".git/hooks").canonicalize()?
+    );
+
+    // core.hooksPath 相对于工作树根目录
+    repo.config()?.set_str("core.hooksPath", ".githooks")?;
+    assert_eq!(
+        gcop_rs::git::hooks_dir(&repo_path),
+        repo_path.join(".githooks")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_git_path_normalizes_separators() {
+    use gcop_rs::git::to_git_path;
+    assert_eq!(to_git_path(Path::new("./src/main.rs")), "src/main.rs");
+    assert_eq!(to_git_path(Path::new("src/./lib.rs")), "src/lib.rs");
+    // Windows 下反斜杠是分隔符
+    if cfg!(windows) {
+        assert_eq!(
+            to_git_path(Path::new(r"src\commands\hook.rs")),
+            "src/commands/hook.rs"
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn test_reword_commit_refuses_during_merge() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let repo_path = temp_dir.path();
+    let repo = init_git_repo(repo_path)?;
+
+    create_test_file(repo_path, "a.txt", "a")?;
+    add_file_to_index(&repo, "a.txt")?;
+    let first_commit_id = create_commit(&repo, "First commit", vec![])?;
+    create_test_file(repo_path, "b.txt", "b")?;
+    add_file_to_index(&repo, "b.txt")?;
+    let first_commit = repo.find_commit(first_commit_id)?;
+    let head = create_commit(&repo, "Second commit", vec![&first_commit])?;
+
+    // 模拟用户正在进行的 merge
+    fs::write(repo_path.join(".git/MERGE_HEAD"), format!("{}\n", head))?;
+
+    let original_dir = env::current_dir()?;
+    env::set_current_dir(repo_path)?;
+    let git_repo = GitRepository::open(None)?;
+    let result = git_repo.reword_commit(&first_commit_id.to_string(), "feat: reworded");
+    env::set_current_dir(original_dir)?;
+
+    assert!(matches!(result, Err(GcopError::GitCommand(_))));
+    // 用户的 merge 状态保持不变
+    assert!(repo_path.join(".git/MERGE_HEAD").exists());
+    assert_eq!(repo.head()?.peel_to_commit()?.id(), head);
+    Ok(())
+}
@@ -2,7 +2,7 @@
 //!
 //! 测试核心功能的完整流程
 
-use gcop_rs::config::AppConfig;
+use gcop_rs::config::{AppConfig, BodyMode};
 use gcop_rs::git::diff::parse_diff_stats;
 use gcop_rs::llm::prompt::{build_commit_prompt_split, build_review_prompt_split};
 use gcop_rs::llm::provider::base::{clean_json_response, parse_review_response};
@@ -71,10 +71,15 @@ index 1234567..abcdefg 100644
         insertions: 2,
         deletions: 1,
         branch_name: Some("feature/greeting".to_string()),
+        ticket: None,
         custom_prompt: None,
         user_feedback: vec![],
         convention: None,
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let (system, user) = build_commit_prompt_split(diff, &context, None, None);
@@ -171,6 +176,7 @@ fn test_user_feedback_accumulation() {
         insertions: 1,
         deletions: 0,
         branch_name: None,
+        ticket: None,
         custom_prompt: None,
         user_feedback: vec![
             "请使用中文".to_string(),
@@ -179,6 +185,10 @@ fn test_user_feedback_accumulation() {
         ],
         convention: None,
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let (_, user) = build_commit_prompt_split("diff", &context, None, None);
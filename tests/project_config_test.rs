@@ -2,7 +2,7 @@
 //!
 //! 测试 .gcop/config.toml 项目配置的完整流程
 
-use gcop_rs::config::{AppConfig, CommitConvention, ConventionStyle};
+use gcop_rs::config::{AppConfig, BodyMode, CommitConvention, ConventionStyle};
 use gcop_rs::llm::CommitContext;
 use gcop_rs::llm::prompt::build_commit_prompt_split;
 
@@ -22,6 +22,7 @@ fn test_convention_conventional_e2e() {
         ]),
         template: None,
         extra_prompt: Some("All commit messages must be in English".to_string()),
+        ..Default::default()
     };
 
     let context = CommitContext {
@@ -29,10 +30,15 @@ fn test_convention_conventional_e2e() {
         insertions: 15,
         deletions: 3,
         branch_name: Some("feature/auth".to_string()),
+        ticket: None,
         custom_prompt: None,
         user_feedback: vec![],
         convention: Some(convention),
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let diff = "diff --git a/src/lib.rs b/src/lib.rs\n+pub fn authenticate() {}";
@@ -61,6 +67,7 @@ fn test_convention_gitmoji_e2e() {
         types: None,
         template: None,
         extra_prompt: None,
+        ..Default::default()
     };
 
     let context = CommitContext {
@@ -68,10 +75,15 @@ fn test_convention_gitmoji_e2e() {
         insertions: 5,
         deletions: 0,
         branch_name: None,
+        ticket: None,
         custom_prompt: None,
         user_feedback: vec![],
         convention: Some(convention),
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let (system, _) =
@@ -89,6 +101,7 @@ fn test_convention_custom_with_template_e2e() {
         types: Some(vec!["feature".to_string(), "bugfix".to_string()]),
         template: Some("[{type}] {subject}".to_string()),
         extra_prompt: Some("Use imperative mood".to_string()),
+        ..Default::default()
     };
 
     let context = CommitContext {
@@ -96,10 +109,15 @@ fn test_convention_custom_with_template_e2e() {
         insertions: 1,
         deletions: 1,
         branch_name: None,
+        ticket: None,
         custom_prompt: None,
         user_feedback: vec![],
         convention: Some(convention),
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let (system, _) =
@@ -130,10 +148,15 @@ fn test_convention_with_custom_prompt_e2e() {
         insertions: 1,
         deletions: 0,
         branch_name: None,
+        ticket: None,
         custom_prompt: Some("You are a minimal commit message generator.".to_string()),
         user_feedback: vec![],
         convention: Some(convention),
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let (system, _) = build_commit_prompt_split(
@@ -163,10 +186,15 @@ fn test_convention_with_feedback_e2e() {
         insertions: 1,
         deletions: 0,
         branch_name: None,
+        ticket: None,
         custom_prompt: None,
         user_feedback: vec!["请使用中文".to_string()],
         convention: Some(convention),
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let (system, user) =
@@ -187,10 +215,15 @@ fn test_no_convention_no_section_e2e() {
         insertions: 1,
         deletions: 0,
         branch_name: None,
+        ticket: None,
         custom_prompt: None,
         user_feedback: vec![],
         convention: None,
         scope_info: None,
+        history_examples: vec![],
+        previous_message: None,
+        body: BodyMode::Auto,
+        body_bullets: false,
     };
 
     let (system, _) = build_commit_prompt_split("diff", &context, None, None);
@@ -43,6 +43,13 @@ async fn test_claude_validate_success() {
         model: "claude-3-haiku-20240307".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -71,6 +78,13 @@ async fn test_claude_validate_401_unauthorized() {
         model: "claude-3-haiku-20240307".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -108,6 +122,13 @@ async fn test_claude_validate_429_rate_limit() {
         model: "claude-3-haiku-20240307".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -137,6 +158,13 @@ async fn test_claude_validate_empty_api_key() {
         model: "claude-3-haiku-20240307".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -175,6 +203,13 @@ async fn test_openai_validate_success() {
         model: "gpt-4o-mini".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -203,6 +238,13 @@ async fn test_openai_validate_401_unauthorized() {
         model: "gpt-4o-mini".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -243,6 +285,13 @@ async fn test_ollama_validate_success() {
         model: "llama3.2".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -272,6 +321,13 @@ async fn test_ollama_validate_model_not_found() {
         model: "mistral".to_string(), // 不存在的模型
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -302,6 +358,13 @@ async fn test_ollama_validate_connection_error() {
         model: "llama3.2".to_string(),
         max_tokens: None,
         temperature: None,
+        top_p: None,
+        stop: None,
+        proxy: None,
+        network: None,
+        pricing: None,
+        tiers: None,
+        allowed_models: None,
         extra: HashMap::new(),
     };
 
@@ -3,22 +3,30 @@
 //! 测试 review 命令的：
 //! - 4 种 target 类型路由（Changes/Commit/Range/File）
 //! - 错误处理（空 diff、LLM 失败）
+//! - 审查基线（记录与加载）
+//! - --fail-on 退出码约定
+//! - --apply 参数校验
+//! - commit/range 审查结果缓存
 
 use async_trait::async_trait;
 use gcop_rs::cli::ReviewTarget;
 use gcop_rs::commands::{OutputFormat, ReviewOptions};
 use gcop_rs::config::AppConfig;
 use gcop_rs::error::{GcopError, Result};
-use gcop_rs::git::MockGitOperations;
+use gcop_rs::git::{CommitInfo, MockGitOperations};
 use gcop_rs::llm::{
     CommitContext, IssueSeverity, LLMProvider, ReviewIssue, ReviewResult, ReviewType,
 };
+use gcop_rs::ui::{JsonReporter, SilentReporter};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 // ========== Mock LLM Provider ==========
 
 struct MockReviewLLM {
     expected_review_type: ReviewType,
     should_fail: bool,
+    /// review_code 调用次数（分块审查时每个部分一次）
+    review_calls: AtomicUsize,
 }
 
 impl MockReviewLLM {
@@ -26,6 +34,7 @@ impl MockReviewLLM {
         Self {
             expected_review_type,
             should_fail: false,
+            review_calls: AtomicUsize::new(0),
         }
     }
 
@@ -33,6 +42,7 @@ impl MockReviewLLM {
         Self {
             expected_review_type: ReviewType::UncommittedChanges,
             should_fail: true,
+            review_calls: AtomicUsize::new(0),
         }
     }
 }
@@ -55,6 +65,7 @@ impl LLMProvider for MockReviewLLM {
         _custom_prompt: Option<&str>,
         _progress: Option<&dyn gcop_rs::llm::ProgressReporter>,
     ) -> Result<ReviewResult> {
+        self.review_calls.fetch_add(1, Ordering::SeqCst);
         if self.should_fail {
             return Err(GcopError::LlmApi {
                 status: 503,
@@ -67,7 +78,8 @@ impl LLMProvider for MockReviewLLM {
             (ReviewType::UncommittedChanges, ReviewType::UncommittedChanges) => {}
             (ReviewType::SingleCommit(a), ReviewType::SingleCommit(b)) if a == b => {}
             (ReviewType::CommitRange(a), ReviewType::CommitRange(b)) if a == b => {}
-            (ReviewType::FileOrDir(a), ReviewType::FileOrDir(b)) if a == b => {}
+            (ReviewType::FileOrDir { path: a, .. }, ReviewType::FileOrDir { path: b, .. })
+                if a == b => {}
             _ => {
                 panic!(
                     "Review type mismatch: expected {:?}, got {:?}",
@@ -83,6 +95,8 @@ impl LLMProvider for MockReviewLLM {
                 description: "Test issue".to_string(),
                 file: Some("test.rs".to_string()),
                 line: Some(42),
+                suggested_patch: None,
+                category: None,
             }],
             suggestions: vec!["Test suggestion".to_string()],
         })
@@ -115,12 +129,32 @@ fn make_review_options(target: &ReviewTarget) -> ReviewOptions<'_> {
         format: OutputFormat::Text,
         verbose: false,
         provider_override: None,
+        model_override: None,
+        update_baseline: false,
+        fail_on: None,
+        apply: false,
+        preset: None,
+        show_redacted: false,
+        incremental: None,
+        // 缓存测试单独开启，避免其它用例需要 get_commit_info 期望
+        no_cache: true,
     }
 }
 
+/// 期望一次 get_workdir 调用，返回指定目录（基线文件位于其下 .gcop/）
+fn expect_workdir(mock_git: &mut MockGitOperations, workdir: &std::path::Path) {
+    let workdir = workdir.to_path_buf();
+    mock_git
+        .expect_get_workdir()
+        .times(1)
+        .returning(move || Ok(workdir.clone()));
+}
+
 #[tokio::test]
 async fn test_review_target_uncommitted_changes() {
+    let dir = tempfile::tempdir().unwrap();
     let mut mock_git = MockGitOperations::new();
+    expect_workdir(&mut mock_git, dir.path());
     mock_git
         .expect_get_uncommitted_diff()
         .times(1)
@@ -132,15 +166,59 @@ async fn test_review_target_uncommitted_changes() {
     let target = ReviewTarget::Changes;
     let options = make_review_options(&target);
 
-    let result =
-        gcop_rs::commands::review::run_internal(&options, &config, &mock_git, &mock_llm).await;
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_review_reports_progress_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut mock_git = MockGitOperations::new();
+    expect_workdir(&mut mock_git, dir.path());
+    mock_git
+        .expect_get_uncommitted_diff()
+        .times(1)
+        .returning(|| Ok("diff --git a/test.rs\n+new line".to_string()));
+
+    let mock_llm = MockReviewLLM::new(ReviewType::UncommittedChanges);
+    let config = AppConfig::default();
+    let target = ReviewTarget::Changes;
+    let mut options = make_review_options(&target);
+    // 状态输出全部交给 reporter，stdout 只留下 JSON 结果
+    options.format = OutputFormat::Json;
 
+    let reporter = JsonReporter::new(Vec::new());
+    let result =
+        gcop_rs::commands::review::run_internal(&options, &config, &mock_git, &mock_llm, &reporter)
+            .await;
     assert!(result.is_ok());
+
+    let events: Vec<serde_json::Value> = String::from_utf8(reporter.into_inner())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let progress = events
+        .iter()
+        .filter(|event| event["event"] == "progress")
+        .count();
+    // 分析步骤、审查 spinner、格式化步骤
+    assert_eq!(progress, 3);
 }
 
 #[tokio::test]
 async fn test_review_target_single_commit() {
+    let dir = tempfile::tempdir().unwrap();
     let mut mock_git = MockGitOperations::new();
+    expect_workdir(&mut mock_git, dir.path());
     mock_git
         .expect_get_commit_diff()
         .with(mockall::predicate::eq("abc123"))
@@ -155,15 +233,23 @@ async fn test_review_target_single_commit() {
     };
     let options = make_review_options(&target);
 
-    let result =
-        gcop_rs::commands::review::run_internal(&options, &config, &mock_git, &mock_llm).await;
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
 
     assert!(result.is_ok());
 }
 
 #[tokio::test]
 async fn test_review_target_range() {
+    let dir = tempfile::tempdir().unwrap();
     let mut mock_git = MockGitOperations::new();
+    expect_workdir(&mut mock_git, dir.path());
     mock_git
         .expect_get_range_diff()
         .with(mockall::predicate::eq("main..feature"))
@@ -174,35 +260,256 @@ async fn test_review_target_range() {
 
     let config = AppConfig::default();
     let target = ReviewTarget::Range {
-        range: "main..feature".to_string(),
+        range: Some("main..feature".to_string()),
     };
     let options = make_review_options(&target);
 
-    let result =
-        gcop_rs::commands::review::run_internal(&options, &config, &mock_git, &mock_llm).await;
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_review_range_over_budget_is_chunked() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut mock_git = MockGitOperations::new();
+    expect_workdir(&mut mock_git, dir.path());
+    let file = |name: &str| {
+        format!(
+            "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n{1}",
+            name,
+            "+line\n".repeat(40)
+        )
+    };
+    let diff = [file("a.rs"), file("b.rs"), file("c.rs")].join("\n");
+    mock_git
+        .expect_get_range_diff()
+        .times(1)
+        .returning(move |_| Ok(diff.clone()));
+
+    let mock_llm = MockReviewLLM::new(ReviewType::CommitRange("main...feature".to_string()));
+
+    let mut config = AppConfig::default();
+    config.llm.max_diff_size = 300;
+    let target = ReviewTarget::Range {
+        range: Some("main...feature".to_string()),
+    };
+    let options = make_review_options(&target);
+
+    // 每个文件单独成块；合并请求返回空响应时回退为拼接结果
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(mock_llm.review_calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_review_changes_incremental_uses_last_snapshot() {
+    let dir = tempfile::tempdir().unwrap();
+    let git_dir = dir.path().join(".git");
+    let target = ReviewTarget::Changes;
+    let mut options = make_review_options(&target);
+    options.incremental = Some(true);
+    let config = AppConfig::default();
+    let mock_llm = MockReviewLLM::new(ReviewType::UncommittedChanges);
+
+    let mock_git = |first: bool| {
+        let mut mock_git = MockGitOperations::new();
+        expect_workdir(&mut mock_git, dir.path());
+        let git_dir = git_dir.clone();
+        mock_git
+            .expect_get_git_dir()
+            .returning(move || Ok(git_dir.clone()));
+        mock_git
+            .expect_get_current_branch()
+            .returning(|| Ok(Some("feature".to_string())));
+        let tree = if first { "tree-1" } else { "tree-2" };
+        mock_git
+            .expect_snapshot_workdir()
+            .times(1)
+            .returning(move || Ok(tree.to_string()));
+        if first {
+            // 首次运行没有快照，审查全部未暂存变更
+            mock_git
+                .expect_get_uncommitted_diff()
+                .times(1)
+                .returning(|| Ok("diff --git a/test.rs\n+new line".to_string()));
+        } else {
+            mock_git
+                .expect_get_snapshot_diff()
+                .with(mockall::predicate::eq("tree-1"))
+                .times(1)
+                .returning(|_| Ok("diff --git a/test.rs\n+newer line".to_string()));
+        }
+        mock_git
+    };
+
+    let first = mock_git(true);
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &first,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+    assert!(result.is_ok());
+    let recorded = std::fs::read_to_string(git_dir.join("gcop/reviewed.json")).unwrap();
+    assert!(recorded.contains("\"feature\": \"tree-1\""));
+
+    let second = mock_git(false);
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &second,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(mock_llm.review_calls.load(Ordering::SeqCst), 2);
+
+    // --incremental 只适用于 changes
+    let commit = ReviewTarget::Commit {
+        hash: "abc123".to_string(),
+    };
+    let mut options = make_review_options(&commit);
+    options.incremental = Some(true);
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &MockGitOperations::new(),
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+    assert!(matches!(result, Err(GcopError::InvalidInput(_))));
+}
+
+#[tokio::test]
+async fn test_review_commit_result_is_cached() {
+    let dir = tempfile::tempdir().unwrap();
+    let git_dir = dir.path().join(".git");
+    let target = ReviewTarget::Commit {
+        hash: "HEAD".to_string(),
+    };
+    let mut options = make_review_options(&target);
+    options.no_cache = false;
+    let config = AppConfig::default();
+    let mock_llm = MockReviewLLM::new(ReviewType::SingleCommit("HEAD".to_string()));
+
+    let mock_git = || {
+        let mut mock_git = MockGitOperations::new();
+        expect_workdir(&mut mock_git, dir.path());
+        let git_dir = git_dir.clone();
+        mock_git
+            .expect_get_git_dir()
+            .returning(move || Ok(git_dir.clone()));
+        mock_git.expect_get_commit_info().returning(|_| {
+            Ok(CommitInfo {
+                hash: "abc1230000000".to_string(),
+                parent_count: 1,
+                author_name: "Test".to_string(),
+                author_email: "test@example.com".to_string(),
+                timestamp: chrono::Local::now(),
+                message: "feat: add login".to_string(),
+            })
+        });
+        // diff 仍会读取（隐私检查与截断在缓存查询之前）
+        mock_git
+            .expect_get_commit_diff()
+            .times(1)
+            .returning(|_| Ok("diff --git a/test.rs\n+new line".to_string()));
+        mock_git
+    };
+
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git(),
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(mock_llm.review_calls.load(Ordering::SeqCst), 1);
+
+    // 第二次命中缓存，不再调用 LLM
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git(),
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+    assert!(result.is_ok());
+    assert_eq!(mock_llm.review_calls.load(Ordering::SeqCst), 1);
 
+    // --no-cache 重新审查
+    options.no_cache = true;
+    let mut no_cache = MockGitOperations::new();
+    expect_workdir(&mut no_cache, dir.path());
+    no_cache
+        .expect_get_commit_diff()
+        .times(1)
+        .returning(|_| Ok("diff --git a/test.rs\n+new line".to_string()));
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &no_cache,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
     assert!(result.is_ok());
+    assert_eq!(mock_llm.review_calls.load(Ordering::SeqCst), 2);
 }
 
 #[tokio::test]
 async fn test_review_target_file() {
+    let dir = tempfile::tempdir().unwrap();
     let mut mock_git = MockGitOperations::new();
+    expect_workdir(&mut mock_git, dir.path());
     mock_git
         .expect_get_file_content()
         .with(mockall::predicate::eq("src/main.rs"))
         .times(1)
         .returning(|_| Ok("fn main() {}\n".to_string()));
 
-    let mock_llm = MockReviewLLM::new(ReviewType::FileOrDir("src/main.rs".to_string()));
+    let mock_llm = MockReviewLLM::new(ReviewType::FileOrDir {
+        path: "src/main.rs".to_string(),
+        context: None,
+    });
 
     let config = AppConfig::default();
     let target = ReviewTarget::File {
         path: "src/main.rs".to_string(),
+        with_context: false,
     };
     let options = make_review_options(&target);
 
-    let result =
-        gcop_rs::commands::review::run_internal(&options, &config, &mock_git, &mock_llm).await;
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
 
     assert!(result.is_ok());
 }
@@ -223,8 +530,14 @@ async fn test_review_empty_uncommitted_changes_error() {
     let target = ReviewTarget::Changes;
     let options = make_review_options(&target);
 
-    let result =
-        gcop_rs::commands::review::run_internal(&options, &config, &mock_git, &mock_llm).await;
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
 
     assert!(result.is_err());
     match result.unwrap_err() {
@@ -249,8 +562,14 @@ async fn test_review_llm_failure() {
     let target = ReviewTarget::Changes;
     let options = make_review_options(&target);
 
-    let result =
-        gcop_rs::commands::review::run_internal(&options, &config, &mock_git, &mock_llm).await;
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
 
     assert!(result.is_err());
     match result.unwrap_err() {
@@ -260,3 +579,171 @@ async fn test_review_llm_failure() {
         _ => panic!("Expected LlmApi error"),
     }
 }
+
+// ========== 审查基线测试 ==========
+
+#[tokio::test]
+async fn test_review_update_baseline_writes_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut mock_git = MockGitOperations::new();
+    mock_git
+        .expect_get_uncommitted_diff()
+        .times(1)
+        .returning(|| Ok("diff --git a/test.rs\n+line".to_string()));
+    expect_workdir(&mut mock_git, dir.path());
+
+    let mock_llm = MockReviewLLM::new(ReviewType::UncommittedChanges);
+
+    let config = AppConfig::default();
+    let target = ReviewTarget::Changes;
+    let mut options = make_review_options(&target);
+    options.update_baseline = true;
+
+    gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await
+    .unwrap();
+
+    let content =
+        std::fs::read_to_string(dir.path().join(".gcop").join("review-baseline.json")).unwrap();
+    let baseline: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(baseline["version"], 1);
+    assert_eq!(baseline["issues"][0]["file"], "test.rs");
+    assert_eq!(baseline["issues"][0]["description"], "Test issue");
+}
+
+#[tokio::test]
+async fn test_review_invalid_baseline_error() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join(".gcop")).unwrap();
+    std::fs::write(dir.path().join(".gcop").join("review-baseline.json"), "{").unwrap();
+
+    let mut mock_git = MockGitOperations::new();
+    mock_git
+        .expect_get_uncommitted_diff()
+        .times(1)
+        .returning(|| Ok("diff --git a/test.rs\n+line".to_string()));
+    expect_workdir(&mut mock_git, dir.path());
+
+    let mock_llm = MockReviewLLM::new(ReviewType::UncommittedChanges);
+
+    let config = AppConfig::default();
+    let target = ReviewTarget::Changes;
+    let options = make_review_options(&target);
+
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+
+    assert!(matches!(result, Err(GcopError::Config(_))));
+}
+
+// ========== --fail-on 测试 ==========
+
+#[tokio::test]
+async fn test_review_fail_on_gate() {
+    for (threshold, expect_failure) in [
+        (IssueSeverity::Critical, false),
+        (IssueSeverity::Warning, true),
+        (IssueSeverity::Info, true),
+    ] {
+        let dir = tempfile::tempdir().unwrap();
+        let mut mock_git = MockGitOperations::new();
+        mock_git
+            .expect_get_uncommitted_diff()
+            .times(1)
+            .returning(|| Ok("diff --git a/test.rs\n+line".to_string()));
+        expect_workdir(&mut mock_git, dir.path());
+
+        let mock_llm = MockReviewLLM::new(ReviewType::UncommittedChanges);
+
+        let config = AppConfig::default();
+        let target = ReviewTarget::Changes;
+        let mut options = make_review_options(&target);
+        options.fail_on = Some(threshold);
+
+        let result = gcop_rs::commands::review::run_internal(
+            &options,
+            &config,
+            &mock_git,
+            &mock_llm,
+            &SilentReporter,
+        )
+        .await;
+
+        if expect_failure {
+            // The mock reports a single warning
+            let err = result.unwrap_err();
+            assert!(matches!(
+                err,
+                GcopError::ReviewGateFailed {
+                    highest: IssueSeverity::Warning,
+                    count: 1,
+                    ..
+                }
+            ));
+            assert_eq!(err.exit_code(), 11);
+        } else {
+            assert!(result.is_ok());
+        }
+    }
+}
+
+// ========== --apply 测试 ==========
+
+#[tokio::test]
+async fn test_review_apply_rejects_non_changes_target() {
+    let mock_git = MockGitOperations::new();
+    let mock_llm = MockReviewLLM::new(ReviewType::SingleCommit("abc123".to_string()));
+
+    let config = AppConfig::default();
+    let target = ReviewTarget::Commit {
+        hash: "abc123".to_string(),
+    };
+    let mut options = make_review_options(&target);
+    options.apply = true;
+
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+
+    assert!(matches!(result, Err(GcopError::InvalidInput(_))));
+}
+
+#[tokio::test]
+async fn test_review_apply_rejects_json_output() {
+    let mock_git = MockGitOperations::new();
+    let mock_llm = MockReviewLLM::new(ReviewType::UncommittedChanges);
+
+    let config = AppConfig::default();
+    let target = ReviewTarget::Changes;
+    let mut options = make_review_options(&target);
+    options.format = OutputFormat::Json;
+    options.apply = true;
+
+    let result = gcop_rs::commands::review::run_internal(
+        &options,
+        &config,
+        &mock_git,
+        &mock_llm,
+        &SilentReporter,
+    )
+    .await;
+
+    assert!(matches!(result, Err(GcopError::InvalidInput(_))));
+}
@@ -5,9 +5,13 @@
 /// - 作者过滤
 /// - 跳过 merge commits
 /// - 百分比计算
-use gcop_rs::commands::stats::compute_contrib_stats;
+/// - 多仓库合并与作者别名
+/// - .mailmap 身份合并
+use std::collections::BTreeMap;
+
+use gcop_rs::commands::stats::{AuthorAliases, compute_contrib_stats, merge_contrib_stats};
 use gcop_rs::error::Result;
-use gcop_rs::git::{GitOperations, repository::GitRepository};
+use gcop_rs::git::{GitOperations, HistoryOptions, repository::GitRepository};
 use serial_test::serial;
 use std::env;
 use std::fs;
@@ -67,8 +71,8 @@ fn test_compute_contrib_stats_basic() -> Result<()> {
     env::set_current_dir(repo_path)?;
 
     let git_repo = GitRepository::open(None)?;
-    let commits = git_repo.get_commit_history()?;
-    let contrib = compute_contrib_stats(&commits, &git_repo, None)?;
+    let commits = git_repo.get_commit_history(&HistoryOptions::default())?;
+    let contrib = compute_contrib_stats(&commits, &git_repo, None, &HistoryOptions::default())?;
 
     assert_eq!(contrib.total_insertions, 5);
     assert_eq!(contrib.total_deletions, 0);
@@ -106,8 +110,8 @@ fn test_compute_contrib_stats_multiple_commits() -> Result<()> {
     env::set_current_dir(repo_path)?;
 
     let git_repo = GitRepository::open(None)?;
-    let commits = git_repo.get_commit_history()?;
-    let contrib = compute_contrib_stats(&commits, &git_repo, None)?;
+    let commits = git_repo.get_commit_history(&HistoryOptions::default())?;
+    let contrib = compute_contrib_stats(&commits, &git_repo, None, &HistoryOptions::default())?;
 
     // 总计：3 + 3 = 6 insertions, 2 deletions
     // （第二个 commit 的统计是 3 insertions, 2 deletions）
@@ -163,8 +167,8 @@ fn test_compute_contrib_stats_skip_merge_commits() -> Result<()> {
     env::set_current_dir(repo_path)?;
 
     let git_repo = GitRepository::open(None)?;
-    let commits = git_repo.get_commit_history()?;
-    let contrib = compute_contrib_stats(&commits, &git_repo, None)?;
+    let commits = git_repo.get_commit_history(&HistoryOptions::default())?;
+    let contrib = compute_contrib_stats(&commits, &git_repo, None, &HistoryOptions::default())?;
 
     // Merge commit 应该被跳过
     assert_eq!(contrib.merge_commits_skipped, 1);
@@ -184,8 +188,8 @@ fn test_compute_contrib_stats_empty_repo() -> Result<()> {
     env::set_current_dir(repo_path)?;
 
     let git_repo = GitRepository::open(None)?;
-    let commits = git_repo.get_commit_history()?;
-    let contrib = compute_contrib_stats(&commits, &git_repo, None)?;
+    let commits = git_repo.get_commit_history(&HistoryOptions::default())?;
+    let contrib = compute_contrib_stats(&commits, &git_repo, None, &HistoryOptions::default())?;
 
     assert_eq!(contrib.total_insertions, 0);
     assert_eq!(contrib.total_deletions, 0);
@@ -195,3 +199,112 @@ fn test_compute_contrib_stats_empty_repo() -> Result<()> {
     env::set_current_dir(original_dir)?;
     Ok(())
 }
+
+// ========== 多仓库合并测试 ==========
+
+fn commit_as(
+    repo_path: &Path,
+    name: &str,
+    email: &str,
+    filename: &str,
+    content: &str,
+) -> Result<()> {
+    let repo = git2::Repository::open(repo_path)?;
+    create_test_file(repo_path, filename, content)?;
+    add_file_to_index(&repo, filename)?;
+    let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+    let sig = git2::Signature::now(name, email)?;
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, "change", &tree, &parents)?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_merge_contrib_stats_across_repos_with_aliases() -> Result<()> {
+    let api_dir = TempDir::new()?;
+    let web_dir = TempDir::new()?;
+    init_git_repo(api_dir.path())?;
+    init_git_repo(web_dir.path())?;
+    commit_as(
+        api_dir.path(),
+        "Jane Doe",
+        "jane@example.com",
+        "a.txt",
+        "a\nb\nc\n",
+    )?;
+    commit_as(
+        web_dir.path(),
+        "jdoe",
+        "jane@old.example.com",
+        "a.txt",
+        "a\nb\n",
+    )?;
+    commit_as(
+        web_dir.path(),
+        "Bob",
+        "bob@example.com",
+        "b.txt",
+        "a\nb\nc\nd\ne\n",
+    )?;
+
+    let mut parts = Vec::new();
+    for dir in [&api_dir, &web_dir] {
+        let repo = GitRepository::open_at(dir.path(), None)?;
+        let commits = repo.get_commit_history(&HistoryOptions::default())?;
+        parts.push(compute_contrib_stats(
+            &commits,
+            &repo,
+            None,
+            &HistoryOptions::default(),
+        )?);
+    }
+
+    let mut aliases = BTreeMap::new();
+    aliases.insert(
+        "jane@old.example.com".to_string(),
+        "Jane Doe <jane@example.com>".to_string(),
+    );
+    let contrib = merge_contrib_stats(parts.clone(), &AuthorAliases::new(&aliases), None);
+
+    assert_eq!(contrib.authors.len(), 2);
+    let jane = contrib
+        .authors
+        .iter()
+        .find(|a| a.email == "jane@example.com")
+        .expect("aliased author merged");
+    assert_eq!(jane.name, "Jane Doe");
+    assert_eq!(jane.insertions, 5);
+    assert_eq!(contrib.total_insertions, 10);
+
+    // 作者过滤在合并别名之后生效
+    let filtered = merge_contrib_stats(parts, &AuthorAliases::new(&aliases), Some("jdoe"));
+    assert!(filtered.authors.is_empty());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_apply_mailmap_merges_identities() -> Result<()> {
+    let dir = TempDir::new()?;
+    init_git_repo(dir.path())?;
+    commit_as(dir.path(), "Jane Doe", "jane@example.com", "a.txt", "a\n")?;
+    commit_as(dir.path(), "jdoe", "jd@laptop.local", "b.txt", "b\n")?;
+    create_test_file(
+        dir.path(),
+        ".mailmap",
+        "Jane Doe <jane@example.com> <jd@laptop.local>\n",
+    )?;
+
+    let repo = GitRepository::open_at(dir.path(), None)?;
+    let mut commits = repo.get_commit_history(&HistoryOptions::default())?;
+    repo.apply_mailmap(&mut commits)?;
+
+    assert!(
+        commits
+            .iter()
+            .all(|c| c.author_name == "Jane Doe" && c.author_email == "jane@example.com")
+    );
+    Ok(())
+}
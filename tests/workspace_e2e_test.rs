@@ -46,14 +46,14 @@ members = ["crates/*", "apps/cli"]
         "crates/core/src/lib.rs".into(),
         "crates/core/Cargo.toml".into(),
     ];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert_eq!(scope.suggested_scope, Some("core".into()));
     assert_eq!(scope.packages.len(), 1);
     assert!(scope.root_files.is_empty());
 
     // 精确路径 member (apps/cli)
     let files2 = vec!["apps/cli/src/main.rs".into()];
-    let scope2 = workspace::scope::infer_scope(&files2, &info, None);
+    let scope2 = workspace::scope::infer_scope(&files2, &info, None, &Default::default());
     assert_eq!(scope2.suggested_scope, Some("cli".into()));
 }
 
@@ -107,7 +107,7 @@ fn test_e2e_pnpm_workspace() {
         "packages/ui/src/button.tsx".into(),
         "apps/web/index.ts".into(),
     ];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert!(scope.suggested_scope.is_some());
     assert_eq!(scope.packages.len(), 2);
 }
@@ -130,7 +130,7 @@ fn test_e2e_npm_workspace_array() {
     assert!(info.workspace_types.iter().any(|t| format!("{t}") == "npm"));
 
     let files = vec!["packages/utils/index.ts".into()];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert_eq!(scope.suggested_scope, Some("utils".into()));
 }
 
@@ -157,7 +157,7 @@ fn test_e2e_npm_workspace_yarn_style() {
         "packages/core/src/index.ts".into(),
         "libs/shared/util.ts".into(),
     ];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert!(scope.suggested_scope.is_some());
     assert_eq!(scope.packages.len(), 2);
 }
@@ -182,7 +182,7 @@ fn test_e2e_npm_with_nx() {
     assert!(info.workspace_types.iter().any(|t| format!("{t}") == "nx"));
 
     let files = vec!["packages/feature-a/src/lib.ts".into()];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert_eq!(scope.suggested_scope, Some("feature-a".into()));
 }
 
@@ -215,7 +215,7 @@ fn test_e2e_npm_with_turbo() {
         "packages/utils/index.ts".into(),
         "apps/web/app.tsx".into(),
     ];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert!(scope.suggested_scope.is_some());
     assert_eq!(scope.packages.len(), 3);
 }
@@ -248,7 +248,7 @@ fn test_e2e_lerna_workspace() {
         "packages/b/index.js".into(),
         "modules/c/index.js".into(),
     ];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert!(scope.suggested_scope.is_some());
     assert_eq!(scope.packages.len(), 3);
 }
@@ -280,12 +280,12 @@ members = ["crates/*"]
 
     // cargo 子包
     let files1 = vec!["crates/parser/src/lib.rs".into()];
-    let scope1 = workspace::scope::infer_scope(&files1, &info, None);
+    let scope1 = workspace::scope::infer_scope(&files1, &info, None, &Default::default());
     assert_eq!(scope1.suggested_scope, Some("parser".into()));
 
     // pnpm 子包
     let files2 = vec!["packages/ui/button.tsx".into()];
-    let scope2 = workspace::scope::infer_scope(&files2, &info, None);
+    let scope2 = workspace::scope::infer_scope(&files2, &info, None, &Default::default());
     assert_eq!(scope2.suggested_scope, Some("ui".into()));
 
     // 跨 workspace 类型
@@ -293,7 +293,7 @@ members = ["crates/*"]
         "crates/parser/src/lib.rs".into(),
         "packages/ui/button.tsx".into(),
     ];
-    let scope3 = workspace::scope::infer_scope(&files3, &info, None);
+    let scope3 = workspace::scope::infer_scope(&files3, &info, None, &Default::default());
     assert!(scope3.suggested_scope.is_some());
     assert_eq!(scope3.packages.len(), 2);
 }
@@ -331,7 +331,7 @@ members = ["crates/*"]
 
     // 只有 root 文件
     let files = vec!["README.md".into(), "Cargo.toml".into()];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert!(scope.suggested_scope.is_none());
     assert!(scope.packages.is_empty());
     assert_eq!(scope.root_files.len(), 2);
@@ -358,7 +358,7 @@ fn test_e2e_four_plus_packages_no_scope() {
         "packages/c/index.ts".into(),
         "packages/d/index.ts".into(),
     ];
-    let scope = workspace::scope::infer_scope(&files, &info, None);
+    let scope = workspace::scope::infer_scope(&files, &info, None, &Default::default());
     assert!(scope.suggested_scope.is_none());
     assert_eq!(scope.packages.len(), 4);
 }
@@ -381,7 +381,8 @@ members = ["crates/*"]
     let info = workspace::detect_workspace(root).expect("should detect cargo workspace");
 
     let files = vec!["crates/core/src/lib.rs".into()];
-    let scope = workspace::scope::infer_scope(&files, &info, Some("my-custom-scope"));
+    let scope =
+        workspace::scope::infer_scope(&files, &info, Some("my-custom-scope"), &Default::default());
     assert_eq!(scope.suggested_scope, Some("my-custom-scope".into()));
 }
 